@@ -0,0 +1,123 @@
+//! Bust and eviction throughput of `DimensionalCacheManager`
+//!
+//! `cargo bench --bench cache`. The storm benchmarks bust from several
+//! threads at once, as the PID and file watchers do during a burst of file
+//! changes, which is where contention on shared cache state shows.
+
+use std::sync::Arc;
+
+use bustcall_core::dimensional_cache::{CacheBustSeverity, DimensionalCacheManager, EvictionStrategy, ModelBinding};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+const TARGETS: usize = 64;
+const ENTRIES_PER_TARGET: usize = 64;
+const BUSTS_PER_THREAD: usize = 256;
+
+fn target(i: usize) -> String {
+    format!("target-{}", i % TARGETS)
+}
+
+/// Manager with `TARGETS` bound targets of `ENTRIES_PER_TARGET` entries each
+fn populated() -> DimensionalCacheManager {
+    let cache = DimensionalCacheManager::new().unwrap();
+    for i in 0..TARGETS {
+        cache
+            .bind_model(
+                &target(i),
+                ModelBinding {
+                    runtime: "bench".to_string(),
+                    pid: None,
+                    pid_start_time: None,
+                    path: String::new(),
+                    last_modified: 0,
+                    cache_dependencies: Vec::new(),
+                    compiler_caches: Vec::new(),
+                    hooks: Default::default(),
+                },
+            )
+            .unwrap();
+        for entry in 0..ENTRIES_PER_TARGET {
+            cache.register_entry(&format!("{}/{}", target(i), entry), &target(i), 1024, EvictionStrategy::LRU);
+        }
+    }
+    cache
+}
+
+fn severity(i: usize) -> CacheBustSeverity {
+    match i % 4 {
+        0 => CacheBustSeverity::Low,
+        1 => CacheBustSeverity::Medium,
+        2 => CacheBustSeverity::High,
+        _ => CacheBustSeverity::Critical,
+    }
+}
+
+fn bust(c: &mut Criterion) {
+    let cache = populated();
+    let mut i = 0;
+    c.bench_function("bust/single", |b| {
+        b.iter(|| {
+            i += 1;
+            cache.bust_cache(black_box(&target(i)), severity(i)).unwrap();
+        })
+    });
+}
+
+fn bust_storm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bust/storm");
+    for threads in [1, 4, 8] {
+        group.throughput(Throughput::Elements((threads * BUSTS_PER_THREAD) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(threads), &threads, |b, &threads| {
+            b.iter_batched(
+                || Arc::new(populated()),
+                |cache| {
+                    std::thread::scope(|scope| {
+                        for thread in 0..threads {
+                            let cache = &cache;
+                            scope.spawn(move || {
+                                for i in 0..BUSTS_PER_THREAD {
+                                    let n = thread * BUSTS_PER_THREAD + i;
+                                    cache.bust_cache(&target(n), severity(n)).unwrap();
+                                }
+                            });
+                        }
+                    });
+                    // Drain as the rebuild executor would
+                    while cache.next_rebuild().is_some() {}
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn evict(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evict");
+    for (name, strategy) in [
+        ("lru", EvictionStrategy::LRU),
+        ("lfu", EvictionStrategy::LFU),
+        ("model_aware", EvictionStrategy::ModelAware(Default::default())),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter_batched(populated, |cache| cache.cache_evict(black_box(&strategy)).unwrap(), BatchSize::LargeInput)
+        });
+    }
+    group.bench_function("enforce_limits", |b| {
+        b.iter_batched(
+            || {
+                let cache = populated();
+                let mut limits = cache.limits();
+                limits.max_entries = Some(TARGETS * ENTRIES_PER_TARGET / 2);
+                cache.set_limits(limits);
+                cache
+            },
+            |cache| cache.enforce_limits(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bust, bust_storm, evict);
+criterion_main!(benches);
@@ -138,7 +138,7 @@ async fn run_delegate_node(args: &[String]) -> Result<()> {
     info!("🔗 Delegate node {} starting (parent: {})", node_id, parent_lpid);
     
     // Initialize as delegate worker
-    let cache_manager = DimensionalCacheManager::new()?;
+    let cache_manager = Arc::new(DimensionalCacheManager::new()?);
     
     // Delegate worker loop
     let mut heartbeat_interval = interval(Duration::from_secs(3));
@@ -266,7 +266,7 @@ async fn cleanup_delegates(handles: &[Child]) -> Result<()> {
 }
 
 async fn delegate_cache_work(
-    cache_manager: &DimensionalCacheManager, 
+    cache_manager: &Arc<DimensionalCacheManager>, 
     node_id: &str
 ) -> Result<()> {
     // Simulate delegated cache work
@@ -274,11 +274,11 @@ async fn delegate_cache_work(
     
     // Example: Perform cache invalidation based on node specialty
     match node_id {
-        "0" => cache_manager.bust_cache("node-target", CacheBustSeverity::Low)?,
-        "1" => cache_manager.bust_cache("python-target", CacheBustSeverity::Medium)?,
-        "2" => cache_manager.bust_cache("c-target", CacheBustSeverity::High)?,
-        _ => cache_manager.bust_cache("generic-target", CacheBustSeverity::Low)?,
-    }
+        "0" => cache_manager.bust_cache_async("node-target", CacheBustSeverity::Low, "delegate maintenance").await?,
+        "1" => cache_manager.bust_cache_async("python-target", CacheBustSeverity::Medium, "delegate maintenance").await?,
+        "2" => cache_manager.bust_cache_async("c-target", CacheBustSeverity::High, "delegate maintenance").await?,
+        _ => cache_manager.bust_cache_async("generic-target", CacheBustSeverity::Low, "delegate maintenance").await?,
+    };
     
     Ok(())
 }
@@ -0,0 +1,21 @@
+// src/bin/bustcall-server.rs
+//! OBINexus Bustcall API server, for the CLI's daemon-backed commands
+
+use bustcall_core::core::config::{BustcallConfig, DEFAULT_CONFIG_PATH};
+use bustcall_core::servers::BustcallServer;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    let config = if std::path::Path::new(DEFAULT_CONFIG_PATH).exists() {
+        BustcallConfig::load_from_file(DEFAULT_CONFIG_PATH)?
+    } else {
+        BustcallConfig::default()
+    };
+    // The watcher runs for as long as it is held
+    let (mut server, _watcher) = BustcallServer::from_config(&config).await?;
+    server.start().await?;
+
+    Ok(())
+}
@@ -6,6 +6,8 @@
 
 use bustcall::dimensional_cache::{DimensionalCacheManager, CacheBustSeverity, CacheState};
 use bustcall::pid_watcher::{BustCallDaemon, ModelBinding};
+use bustcall::core::daemon::DaemonConfig;
+use bustcall::core::preflight::{run_preflight, PreflightConfig};
 
 use std::collections::{HashMap, BTreeMap, VecDeque};
 use std::process::{Command, Child, Stdio};
@@ -252,9 +254,12 @@ impl FaultTorrentStaging {
         let binding = ModelBinding {
             runtime: "bustcall-daemon".to_string(),
             pid: root_node.unix_pid,
+            pid_start_time: None,
             path: std::env::current_exe()?.to_string_lossy().to_string(),
             last_modified: 0,
             cache_dependencies: Vec::new(),
+            compiler_caches: Vec::new(),
+            hooks: Default::default(),
         };
         
         self.cache_manager.bind_model("fault-torrent-root", binding)?;
@@ -283,7 +288,7 @@ impl FaultTorrentStaging {
             .map_err(|e| anyhow!("Failed to queue task: {}", e))?;
         
         // Trigger cache awareness
-        self.cache_manager.bust_cache(&task.target_node, CacheBustSeverity::Medium)?;
+        self.cache_manager.bust_cache_async(&task.target_node, CacheBustSeverity::Medium, "task delegated").await?;
         
         Ok(task.task_id)
     }
@@ -505,7 +510,7 @@ impl FaultTorrentStaging {
         }
         
         // Trigger cache bust for cleanup
-        self.cache_manager.bust_cache(&node_id, CacheBustSeverity::High)?;
+        self.cache_manager.bust_cache_async(&node_id, CacheBustSeverity::High, "task terminated").await?;
         
         Ok(())
     }
@@ -619,6 +624,10 @@ async fn main() -> Result<()> {
     
     info!("🚀 Starting OBINexus FaultTorrent Staging Daemon");
     
+    // Report every environment problem up front instead of failing mid-run
+    run_preflight(&PreflightConfig::from_daemon_config(&DaemonConfig::load_default()?))
+        .into_result()?;
+    
     let config = FaultTorrentConfig::default();
     let staging = FaultTorrentStaging::new(config).await?;
     
@@ -10,6 +10,20 @@ use bustcall_core::{CacheManager, HealthMonitor, ProcessWatcher};
 #[command(name = "bustcall")]
 #[command(about = "OBINexus cache invalidation and system orchestration")]
 struct Cli {
+    /// Config file; missing means defaults
+    #[arg(long, global = true, default_value = bustcall_core::core::config::DEFAULT_CONFIG_PATH)]
+    config: String,
+    /// Override a config key, e.g. --set global.daemon_interval_seconds=10.
+    /// Takes precedence over the file and BUSTCALL_* variables.
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+    /// Merge the config file's [profile.<NAME>] over it, e.g. ci or dev
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+    /// Only accept a remote --config signed by a key in this ssh-keygen
+    /// allowed signers file
+    #[arg(long, global = true, value_name = "FILE")]
+    config_signers: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -17,7 +31,12 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Start daemon mode for continuous monitoring
-    Daemon,
+    Daemon {
+        /// Run the full watcher pipeline but only report the busts and
+        /// evictions that would have happened
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Bind runtime targets for cache management
     Bind {
         #[arg(long)]
@@ -34,20 +53,1601 @@ enum Commands {
         #[arg(long)]
         severity: String,
     },
+    /// Evict cache entries on the daemon by strategy
+    Evict {
+        /// lru, mru, lfu, fifo or model-aware
+        #[arg(long, default_value = "model-aware")]
+        strategy: String,
+        #[command(flatten)]
+        weights: WeightArgs,
+        /// Only plan the eviction
+        #[arg(long)]
+        dry_run: bool,
+        /// Print each entry's eviction score by component
+        #[arg(long)]
+        explain: bool,
+        /// Daemon API to use
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
     /// Display system status and health metrics
-    Status,
+    Status {
+        /// Daemon API to query
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+        /// Also show each target's file watcher: backend, watched paths,
+        /// last event and bust, pids and health
+        #[arg(long)]
+        verbose: bool,
+    },
+    /// List running processes
+    List {
+        #[command(flatten)]
+        filter: ProcessFilterArgs,
+    },
+    /// Sample CPU and memory of matching processes
+    Monitor {
+        /// Shorthand for --pid
+        #[arg(value_name = "PID")]
+        process: Option<u32>,
+        #[command(flatten)]
+        filter: ProcessFilterArgs,
+        /// Also show open files and listening sockets
+        #[arg(long)]
+        verbose: bool,
+        /// Keep sampling until interrupted
+        #[arg(long)]
+        continuous: bool,
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+        /// Window for the trend shown in continuous mode, e.g. 90s, 5m
+        #[arg(long, default_value = "5m")]
+        window: String,
+    },
+    /// Live view of monitored targets, their processes and recent busts
+    Top {
+        /// Daemon API to poll
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+        /// Number of recent bust events to show
+        #[arg(long, default_value_t = 10)]
+        busts: usize,
+    },
     /// Test warning protocols
     TestWarn,
+    /// Acknowledge a notification so it is not escalated further
+    Ack {
+        id: u64,
+        #[arg(long, default_value = bustcall_core::core::notify::escalation::DEFAULT_ACK_PATH)]
+        ack_file: String,
+    },
+    /// Inspect notification history
+    Notifications {
+        #[command(subcommand)]
+        command: NotificationCommands,
+    },
+    /// Control outbound notifications of a running daemon
+    Notify {
+        #[command(subcommand)]
+        command: NotifyCommands,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Save or replace the dimensional cache state of a running daemon
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+    /// Inspect the file watcher's decisions
+    #[command(args_conflicts_with_subcommands = true)]
+    Watch {
+        #[command(subcommand)]
+        command: Option<WatchCommands>,
+        #[command(flatten)]
+        follow: WatchArgs,
+    },
+    /// Inspect and close the circuits stopping automated recovery
+    Heal {
+        #[command(subcommand)]
+        command: HealCommands,
+    },
+}
+
+/// Follow the file watcher of targets in the foreground
+#[derive(clap::Args)]
+struct WatchArgs {
+    /// Targets to follow, templated ones by their template's name
+    #[arg(value_name = "TARGET")]
+    targets: Vec<String>,
+    /// Follow every target
+    #[arg(long, conflicts_with = "targets")]
+    all: bool,
+    /// Print each event and bust as a JSON line
+    #[arg(long)]
+    json: bool,
+    /// Watch here rather than through the daemon, even if one is running
+    #[arg(long)]
+    standalone: bool,
+    /// With --standalone, only report the busts that would have happened
+    #[arg(long)]
+    dry_run: bool,
+    /// Daemon API to follow
+    #[arg(long, default_value = "http://127.0.0.1:8989")]
+    url: String,
+}
+
+/// Model-aware eviction weights, the defaults when unset
+#[derive(clap::Args)]
+struct WeightArgs {
+    #[arg(long)]
+    language_priority: Option<f32>,
+    #[arg(long)]
+    dependency_impact: Option<f32>,
+    #[arg(long)]
+    build_cost: Option<f32>,
+    /// Score entries as on the critical path
+    #[arg(long)]
+    critical_path: bool,
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Write the daemon's cache entries, dimensions, bindings and rebuild
+    /// queue as JSON, e.g. to attach to a bug report
+    Export {
+        /// File to write; stdout when omitted
+        #[arg(long)]
+        out: Option<String>,
+        /// Daemon API to query
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+    /// Replace the daemon's cache state with an exported snapshot
+    Import {
+        file: String,
+        /// Daemon API to update
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+    /// Show the targets taking the most cache space and their largest
+    /// entries
+    Du {
+        /// Largest entries listed per target
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+        /// Daemon API to query
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+    /// Drop a namespace's targets, entries and queued rebuilds, e.g. once
+    /// its branch is deleted
+    DropNamespace {
+        /// Namespace as bound, e.g. `project:branch`
+        namespace: String,
+        /// Daemon API to update
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+    /// Replay a target's transitions, busts and evictions from the audit
+    /// log, and say why it was invalidated
+    History {
+        target: String,
+        /// Only events this recent, e.g. `30m`, `1h` or `2d`
+        #[arg(long)]
+        since: Option<String>,
+        /// Reconstruct the target as it was at this time: `HH:MM[:SS]`
+        /// today, or an RFC 3339 timestamp
+        #[arg(long)]
+        at: Option<String>,
+        #[arg(long, default_value = bustcall_core::core::audit::DEFAULT_AUDIT_PATH)]
+        audit_file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum WatchCommands {
+    /// Assess journaled file events again against the current config's
+    /// targets, ignore rules and severity overrides
+    Replay {
+        /// Only events this recent, e.g. `30m`, or since this time:
+        /// `HH:MM[:SS]` today or an RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Only events of this target, then or now
+        #[arg(long)]
+        target: Option<String>,
+        /// Only events assessed differently now
+        #[arg(long)]
+        changed: bool,
+        /// Journal to read; defaults to [monitoring] event_journal
+        #[arg(long)]
+        journal: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the configuration as TOML
+    Show {
+        /// Apply BUSTCALL_* variables and --set, and list where each key's
+        /// value came from
+        #[arg(long)]
+        resolved: bool,
+    },
+    /// Check the config file and report every problem found
+    Validate,
+    /// Upgrade the config file to the current config_version, keeping a
+    /// backup of the original
+    Migrate {
+        /// Only print what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show which targets and keys differ between two configs. Each side is
+    /// a file or <revision>:<path> from git; NEW defaults to --config.
+    Diff {
+        /// Config to compare against; omit with --daemon
+        #[arg(required_unless_present = "daemon")]
+        old: Option<String>,
+        new: Option<String>,
+        /// Compare the config the daemon at this API is running with
+        /// against the file
+        #[arg(long, value_name = "URL", num_args = 0..=1, default_missing_value = "http://127.0.0.1:8989")]
+        daemon: Option<String>,
+    },
+    /// Propose [target.*] entries for the packages in a workspace
+    Discover {
+        /// Workspace to scan
+        #[arg(long, default_value = ".")]
+        root: String,
+        #[arg(long, default_value_t = bustcall_core::core::discover::DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+        /// Append the entries to the config file instead of printing them
+        #[arg(long)]
+        write: bool,
+    },
+}
+
+/// Process selection shared by `list` and `monitor`; criteria combine
+#[derive(clap::Args)]
+struct ProcessFilterArgs {
+    #[arg(long)]
+    pid: Option<u32>,
+    /// Substring of the process name
+    #[arg(long)]
+    name: Option<String>,
+    /// Regular expression matched against the process name
+    #[arg(long)]
+    regex: Option<String>,
+    /// Minimum CPU usage in percent
+    #[arg(long)]
+    min_cpu: Option<f64>,
+    /// Minimum resident memory in MiB
+    #[arg(long)]
+    min_memory_mb: Option<u64>,
+    /// Only this process and its descendants
+    #[arg(long)]
+    tree_root: Option<u32>,
+    /// Only processes inside this container (name or ID prefix)
+    #[arg(long)]
+    container: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum HealCommands {
+    /// List components whose recovery failed lately and whether their
+    /// circuit is open
+    Status {
+        /// Daemon API to query
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+    /// Close a component's circuit so failures are recovered again
+    Reset {
+        component: String,
+        /// Daemon API to update
+        #[arg(long, default_value = "http://127.0.0.1:8989")]
+        url: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotifyCommands {
+    /// Suppress notifications for a while; they are still kept in history
+    Mute {
+        /// How long to mute, e.g. 30m, 2h, 1d
+        #[arg(long = "for")]
+        duration: String,
+        /// Only mute notifications for this target
+        #[arg(long)]
+        target: Option<String>,
+        #[arg(long, default_value = bustcall_core::core::notify::mute::DEFAULT_MUTE_PATH)]
+        mute_file: String,
+    },
+    /// Lift mutes for a target, or all mutes
+    Unmute {
+        #[arg(long)]
+        target: Option<String>,
+        #[arg(long, default_value = bustcall_core::core::notify::mute::DEFAULT_MUTE_PATH)]
+        mute_file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotificationCommands {
+    /// List recorded notifications, newest first
+    List {
+        /// Only show notifications newer than this, e.g. 30m, 1h, 2d
+        #[arg(long)]
+        since: Option<String>,
+        /// Minimum level: info, warning, error, critical
+        #[arg(long)]
+        level: Option<String>,
+        #[arg(long)]
+        target: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        #[arg(long, default_value = bustcall_core::core::notify::history::DEFAULT_HISTORY_PATH)]
+        history_file: String,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Daemon => daemon_mode(),
+        Commands::Daemon { dry_run } => daemon_mode(dry_run),
         Commands::Bind { target, path, runtime } => bind_target(target, path, runtime),
         Commands::Bust { target, severity } => execute_bust(target, severity),
-        Commands::Status => display_status(),
+        Commands::Evict { strategy, weights, dry_run, explain, url } => {
+            evict(&url, &eviction_strategy(&strategy, weights)?, dry_run, explain)
+        }
+        Commands::Status { url, verbose } => display_status(&url, verbose),
+        Commands::List { filter } => list_processes(&process_filter(filter)?),
+        Commands::Monitor { process, mut filter, verbose, continuous, interval, window } => {
+            filter.pid = filter.pid.or(process);
+            monitor_processes(&process_filter(filter)?, verbose, continuous, interval, &window)
+        }
+        Commands::Top { url, interval, busts } => top(&url, interval, busts),
         Commands::TestWarn => test_warning_protocols(),
+        Commands::Ack { id, ack_file } => {
+            bustcall_core::core::notify::escalation::write_ack(&ack_file, id)?;
+            println!("Acknowledged notification #{}", id);
+            Ok(())
+        }
+        Commands::Notifications { command } => match command {
+            NotificationCommands::List { since, level, target, limit, history_file } => {
+                list_notifications(since, level, target, limit, history_file)
+            }
+        },
+        Commands::Config { command } => {
+            let remote = remote_config(&cli.config, cli.config_signers.as_deref())?;
+            let path = match &remote {
+                Some(remote) => remote.cached_path().to_string_lossy().into_owned(),
+                None => cli.config.clone(),
+            };
+            match command {
+                ConfigCommands::Show { resolved } => {
+                    show_config(&path, remote.as_ref(), cli.profile.as_deref(), &cli.overrides, resolved)
+                }
+                ConfigCommands::Validate => validate_config(&path, remote.as_ref()),
+                ConfigCommands::Migrate { .. } | ConfigCommands::Discover { write: true, .. } if remote.is_some() => {
+                    Err(format!("{} is remote; change it at its source", cli.config).into())
+                }
+                ConfigCommands::Migrate { dry_run } => migrate_config(&path, dry_run),
+                ConfigCommands::Diff { old, new, daemon } => diff_configs(&cli.config, old, new, daemon),
+                ConfigCommands::Discover { root, max_depth, write } => discover_targets(&path, &root, max_depth, write),
+            }
+        }
+        Commands::Cache { command } => match command {
+            CacheCommands::Export { out, url } => export_cache(&url, out.as_deref()),
+            CacheCommands::Import { file, url } => import_cache(&url, &file),
+            CacheCommands::Du { top, url } => cache_usage(&url, top),
+            CacheCommands::DropNamespace { namespace, url } => drop_namespace(&url, &namespace),
+            CacheCommands::History { target, since, at, audit_file } => {
+                cache_history(&target, since, at, &audit_file)
+            }
+        },
+        // By reference, as `load_config` borrows all of `cli`
+        Commands::Watch { ref command, ref follow } => match command {
+            Some(WatchCommands::Replay { since, target, changed, journal }) => {
+                replay_events(&load_config(&cli)?, since.clone(), target.clone(), *changed, journal.clone())
+            }
+            None => watch_targets(&load_config(&cli)?, follow),
+        },
+        Commands::Heal { command } => match command {
+            HealCommands::Status { url } => display_circuits(&url),
+            HealCommands::Reset { component, url } => reset_circuit(&url, &component),
+        },
+        Commands::Notify { command } => match command {
+            NotifyCommands::Mute { duration, target, mute_file } => mute_notifications(duration, target, mute_file),
+            NotifyCommands::Unmute { target, mute_file } => {
+                let removed = bustcall_core::core::notify::mute::clear_mutes(&mute_file, target.as_deref())?;
+                println!("🔔 Removed {} mute(s)", removed);
+                Ok(())
+            }
+        },
+    }
+}
+
+/// The config `--config` and `--profile` name; missing means defaults
+fn load_config(cli: &Cli) -> Result<bustcall_core::BustcallConfig, Box<dyn std::error::Error>> {
+    let remote = remote_config(&cli.config, cli.config_signers.as_deref())?;
+    Ok(if let Some(remote) = &remote {
+        remote.load(cli.profile.as_deref())?
+    } else if std::path::Path::new(&cli.config).exists() {
+        bustcall_core::BustcallConfig::load_with_profile(&cli.config, cli.profile.as_deref())?
+    } else {
+        bustcall_core::BustcallConfig::default()
+    })
+}
+
+fn process_filter(args: ProcessFilterArgs) -> Result<bustcall_core::ProcessFilter, Box<dyn std::error::Error>> {
+    let mut filter = bustcall_core::ProcessFilter {
+        pid: args.pid,
+        name_pattern: args.name,
+        min_cpu_usage: args.min_cpu,
+        min_memory_usage: args.min_memory_mb.map(|mb| mb * 1024 * 1024),
+        tree_root: args.tree_root,
+        container: args.container,
+        ..Default::default()
+    };
+    if let Some(regex) = args.regex {
+        filter = filter.regex(&regex)?;
+    }
+    Ok(filter)
+}
+
+fn print_processes(processes: &[bustcall_core::ProcessInfo]) {
+    println!(
+        "{:>8} {:<24} {:>7} {:>12} {:<10} CONTAINER",
+        "PID", "NAME", "CPU%", "MEMORY", "STATUS"
+    );
+    for process in processes {
+        let container = process.container.as_ref().map_or("-".to_string(), |c| {
+            c.name.clone().unwrap_or_else(|| c.id[..12].to_string())
+        });
+        println!(
+            "{:>8} {:<24} {:>7.1} {:>9} KiB {:<10} {}",
+            process.pid,
+            process.name,
+            process.cpu_usage,
+            process.memory_usage / 1024,
+            process.status,
+            container
+        );
+    }
+}
+
+fn export_cache(url: &str, out: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/cache/snapshot", url.trim_end_matches('/'));
+    let snapshot: bustcall_core::dimensional_cache::CacheSnapshot = ureq::get(&endpoint)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+        .into_json()?;
+
+    match out {
+        Some(out) => {
+            snapshot.save(out)?;
+            eprintln!(
+                "📤 Exported {} entries and {} targets to {}",
+                snapshot.evicons.len(),
+                snapshot.bindings.len(),
+                out
+            );
+        }
+        None => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+    }
+    Ok(())
+}
+
+fn import_cache(url: &str, file: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let snapshot = bustcall_core::dimensional_cache::CacheSnapshot::load(file)?;
+    let endpoint = format!("{}/api/v1/cache/snapshot", url.trim_end_matches('/'));
+    match ureq::post(&endpoint)
+        .timeout(std::time::Duration::from_secs(30))
+        .send_json(&snapshot)
+    {
+        Ok(_) => {
+            println!("📥 Imported {} entries and {} targets from {}", snapshot.evicons.len(), snapshot.bindings.len(), file);
+            Ok(())
+        }
+        Err(ureq::Error::Status(_, response)) => {
+            let body: serde_json::Value = response.into_json().unwrap_or_default();
+            Err(format!("Daemon rejected {}: {}", file, body["message"].as_str().unwrap_or("unknown error")).into())
+        }
+        Err(e) => Err(format!("Daemon API at {} unavailable: {}", url, e).into()),
+    }
+}
+
+fn cache_usage(url: &str, top: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/cache/usage", url.trim_end_matches('/'));
+    let usage: Vec<bustcall_core::dimensional_cache::TargetUsage> = ureq::get(&endpoint)
+        .query("top", &top.to_string())
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+        .into_json()?;
+
+    if usage.is_empty() {
+        println!("No cache entries");
+        return Ok(());
+    }
+    for target in &usage {
+        println!("{:>10}  {} ({} entries)", human_bytes(target.bytes), target.target, target.entries);
+        for (cache_id, bytes) in &target.largest {
+            println!("{:>10}    {}", human_bytes(*bytes), cache_id);
+        }
+    }
+    println!("{:>10}  total", human_bytes(usage.iter().map(|target| target.bytes).sum()));
+    Ok(())
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn cache_history(
+    target: &str,
+    since: Option<String>,
+    at: Option<String>,
+    audit_file: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::audit::{replay, AuditEvent, AuditFilter, AuditLog};
+    use chrono::Local;
+
+    let at = at.map(|at| parse_time(&at)).transpose()?;
+    let filter = AuditFilter {
+        target: Some(target.to_string()),
+        since: since
+            .map(|s| parse_age(&s).map(|age| chrono::Utc::now() - age))
+            .transpose()?,
+        until: at,
+    };
+    let records = AuditLog::new(audit_file).read(&filter)?;
+    if records.is_empty() {
+        println!("No audit records for {} in {}", target, audit_file);
+        return Ok(());
+    }
+
+    for record in &records {
+        let when = record.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S");
+        match &record.event {
+            AuditEvent::Transition { from, to } => println!("{}  state  {:?} -> {:?}", when, from, to),
+            AuditEvent::Bust { severity, reason, trigger } => {
+                println!("{}  bust   {:?} ({}): {}", when, severity, trigger.as_str(), reason)
+            }
+            AuditEvent::Hook(hook) => {
+                let result = match (hook.success, hook.timed_out) {
+                    (true, _) => "ok",
+                    (false, true) => "timed out",
+                    (false, false) => "failed",
+                };
+                println!("{}  hook   {} `{}` {} in {}ms", when, hook.stage.as_str(), hook.command, result, hook.duration_ms);
+                if !hook.success {
+                    for line in hook.output.lines() {
+                        println!("{:28}{}", "", line);
+                    }
+                }
+            }
+            AuditEvent::Evict { cache_id, kind, reason } => {
+                println!("{}  evict  {} ({}): {}", when, cache_id, kind, reason)
+            }
+        }
+    }
+
+    let (state, cause) = replay(&records);
+    let moment = at.map_or_else(|| "now".to_string(), |at| format!("at {}", at.with_timezone(&Local).format("%H:%M:%S")));
+    match (state, cause) {
+        (Some(state), Some(bust)) => {
+            if let AuditEvent::Bust { severity, reason, trigger } = &bust.event {
+                println!(
+                    "\n{} {}: {:?}, invalidated at {} by a {:?} {} bust: {}",
+                    target,
+                    moment,
+                    state,
+                    bust.timestamp.with_timezone(&Local).format("%H:%M:%S"),
+                    severity,
+                    trigger.as_str(),
+                    reason
+                );
+            }
+        }
+        (Some(state), None) => println!("\n{} {}: {:?}", target, moment, state),
+        (None, _) => println!("\n{} {}: no state change recorded", target, moment),
+    }
+    Ok(())
+}
+
+fn replay_events(
+    config: &bustcall_core::BustcallConfig,
+    since: Option<String>,
+    target: Option<String>,
+    changed: bool,
+    journal: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::event_journal::{Assessment, EventJournal};
+    use bustcall_core::pid_watcher::{BustCallConfig, BustCallDaemon};
+    use chrono::Local;
+
+    let journal = match journal {
+        Some(path) => EventJournal::new(path),
+        None => config.monitoring.journal().ok_or("No event_journal set under [monitoring]")?,
+    };
+    let since = since
+        .map(|s| parse_age(&s).map(|age| chrono::Utc::now() - age).or_else(|_| parse_time(&s)))
+        .transpose()?;
+    let watcher = BustCallConfig::default().with_targets(&config.target)?;
+    let replayed: Vec<_> = BustCallDaemon::reassess(&watcher, journal.read(since)?)
+        .into_iter()
+        .filter(|(record, now)| {
+            target.as_ref().is_none_or(|target| &record.assessment.target == target || &now.target == target)
+        })
+        .collect();
+    if replayed.is_empty() {
+        println!("No journaled events in {}", journal.path().display());
+        return Ok(());
+    }
+
+    let describe = |assessment: &Assessment| match (&assessment.severity, assessment.ignored) {
+        (_, true) => format!("{} ignored", assessment.target),
+        (Some(severity), false) => format!("{} {:?}", assessment.target, severity),
+        (None, false) => format!("{} no bust", assessment.target),
+    };
+    let mut differing = 0;
+    for (record, now) in &replayed {
+        let same = record.assessment == *now;
+        differing += usize::from(!same);
+        if changed && same {
+            continue;
+        }
+        let when = record.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S");
+        let verdict = if same {
+            describe(now)
+        } else {
+            format!("{} => {}", describe(&record.assessment), describe(now))
+        };
+        let kind = format!("{:?}", record.kind);
+        println!("{}  {:<6}  {}  {}", when, kind, record.path.display(), verdict);
+    }
+    println!(
+        "\n{} events replayed, {} assessed differently under the current rules",
+        replayed.len(),
+        differing
+    );
+    Ok(())
+}
+
+/// Print the events and busts of the targets in `args` as they happen,
+/// following the daemon's watcher or, with none running, one of our own,
+/// until Ctrl+C
+fn watch_targets(config: &bustcall_core::BustcallConfig, args: &WatchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::pid_watcher::{BustCallConfig, BustCallDaemon, WatchFeedEvent};
+    use tokio::sync::{broadcast, mpsc};
+
+    if args.targets.is_empty() && !args.all {
+        return Err("Name the targets to watch, or pass --all".into());
+    }
+    let (mut shown, mut busts) = (0, 0);
+    let mut show = |event: &WatchFeedEvent| -> Result<(), Box<dyn std::error::Error>> {
+        if !args.all && !args.targets.iter().any(|target| event.concerns(target)) {
+            return Ok(());
+        }
+        shown += 1;
+        busts += usize::from(matches!(event, WatchFeedEvent::Bust { .. } | WatchFeedEvent::StormEnded { .. }));
+        print_watch_event(event, args.json)
+    };
+
+    let followed = (!args.standalone).then(|| daemon_feed(&args.url)).flatten();
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(async {
+        let (mut events, mut daemon) = match followed {
+            Some(events) => {
+                eprintln!("👀 Following the daemon's watcher at {}, Ctrl+C to stop", args.url);
+                (events, None)
+            }
+            None => {
+                if let Some(unknown) = args.targets.iter().find(|target| !config.target.contains_key(*target)) {
+                    return Err(format!("No target {} in the config", unknown).into());
+                }
+                let targets: std::collections::HashMap<_, _> = config
+                    .target
+                    .iter()
+                    .filter(|(name, _)| args.all || args.targets.contains(name))
+                    .map(|(name, target)| (name.clone(), target.clone()))
+                    .collect();
+                let mut watcher = BustCallConfig { dry_run: args.dry_run, ..Default::default() }.with_targets(&targets)?;
+                watcher.watch_paths = watcher
+                    .targets
+                    .iter()
+                    .filter(|target| !target.templated && target.root.exists())
+                    .map(|target| target.root.clone())
+                    .collect();
+                if watcher.targets.is_empty() {
+                    return Err("None of the targets has a directory to watch".into());
+                }
+
+                let mut daemon = BustCallDaemon::new(watcher)?;
+                if config.global.self_healing {
+                    let cache = std::sync::Arc::new(daemon.cache_manager().clone());
+                    let (healing, probe) = (config.self_healing(cache.clone()), config.health_probe(cache));
+                    daemon = daemon.with_self_healing(healing).with_health_probe(probe);
+                }
+                config.configure_cache(daemon.cache_manager())?;
+                let mut feed = daemon.subscribe();
+                daemon.start().await?;
+                eprintln!(
+                    "👀 No daemon at {}, watching {} targets here{}, Ctrl+C to stop",
+                    args.url,
+                    targets.len(),
+                    if args.dry_run { " (dry run)" } else { "" }
+                );
+
+                let (tx, events) = mpsc::unbounded_channel();
+                tokio::spawn(async move {
+                    loop {
+                        match feed.recv().await {
+                            Ok(event) => {
+                                if tx.send(event).is_err() {
+                                    return;
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                                eprintln!("⚠️ Fell behind, {} events not shown", missed)
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return,
+                        }
+                    }
+                });
+                (events, Some(daemon))
+            }
+        };
+
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) => show(&event)?,
+                    None => {
+                        eprintln!("⏹️ The daemon's watcher stopped");
+                        break;
+                    }
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        // Bursts still held are busted on shutdown, and shown
+        if let Some(daemon) = daemon.as_mut() {
+            daemon.shutdown().await?;
+            while let Ok(event) = events.try_recv() {
+                show(&event)?;
+            }
+        }
+        Ok::<_, Box<dyn std::error::Error>>(())
+    });
+    // The daemon's event stream blocks in a read nothing else ends
+    runtime.shutdown_background();
+    result?;
+    eprintln!("\n{} events shown, {} of them busts", shown, busts);
+    Ok(())
+}
+
+/// Events of the daemon's watcher at `url`, or None when there is no
+/// daemon or it has no watcher
+fn daemon_feed(url: &str) -> Option<tokio::sync::mpsc::UnboundedReceiver<bustcall_core::pid_watcher::WatchFeedEvent>> {
+    use std::io::BufRead;
+
+    let endpoint = format!("{}/api/v1/watch/events", url.trim_end_matches('/'));
+    // No read timeout: the stream is quiet for as long as the targets are
+    let agent = ureq::AgentBuilder::new().timeout_connect(std::time::Duration::from_secs(2)).build();
+    let response = match agent.get(&endpoint).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) => {
+            eprintln!("ℹ️ The daemon at {} has no file watcher attached", url);
+            return None;
+        }
+        Err(_) => return None,
+    };
+
+    let (tx, events) = tokio::sync::mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(response.into_reader()).lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            let Some(data) = line.strip_prefix("data:") else {
+                continue;
+            };
+            match serde_json::from_str(data.trim()) {
+                Ok(event) => {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => eprintln!("⚠️ Unreadable watch event: {}", e),
+            }
+        }
+    });
+    Some(events)
+}
+
+fn print_watch_event(event: &bustcall_core::pid_watcher::WatchFeedEvent, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::pid_watcher::WatchFeedEvent;
+    use chrono::Local;
+
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+        return Ok(());
+    }
+    let at = |time: &chrono::DateTime<chrono::Utc>| time.with_timezone(&Local).format("%H:%M:%S").to_string();
+    match event {
+        WatchFeedEvent::Event(record) => {
+            let assessment = &record.assessment;
+            let verdict = match (&assessment.severity, assessment.ignored) {
+                (_, true) => "ignored".to_string(),
+                (Some(severity), false) => severity.label().to_string(),
+                (None, false) => "no bust".to_string(),
+            };
+            let kind = format!("{:?}", record.kind);
+            println!(
+                "{}  {:<6}  {:<20}  {}  {}",
+                at(&record.timestamp),
+                kind,
+                assessment.target,
+                record.path.display(),
+                verdict
+            );
+        }
+        WatchFeedEvent::Bust { timestamp, target, severity, reason, .. } => {
+            println!("{}  💥 {} busted {}: {}", at(timestamp), target, severity.label(), reason);
+        }
+        WatchFeedEvent::StormStarted { timestamp } => {
+            println!("{}  🌪️ Event storm, per-file events paused until it subsides", at(timestamp));
+        }
+        WatchFeedEvent::StormEnded { timestamp, description, .. } => {
+            println!("{}  🌪️ {}", at(timestamp), description);
+        }
+    }
+    Ok(())
+}
+
+/// Parse `HH:MM[:SS]`, local time today, or an RFC 3339 timestamp
+fn parse_time(value: &str) -> Result<chrono::DateTime<chrono::Utc>, Box<dyn std::error::Error>> {
+    use chrono::{Local, NaiveTime, TimeZone, Utc};
+
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let time = NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| format!("Invalid time: {}, expected HH:MM[:SS] or RFC 3339", value))?;
+    let local = Local
+        .from_local_datetime(&Local::now().date_naive().and_time(time))
+        .earliest()
+        .ok_or_else(|| format!("{} does not exist in the local timezone today", value))?;
+    Ok(local.with_timezone(&Utc))
+}
+
+fn drop_namespace(url: &str, namespace: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/cache/namespace", url.trim_end_matches('/'));
+    match ureq::delete(&endpoint)
+        .query("name", namespace)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+    {
+        Ok(response) => {
+            let report: serde_json::Value = response.into_json()?;
+            println!(
+                "🧺 Dropped namespace {}: {} targets, {} entries ({} bytes), {} queued rebuilds",
+                namespace, report["targets"], report["entries"], report["reclaimed_bytes"], report["rebuilds"]
+            );
+            Ok(())
+        }
+        Err(ureq::Error::Status(_, response)) => {
+            let body: serde_json::Value = response.into_json().unwrap_or_default();
+            Err(format!("Daemon rejected {}: {}", namespace, body["message"].as_str().unwrap_or("unknown error")).into())
+        }
+        Err(e) => Err(format!("Daemon API at {} unavailable: {}", url, e).into()),
+    }
+}
+
+fn display_circuits(url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/heal/circuits", url.trim_end_matches('/'));
+    let circuits: Vec<bustcall_core::self_healing::CircuitStatus> =
+        match ureq::get(&endpoint).timeout(std::time::Duration::from_secs(30)).call() {
+            Ok(response) => response.into_json()?,
+            Err(ureq::Error::Status(404, _)) => {
+                println!("Self-healing is not enabled");
+                return Ok(());
+            }
+            Err(e) => return Err(format!("Daemon API at {} unavailable: {}", url, e).into()),
+        };
+
+    if circuits.is_empty() {
+        println!("✅ No failed recoveries");
+        return Ok(());
+    }
+    for circuit in circuits {
+        match circuit.opened_at {
+            Some(opened_at) => println!(
+                "🚨 {}: open since {} after {} failed recoveries",
+                circuit.component,
+                chrono::DateTime::from_timestamp(opened_at as i64, 0)
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_else(|| opened_at.to_string()),
+                circuit.failures
+            ),
+            None => println!("⚠️ {}: closed, {} failed recoveries", circuit.component, circuit.failures),
+        }
+    }
+    Ok(())
+}
+
+/// `/api/v1/heal/circuits/<component>/reset`, with the component encoded as
+/// a single path segment whatever it contains
+fn reset_circuit_endpoint(url: &str, component: &str) -> String {
+    let component = percent_encoding::utf8_percent_encode(component, percent_encoding::NON_ALPHANUMERIC);
+    format!("{}/api/v1/heal/circuits/{}/reset", url.trim_end_matches('/'), component)
+}
+
+fn reset_circuit(url: &str, component: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = reset_circuit_endpoint(url, component);
+    match ureq::post(&endpoint).timeout(std::time::Duration::from_secs(30)).call() {
+        Ok(response) => {
+            let reply: serde_json::Value = response.into_json()?;
+            if reply["was_open"].as_bool().unwrap_or(false) {
+                println!("🔌 Closed the circuit of {}; its failures are recovered again", component);
+            } else {
+                println!("ℹ️ The circuit of {} was not open", component);
+            }
+            Ok(())
+        }
+        Err(ureq::Error::Status(404, _)) => Err(format!("Self-healing is not enabled on the daemon at {}", url).into()),
+        Err(e) => Err(format!("Daemon API at {} unavailable: {}", url, e).into()),
+    }
+}
+
+fn list_processes(filter: &bustcall_core::ProcessFilter) -> Result<(), Box<dyn std::error::Error>> {
+    let manager = bustcall_core::ProcessManager::new();
+    print_processes(&manager.list_processes(filter)?);
+    Ok(())
+}
+
+fn monitor_processes(
+    filter: &bustcall_core::ProcessFilter,
+    verbose: bool,
+    continuous: bool,
+    interval: u64,
+    window: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manager = bustcall_core::ProcessManager::new();
+    let interval = std::time::Duration::from_secs(interval.max(1));
+    let window = parse_age(window)?.to_std()?;
+
+    loop {
+        // CPU usage needs a previous sample to compare against
+        std::thread::sleep(interval);
+        let processes = if verbose {
+            manager.inspect(filter)?
+        } else {
+            manager.list_processes(filter)?
+        };
+        print_processes(&processes);
+        if verbose {
+            print_open_files(&processes);
+        }
+        if !continuous {
+            return Ok(());
+        }
+        print_trends(&manager, &processes, window);
+        println!();
+    }
+}
+
+fn print_open_files(processes: &[bustcall_core::ProcessInfo]) {
+    for process in processes {
+        if let Some(fingerprint) = &process.fingerprint {
+            println!("{:>8} exe {}", process.pid, fingerprint.exe.display());
+            for (name, value) in &fingerprint.env {
+                println!("{:>8}   {}={}", "", name, value);
+            }
+        }
+        let Some(files) = &process.open_files else {
+            println!("{:>8} open files unavailable", process.pid);
+            continue;
+        };
+        println!("{:>8} {} open descriptors", process.pid, files.fd_count);
+        for socket in &files.listening {
+            println!("{:>8}   listening {} {}", "", socket.protocol, socket.address);
+        }
+        for path in &files.paths {
+            println!("{:>8}   {}", "", path.display());
+        }
+    }
+}
+
+fn print_trends(
+    manager: &bustcall_core::ProcessManager,
+    processes: &[bustcall_core::ProcessInfo],
+    window: std::time::Duration,
+) {
+    for process in processes {
+        let history = manager.metrics_history(process.pid, window);
+        if history.len() < 2 {
+            continue;
+        }
+        let avg_cpu = history.iter().map(|s| s.cpu_usage).sum::<f64>() / history.len() as f64;
+        let peak_memory = history.iter().map(|s| s.memory_usage).max().unwrap_or(0);
+        println!(
+            "{:>8} avg {:.1}% CPU, peak {} KiB over {} samples",
+            process.pid,
+            avg_cpu,
+            peak_memory / 1024,
+            history.len()
+        );
+    }
+}
+
+/// `/api/v1/targets` as served by the daemon
+#[derive(serde::Deserialize)]
+struct TopSnapshot {
+    targets: Vec<TopTarget>,
+    recent_busts: Vec<bustcall_core::dimensional_cache::BustRecord>,
+}
+
+#[derive(serde::Deserialize)]
+struct TopTarget {
+    name: String,
+    cache_state: Option<bustcall_core::dimensional_cache::CacheState>,
+    processes: Vec<(u32, bustcall_core::core::MetricSample)>,
+}
+
+fn eviction_strategy(
+    name: &str,
+    args: WeightArgs,
+) -> Result<bustcall_core::dimensional_cache::EvictionStrategy, Box<dyn std::error::Error>> {
+    use bustcall_core::dimensional_cache::{EvictionStrategy, ModelWeights};
+    Ok(match name.to_lowercase().as_str() {
+        "lru" => EvictionStrategy::LRU,
+        "mru" => EvictionStrategy::MRU,
+        "lfu" => EvictionStrategy::LFU,
+        "fifo" => EvictionStrategy::FIFO,
+        "model-aware" | "model_aware" => {
+            let defaults = ModelWeights::default();
+            EvictionStrategy::ModelAware(ModelWeights {
+                language_priority: args.language_priority.unwrap_or(defaults.language_priority),
+                dependency_impact: args.dependency_impact.unwrap_or(defaults.dependency_impact),
+                build_cost: args.build_cost.unwrap_or(defaults.build_cost),
+                critical_path: args.critical_path,
+            })
+        }
+        other => return Err(format!("Unknown eviction strategy {:?}: use lru, mru, lfu, fifo or model-aware", other).into()),
+    })
+}
+
+/// `/api/v1/cache/evict` response
+#[derive(serde::Deserialize)]
+struct EvictionPlan {
+    dry_run: bool,
+    candidates: Vec<bustcall_core::dimensional_cache::EvictionCandidate>,
+}
+
+fn evict(
+    url: &str,
+    strategy: &bustcall_core::dimensional_cache::EvictionStrategy,
+    dry_run: bool,
+    explain: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/cache/evict", url.trim_end_matches('/'));
+    let plan: EvictionPlan = ureq::post(&endpoint)
+        .timeout(std::time::Duration::from_secs(30))
+        .send_json(serde_json::json!({ "strategy": strategy, "dry_run": dry_run }))
+        .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+        .into_json()?;
+
+    let verb = if plan.dry_run { "Would evict" } else { "Evicted" };
+    println!("🗑️ {} {} entries ({})", verb, plan.candidates.len(), strategy.label());
+    if !explain {
+        for candidate in &plan.candidates {
+            println!("  {}  {}", candidate.cache_id, candidate.reason);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{:<40} {:<16} {:>8} {:>9} {:>10} {:>8} {:>8} {:>8}",
+        "ENTRY", "TARGET", "ACCESS", "INTEGRITY", "DEPENDENCY", "LANGUAGE", "CRITICAL", "SCORE"
+    );
+    for candidate in &plan.candidates {
+        let score = &candidate.score;
+        println!(
+            "{:<40} {:<16} {:>8.2} {:>9.2} {:>10.2} {:>8.2} {:>7.0}x {:>8.2}",
+            candidate.cache_id,
+            candidate.target,
+            score.access,
+            score.integrity,
+            score.dependency,
+            score.language,
+            score.critical_path,
+            score.total
+        );
+    }
+    Ok(())
+}
+
+/// The parts of `/api/v1/status` that `status` prints
+#[derive(serde::Deserialize)]
+struct StatusSnapshot {
+    daemon_pid: u32,
+    cache: Option<bustcall_core::dimensional_cache::CacheMetrics>,
+    #[serde(default)]
+    targets: Vec<bustcall_core::dimensional_cache::TargetCacheState>,
+}
+
+fn display_status(url: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/status", url.trim_end_matches('/'));
+    let status: StatusSnapshot = ureq::get(&endpoint)
+        .timeout(std::time::Duration::from_secs(5))
+        .call()
+        .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+        .into_json()?;
+
+    println!("🌀 bustcall daemon (PID {}) at {}", status.daemon_pid, url);
+    if verbose {
+        display_watchers(url)?;
+    }
+    let Some(cache) = status.cache else {
+        println!("No dimensional cache attached");
+        return Ok(());
+    };
+
+    let lookups = cache.hits + cache.misses;
+    let hit_rate = if lookups == 0 { 0.0 } else { cache.hits as f64 * 100.0 / lookups as f64 };
+    let join = |counts: &std::collections::BTreeMap<String, u64>| {
+        counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(key, count)| format!("{} {}", key, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    println!("Cache: {} entries, {} bytes", cache.entries, cache.bytes);
+    println!("  hits {} / misses {} ({:.1}% hit rate)", cache.hits, cache.misses, hit_rate);
+    println!("  busts: {}", join(&cache.busts));
+    println!("  evictions: {}", join(&cache.evictions));
+    println!("  stale targets used again: {}", cache.stale_to_hot);
+    println!("  reclaimed: {} bytes", cache.reclaimed_bytes);
+    let targets: std::collections::BTreeMap<String, u64> =
+        cache.targets.into_iter().map(|(state, count)| (state, count as u64)).collect();
+    println!("  targets: {}", join(&targets));
+    for target in &status.targets {
+        println!(
+            "    {:<20} {:<6} hot path {:>6.2}  {} entries ({}, {}), integrity {}",
+            target.target,
+            format!("{:?}", target.cache_state).to_lowercase(),
+            target.hot_path_score,
+            target.entries,
+            human_bytes(target.bytes),
+            target.tier.label(),
+            target.integrity_score
+        );
+    }
+    Ok(())
+}
+
+fn display_watchers(url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::pid_watcher::{WatcherHealth, WatcherStatus};
+
+    let endpoint = format!("{}/api/v1/watchers", url.trim_end_matches('/'));
+    let watchers: Vec<WatcherStatus> = match ureq::get(&endpoint).timeout(std::time::Duration::from_secs(5)).call() {
+        Ok(response) => response.into_json()?,
+        Err(ureq::Error::Status(404, _)) => {
+            println!("No file watcher attached");
+            return Ok(());
+        }
+        Err(e) => return Err(format!("Daemon API at {} unavailable: {}", url, e).into()),
+    };
+
+    let ago = |time: chrono::DateTime<chrono::Utc>| {
+        let seconds = (chrono::Utc::now() - time).num_seconds().max(0);
+        match seconds {
+            0..=59 => format!("{}s ago", seconds),
+            60..=3599 => format!("{}m ago", seconds / 60),
+            _ => format!("{}h ago", seconds / 3600),
+        }
+    };
+    println!("Watchers:");
+    println!(
+        "  {:<20} {:<9} {:<14} {:>5} {:<10} {:<24} PIDS",
+        "TARGET", "HEALTH", "BACKEND", "PATHS", "EVENT", "BUST"
+    );
+    for watcher in &watchers {
+        let health = match watcher.health {
+            WatcherHealth::Healthy => "healthy",
+            WatcherHealth::Degraded => "degraded",
+            WatcherHealth::Dead => "dead",
+            WatcherHealth::Stopped => "stopped",
+        };
+        let backends: Vec<&str> = watcher.backends.iter().map(|backend| backend.label()).collect();
+        let paths = match watcher.lost_paths {
+            0 => watcher.watched_paths.to_string(),
+            lost => format!("{}+{}!", watcher.watched_paths, lost),
+        };
+        let bust = watcher.last_bust.as_ref().map_or("-".to_string(), |bust| {
+            let at = chrono::DateTime::from_timestamp(bust.timestamp as i64, 0).map_or("-".to_string(), ago);
+            format!("{} {}", bust.severity.label(), at)
+        });
+        let pids: Vec<String> = watcher.pids.iter().map(u32::to_string).collect();
+        println!(
+            "  {:<20} {:<9} {:<14} {:>5} {:<10} {:<24} {}",
+            watcher.target,
+            health,
+            if backends.is_empty() { "-".to_string() } else { backends.join(",") },
+            paths,
+            watcher.last_event.map_or("-".to_string(), ago),
+            bust,
+            if pids.is_empty() { "-".to_string() } else { pids.join(",") }
+        );
+        if let Some(detail) = &watcher.detail {
+            println!("  {:<20} ⚠️ {}", "", detail);
+        }
+    }
+    Ok(())
+}
+
+fn top(url: &str, interval: u64, busts: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/api/v1/targets", url.trim_end_matches('/'));
+    let interval = std::time::Duration::from_secs(interval.max(1));
+
+    loop {
+        let snapshot: TopSnapshot = ureq::get(&endpoint)
+            .timeout(std::time::Duration::from_secs(5))
+            .call()
+            .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+            .into_json()?;
+
+        // Clear the screen and redraw from the top left
+        print!("\x1b[2J\x1b[H");
+        println!("bustcall top - {}  (every {}s, Ctrl-C to quit)", chrono::Local::now().format("%H:%M:%S"), interval.as_secs());
+        println!();
+        println!("{:<20} {:<6} {:>8} {:>7} {:>12} STATUS", "TARGET", "CACHE", "PID", "CPU%", "MEMORY");
+        for target in &snapshot.targets {
+            let state = target.cache_state.as_ref().map_or("-".to_string(), |state| format!("{:?}", state));
+            if target.processes.is_empty() {
+                println!("{:<20} {:<6} {:>8}", target.name, state, "-");
+            }
+            for (pid, sample) in &target.processes {
+                println!(
+                    "{:<20} {:<6} {:>8} {:>7.1} {:>8} KiB {}",
+                    target.name,
+                    state,
+                    pid,
+                    sample.cpu_usage,
+                    sample.memory_usage / 1024,
+                    sample.status
+                );
+            }
+        }
+
+        println!();
+        println!("Recent busts");
+        let skip = snapshot.recent_busts.len().saturating_sub(busts);
+        for bust in snapshot.recent_busts.iter().skip(skip).rev() {
+            let at = chrono::DateTime::from_timestamp(bust.timestamp as i64, 0)
+                .map(|time| time.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                .unwrap_or_default();
+            let severity = format!("{:?}", bust.severity);
+            let freed = match bust.reclaimed_bytes {
+                0 => String::new(),
+                bytes => format!(" ({} freed)", human_bytes(bytes)),
+            };
+            println!("  {} {:<20} {:<8} {}{}", at, bust.target, severity, bust.reason, freed);
+        }
+
+        std::thread::sleep(interval);
+    }
+}
+
+/// A remote `--config`, fetched; `None` for a local file
+fn remote_config(
+    spec: &str,
+    signers: Option<&str>,
+) -> Result<Option<bustcall_core::core::RemoteConfig>, Box<dyn std::error::Error>> {
+    if !bustcall_core::core::remote_config::is_remote(spec) {
+        return Ok(None);
+    }
+    let mut remote = bustcall_core::core::RemoteConfig::new(spec)?;
+    if let Some(signers) = signers {
+        remote = remote.allowed_signers(signers);
+    }
+    remote.fetch()?;
+    Ok(Some(remote))
+}
+
+fn resolve_config(
+    path: &str,
+    profile: Option<&str>,
+    overrides: &[String],
+) -> Result<bustcall_core::ResolvedConfig, Box<dyn std::error::Error>> {
+    let mut resolver = bustcall_core::ConfigResolver::new().file(path).process_env();
+    if let Some(profile) = profile {
+        resolver = resolver.profile(profile);
+    }
+    let resolver = overrides.iter().fold(resolver, |resolver, assignment| resolver.flag(assignment));
+    let resolved = resolver.resolve()?;
+    for name in &resolved.unmatched_env {
+        log::debug!("{} matches no config key", name);
+    }
+    Ok(resolved)
+}
+
+fn show_config(
+    path: &str,
+    remote: Option<&bustcall_core::core::RemoteConfig>,
+    profile: Option<&str>,
+    overrides: &[String],
+    resolved: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !resolved {
+        let config = if let Some(remote) = remote {
+            remote.load(profile)?
+        } else if std::path::Path::new(path).exists() {
+            bustcall_core::BustcallConfig::load_with_profile(path, profile)?
+        } else {
+            bustcall_core::BustcallConfig::default()
+        };
+        print!("{}", toml::to_string_pretty(&config.redacted()?)?);
+        return Ok(());
+    }
+
+    let mut resolved = resolve_config(path, profile, overrides)?;
+    if let Some(remote) = remote {
+        remote.resolve_paths(&mut resolved.config)?;
+    }
+    for (key, value, source) in resolved.entries()? {
+        println!("{} = {}  # {}", key, value, source);
+    }
+
+    let mut targets: Vec<_> = resolved.config.target.iter().collect();
+    targets.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, target) in targets {
+        if let (Some(written), Some(resolved_path)) = (&target.path, &target.resolved_path) {
+            println!("# target.{}.path {:?} resolves to {}", name, written, resolved_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn validate_config(path: &str, remote: Option<&bustcall_core::core::RemoteConfig>) -> Result<(), Box<dyn std::error::Error>> {
+    let report = match remote {
+        Some(remote) => remote.validate()?,
+        None => bustcall_core::core::validate_file(std::path::Path::new(path))?,
+    };
+    print!("{}", report);
+
+    let errors = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.level == bustcall_core::core::DiagnosticLevel::Error)
+        .count();
+    let warnings = report.diagnostics.len() - errors;
+    if errors > 0 {
+        return Err(format!("{}: {} error(s), {} warning(s)", path, errors, warnings).into());
+    }
+    println!("✅ {} is valid ({} warning(s))", path, warnings);
+    Ok(())
+}
+
+fn migrate_config(path: &str, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::config_migrate::{migrate_file, CURRENT_CONFIG_VERSION};
+
+    let report = migrate_file(std::path::Path::new(path), dry_run)?;
+    if report.applied.is_empty() {
+        println!("✅ {} is already at config_version {}", path, CURRENT_CONFIG_VERSION);
+        return Ok(());
+    }
+
+    println!(
+        "🔧 {}: config_version {} -> {}",
+        path, report.from_version, CURRENT_CONFIG_VERSION
+    );
+    for step in &report.applied {
+        println!("  - {}", step);
+    }
+    println!();
+    for line in &report.diff {
+        println!("{}", line);
+    }
+    match &report.backup {
+        Some(backup) => println!("\n📝 Migrated {}; the original is at {}", path, backup.display()),
+        None => println!("\n(dry run, nothing written)"),
+    }
+    Ok(())
+}
+
+fn diff_configs(
+    path: &str,
+    old: Option<String>,
+    new: Option<String>,
+    daemon: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::config_diff::{load_config_source, ConfigDiff};
+
+    let (diff, old_name, new_name) = match daemon {
+        Some(url) => {
+            let endpoint = format!("{}/api/v1/config", url.trim_end_matches('/'));
+            let running: toml::Table = ureq::get(&endpoint)
+                .timeout(std::time::Duration::from_secs(5))
+                .call()
+                .map_err(|e| format!("Daemon API at {} unavailable: {}", url, e))?
+                .into_json()?;
+            let new_name = new.or(old).unwrap_or_else(|| path.to_string());
+            let file = load_config_source(&new_name)?;
+            (ConfigDiff::between_tables(&running, &file.redacted()?), url, new_name)
+        }
+        None => {
+            let old_name = old.ok_or("Nothing to compare against")?;
+            let new_name = new.unwrap_or_else(|| path.to_string());
+            let diff = ConfigDiff::between(&load_config_source(&old_name)?, &load_config_source(&new_name)?)?;
+            (diff, old_name, new_name)
+        }
+    };
+
+    if diff.is_empty() {
+        println!("✅ {} and {} are equivalent", old_name, new_name);
+        return Ok(());
+    }
+    println!("--- {}\n+++ {}", old_name, new_name);
+    print!("{}", diff);
+    println!(
+        "\n{} target(s) added, {} removed, {} key(s) changed",
+        diff.added_targets.len(),
+        diff.removed_targets.len(),
+        diff.changes.len()
+    );
+    Ok(())
+}
+
+fn discover_targets(path: &str, root: &str, max_depth: usize, write: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::config::ConfigFormat;
+    use bustcall_core::core::discover::{discover, render};
+
+    let config_path = std::path::Path::new(path);
+    let existing = if config_path.exists() {
+        bustcall_core::BustcallConfig::load_from_file(config_path)?
+    } else {
+        bustcall_core::BustcallConfig::default()
+    };
+
+    let mut targets = discover(std::path::Path::new(root), max_depth)?;
+    targets.retain(|target| {
+        let configured = existing.target.contains_key(&target.name)
+            || existing.target.values().any(|t| t.path.as_deref() == Some(target.path.as_str()));
+        if configured {
+            println!("⏭️  Skipping {} ({}): already configured", target.name, target.path);
+        }
+        !configured
+    });
+    if targets.is_empty() {
+        println!("No new targets found under {}", root);
+        return Ok(());
+    }
+
+    let rendered = render(&targets)?;
+    if !write {
+        print!("{}", rendered);
+        return Ok(());
+    }
+
+    if ConfigFormat::from_path(config_path) != ConfigFormat::Toml {
+        return Err(format!("--write appends TOML; {} is not a TOML file", path).into());
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(config_path)?;
+    std::io::Write::write_all(&mut file, format!("\n{}", rendered).as_bytes())?;
+    println!("📝 Added {} target(s) to {}", targets.len(), path);
+    Ok(())
+}
+
+fn mute_notifications(
+    duration: String,
+    target: Option<String>,
+    mute_file: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::notify::mute::{write_mute, MuteRule};
+
+    let until = chrono::Utc::now() + parse_age(&duration)?;
+    write_mute(&mute_file, &MuteRule { until, target: target.clone() })?;
+
+    println!(
+        "🔇 Notifications for {} muted until {}",
+        target.as_deref().unwrap_or("all targets"),
+        until.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    Ok(())
+}
+
+fn list_notifications(
+    since: Option<String>,
+    level: Option<String>,
+    target: Option<String>,
+    limit: usize,
+    history_file: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use bustcall_core::core::notify::history::{HistoryFilter, NotificationHistory, DEFAULT_HISTORY_CAPACITY};
+
+    let filter = HistoryFilter {
+        since: since
+            .map(|s| parse_age(&s).map(|age| chrono::Utc::now() - age))
+            .transpose()?,
+        level: level.map(|l| l.parse()).transpose()?,
+        target,
+        limit: Some(limit),
+    };
+
+    let history = NotificationHistory::open(&history_file, DEFAULT_HISTORY_CAPACITY)?;
+    for record in history.query(&filter) {
+        let failed: Vec<_> = record.channels.iter().filter(|c| !c.delivered).map(|c| c.channel.as_str()).collect();
+        println!(
+            "#{:<6} {} {:<8} {:<12} {}{}",
+            record.id,
+            record.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            record.level.as_str(),
+            record.target.as_deref().unwrap_or("-"),
+            record.message,
+            if record.muted {
+                " (muted)".to_string()
+            } else if failed.is_empty() {
+                String::new()
+            } else {
+                format!(" (failed: {})", failed.join(", "))
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a relative age such as `90s`, `30m`, `1h` or `2d`
+fn parse_age(value: &str) -> Result<chrono::Duration, Box<dyn std::error::Error>> {
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let amount: i64 = amount.parse().map_err(|_| format!("Invalid duration: {}", value))?;
+
+    match unit {
+        "s" => Ok(chrono::Duration::seconds(amount)),
+        "m" => Ok(chrono::Duration::minutes(amount)),
+        "h" => Ok(chrono::Duration::hours(amount)),
+        "d" => Ok(chrono::Duration::days(amount)),
+        _ => Err(format!("Invalid duration unit in {}, expected s/m/h/d", value).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_component_is_one_path_segment() {
+        let endpoint = reset_circuit_endpoint("http://127.0.0.1:8080/", "cache/web sync?");
+        assert_eq!(endpoint, "http://127.0.0.1:8080/api/v1/heal/circuits/cache%2Fweb%20sync%3F/reset");
+
+        let segment = endpoint.split('/').nth(7).unwrap();
+        assert_eq!(percent_encoding::percent_decode_str(segment).decode_utf8().unwrap(), "cache/web sync?");
     }
 }
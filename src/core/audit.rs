@@ -0,0 +1,190 @@
+//! Append-only audit log of cache transitions
+//!
+//! Every state change, bust and eviction the dimensional cache applies is
+//! appended to a JSONL file, so `bustcall cache history <target>` can
+//! reconstruct why a cache was invalidated long after the fact, across
+//! daemon restarts. The file is never rewritten; rotate it externally.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::bust_hook::HookOutcome;
+use crate::dimensional_cache::{BustReason, CacheBustSeverity, CacheState};
+use crate::utils::error::{BustcallError, Result};
+
+pub const DEFAULT_AUDIT_PATH: &str = "/tmp/bustcall-audit.jsonl";
+
+/// What started a bust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BustTrigger {
+    /// Asked of this target: a watcher, the API or the CLI
+    Direct,
+    /// Reached from a bust of a target it depends on
+    Cascade,
+    /// Part of a `bust_many` batch
+    Batch,
+    /// Shared by another daemon over the bus
+    Remote,
+}
+
+impl BustTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BustTrigger::Direct => "direct",
+            BustTrigger::Cascade => "cascade",
+            BustTrigger::Batch => "batch",
+            BustTrigger::Remote => "remote",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// The target moved between Hot, Warm, Cold and Stale
+    Transition { from: CacheState, to: CacheState },
+    Bust {
+        severity: CacheBustSeverity,
+        reason: BustReason,
+        trigger: BustTrigger,
+    },
+    /// One of the target's `pre_bust`, `on_bust` and `post_bust` hooks ran
+    Hook(HookOutcome),
+    /// One entry evicted; `kind` is the strategy, `ttl`, `limits`, ...
+    Evict { cache_id: String, kind: String, reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub target: String,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub target: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    fn matches(&self, record: &AuditRecord) -> bool {
+        self.target.as_ref().is_none_or(|target| &record.target == target)
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && self.until.is_none_or(|until| record.timestamp <= until)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one record, timestamped now
+    pub fn append(&self, target: &str, event: AuditEvent) -> Result<()> {
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            target: target.to_string(),
+            event,
+        };
+        let line = serde_json::to_string(&record)
+            .map_err(|e| BustcallError::DaemonError(format!("Audit record serialization failed: {}", e)))?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Matching records, oldest first. A missing file holds no records.
+    pub fn read(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut records = Vec::new();
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let line = line?;
+            match serde_json::from_str::<AuditRecord>(&line) {
+                Ok(record) if filter.matches(&record) => records.push(record),
+                Ok(_) => {}
+                Err(e) => log::warn!("Skipping corrupt audit line in {}: {}", self.path.display(), e),
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// State the records leave their target in, and the bust that made it
+/// Stale when it is. Pass one target's records, oldest first.
+pub fn replay(records: &[AuditRecord]) -> (Option<CacheState>, Option<&AuditRecord>) {
+    let mut state = None;
+    let mut last_bust = None;
+    for record in records {
+        match &record.event {
+            AuditEvent::Transition { to, .. } => state = Some(to.clone()),
+            AuditEvent::Bust { .. } => last_bust = Some(record),
+            AuditEvent::Hook(_) | AuditEvent::Evict { .. } => {}
+        }
+    }
+    let cause = (state == Some(CacheState::Stale)).then_some(last_bust).flatten();
+    (state, cause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_replay_finds_the_invalidating_bust() {
+        let dir = TempDir::new().unwrap();
+        let log = AuditLog::new(dir.path().join("audit.jsonl"));
+
+        log.append("node", AuditEvent::Transition { from: CacheState::Cold, to: CacheState::Hot }).unwrap();
+        log.append(
+            "node",
+            AuditEvent::Bust {
+                severity: CacheBustSeverity::High,
+                reason: BustReason::FileChanged { path: "package.json".to_string() },
+                trigger: BustTrigger::Direct,
+            },
+        )
+        .unwrap();
+        log.append("node", AuditEvent::Transition { from: CacheState::Hot, to: CacheState::Stale }).unwrap();
+        log.append("python", AuditEvent::Transition { from: CacheState::Cold, to: CacheState::Warm }).unwrap();
+
+        let records = log
+            .read(&AuditFilter {
+                target: Some("node".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(records.len(), 3);
+
+        let (state, cause) = replay(&records);
+        assert_eq!(state, Some(CacheState::Stale));
+        match &cause.unwrap().event {
+            AuditEvent::Bust { reason, trigger, .. } => {
+                assert_eq!(reason.to_string(), "file changed: package.json");
+                assert_eq!(*trigger, BustTrigger::Direct);
+            }
+            event => panic!("unexpected cause {:?}", event),
+        }
+    }
+}
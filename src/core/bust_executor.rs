@@ -0,0 +1,575 @@
+//! On-disk side of a cache bust
+//!
+//! `DimensionalCacheManager` only tracks cache state; a `BustExecutor` clears
+//! what a runtime actually keeps on disk. Executors plan the actions a
+//! severity calls for and `run_actions` carries them out, so a dry run can
+//! show the plan without touching anything. Each level includes the ones
+//! below it: low clears build caches, critical also clears the package
+//! manager's or compiler's shared cache. Targets whose builds go through an
+//! external compiler cache (sccache, ccache, turbo) have it cleared on high
+//! and critical busts as well.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dimensional_cache::CacheBustSeverity;
+
+/// One step of a bust
+#[derive(Debug, Clone, PartialEq)]
+pub enum BustAction {
+    /// Remove a directory; relative paths are taken from the target root
+    RemoveDir(PathBuf),
+    /// Remove every directory with this name below the target root
+    RemoveNamed(String),
+    /// Run a tool in the target root. A tool that is not installed is skipped.
+    Run { program: String, args: Vec<String> },
+    /// Run a tool that clears the cache kept in `dir`, like `Run`, counting
+    /// the space it frees
+    Clear { program: String, args: Vec<String>, dir: PathBuf },
+}
+
+impl BustAction {
+    fn run(program: &str, args: &[&str]) -> Self {
+        BustAction::Run {
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for BustAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BustAction::RemoveDir(path) => write!(f, "remove {}", path.display()),
+            BustAction::RemoveNamed(name) => write!(f, "remove every {}/", name),
+            BustAction::Run { program, args } => write!(f, "run {} {}", program, args.join(" ")),
+            BustAction::Clear { program, args, dir } => {
+                write!(f, "run {} {} (clears {})", program, args.join(" "), dir.display())
+            }
+        }
+    }
+}
+
+/// Clears the on-disk caches of one language ecosystem
+pub trait BustExecutor: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Target runtimes handled, as written in `[target.*] runtime`
+    fn runtimes(&self) -> &[&str];
+
+    /// Actions for a bust of the target rooted at `root`
+    fn plan(&self, root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction>;
+}
+
+/// What running a plan did
+#[derive(Debug, Clone, Default)]
+pub struct BustOutcome {
+    pub removed: Vec<PathBuf>,
+    pub reclaimed_bytes: u64,
+    pub commands: Vec<String>,
+    /// Actions skipped because their tool is not installed
+    pub skipped: Vec<String>,
+    pub failures: Vec<String>,
+}
+
+/// Executors by runtime, consulted by `DimensionalCacheManager` on every bust
+#[derive(Default)]
+pub struct BustExecutors {
+    executors: RwLock<Vec<Arc<dyn BustExecutor>>>,
+}
+
+impl std::fmt::Debug for BustExecutors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self.executors.read().unwrap().iter().map(|e| e.name().to_string()).collect();
+        f.debug_struct("BustExecutors").field("executors", &names).finish()
+    }
+}
+
+impl BustExecutors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Node, Python, Cargo and C executors
+    pub fn builtin() -> Self {
+        let executors = Self::new();
+        executors.register(Arc::new(NodeExecutor));
+        executors.register(Arc::new(PythonExecutor));
+        executors.register(Arc::new(CargoExecutor));
+        executors.register(Arc::new(CExecutor));
+        executors
+    }
+
+    /// Add an executor; it takes over runtimes already handled by an
+    /// earlier one
+    pub fn register(&self, executor: Arc<dyn BustExecutor>) {
+        log::debug!("🧹 Bust executor registered: {}", executor.name());
+        self.executors.write().unwrap().push(executor);
+    }
+
+    pub fn for_runtime(&self, runtime: &str) -> Option<Arc<dyn BustExecutor>> {
+        let runtime = runtime.to_lowercase();
+        self.executors
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|executor| executor.runtimes().contains(&runtime.as_str()))
+            .cloned()
+    }
+
+    /// The plan for a target, empty when no executor handles its runtime
+    pub fn plan(&self, runtime: &str, root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        self.for_runtime(runtime)
+            .map(|executor| executor.plan(root, severity))
+            .unwrap_or_default()
+    }
+
+    /// Plan and run a bust, `None` when no executor handles the runtime
+    pub fn execute(&self, runtime: &str, root: &Path, severity: &CacheBustSeverity) -> Option<BustOutcome> {
+        let executor = self.for_runtime(runtime)?;
+        let outcome = run_actions(root, &executor.plan(root, severity));
+        for failure in &outcome.failures {
+            log::warn!("⚠️  {} bust of {}: {}", executor.name(), root.display(), failure);
+        }
+        Some(outcome)
+    }
+
+    /// The plan for a target whose builds also go through
+    /// `compiler_caches`: its runtime's, then the compiler caches' actions
+    /// not already in it
+    pub fn plan_target(
+        &self,
+        runtime: &str,
+        root: &Path,
+        compiler_caches: &[CompilerCache],
+        severity: &CacheBustSeverity,
+    ) -> Vec<BustAction> {
+        let mut actions = self.plan(runtime, root, severity);
+        for action in compiler_caches.iter().flat_map(|cache| cache.plan(root, severity)) {
+            if !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// Plan and run a bust of a target, `None` when neither an executor nor
+    /// a compiler cache applies
+    pub fn execute_target(
+        &self,
+        runtime: &str,
+        root: &Path,
+        compiler_caches: &[CompilerCache],
+        severity: &CacheBustSeverity,
+    ) -> Option<BustOutcome> {
+        if compiler_caches.is_empty() {
+            return self.execute(runtime, root, severity);
+        }
+        let outcome = run_actions(root, &self.plan_target(runtime, root, compiler_caches, severity));
+        for failure in &outcome.failures {
+            log::warn!("⚠️  Bust of {}: {}", root.display(), failure);
+        }
+        Some(outcome)
+    }
+}
+
+/// Carry out a plan. Failures are collected rather than stopping the plan,
+/// so one locked directory does not keep the rest of the caches around.
+pub fn run_actions(root: &Path, actions: &[BustAction]) -> BustOutcome {
+    let mut outcome = BustOutcome::default();
+    for action in actions {
+        match action {
+            BustAction::RemoveDir(path) => {
+                if path.components().any(|c| c == Component::ParentDir) {
+                    outcome.failures.push(format!("refusing to remove {}", path.display()));
+                    continue;
+                }
+                remove_dir(&root.join(path), &mut outcome);
+            }
+            BustAction::RemoveNamed(name) => {
+                let mut found = Vec::new();
+                find_named(root, name, &mut found);
+                for dir in found {
+                    remove_dir(&dir, &mut outcome);
+                }
+            }
+            BustAction::Run { program, args } => {
+                run_command(root, program, args, &mut outcome);
+            }
+            BustAction::Clear { program, args, dir } => {
+                let dir = root.join(dir);
+                let before = dir_size(&dir);
+                if run_command(root, program, args, &mut outcome) {
+                    outcome.reclaimed_bytes += before.saturating_sub(dir_size(&dir));
+                }
+            }
+        }
+    }
+    outcome
+}
+
+/// Run a tool in `root`, returning whether it succeeded
+fn run_command(root: &Path, program: &str, args: &[String], outcome: &mut BustOutcome) -> bool {
+    let rendered = format!("{} {}", program, args.join(" "));
+    match Command::new(program).args(args).current_dir(root).output() {
+        Ok(output) if output.status.success() => {
+            outcome.commands.push(rendered);
+            return true;
+        }
+        Ok(output) => outcome.failures.push(format!(
+            "{}: {}",
+            rendered,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(e) if e.kind() == ErrorKind::NotFound => outcome.skipped.push(rendered),
+        Err(e) => outcome.failures.push(format!("{}: {}", rendered, e)),
+    }
+    false
+}
+
+fn remove_dir(dir: &Path, outcome: &mut BustOutcome) {
+    if !dir.is_dir() {
+        return;
+    }
+    let size = dir_size(dir);
+    match fs::remove_dir_all(dir) {
+        Ok(()) => {
+            outcome.removed.push(dir.to_path_buf());
+            outcome.reclaimed_bytes += size;
+        }
+        Err(e) => outcome.failures.push(format!("remove {}: {}", dir.display(), e)),
+    }
+}
+
+/// Directories never searched by `RemoveNamed`
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules"];
+
+fn find_named(dir: &Path, name: &str, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_dir() {
+            continue;
+        }
+        let file_name = entry.file_name();
+        if file_name == name {
+            found.push(entry.path());
+        } else if !SKIPPED_DIRS.iter().any(|skipped| file_name == *skipped) {
+            find_named(&entry.path(), name, found);
+        }
+    }
+}
+
+/// Total size of the files under `dir`, symlinks not followed
+pub(crate) fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn level(severity: &CacheBustSeverity) -> u8 {
+    match severity {
+        CacheBustSeverity::Low => 0,
+        CacheBustSeverity::Medium => 1,
+        CacheBustSeverity::High => 2,
+        CacheBustSeverity::Critical => 3,
+    }
+}
+
+/// npm, yarn or pnpm by lockfile.
+/// Low: bundler caches in `node_modules/.cache`; medium: prune extraneous
+/// packages; high: remove `node_modules`; critical: clean the package
+/// manager's cache too.
+pub struct NodeExecutor;
+
+impl BustExecutor for NodeExecutor {
+    fn name(&self) -> &str {
+        "node"
+    }
+
+    fn runtimes(&self) -> &[&str] {
+        &["node", "javascript", "typescript"]
+    }
+
+    fn plan(&self, root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        let manager = if root.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if root.join("yarn.lock").exists() {
+            "yarn"
+        } else {
+            "npm"
+        };
+        let level = level(severity);
+
+        let mut actions = Vec::new();
+        if level < 2 {
+            actions.push(BustAction::RemoveDir("node_modules/.cache".into()));
+        }
+        if level == 1 {
+            actions.push(match manager {
+                "yarn" => BustAction::run("yarn", &["autoclean", "--force"]),
+                manager => BustAction::run(manager, &["prune"]),
+            });
+        }
+        if level >= 2 {
+            actions.push(BustAction::RemoveDir("node_modules".into()));
+        }
+        if level >= 3 {
+            actions.push(match manager {
+                "pnpm" => BustAction::run("pnpm", &["store", "prune"]),
+                "yarn" => BustAction::run("yarn", &["cache", "clean"]),
+                _ => BustAction::run("npm", &["cache", "clean", "--force"]),
+            });
+        }
+        actions
+    }
+}
+
+/// Low: `__pycache__`; medium: test and type checker caches; high: `build`;
+/// critical: the pip cache too.
+pub struct PythonExecutor;
+
+impl BustExecutor for PythonExecutor {
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn runtimes(&self) -> &[&str] {
+        &["python", "python3"]
+    }
+
+    fn plan(&self, _root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        let level = level(severity);
+        let mut actions = vec![BustAction::RemoveNamed("__pycache__".to_string())];
+        if level >= 1 {
+            actions.extend(
+                [".pytest_cache", ".mypy_cache", ".ruff_cache"]
+                    .iter()
+                    .map(|name| BustAction::RemoveDir(name.into())),
+            );
+        }
+        if level >= 2 {
+            actions.push(BustAction::RemoveDir("build".into()));
+        }
+        if level >= 3 {
+            actions.push(BustAction::run("python3", &["-m", "pip", "cache", "purge"]));
+        }
+        actions
+    }
+}
+
+/// Low: incremental state; medium: the debug profile; high: `cargo clean`;
+/// critical: the sccache cache too.
+pub struct CargoExecutor;
+
+impl BustExecutor for CargoExecutor {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn runtimes(&self) -> &[&str] {
+        &["rust", "cargo"]
+    }
+
+    fn plan(&self, _root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        let mut actions = match level(severity) {
+            0 => vec![BustAction::RemoveDir("target/debug/incremental".into())],
+            1 => vec![BustAction::RemoveDir("target/debug".into())],
+            _ => vec![BustAction::run("cargo", &["clean"])],
+        };
+        if level(severity) >= 3 {
+            // sccache has no clear command; stop the server so it lets go of
+            // the cache before removing it
+            actions.push(BustAction::run("sccache", &["--stop-server"]));
+            if let Some(dir) = sccache_dir() {
+                actions.push(BustAction::RemoveDir(dir));
+            }
+        }
+        actions
+    }
+}
+
+fn sccache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("SCCACHE_DIR") {
+        return Some(dir.into());
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache/sccache"))
+}
+
+/// `CCACHE_DIR`, else the XDG location ccache 4 uses unless the legacy
+/// `~/.ccache` is still there
+fn ccache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("CCACHE_DIR") {
+        return Some(dir.into());
+    }
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    let legacy = home.join(".ccache");
+    Some(if legacy.is_dir() { legacy } else { home.join(".cache/ccache") })
+}
+
+/// `ccache --clear`, measuring what it frees when the cache can be found
+fn ccache_clear() -> BustAction {
+    match ccache_dir() {
+        Some(dir) => BustAction::Clear {
+            program: "ccache".to_string(),
+            args: vec!["--clear".to_string()],
+            dir,
+        },
+        None => BustAction::run("ccache", &["--clear"]),
+    }
+}
+
+/// Low: nothing, make and CMake track changes themselves; medium: `make
+/// clean`; high: the `build` directory too; critical: clear ccache.
+pub struct CExecutor;
+
+impl BustExecutor for CExecutor {
+    fn name(&self) -> &str {
+        "c"
+    }
+
+    fn runtimes(&self) -> &[&str] {
+        &["c", "cpp", "c++"]
+    }
+
+    fn plan(&self, root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        let level = level(severity);
+        let mut actions = Vec::new();
+        if level >= 1 && root.join("Makefile").exists() {
+            actions.push(BustAction::run("make", &["clean"]));
+        }
+        if level >= 2 {
+            actions.push(BustAction::RemoveDir("build".into()));
+        }
+        if level >= 3 {
+            actions.push(ccache_clear());
+        }
+        actions
+    }
+}
+
+/// External compiler or build cache a target's builds go through, set per
+/// target in `[target.*] compiler_caches`. High and critical busts clear
+/// it; milder ones leave it, as it keys its entries by content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompilerCache {
+    Sccache,
+    Ccache,
+    Turbo,
+}
+
+impl CompilerCache {
+    /// Actions clearing the cache for a bust of the target rooted at `root`
+    pub fn plan(&self, _root: &Path, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        if level(severity) < 2 {
+            return Vec::new();
+        }
+        match self {
+            // sccache has no clear command; stop the server, which also
+            // zeroes its stats, so it lets go of the cache before pruning it
+            CompilerCache::Sccache => {
+                let mut actions = vec![BustAction::run("sccache", &["--stop-server"])];
+                actions.extend(sccache_dir().map(BustAction::RemoveDir));
+                actions
+            }
+            CompilerCache::Ccache => vec![ccache_clear()],
+            // `turbo prune` only writes a pruned copy of the workspace; the
+            // local cache is these directories, by turbo version
+            CompilerCache::Turbo => vec![
+                BustAction::RemoveDir(".turbo/cache".into()),
+                BustAction::RemoveDir("node_modules/.cache/turbo".into()),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plans_by_runtime_and_severity() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        let executors = BustExecutors::builtin();
+
+        assert_eq!(
+            executors.plan("node", dir.path(), &CacheBustSeverity::Low),
+            vec![BustAction::RemoveDir("node_modules/.cache".into())]
+        );
+        assert_eq!(
+            executors.plan("Node", dir.path(), &CacheBustSeverity::Critical),
+            vec![
+                BustAction::RemoveDir("node_modules".into()),
+                BustAction::run("pnpm", &["store", "prune"]),
+            ]
+        );
+        assert!(executors.plan("c", dir.path(), &CacheBustSeverity::Low).is_empty());
+        assert!(executors.plan("gosilang", dir.path(), &CacheBustSeverity::Critical).is_empty());
+    }
+
+    #[test]
+    fn test_removes_caches_below_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("pkg/__pycache__")).unwrap();
+        fs::write(root.join("pkg/__pycache__/mod.pyc"), [0u8; 16]).unwrap();
+        fs::create_dir_all(root.join("node_modules/dep/__pycache__")).unwrap();
+        fs::write(root.join("pkg/mod.py"), "").unwrap();
+
+        let outcome = BustExecutors::builtin().execute("python", root, &CacheBustSeverity::Low).unwrap();
+        assert_eq!(outcome.removed, vec![root.join("pkg/__pycache__")]);
+        assert_eq!(outcome.reclaimed_bytes, 16);
+        assert!(root.join("pkg/mod.py").exists());
+        assert!(root.join("node_modules/dep/__pycache__").exists());
+
+        let outcome = run_actions(root, &[BustAction::RemoveDir("../escape".into())]);
+        assert_eq!(outcome.failures.len(), 1);
+    }
+
+    #[test]
+    fn test_compiler_caches_join_high_busts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".turbo/cache")).unwrap();
+        fs::write(root.join(".turbo/cache/abc.tar.zst"), [0u8; 64]).unwrap();
+        let executors = BustExecutors::builtin();
+        let caches = [CompilerCache::Turbo, CompilerCache::Turbo];
+
+        assert_eq!(
+            executors.plan_target("node", root, &caches, &CacheBustSeverity::Medium),
+            executors.plan("node", root, &CacheBustSeverity::Medium)
+        );
+        assert_eq!(
+            executors.plan_target("node", root, &caches, &CacheBustSeverity::High),
+            vec![
+                BustAction::RemoveDir("node_modules".into()),
+                BustAction::RemoveDir(".turbo/cache".into()),
+                BustAction::RemoveDir("node_modules/.cache/turbo".into()),
+            ]
+        );
+
+        let outcome = executors.execute_target("gosilang", root, &caches, &CacheBustSeverity::Critical).unwrap();
+        assert_eq!(outcome.reclaimed_bytes, 64);
+        assert!(!root.join(".turbo/cache").exists());
+    }
+}
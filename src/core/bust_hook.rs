@@ -0,0 +1,231 @@
+//! Commands a target runs around its busts
+//!
+//! ```toml
+//! [target.node]
+//! path = "web"
+//! pre_bust = "npm run stop-dev"
+//! on_bust = "npm ci"
+//! post_bust = "npm run start-dev"
+//! hook_timeout_seconds = 600
+//! pre_bust_aborts = true
+//! ```
+//!
+//! `pre_bust` runs before the target's entries and on-disk caches are
+//! cleared, `on_bust` right after, and `post_bust` once `on_bust` is done.
+//! Each runs with `sh -c` in the target's path, with `BUSTCALL_TARGET`,
+//! `BUSTCALL_SEVERITY` and `BUSTCALL_REASON` set, and is killed, with
+//! anything it started, once the timeout elapses. A failing hook is logged
+//! and the bust goes on, unless it is `pre_bust` and `pre_bust_aborts` is
+//! set: then the target is left as it was. The outcome of each, with the
+//! tail of its output, is audited as an event of its own. Hooks run
+//! outside the cache's batch lock, so a slow `npm ci` holds up no
+//! evictions.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 300;
+
+/// Lines of a hook's output kept on its outcome
+const OUTPUT_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreBust,
+    OnBust,
+    PostBust,
+}
+
+impl HookStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::PreBust => "pre_bust",
+            HookStage::OnBust => "on_bust",
+            HookStage::PostBust => "post_bust",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BustHooks {
+    pub pre_bust: Option<String>,
+    pub on_bust: Option<String>,
+    pub post_bust: Option<String>,
+    /// Seconds each hook may run, `DEFAULT_HOOK_TIMEOUT_SECONDS` if unset
+    pub hook_timeout_seconds: Option<u64>,
+    /// Abort the bust when `pre_bust` fails or times out
+    pub pre_bust_aborts: bool,
+}
+
+impl BustHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_bust.is_none() && self.on_bust.is_none() && self.post_bust.is_none()
+    }
+
+    pub fn command(&self, stage: HookStage) -> Option<&str> {
+        match stage {
+            HookStage::PreBust => self.pre_bust.as_deref(),
+            HookStage::OnBust => self.on_bust.as_deref(),
+            HookStage::PostBust => self.post_bust.as_deref(),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.hook_timeout_seconds.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS))
+    }
+
+    /// Run the hook of `stage` in `cwd` for a bust of `target`, if it has
+    /// one
+    pub fn run(&self, stage: HookStage, cwd: Option<&Path>, target: &str, severity: &str, reason: &str) -> Option<HookOutcome> {
+        let command = self.command(stage)?;
+        log::info!("🪝 Running {} hook of {}: {}", stage.as_str(), target, command);
+        let mut process = Command::new("sh");
+        process
+            .arg("-c")
+            .arg(command)
+            .env("BUSTCALL_TARGET", target)
+            .env("BUSTCALL_SEVERITY", severity)
+            .env("BUSTCALL_REASON", reason)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(cwd) = cwd {
+            process.current_dir(cwd);
+        }
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut process, 0);
+
+        let started = Instant::now();
+        let (success, timed_out, output) = match process.spawn() {
+            Ok(child) => wait_with_timeout(child, self.timeout()),
+            Err(e) => (false, false, e.to_string()),
+        };
+        let outcome = HookOutcome {
+            stage,
+            command: command.to_string(),
+            success,
+            timed_out,
+            duration_ms: started.elapsed().as_millis() as u64,
+            output,
+        };
+        if outcome.success {
+            log::info!("✅ {} hook of {} done in {}ms", stage.as_str(), target, outcome.duration_ms);
+        } else if outcome.timed_out {
+            log::warn!("⏱️ {} hook of {} killed after {}s", stage.as_str(), target, self.timeout().as_secs());
+        } else {
+            log::warn!("❌ {} hook of {} failed: {}", stage.as_str(), target, outcome.output);
+        }
+        Some(outcome)
+    }
+}
+
+/// Success, whether the child was killed, and the tail of its stdout and
+/// stderr
+pub(crate) fn wait_with_timeout(mut child: Child, timeout: Duration) -> (bool, bool, String) {
+    let readers: Vec<_> = [
+        child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>),
+        child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut pipe| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    })
+    .collect();
+
+    let deadline = Instant::now() + timeout;
+    let (status, timed_out) = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break (Some(status), false),
+            Ok(None) if Instant::now() >= deadline => {
+                kill(&mut child);
+                break (child.wait().ok(), true);
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                kill(&mut child);
+                let _ = child.wait();
+                log::warn!("Failed to wait for hook: {}", e);
+                break (None, false);
+            }
+        }
+    };
+
+    let mut text = String::new();
+    for reader in readers {
+        text.push_str(&String::from_utf8_lossy(&reader.join().unwrap_or_default()));
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let mut output = lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..].join("\n");
+    let success = !timed_out && status.is_some_and(|status| status.success());
+    if let (false, Some(status)) = (success, status) {
+        output = format!("{}\n{}", status, output).trim().to_string();
+    }
+    (success, timed_out, output)
+}
+
+/// Kill the hook with everything it started, so nothing holds its pipes open
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+/// One hook run, as audited
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookOutcome {
+    pub stage: HookStage,
+    pub command: String,
+    pub success: bool,
+    #[serde(default)]
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    /// Last lines of the hook's stdout and stderr
+    #[serde(default)]
+    pub output: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_capture_output_and_time_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = BustHooks {
+            on_bust: Some("echo \"$BUSTCALL_TARGET $BUSTCALL_SEVERITY\" > ran; pwd".to_string()),
+            post_bust: Some("sleep 5".to_string()),
+            pre_bust: Some("echo nope >&2; exit 3".to_string()),
+            hook_timeout_seconds: Some(1),
+            pre_bust_aborts: false,
+        };
+
+        let outcome = hooks.run(HookStage::OnBust, Some(dir.path()), "node", "high", "manual").unwrap();
+        assert!(outcome.success);
+        assert_eq!(std::fs::read_to_string(dir.path().join("ran")).unwrap(), "node high\n");
+        assert!(outcome.output.ends_with(dir.path().file_name().unwrap().to_str().unwrap()));
+
+        let failed = hooks.run(HookStage::PreBust, Some(dir.path()), "node", "high", "manual").unwrap();
+        assert!(!failed.success && !failed.timed_out);
+        assert!(failed.output.contains("nope"));
+
+        let killed = hooks.run(HookStage::PostBust, Some(dir.path()), "node", "high", "manual").unwrap();
+        assert!(!killed.success && killed.timed_out);
+        assert!(killed.duration_ms < 4000);
+
+        assert!(BustHooks::default().run(HookStage::OnBust, None, "node", "high", "manual").is_none());
+    }
+}
@@ -0,0 +1,329 @@
+//! Transport for sharing cache busts between daemons
+//!
+//! `DimensionalCacheManager` publishes every local bust on a `CacheBus` and
+//! applies what other daemons publish (see `spawn_subscriber`). Redis and
+//! NATS are built in behind the `redis-backend` and `nats-backend` features;
+//! `LocalBus` keeps busts to this daemon.
+
+use std::fmt;
+#[cfg(feature = "redis-backend")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "redis-backend")]
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::error::{BustcallError, Result};
+
+/// `[cache] bus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BusKind {
+    Redis,
+    Nats,
+    Local,
+}
+
+impl Default for BusKind {
+    /// Redis when built with it, as before buses were pluggable
+    fn default() -> Self {
+        if cfg!(feature = "redis-backend") {
+            BusKind::Redis
+        } else {
+            BusKind::Local
+        }
+    }
+}
+
+/// Publish/subscribe transport for bust messages
+pub trait CacheBus: fmt::Debug + Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Whether busts reach other daemons; the subscriber is not started
+    /// otherwise
+    fn distributed(&self) -> bool {
+        true
+    }
+
+    fn publish(&self, channel: &str, payload: &str) -> Result<()>;
+
+    fn subscribe(&self, channels: &[String]) -> Result<Box<dyn BusSubscription>>;
+}
+
+/// Open subscription to one or more channels
+pub trait BusSubscription: Send {
+    /// Next payload, or `None` when nothing arrived within `timeout`. An
+    /// error means the connection is gone and the caller should resubscribe.
+    fn next_message(&mut self, timeout: Duration) -> Result<Option<String>>;
+}
+
+/// Connect the bus for `kind`; `url` defaults to the backend's local server
+pub fn open_bus(kind: BusKind, url: Option<&str>) -> Result<Arc<dyn CacheBus>> {
+    match kind {
+        BusKind::Local => Ok(Arc::new(LocalBus)),
+        #[cfg(feature = "redis-backend")]
+        BusKind::Redis => Ok(Arc::new(RedisBus::new(url.unwrap_or("redis://127.0.0.1/"))?)),
+        #[cfg(feature = "nats-backend")]
+        BusKind::Nats => Ok(Arc::new(NatsBus::new(url.unwrap_or("nats://127.0.0.1:4222")))),
+        #[allow(unreachable_patterns)]
+        kind => {
+            let _ = url;
+            Err(BustcallError::ConfigError(format!(
+                "cache bus {:?} needs bustcall built with the {}-backend feature",
+                kind,
+                format!("{:?}", kind).to_lowercase()
+            )))
+        }
+    }
+}
+
+/// Busts stay on this daemon
+#[derive(Debug, Default)]
+pub struct LocalBus;
+
+impl CacheBus for LocalBus {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn distributed(&self) -> bool {
+        false
+    }
+
+    fn publish(&self, _channel: &str, _payload: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn subscribe(&self, _channels: &[String]) -> Result<Box<dyn BusSubscription>> {
+        Ok(Box::new(LocalSubscription))
+    }
+}
+
+struct LocalSubscription;
+
+impl BusSubscription for LocalSubscription {
+    fn next_message(&mut self, timeout: Duration) -> Result<Option<String>> {
+        std::thread::sleep(timeout);
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+#[derive(Debug)]
+pub struct RedisBus {
+    client: redis::Client,
+}
+
+#[cfg(feature = "redis-backend")]
+impl RedisBus {
+    /// Connections are opened per use, so an unreachable server only fails
+    /// the publish or subscription at hand
+    pub fn new(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| BustcallError::DaemonError(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+fn redis_error(e: redis::RedisError) -> BustcallError {
+    BustcallError::DaemonError(format!("Redis: {}", e))
+}
+
+#[cfg(feature = "redis-backend")]
+impl CacheBus for RedisBus {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    fn publish(&self, channel: &str, payload: &str) -> Result<()> {
+        let mut conn = self.client.get_connection().map_err(redis_error)?;
+        redis::cmd("PUBLISH")
+            .arg(channel)
+            .arg(payload)
+            .query::<i64>(&mut conn)
+            .map_err(redis_error)?;
+        Ok(())
+    }
+
+    /// Reads on a thread of its own: a `PubSub` borrows its connection and
+    /// unsubscribes when dropped, so it cannot be handed out
+    fn subscribe(&self, channels: &[String]) -> Result<Box<dyn BusSubscription>> {
+        let mut conn = self.client.get_connection().map_err(redis_error)?;
+        // Wake up regularly to notice the subscription was dropped
+        conn.set_read_timeout(Some(Duration::from_secs(1))).map_err(redis_error)?;
+        let channels = channels.to_vec();
+        let closed = Arc::new(AtomicBool::new(false));
+        let (subscribed_tx, subscribed) = mpsc::channel::<Result<()>>();
+        let (messages_tx, messages) = mpsc::channel::<Result<String>>();
+
+        let reader_closed = closed.clone();
+        std::thread::spawn(move || {
+            let mut pubsub = conn.as_pubsub();
+            let subscribed = channels.iter().try_for_each(|channel| pubsub.subscribe(channel));
+            let failed = subscribed.is_err();
+            let _ = subscribed_tx.send(subscribed.map_err(redis_error));
+            if failed {
+                return;
+            }
+            while !reader_closed.load(Ordering::Relaxed) {
+                let payload = match pubsub.get_message() {
+                    Ok(message) => message.get_payload::<String>().map_err(redis_error),
+                    Err(e) if e.is_timeout() => continue,
+                    Err(e) => Err(redis_error(e)),
+                };
+                let failed = payload.is_err();
+                if messages_tx.send(payload).is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        subscribed
+            .recv()
+            .map_err(|_| BustcallError::DaemonError("Redis subscriber exited".to_string()))??;
+        Ok(Box::new(RedisSubscription { messages, closed }))
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+struct RedisSubscription {
+    messages: mpsc::Receiver<Result<String>>,
+    closed: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "redis-backend")]
+impl BusSubscription for RedisSubscription {
+    fn next_message(&mut self, timeout: Duration) -> Result<Option<String>> {
+        match self.messages.recv_timeout(timeout) {
+            Ok(payload) => payload.map(Some),
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(None),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Err(BustcallError::DaemonError("Redis subscription closed".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+impl Drop for RedisSubscription {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Channels are NATS subjects
+#[cfg(feature = "nats-backend")]
+pub struct NatsBus {
+    url: String,
+    connection: std::sync::Mutex<Option<nats::Connection>>,
+}
+
+#[cfg(feature = "nats-backend")]
+impl fmt::Debug for NatsBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NatsBus").field("url", &self.url).finish()
+    }
+}
+
+#[cfg(feature = "nats-backend")]
+impl NatsBus {
+    /// Connects on first use and again after a failure
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            connection: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn connection(&self) -> Result<nats::Connection> {
+        let mut connection = self.connection.lock().unwrap();
+        if let Some(conn) = connection.as_ref() {
+            return Ok(conn.clone());
+        }
+        let conn = nats::connect(&self.url)
+            .map_err(|e| BustcallError::DaemonError(format!("NATS connection to {} failed: {}", self.url, e)))?;
+        *connection = Some(conn.clone());
+        Ok(conn)
+    }
+
+    fn reset(&self) {
+        self.connection.lock().unwrap().take();
+    }
+}
+
+#[cfg(feature = "nats-backend")]
+impl CacheBus for NatsBus {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    fn publish(&self, channel: &str, payload: &str) -> Result<()> {
+        self.connection()?.publish(channel, payload).map_err(|e| {
+            self.reset();
+            BustcallError::DaemonError(format!("NATS publish failed: {}", e))
+        })
+    }
+
+    fn subscribe(&self, channels: &[String]) -> Result<Box<dyn BusSubscription>> {
+        let conn = self.connection()?;
+        let subscriptions = channels
+            .iter()
+            .map(|channel| conn.subscribe(channel))
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| {
+                self.reset();
+                BustcallError::DaemonError(format!("NATS subscribe failed: {}", e))
+            })?;
+        Ok(Box::new(NatsSubscription { subscriptions }))
+    }
+}
+
+#[cfg(feature = "nats-backend")]
+struct NatsSubscription {
+    subscriptions: Vec<nats::Subscription>,
+}
+
+#[cfg(feature = "nats-backend")]
+impl BusSubscription for NatsSubscription {
+    fn next_message(&mut self, timeout: Duration) -> Result<Option<String>> {
+        // A single subscription can block; several are polled in turn
+        if let [subscription] = self.subscriptions.as_slice() {
+            return match subscription.next_timeout(timeout) {
+                Ok(message) => Ok(Some(String::from_utf8_lossy(&message.data).into_owned())),
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => Ok(None),
+                Err(e) => Err(e.into()),
+            };
+        }
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            if let Some(message) = self.subscriptions.iter().find_map(|subscription| subscription.try_next()) {
+                return Ok(Some(String::from_utf8_lossy(&message.data).into_owned()));
+            }
+            if std::time::Instant::now() >= deadline {
+                return Ok(None);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_bus_by_kind() {
+        let local = open_bus(BusKind::Local, None).unwrap();
+        assert_eq!(local.name(), "local");
+        assert!(!local.distributed());
+        local.publish("bustcall:cache_bust", "node|web:Low").unwrap();
+        let mut subscription = local.subscribe(&["bustcall:cache_bust".to_string()]).unwrap();
+        assert_eq!(subscription.next_message(Duration::from_millis(1)).unwrap(), None);
+
+        #[cfg(not(feature = "nats-backend"))]
+        assert!(open_bus(BusKind::Nats, None).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Storage tiers cache entries are spilled to out of memory
+//!
+//! `DimensionalCacheManager` keeps the entries of Hot targets in memory. A
+//! target cooling to Warm has its entries spilled to the disk tier, and one
+//! going Cold to the remote tier when there is one; using the target again
+//! promotes them back. The daemon's memory then grows with its busy
+//! targets rather than with the repository. Redis is built in as a remote
+//! tier behind the `redis-backend` feature; other stores implement
+//! `TierStore` and are passed to `with_tiers`.
+
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dimensional_cache::CacheEvicon;
+use crate::utils::error::{BustcallError, Result};
+
+/// Where a target's entries are held
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheTier {
+    #[default]
+    Memory,
+    Disk,
+    Remote,
+}
+
+impl CacheTier {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CacheTier::Memory => "memory",
+            CacheTier::Disk => "disk",
+            CacheTier::Remote => "remote",
+        }
+    }
+}
+
+/// Store of spilled entries, by target
+pub trait TierStore: fmt::Debug + Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Store `target`'s entries, replacing any the tier held of it
+    fn put(&self, target: &str, entries: &[CacheEvicon]) -> Result<()>;
+
+    /// `target`'s entries, none when the tier holds nothing of it
+    fn load(&self, target: &str) -> Result<Vec<CacheEvicon>>;
+
+    fn remove(&self, target: &str) -> Result<()>;
+}
+
+/// The disk tier, and the remote tier when there is one
+#[derive(Debug, Clone)]
+pub struct CacheTiers {
+    pub disk: Arc<dyn TierStore>,
+    pub remote: Option<Arc<dyn TierStore>>,
+}
+
+impl CacheTiers {
+    pub fn store(&self, tier: CacheTier) -> Option<Arc<dyn TierStore>> {
+        match tier {
+            CacheTier::Memory => None,
+            CacheTier::Disk => Some(self.disk.clone()),
+            CacheTier::Remote => self.remote.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpilledEntries {
+    target: String,
+    entries: Vec<CacheEvicon>,
+}
+
+fn encode(target: &str, entries: &[CacheEvicon]) -> Result<String> {
+    serde_json::to_string(&SpilledEntries {
+        target: target.to_string(),
+        entries: entries.to_vec(),
+    })
+    .map_err(|e| BustcallError::DaemonError(format!("Spilled entries of {} not serialized: {}", target, e)))
+}
+
+fn decode(target: &str, data: &str) -> Result<Vec<CacheEvicon>> {
+    serde_json::from_str::<SpilledEntries>(data)
+        .map(|spilled| spilled.entries)
+        .map_err(|e| BustcallError::DaemonError(format!("Spilled entries of {} unreadable: {}", target, e)))
+}
+
+/// Local disk index: one JSON file per target, named by the target's hash
+#[derive(Debug, Clone)]
+pub struct DiskTier {
+    dir: PathBuf,
+}
+
+impl DiskTier {
+    /// `dir` is created on the first spill
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, target: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", &blake3::hash(target.as_bytes()).to_hex()[..32]))
+    }
+}
+
+impl TierStore for DiskTier {
+    fn name(&self) -> &str {
+        "disk"
+    }
+
+    fn put(&self, target: &str, entries: &[CacheEvicon]) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(target);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, encode(target, entries)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn load(&self, target: &str) -> Result<Vec<CacheEvicon>> {
+        match fs::read_to_string(self.path(target)) {
+            Ok(data) => decode(target, &data),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn remove(&self, target: &str) -> Result<()> {
+        match fs::remove_file(self.path(target)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Connect the remote tier at `url`, by its scheme
+pub fn open_remote_tier(url: &str) -> Result<Arc<dyn TierStore>> {
+    match url.split("://").next().unwrap_or_default() {
+        #[cfg(feature = "redis-backend")]
+        "redis" | "rediss" => Ok(Arc::new(RedisTier::new(url)?)),
+        #[cfg(not(feature = "redis-backend"))]
+        "redis" | "rediss" => Err(BustcallError::ConfigError(
+            "remote tier redis needs bustcall built with the redis-backend feature".to_string(),
+        )),
+        scheme => Err(BustcallError::ConfigError(format!(
+            "Unsupported remote tier {}: {} is not built in",
+            url, scheme
+        ))),
+    }
+}
+
+/// Remote tier shared by the daemons using the same server: a target's
+/// entries are one key, so the last daemon to spill a target wins
+#[cfg(feature = "redis-backend")]
+#[derive(Debug)]
+pub struct RedisTier {
+    client: redis::Client,
+}
+
+#[cfg(feature = "redis-backend")]
+impl RedisTier {
+    /// Connections are opened per use, like `RedisBus`
+    pub fn new(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| BustcallError::DaemonError(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self { client })
+    }
+
+    fn key(target: &str) -> String {
+        format!("bustcall:tier:{}", target)
+    }
+
+    fn connection(&self) -> Result<redis::Connection> {
+        self.client.get_connection().map_err(redis_error)
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+fn redis_error(e: redis::RedisError) -> BustcallError {
+    BustcallError::DaemonError(format!("Redis: {}", e))
+}
+
+#[cfg(feature = "redis-backend")]
+impl TierStore for RedisTier {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    fn put(&self, target: &str, entries: &[CacheEvicon]) -> Result<()> {
+        redis::cmd("SET")
+            .arg(Self::key(target))
+            .arg(encode(target, entries)?)
+            .query::<()>(&mut self.connection()?)
+            .map_err(redis_error)
+    }
+
+    fn load(&self, target: &str) -> Result<Vec<CacheEvicon>> {
+        let data: Option<String> = redis::cmd("GET")
+            .arg(Self::key(target))
+            .query(&mut self.connection()?)
+            .map_err(redis_error)?;
+        data.map_or(Ok(Vec::new()), |data| decode(target, &data))
+    }
+
+    fn remove(&self, target: &str) -> Result<()> {
+        redis::cmd("DEL")
+            .arg(Self::key(target))
+            .query::<()>(&mut self.connection()?)
+            .map_err(redis_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensional_cache::EvictionStrategy;
+
+    #[test]
+    fn test_disk_tier_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let tier = DiskTier::new(dir.path().join("tiers"));
+        assert!(tier.load("shop::web").unwrap().is_empty());
+
+        let entry = CacheEvicon {
+            cache_id: "web/dist/app.js".to_string(),
+            model_binding: "shop::web".to_string(),
+            eviction_strategy: EvictionStrategy::LRU,
+            last_access: 10,
+            inserted_at: 5,
+            size_bytes: 300,
+            access_frequency: 2,
+            integrity_score: 100,
+            dependency_depth: 0,
+            content_hash: None,
+        };
+        tier.put("shop::web", &[entry]).unwrap();
+        let loaded = tier.load("shop::web").unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].cache_id, "web/dist/app.js");
+
+        tier.remove("shop::web").unwrap();
+        tier.remove("shop::web").unwrap();
+        assert!(tier.load("shop::web").unwrap().is_empty());
+        assert!(open_remote_tier("s3://bucket/cache").is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! cgroup v2 metrics for monitored runtimes
+//!
+//! The cgroup a runtime's processes live in is found through
+//! `/proc/<pid>/cgroup`, and its `memory.current`, `cpu.stat`, `io.stat` and
+//! pressure (PSI) files are read from `/sys/fs/cgroup`. Memory nearing
+//! `memory.max`, CPU throttling and sustained pressure raise a Warning before
+//! the kernel starts killing or starving the runtime.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::notify::{Notification, NotificationLevel};
+use crate::core::process::{ProcessFilter, ProcessManager};
+use crate::utils::error::Result;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CpuStat {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IoStat {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub read_ios: u64,
+    pub write_ios: u64,
+}
+
+/// `avg10` of the `some` and `full` lines of a `*.pressure` file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Pressure {
+    pub some_avg10: f64,
+    pub full_avg10: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CgroupStats {
+    /// Path relative to the cgroup root, e.g. `/system.slice/app.service`
+    pub path: PathBuf,
+    pub memory_current: Option<u64>,
+    /// `None` when unlimited
+    pub memory_max: Option<u64>,
+    pub cpu: Option<CpuStat>,
+    pub io: Option<IoStat>,
+    pub cpu_pressure: Option<Pressure>,
+    pub memory_pressure: Option<Pressure>,
+    pub io_pressure: Option<Pressure>,
+}
+
+impl CgroupStats {
+    /// Share of `memory.max` in use, if there is a limit
+    pub fn memory_ratio(&self) -> Option<f64> {
+        match (self.memory_current, self.memory_max) {
+            (Some(current), Some(max)) if max > 0 => Some(current as f64 / max as f64),
+            _ => None,
+        }
+    }
+}
+
+/// The cgroup v2 path of `pid`; `None` on cgroup v1 or outside Linux
+pub fn cgroup_of(pid: u32) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    parse_proc_cgroup(&content)
+}
+
+/// The unified hierarchy is the `0::<path>` line
+fn parse_proc_cgroup(content: &str) -> Option<PathBuf> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| PathBuf::from(path.trim()))
+}
+
+/// Read the stats of the cgroup at `path` (relative to the cgroup root)
+pub fn read_stats(path: &Path) -> Option<CgroupStats> {
+    let dir = Path::new(CGROUP_ROOT).join(path.strip_prefix("/").unwrap_or(path));
+    if !dir.is_dir() {
+        return None;
+    }
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+
+    Some(CgroupStats {
+        path: path.to_path_buf(),
+        memory_current: read("memory.current").and_then(|s| s.trim().parse().ok()),
+        memory_max: read("memory.max").and_then(|s| s.trim().parse().ok()),
+        cpu: read("cpu.stat").map(|s| parse_cpu_stat(&s)),
+        io: read("io.stat").map(|s| parse_io_stat(&s)),
+        cpu_pressure: read("cpu.pressure").and_then(|s| parse_pressure(&s)),
+        memory_pressure: read("memory.pressure").and_then(|s| parse_pressure(&s)),
+        io_pressure: read("io.pressure").and_then(|s| parse_pressure(&s)),
+    })
+}
+
+/// Stats of every distinct cgroup the given processes belong to
+pub fn stats_for_pids(pids: impl IntoIterator<Item = u32>) -> Vec<CgroupStats> {
+    let paths: BTreeSet<PathBuf> = pids.into_iter().filter_map(cgroup_of).collect();
+    paths.iter().filter_map(|path| read_stats(path)).collect()
+}
+
+fn parse_cpu_stat(content: &str) -> CpuStat {
+    let mut stat = CpuStat::default();
+    for (key, value) in content.lines().filter_map(|line| line.split_once(' ')) {
+        let Ok(value) = value.trim().parse() else {
+            continue;
+        };
+        match key {
+            "usage_usec" => stat.usage_usec = value,
+            "user_usec" => stat.user_usec = value,
+            "system_usec" => stat.system_usec = value,
+            "nr_periods" => stat.nr_periods = value,
+            "nr_throttled" => stat.nr_throttled = value,
+            "throttled_usec" => stat.throttled_usec = value,
+            _ => {}
+        }
+    }
+    stat
+}
+
+/// Sum `rbytes=... wbytes=...` over all devices
+fn parse_io_stat(content: &str) -> IoStat {
+    let mut stat = IoStat::default();
+    for field in content.lines().flat_map(|line| line.split_whitespace().skip(1)) {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let Ok(value) = value.parse::<u64>() else {
+            continue;
+        };
+        match key {
+            "rbytes" => stat.read_bytes += value,
+            "wbytes" => stat.write_bytes += value,
+            "rios" => stat.read_ios += value,
+            "wios" => stat.write_ios += value,
+            _ => {}
+        }
+    }
+    stat
+}
+
+fn parse_pressure(content: &str) -> Option<Pressure> {
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find(|line| line.starts_with(kind))?
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix("avg10="))?
+            .parse::<f64>()
+            .ok()
+    };
+    Some(Pressure {
+        some_avg10: avg10("some ")?,
+        full_avg10: avg10("full "),
+    })
+}
+
+/// `[monitoring.pressure]` thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PressureConfig {
+    /// Share of `memory.max` in use
+    #[serde(default = "default_memory_ratio")]
+    pub memory_ratio: f64,
+    /// Share of CPU periods throttled since the previous sample
+    #[serde(default = "default_throttled_ratio")]
+    pub throttled_ratio: f64,
+    /// PSI `some avg10` percentages; 100 disables a check
+    #[serde(default = "default_psi")]
+    pub cpu_pressure: f64,
+    #[serde(default = "default_psi")]
+    pub memory_pressure: f64,
+    #[serde(default = "default_psi")]
+    pub io_pressure: f64,
+}
+
+impl Default for PressureConfig {
+    fn default() -> Self {
+        Self {
+            memory_ratio: default_memory_ratio(),
+            throttled_ratio: default_throttled_ratio(),
+            cpu_pressure: default_psi(),
+            memory_pressure: default_psi(),
+            io_pressure: default_psi(),
+        }
+    }
+}
+
+fn default_memory_ratio() -> f64 {
+    0.9
+}
+
+fn default_throttled_ratio() -> f64 {
+    0.5
+}
+
+fn default_psi() -> f64 {
+    40.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    Memory,
+    Throttling,
+    CpuPressure,
+    MemoryPressure,
+    IoPressure,
+}
+
+impl Signal {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Signal::Memory => "memory",
+            Signal::Throttling => "cpu throttling",
+            Signal::CpuPressure => "cpu pressure",
+            Signal::MemoryPressure => "memory pressure",
+            Signal::IoPressure => "io pressure",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PressureAlarm {
+    pub target: String,
+    pub cgroup: PathBuf,
+    pub signal: Signal,
+    /// Percent of the respective limit
+    pub value: f64,
+    pub limit: f64,
+}
+
+impl PressureAlarm {
+    pub fn notification(&self) -> Notification {
+        Notification::new(
+            NotificationLevel::Warning,
+            &format!(
+                "{} cgroup {} {} at {:.1}% over limit {:.1}%",
+                self.target,
+                self.cgroup.display(),
+                self.signal.as_str(),
+                self.value,
+                self.limit
+            ),
+        )
+        .title("Runtime under resource pressure")
+        .component("cgroup")
+        .target(&self.target)
+        .with_metadata("cgroup", &self.cgroup.to_string_lossy())
+        .with_metadata("signal", self.signal.as_str())
+    }
+}
+
+/// Signals over their threshold in `stats`; `previous` is the last sample of
+/// the same cgroup, needed to turn throttling counters into a rate
+pub fn pressure_signals(config: &PressureConfig, stats: &CgroupStats, previous: Option<&CpuStat>) -> Vec<(Signal, f64, f64)> {
+    let mut signals = Vec::new();
+    let mut check = |signal, value: Option<f64>, limit: f64| {
+        if let Some(value) = value {
+            if value > limit {
+                signals.push((signal, value, limit));
+            }
+        }
+    };
+
+    check(Signal::Memory, stats.memory_ratio().map(|r| r * 100.0), config.memory_ratio * 100.0);
+
+    let throttled = match (&stats.cpu, previous) {
+        (Some(cpu), Some(previous)) if cpu.nr_periods > previous.nr_periods => {
+            let periods = cpu.nr_periods - previous.nr_periods;
+            let throttled = cpu.nr_throttled.saturating_sub(previous.nr_throttled);
+            Some(throttled as f64 / periods as f64 * 100.0)
+        }
+        _ => None,
+    };
+    check(Signal::Throttling, throttled, config.throttled_ratio * 100.0);
+
+    check(Signal::CpuPressure, stats.cpu_pressure.map(|p| p.some_avg10), config.cpu_pressure);
+    check(Signal::MemoryPressure, stats.memory_pressure.map(|p| p.some_avg10), config.memory_pressure);
+    check(Signal::IoPressure, stats.io_pressure.map(|p| p.some_avg10), config.io_pressure);
+    signals
+}
+
+/// Samples the cgroups of monitored targets and raises each signal once until
+/// it drops back below its threshold
+#[derive(Debug)]
+pub struct CgroupMonitor {
+    processes: ProcessManager,
+    targets: Vec<String>,
+    config: PressureConfig,
+    previous: HashMap<PathBuf, CpuStat>,
+    raised: HashSet<(String, PathBuf, Signal)>,
+}
+
+impl CgroupMonitor {
+    /// Monitor the cgroups of processes named like each of `targets`
+    pub fn new(targets: Vec<String>, config: PressureConfig) -> Self {
+        Self {
+            processes: ProcessManager::new(),
+            targets,
+            config,
+            previous: HashMap::new(),
+            raised: HashSet::new(),
+        }
+    }
+
+    /// Current stats per target
+    pub fn stats(&self) -> Result<Vec<(String, CgroupStats)>> {
+        let mut stats = Vec::new();
+        for target in &self.targets {
+            let pids = self
+                .processes
+                .list_processes(&ProcessFilter::all().name(target))?
+                .into_iter()
+                .map(|process| process.pid);
+            stats.extend(stats_for_pids(pids).into_iter().map(|s| (target.clone(), s)));
+        }
+        Ok(stats)
+    }
+
+    /// Take one sample; returns newly raised alarms
+    pub fn sample(&mut self) -> Result<Vec<PressureAlarm>> {
+        let mut alarms = Vec::new();
+        let mut seen = HashSet::new();
+        let mut cpu = HashMap::new();
+
+        for (target, stats) in self.stats()? {
+            let previous = self.previous.get(&stats.path);
+            for (signal, value, limit) in pressure_signals(&self.config, &stats, previous) {
+                let key = (target.clone(), stats.path.clone(), signal);
+                if self.raised.insert(key.clone()) {
+                    log::warn!("🔥 {} cgroup {} {} at {:.1}%", target, stats.path.display(), signal.as_str(), value);
+                    alarms.push(PressureAlarm {
+                        target: target.clone(),
+                        cgroup: stats.path.clone(),
+                        signal,
+                        value,
+                        limit,
+                    });
+                }
+                seen.insert(key);
+            }
+            if let Some(stat) = stats.cpu {
+                cpu.insert(stats.path, stat);
+            }
+        }
+
+        self.raised.retain(|key| seen.contains(key));
+        self.previous = cpu;
+        Ok(alarms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgroup_files() {
+        let proc_cgroup = "12:cpu,cpuacct:/legacy\n0::/system.slice/app.service\n";
+        assert_eq!(parse_proc_cgroup(proc_cgroup), Some(PathBuf::from("/system.slice/app.service")));
+
+        let cpu = parse_cpu_stat("usage_usec 900\nuser_usec 600\nsystem_usec 300\nnr_periods 10\nnr_throttled 4\nthrottled_usec 50\n");
+        assert_eq!(cpu.nr_throttled, 4);
+        assert_eq!(cpu.usage_usec, 900);
+
+        let io = parse_io_stat("8:0 rbytes=100 wbytes=20 rios=1 wios=2 dbytes=0 dios=0\n8:16 rbytes=5 wbytes=0 rios=1 wios=0\n");
+        assert_eq!(io.read_bytes, 105);
+        assert_eq!(io.write_ios, 2);
+
+        let pressure = parse_pressure(
+            "some avg10=12.50 avg60=3.00 avg300=1.00 total=1234\nfull avg10=2.00 avg60=0.00 avg300=0.00 total=10\n",
+        )
+        .unwrap();
+        assert_eq!(pressure.some_avg10, 12.5);
+        assert_eq!(pressure.full_avg10, Some(2.0));
+    }
+
+    #[test]
+    fn test_signals_over_threshold() {
+        let stats = CgroupStats {
+            path: PathBuf::from("/app"),
+            memory_current: Some(95),
+            memory_max: Some(100),
+            cpu: Some(CpuStat {
+                nr_periods: 20,
+                nr_throttled: 9,
+                ..Default::default()
+            }),
+            io: None,
+            cpu_pressure: Some(Pressure::default()),
+            memory_pressure: Some(Pressure {
+                some_avg10: 55.0,
+                full_avg10: None,
+            }),
+            io_pressure: None,
+        };
+        let previous = CpuStat {
+            nr_periods: 10,
+            nr_throttled: 2,
+            ..Default::default()
+        };
+
+        let signals: Vec<Signal> = pressure_signals(&PressureConfig::default(), &stats, Some(&previous))
+            .into_iter()
+            .map(|(signal, _, _)| signal)
+            .collect();
+        assert_eq!(signals, vec![Signal::Memory, Signal::Throttling, Signal::MemoryPressure]);
+    }
+}
@@ -1,5 +1,72 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use toml::{Table, Value};
+
+use crate::core::audit::AuditLog;
+use crate::core::bust_executor::{BustExecutors, CompilerCache};
+use crate::core::bust_hook::BustHooks;
+use crate::core::cache_bus::{open_bus, BusKind};
+use crate::core::cache_tier::{open_remote_tier, CacheTiers, DiskTier};
+use crate::core::config_migrate::{canonical_sections, migrate, CURRENT_CONFIG_VERSION};
+use crate::core::event_journal::{EventJournal, DEFAULT_JOURNAL_MAX_BYTES, DEFAULT_JOURNAL_PATH};
+use crate::core::health::HealthProbe;
+use crate::core::notify::NotificationManager;
+use crate::core::open_files::SocketDetect;
+use crate::core::poll_backoff::PollBackoff;
+use crate::core::process::{ProcessManager, RuntimeLookup};
+use crate::core::rebuild::{RebuildCommand, RebuildExecutor};
+use crate::core::recovery::{RecoveryActions, RecoveryConfig};
+use crate::core::schedule::TargetSchedule;
+use crate::core::secrets::{redact, resolve_secrets};
+use crate::core::severity::SeverityOverride;
+use crate::core::watch_backend::WatchBackend;
+use crate::pid_watcher::RuntimeWatcher;
+use crate::self_healing::{HealthMonitor, SelfHealingArchitecture};
+use crate::dimensional_cache::{CacheLimits, CacheSnapshot, DimensionalCacheManager, ModelBinding, ModelWeights, WarmReport};
+
+/// Looked up in the working directory unless `--config` says otherwise
+pub const DEFAULT_CONFIG_PATH: &str = "bustcall.toml";
+
+/// On-disk config format, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// `.json`, `.yaml`/`.yml`, anything else is TOML
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => ConfigFormat::Json,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Toml,
+        }
+    }
+
+    /// Deserialize `content`; the same serde types are used for every
+    /// format, so validation is identical
+    pub fn parse<T: serde::de::DeserializeOwned>(&self, content: &str) -> Result<T, ConfigError> {
+        match self {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string())),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| ConfigError::Parse(e.to_string())),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string())),
+        }
+    }
+
+    pub fn render<T: Serialize>(&self, value: &T) -> Result<String, ConfigError> {
+        match self {
+            ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|e| ConfigError::Parse(e.to_string())),
+            ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|e| ConfigError::Parse(e.to_string())),
+            ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|e| ConfigError::Parse(e.to_string())),
+        }
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -8,39 +75,1175 @@ pub enum ConfigError {
     
     #[error("Parse error: {0}")]
     Parse(String),
+
+    #[error("Remote config error: {0}")]
+    Remote(String),
 }
 
+/// The whole config file. Every section is optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BustcallConfig {
+    /// Schema version the file was written for; older files are migrated
+    /// on load, see [`crate::core::config_migrate`]
+    #[serde(default = "current_config_version")]
+    pub config_version: u32,
+    /// Glob patterns, relative to this file, of fragments merged into it,
+    /// e.g. `include = ["targets/*.toml"]`. Emptied once merged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default)]
     pub daemon: crate::core::daemon::DaemonConfig,
-    pub notifications: NotificationConfig,
+    /// `[notifications]` in older files
+    #[serde(default, alias = "notifications")]
+    pub notify: NotificationConfig,
+    #[serde(default)]
+    pub global: GlobalConfig,
+    #[serde(default)]
     pub monitoring: MonitoringConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Per-component recovery chains and command actions; see
+    /// [`crate::core::recovery`]
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+    /// `[targets.*]` in older files
+    #[serde(default, alias = "targets")]
+    pub target: HashMap<String, TargetConfig>,
+    /// `[profile.<name>]` override blocks in the same layout as the file,
+    /// e.g. `[profile.ci.global]`; merged over it when selected
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profile: HashMap<String, Table>,
+    /// Key paths whose values were resolved from a secret reference
+    #[serde(skip)]
+    pub secrets: BTreeSet<String>,
 }
 
+/// `[global]` supervisor settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalConfig {
+    /// Attempt recovery of failed busts and panicked watcher tasks; see
+    /// [`crate::self_healing`]
+    pub self_healing: bool,
+    pub supervisor_mode: bool,
+    pub default_max_retries: u32,
+    pub daemon_interval_seconds: u64,
+    /// Longest wait between pid polls of a runtime target that stays
+    /// stable; see [`crate::core::poll_backoff`]
+    pub pid_poll_max_interval_seconds: u64,
+    /// Target paths that do not exist yet are expected to be created by the
+    /// build rather than being a config mistake
+    pub create_missing: bool,
+    /// Reload the config file when it changes; see
+    /// [`crate::core::config_watch`]
+    pub watch_config: bool,
+    /// `${workspace_root}` in target paths; defaults to the directory of
+    /// the config file
+    pub workspace_root: Option<String>,
+    /// How often a remote `--config` is fetched again; see
+    /// [`crate::core::remote_config`]
+    pub config_refresh_seconds: u64,
+}
+
+/// `[target.<name>]`: what the target is, how its rebuilds are weighted, and
+/// its schedule, restart, probe and resource settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TargetConfig {
+    /// May use `${workspace_root}`, `${home}` and `${ENV_VAR}`; relative
+    /// paths are taken from the directory of the file defining the target
+    pub path: Option<String>,
+    pub runtime: Option<String>,
+    /// Socket the runtime's processes hold, `{ port = 3000 }` or
+    /// `{ unix_socket = "/tmp/app.sock" }`; alone it replaces matching by
+    /// `runtime`, with `runtime` set only processes of that name holding it
+    /// count
+    pub detect: Option<SocketDetect>,
+    pub pid_watch: bool,
+    /// Seconds between pid polls of `runtime` while it changes, overriding
+    /// `daemon_interval_seconds`
+    pub pid_poll_interval_seconds: Option<u64>,
+    pub enabled: bool,
+    pub language_priority: f64,
+    pub dependency_impact: f64,
+    pub build_cost: f64,
+    pub critical_path: bool,
+    /// Glob pattern -> severity for file changes below `path`; see
+    /// [`crate::core::severity`]
+    pub severity_overrides: BTreeMap<String, SeverityOverride>,
+    /// Seconds an unaccessed cache entry of this target is kept
+    pub cache_ttl_seconds: Option<u64>,
+    /// Targets whose busts invalidate this one too, e.g. `["c"]` for a node
+    /// native addon, when `[cache] cascade` is on
+    pub depends_on: Vec<String>,
+    /// Shell command run in `path` to rebuild the target after a bust
+    pub rebuild_command: Option<String>,
+    /// Glob patterns below `path` of the entries registered when the daemon
+    /// starts, so the target starts Warm rather than Cold
+    pub warm: Vec<String>,
+    /// External caches the target's builds go through, cleared on High and
+    /// Critical busts: `sccache`, `ccache` or `turbo`
+    pub compiler_caches: Vec<CompilerCache>,
+    /// Quiet time after which a burst of file events below `path` is
+    /// busted, overriding the watcher's default
+    pub debounce_ms: Option<u64>,
+    /// Patterns below `path`, in `.gitignore` syntax, whose changes are
+    /// not watched; see [`crate::core::watch_ignore`]
+    pub ignore: Vec<String>,
+    /// Also honour the `.gitignore` and `.bustcallignore` files below `path`
+    pub ignore_files: bool,
+    /// Only bust for changed files whose content changed, not for ones
+    /// merely touched
+    pub content_hash: bool,
+    /// How changes below `path` are watched: `auto`, `inotify`, `poll` or
+    /// `fanotify`; see [`crate::core::watch_backend`]
+    pub watch_backend: WatchBackend,
+    #[serde(flatten)]
+    pub schedule: TargetSchedule,
+    /// `pre_bust`, `on_bust` and `post_bust` commands run around the
+    /// target's busts; see [`crate::core::bust_hook`]
+    #[serde(flatten)]
+    pub hooks: BustHooks,
+    /// `path` expanded and made absolute when loaded from a file
+    #[serde(skip)]
+    pub resolved_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NotificationConfig {
     pub enabled: bool,
     pub channels: Vec<String>,
+    #[serde(default)]
+    pub throttle: Option<crate::core::notify::throttle::ThrottleConfig>,
+    #[serde(default)]
+    pub history_path: Option<String>,
+    #[serde(default = "default_history_capacity")]
+    pub history_capacity: usize,
+    #[serde(default)]
+    pub escalation: Option<crate::core::notify::escalation::EscalationConfig>,
+    #[serde(default)]
+    pub mute_path: Option<String>,
+    #[serde(default)]
+    pub retry: Option<crate::core::notify::retry::RetryConfig>,
+    #[serde(default)]
+    pub digest: Option<crate::core::notify::digest::DigestConfig>,
+    /// Automatic actions per level/severity band, executed by the daemon
+    #[serde(default)]
+    pub policy: Vec<crate::core::notify::policy::PolicyRule>,
+    #[serde(default)]
+    pub console: Option<crate::core::notify::ConsoleConfig>,
+    #[serde(default)]
+    pub syslog: Option<crate::core::notify::syslog::SyslogConfig>,
+    #[serde(default)]
+    pub exec: Option<crate::core::notify::exec::ExecConfig>,
+    #[cfg(unix)]
+    #[serde(default)]
+    pub journald: Option<crate::core::notify::journald::JournaldConfig>,
+    #[cfg(feature = "notify-webhooks")]
+    #[serde(default)]
+    pub slack: Option<crate::core::notify::slack::SlackConfig>,
+    #[cfg(feature = "notify-webhooks")]
+    #[serde(default)]
+    pub discord: Option<crate::core::notify::discord::DiscordConfig>,
+    #[cfg(feature = "notify-webhooks")]
+    #[serde(default)]
+    pub teams: Option<crate::core::notify::teams::TeamsConfig>,
+    #[cfg(feature = "notify-webhooks")]
+    #[serde(default)]
+    pub matrix: Option<crate::core::notify::matrix::MatrixConfig>,
+    #[cfg(feature = "notify-webhooks")]
+    #[serde(default)]
+    pub incident: Option<crate::core::notify::incident::IncidentConfig>,
+    #[cfg(feature = "notify-email")]
+    #[serde(default)]
+    pub email: Option<crate::core::notify::email::EmailConfig>,
+    #[cfg(feature = "notify-desktop")]
+    #[serde(default)]
+    pub desktop: Option<crate::core::notify::desktop::DesktopConfig>,
+}
+
+fn current_config_version() -> u32 {
+    CURRENT_CONFIG_VERSION
+}
+
+fn default_history_capacity() -> usize {
+    crate::core::notify::history::DEFAULT_HISTORY_CAPACITY
 }
 
+/// `[cache]`: bounds of the dimensional cache, enforced in the background
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub max_entries: Option<usize>,
+    pub max_bytes: Option<u64>,
+    /// How often expired entries are removed and the limits enforced
+    pub expiry_interval_seconds: u64,
+    /// Bust the targets that `depends_on` a busted target too, one severity
+    /// level milder per hop
+    pub cascade: bool,
+    /// Clear what each target's runtime caches on disk (`node_modules`,
+    /// `__pycache__`, `target`, ...) on a bust, as deep as the severity asks
+    pub executors: bool,
+    /// Transport busts are shared with other daemons on: redis, nats or
+    /// local to keep them to this daemon
+    pub bus: BusKind,
+    /// Server of the bus, the backend's local default when unset
+    pub bus_url: Option<String>,
+    /// Channel (NATS subject) busts are shared on
+    pub bust_channel: String,
+    /// Further channels to apply busts from, e.g. another cluster's
+    pub subscribe_channels: Vec<String>,
+    /// Names this daemon in shared busts; hostname and pid by default
+    pub node_id: Option<String>,
+    /// Rebuilds run at once by the rebuild executor
+    pub rebuild_concurrency: usize,
+    /// Snapshot (`bustcall cache export`) whose entries are registered when
+    /// the daemon starts, alongside each target's `warm` patterns
+    pub warm_snapshot: Option<String>,
+    /// Queue a rebuild of every warmed target on start
+    pub prebuild_on_warm: bool,
+    /// Append-only log of every transition, bust and eviction, read by
+    /// `bustcall cache history`; unset to disable
+    pub audit_log: Option<String>,
+    /// Disk tier: the entries of targets cooling from Hot are spilled to
+    /// this directory out of memory until used again. Unset keeps every
+    /// entry in memory.
+    pub spill_dir: Option<String>,
+    /// Remote tier Cold targets' entries are spilled to, shared by the
+    /// daemons using it, e.g. `redis://cache:6379/`; needs `spill_dir`
+    pub remote_tier: Option<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: None,
+            max_bytes: None,
+            expiry_interval_seconds: 60,
+            cascade: false,
+            executors: true,
+            bus: BusKind::default(),
+            bus_url: None,
+            bust_channel: "bustcall:cache_bust".to_string(),
+            subscribe_channels: Vec::new(),
+            node_id: None,
+            rebuild_concurrency: 2,
+            warm_snapshot: None,
+            prebuild_on_warm: false,
+            audit_log: Some(crate::core::audit::DEFAULT_AUDIT_PATH.to_string()),
+            spill_dir: None,
+            remote_tier: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MonitoringConfig {
     pub interval_seconds: u64,
     pub processes: Vec<String>,
+    /// Resource thresholds per process, sampled every `interval_seconds`
+    #[serde(default)]
+    pub watches: Vec<crate::core::watch::ProcessWatchConfig>,
+    /// Source of process start/exit events
+    #[serde(default)]
+    pub event_backend: crate::core::proc_events::EventBackend,
+    /// cgroup v2 memory, throttling and PSI thresholds for `processes`
+    #[serde(default)]
+    pub pressure: Option<crate::core::cgroup::PressureConfig>,
+    /// Journal of the file events the watcher assessed, read by `bustcall
+    /// watch replay`; unset to disable
+    pub event_journal: Option<String>,
+    /// Size at which the journal rolls over to `<event_journal>.1`
+    pub event_journal_max_bytes: u64,
+}
+
+impl MonitoringConfig {
+    pub fn journal(&self) -> Option<EventJournal> {
+        self.event_journal
+            .as_ref()
+            .map(|path| EventJournal::new(path).with_max_bytes(self.event_journal_max_bytes))
+    }
 }
 
 impl Default for BustcallConfig {
     fn default() -> Self {
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
+            include: Vec::new(),
             daemon: crate::core::daemon::DaemonConfig::default(),
-            notifications: NotificationConfig {
-                enabled: true,
-                channels: vec!["console".to_string()],
-            },
-            monitoring: MonitoringConfig {
-                interval_seconds: 5,
-                processes: vec![],
-            },
+            notify: NotificationConfig::default(),
+            global: GlobalConfig::default(),
+            monitoring: MonitoringConfig::default(),
+            cache: CacheConfig::default(),
+            recovery: RecoveryConfig::default(),
+            target: HashMap::new(),
+            profile: HashMap::new(),
+            secrets: BTreeSet::new(),
+        }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            channels: vec!["console".to_string()],
+            throttle: None,
+            history_path: Some(crate::core::notify::history::DEFAULT_HISTORY_PATH.to_string()),
+            history_capacity: default_history_capacity(),
+            escalation: None,
+            mute_path: Some(crate::core::notify::mute::DEFAULT_MUTE_PATH.to_string()),
+            retry: None,
+            digest: None,
+            policy: Vec::new(),
+            console: None,
+            syslog: None,
+            exec: None,
+            #[cfg(unix)]
+            journald: None,
+            #[cfg(feature = "notify-webhooks")]
+            slack: None,
+            #[cfg(feature = "notify-webhooks")]
+            discord: None,
+            #[cfg(feature = "notify-webhooks")]
+            teams: None,
+            #[cfg(feature = "notify-webhooks")]
+            matrix: None,
+            #[cfg(feature = "notify-webhooks")]
+            incident: None,
+            #[cfg(feature = "notify-email")]
+            email: None,
+            #[cfg(feature = "notify-desktop")]
+            desktop: None,
+        }
+    }
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 5,
+            processes: Vec::new(),
+            watches: Vec::new(),
+            event_backend: Default::default(),
+            pressure: None,
+            event_journal: Some(DEFAULT_JOURNAL_PATH.to_string()),
+            event_journal_max_bytes: DEFAULT_JOURNAL_MAX_BYTES,
+        }
+    }
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            self_healing: true,
+            supervisor_mode: true,
+            default_max_retries: 3,
+            daemon_interval_seconds: 5,
+            pid_poll_max_interval_seconds: crate::core::poll_backoff::DEFAULT_MAX_POLL_INTERVAL_SECONDS,
+            create_missing: false,
+            watch_config: false,
+            workspace_root: None,
+            config_refresh_seconds: crate::core::remote_config::DEFAULT_REFRESH_SECONDS,
+        }
+    }
+}
+
+impl Default for TargetConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            runtime: None,
+            detect: None,
+            pid_watch: true,
+            pid_poll_interval_seconds: None,
+            enabled: true,
+            language_priority: 0.5,
+            dependency_impact: 0.5,
+            build_cost: 0.5,
+            critical_path: false,
+            severity_overrides: BTreeMap::new(),
+            cache_ttl_seconds: None,
+            depends_on: Vec::new(),
+            rebuild_command: None,
+            warm: Vec::new(),
+            compiler_caches: Vec::new(),
+            debounce_ms: None,
+            ignore: Vec::new(),
+            ignore_files: false,
+            content_hash: true,
+            watch_backend: WatchBackend::Auto,
+            schedule: TargetSchedule::default(),
+            hooks: BustHooks::default(),
+            resolved_path: None,
+        }
+    }
+}
+
+impl TargetConfig {
+    /// Weights the dimensional cache prioritises this target's rebuilds by
+    pub fn model_weights(&self) -> ModelWeights {
+        ModelWeights {
+            language_priority: self.language_priority as f32,
+            dependency_impact: self.dependency_impact as f32,
+            build_cost: self.build_cost as f32,
+            critical_path: self.critical_path,
+        }
+    }
+
+    /// Binding of this target for `DimensionalCacheManager::bind_model`
+    pub fn model_binding(&self) -> ModelBinding {
+        ModelBinding {
+            runtime: self.runtime.clone().unwrap_or_default(),
+            pid: None,
+            pid_start_time: None,
+            path: self.root().map(|path| path.to_string_lossy().into_owned()).unwrap_or_default(),
+            last_modified: 0,
+            cache_dependencies: self.depends_on.clone(),
+            compiler_caches: self.compiler_caches.clone(),
+            hooks: self.hooks.clone(),
+        }
+    }
+
+    /// When to poll the target's runtime, backing off from its own interval
+    /// while nothing changes
+    pub fn pid_poll_backoff(&self, global: &GlobalConfig) -> PollBackoff {
+        let base = self.pid_poll_interval_seconds.unwrap_or(global.daemon_interval_seconds).max(1);
+        PollBackoff::new(
+            Duration::from_secs(base),
+            Duration::from_secs(global.pid_poll_max_interval_seconds),
+        )
+    }
+
+    /// How the target's runtime processes are found, by `runtime` name,
+    /// `detect` socket or both
+    pub fn runtime_lookup(&self) -> Option<RuntimeLookup> {
+        let lookup = match (&self.runtime, &self.detect) {
+            (Some(runtime), _) => RuntimeLookup::new(runtime),
+            (None, Some(socket)) => RuntimeLookup::by_socket(socket.clone()),
+            (None, None) => return None,
+        };
+        Some(match &self.detect {
+            Some(socket) => lookup.socket(socket.clone()),
+            None => lookup,
+        })
+    }
+
+    /// Watcher of the target's runtime processes, if it has one to watch
+    pub fn runtime_watcher(&self, name: &str, global: &GlobalConfig) -> Option<RuntimeWatcher> {
+        let lookup = self.runtime_lookup().filter(|_| self.pid_watch && self.enabled)?;
+        Some(RuntimeWatcher::new(name, lookup).with_poll_backoff(self.pid_poll_backoff(global)))
+    }
+
+    /// The absolute path when loaded from a file, else `path` as written
+    pub fn root(&self) -> Option<PathBuf> {
+        self.resolved_path.clone().or_else(|| self.path.as_ref().map(PathBuf::from))
+    }
+}
+
+/// Expand `${workspace_root}`, `${home}` and `${ENV_VAR}` in a target path
+/// and make it absolute against `base`. `${env:...}` is left to secret
+/// resolution, which runs first.
+pub fn expand_path(raw: &str, base: &Path, workspace_root: &Path) -> Result<PathBuf, ConfigError> {
+    let mut expanded = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| ConfigError::Parse(format!("Unclosed ${{ in path {}", raw)))?;
+        let value = match &rest[start + 2..end] {
+            "workspace_root" => workspace_root.to_string_lossy().into_owned(),
+            "home" => std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .map_err(|_| ConfigError::Parse(format!("${{home}} in {} but HOME is not set", raw)))?,
+            name => std::env::var(name)
+                .map_err(|_| ConfigError::Parse(format!("${{{}}} in {} is not set", name, raw)))?,
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+
+    Ok(normalize_path(&base.join(expanded)))
+}
+
+/// Lexically resolve `.` and `..`, without touching the filesystem
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+impl BustcallConfig {
+    /// Load a config file in the current layout or either older one: the
+    /// `[global]`/`[target.*]` layout and the
+    /// `[daemon]`/`[notifications]`/`[monitoring]`/`[targets.*]` layout. Old
+    /// section names are accepted as serde aliases and every section has
+    /// defaults, so both deserialize into the same schema. JSON and YAML
+    /// files use the same layout.
+    ///
+    /// Included fragments are merged in; see [`load_layers`]. Secret
+    /// references are resolved; see [`crate::core::secrets`].
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Self::load_with_profile(path, None)
+    }
+
+    /// As [`Self::load_from_file`], with `[profile.<profile>]` merged over
+    /// the file
+    pub fn load_with_profile<P: AsRef<Path>>(path: P, profile: Option<&str>) -> Result<Self, ConfigError> {
+        let layers = load_layers(path.as_ref())?;
+        let mut table = merge_layers(&layers);
+        if let Some(profile) = profile {
+            apply_profile(&mut table, profile)?;
+        }
+        let mut config = Self::from_table(table)?;
+        config.resolve_paths(path.as_ref(), &layers)?;
+        Ok(config)
+    }
+
+    /// Set `resolved_path` of every target, relative paths being taken from
+    /// the directory of the layer that defines the target
+    pub fn resolve_paths(&mut self, file: &Path, layers: &[(PathBuf, Table)]) -> Result<(), ConfigError> {
+        let cwd = std::env::current_dir()?;
+        let dir_of = |file: &Path| normalize_path(&cwd.join(file.parent().unwrap_or_else(|| Path::new(""))));
+        let root_dir = dir_of(file);
+        let workspace_root = match &self.global.workspace_root {
+            Some(raw) => expand_path(raw, &root_dir, &root_dir)?,
+            None => root_dir.clone(),
+        };
+
+        for (name, target) in &mut self.target {
+            let Some(raw) = &target.path else {
+                continue;
+            };
+            // Targets only defined by a profile belong to the root file
+            let defined_in = layers
+                .iter()
+                .find(|(_, table)| table.get("target").and_then(|targets| targets.get(name)).is_some())
+                .map_or(root_dir.clone(), |(layer, _)| dir_of(layer));
+            target.resolved_path = Some(expand_path(raw, &defined_in, &workspace_root)?);
+        }
+        Ok(())
+    }
+
+    /// Deserialize a migrated, merged tree, resolving its secret references
+    pub fn from_table(mut table: Table) -> Result<Self, ConfigError> {
+        let secrets = resolve_secrets(&mut table)?;
+        let mut config: Self = Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| ConfigError::Parse(e.to_string()))?;
+        config.secrets = secrets;
+        Ok(config)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
+        let mut table: Table = ConfigFormat::Toml.parse(content)?;
+        migrate(&mut table)?;
+        Self::from_table(table)
+    }
+
+    /// Write the config in the current layout, migrating older files. The
+    /// format follows the extension as for loading. A config holding
+    /// resolved secrets is refused, so they never end up on disk.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
+        let path = path.as_ref();
+        if !self.secrets.is_empty() {
+            return Err(ConfigError::Parse(format!(
+                "Refusing to write resolved secrets ({}) to {}",
+                self.secrets.iter().cloned().collect::<Vec<_>>().join(", "),
+                path.display()
+            )));
+        }
+        let content = ConfigFormat::from_path(path).render(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// The config as a TOML tree with secrets replaced by
+    /// [`crate::core::secrets::REDACTED`], for display
+    pub fn redacted(&self) -> Result<Table, ConfigError> {
+        let mut table = match Value::try_from(self).map_err(|e| ConfigError::Parse(e.to_string()))? {
+            Value::Table(table) => table,
+            _ => return Err(ConfigError::Parse("Config did not serialize to a table".to_string())),
+        };
+        redact(&mut table, &self.secrets);
+        Ok(table)
+    }
+
+    /// Bind the enabled targets with their dependencies and apply the
+    /// `[cache]` settings. Fails on a dependency cycle.
+    pub fn configure_cache(&self, cache: &DimensionalCacheManager) -> Result<(), ConfigError> {
+        let mut names: Vec<&String> = self.target.iter().filter(|(_, target)| target.enabled).map(|(name, _)| name).collect();
+        names.sort();
+        for name in names {
+            cache
+                .bind_model(name, self.target[name].model_binding())
+                .map_err(|e| ConfigError::Parse(e.to_string()))?;
+        }
+        cache.set_limits(self.cache_limits());
+        cache.set_cascade(self.cache.cascade);
+        cache.set_executors(self.cache.executors.then(|| Arc::new(BustExecutors::builtin())));
+        cache.set_bus(open_bus(self.cache.bus, self.cache.bus_url.as_deref()).map_err(|e| ConfigError::Parse(e.to_string()))?);
+        let mut sync = cache.bus_sync();
+        sync.channel = self.cache.bust_channel.clone();
+        sync.subscribe = self.cache.subscribe_channels.clone();
+        if let Some(node_id) = &self.cache.node_id {
+            sync.node_id = node_id.clone();
+        }
+        cache.set_bus_sync(sync);
+        cache.set_audit_log(self.cache.audit_log.as_ref().map(AuditLog::new));
+        cache.set_tiers(self.cache_tiers()?);
+        Ok(())
+    }
+
+    /// Tiers of `[cache] spill_dir` and `remote_tier`, none without a
+    /// `spill_dir`
+    pub fn cache_tiers(&self) -> Result<Option<CacheTiers>, ConfigError> {
+        let Some(dir) = &self.cache.spill_dir else {
+            if self.cache.remote_tier.is_some() {
+                return Err(ConfigError::Parse("[cache] remote_tier needs spill_dir".to_string()));
+            }
+            return Ok(None);
+        };
+        let remote = self
+            .cache
+            .remote_tier
+            .as_deref()
+            .map(open_remote_tier)
+            .transpose()
+            .map_err(|e| ConfigError::Parse(e.to_string()))?;
+        Ok(Some(CacheTiers {
+            disk: Arc::new(DiskTier::new(dir)),
+            remote,
+        }))
+    }
+
+    /// Warm the enabled targets with the entries matching their `warm`
+    /// patterns, then with those in `[cache] warm_snapshot`. Call after
+    /// `configure_cache` bound them.
+    pub fn warm_cache(&self, cache: &DimensionalCacheManager) -> Result<Vec<WarmReport>, ConfigError> {
+        let mut reports = Vec::new();
+        let prebuild = self.cache.prebuild_on_warm;
+        let mut names: Vec<&String> = self
+            .target
+            .iter()
+            .filter(|(_, target)| target.enabled && !target.warm.is_empty())
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        for name in names {
+            let target = &self.target[name];
+            let root = target.root().unwrap_or_else(|| PathBuf::from("."));
+            let mut entries = Vec::new();
+            for pattern in &target.warm {
+                let full = root.join(pattern);
+                let matches = glob::glob(&full.to_string_lossy())
+                    .map_err(|e| ConfigError::Parse(format!("Invalid warm pattern {} of {}: {}", pattern, name, e)))?;
+                entries.extend(matches.filter_map(|entry| entry.ok()).map(|path| path.to_string_lossy().into_owned()));
+            }
+            reports.push(cache.warm(name, &entries, prebuild).map_err(|e| ConfigError::Parse(e.to_string()))?);
+        }
+        if let Some(path) = &self.cache.warm_snapshot {
+            match CacheSnapshot::load(path) {
+                Ok(snapshot) => {
+                    reports.extend(cache.warm_from_snapshot(&snapshot, prebuild).map_err(|e| ConfigError::Parse(e.to_string()))?)
+                }
+                // A first start has no snapshot yet
+                Err(e) => log::warn!("Cache not warmed from {}: {}", path, e),
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Rebuild commands of the enabled targets, for `RebuildExecutor::new`
+    pub fn rebuild_commands(&self) -> HashMap<String, RebuildCommand> {
+        self.target
+            .iter()
+            .filter(|(_, target)| target.enabled)
+            .filter_map(|(name, target)| {
+                let command = RebuildCommand {
+                    command: target.rebuild_command.clone()?,
+                    cwd: target.root(),
+                    sandbox: target.schedule.sandbox.clone(),
+                };
+                Some((name.clone(), command))
+            })
+            .collect()
+    }
+
+    /// `[cache]` bounds with the TTLs and weights of the enabled targets,
+    /// for `DimensionalCacheManager::with_limits`
+    pub fn cache_limits(&self) -> CacheLimits {
+        let enabled = || self.target.iter().filter(|(_, target)| target.enabled);
+        CacheLimits {
+            max_entries: self.cache.max_entries,
+            max_bytes: self.cache.max_bytes,
+            ttl_seconds: enabled()
+                .filter_map(|(name, target)| Some((name.clone(), target.cache_ttl_seconds?)))
+                .collect(),
+            weights: enabled().map(|(name, target)| (name.clone(), target.model_weights())).collect(),
+        }
+    }
+
+    /// The built-in recovery actions over `cache` and the targets' restart
+    /// and rebuild commands, plus the `[recovery.actions]` commands
+    pub fn recovery_actions(&self, cache: Arc<DimensionalCacheManager>) -> RecoveryActions {
+        let processes = ProcessManager::new().with_restart_targets(&self.target_schedules());
+        let rebuilds = RebuildExecutor::new(self.rebuild_commands(), self.cache.rebuild_concurrency);
+        RecoveryActions::builtin(cache, Arc::new(processes), Arc::new(rebuilds)).with_config(&self.recovery)
+    }
+
+    /// Self-healing recovering with [`Self::recovery_actions`] by the
+    /// `[recovery]` chains, checking the health of the enabled targets and
+    /// escalating open circuits through `[notify]`
+    pub fn self_healing(&self, cache: Arc<DimensionalCacheManager>) -> SelfHealingArchitecture {
+        let recovery = &self.recovery;
+        let monitors = match recovery.health_interval_seconds {
+            0 => Vec::new(),
+            seconds => self
+                .target
+                .iter()
+                .filter(|(_, target)| target.enabled)
+                .map(|(name, _)| {
+                    HealthMonitor::new(
+                        name,
+                        Duration::from_secs(seconds),
+                        recovery.health_threshold,
+                        recovery.unhealthy_checks,
+                    )
+                })
+                .collect(),
+        };
+        let healing = SelfHealingArchitecture::new()
+            .with_actions(self.recovery_actions(cache))
+            .with_recovery(recovery.clone())
+            .with_health_monitors(monitors);
+        match NotificationManager::from_config(&self.notify) {
+            Ok(notifications) => healing.with_notifications(Arc::new(notifications)),
+            Err(e) => {
+                log::warn!("Recovery escalations disabled: {}", e);
+                healing
+            }
+        }
+    }
+
+    /// Probe sampling the health of the enabled targets, their runtimes'
+    /// processes included
+    pub fn health_probe(&self, cache: Arc<DimensionalCacheManager>) -> HealthProbe {
+        self.target
+            .iter()
+            .filter(|(_, target)| target.enabled)
+            .filter_map(|(name, target)| Some((name, target.runtime_lookup()?)))
+            .fold(HealthProbe::new(cache), |probe, (name, lookup)| probe.with_runtime(name, lookup))
+    }
+
+    /// Schedules of the enabled targets, for `BustScheduler::from_targets` and
+    /// friends
+    pub fn target_schedules(&self) -> HashMap<String, TargetSchedule> {
+        self.target
+            .iter()
+            .filter(|(_, target)| target.enabled)
+            .map(|(name, target)| (name.clone(), target.schedule.clone()))
+            .collect()
+    }
+}
+
+/// A config file and everything it includes, in merge order: each file's
+/// fragments in path order, depth first, then the file itself, so a file
+/// overrides the fragments it includes. Sections are in their current names
+/// and `include` is removed. A target may only be defined in one file.
+pub fn load_layers(path: &Path) -> Result<Vec<(PathBuf, Table)>, ConfigError> {
+    let mut layers = Vec::new();
+    collect_layers(path, &mut Vec::new(), &mut layers)?;
+
+    let mut owners: HashMap<&str, &Path> = HashMap::new();
+    for (file, table) in &layers {
+        let Some(Value::Table(targets)) = table.get("target") else {
+            continue;
+        };
+        for name in targets.keys() {
+            if let Some(first) = owners.insert(name, file) {
+                return Err(ConfigError::Parse(format!(
+                    "Target {} is defined in both {} and {}",
+                    name,
+                    first.display(),
+                    file.display()
+                )));
+            }
+        }
+    }
+    Ok(layers)
+}
+
+/// Deep-merge layers from [`load_layers`]; later layers win
+pub fn merge_layers(layers: &[(PathBuf, Table)]) -> Table {
+    let mut merged = Table::new();
+    for (_, table) in layers {
+        merge_table(&mut merged, table.clone());
+    }
+    merged
+}
+
+/// Merge `[profile.<name>]` over the rest of `table`. Returns the
+/// overrides that were applied, in current section names.
+pub fn apply_profile(table: &mut Table, name: &str) -> Result<Table, ConfigError> {
+    let profiles = table.get("profile").and_then(Value::as_table);
+    let Some(overrides) = profiles.and_then(|profiles| profiles.get(name)).and_then(Value::as_table) else {
+        let mut defined: Vec<&str> = profiles.map(|p| p.keys().map(String::as_str).collect()).unwrap_or_default();
+        defined.sort();
+        return Err(ConfigError::Parse(format!(
+            "Unknown profile {} (defined: {})",
+            name,
+            if defined.is_empty() { "none".to_string() } else { defined.join(", ") }
+        )));
+    };
+
+    let overrides = canonical_sections(overrides.clone());
+    merge_table(table, overrides.clone());
+    Ok(overrides)
+}
+
+fn merge_table(into: &mut Table, from: Table) {
+    for (key, value) in from {
+        match value {
+            Value::Table(table) if into.get(&key).is_some_and(Value::is_table) => {
+                if let Some(Value::Table(existing)) = into.get_mut(&key) {
+                    merge_table(existing, table);
+                }
+            }
+            value => {
+                into.insert(key, value);
+            }
+        }
+    }
+}
+
+fn collect_layers(path: &Path, stack: &mut Vec<PathBuf>, layers: &mut Vec<(PathBuf, Table)>) -> Result<(), ConfigError> {
+    let canonical = std::fs::canonicalize(path)?;
+    if stack.contains(&canonical) {
+        return Err(ConfigError::Parse(format!("{} includes itself", path.display())));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let mut table: Table = ConfigFormat::from_path(path).parse(&content)?;
+    for step in migrate(&mut table).map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))? {
+        log::info!("🔧 Migrated {} in memory: {}; run `bustcall config migrate` to update it", path.display(), step);
+    }
+    let patterns: Vec<String> = match table.remove("include") {
+        Some(value) => value
+            .try_into()
+            .map_err(|e: toml::de::Error| ConfigError::Parse(format!("{}: include: {}", path.display(), e)))?,
+        None => Vec::new(),
+    };
+
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut fragments = BTreeSet::new();
+    for pattern in &patterns {
+        fragments.extend(expand_include(base, pattern)?);
+    }
+
+    stack.push(canonical);
+    for fragment in &fragments {
+        collect_layers(fragment, stack, layers)?;
+    }
+    stack.pop();
+
+    layers.push((path.to_path_buf(), table));
+    Ok(())
+}
+
+/// Files matching `pattern`; a pattern without wildcards must exist
+fn expand_include(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, ConfigError> {
+    let full = base.join(pattern);
+    let full = full.to_string_lossy();
+    let matches: Vec<PathBuf> = glob::glob(&full)
+        .map_err(|e| ConfigError::Parse(format!("Invalid include pattern {}: {}", pattern, e)))?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+
+    if matches.is_empty() && glob::Pattern::escape(pattern) == pattern {
+        return Err(ConfigError::Parse(format!("Included file {} does not exist", full)));
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loads_global_target_layout() {
+        let config = BustcallConfig::from_toml(
+            r#"
+            [global]
+            self_healing = false
+            supervisor_mode = true
+            default_max_retries = 5
+            daemon_interval_seconds = 10
+
+            [target.node]
+            path = "./node_modules"
+            runtime = "node"
+            pid_watch = true
+            enabled = true
+            language_priority = 0.8
+            dependency_impact = 0.9
+            build_cost = 0.7
+            critical_path = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(!config.global.self_healing);
+        assert_eq!(config.global.daemon_interval_seconds, 10);
+        assert_eq!(config.target["node"].runtime.as_deref(), Some("node"));
+        assert!(config.target["node"].model_weights().critical_path);
+        assert!(config.notify.enabled);
+    }
+
+    #[test]
+    fn test_pid_poll_intervals_fall_back_to_the_daemon_interval() {
+        let config = BustcallConfig::from_toml(
+            r#"
+            [global]
+            daemon_interval_seconds = 10
+            pid_poll_max_interval_seconds = 40
+
+            [target.node]
+            runtime = "node"
+            pid_poll_interval_seconds = 2
+
+            [target.python]
+            runtime = "python"
+
+            [target.docs]
+            path = "docs"
+            "#,
+        )
+        .unwrap();
+
+        let mut node = config.target["node"].pid_poll_backoff(&config.global);
+        assert_eq!(node.interval(), Duration::from_secs(2));
+        let now = std::time::Instant::now();
+        for _ in 0..6 {
+            node.record(now, false);
         }
+        assert_eq!(node.interval(), Duration::from_secs(40));
+
+        assert_eq!(config.target["python"].pid_poll_backoff(&config.global).interval(), Duration::from_secs(10));
+        assert!(config.target["python"].runtime_watcher("python", &config.global).is_some());
+        assert!(config.target["docs"].runtime_watcher("docs", &config.global).is_none());
+    }
+
+    #[test]
+    fn test_runtimes_detected_by_socket() {
+        let config = BustcallConfig::from_toml(
+            r#"
+            [target.web]
+            detect = { port = 3000 }
+
+            [target.api]
+            runtime = "node"
+            detect = { unix_socket = "/tmp/app.sock" }
+            "#,
+        )
+        .unwrap();
+
+        let web = config.target["web"].runtime_lookup().unwrap();
+        assert_eq!(web.socket, Some(SocketDetect::Port(3000)));
+        assert_eq!(web.describe(), "port 3000");
+
+        let api = config.target["api"].runtime_lookup().unwrap();
+        assert_eq!(api.pattern, "node");
+        assert_eq!(api.socket, Some(SocketDetect::UnixSocket(PathBuf::from("/tmp/app.sock"))));
+        assert!(config.target["api"].runtime_watcher("api", &config.global).is_some());
+    }
+
+    #[test]
+    fn test_loads_daemon_notifications_targets_layout() {
+        let config = BustcallConfig::from_toml(
+            r#"
+            [daemon]
+            bind_address = "0.0.0.0"
+            port = 9000
+            log_level = "debug"
+            pid_file = "/run/bustcall.pid"
+
+            [notifications]
+            enabled = true
+            channels = ["console", "syslog"]
+
+            [monitoring]
+            interval_seconds = 2
+            processes = ["node"]
+
+            [targets.node]
+            quiet_hours = "22:00-06:00"
+            cpu_quota = 150.0
+
+            [targets.python]
+            enabled = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.daemon.port, 9000);
+        assert_eq!(config.notify.channels, vec!["console", "syslog"]);
+        assert_eq!(config.monitoring.processes, vec!["node"]);
+        assert_eq!(config.target["node"].schedule.resources.cpu_quota, Some(150.0));
+
+        let schedules = config.target_schedules();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules["node"].quiet_hours.as_deref(), Some("22:00-06:00"));
+
+        // Saving writes the current section names
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(saved.contains("[notify]") && saved.contains("[target.node]"));
+    }
+
+    #[test]
+    fn test_json_and_yaml_match_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("bustcall.toml");
+        let json_path = dir.path().join("bustcall.json");
+        let yaml_path = dir.path().join("bustcall.yml");
+
+        std::fs::write(&toml_path, "[global]\ndefault_max_retries = 7\n\n[target.node]\nruntime = \"node\"\ncpu_quota = 150.0\n").unwrap();
+        std::fs::write(&json_path, r#"{"global": {"default_max_retries": 7}, "target": {"node": {"runtime": "node", "cpu_quota": 150.0}}}"#).unwrap();
+        std::fs::write(&yaml_path, "global:\n  default_max_retries: 7\ntarget:\n  node:\n    runtime: node\n    cpu_quota: 150.0\n").unwrap();
+
+        for path in [&toml_path, &json_path, &yaml_path] {
+            let config = BustcallConfig::load_from_file(path).unwrap();
+            assert_eq!(config.global.default_max_retries, 7, "{}", path.display());
+            assert_eq!(config.target["node"].schedule.resources.cpu_quota, Some(150.0));
+        }
+
+        // Same validation in every format: unknown enum values are rejected
+        std::fs::write(&json_path, r#"{"target": {"node": {"quiet_hours_action": "sometimes"}}}"#).unwrap();
+        assert!(BustcallConfig::load_from_file(&json_path).is_err());
+    }
+
+    #[test]
+    fn test_includes_merge_fragments_and_reject_duplicate_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("bustcall.toml");
+        std::fs::create_dir(dir.path().join("targets")).unwrap();
+        std::fs::write(
+            &main,
+            "include = [\"targets/*.toml\"]\n\n[global]\ndefault_max_retries = 7\n\n[target.api]\nruntime = \"python\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("targets/web.toml"), "[target.web]\nruntime = \"node\"\n").unwrap();
+        // Fragments may set other sections too; the including file wins
+        std::fs::write(
+            dir.path().join("targets/worker.toml"),
+            "[global]\ndefault_max_retries = 1\nself_healing = false\n\n[targets.worker]\nruntime = \"go\"\n",
+        )
+        .unwrap();
+
+        let config = BustcallConfig::load_from_file(&main).unwrap();
+        let mut names: Vec<&String> = config.target.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["api", "web", "worker"]);
+        assert_eq!(config.global.default_max_retries, 7);
+        assert!(!config.global.self_healing);
+        assert!(config.include.is_empty());
+
+        std::fs::write(dir.path().join("targets/web-copy.toml"), "[target.web]\nruntime = \"deno\"\n").unwrap();
+        let error = BustcallConfig::load_from_file(&main).unwrap_err().to_string();
+        assert!(error.contains("Target web is defined in both"), "{}", error);
+    }
+
+    #[test]
+    fn test_profile_overrides_base_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bustcall.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [global]
+            daemon_interval_seconds = 30
+
+            [target.web]
+            runtime = "node"
+            build_cost = 0.2
+
+            [profile.ci.global]
+            daemon_interval_seconds = 1
+
+            [profile.ci.targets.web]
+            build_cost = 0.9
+            "#,
+        )
+        .unwrap();
+
+        let base = BustcallConfig::load_from_file(&path).unwrap();
+        assert_eq!(base.global.daemon_interval_seconds, 30);
+        assert_eq!(base.target["web"].build_cost, 0.2);
+
+        let ci = BustcallConfig::load_with_profile(&path, Some("ci")).unwrap();
+        assert_eq!(ci.global.daemon_interval_seconds, 1);
+        assert_eq!(ci.target["web"].build_cost, 0.9);
+        assert_eq!(ci.target["web"].runtime.as_deref(), Some("node"));
+
+        let error = BustcallConfig::load_with_profile(&path, Some("prod")).unwrap_err();
+        assert_eq!(error.to_string(), "Parse error: Unknown profile prod (defined: ci)");
+    }
+
+    #[test]
+    fn test_target_paths_resolve_against_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("packages")).unwrap();
+        std::fs::write(
+            root.join("bustcall.toml"),
+            "include = [\"packages/*.toml\"]\n\n[target.node]\npath = \"./node_modules\"\n\n\
+             [target.cache]\npath = \"${workspace_root}/../shared/${CONFIG_TEST_CACHE_DIR}\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("packages/web.toml"), "[target.web]\npath = \"web/dist\"\n").unwrap();
+        std::env::set_var("CONFIG_TEST_CACHE_DIR", "ccache");
+
+        let config = BustcallConfig::load_from_file(root.join("bustcall.toml")).unwrap();
+        let resolved = |name: &str| config.target[name].resolved_path.clone().unwrap();
+        assert_eq!(resolved("node"), root.join("node_modules"));
+        assert_eq!(resolved("web"), root.join("packages/web/dist"));
+        assert_eq!(resolved("cache"), root.parent().unwrap().join("shared/ccache"));
+        // Saved as written
+        assert_eq!(config.target["node"].path.as_deref(), Some("./node_modules"));
+
+        assert!(expand_path("${CONFIG_TEST_UNSET}/x", root, root).is_err());
+    }
+
+    #[test]
+    fn test_resolved_secrets_are_redacted_and_never_saved() {
+        std::env::set_var("CONFIG_TEST_REDIS_URL", "redis://:hunter2@cache/");
+        let config = BustcallConfig::from_toml(
+            "[daemon.leader_election]\nredis_url = \"${env:CONFIG_TEST_REDIS_URL}\"\nlock_key = \"l\"\n\
+             forward_channel = \"f\"\nlease_ms = 1000\nnode_id = \"n\"\n",
+        )
+        .unwrap();
+
+        let leader = config.daemon.leader_election.as_ref().unwrap();
+        assert_eq!(leader.redis_url, "redis://:hunter2@cache/");
+        let shown = toml::to_string_pretty(&config.redacted().unwrap()).unwrap();
+        assert!(!shown.contains("hunter2") && shown.contains(crate::core::secrets::REDACTED));
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(config.save_to_file(dir.path().join("bustcall.toml")).is_err());
+        assert!(!dir.path().join("bustcall.toml").exists());
     }
 }
@@ -0,0 +1,211 @@
+//! Structural comparison of two configs
+//!
+//! `bustcall config diff` compares what two configs do rather than how they
+//! are written: both sides are loaded, migrated and merged with their
+//! includes first, so reordering keys, moving a target into a fragment or
+//! upgrading `config_version` shows no change. Secrets are compared
+//! redacted and never printed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use toml::{Table, Value};
+
+use crate::core::config::{BustcallConfig, ConfigError, ConfigFormat};
+use crate::core::config_migrate::migrate;
+use crate::core::remote_config::{is_remote, RemoteConfig};
+
+/// A key whose value differs; `None` on the side that lacks it
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyChange {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    pub added_targets: Vec<String>,
+    pub removed_targets: Vec<String>,
+    /// Changed keys outside the added and removed targets, by key
+    pub changes: Vec<KeyChange>,
+}
+
+impl ConfigDiff {
+    /// Compare two configs as loaded
+    pub fn between(old: &BustcallConfig, new: &BustcallConfig) -> Result<Self, ConfigError> {
+        Ok(Self::between_tables(&old.redacted()?, &new.redacted()?))
+    }
+
+    /// Compare two redacted config trees, as returned by
+    /// [`BustcallConfig::redacted`]
+    pub fn between_tables(old: &Table, new: &Table) -> Self {
+        let targets = |table: &Table| -> Vec<String> {
+            table
+                .get("target")
+                .and_then(Value::as_table)
+                .map(|targets| targets.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        let (old_targets, new_targets) = (targets(old), targets(new));
+        let added_targets: Vec<String> = new_targets.iter().filter(|t| !old_targets.contains(t)).cloned().collect();
+        let removed_targets: Vec<String> = old_targets.iter().filter(|t| !new_targets.contains(t)).cloned().collect();
+
+        let mut before = BTreeMap::new();
+        flatten("", &Value::Table(old.clone()), &mut before);
+        let mut after = BTreeMap::new();
+        flatten("", &Value::Table(new.clone()), &mut after);
+
+        let in_whole_target = |key: &str| {
+            added_targets
+                .iter()
+                .chain(&removed_targets)
+                .any(|target| key.starts_with(&format!("target.{}.", target)))
+        };
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        let changes = keys
+            .into_iter()
+            .filter(|key| !in_whole_target(key))
+            .filter(|key| before.get(*key) != after.get(*key))
+            .map(|key| KeyChange {
+                key: key.clone(),
+                before: before.get(key).cloned(),
+                after: after.get(key).cloned(),
+            })
+            .collect();
+
+        Self {
+            added_targets,
+            removed_targets,
+            changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_targets.is_empty() && self.removed_targets.is_empty() && self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for target in &self.added_targets {
+            writeln!(f, "+ target {}", target)?;
+        }
+        for target in &self.removed_targets {
+            writeln!(f, "- target {}", target)?;
+        }
+        for change in &self.changes {
+            match (&change.before, &change.after) {
+                (Some(before), Some(after)) => writeln!(f, "~ {} = {} -> {}", change.key, before, after)?,
+                (None, Some(after)) => writeln!(f, "+ {} = {}", change.key, after)?,
+                (Some(before), None) => writeln!(f, "- {} = {}", change.key, before)?,
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Leaf values by dotted key. Arrays are compared whole.
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&key, value, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Load one side of a diff: a config file path, a remote config URL, or
+/// `<revision>:<path>` for the file as committed in git. A file that exists
+/// wins over the revision reading. Includes are not followed for revisions.
+pub fn load_config_source(spec: &str) -> Result<BustcallConfig, ConfigError> {
+    if is_remote(spec) {
+        let remote = RemoteConfig::new(spec)?;
+        remote.fetch()?;
+        return remote.load(None);
+    }
+    if Path::new(spec).exists() {
+        return BustcallConfig::load_from_file(spec);
+    }
+    let Some((_, path)) = spec.split_once(':') else {
+        return BustcallConfig::load_from_file(spec);
+    };
+
+    let output = Command::new("git").args(["show", spec]).output()?;
+    if !output.status.success() {
+        return Err(ConfigError::Parse(format!(
+            "git show {}: {}",
+            spec,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let content = String::from_utf8_lossy(&output.stdout);
+    let mut table: Table = ConfigFormat::from_path(Path::new(path)).parse(&content)?;
+    migrate(&mut table)?;
+    table.remove("include");
+    BustcallConfig::from_table(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_targets_and_keys() {
+        let old = BustcallConfig::from_toml(
+            r#"
+            [global]
+            daemon_interval_seconds = 5
+
+            [target.web]
+            path = "web"
+            runtime = "node"
+
+            [target.api]
+            path = "api"
+            "#,
+        )
+        .unwrap();
+        // Unversioned file with the old section name: same meaning
+        let new = BustcallConfig::from_toml(
+            r#"
+            [global]
+            daemon_interval_seconds = 2
+
+            [targets.web]
+            runtime = "node"
+            path = "web"
+            severity_overrides = { "*.lock" = "critical" }
+
+            [targets.worker]
+            path = "worker"
+            "#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::between(&old, &new).unwrap();
+        assert_eq!(diff.added_targets, vec!["worker"]);
+        assert_eq!(diff.removed_targets, vec!["api"]);
+        let rendered = diff.to_string();
+        assert!(rendered.contains("~ global.daemon_interval_seconds = 5 -> 2"), "{}", rendered);
+        assert!(rendered.contains("+ target.web.severity_overrides.*.lock = \"critical\""), "{}", rendered);
+        assert!(!rendered.contains("target.worker.path"), "{}", rendered);
+
+        assert!(ConfigDiff::between(&old, &old).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+//! Config file versions and the migrations between them
+//!
+//! Every file carries a `config_version`; files without one predate it and
+//! are version 1. On load each file is upgraded step by step to
+//! [`CURRENT_CONFIG_VERSION`] before it is deserialized, so a schema change
+//! only needs a migration here instead of serde aliases everywhere.
+//! `bustcall config migrate` writes the upgrade back, keeping a backup.
+
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::core::config::{ConfigError, ConfigFormat};
+
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+struct Migration {
+    /// Upgrades files of this version to the next
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Table),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "rename [notifications] to [notify] and [targets.*] to [target.*]",
+    apply: rename_sections,
+}];
+
+fn rename_sections(table: &mut Table) {
+    *table = canonical_sections(std::mem::take(table));
+}
+
+/// Rename the sections older files used to their current names
+pub(crate) fn canonical_sections(mut table: Table) -> Table {
+    for (old, new) in [("notifications", "notify"), ("targets", "target")] {
+        if let Some(section) = table.remove(old) {
+            table.entry(new).or_insert(section);
+        }
+    }
+    table
+}
+
+/// Version `table` was written for
+pub fn config_version(table: &Table) -> Result<u32, ConfigError> {
+    match table.get("config_version") {
+        None => Ok(1),
+        Some(Value::Integer(version)) if *version >= 1 => Ok(*version as u32),
+        Some(other) => Err(ConfigError::Parse(format!("Invalid config_version {}", other))),
+    }
+}
+
+/// Upgrade `table` to the current version in place. Returns what each
+/// applied migration did; empty when it was already current.
+pub fn migrate(table: &mut Table) -> Result<Vec<&'static str>, ConfigError> {
+    let version = config_version(table)?;
+    if version > CURRENT_CONFIG_VERSION {
+        return Err(ConfigError::Parse(format!(
+            "config_version {} is newer than this bustcall supports ({}); upgrade bustcall",
+            version, CURRENT_CONFIG_VERSION
+        )));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+        (migration.apply)(table);
+        applied.push(migration.description);
+    }
+    table.insert("config_version".to_string(), Value::Integer(CURRENT_CONFIG_VERSION as i64));
+    Ok(applied)
+}
+
+#[derive(Debug)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub applied: Vec<&'static str>,
+    /// Where the original was copied to, unless this was a dry run or
+    /// nothing changed
+    pub backup: Option<PathBuf>,
+    /// `-`/`+`/` ` prefixed lines
+    pub diff: Vec<String>,
+}
+
+/// Upgrade the file at `path` and, unless `dry_run`, write it back after
+/// copying the original to `<path>.v<version>.bak`. Only this file is
+/// migrated, not the fragments it includes.
+pub fn migrate_file(path: &Path, dry_run: bool) -> Result<MigrationReport, ConfigError> {
+    let content = std::fs::read_to_string(path)?;
+    let format = ConfigFormat::from_path(path);
+    let mut table: Table = format.parse(&content)?;
+    let from_version = config_version(&table)?;
+    let applied = migrate(&mut table)?;
+
+    if applied.is_empty() {
+        return Ok(MigrationReport {
+            from_version,
+            applied,
+            backup: None,
+            diff: Vec::new(),
+        });
+    }
+
+    let migrated = format.render(&table)?;
+    let diff = line_diff(&content, &migrated);
+    let mut backup = None;
+    if !dry_run {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(format!(".v{}.bak", from_version));
+        let backup_path = PathBuf::from(name);
+        std::fs::copy(path, &backup_path)?;
+        std::fs::write(path, migrated)?;
+        backup = Some(backup_path);
+    }
+
+    Ok(MigrationReport {
+        from_version,
+        applied,
+        backup,
+        diff,
+    })
+}
+
+/// Line diff of `old` and `new` by longest common subsequence
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j]: LCS length of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            diff.push(format!("+ {}", new[j]));
+            j += 1;
+        } else {
+            diff.push(format!("- {}", old[i]));
+            i += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_unversioned_file_with_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bustcall.toml");
+        let original = "[notifications]\nenabled = false\n\n[targets.node]\nruntime = \"node\"\n";
+        std::fs::write(&path, original).unwrap();
+
+        let preview = migrate_file(&path, true).unwrap();
+        assert_eq!(preview.from_version, 1);
+        assert!(preview.backup.is_none());
+        assert!(preview.diff.contains(&"- [targets.node]".to_string()));
+        assert!(preview.diff.contains(&"+ [target.node]".to_string()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        let report = migrate_file(&path, false).unwrap();
+        let backup = report.backup.unwrap();
+        assert_eq!(backup.file_name().unwrap(), "bustcall.toml.v1.bak");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), original);
+
+        let migrated: Table = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(config_version(&migrated).unwrap(), CURRENT_CONFIG_VERSION);
+        assert!(migrated.contains_key("notify") && migrated.contains_key("target"));
+
+        // Already current: nothing to do
+        assert!(migrate_file(&path, false).unwrap().applied.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_newer_versions() {
+        let mut table: Table = "config_version = 99".parse().unwrap();
+        let error = migrate(&mut table).unwrap_err().to_string();
+        assert!(error.contains("newer than this bustcall supports"), "{}", error);
+    }
+}
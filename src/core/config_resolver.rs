@@ -0,0 +1,352 @@
+//! Layered configuration: defaults < file < profile < `BUSTCALL_*`
+//! environment < CLI
+//!
+//! Every layer is applied to the TOML tree before it is deserialized, so an
+//! override goes through exactly the same parsing as the file would. An
+//! environment variable names its key with `_` in place of `.`, e.g.
+//! `BUSTCALL_GLOBAL_DAEMON_INTERVAL_SECONDS` or `BUSTCALL_TARGET_NODE_ENABLED`;
+//! since keys contain underscores themselves, the name is matched against the
+//! keys present in the merged file and defaults. Keys that are unset there
+//! can still be given on the command line with `--set key.path=value`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::core::config::{apply_profile, load_layers, merge_layers, BustcallConfig, ConfigError, DEFAULT_CONFIG_PATH};
+
+pub const ENV_PREFIX: &str = "BUSTCALL_";
+
+/// Where the effective value of a key came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Profile(String),
+    Env(String),
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "file {}", path.display()),
+            Source::Profile(name) => write!(f, "profile {}", name),
+            Source::Env(name) => write!(f, "env {}", name),
+            Source::Flag => write!(f, "--set"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub config: BustcallConfig,
+    /// Source per dotted key path of every leaf value
+    pub provenance: BTreeMap<String, Source>,
+    /// `BUSTCALL_*` variables that matched no key
+    pub unmatched_env: Vec<String>,
+}
+
+impl ResolvedConfig {
+    /// Every effective leaf value with its source, ordered by key. Secrets
+    /// are redacted.
+    pub fn entries(&self) -> Result<Vec<(String, Value, Source)>, ConfigError> {
+        let tree = Value::Table(self.config.redacted()?);
+        let mut leaves = Vec::new();
+        flatten("", &tree, &mut leaves);
+        Ok(leaves
+            .into_iter()
+            .map(|(key, value)| {
+                let source = self.provenance.get(&key).cloned().unwrap_or(Source::Default);
+                (key, value, source)
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ConfigResolver {
+    file: Option<PathBuf>,
+    profile: Option<String>,
+    env: Vec<(String, String)>,
+    flags: Vec<String>,
+}
+
+impl ConfigResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Config file to start from; a missing file leaves the defaults
+    pub fn file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// `[profile.<name>]` of the file to merge over it
+    pub fn profile(mut self, name: &str) -> Self {
+        self.profile = Some(name.to_string());
+        self
+    }
+
+    /// `BUSTCALL_*` variables from `vars`; others are ignored
+    pub fn env_vars(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env
+            .extend(vars.into_iter().filter(|(name, _)| name.starts_with(ENV_PREFIX)));
+        self.env.sort();
+        self
+    }
+
+    pub fn process_env(self) -> Self {
+        self.env_vars(std::env::vars())
+    }
+
+    /// A `key.path=value` assignment from the command line
+    pub fn flag(mut self, assignment: &str) -> Self {
+        self.flags.push(assignment.to_string());
+        self
+    }
+
+    pub fn resolve(&self) -> Result<ResolvedConfig, ConfigError> {
+        let mut provenance = BTreeMap::new();
+
+        let mut tree = to_table(&BustcallConfig::default())?;
+        let mut layers = Vec::new();
+        if let Some(path) = self.file.as_deref().filter(|path| path.exists()) {
+            // Keys are attributed to the file or included fragment that set
+            // them, in the order the layers are merged
+            layers = load_layers(path)?;
+            for (file, written) in &layers {
+                let mut written_keys = Vec::new();
+                flatten("", &Value::Table(written.clone()), &mut written_keys);
+                for (key, _) in written_keys {
+                    provenance.insert(key, Source::File(file.clone()));
+                }
+            }
+            // Round-trip through the schema so older layouts land on the
+            // current key names
+            let file_config: BustcallConfig = Value::Table(merge_layers(&layers))
+                .try_into()
+                .map_err(|e: toml::de::Error| ConfigError::Parse(e.to_string()))?;
+            tree = to_table(&file_config)?;
+        }
+
+        if let Some(name) = &self.profile {
+            let overrides = apply_profile(&mut tree, name)?;
+            let mut profile_keys = Vec::new();
+            flatten("", &Value::Table(overrides), &mut profile_keys);
+            for (key, _) in profile_keys {
+                provenance.insert(key, Source::Profile(name.clone()));
+            }
+        }
+
+        let mut unmatched_env = Vec::new();
+        for (name, raw) in &self.env {
+            let words = name[ENV_PREFIX.len()..].to_lowercase();
+            match match_env_path(&tree, &words) {
+                Some(path) => {
+                    set_path(&mut tree, &path, raw)?;
+                    mark(&mut provenance, &tree, &path, Source::Env(name.clone()));
+                }
+                None => unmatched_env.push(name.clone()),
+            }
+        }
+
+        for assignment in &self.flags {
+            let (key, raw) = assignment
+                .split_once('=')
+                .ok_or_else(|| ConfigError::Parse(format!("Expected key=value, got {}", assignment)))?;
+            let path: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+            set_path(&mut tree, &path, raw.trim())?;
+            mark(&mut provenance, &tree, &path, Source::Flag);
+        }
+
+        // Secret references are resolved last, so overrides may use them too
+        let mut config = BustcallConfig::from_table(tree)?;
+        let file = self.file.as_deref().unwrap_or_else(|| Path::new(DEFAULT_CONFIG_PATH));
+        config.resolve_paths(file, &layers)?;
+
+        Ok(ResolvedConfig {
+            config,
+            provenance,
+            unmatched_env,
+        })
+    }
+}
+
+fn to_table(config: &BustcallConfig) -> Result<Table, ConfigError> {
+    match Value::try_from(config).map_err(|e| ConfigError::Parse(e.to_string()))? {
+        Value::Table(table) => Ok(table),
+        _ => Err(ConfigError::Parse("Config did not serialize to a table".to_string())),
+    }
+}
+
+/// Dotted paths of all leaves below `value`; arrays count as one leaf
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+/// Attribute every leaf at or below `path` to `source`
+fn mark(provenance: &mut BTreeMap<String, Source>, tree: &Table, path: &[String], source: Source) {
+    let mut value = None;
+    let mut table = Some(tree);
+    for part in path {
+        value = table.and_then(|t| t.get(part));
+        table = value.and_then(Value::as_table);
+    }
+    let mut leaves = Vec::new();
+    if let Some(value) = value {
+        flatten(&path.join("."), value, &mut leaves);
+    }
+    for (key, _) in leaves {
+        provenance.insert(key, source.clone());
+    }
+}
+
+/// Find the key path spelled by `words` (lowercase, `_`-separated) among the
+/// keys of `table`, preferring the longest key at each level
+fn match_env_path(table: &Table, words: &str) -> Option<Vec<String>> {
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort_by_key(|key| std::cmp::Reverse(key.len()));
+
+    for key in keys {
+        let spelled = key.to_lowercase().replace(['-', '.'], "_");
+        if words == spelled {
+            return Some(vec![key.clone()]);
+        }
+        let Some(rest) = words.strip_prefix(&spelled).and_then(|rest| rest.strip_prefix('_')) else {
+            continue;
+        };
+        if let Some(Value::Table(child)) = table.get(key) {
+            if let Some(mut path) = match_env_path(child, rest) {
+                path.insert(0, key.clone());
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Set `path` to `raw`, converted to the type of the value it replaces
+fn set_path(tree: &mut Table, path: &[String], raw: &str) -> Result<(), ConfigError> {
+    let (last, parents) = path
+        .split_last()
+        .ok_or_else(|| ConfigError::Parse("Empty config key".to_string()))?;
+
+    let mut table = tree;
+    for part in parents {
+        let entry = table.entry(part.clone()).or_insert_with(|| Value::Table(Table::new()));
+        table = entry
+            .as_table_mut()
+            .ok_or_else(|| ConfigError::Parse(format!("{} is not a table", path.join("."))))?;
+    }
+
+    let value = parse_value(table.get(last), raw)
+        .ok_or_else(|| ConfigError::Parse(format!("Invalid value for {}: {}", path.join("."), raw)))?;
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+fn parse_value(current: Option<&Value>, raw: &str) -> Option<Value> {
+    match current {
+        Some(Value::String(_)) => Some(Value::String(raw.to_string())),
+        Some(Value::Boolean(_)) => raw.parse().ok().map(Value::Boolean),
+        Some(Value::Integer(_)) => raw.parse().ok().map(Value::Integer),
+        Some(Value::Float(_)) => raw.parse().ok().map(Value::Float),
+        // Arrays accept TOML (`["a", "b"]`) or a comma-separated list
+        Some(Value::Array(_)) => parse_inline(raw).filter(Value::is_array).or_else(|| {
+            Some(Value::Array(
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_string()))
+                    .collect(),
+            ))
+        }),
+        Some(_) => parse_inline(raw),
+        // Unset keys take whatever the value looks like
+        None => parse_inline(raw).or_else(|| Some(Value::String(raw.to_string()))),
+    }
+}
+
+fn parse_inline(raw: &str) -> Option<Value> {
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_layers_and_provenance() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bustcall.toml");
+        std::fs::write(
+            &path,
+            "[global]\ndaemon_interval_seconds = 10\n\n[targets.node]\nruntime = \"node\"\n\n\
+             [profile.ci.global]\nsupervisor_mode = false\ndaemon_interval_seconds = 1\n",
+        )
+        .unwrap();
+
+        let resolved = ConfigResolver::new()
+            .file(&path)
+            .profile("ci")
+            .env_vars(env(&[
+                ("BUSTCALL_TARGET_NODE_ENABLED", "false"),
+                ("BUSTCALL_GLOBAL_DAEMON_INTERVAL_SECONDS", "20"),
+                ("BUSTCALL_LEVEL", "warning"),
+                ("HOME", "/root"),
+            ]))
+            .flag("global.daemon_interval_seconds=30")
+            .flag("monitoring.processes=node,python3")
+            .resolve()
+            .unwrap();
+
+        let config = &resolved.config;
+        assert_eq!(config.global.daemon_interval_seconds, 30);
+        assert!(!config.global.supervisor_mode);
+        assert!(!config.target["node"].enabled);
+        assert_eq!(config.monitoring.processes, vec!["node", "python3"]);
+        assert_eq!(resolved.unmatched_env, vec!["BUSTCALL_LEVEL"]);
+
+        let provenance = &resolved.provenance;
+        assert_eq!(provenance["global.daemon_interval_seconds"], Source::Flag);
+        assert_eq!(
+            provenance["target.node.enabled"],
+            Source::Env("BUSTCALL_TARGET_NODE_ENABLED".to_string())
+        );
+        assert_eq!(provenance["target.node.runtime"], Source::File(path.clone()));
+        assert_eq!(provenance["global.supervisor_mode"], Source::Profile("ci".to_string()));
+        assert!(!provenance.contains_key("global.self_healing"));
+    }
+
+    #[test]
+    fn test_rejects_mistyped_override() {
+        let result = ConfigResolver::new()
+            .env_vars(env(&[("BUSTCALL_GLOBAL_SELF_HEALING", "maybe")]))
+            .resolve();
+        assert!(result.is_err());
+    }
+}
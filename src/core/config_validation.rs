@@ -0,0 +1,673 @@
+//! Semantic validation of config files
+//!
+//! Parsing stops at the first error and silently drops keys it does not know.
+//! The validator instead collects every problem it can find — syntax,
+//! unknown keys, invalid severities, missing or overlapping target paths,
+//! unknown or cyclic target dependencies, weights outside `0..=1` and
+//! unknown recovery actions — each with the line it was written on where the
+//! format allows, and a suggested fix.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::core::config::{apply_profile, expand_path, load_layers, BustcallConfig, ConfigFormat};
+use crate::core::config_migrate::migrate;
+use crate::core::recovery::BUILTIN_ACTIONS;
+use crate::core::severity::SeverityRules;
+use crate::core::watch_ignore::IgnoreRules;
+use crate::core::watch_template::is_template;
+use crate::dimensional_cache::{CacheBustSeverity, DimensionalCacheManager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticLevel {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    /// Dotted key path, empty for file-level problems
+    pub key: String,
+    pub line: Option<usize>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub file: PathBuf,
+    /// Ordered by line
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.level == DiagnosticLevel::Error)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            let level = match diagnostic.level {
+                DiagnosticLevel::Warning => "warning",
+                DiagnosticLevel::Error => "error",
+            };
+            match diagnostic.line {
+                Some(line) => write!(f, "{}:{}: ", self.file.display(), line)?,
+                None => write!(f, "{}: ", self.file.display())?,
+            }
+            write!(f, "{}: {}", level, diagnostic.message)?;
+            if !diagnostic.key.is_empty() {
+                write!(f, " [{}]", diagnostic.key)?;
+            }
+            writeln!(f)?;
+            if let Some(suggestion) = &diagnostic.suggestion {
+                writeln!(f, "    help: {}", suggestion)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate the config file at `path`; relative target paths are resolved
+/// against its directory. Included fragments are checked for existence and
+/// duplicate targets, but not validated themselves.
+pub fn validate_file(path: &Path) -> std::io::Result<ValidationReport> {
+    let content = std::fs::read_to_string(path)?;
+    let format = ConfigFormat::from_path(path);
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut diagnostics = validate_str(&content, format, base);
+
+    let includes = format.parse::<Table>(&content).is_ok_and(|table| table.contains_key("include"));
+    if includes {
+        if let Err(e) = load_layers(path) {
+            let lines = toml_key_lines(&content);
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                key: "include".to_string(),
+                line: key_line(&lines, "include"),
+                message: e.to_string(),
+                suggestion: None,
+            });
+        }
+    }
+
+    Ok(ValidationReport {
+        file: path.to_path_buf(),
+        diagnostics: sorted(diagnostics),
+    })
+}
+
+pub fn validate_str(content: &str, format: ConfigFormat, base: &Path) -> Vec<Diagnostic> {
+    let lines = match format {
+        ConfigFormat::Toml => toml_key_lines(content),
+        // Keys of JSON and YAML files are reported without a line
+        ConfigFormat::Json | ConfigFormat::Yaml => HashMap::new(),
+    };
+    let line_of = |key: &str| key_line(&lines, key);
+
+    let mut written: Table = match format.parse(content) {
+        Ok(table) => table,
+        Err(e) => {
+            return vec![Diagnostic {
+                level: DiagnosticLevel::Error,
+                key: String::new(),
+                line: syntax_error_line(content, format),
+                message: e.to_string(),
+                suggestion: None,
+            }]
+        }
+    };
+    if let Err(e) = migrate(&mut written) {
+        return vec![Diagnostic {
+            level: DiagnosticLevel::Error,
+            key: "config_version".to_string(),
+            line: line_of("config_version"),
+            message: e.to_string(),
+            suggestion: None,
+        }];
+    }
+    let mut tree = Value::Table(written);
+    let mut written_leaves = Vec::new();
+    leaves("", &tree, &mut written_leaves);
+
+    let mut diagnostics = Vec::new();
+
+    // Checked up front and patched over, so every bad severity is reported
+    // and the remaining checks still run
+    for (key, value) in &written_leaves {
+        let field = key.rsplit('.').next().unwrap_or_default();
+        if field != "severity" && field != "bust" {
+            continue;
+        }
+        if value.clone().try_into::<CacheBustSeverity>().is_err() {
+            let given = value.as_str().unwrap_or_default();
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                key: key.clone(),
+                line: line_of(key),
+                message: format!("invalid severity {}", value),
+                suggestion: Some(match closest(given, &["low", "medium", "high", "critical"]) {
+                    Some(name) => format!("did you mean \"{}\"?", name),
+                    None => "use one of low, medium, high, critical".to_string(),
+                }),
+            });
+            replace_leaf(&mut tree, key, Value::String("medium".to_string()));
+        }
+    }
+
+    let config: BustcallConfig = match tree.clone().try_into() {
+        Ok(config) => config,
+        Err(e) => {
+            if diagnostics.is_empty() {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    key: String::new(),
+                    line: syntax_error_line(content, format),
+                    message: e.to_string(),
+                    suggestion: None,
+                });
+            }
+            return sorted(diagnostics);
+        }
+    };
+
+    // Anything written that does not survive a round trip was ignored
+    let known = known_keys(&config);
+    for (key, _) in written_leaves.iter().filter(|(key, _)| !known.contains(key)) {
+        diagnostics.push(unknown_key(key, key, &known, line_of(key)));
+    }
+
+    // Profiles round-trip as free-form tables, so each is checked by
+    // applying it
+    let mut profiles: Vec<&String> = config.profile.keys().collect();
+    profiles.sort();
+    for name in profiles {
+        let prefix = format!("profile.{}", name);
+        let mut applied = tree.clone();
+        let Value::Table(table) = &mut applied else {
+            continue;
+        };
+        let overrides = match apply_profile(table, name) {
+            Ok(overrides) => overrides,
+            Err(_) => continue,
+        };
+        let profiled: BustcallConfig = match applied.try_into() {
+            Ok(profiled) => profiled,
+            Err(e) => {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    line: line_of(&prefix),
+                    key: prefix,
+                    message: format!("profile {} does not apply: {}", name, e),
+                    suggestion: None,
+                });
+                continue;
+            }
+        };
+        let mut override_leaves = Vec::new();
+        leaves("", &Value::Table(overrides), &mut override_leaves);
+        let known = known_keys(&profiled);
+        for (key, _) in override_leaves.iter().filter(|(key, _)| !known.contains(key)) {
+            let written = format!("{}.{}", prefix, key);
+            diagnostics.push(unknown_key(&written, key, &known, line_of(&written)));
+        }
+    }
+
+    let mut paths: Vec<(&str, PathBuf)> = Vec::new();
+    let target_names: Vec<&str> = config.target.keys().map(String::as_str).collect();
+    for (name, target) in &config.target {
+        for dependency in target.depends_on.iter().filter(|d| !config.target.contains_key(*d)) {
+            let key = format!("target.{}.depends_on", name);
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                line: line_of(&key),
+                key,
+                message: format!("unknown target `{}`", dependency),
+                suggestion: Some(match closest(dependency, &target_names) {
+                    Some(similar) => format!("did you mean `{}`?", similar),
+                    None => "define it under [target] or remove it".to_string(),
+                }),
+            });
+        }
+
+        for (weight, value) in [
+            ("language_priority", target.language_priority),
+            ("dependency_impact", target.dependency_impact),
+            ("build_cost", target.build_cost),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                let key = format!("target.{}.{}", name, weight);
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    line: line_of(&key),
+                    key,
+                    message: format!("weight {} is outside 0..1", value),
+                    suggestion: Some(format!("use {}", value.clamp(0.0, 1.0))),
+                });
+            }
+        }
+
+        if let Err(e) = SeverityRules::new(name, None, &target.severity_overrides) {
+            let key = format!("target.{}.severity_overrides", name);
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                line: line_of(&key),
+                key,
+                message: e.to_string(),
+                suggestion: Some("see the glob crate's pattern syntax; escape literal brackets as [[]".to_string()),
+            });
+        }
+
+        if let Err(e) = IgnoreRules::new(name, Path::new("."), &target.ignore, false) {
+            let key = format!("target.{}.ignore", name);
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                line: line_of(&key),
+                key,
+                message: e.to_string(),
+                suggestion: Some("use .gitignore pattern syntax".to_string()),
+            });
+        }
+
+        if let Some(path) = &target.path {
+            let key = format!("target.{}.path", name);
+            let workspace_root = config.global.workspace_root.as_deref().unwrap_or(".");
+            let resolved = match expand_path(workspace_root, base, base)
+                .and_then(|workspace_root| expand_path(path, base, &workspace_root))
+            {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        line: line_of(&key),
+                        key,
+                        message: e.to_string(),
+                        suggestion: Some("use ${workspace_root}, ${home} or a variable that is set".to_string()),
+                    });
+                    continue;
+                }
+            };
+            // Templates stand for the directories they match, if any yet
+            if is_template(&resolved) {
+                if let Err(e) = glob::Pattern::new(&resolved.to_string_lossy()) {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        line: line_of(&key),
+                        key,
+                        message: format!("Invalid path template: {}", e),
+                        suggestion: Some("use `*`, `**`, `?` and `[...]` wildcards as in shell globs".to_string()),
+                    });
+                }
+                continue;
+            }
+            if !resolved.exists() && !config.global.create_missing {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    line: line_of(&key),
+                    key,
+                    message: format!("{} does not exist", resolved.display()),
+                    suggestion: Some("create it, or set `create_missing = true` under [global]".to_string()),
+                });
+            }
+            paths.push((name, resolved));
+        }
+    }
+
+    paths.sort();
+    for (i, (outer_name, outer)) in paths.iter().enumerate() {
+        for (inner_name, inner) in &paths[i + 1..] {
+            let (parent, child, child_name) = if inner.starts_with(outer) {
+                (outer_name, inner, inner_name)
+            } else if outer.starts_with(inner) {
+                (inner_name, outer, outer_name)
+            } else {
+                continue;
+            };
+            let key = format!("target.{}.path", child_name);
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                line: line_of(&key),
+                key,
+                message: format!("{} overlaps the path of target `{}`", child.display(), parent),
+                suggestion: Some("changes there will bust both targets; narrow one of the paths".to_string()),
+            });
+        }
+    }
+
+    // Chains may only name built-in and declared actions
+    let actions: Vec<&str> = BUILTIN_ACTIONS
+        .into_iter()
+        .chain(config.recovery.actions.keys().map(String::as_str))
+        .collect();
+    let chains = std::iter::once(("recovery.default".to_string(), &config.recovery.default)).chain(
+        config
+            .recovery
+            .chains
+            .iter()
+            .map(|(component, chain)| (format!("recovery.chains.{}", component), chain)),
+    );
+    for (key, chain) in chains {
+        for action in chain.iter().filter(|action| !actions.contains(&action.as_str())) {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                line: line_of(&key),
+                key: key.clone(),
+                message: format!("unknown recovery action `{}`, skipped", action),
+                suggestion: Some(match closest(action, &actions) {
+                    Some(name) => format!("did you mean `{}`?", name),
+                    None => format!("declare it under [recovery.actions.{}]", action),
+                }),
+            });
+        }
+    }
+
+    // Binding the targets rejects dependency cycles
+    if let Ok(cache) = DimensionalCacheManager::new() {
+        if let Err(e) = config.configure_cache(&cache) {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                key: "target".to_string(),
+                line: None,
+                message: e.to_string(),
+                suggestion: Some("remove one of the depends_on entries of the cycle".to_string()),
+            });
+        }
+    }
+
+    sorted(diagnostics)
+}
+
+/// Keys `config` serializes back
+fn known_keys(config: &BustcallConfig) -> Vec<String> {
+    let mut known = Vec::new();
+    if let Ok(tree) = Value::try_from(config) {
+        leaves("", &tree, &mut known);
+    }
+    known.into_iter().map(|(key, _)| key).collect()
+}
+
+/// Diagnostic for `key`, missing from `known` and written at `written`;
+/// suggests the closest key next to it
+fn unknown_key(written: &str, key: &str, known: &[String], line: Option<usize>) -> Diagnostic {
+    let (parent, field) = key.rsplit_once('.').unwrap_or(("", key));
+    let siblings: Vec<&str> = known
+        .iter()
+        .filter_map(|known| known.strip_prefix(parent).map(|rest| rest.trim_start_matches('.')))
+        .filter(|rest| !rest.contains('.'))
+        .collect();
+
+    Diagnostic {
+        level: DiagnosticLevel::Error,
+        key: written.to_string(),
+        line,
+        message: format!("unknown key `{}`", field),
+        suggestion: Some(match closest(field, &siblings) {
+            Some(name) => format!("did you mean `{}`?", name),
+            None => "remove it; it has no effect".to_string(),
+        }),
+    }
+}
+
+pub(crate) fn sorted(mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    diagnostics.sort_by(|a, b| a.line.cmp(&b.line).then_with(|| a.key.cmp(&b.key)));
+    diagnostics
+}
+
+/// Dotted paths of all leaves; arrays of tables are descended by index
+fn leaves(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                leaves(&join(key), value, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_table) => {
+            for (index, item) in items.iter().enumerate() {
+                leaves(&join(&index.to_string()), item, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn replace_leaf(tree: &mut Value, key: &str, value: Value) {
+    let mut node = tree;
+    for part in key.split('.') {
+        let next = match node {
+            Value::Table(table) => table.get_mut(part),
+            Value::Array(items) => part.parse::<usize>().ok().and_then(|index| items.get_mut(index)),
+            _ => None,
+        };
+        match next {
+            Some(next) => node = next,
+            None => return,
+        }
+    }
+    *node = value;
+}
+
+/// Line of each key and table header in a TOML document, keyed by dotted
+/// path with array tables numbered like `leaves` does
+fn toml_key_lines(content: &str) -> HashMap<String, usize> {
+    let mut lines = HashMap::new();
+    let mut table = String::new();
+    let mut array_tables: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix("[[").and_then(|rest| rest.split("]]").next()) {
+            let name = canonical_key(header);
+            let count = array_tables.entry(name.clone()).or_insert(0);
+            table = format!("{}.{}", name, count);
+            *count += 1;
+            lines.entry(table.clone()).or_insert(number);
+        } else if let Some(header) = line.strip_prefix('[').and_then(|rest| rest.split(']').next()) {
+            table = canonical_key(header);
+            lines.entry(table.clone()).or_insert(number);
+        } else if let Some((key, _)) = line.split_once('=').filter(|_| !line.starts_with('#')) {
+            let path = if table.is_empty() { key.to_string() } else { format!("{}.{}", table, key) };
+            lines.entry(canonical_key(&path)).or_insert(number);
+        }
+    }
+    lines
+}
+
+/// Strip quotes and whitespace from a dotted key path and map old section
+/// names, at the top level and within a profile
+fn canonical_key(key: &str) -> String {
+    let mut parts: Vec<String> = key
+        .split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\'').to_string())
+        .collect();
+    let section = if parts.len() > 2 && parts[0] == "profile" { 2 } else { 0 };
+    for (old, new) in [("notifications", "notify"), ("targets", "target")] {
+        if parts[section] == old {
+            parts[section] = new.to_string();
+        }
+    }
+    parts.join(".")
+}
+
+/// Line of `key`, or of the nearest enclosing table written in the file
+fn key_line(lines: &HashMap<String, usize>, key: &str) -> Option<usize> {
+    let mut key = key;
+    loop {
+        if let Some(line) = lines.get(key) {
+            return Some(*line);
+        }
+        key = key.rsplit_once('.')?.0;
+    }
+}
+
+fn syntax_error_line(content: &str, format: ConfigFormat) -> Option<usize> {
+    match format {
+        ConfigFormat::Toml => {
+            let error = toml::from_str::<Table>(content)
+                .err()
+                .or_else(|| toml::from_str::<BustcallConfig>(content).err())?;
+            let offset = error.span()?.start;
+            Some(content[..offset.min(content.len())].matches('\n').count() + 1)
+        }
+        ConfigFormat::Json => serde_json::from_str::<BustcallConfig>(content).err().map(|e| e.line()),
+        ConfigFormat::Yaml => serde_yaml::from_str::<BustcallConfig>(content)
+            .err()
+            .and_then(|e| e.location())
+            .map(|location| location.line()),
+    }
+}
+
+/// The candidate closest to `given` by edit distance, if reasonably close
+fn closest<'a>(given: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let given = given.to_lowercase();
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(&given, &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, candidate)| *distance <= 2.max(candidate.len() / 3))
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substituted = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_every_problem_with_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("web/node_modules")).unwrap();
+
+        let content = r#"[global]
+self_healng = true
+
+[target.web]
+path = "web"
+build_cost = 1.5
+
+[target.deps]
+path = "web/node_modules"
+
+[target.api]
+path = "api"
+
+[[monitoring.watches]]
+name = "node"
+bust = "hgih"
+"#;
+        let diagnostics = validate_str(content, ConfigFormat::Toml, dir.path());
+        let summary: Vec<(Option<usize>, DiagnosticLevel, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.line, d.level, d.key.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (Some(2), DiagnosticLevel::Error, "global.self_healng"),
+                (Some(6), DiagnosticLevel::Error, "target.web.build_cost"),
+                (Some(9), DiagnosticLevel::Warning, "target.deps.path"),
+                (Some(12), DiagnosticLevel::Error, "target.api.path"),
+                (Some(16), DiagnosticLevel::Error, "monitoring.watches.0.bust"),
+            ]
+        );
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("did you mean `self_healing`?"));
+        assert_eq!(diagnostics[4].suggestion.as_deref(), Some("did you mean \"high\"?"));
+    }
+
+    #[test]
+    fn test_create_missing_and_syntax_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "[global]\ncreate_missing = true\n\n[target.api]\npath = \"api\"\n";
+        assert!(validate_str(content, ConfigFormat::Toml, dir.path()).is_empty());
+
+        let broken = validate_str("[global]\ncreate_missing = \n", ConfigFormat::Toml, dir.path());
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_profiles_are_checked_as_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "[profile.ci.global]\ndaemon_intervl_seconds = 1\n\n[profile.ci.targets.web]\nbuild_cost = 0.9\n";
+        let diagnostics = validate_str(content, ConfigFormat::Toml, dir.path());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].key, "profile.ci.global.daemon_intervl_seconds");
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(
+            diagnostics[0].suggestion.as_deref(),
+            Some("did you mean `daemon_interval_seconds`?")
+        );
+    }
+
+    #[test]
+    fn test_target_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = r#"[target.c]
+depends_on = ["node"]
+
+[target.node]
+depends_on = ["c", "pyhton"]
+
+[target.python]
+"#;
+        let diagnostics = validate_str(content, ConfigFormat::Toml, dir.path());
+
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].key, "target");
+        assert!(diagnostics[0].message.contains("node -> c -> node"), "{}", diagnostics[0].message);
+        assert_eq!(diagnostics[1].key, "target.node.depends_on");
+        assert_eq!(diagnostics[1].line, Some(5));
+        assert_eq!(diagnostics[1].suggestion.as_deref(), Some("did you mean `python`?"));
+    }
+
+    #[test]
+    fn test_recovery_chains_name_known_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = r#"[recovery]
+default = ["cache_refresh"]
+
+[recovery.chains]
+api = ["rebuld", "purge-cdn"]
+web = ["purge-cdn", "isolate"]
+
+[recovery.actions.purge-cdn]
+command = "./purge.sh"
+"#;
+        let diagnostics = validate_str(content, ConfigFormat::Toml, dir.path());
+
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+        assert_eq!(diagnostics[0].key, "recovery.chains.api");
+        assert_eq!(diagnostics[0].line, Some(5));
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("did you mean `rebuild`?"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Safe reload of the daemon's own config file
+//!
+//! With `watch_config = true` under `[global]`, the daemon checks the config
+//! file and its included fragments on every interval. A changed file is
+//! validated first; only a version without errors replaces the running
+//! config. A broken edit keeps the old config and is reported, so a typo
+//! never takes the daemon down or goes unnoticed. A remote config is
+//! fetched again every `config_refresh_seconds` and reloaded the same way.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::config::{load_layers, BustcallConfig, ConfigError};
+use crate::core::config_validation::{validate_file, DiagnosticLevel, ValidationReport};
+use crate::core::notify::{Notification, NotificationLevel};
+use crate::core::remote_config::RemoteConfig;
+
+/// Outcome of a config file change
+#[derive(Debug)]
+pub enum ConfigReload {
+    Applied(Box<BustcallConfig>),
+    /// The old config stays in effect
+    Rejected(String),
+}
+
+impl ConfigReload {
+    pub fn notification(&self, path: &Path) -> Notification {
+        let (level, message) = match self {
+            ConfigReload::Applied(_) => (NotificationLevel::Info, format!("Reloaded {}", path.display())),
+            ConfigReload::Rejected(reason) => (
+                NotificationLevel::Error,
+                format!("Ignoring invalid {}, keeping the running config:\n{}", path.display(), reason),
+            ),
+        };
+        Notification::new(level, &message)
+            .title("Config reload")
+            .component("config")
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    profile: Option<String>,
+    /// Modification time of the file and every fragment it included when
+    /// last checked
+    seen: Vec<(PathBuf, Option<SystemTime>)>,
+    remote: Option<RemoteRefresh>,
+}
+
+#[derive(Debug)]
+struct RemoteRefresh {
+    config: RemoteConfig,
+    every: Duration,
+    last: Instant,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`, which was loaded with `profile`
+    pub fn new<P: AsRef<Path>>(path: P, profile: Option<&str>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let seen = snapshot(&path);
+        Self {
+            path,
+            profile: profile.map(str::to_string),
+            seen,
+            remote: None,
+        }
+    }
+
+    /// Watch the cached copy of `remote`, fetching it again every `every`
+    pub fn remote(remote: RemoteConfig, profile: Option<&str>, every: Duration) -> Self {
+        let mut watcher = Self::new(remote.cached_path(), profile);
+        watcher.remote = Some(RemoteRefresh {
+            config: remote,
+            every,
+            last: Instant::now(),
+        });
+        watcher
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check for changes since the last call. `None` when nothing changed; a
+    /// rejected version is reported once, not on every check.
+    pub fn poll(&mut self) -> Option<ConfigReload> {
+        if let Some(remote) = &mut self.remote {
+            if remote.last.elapsed() >= remote.every {
+                remote.last = Instant::now();
+                if let Err(e) = remote.config.refresh() {
+                    log::warn!("⚠️ Keeping the cached copy of {}: {}", remote.config.url(), e);
+                }
+            }
+        }
+
+        let current = snapshot(&self.path);
+        if current == self.seen {
+            return None;
+        }
+        self.seen = current;
+
+        match self.reload() {
+            Ok(config) => {
+                log::info!("🔄 Reloaded {}", self.path.display());
+                Some(ConfigReload::Applied(Box::new(config)))
+            }
+            Err(reason) => {
+                log::error!("❌ Ignoring invalid {}: {}", self.path.display(), reason);
+                Some(ConfigReload::Rejected(reason))
+            }
+        }
+    }
+
+    fn reload(&self) -> std::result::Result<BustcallConfig, String> {
+        let report: ValidationReport = match &self.remote {
+            Some(remote) => remote.config.validate(),
+            None => validate_file(&self.path),
+        }
+        .map_err(|e| e.to_string())?;
+        if report.has_errors() {
+            let errors = ValidationReport {
+                file: report.file,
+                diagnostics: report
+                    .diagnostics
+                    .into_iter()
+                    .filter(|d| d.level == DiagnosticLevel::Error)
+                    .collect(),
+            };
+            return Err(errors.to_string().trim_end().to_string());
+        }
+        match &self.remote {
+            Some(remote) => remote.config.load(self.profile.as_deref()),
+            None => BustcallConfig::load_with_profile(&self.path, self.profile.as_deref()),
+        }
+        .map_err(|e: ConfigError| e.to_string())
+    }
+}
+
+/// The config file and its fragments with their modification times. A
+/// file that cannot be loaded is listed alone, so fixing it is noticed.
+fn snapshot(path: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let files = match load_layers(path) {
+        Ok(layers) => layers.into_iter().map(|(file, _)| file).collect(),
+        Err(_) => vec![path.to_path_buf()],
+    };
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = std::fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write and move the mtime ahead, so the change is visible on
+    /// filesystems with coarse timestamps
+    fn edit(path: &Path, content: &str, seconds_later: u64) {
+        std::fs::write(path, content).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(seconds_later))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_applies_valid_and_rejects_invalid_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bustcall.toml");
+        std::fs::write(&path, "[global]\nwatch_config = true\ndaemon_interval_seconds = 5\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(&path, None);
+        assert!(watcher.poll().is_none());
+
+        edit(&path, "[global]\nwatch_config = true\ndaemon_interval_seconds = 2\n", 5);
+        match watcher.poll() {
+            Some(ConfigReload::Applied(config)) => assert_eq!(config.global.daemon_interval_seconds, 2),
+            other => panic!("expected a reload, got {:?}", other),
+        }
+
+        edit(&path, "[global]\nwatch_config = true\ndaemon_interval_secs = 1\n", 10);
+        match watcher.poll() {
+            Some(reload @ ConfigReload::Rejected(_)) => {
+                let notification = reload.notification(&path);
+                assert_eq!(notification.level, NotificationLevel::Error);
+                assert!(notification.body.contains("daemon_interval_secs"), "{}", notification.body);
+            }
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+        // Reported once, not on every check
+        assert!(watcher.poll().is_none());
+    }
+}
@@ -0,0 +1,158 @@
+//! Container detection for monitored processes
+//!
+//! A process's cgroup path names the container it runs in, e.g.
+//! `/system.slice/docker-<id>.scope` or `/kubepods/.../cri-containerd-<id>.scope`.
+//! Docker names are resolved from the engine's on-disk container config;
+//! other runtimes only expose the ID.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    Docker,
+    Containerd,
+    Podman,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContainerInfo {
+    pub runtime: ContainerRuntime,
+    pub id: String,
+    pub name: Option<String>,
+}
+
+impl ContainerInfo {
+    /// Matches the container name, or an ID prefix of at least 12 characters
+    /// as printed by `docker ps`
+    pub fn matches(&self, selector: &str) -> bool {
+        self.name.as_deref() == Some(selector) || (selector.len() >= 12 && self.id.starts_with(selector))
+    }
+}
+
+const DOCKER_CONTAINERS_DIR: &str = "/var/lib/docker/containers";
+
+/// Runtime and container ID from the contents of `/proc/<pid>/cgroup`
+pub fn parse_cgroup(contents: &str) -> Option<(ContainerRuntime, String)> {
+    contents
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(parse_cgroup_path)
+}
+
+fn parse_cgroup_path(path: &str) -> Option<(ContainerRuntime, String)> {
+    for segment in path.rsplit('/') {
+        let segment = segment.trim_end_matches(".scope");
+        let found = if let Some(id) = segment.strip_prefix("docker-") {
+            Some((ContainerRuntime::Docker, id))
+        } else if let Some(id) = segment.strip_prefix("cri-containerd-") {
+            Some((ContainerRuntime::Containerd, id))
+        } else {
+            segment.strip_prefix("libpod-").map(|id| (ContainerRuntime::Podman, id))
+        };
+
+        if let Some((runtime, id)) = found {
+            if is_container_id(id) {
+                return Some((runtime, id.to_string()));
+            }
+        }
+    }
+
+    // cgroup v1 layout: /docker/<id>
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    while let Some(segment) = segments.next() {
+        if segment == "docker" {
+            if let Some(id) = segments.next().filter(|id| is_container_id(id)) {
+                return Some((ContainerRuntime::Docker, id.to_string()));
+            }
+        }
+    }
+    None
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Resolves the container a process runs in, caching container names
+#[derive(Debug, Default)]
+pub struct ContainerResolver {
+    names: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl ContainerResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn resolve(&self, pid: u32) -> Option<ContainerInfo> {
+        let cgroup = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+        let (runtime, id) = parse_cgroup(&cgroup)?;
+
+        let name = self
+            .names
+            .lock()
+            .unwrap()
+            .entry(id.clone())
+            .or_insert_with(|| match runtime {
+                ContainerRuntime::Docker => docker_name(&id),
+                _ => None,
+            })
+            .clone();
+
+        Some(ContainerInfo { runtime, id, name })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn resolve(&self, _pid: u32) -> Option<ContainerInfo> {
+        None
+    }
+}
+
+/// Read the name from Docker's `config.v2.json`; needs access to the engine's
+/// data directory, so usually root
+fn docker_name(id: &str) -> Option<String> {
+    let path = format!("{}/{}/config.v2.json", DOCKER_CONTAINERS_DIR, id);
+    let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    config
+        .get("Name")?
+        .as_str()
+        .map(|name| name.trim_start_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "3f4e2b9c1a0d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f";
+
+    #[test]
+    fn test_parse_cgroup_layouts() {
+        let v2 = format!("0::/system.slice/docker-{}.scope\n", ID);
+        assert_eq!(parse_cgroup(&v2), Some((ContainerRuntime::Docker, ID.to_string())));
+
+        let v1 = format!("12:memory:/docker/{}\n11:cpu:/docker/{}\n", ID, ID);
+        assert_eq!(parse_cgroup(&v1), Some((ContainerRuntime::Docker, ID.to_string())));
+
+        let k8s = format!("0::/kubepods.slice/kubepods-pod1.slice/cri-containerd-{}.scope\n", ID);
+        assert_eq!(parse_cgroup(&k8s), Some((ContainerRuntime::Containerd, ID.to_string())));
+
+        assert_eq!(parse_cgroup("0::/user.slice/user-1000.slice/session-2.scope\n"), None);
+    }
+
+    #[test]
+    fn test_matches_name_or_id_prefix() {
+        let container = ContainerInfo {
+            runtime: ContainerRuntime::Docker,
+            id: ID.to_string(),
+            name: Some("api".to_string()),
+        };
+        assert!(container.matches("api"));
+        assert!(container.matches(&ID[..12]));
+        assert!(!container.matches("3f4e"));
+    }
+}
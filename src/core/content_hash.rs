@@ -0,0 +1,118 @@
+//! Content change detection for watched files
+//!
+//! Editors, `touch` and checkouts of the revision already checked out fire
+//! modify events for files whose content is the same. `ContentHashes` keeps
+//! the size, mtime and BLAKE3 digest of every file it is asked about: a file
+//! whose size and mtime did not move is unchanged without being read, any
+//! other is hashed and only a new digest counts as a change. A file seen
+//! for the first time is compared against the baseline digest given, e.g.
+//! the one its cache entry was registered with, and changed without one.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::dimensional_cache::hash_file;
+
+#[derive(Debug, Clone, PartialEq)]
+struct FileStamp {
+    size: u64,
+    modified: Option<SystemTime>,
+    digest: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentHashes {
+    files: HashMap<PathBuf, FileStamp>,
+    /// Checks that found the content unchanged
+    unchanged: u64,
+}
+
+impl ContentHashes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `path`'s content differs from when it was last checked, or
+    /// from `baseline` when it was not. Files that cannot be read count as
+    /// changed.
+    pub fn changed(&mut self, path: &Path, baseline: Option<&str>) -> bool {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => metadata,
+            _ => {
+                self.files.remove(path);
+                return true;
+            }
+        };
+        let (size, modified) = (metadata.len(), metadata.modified().ok());
+        let previous = self.files.get(path);
+        if previous.is_some_and(|stamp| stamp.size == size && modified.is_some() && stamp.modified == modified) {
+            self.unchanged += 1;
+            return false;
+        }
+
+        let Some(digest) = hash_file(&path.to_string_lossy()) else {
+            self.files.remove(path);
+            return true;
+        };
+        let changed = previous.map(|stamp| stamp.digest.as_str()).or(baseline) != Some(digest.as_str());
+        if !changed {
+            self.unchanged += 1;
+        }
+        self.files.insert(path.to_path_buf(), FileStamp { size, modified, digest });
+        changed
+    }
+
+    /// Drop a removed file, so one created in its place is a change
+    pub fn forget(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+
+    /// Files whose digest is known
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Checks that found the content unchanged, so busted nothing
+    pub fn unchanged(&self) -> u64 {
+        self.unchanged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_only_new_content_is_a_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(&path, "{}").unwrap();
+        let digest = hash_file(&path.to_string_lossy()).unwrap();
+
+        let mut hashes = ContentHashes::new();
+        assert!(!hashes.changed(&path, Some(&digest)));
+        // Same size and mtime: not read again
+        assert!(!hashes.changed(&path, None));
+
+        // Touched: new mtime, same content
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        assert!(!hashes.changed(&path, None));
+
+        fs::write(&path, "{\"name\":\"web\"}").unwrap();
+        assert!(hashes.changed(&path, None));
+        assert_eq!(hashes.unchanged(), 3);
+
+        hashes.forget(&path);
+        assert!(hashes.changed(&path, None));
+        assert!(hashes.changed(&dir.path().join("missing.json"), None));
+        assert_eq!(hashes.len(), 1);
+    }
+}
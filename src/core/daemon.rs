@@ -1,13 +1,47 @@
+#[cfg(feature = "redis-backend")]
+use crate::core::cache_bus::RedisBus;
+use crate::core::cache_bus::{BusSubscription, CacheBus, LocalBus};
+use crate::core::cgroup::CgroupMonitor;
+use crate::core::config::{BustcallConfig, GlobalConfig};
+use crate::core::config_watch::{ConfigReload, ConfigWatcher};
+#[cfg(feature = "daemon")]
+use crate::core::notify::dispatch::NotificationDispatcher;
+use crate::core::notify::exec::ExecChannel;
+use crate::core::notify::policy::{bust_severity_for, PolicyAction};
+use crate::core::notify::{Notification, NotificationChannel, NotificationManager};
+use crate::core::plugin::{DaemonEvent, DaemonPlugin, PluginRegistry};
+use crate::core::priority::ResourceGovernor;
+use crate::core::probe::LivenessProber;
+use crate::core::process::{ProcessInfo, ProcessManager, RestartOutcome};
+use crate::core::schedule::{BustScheduler, ScheduleDecision};
+use crate::core::watch::ProcessWatch;
+use crate::dimensional_cache::{
+    bust_payload, parse_bust_message, BackgroundTask, BustReason, CacheBustSeverity, DimensionalCacheManager,
+    RECONNECT_BACKOFF,
+};
+use crate::pid_watcher::{BustDispatch, RuntimeWatcher};
 use crate::utils::error::{BustcallError, Result};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Renewals of the leader lease per lease period, so one failing does not
+/// let the lease lapse
+const LEASE_RENEWALS: u32 = 3;
+
+/// How long `stop` waits for queued notifications to go out
+#[cfg(feature = "daemon")]
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DaemonConfig {
     pub bind_address: String,
     pub port: u16,
     pub log_level: String,
     pub pid_file: String,
+    #[serde(default)]
+    pub leader_election: Option<LeaderElectionConfig>,
 }
 
 impl Default for DaemonConfig {
@@ -17,6 +51,7 @@ impl Default for DaemonConfig {
             port: 8080,
             log_level: "info".to_string(),
             pid_file: "/tmp/bustcall.pid".to_string(),
+            leader_election: None,
         }
     }
 }
@@ -35,6 +70,136 @@ impl DaemonConfig {
     }
 }
 
+/// Leader election settings for daemons sharing a distributed cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderElectionConfig {
+    pub redis_url: String,
+    pub lock_key: String,
+    pub forward_channel: String,
+    pub lease_ms: u64,
+    pub node_id: String,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: "redis://127.0.0.1/".to_string(),
+            lock_key: "bustcall:leader".to_string(),
+            forward_channel: "bustcall:forwarded_busts".to_string(),
+            lease_ms: 10_000,
+            node_id: format!("{}-{}", hostname(), std::process::id()),
+        }
+    }
+}
+
+pub(crate) fn hostname() -> String {
+    std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string())
+}
+
+/// Role held by this daemon within a group of agents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonRole {
+    Leader,
+    Follower,
+}
+
+/// Distributed lock used to elect a single evicting daemon
+pub trait LeaderLock: std::fmt::Debug + Send + Sync {
+    /// Acquire the lock, or renew it if already held. Returns true while leading.
+    fn try_acquire(&self) -> Result<bool>;
+
+    /// Release the lock if held by this node
+    fn release(&self) -> Result<()>;
+
+    /// Hand an event to the current leader instead of acting on it locally
+    fn forward(&self, payload: &str) -> Result<()>;
+
+    /// Events followers forward, each `node_id|payload`, for the leader
+    fn forwarded(&self) -> Result<Box<dyn BusSubscription>> {
+        LocalBus.subscribe(&[])
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+#[derive(Debug)]
+pub struct RedisLeaderLock {
+    client: redis::Client,
+    config: LeaderElectionConfig,
+}
+
+#[cfg(feature = "redis-backend")]
+impl RedisLeaderLock {
+    pub fn new(config: LeaderElectionConfig) -> Result<Self> {
+        let client = redis::Client::open(config.redis_url.as_str())
+            .map_err(|e| BustcallError::DaemonError(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self { client, config })
+    }
+
+    fn connection(&self) -> Result<redis::Connection> {
+        self.client
+            .get_connection()
+            .map_err(|e| BustcallError::DaemonError(format!("Redis connection failed: {}", e)))
+    }
+}
+
+#[cfg(feature = "redis-backend")]
+impl LeaderLock for RedisLeaderLock {
+    fn try_acquire(&self) -> Result<bool> {
+        let mut conn = self.connection()?;
+
+        // Renew our own lease, or take the lock if nobody holds it
+        let script = redis::Script::new(
+            r"if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+              end
+              if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+                return 1
+              end
+              return 0",
+        );
+
+        let acquired: i32 = script
+            .key(&self.config.lock_key)
+            .arg(&self.config.node_id)
+            .arg(self.config.lease_ms)
+            .invoke(&mut conn)
+            .map_err(|e| BustcallError::DaemonError(format!("Leader lock failed: {}", e)))?;
+
+        Ok(acquired == 1)
+    }
+
+    fn release(&self) -> Result<()> {
+        let mut conn = self.connection()?;
+        let script = redis::Script::new(
+            r"if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('DEL', KEYS[1])
+              end
+              return 0",
+        );
+
+        let _: i32 = script
+            .key(&self.config.lock_key)
+            .arg(&self.config.node_id)
+            .invoke(&mut conn)
+            .map_err(|e| BustcallError::DaemonError(format!("Leader release failed: {}", e)))?;
+        Ok(())
+    }
+
+    fn forward(&self, payload: &str) -> Result<()> {
+        let mut conn = self.connection()?;
+        let _: i64 = redis::cmd("PUBLISH")
+            .arg(&self.config.forward_channel)
+            .arg(format!("{}|{}", self.config.node_id, payload))
+            .query(&mut conn)
+            .map_err(|e| BustcallError::DaemonError(format!("Event forward failed: {}", e)))?;
+        Ok(())
+    }
+
+    fn forwarded(&self) -> Result<Box<dyn BusSubscription>> {
+        RedisBus::new(&self.config.redis_url)?.subscribe(std::slice::from_ref(&self.config.forward_channel))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DaemonStatus {
     Running { pid: u32, uptime: u64 },
@@ -42,26 +207,175 @@ pub enum DaemonStatus {
     Error(String),
 }
 
-#[derive(Debug)]
 pub struct Daemon {
     config: DaemonConfig,
     status: Arc<Mutex<DaemonStatus>>,
+    leader_lock: Option<Arc<dyn LeaderLock>>,
+    role: Arc<Mutex<DaemonRole>>,
+    plugins: Arc<PluginRegistry>,
+    /// Replaced when a reloaded config changes the target schedules
+    scheduler: Arc<Mutex<Arc<BustScheduler>>>,
+    processes: Option<Arc<ProcessManager>>,
+    cache: Option<Arc<DimensionalCacheManager>>,
+    notifications: Arc<NotificationManager>,
+    /// Queues the daemon's notifications so slow channels do not hold up a tick
+    #[cfg(feature = "daemon")]
+    dispatcher: Option<Arc<NotificationDispatcher>>,
+    process_watch: Option<Arc<Mutex<ProcessWatch>>>,
+    governor: Option<Arc<Mutex<ResourceGovernor>>>,
+    cgroups: Option<Arc<Mutex<CgroupMonitor>>>,
+    liveness: Option<Arc<Mutex<LivenessProber>>>,
+    config_watcher: Option<Arc<Mutex<ConfigWatcher>>>,
+    runtimes: Arc<Mutex<Vec<RuntimeWatcher>>>,
+    tick_interval: Duration,
+    /// Daemon loop, lease renewal and forwarded busts, from `start` to `stop`
+    tasks: Arc<Mutex<Vec<BackgroundTask>>>,
 }
 
 impl Daemon {
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            config: DaemonConfig::default(),
-            status: Arc::new(Mutex::new(DaemonStatus::Stopped)),
-        })
+        Self::with_config(DaemonConfig::default())
     }
     
     pub fn with_config(config: DaemonConfig) -> Result<Self> {
+        let leader_lock = Self::build_leader_lock(&config)?;
+
         Ok(Self {
             config,
             status: Arc::new(Mutex::new(DaemonStatus::Stopped)),
+            leader_lock,
+            role: Arc::new(Mutex::new(DaemonRole::Leader)),
+            plugins: Arc::new(PluginRegistry::new()),
+            scheduler: Arc::new(Mutex::new(Arc::new(BustScheduler::new()))),
+            processes: None,
+            cache: None,
+            notifications: Arc::new(NotificationManager::new()),
+            #[cfg(feature = "daemon")]
+            dispatcher: None,
+            process_watch: None,
+            governor: None,
+            cgroups: None,
+            liveness: None,
+            config_watcher: None,
+            runtimes: Arc::new(Mutex::new(Vec::new())),
+            tick_interval: Duration::from_secs(GlobalConfig::default().daemon_interval_seconds),
+            tasks: Arc::new(Mutex::new(Vec::new())),
         })
     }
+
+    /// Use a custom leader lock instead of the configured backend
+    pub fn with_leader_lock(mut self, lock: Arc<dyn LeaderLock>) -> Self {
+        self.leader_lock = Some(lock);
+        self
+    }
+
+    /// Apply per-target quiet hours to busts dispatched by this daemon
+    pub fn with_scheduler(mut self, scheduler: BustScheduler) -> Self {
+        self.scheduler = Arc::new(Mutex::new(Arc::new(scheduler)));
+        self
+    }
+
+    /// Restart targets through their configured `restart_command`
+    pub fn with_process_manager(mut self, processes: Arc<ProcessManager>) -> Self {
+        self.processes = Some(processes);
+        self
+    }
+
+    /// The cache this daemon busts: it evicts only while leading, and
+    /// applies the busts followers forward once started
+    pub fn with_cache(mut self, cache: Arc<DimensionalCacheManager>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Notify through `manager` rather than the console, executing the
+    /// policy actions its notifications trigger on every tick once started
+    pub fn with_notifications(mut self, manager: Arc<NotificationManager>) -> Self {
+        self.notifications = manager;
+        self
+    }
+
+    /// Send notifications through `dispatcher`, spawned on the manager given
+    /// to `with_notifications`; `stop` drains it
+    #[cfg(feature = "daemon")]
+    pub fn with_dispatcher(mut self, dispatcher: Arc<NotificationDispatcher>) -> Self {
+        self.dispatcher = Some(dispatcher);
+        self
+    }
+
+    /// Sample `watch` on every tick once started
+    pub fn with_process_watch(mut self, watch: ProcessWatch) -> Self {
+        self.process_watch = Some(Arc::new(Mutex::new(watch)));
+        self
+    }
+
+    /// Throttle runtimes over their `cpu_quota` on every tick once started
+    pub fn with_resource_governor(mut self, governor: ResourceGovernor) -> Self {
+        self.governor = Some(Arc::new(Mutex::new(governor)));
+        self
+    }
+
+    /// Sample the cgroups of `monitor`'s targets on every tick once started
+    pub fn with_cgroup_monitor(mut self, monitor: CgroupMonitor) -> Self {
+        self.cgroups = Some(Arc::new(Mutex::new(monitor)));
+        self
+    }
+
+    /// Run every target's liveness probe on every tick once started
+    pub fn with_liveness(mut self, prober: LivenessProber) -> Self {
+        self.liveness = Some(Arc::new(Mutex::new(prober)));
+        self
+    }
+
+    /// Reload the config `watcher` watches on every tick once started, for
+    /// `watch_config = true`
+    pub fn with_config_watcher(mut self, watcher: ConfigWatcher) -> Self {
+        self.config_watcher = Some(Arc::new(Mutex::new(watcher)));
+        self
+    }
+
+    /// Poll `watchers` as they fall due, checked on every tick once started
+    pub fn with_runtime_watchers(self, watchers: Vec<RuntimeWatcher>) -> Self {
+        *self.runtimes.lock().unwrap() = watchers;
+        self
+    }
+
+    /// Run the daemon loop every `interval` rather than every
+    /// `daemon_interval_seconds`
+    pub fn with_tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = interval;
+        self
+    }
+
+    /// Register a plugin to receive daemon lifecycle and bust hooks
+    pub fn register_plugin(&self, plugin: Arc<dyn DaemonPlugin>) {
+        self.plugins.register(plugin);
+    }
+
+    pub fn plugins(&self) -> &PluginRegistry {
+        &self.plugins
+    }
+
+    /// Feed a pipeline event to registered plugins
+    pub fn emit_event(&self, event: &DaemonEvent) {
+        self.plugins.on_event(event);
+    }
+
+    #[cfg(feature = "redis-backend")]
+    fn build_leader_lock(config: &DaemonConfig) -> Result<Option<Arc<dyn LeaderLock>>> {
+        match &config.leader_election {
+            Some(election) => Ok(Some(Arc::new(RedisLeaderLock::new(election.clone())?))),
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(not(feature = "redis-backend"))]
+    fn build_leader_lock(config: &DaemonConfig) -> Result<Option<Arc<dyn LeaderLock>>> {
+        if config.leader_election.is_some() {
+            log::warn!("Leader election configured but redis-backend feature is disabled");
+        }
+        Ok(None)
+    }
     
     pub fn connect() -> Result<Self> {
         // Implementation for connecting to existing daemon
@@ -69,39 +383,999 @@ impl Daemon {
     }
     
     pub fn start(&mut self) -> Result<()> {
+        self.refresh_role()?;
+
         let mut status = self.status.lock().unwrap();
         *status = DaemonStatus::Running { 
             pid: std::process::id(), 
             uptime: 0 
         };
+        drop(status);
+
+        self.spawn_tasks();
+        self.plugins.on_start();
         Ok(())
     }
+
+    /// Run the daemon loop, keep the lease while leading and take the
+    /// busts followers forward
+    fn spawn_tasks(&self) {
+        let mut tasks = self.tasks.lock().unwrap();
+        let daemon = self.clone();
+        tasks.push(BackgroundTask::every(self.tick_interval, move || daemon.tick()));
+
+        let Some(lock) = self.leader_lock.clone() else {
+            return;
+        };
+        let daemon = self.clone();
+        tasks.push(BackgroundTask::every(self.lease() / LEASE_RENEWALS, move || daemon.renew_lease()));
+        if let Some(cache) = self.cache.clone() {
+            let daemon = self.clone();
+            tasks.push(BackgroundTask::spawn(move |stopped| daemon.receive_forwarded(lock.as_ref(), &cache, &stopped)));
+        }
+    }
     
     pub fn start_detached(&mut self) -> Result<()> {
         self.start()
     }
     
     pub fn stop(&mut self) -> Result<()> {
+        self.plugins.on_shutdown();
+        for task in std::mem::take(&mut *self.tasks.lock().unwrap()) {
+            task.stop();
+        }
+        // Queued and batched notifications would be lost with the process
+        #[cfg(feature = "daemon")]
+        let flushed = match &self.dispatcher {
+            Some(dispatcher) => dispatcher.drain(DRAIN_TIMEOUT),
+            None => self.notifications.flush(),
+        };
+        #[cfg(not(feature = "daemon"))]
+        let flushed = self.notifications.flush();
+        if let Err(e) = flushed {
+            log::warn!("Notifications not flushed: {}", e);
+        }
+
+        if let Some(lock) = &self.leader_lock {
+            if self.is_leader() {
+                lock.release()?;
+            }
+        }
+
         let mut status = self.status.lock().unwrap();
         *status = DaemonStatus::Stopped;
         Ok(())
     }
+
+    /// Re-run the election, renewing the lease when already leading.
+    /// Called every third of the lease once started.
+    pub fn refresh_role(&self) -> Result<DaemonRole> {
+        let role = match &self.leader_lock {
+            Some(lock) => {
+                if lock.try_acquire()? {
+                    DaemonRole::Leader
+                } else {
+                    DaemonRole::Follower
+                }
+            }
+            None => DaemonRole::Leader,
+        };
+        self.set_role(role);
+        Ok(role)
+    }
+
+    /// Renew the lease, following when that fails: another daemon takes
+    /// the lock once the lease lapses
+    fn renew_lease(&self) {
+        if let Err(e) = self.refresh_role() {
+            log::warn!("⚠️ Leader lease not renewed, following: {}", e);
+            self.set_role(DaemonRole::Follower);
+        }
+    }
+
+    fn set_role(&self, role: DaemonRole) {
+        let mut current = self.role.lock().unwrap();
+        if *current != role {
+            log::info!("Daemon role changed: {:?} -> {:?}", *current, role);
+        }
+        *current = role;
+        if let Some(cache) = &self.cache {
+            cache.set_evicting(role == DaemonRole::Leader);
+        }
+    }
+
+    /// One pass of the daemon loop, every tick interval once started
+    fn tick(&self) {
+        let manager = &self.notifications;
+
+        if let Some(watcher) = &self.config_watcher {
+            match self.check_config(&mut watcher.lock().unwrap(), manager) {
+                Ok(Some(config)) => self.rebuild_checks(&config),
+                Ok(None) => {}
+                Err(e) => log::warn!("Config not checked for changes: {}", e),
+            }
+        }
+
+        if let Some(monitor) = &self.cgroups {
+            if let Err(e) = self.check_cgroup_pressure(&mut monitor.lock().unwrap(), manager) {
+                log::warn!("Cgroup pressure not sampled: {}", e);
+            }
+        }
+
+        // Re-send notifications nobody acknowledged in time
+        if let Err(e) = manager.process_escalations() {
+            log::warn!("Escalations not processed: {}", e);
+        }
+        // Send digests and batches that came due
+        if let Err(e) = manager.poll() {
+            log::warn!("Notification channels not polled: {}", e);
+        }
+
+        let Some(cache) = &self.cache else {
+            return;
+        };
+
+        if let Some(watch) = &self.process_watch {
+            if let Err(e) = self.check_process_watches(&mut watch.lock().unwrap(), manager, cache) {
+                log::warn!("Process watches not sampled: {}", e);
+            }
+        }
+        if let Some(governor) = &self.governor {
+            let throttled = self
+                .runtime_processes()
+                .and_then(|samples| self.check_resources(&mut governor.lock().unwrap(), &samples));
+            if let Err(e) = throttled {
+                log::warn!("Resource quotas not enforced: {}", e);
+            }
+        }
+
+        if let Some(prober) = &self.liveness {
+            if let Err(e) = self.check_liveness(&mut prober.lock().unwrap(), manager, cache) {
+                log::warn!("Liveness probes not run: {}", e);
+            }
+        }
+
+        if let Err(e) = self.check_runtimes(&mut self.runtimes.lock().unwrap(), cache) {
+            log::warn!("Runtimes not polled: {}", e);
+        }
+
+        // Quiet windows that ended since the last tick release their busts
+        if let Err(e) = self.replay_queued(cache) {
+            log::warn!("Queued busts not replayed: {}", e);
+        }
+
+        // Act on the notifications sent since the last tick
+        self.run_policy_actions(manager, cache);
+    }
+
+    /// Rebuild the probes and watches this daemon runs from a reloaded config
+    fn rebuild_checks(&self, config: &BustcallConfig) {
+        if let Some(prober) = &self.liveness {
+            *prober.lock().unwrap() = LivenessProber::from_targets(&config.target_schedules());
+        }
+        if let Some(watch) = &self.process_watch {
+            match ProcessWatch::new(config.monitoring.watches.clone()) {
+                Ok(rebuilt) => *watch.lock().unwrap() = rebuilt,
+                Err(e) => log::warn!("⚠️ Keeping the running process watches: {}", e),
+            }
+        }
+        if let Some(governor) = &self.governor {
+            *governor.lock().unwrap() = ResourceGovernor::from_targets(&config.target_schedules());
+        }
+        if let Some(monitor) = &self.cgroups {
+            let pressure = config.monitoring.pressure.clone().unwrap_or_default();
+            *monitor.lock().unwrap() = CgroupMonitor::new(config.monitoring.processes.clone(), pressure);
+        }
+    }
+
+    fn lease(&self) -> Duration {
+        let election = self.config.leader_election.clone().unwrap_or_default();
+        Duration::from_millis(election.lease_ms)
+    }
+
+    /// Apply the busts followers forward while leading, resubscribing with
+    /// backoff when the connection drops, until `stopped`
+    fn receive_forwarded(&self, lock: &dyn LeaderLock, cache: &DimensionalCacheManager, stopped: &mpsc::Receiver<()>) {
+        let (min, max) = RECONNECT_BACKOFF;
+        let mut backoff = min;
+        loop {
+            match lock.forwarded() {
+                Ok(mut forwarded) => {
+                    backoff = min;
+                    loop {
+                        if !matches!(stopped.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                            return;
+                        }
+                        match forwarded.next_message(Duration::from_secs(1)) {
+                            Ok(Some(message)) => {
+                                if let Err(e) = self.apply_forwarded(cache, &message) {
+                                    log::warn!("Forwarded bust {:?} not applied: {}", message, e);
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                log::warn!("⚠️  Forwarded busts disconnected, retrying in {:?}: {}", backoff, e);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::warn!("⚠️  Forwarded busts unavailable, retrying in {:?}: {}", backoff, e),
+            }
+            if !matches!(stopped.recv_timeout(backoff), Err(mpsc::RecvTimeoutError::Timeout)) {
+                return;
+            }
+            backoff = (backoff * 2).min(max);
+        }
+    }
+
+    /// Bust what a follower forwarded, `node_id|target:severity|reasons`,
+    /// when leading. Returns whether it was applied.
+    pub fn apply_forwarded(&self, cache: &DimensionalCacheManager, message: &str) -> Result<bool> {
+        if !self.is_leader() {
+            return Ok(false);
+        }
+        let (origin, busts) = parse_bust_message(message)
+            .ok_or_else(|| BustcallError::DaemonError(format!("Malformed forwarded bust: {}", message)))?;
+        let origin = origin.unwrap_or("unknown node");
+        for (target, severity, reason) in busts {
+            log::debug!("Busting {} as forwarded by {}", target, origin);
+            self.plugins.on_bust(target, &severity);
+            let reason = reason.unwrap_or_else(|| format!("forwarded by {}", origin).into());
+            cache
+                .bust_cache_with_reason(target, severity, reason)
+                .map_err(|e| BustcallError::DaemonError(format!("Cache bust failed: {}", e)))?;
+        }
+        Ok(true)
+    }
+
+    pub fn role(&self) -> DaemonRole {
+        *self.role.lock().unwrap()
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role() == DaemonRole::Leader
+    }
+
+    /// Bust locally when leading, otherwise forward the bust to the leader.
+    /// Busts falling inside a target's quiet hours may be queued or downgraded.
+    pub fn dispatch_bust(
+        &self,
+        cache: &DimensionalCacheManager,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: impl Into<BustReason>,
+    ) -> Result<()> {
+        let reason = reason.into();
+        let severity = match self.scheduler().submit(target, severity, &reason) {
+            ScheduleDecision::Execute(severity) => severity,
+            ScheduleDecision::Queued => return Ok(()),
+        };
+
+        match (&self.leader_lock, self.role()) {
+            (Some(lock), DaemonRole::Follower) => {
+                log::debug!("Forwarding bust for {} to leader", target);
+                lock.forward(&bust_payload(&[(target.to_string(), severity, reason)]))
+            }
+            _ => {
+                self.plugins.on_bust(target, &severity);
+                cache
+                    .bust_cache_with_reason(target, severity, reason)
+                    .map_err(|e| BustcallError::DaemonError(format!("Cache bust failed: {}", e)))
+            }
+        }
+    }
     
+    /// Dispatch for a file watcher busting `cache`, so its busts go the way
+    /// of the daemon's own
+    pub fn watcher_dispatch(&self, cache: DimensionalCacheManager) -> BustDispatch {
+        let daemon = self.clone();
+        Arc::new(move |target, severity, reason| daemon.dispatch_bust(&cache, target, severity, reason))
+    }
+
+    fn scheduler(&self) -> Arc<BustScheduler> {
+        Arc::clone(&self.scheduler.lock().unwrap())
+    }
+
     pub fn status(&self) -> DaemonStatus {
         self.status.lock().unwrap().clone()
     }
     
+    /// Execute busts held back by quiet hours whose window has now opened
+    pub fn replay_queued(&self, cache: &DimensionalCacheManager) -> Result<usize> {
+        let ready = self.scheduler().drain_ready();
+        let count = ready.len();
+
+        for (target, severity, reason) in ready {
+            log::info!("☀️ Replaying queued bust for {} ({:?})", target, severity);
+            self.dispatch_bust(cache, &target, severity, reason)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Execute the policy actions triggered by notifications sent through
+    /// `manager`. Failures are logged per action; the count of actions that
+    /// ran successfully is returned.
+    pub fn run_policy_actions(&self, manager: &NotificationManager, cache: &DimensionalCacheManager) -> usize {
+        let mut executed = 0;
+
+        for invocation in manager.take_policy_actions() {
+            let notification = &invocation.notification;
+            let target = notification.target.as_deref();
+            let reason = format!("policy: {}", notification.text());
+
+            let result = match (&invocation.action, target) {
+                (PolicyAction::Bust { severity }, Some(target)) => {
+                    let severity = severity.clone().unwrap_or_else(|| bust_severity_for(notification.level));
+                    self.dispatch_bust(cache, target, severity, reason)
+                }
+                // The mildest bust drops the target's entries, and is
+                // forwarded or held back like any other
+                (PolicyAction::Evict, Some(target)) => {
+                    self.dispatch_bust(cache, target, CacheBustSeverity::Low, reason)
+                }
+                (PolicyAction::Restart, Some(target)) => self.restart_target(target),
+                (PolicyAction::RunHook(hook), _) => ExecChannel::new(hook.clone()).deliver_notification(notification),
+                (action, None) => Err(BustcallError::DaemonError(format!(
+                    "Policy action {:?} needs a notification target",
+                    action
+                ))),
+            };
+
+            match result {
+                Ok(()) => {
+                    log::info!("⚙️ Policy action {:?} for {:?}", invocation.action, target);
+                    executed += 1;
+                }
+                Err(e) => log::warn!("Policy action {:?} failed: {}", invocation.action, e),
+            }
+        }
+
+        executed
+    }
+
+    /// Bring a target back with its `restart_command`. Targets without one
+    /// are left to plugins supervising them, via a "restart" event.
+    pub fn restart_target(&self, target: &str) -> Result<()> {
+        let processes = match &self.processes {
+            Some(processes) if processes.can_restart(target) => processes,
+            _ => {
+                self.emit_event(&DaemonEvent {
+                    kind: "restart".to_string(),
+                    target: Some(target.to_string()),
+                    path: None,
+                });
+                return Ok(());
+            }
+        };
+
+        match processes.restart(target)? {
+            RestartOutcome::Started { pid, attempt } => {
+                log::info!("♻️ {} restarted as pid {} (attempt {})", target, pid, attempt);
+                Ok(())
+            }
+            RestartOutcome::BackingOff { retry_in } => {
+                log::info!("⏳ Restart of {} backing off for {:?}", target, retry_in);
+                Ok(())
+            }
+            RestartOutcome::GaveUp { attempts } => Err(BustcallError::DaemonError(format!(
+                "Giving up restarting {} after {} attempts",
+                target, attempts
+            ))),
+        }
+    }
+
+    /// Take one process watch sample, notifying and busting for each alarm
+    /// raised. Returns the number of alarms.
+    pub fn check_process_watches(
+        &self,
+        watch: &mut ProcessWatch,
+        manager: &NotificationManager,
+        cache: &DimensionalCacheManager,
+    ) -> Result<usize> {
+        let alarms = watch.sample()?;
+
+        for alarm in &alarms {
+            let notification = alarm.notification();
+            self.notify(manager, &notification)?;
+            if let (Some(target), Some(severity)) = (&alarm.target, &alarm.bust) {
+                self.dispatch_bust(cache, target, severity.clone(), notification.text())?;
+            }
+        }
+
+        Ok(alarms.len())
+    }
+
+    /// Current processes of every watched runtime, by target
+    fn runtime_processes(&self) -> Result<Vec<(String, Vec<ProcessInfo>)>> {
+        self.runtimes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|watcher| Ok((watcher.target().to_string(), watcher.processes()?)))
+            .collect()
+    }
+
+    /// Throttle the targets whose processes in `samples` use more than their
+    /// CPU quota, with the nice level and CPU set their policy gives.
+    /// Returns the number of processes throttled.
+    pub fn check_resources(&self, governor: &mut ResourceGovernor, samples: &[(String, Vec<ProcessInfo>)]) -> Result<usize> {
+        let mut throttled = 0;
+        for (target, processes) in samples {
+            throttled += governor.enforce(target, processes)?.len();
+        }
+        Ok(throttled)
+    }
+
+    /// Run every target's liveness probe, notifying on escalation steps and
+    /// recoveries and busting with the escalated severity. Returns the number
+    /// of busts.
+    pub fn check_liveness(
+        &self,
+        prober: &mut LivenessProber,
+        manager: &NotificationManager,
+        cache: &DimensionalCacheManager,
+    ) -> Result<usize> {
+        let mut busts = 0;
+        for event in prober.probe_all() {
+            let notification = event.notification();
+            self.notify(manager, &notification)?;
+            if let Some(severity) = event.bust {
+                self.dispatch_bust(cache, &event.target, severity, notification.text())?;
+                busts += 1;
+            }
+        }
+        Ok(busts)
+    }
+
+    /// Sample the cgroups of monitored runtimes and warn about memory,
+    /// throttling or pressure before the runtime is killed. Returns the
+    /// number of alarms.
+    pub fn check_cgroup_pressure(&self, monitor: &mut CgroupMonitor, manager: &NotificationManager) -> Result<usize> {
+        let alarms = monitor.sample()?;
+        for alarm in &alarms {
+            self.notify(manager, &alarm.notification())?;
+        }
+        Ok(alarms.len())
+    }
+
+    /// Poll the runtime watchers whose poll is due, busting for each change
+    /// that warrants it. Stable runtimes are polled less and less often;
+    /// see [`crate::core::poll_backoff`]. Returns the number of busts.
+    pub fn check_runtimes(&self, watchers: &mut [RuntimeWatcher], cache: &DimensionalCacheManager) -> Result<usize> {
+        let now = Instant::now();
+        let mut busts = 0;
+        for watcher in watchers.iter_mut().filter(|watcher| watcher.is_due(now)) {
+            for change in watcher.poll()? {
+                if let Some(severity) = change.severity() {
+                    log::info!("🔁 Runtime {} changed: {:?} -> {:?}", watcher.target(), change, severity);
+                    self.dispatch_bust(cache, watcher.target(), severity, change.bust_reason())?;
+                    busts += 1;
+                }
+            }
+            log::debug!("Next poll of {} in {:?}", watcher.target(), watcher.poll_interval());
+        }
+        Ok(busts)
+    }
+
+    /// Pick up edits to the config file, for `watch_config = true`. A valid
+    /// version replaces the target schedules and is returned so the caller
+    /// can rebuild its probes and watches; an invalid one is reported as an
+    /// Error notification and the running config is kept.
+    pub fn check_config(
+        &self,
+        watcher: &mut ConfigWatcher,
+        manager: &NotificationManager,
+    ) -> Result<Option<BustcallConfig>> {
+        let reload = match watcher.poll() {
+            Some(ConfigReload::Applied(config)) => match BustScheduler::from_targets(&config.target_schedules()) {
+                Ok(scheduler) => {
+                    if toml::to_string(&config.daemon).ok() != toml::to_string(&self.config).ok() {
+                        log::warn!("⚠️ [daemon] changes in {} take effect on restart", watcher.path().display());
+                    }
+                    *self.scheduler.lock().unwrap() = Arc::new(scheduler);
+                    ConfigReload::Applied(config)
+                }
+                Err(e) => ConfigReload::Rejected(e.to_string()),
+            },
+            Some(rejected) => rejected,
+            None => return Ok(None),
+        };
+
+        self.notify(manager, &reload.notification(watcher.path()))?;
+        match reload {
+            ConfigReload::Applied(config) => Ok(Some(*config)),
+            ConfigReload::Rejected(_) => Ok(None),
+        }
+    }
+
+    /// Queue `notification` on the dispatcher, or send it through `manager`
+    /// when there is none
+    fn notify(&self, manager: &NotificationManager, notification: &Notification) -> Result<()> {
+        #[cfg(feature = "daemon")]
+        if let Some(dispatcher) = &self.dispatcher {
+            return dispatcher.send_structured(notification);
+        }
+        manager.send_structured(notification).map(|_| ())
+    }
+
     pub fn wait_for_shutdown(&self) -> Result<()> {
         // Implementation for graceful shutdown
         Ok(())
     }
 }
 
+impl std::fmt::Debug for Daemon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Daemon")
+            .field("config", &self.config)
+            .field("status", &self.status)
+            .field("leader_lock", &self.leader_lock)
+            .field("role", &self.role)
+            .field("plugins", &self.plugins)
+            .field("scheduler", &self.scheduler)
+            .field("processes", &self.processes)
+            .field("notifications", &self.notifications)
+            .field("process_watch", &self.process_watch)
+            .field("governor", &self.governor)
+            .field("cgroups", &self.cgroups)
+            .field("liveness", &self.liveness)
+            .field("config_watcher", &self.config_watcher)
+            .field("tick_interval", &self.tick_interval)
+            .finish_non_exhaustive()
+    }
+}
+
 impl Clone for Daemon {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone(),
             status: Arc::clone(&self.status),
+            leader_lock: self.leader_lock.clone(),
+            role: Arc::clone(&self.role),
+            plugins: Arc::clone(&self.plugins),
+            scheduler: Arc::clone(&self.scheduler),
+            processes: self.processes.clone(),
+            cache: self.cache.clone(),
+            notifications: Arc::clone(&self.notifications),
+            #[cfg(feature = "daemon")]
+            dispatcher: self.dispatcher.clone(),
+            process_watch: self.process_watch.clone(),
+            governor: self.governor.clone(),
+            cgroups: self.cgroups.clone(),
+            liveness: self.liveness.clone(),
+            config_watcher: self.config_watcher.clone(),
+            runtimes: Arc::clone(&self.runtimes),
+            tick_interval: self.tick_interval,
+            tasks: Arc::clone(&self.tasks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::core::notify::NotificationLevel;
+
+    #[derive(Debug)]
+    struct HeldElsewhere;
+
+    /// Counts what the manager asks of it
+    #[derive(Default)]
+    struct Batching {
+        delivered: AtomicUsize,
+        polls: AtomicUsize,
+        flushes: AtomicUsize,
+    }
+
+    impl NotificationChannel for Arc<Batching> {
+        fn name(&self) -> &str {
+            "batching"
+        }
+
+        fn deliver(&self, _level: NotificationLevel, _message: &str) -> Result<()> {
+            self.delivered.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn poll(&self) -> Result<()> {
+            self.polls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn flush(&self) -> Result<()> {
+            self.flushes.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    impl LeaderLock for HeldElsewhere {
+        fn try_acquire(&self) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn release(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn forward(&self, _payload: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_standalone_daemon_leads() {
+        let daemon = Daemon::new().unwrap();
+        assert_eq!(daemon.refresh_role().unwrap(), DaemonRole::Leader);
+    }
+
+    #[test]
+    fn test_lock_held_elsewhere_makes_follower() {
+        let daemon = Daemon::new().unwrap().with_leader_lock(Arc::new(HeldElsewhere));
+        assert_eq!(daemon.refresh_role().unwrap(), DaemonRole::Follower);
+        assert!(!daemon.is_leader());
+    }
+
+    /// Leads until `lost`, and hands out the busts sent on `forwarded`
+    #[derive(Debug)]
+    struct ChannelLock {
+        lost: std::sync::atomic::AtomicBool,
+        forwarded: Mutex<Option<mpsc::Receiver<String>>>,
+    }
+
+    struct ChannelSubscription(mpsc::Receiver<String>);
+
+    impl BusSubscription for ChannelSubscription {
+        fn next_message(&mut self, timeout: Duration) -> Result<Option<String>> {
+            Ok(self.0.recv_timeout(timeout).ok())
+        }
+    }
+
+    impl LeaderLock for ChannelLock {
+        fn try_acquire(&self) -> Result<bool> {
+            match self.lost.load(std::sync::atomic::Ordering::SeqCst) {
+                true => Err(BustcallError::DaemonError("connection refused".to_string())),
+                false => Ok(true),
+            }
+        }
+
+        fn release(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn forward(&self, _payload: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn forwarded(&self) -> Result<Box<dyn BusSubscription>> {
+            let messages = self.forwarded.lock().unwrap().take();
+            Ok(Box::new(ChannelSubscription(messages.unwrap_or_else(|| mpsc::channel().1))))
+        }
+    }
+
+    #[test]
+    fn test_leader_applies_forwarded_busts_and_follows_once_renewal_fails() {
+        let (forward, forwarded) = mpsc::channel();
+        let lock = Arc::new(ChannelLock { lost: Default::default(), forwarded: Mutex::new(Some(forwarded)) });
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new().unwrap().with_leader_lock(lock.clone()).with_cache(cache.clone());
+        daemon.start().unwrap();
+
+        forward.send("node-2|web:High".to_string()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let busts = cache.recent_busts();
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::High));
+        assert_eq!(busts[0].reason.to_string(), "forwarded by node-2");
+        // Followers forward the bust's own reason along with it
+        let forwarded = bust_payload(&[("api".to_string(), CacheBustSeverity::Low, BustReason::from("deploy"))]);
+        assert!(daemon.apply_forwarded(&cache, &format!("node-2|{}", forwarded)).unwrap());
+        assert_eq!(cache.recent_busts()[1].reason.to_string(), "deploy");
+        assert!(cache.is_evicting());
+
+        lock.lost.store(true, std::sync::atomic::Ordering::SeqCst);
+        daemon.renew_lease();
+        assert!(!daemon.is_leader());
+        assert!(!cache.is_evicting());
+        assert!(!daemon.apply_forwarded(&cache, "node-2|web:Low").unwrap());
+
+        // Only the leader evicts
+        cache.register_entry("a", "web", 10, crate::dimensional_cache::EvictionStrategy::LRU);
+        cache.set_limits(crate::dimensional_cache::CacheLimits { max_entries: Some(0), ..Default::default() });
+        assert!(cache.cache_evict(&crate::dimensional_cache::EvictionStrategy::LRU).unwrap().is_empty());
+        assert_eq!(cache.enforce_limits().evicted, 0);
+        lock.lost.store(false, std::sync::atomic::Ordering::SeqCst);
+        daemon.renew_lease();
+        assert_eq!(cache.enforce_limits().evicted, 1);
+        daemon.stop().unwrap();
+    }
+
+    #[test]
+    fn test_daemon_loop_replays_busts_once_quiet_hours_end() {
+        use crate::core::schedule::{QuietHours, QuietHoursAction};
+
+        let now = chrono::Local::now().time();
+        let mut scheduler = BustScheduler::new();
+        let window = QuietHours { start: now + chrono::Duration::hours(2), end: now + chrono::Duration::hours(3) };
+        scheduler.set_quiet_hours("web", window, QuietHoursAction::Queue);
+        let during = now + chrono::Duration::minutes(150);
+        let queued = scheduler.submit_at("web", CacheBustSeverity::High, &BustReason::from("deploy"), during);
+        assert_eq!(queued, ScheduleDecision::Queued);
+
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_scheduler(scheduler)
+            .with_cache(cache.clone())
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        daemon.stop().unwrap();
+        let busts = cache.recent_busts();
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::High));
+        assert_eq!(busts[0].reason.to_string(), "deploy");
+        assert_eq!(daemon.scheduler().queued_len(), 0);
+    }
+
+    #[test]
+    fn test_daemon_loop_runs_the_policy_actions_notifications_trigger() {
+        use crate::core::notify::policy::PolicyRule;
+        use crate::dimensional_cache::EvictionStrategy;
+
+        let rule: PolicyRule = toml::from_str("levels = [\"warning\"]\naction = { type = \"evict\" }").unwrap();
+        let manager = Arc::new(NotificationManager::new().with_policy(vec![rule]));
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        cache.register_entry("a", "web", 10, EvictionStrategy::LRU);
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_cache(cache.clone())
+            .with_notifications(manager.clone())
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        manager.send_structured(&Notification::new(NotificationLevel::Info, "web is up").target("web")).unwrap();
+        manager.send_structured(&Notification::new(NotificationLevel::Warning, "web is slow").target("web")).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        daemon.stop().unwrap();
+        let busts = cache.recent_busts();
+        assert_eq!(busts.len(), 1);
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::Low));
+        assert!(cache.list_entries(&crate::dimensional_cache::EntryFilter::all()).is_empty());
+        assert!(manager.take_policy_actions().is_empty());
+    }
+
+    #[test]
+    fn test_daemon_loop_polls_channels_and_flushes_on_stop() {
+        let batching = Arc::new(Batching::default());
+        let mut manager = NotificationManager::new();
+        manager.add_channel(Box::new(batching.clone()));
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_notifications(Arc::new(manager))
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while batching.polls.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(batching.flushes.load(Ordering::SeqCst), 0);
+        daemon.stop().unwrap();
+        assert!(batching.polls.load(Ordering::SeqCst) > 0);
+        assert_eq!(batching.flushes.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "daemon")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_notifications_go_through_the_dispatcher_and_are_drained_on_stop() {
+        use crate::core::notify::dispatch::DispatchConfig;
+
+        let batching = Arc::new(Batching::default());
+        let mut manager = NotificationManager::new();
+        manager.add_channel(Box::new(batching.clone()));
+        let manager = Arc::new(manager);
+        let dispatcher = Arc::new(NotificationDispatcher::spawn(manager.clone(), DispatchConfig::default()));
+        let mut daemon = Daemon::new().unwrap().with_notifications(manager.clone()).with_dispatcher(dispatcher.clone());
+
+        for i in 0..5 {
+            let notification = Notification::new(NotificationLevel::Warning, &format!("web is slow ({})", i));
+            daemon.notify(&manager, &notification).unwrap();
+        }
+        daemon.stop().unwrap();
+        assert_eq!(dispatcher.pending(), 0);
+        assert_eq!(batching.delivered.load(Ordering::SeqCst), 5);
+        assert_eq!(batching.flushes.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resource_governor_throttles_targets_over_quota() {
+        use crate::core::priority::ResourcePolicy;
+
+        fn process(pid: u32, cpu: f64) -> ProcessInfo {
+            ProcessInfo {
+                pid,
+                name: "node".to_string(),
+                status: "Run".to_string(),
+                cpu_usage: cpu,
+                memory_usage: 0,
+                parent_pid: None,
+                session_id: None,
+                pgid: None,
+                container: None,
+                cmdline: Vec::new(),
+                start_time: 0,
+                open_files: None,
+                fingerprint: None,
+            }
+        }
+
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        let pid = child.id();
+        let mut governor = ResourceGovernor::new();
+        governor.set_policy("web", toml::from_str::<ResourcePolicy>("cpu_quota = 100.0
+nice = 5").unwrap());
+        let daemon = Daemon::new().unwrap();
+
+        let calm = [("web".to_string(), vec![process(pid, 40.0)]), ("api".to_string(), vec![process(1, 400.0)])];
+        let busy = [("web".to_string(), vec![process(pid, 120.0)])];
+        let calm_throttled = daemon.check_resources(&mut governor, &calm);
+        let busy_throttled = daemon.check_resources(&mut governor, &busy);
+        // Already throttled processes are left alone
+        let again = daemon.check_resources(&mut governor, &busy);
+        // SAFETY: getpriority only reads its integer arguments
+        let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t) };
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(calm_throttled.unwrap(), 0);
+        assert_eq!(busy_throttled.unwrap(), 1);
+        assert_eq!(again.unwrap(), 0);
+        assert_eq!(nice, 5);
+    }
+
+    #[test]
+    fn test_daemon_loop_busts_for_process_watch_alarms() {
+        use crate::core::watch::ProcessWatchConfig;
+
+        let watch = ProcessWatch::new(vec![ProcessWatchConfig {
+            name: None,
+            pid: Some(std::process::id()),
+            max_cpu_percent: None,
+            max_rss_mb: Some(0),
+            max_fds: None,
+            max_threads: None,
+            consecutive_samples: 1,
+            level: NotificationLevel::Warning,
+            target: Some("web".to_string()),
+            bust: Some(CacheBustSeverity::High),
+        }])
+        .unwrap();
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_cache(cache.clone())
+            .with_process_watch(watch)
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        daemon.stop().unwrap();
+        let busts = cache.recent_busts();
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::High));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_daemon_loop_escalates_failing_liveness_probes() {
+        use crate::core::probe::{LivenessProbe, ProbeCheck};
+
+        let mut prober = LivenessProber::new();
+        prober.set_probe(
+            "web",
+            LivenessProbe {
+                check: ProbeCheck::Exec { command: vec!["false".to_string()], expected_exit: 0 },
+                timeout_seconds: 1,
+                failure_threshold: 2,
+            },
+        );
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_cache(cache.clone())
+            .with_liveness(prober)
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().len() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        daemon.stop().unwrap();
+        let severities: Vec<_> = cache.recent_busts().into_iter().map(|bust| bust.severity).collect();
+        assert_eq!(severities[..2], [CacheBustSeverity::Medium, CacheBustSeverity::High]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_daemon_loop_reloads_the_config_and_rebuilds_probes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bustcall.toml");
+        std::fs::write(&path, "[global]\nwatch_config = true\n\n[target.web]\n").unwrap();
+
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_cache(cache.clone())
+            .with_liveness(LivenessProber::new())
+            .with_config_watcher(ConfigWatcher::new(&path, None))
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        // Quiet hours from now on downgrade the first failure's Medium bust
+        let now = chrono::Local::now().time();
+        let quiet_hours = format!("{}-{}", now.format("%H:%M"), (now + chrono::Duration::hours(1)).format("%H:%M"));
+        std::fs::write(
+            &path,
+            format!(
+                "[global]\nwatch_config = true\n\n[target.web]\nquiet_hours = \"{}\"\nquiet_hours_action = \"downgrade\"\n\n[target.web.liveness]\ntype = \"exec\"\ncommand = [\"false\"]\n",
+                quiet_hours
+            ),
+        )
+        .unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + Duration::from_secs(5)).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        daemon.stop().unwrap();
+        let busts = cache.recent_busts();
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::Low));
+    }
+
+    #[test]
+    fn test_daemon_loop_polls_runtimes() {
+        use crate::core::process::{ProcessFilter, RuntimeLookup};
+
+        let own = ProcessManager::new()
+            .list_processes(&ProcessFilter::all().pid(std::process::id()))
+            .unwrap()
+            .remove(0);
+        let watcher = RuntimeWatcher::new("web", RuntimeLookup::new(&own.name).include_own_tree());
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        let mut daemon = Daemon::new()
+            .unwrap()
+            .with_cache(cache.clone())
+            .with_runtime_watchers(vec![watcher])
+            .with_tick_interval(Duration::from_millis(20));
+        daemon.start().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.recent_busts().is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
         }
+        daemon.stop().unwrap();
+        // The first poll finds the runtime started
+        let busts = cache.recent_busts();
+        assert_eq!((busts[0].target.as_str(), &busts[0].severity), ("web", &CacheBustSeverity::Medium));
+        assert_eq!(busts[0].reason.kind(), "pid_restarted");
+        assert!(daemon.runtimes.lock().unwrap()[0].pids().contains(&own.pid));
     }
 }
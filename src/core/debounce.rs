@@ -0,0 +1,186 @@
+//! Coalescing of file events into one bust per burst
+//!
+//! Saving a file from an editor fires a handful of events, `npm install`
+//! thousands. The watcher hands every event to a `Debouncer`, which holds a
+//! target's events until the target has been quiet for its debounce window
+//! and then releases them as one `Burst`, busted once. A target written to
+//! without pause is released after `MAX_HOLD_WINDOWS` windows regardless, so
+//! its busts are late but never starved.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::dimensional_cache::{BustReason, CacheBustSeverity};
+
+/// Windows a burst is held at most, however busy its target stays
+pub const MAX_HOLD_WINDOWS: u32 = 10;
+
+/// What a burst left a path as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Changed,
+    Removed,
+}
+
+/// A target's coalesced events
+#[derive(Debug, Clone, PartialEq)]
+pub struct Burst {
+    pub target: String,
+    /// The most severe of the events
+    pub severity: CacheBustSeverity,
+    /// Last change of every path the events touched
+    pub paths: BTreeMap<PathBuf, FileChange>,
+    pub events: usize,
+}
+
+impl Burst {
+    /// A lone change keeps its own reason, anything more is `FilesChanged`
+    pub fn reason(&self) -> BustReason {
+        let mut paths = self.paths.iter().map(|(path, change)| (path.display().to_string(), *change));
+        match (paths.next(), self.paths.len()) {
+            (Some((path, FileChange::Changed)), 1) if self.events == 1 => BustReason::FileChanged { path },
+            (Some((path, FileChange::Removed)), 1) if self.events == 1 => BustReason::FileRemoved { path },
+            _ => BustReason::FilesChanged {
+                paths: self.paths.keys().map(|path| path.display().to_string()).collect(),
+                events: self.events,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Pending {
+    burst: Burst,
+    first: Instant,
+    last: Instant,
+}
+
+/// Per-target trailing-edge debouncer
+#[derive(Debug)]
+pub struct Debouncer {
+    window: Duration,
+    windows: HashMap<String, Duration>,
+    pending: HashMap<String, Pending>,
+}
+
+impl Debouncer {
+    /// `window` applies to targets without one of their own
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            windows: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn with_window(mut self, target: &str, window: Duration) -> Self {
+        self.set_window(target, window);
+        self
+    }
+
+    pub fn set_window(&mut self, target: &str, window: Duration) {
+        self.windows.insert(target.to_string(), window);
+    }
+
+    pub fn window(&self, target: &str) -> Duration {
+        self.windows.get(target).copied().unwrap_or(self.window)
+    }
+
+    /// Add one event of `target` to its burst
+    pub fn push(&mut self, target: &str, path: PathBuf, change: FileChange, severity: CacheBustSeverity, now: Instant) {
+        let pending = self.pending.entry(target.to_string()).or_insert_with(|| Pending {
+            burst: Burst {
+                target: target.to_string(),
+                severity: severity.clone(),
+                paths: BTreeMap::new(),
+                events: 0,
+            },
+            first: now,
+            last: now,
+        });
+        pending.burst.severity = pending.burst.severity.clone().max(severity);
+        pending.burst.paths.insert(path, change);
+        pending.burst.events += 1;
+        pending.last = now;
+    }
+
+    fn due(&self, pending: &Pending) -> Instant {
+        let window = self.window(&pending.burst.target);
+        (pending.last + window).min(pending.first + window * MAX_HOLD_WINDOWS)
+    }
+
+    /// When the next burst is released, if one is held
+    pub fn next_due(&self) -> Option<Instant> {
+        self.pending.values().map(|pending| self.due(pending)).min()
+    }
+
+    /// Bursts due by `now`, oldest first
+    pub fn ready(&mut self, now: Instant) -> Vec<Burst> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| self.due(pending) <= now)
+            .map(|(target, _)| target.clone())
+            .collect();
+        let mut ready: Vec<Pending> = due.iter().filter_map(|target| self.pending.remove(target)).collect();
+        ready.sort_by_key(|pending| pending.first);
+        ready.into_iter().map(|pending| pending.burst).collect()
+    }
+
+    /// Every held burst, due or not, for shutdown
+    pub fn flush(&mut self) -> Vec<Burst> {
+        let mut held: Vec<Pending> = self.pending.drain().map(|(_, pending)| pending).collect();
+        held.sort_by_key(|pending| pending.first);
+        held.into_iter().map(|pending| pending.burst).collect()
+    }
+
+    /// Targets with a burst held
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bursts_are_released_once_quiet() {
+        let start = Instant::now();
+        let ms = |ms: u64| start + Duration::from_millis(ms);
+        let mut debouncer = Debouncer::new(Duration::from_millis(100)).with_window("web", Duration::from_millis(300));
+
+        debouncer.push("node", PathBuf::from("a.js"), FileChange::Changed, CacheBustSeverity::Low, ms(0));
+        debouncer.push("node", PathBuf::from("a.js"), FileChange::Changed, CacheBustSeverity::Low, ms(50));
+        debouncer.push("node", PathBuf::from("b.json"), FileChange::Removed, CacheBustSeverity::High, ms(90));
+        debouncer.push("web", PathBuf::from("index.ts"), FileChange::Changed, CacheBustSeverity::Medium, ms(10));
+        assert_eq!(debouncer.next_due(), Some(ms(190)));
+
+        assert!(debouncer.ready(ms(150)).is_empty());
+        let ready = debouncer.ready(ms(200));
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].target, "node");
+        assert_eq!(ready[0].severity, CacheBustSeverity::High);
+        assert_eq!(ready[0].events, 3);
+        assert_eq!(
+            ready[0].reason(),
+            BustReason::FilesChanged { paths: vec!["a.js".to_string(), "b.json".to_string()], events: 3 }
+        );
+
+        let ready = debouncer.ready(ms(310));
+        assert_eq!(ready[0].reason(), BustReason::FileChanged { path: "index.ts".to_string() });
+        assert_eq!(debouncer.pending(), 0);
+    }
+
+    #[test]
+    fn test_busy_targets_are_released_after_the_hold_limit() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(10));
+        for i in 0..200 {
+            debouncer.push("node", PathBuf::from("a.js"), FileChange::Changed, CacheBustSeverity::Low, start + Duration::from_millis(i));
+        }
+        assert_eq!(debouncer.next_due(), Some(start + Duration::from_millis(100)));
+        assert_eq!(debouncer.ready(start + Duration::from_millis(200)).len(), 1);
+    }
+}
@@ -0,0 +1,277 @@
+//! Target discovery from project manifests
+//!
+//! Walks a workspace for package.json, pyproject.toml/requirements.txt,
+//! Cargo.toml, go.mod, CMakeLists.txt and friends, and proposes one
+//! `[target.<name>]` per package with a runtime and weights typical for its
+//! ecosystem. The result is a starting point to edit, not a final config.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::core::config::ConfigError;
+
+/// Directories that hold dependencies or build output, never packages of
+/// their own
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "venv",
+    "__pycache__",
+    "build",
+    "dist",
+    "vendor",
+];
+
+pub const DEFAULT_MAX_DEPTH: usize = 6;
+
+struct Ecosystem {
+    runtime: &'static str,
+    /// Any of these marks a package; the first one found names it
+    manifests: &'static [&'static str],
+    language_priority: f64,
+    dependency_impact: f64,
+    build_cost: f64,
+}
+
+const ECOSYSTEMS: &[Ecosystem] = &[
+    Ecosystem {
+        runtime: "node",
+        manifests: &["package.json"],
+        language_priority: 0.8,
+        dependency_impact: 0.9,
+        build_cost: 0.4,
+    },
+    Ecosystem {
+        runtime: "python",
+        manifests: &["pyproject.toml", "setup.py", "requirements.txt", "Pipfile"],
+        language_priority: 0.7,
+        dependency_impact: 0.7,
+        build_cost: 0.3,
+    },
+    Ecosystem {
+        runtime: "rust",
+        manifests: &["Cargo.toml"],
+        language_priority: 0.7,
+        dependency_impact: 0.6,
+        build_cost: 0.9,
+    },
+    Ecosystem {
+        runtime: "go",
+        manifests: &["go.mod"],
+        language_priority: 0.6,
+        dependency_impact: 0.6,
+        build_cost: 0.5,
+    },
+    Ecosystem {
+        runtime: "c",
+        manifests: &["CMakeLists.txt", "meson.build", "Makefile"],
+        language_priority: 0.5,
+        dependency_impact: 0.5,
+        build_cost: 0.8,
+    },
+];
+
+/// A proposed `[target.<name>]`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveredTarget {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub manifest: PathBuf,
+    /// Relative to the discovery root
+    pub path: String,
+    pub runtime: String,
+    pub language_priority: f64,
+    pub dependency_impact: f64,
+    pub build_cost: f64,
+}
+
+/// Packages below `root`, ordered by path. Names are unique: a name taken by
+/// an earlier package gets the runtime, then a number appended.
+pub fn discover(root: &Path, max_depth: usize) -> Result<Vec<DiscoveredTarget>, ConfigError> {
+    let mut targets = Vec::new();
+    walk(root, root, max_depth, &mut targets)?;
+
+    let mut taken = HashSet::new();
+    for target in &mut targets {
+        let mut name = target.name.clone();
+        if taken.contains(&name) {
+            name = format!("{}-{}", target.name, target.runtime);
+        }
+        let base = name.clone();
+        let mut suffix = 2;
+        while taken.contains(&name) {
+            name = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        taken.insert(name.clone());
+        target.name = name;
+    }
+    Ok(targets)
+}
+
+fn walk(root: &Path, dir: &Path, depth: usize, targets: &mut Vec<DiscoveredTarget>) -> Result<(), ConfigError> {
+    for ecosystem in ECOSYSTEMS {
+        let Some(manifest) = ecosystem.manifests.iter().map(|name| dir.join(name)).find(|path| path.is_file()) else {
+            continue;
+        };
+        // A virtual Cargo workspace only lists its members
+        if ecosystem.runtime == "rust" && package_name(&manifest).is_none() && is_cargo_workspace(&manifest) {
+            continue;
+        }
+
+        let relative = dir.strip_prefix(root).unwrap_or(dir);
+        let path = if relative.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            relative.to_string_lossy().into_owned()
+        };
+        let name = package_name(&manifest)
+            .or_else(|| {
+                let dir = std::fs::canonicalize(dir).ok()?;
+                Some(dir.file_name()?.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| ecosystem.runtime.to_string());
+
+        targets.push(DiscoveredTarget {
+            name: target_name(&name),
+            manifest,
+            path,
+            runtime: ecosystem.runtime.to_string(),
+            language_priority: ecosystem.language_priority,
+            dependency_impact: ecosystem.dependency_impact,
+            build_cost: ecosystem.build_cost,
+        });
+    }
+
+    if depth == 0 {
+        return Ok(());
+    }
+    let mut children: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .collect();
+    children.sort();
+    for child in children {
+        walk(root, &child, depth - 1, targets)?;
+    }
+    Ok(())
+}
+
+/// The package name declared in `manifest`, where the format has one
+fn package_name(manifest: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let file = manifest.file_name()?.to_str()?;
+    let name = match file {
+        "package.json" => {
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            json.get("name")?.as_str()?.to_string()
+        }
+        "Cargo.toml" => {
+            let toml: toml::Table = content.parse().ok()?;
+            toml.get("package")?.get("name")?.as_str()?.to_string()
+        }
+        "pyproject.toml" => {
+            let toml: toml::Table = content.parse().ok()?;
+            let project = toml.get("project").and_then(|project| project.get("name"));
+            let poetry = || toml.get("tool")?.get("poetry")?.get("name");
+            project.or_else(poetry)?.as_str()?.to_string()
+        }
+        "go.mod" => {
+            let module = content.lines().find_map(|line| line.trim().strip_prefix("module "))?;
+            module.trim().rsplit('/').next()?.to_string()
+        }
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn is_cargo_workspace(manifest: &Path) -> bool {
+    std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|toml| toml.contains_key("workspace"))
+}
+
+/// `@scope/My Package` -> `my-package`
+fn target_name(name: &str) -> String {
+    let name = name.rsplit('/').next().unwrap_or(name);
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "target".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `[target.<name>]` tables for `targets`, each preceded by a comment naming
+/// its manifest
+pub fn render(targets: &[DiscoveredTarget]) -> Result<String, ConfigError> {
+    let mut out = String::new();
+    for target in targets {
+        let mut section = BTreeMap::new();
+        section.insert(target.name.clone(), target);
+        let mut document = BTreeMap::new();
+        document.insert("target", section);
+        let rendered = toml::to_string(&document).map_err(|e| ConfigError::Parse(e.to_string()))?;
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("# Discovered from {}\n", target.manifest.display()));
+        out.push_str(&rendered);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovers_packages_across_ecosystems() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+        write("crates/engine/Cargo.toml", "[package]\nname = \"engine\"\nversion = \"0.1.0\"\n");
+        write("web/package.json", r#"{"name": "@acme/web-app"}"#);
+        write("web/node_modules/left-pad/package.json", r#"{"name": "left-pad"}"#);
+        write("ml/requirements.txt", "numpy\n");
+        write("ml/pyproject.toml", "[project]\nname = \"Web App\"\n");
+        write("services/api/go.mod", "module github.com/acme/api\n\ngo 1.22\n");
+
+        let targets = discover(root, DEFAULT_MAX_DEPTH).unwrap();
+        let summary: Vec<(&str, &str, &str)> = targets
+            .iter()
+            .map(|t| (t.name.as_str(), t.path.as_str(), t.runtime.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("engine", "crates/engine", "rust"),
+                ("web-app", "ml", "python"),
+                ("api", "services/api", "go"),
+                ("web-app-node", "web", "node"),
+            ]
+        );
+
+        let rendered = render(&targets[3..]).unwrap();
+        assert!(rendered.contains("[target.web-app-node]\npath = \"web\"\nruntime = \"node\""), "{}", rendered);
+    }
+}
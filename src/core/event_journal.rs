@@ -0,0 +1,177 @@
+//! Journal of the file events the watcher assessed
+//!
+//! Every file event is appended with its path, kind and what the watcher
+//! made of it: the target, the severity and whether ignore rules or
+//! severity overrides dropped it. `bustcall watch replay` reads it back and
+//! assesses the events again against the current rules, showing what a
+//! change to `severity_overrides` or `ignore` would have done. The journal
+//! is bounded: once it reaches `max_bytes` it is moved to `<path>.1`,
+//! replacing the previous one, so at most two files' worth is kept.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind};
+use notify::EventKind;
+use serde::{Deserialize, Serialize};
+
+use crate::dimensional_cache::CacheBustSeverity;
+use crate::utils::error::{BustcallError, Result};
+
+pub const DEFAULT_JOURNAL_PATH: &str = "/tmp/bustcall-events.jsonl";
+pub const DEFAULT_JOURNAL_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// An event's kind, as far as assessing it goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEventKind {
+    Create,
+    Modify,
+    Remove,
+    Access,
+    Other,
+}
+
+impl JournalEventKind {
+    pub fn of(kind: &EventKind) -> Self {
+        match kind {
+            EventKind::Create(_) => JournalEventKind::Create,
+            EventKind::Modify(_) => JournalEventKind::Modify,
+            EventKind::Remove(_) => JournalEventKind::Remove,
+            EventKind::Access(_) => JournalEventKind::Access,
+            _ => JournalEventKind::Other,
+        }
+    }
+
+    /// An event kind of this kind, to assess the event again
+    pub fn event_kind(&self) -> EventKind {
+        match self {
+            JournalEventKind::Create => EventKind::Create(CreateKind::Any),
+            JournalEventKind::Modify => EventKind::Modify(ModifyKind::Any),
+            JournalEventKind::Remove => EventKind::Remove(RemoveKind::Any),
+            JournalEventKind::Access => EventKind::Access(AccessKind::Any),
+            JournalEventKind::Other => EventKind::Other,
+        }
+    }
+}
+
+/// What the watcher made of one path's event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assessment {
+    pub target: String,
+    /// None when the event warrants no bust
+    pub severity: Option<CacheBustSeverity>,
+    /// Dropped by the target's ignore rules or severity overrides
+    pub ignored: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub timestamp: DateTime<Utc>,
+    pub path: PathBuf,
+    pub kind: JournalEventKind,
+    #[serde(flatten)]
+    pub assessment: Assessment,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventJournal {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl EventJournal {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            max_bytes: DEFAULT_JOURNAL_MAX_BYTES,
+        }
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rolled(&self) -> PathBuf {
+        let mut rolled = self.path.clone().into_os_string();
+        rolled.push(".1");
+        PathBuf::from(rolled)
+    }
+
+    pub fn append(&self, record: &JournalRecord) -> Result<()> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| BustcallError::DaemonError(format!("Journal record serialization failed: {}", e)))?;
+        if fs::metadata(&self.path).is_ok_and(|metadata| metadata.len() + line.len() as u64 >= self.max_bytes) {
+            fs::rename(&self.path, self.rolled())?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Records since `since`, oldest first. A missing journal holds none.
+    pub fn read(&self, since: Option<DateTime<Utc>>) -> Result<Vec<JournalRecord>> {
+        let mut records = Vec::new();
+        for path in [self.rolled(), self.path.clone()] {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                match serde_json::from_str::<JournalRecord>(&line) {
+                    Ok(record) if since.is_none_or(|since| record.timestamp >= since) => records.push(record),
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Skipping corrupt journal line in {}: {}", path.display(), e),
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(path: &str, kind: JournalEventKind) -> JournalRecord {
+        JournalRecord {
+            timestamp: Utc::now(),
+            path: PathBuf::from(path),
+            kind,
+            assessment: Assessment {
+                target: "node".to_string(),
+                severity: Some(CacheBustSeverity::Medium),
+                ignored: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_journal_rolls_over_and_reads_back_in_order() {
+        let dir = TempDir::new().unwrap();
+        let journal = EventJournal::new(dir.path().join("events.jsonl")).with_max_bytes(400);
+        let start = Utc::now();
+        for i in 0..10 {
+            journal.append(&record(&format!("/repo/src/{}.ts", i), JournalEventKind::Modify)).unwrap();
+        }
+
+        let records = journal.read(None).unwrap();
+        assert!(records.len() < 10);
+        assert_eq!(records.last().unwrap().path, PathBuf::from("/repo/src/9.ts"));
+        assert!(records.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        assert!(fs::metadata(journal.path()).unwrap().len() <= 400);
+        assert!(journal.read(Some(start + chrono::Duration::hours(1))).unwrap().is_empty());
+
+        assert_eq!(JournalEventKind::of(&JournalEventKind::Remove.event_kind()), JournalEventKind::Remove);
+    }
+}
@@ -0,0 +1,168 @@
+//! Runtime environment fingerprints
+//!
+//! A long-running process can outlive the environment it was started from,
+//! e.g. Python upgraded underneath a venv. The fingerprint covers the
+//! executable (path and mtime), selected environment variables and the
+//! interpreter's `--version` output, so such changes can bust the target even
+//! though its pid never changed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dimensional_cache::CacheBustSeverity;
+
+/// Per-target `[target.<name>.fingerprint]` settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FingerprintConfig {
+    #[serde(default = "default_env_vars")]
+    pub env_vars: Vec<String>,
+    /// Run `<exe> --version` when the executable changes
+    #[serde(default = "default_probe_version")]
+    pub probe_version: bool,
+    #[serde(default = "default_severity")]
+    pub severity: CacheBustSeverity,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        Self {
+            env_vars: default_env_vars(),
+            probe_version: default_probe_version(),
+            severity: default_severity(),
+        }
+    }
+}
+
+fn default_env_vars() -> Vec<String> {
+    ["PATH", "VIRTUAL_ENV", "PYTHONPATH", "PYTHONHOME", "NODE_OPTIONS", "NODE_PATH", "LD_LIBRARY_PATH"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn default_probe_version() -> bool {
+    true
+}
+
+fn default_severity() -> CacheBustSeverity {
+    CacheBustSeverity::Medium
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnvFingerprint {
+    pub exe: PathBuf,
+    /// Seconds since the epoch; `None` once the executable was replaced or
+    /// removed
+    pub exe_mtime: Option<u64>,
+    /// Configured variables that are set, by name
+    pub env: BTreeMap<String, String>,
+    pub version: Option<String>,
+}
+
+impl EnvFingerprint {
+    /// Names of the parts that differ from `previous`
+    pub fn changes_from(&self, previous: &EnvFingerprint) -> Vec<String> {
+        let mut changes = Vec::new();
+        if self.exe != previous.exe || self.exe_mtime != previous.exe_mtime {
+            changes.push("exe".to_string());
+        }
+        if self.version != previous.version {
+            changes.push("version".to_string());
+        }
+
+        let mut names: Vec<&String> = self.env.keys().chain(previous.env.keys()).collect();
+        names.sort();
+        names.dedup();
+        changes.extend(
+            names
+                .into_iter()
+                .filter(|name| self.env.get(*name) != previous.env.get(*name))
+                .map(|name| format!("env:{}", name)),
+        );
+        changes
+    }
+}
+
+/// Builds fingerprints, remembering `--version` output per executable build
+#[derive(Debug, Default)]
+pub struct Fingerprinter {
+    versions: HashMap<(PathBuf, Option<u64>), Option<String>>,
+}
+
+impl Fingerprinter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fingerprint(&mut self, exe: &Path, environ: &[String], config: &FingerprintConfig) -> EnvFingerprint {
+        let exe_mtime = std::fs::metadata(exe)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|age| age.as_secs());
+
+        let env = environ
+            .iter()
+            .filter_map(|entry| entry.split_once('='))
+            .filter(|(name, _)| config.env_vars.iter().any(|wanted| wanted == name))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        let version = if config.probe_version && exe_mtime.is_some() {
+            self.versions
+                .entry((exe.to_path_buf(), exe_mtime))
+                .or_insert_with(|| probe_version(exe))
+                .clone()
+        } else {
+            None
+        };
+
+        EnvFingerprint {
+            exe: exe.to_path_buf(),
+            exe_mtime,
+            env,
+            version,
+        }
+    }
+}
+
+/// First line of `<exe> --version`; Python 2 prints it on stderr
+fn probe_version(exe: &Path) -> Option<String> {
+    let output = Command::new(exe).arg("--version").output().ok()?;
+    let text = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+    String::from_utf8_lossy(&text)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_name_exe_version_and_env() {
+        let config = FingerprintConfig {
+            probe_version: false,
+            ..FingerprintConfig::default()
+        };
+        let mut fingerprinter = Fingerprinter::new();
+        let exe = std::env::current_exe().unwrap();
+
+        let before = fingerprinter.fingerprint(&exe, &["VIRTUAL_ENV=/srv/venv".to_string(), "HOME=/root".to_string()], &config);
+        assert_eq!(before.env.len(), 1);
+        assert!(before.exe_mtime.is_some());
+
+        let mut after = fingerprinter.fingerprint(&exe, &["VIRTUAL_ENV=/srv/venv2".to_string()], &config);
+        after.version = Some("Python 3.12.1".to_string());
+        assert_eq!(after.changes_from(&before), vec!["version", "env:VIRTUAL_ENV"]);
+
+        let gone = fingerprinter.fingerprint(Path::new("/nonexistent/python3"), &[], &config);
+        assert!(gone.changes_from(&before).contains(&"exe".to_string()));
+    }
+}
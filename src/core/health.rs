@@ -0,0 +1,152 @@
+//! Health checks feeding self-healing
+//!
+//! Every `health_interval_seconds` of `[recovery]` each enabled target is
+//! sampled into [`HealthMetrics`] and scored 0-10: a runtime target whose
+//! processes are gone scores 0, its processes above `HIGH_CPU_PERCENT` cost
+//! 3, a cache hit ratio below `LOW_HIT_RATIO` since the last check costs 2,
+//! and each error reported for it per minute costs 2, at most 6. Hit ratios
+//! are cache-wide, so a low one alone never takes a target below the
+//! default threshold. A target scored below `health_threshold` for
+//! `unhealthy_checks` checks in a row is recovered like a failed bust; see
+//! [`crate::self_healing::SelfHealingArchitecture::evaluate_health`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::process::{ProcessManager, RuntimeLookup};
+use crate::dimensional_cache::DimensionalCacheManager;
+use crate::self_healing::HealthMetrics;
+
+pub const HIGH_CPU_PERCENT: f64 = 90.0;
+pub const LOW_HIT_RATIO: f64 = 0.5;
+
+/// Cache accesses needed since the last check for the hit ratio to count
+const MIN_ACCESSES: u64 = 20;
+
+/// Samples the metrics targets are scored by
+pub struct HealthProbe {
+    cache: Arc<DimensionalCacheManager>,
+    processes: ProcessManager,
+    /// How each runtime target's processes are found
+    runtimes: HashMap<String, RuntimeLookup>,
+    /// Cache hits and misses at the last sample
+    counted: Mutex<(u64, u64)>,
+}
+
+impl HealthProbe {
+    pub fn new(cache: Arc<DimensionalCacheManager>) -> Self {
+        Self {
+            cache,
+            processes: ProcessManager::new(),
+            runtimes: HashMap::new(),
+            counted: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Score `component` by the processes `lookup` finds too
+    pub fn with_runtime(mut self, component: &str, lookup: RuntimeLookup) -> Self {
+        self.runtimes.insert(component.to_string(), lookup);
+        self
+    }
+
+    /// Metrics of `components`, scored; `error_rates` are errors reported
+    /// per minute by component
+    pub fn sample(&self, components: &[String], error_rates: &HashMap<String, f64>) -> Vec<HealthMetrics> {
+        let cache_hit_ratio = self.hit_ratio();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        components
+            .iter()
+            .map(|component| {
+                let mut metrics = HealthMetrics {
+                    timestamp,
+                    component: component.clone(),
+                    health_score: 0,
+                    memory_usage_mb: 0.0,
+                    cpu_usage_percent: 0.0,
+                    cache_hit_ratio,
+                    error_rate: error_rates.get(component).copied().unwrap_or(0.0),
+                };
+                let running = self.runtimes.get(component).and_then(|lookup| {
+                    match self.processes.find_runtime(lookup) {
+                        Ok(processes) => {
+                            metrics.cpu_usage_percent = processes.iter().map(|process| process.cpu_usage).sum();
+                            metrics.memory_usage_mb =
+                                processes.iter().map(|process| process.memory_usage).sum::<u64>() as f64 / (1024.0 * 1024.0);
+                            Some(!processes.is_empty())
+                        }
+                        Err(e) => {
+                            log::debug!("Runtime of {} not sampled: {}", component, e);
+                            None
+                        }
+                    }
+                });
+                metrics.health_score = health_score(&metrics, running);
+                metrics
+            })
+            .collect()
+    }
+
+    /// Hit ratio of cache accesses since the last sample, 1 with too few
+    fn hit_ratio(&self) -> f64 {
+        let counts = self.cache.metrics();
+        let mut counted = self.counted.lock().unwrap_or_else(PoisonError::into_inner);
+        let (hits, misses) = (counts.hits.saturating_sub(counted.0), counts.misses.saturating_sub(counted.1));
+        *counted = (counts.hits, counts.misses);
+        if hits + misses < MIN_ACCESSES {
+            return 1.0;
+        }
+        hits as f64 / (hits + misses) as f64
+    }
+}
+
+/// 0-10 by the rules in the module docs; `running` is whether the
+/// component's runtime has processes, `None` without one to look up
+pub fn health_score(metrics: &HealthMetrics, running: Option<bool>) -> u8 {
+    if running == Some(false) {
+        return 0;
+    }
+    let mut score = 10.0;
+    if metrics.cpu_usage_percent >= HIGH_CPU_PERCENT {
+        score -= 3.0;
+    }
+    if metrics.cache_hit_ratio < LOW_HIT_RATIO {
+        score -= 2.0;
+    }
+    score -= (metrics.error_rate * 2.0).min(6.0);
+    score.max(0.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_and_samples_runtimes() {
+        let metrics = |cpu_usage_percent: f64, cache_hit_ratio: f64, error_rate: f64| HealthMetrics {
+            timestamp: 0,
+            component: "api".to_string(),
+            health_score: 0,
+            memory_usage_mb: 0.0,
+            cpu_usage_percent,
+            cache_hit_ratio,
+            error_rate,
+        };
+        assert_eq!(health_score(&metrics(5.0, 1.0, 0.0), None), 10);
+        assert_eq!(health_score(&metrics(5.0, 1.0, 0.0), Some(false)), 0);
+        assert_eq!(health_score(&metrics(95.0, 0.2, 0.0), Some(true)), 5);
+        assert_eq!(health_score(&metrics(5.0, 0.2, 0.5), None), 7);
+        assert_eq!(health_score(&metrics(95.0, 0.2, 10.0), None), 0);
+
+        let probe = HealthProbe::new(Arc::new(DimensionalCacheManager::new().unwrap()))
+            .with_runtime("api", RuntimeLookup::new("bustcall-health-test-no-such-runtime"));
+        let components = ["api".to_string(), "web".to_string()];
+        let sampled = probe.sample(&components, &HashMap::from([("web".to_string(), 1.0)]));
+        assert_eq!(
+            sampled.iter().map(|metrics| (metrics.component.as_str(), metrics.health_score)).collect::<Vec<_>>(),
+            vec![("api", 0), ("web", 8)]
+        );
+        assert_eq!(sampled[1].cache_hit_ratio, 1.0);
+    }
+}
@@ -0,0 +1,439 @@
+//! Dependency diffs of changed lockfiles
+//!
+//! A lockfile rewritten by `npm install` may pin the same packages as
+//! before, or hundreds of new ones. Rather than busting on any change, the
+//! watcher keeps the dependency set of every `package-lock.json`,
+//! `yarn.lock`, `poetry.lock` and `Cargo.lock` it has seen, and scales the
+//! bust by what a change did to it:
+//!
+//! - nothing: the change is tracked but not busted
+//! - a few transitive packages: Medium
+//! - a direct dependency, or `TRANSITIVE_HIGH` packages: High
+//! - `DIRECT_CRITICAL` direct dependencies, or `TOTAL_CRITICAL` packages:
+//!   Critical
+//!
+//! Direct dependencies are those the manifest next to the lockfile names:
+//! `package.json`, `pyproject.toml`, or the workspace members in
+//! `Cargo.lock` itself. A lockfile without a baseline, removed, or that
+//! cannot be parsed is busted Critical, as what changed is unknown.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::dimensional_cache::CacheBustSeverity;
+
+/// Changed packages from which a lockfile change busts High
+pub const TRANSITIVE_HIGH: usize = 20;
+/// Changed direct dependencies from which a lockfile change busts Critical
+pub const DIRECT_CRITICAL: usize = 5;
+/// Changed packages from which a lockfile change busts Critical
+pub const TOTAL_CRITICAL: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockfileKind {
+    /// `package-lock.json` or `npm-shrinkwrap.json`
+    PackageLock,
+    YarnLock,
+    PoetryLock,
+    CargoLock,
+}
+
+impl LockfileKind {
+    pub fn of(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(LockfileKind::PackageLock),
+            "yarn.lock" => Some(LockfileKind::YarnLock),
+            "poetry.lock" => Some(LockfileKind::PoetryLock),
+            "Cargo.lock" => Some(LockfileKind::CargoLock),
+            _ => None,
+        }
+    }
+}
+
+const LOCKFILE_NAMES: [&str; 5] = ["package-lock.json", "npm-shrinkwrap.json", "yarn.lock", "poetry.lock", "Cargo.lock"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LockedPackage {
+    /// Every version locked, as a package may be installed more than once
+    pub versions: BTreeSet<String>,
+    pub direct: bool,
+}
+
+/// Locked packages by name
+pub type DependencySet = BTreeMap<String, LockedPackage>;
+
+fn insert_locked(set: &mut DependencySet, name: &str, version: &str, direct: bool) {
+    let package = set.entry(name.to_string()).or_default();
+    package.versions.insert(version.to_string());
+    package.direct |= direct;
+}
+
+/// Dependency set of the lockfile at `path`, direct dependencies taken
+/// from the manifest next to it
+pub fn read_lockfile(path: &Path) -> Result<DependencySet, String> {
+    let kind = LockfileKind::of(path).ok_or_else(|| format!("{} is not a lockfile", path.display()))?;
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let set = match kind {
+        LockfileKind::PackageLock => parse_package_lock(&content, &package_json_names(dir)),
+        LockfileKind::YarnLock => Ok(parse_yarn_lock(&content, &package_json_names(dir))),
+        LockfileKind::PoetryLock => parse_poetry_lock(&content, &pyproject_names(dir)),
+        LockfileKind::CargoLock => parse_cargo_lock(&content),
+    };
+    set.map_err(|e| format!("failed to parse {}: {}", path.display(), e))
+}
+
+pub fn parse_package_lock(content: &str, direct: &HashSet<String>) -> Result<DependencySet, String> {
+    let lock: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut set = DependencySet::new();
+    // lockfileVersion 2 and 3: packages by install path
+    if let Some(packages) = lock.get("packages").and_then(Value::as_object) {
+        let direct = match packages.get("") {
+            Some(root) => manifest_names(root),
+            None => direct.clone(),
+        };
+        for (key, package) in packages {
+            // The root and workspace packages are not installed
+            let Some((parent, name)) = key.rsplit_once("node_modules/") else {
+                continue;
+            };
+            let Some(version) = package.get("version").and_then(Value::as_str) else {
+                continue;
+            };
+            insert_locked(&mut set, name, version, parent.is_empty() && direct.contains(name));
+        }
+        return Ok(set);
+    }
+
+    // lockfileVersion 1: nested `dependencies`
+    let mut pending: Vec<(&serde_json::Map<String, Value>, bool)> = Vec::new();
+    if let Some(dependencies) = lock.get("dependencies").and_then(Value::as_object) {
+        pending.push((dependencies, true));
+    }
+    while let Some((dependencies, top_level)) = pending.pop() {
+        for (name, dependency) in dependencies {
+            if let Some(version) = dependency.get("version").and_then(Value::as_str) {
+                insert_locked(&mut set, name, version, top_level && direct.contains(name));
+            }
+            if let Some(nested) = dependency.get("dependencies").and_then(Value::as_object) {
+                pending.push((nested, false));
+            }
+        }
+    }
+    Ok(set)
+}
+
+/// Parses both the classic format and the YAML-like one of Yarn 2 and up
+pub fn parse_yarn_lock(content: &str, direct: &HashSet<String>) -> DependencySet {
+    let mut set = DependencySet::new();
+    let mut names: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        // `"@babel/core@^7.0.0", "@babel/core@^7.1.0":` heads an entry
+        if !line.starts_with(' ') {
+            names = line.trim_end_matches(':').split(", ").filter_map(yarn_name).collect();
+            names.dedup();
+            continue;
+        }
+        let Some(version) = line.trim().strip_prefix("version") else {
+            continue;
+        };
+        let version = version.trim_start_matches(':').trim().trim_matches('"');
+        for name in names.drain(..) {
+            let direct = direct.contains(&name);
+            insert_locked(&mut set, &name, version, direct);
+        }
+    }
+    set
+}
+
+/// `@scope/name` of `"@scope/name@npm:^1.0.0"`
+fn yarn_name(spec: &str) -> Option<String> {
+    let spec = spec.trim().trim_matches('"');
+    let at = spec.get(1..)?.find('@')? + 1;
+    Some(spec[..at].to_string())
+}
+
+pub fn parse_poetry_lock(content: &str, direct: &HashSet<String>) -> Result<DependencySet, String> {
+    let lock: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+    let mut set = DependencySet::new();
+    for package in lock.get("package").and_then(toml::Value::as_array).into_iter().flatten() {
+        let name = package.get("name").and_then(toml::Value::as_str);
+        let version = package.get("version").and_then(toml::Value::as_str);
+        if let (Some(name), Some(version)) = (name, version) {
+            let name = python_name(name);
+            let direct = direct.contains(&name);
+            insert_locked(&mut set, &name, version, direct);
+        }
+    }
+    Ok(set)
+}
+
+/// Dependencies of the workspace members, the packages without a
+/// `source`, are direct; the members themselves are left out
+pub fn parse_cargo_lock(content: &str) -> Result<DependencySet, String> {
+    let lock: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+    let packages = lock.get("package").and_then(toml::Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    let (members, locked): (Vec<&toml::Value>, Vec<&toml::Value>) =
+        packages.iter().partition(|package| package.get("source").is_none());
+    let direct: HashSet<&str> = members
+        .iter()
+        .filter_map(|member| member.get("dependencies").and_then(toml::Value::as_array))
+        .flatten()
+        .filter_map(|dependency| dependency.as_str()?.split_whitespace().next())
+        .collect();
+
+    let mut set = DependencySet::new();
+    for package in locked {
+        let name = package.get("name").and_then(toml::Value::as_str);
+        let version = package.get("version").and_then(toml::Value::as_str);
+        if let (Some(name), Some(version)) = (name, version) {
+            insert_locked(&mut set, name, version, direct.contains(name));
+        }
+    }
+    Ok(set)
+}
+
+/// Names of `dependencies`, `devDependencies`, `optionalDependencies` and
+/// `peerDependencies` in a `package.json`, or a `package-lock.json` root
+fn manifest_names(manifest: &Value) -> HashSet<String> {
+    ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"]
+        .iter()
+        .filter_map(|section| manifest.get(section).and_then(Value::as_object))
+        .flat_map(|dependencies| dependencies.keys().cloned())
+        .collect()
+}
+
+fn package_json_names(dir: &Path) -> HashSet<String> {
+    fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .map(|manifest| manifest_names(&manifest))
+        .unwrap_or_default()
+}
+
+/// Names as pip compares them: lowercase, with `-` for `_` and `.`
+fn python_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Dependencies declared in `pyproject.toml`, under `[tool.poetry]` or
+/// PEP 621's `[project]`
+fn pyproject_names(dir: &Path) -> HashSet<String> {
+    let Some(pyproject) = fs::read_to_string(dir.join("pyproject.toml")).ok().and_then(|content| toml::from_str::<toml::Table>(&content).ok()) else {
+        return HashSet::new();
+    };
+    let mut names = HashSet::new();
+    if let Some(poetry) = pyproject.get("tool").and_then(|tool| tool.get("poetry")) {
+        let groups = poetry.get("group").and_then(toml::Value::as_table).into_iter().flat_map(|groups| groups.values());
+        let tables = [poetry.get("dependencies"), poetry.get("dev-dependencies")]
+            .into_iter()
+            .flatten()
+            .chain(groups.filter_map(|group| group.get("dependencies")));
+        for table in tables.filter_map(toml::Value::as_table) {
+            names.extend(table.keys().filter(|name| *name != "python").map(|name| python_name(name)));
+        }
+    }
+    if let Some(project) = pyproject.get("project") {
+        let optional = project.get("optional-dependencies").and_then(toml::Value::as_table).into_iter().flat_map(|extras| extras.values());
+        let requirements = project.get("dependencies").into_iter().chain(optional);
+        for requirement in requirements.filter_map(toml::Value::as_array).flatten().filter_map(toml::Value::as_str) {
+            let end = requirement.find(|c: char| !(c.is_ascii_alphanumeric() || "-_.".contains(c))).unwrap_or(requirement.len());
+            names.insert(python_name(&requirement[..end]));
+        }
+    }
+    names
+}
+
+/// Packages a lockfile change added, removed or moved to other versions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LockfileDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    /// The direct dependencies among them
+    pub direct: Vec<String>,
+}
+
+impl LockfileDiff {
+    pub fn between(old: &DependencySet, new: &DependencySet) -> Self {
+        let mut diff = LockfileDiff::default();
+        for (name, package) in new {
+            let list = match old.get(name) {
+                None => &mut diff.added,
+                Some(before) if before.versions != package.versions => &mut diff.changed,
+                Some(_) => continue,
+            };
+            list.push(name.clone());
+            if package.direct {
+                diff.direct.push(name.clone());
+            }
+        }
+        for (name, package) in old.iter().filter(|(name, _)| !new.contains_key(*name)) {
+            diff.removed.push(name.clone());
+            if package.direct {
+                diff.direct.push(name.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn total(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+
+    /// None when no locked package changed
+    pub fn severity(&self) -> Option<CacheBustSeverity> {
+        match (self.direct.len(), self.total()) {
+            (_, 0) => None,
+            (direct, total) if direct >= DIRECT_CRITICAL || total >= TOTAL_CRITICAL => Some(CacheBustSeverity::Critical),
+            (direct, total) if direct > 0 || total >= TRANSITIVE_HIGH => Some(CacheBustSeverity::High),
+            _ => Some(CacheBustSeverity::Medium),
+        }
+    }
+}
+
+/// What a lockfile event did to its dependencies
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockfileChange {
+    /// First seen, so there is nothing to compare with
+    New,
+    Removed,
+    Unreadable(String),
+    Changed(LockfileDiff),
+}
+
+impl LockfileChange {
+    pub fn severity(&self) -> Option<CacheBustSeverity> {
+        match self {
+            LockfileChange::Changed(diff) => diff.severity(),
+            _ => Some(CacheBustSeverity::Critical),
+        }
+    }
+}
+
+/// The last dependency set of each lockfile seen
+#[derive(Debug, Default)]
+pub struct LockfileSnapshots {
+    sets: HashMap<PathBuf, DependencySet>,
+}
+
+impl LockfileSnapshots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the lockfiles directly in `dir` as the baseline of later
+    /// changes
+    pub fn snapshot_dir(&mut self, dir: &Path) {
+        for name in LOCKFILE_NAMES {
+            let path = dir.join(name);
+            if let Ok(set) = read_lockfile(&path) {
+                self.sets.insert(path, set);
+            }
+        }
+    }
+
+    /// What an event on `path` did, `None` when it is no lockfile. An
+    /// unreadable lockfile keeps its last baseline.
+    pub fn observe(&mut self, path: &Path, removed: bool) -> Option<LockfileChange> {
+        LockfileKind::of(path)?;
+        if removed {
+            self.sets.remove(path);
+            return Some(LockfileChange::Removed);
+        }
+        let set = match read_lockfile(path) {
+            Ok(set) => set,
+            Err(e) => return Some(LockfileChange::Unreadable(e)),
+        };
+        Some(match self.sets.insert(path.to_path_buf(), set) {
+            Some(old) => LockfileChange::Changed(LockfileDiff::between(&old, &self.sets[path])),
+            None => LockfileChange::New,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockfiles_parse_with_direct_dependencies() {
+        let direct = HashSet::from(["react".to_string(), "@babel/core".to_string()]);
+        let package_lock = r#"{"lockfileVersion": 3, "packages": {
+            "": {"dependencies": {"react": "^18.0.0"}},
+            "node_modules/react": {"version": "18.2.0"},
+            "node_modules/loose-envify": {"version": "1.4.0"},
+            "node_modules/a/node_modules/react": {"version": "17.0.2"},
+            "packages/app": {"name": "app"}
+        }}"#;
+        let set = parse_package_lock(package_lock, &HashSet::new()).unwrap();
+        assert_eq!(set.len(), 2);
+        assert!(set["react"].direct && !set["loose-envify"].direct);
+        assert_eq!(set["react"].versions.len(), 2);
+
+        let yarn = "# yarn lockfile v1\n\n\"@babel/core@^7.0.0\", \"@babel/core@^7.1.0\":\n  version \"7.2.0\"\n  dependencies:\n    debug \"^4.1.0\"\n\ndebug@^4.1.0:\n  version \"4.3.4\"\n";
+        let set = parse_yarn_lock(yarn, &direct);
+        assert_eq!(set["@babel/core"].versions, BTreeSet::from(["7.2.0".to_string()]));
+        assert!(set["@babel/core"].direct && !set["debug"].direct);
+        let berry = "__metadata:\n  version: 6\n\n\"debug@npm:^4.1.0\":\n  version: 4.3.4\n";
+        assert_eq!(parse_yarn_lock(berry, &direct).keys().collect::<Vec<_>>(), ["debug"]);
+
+        let poetry = "[[package]]\nname = \"Django\"\nversion = \"4.2.1\"\n\n[[package]]\nname = \"sqlparse\"\nversion = \"0.4.4\"\n";
+        let set = parse_poetry_lock(poetry, &HashSet::from(["django".to_string()])).unwrap();
+        assert!(set["django"].direct && !set["sqlparse"].direct);
+
+        let cargo = "version = 3\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\"serde\", \"syn 2.0.0\"]\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"syn\"\nversion = \"2.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"unicode-ident\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+        let set = parse_cargo_lock(cargo).unwrap();
+        assert!(!set.contains_key("app"));
+        assert!(set["serde"].direct && set["syn"].direct && !set["unicode-ident"].direct);
+    }
+
+    #[test]
+    fn test_lockfile_changes_scale_severity() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package-lock.json");
+        let write = |packages: &[(&str, &str)]| {
+            let packages: serde_json::Map<String, Value> = packages
+                .iter()
+                .map(|(name, version)| (format!("node_modules/{}", name), serde_json::json!({ "version": version })))
+                .chain([(String::new(), serde_json::json!({ "dependencies": { "react": "*" } }))])
+                .collect();
+            fs::write(&path, serde_json::json!({ "lockfileVersion": 3, "packages": packages }).to_string()).unwrap();
+        };
+
+        write(&[("react", "18.2.0"), ("loose-envify", "1.4.0")]);
+        let mut snapshots = LockfileSnapshots::new();
+        assert_eq!(snapshots.observe(&dir.path().join("package.json"), false), None);
+        assert_eq!(snapshots.observe(&path, false), Some(LockfileChange::New));
+
+        // Reformatted, same packages
+        write(&[("loose-envify", "1.4.0"), ("react", "18.2.0")]);
+        assert_eq!(snapshots.observe(&path, false).unwrap().severity(), None);
+
+        write(&[("react", "18.2.0"), ("loose-envify", "1.4.1")]);
+        assert_eq!(snapshots.observe(&path, false).unwrap().severity(), Some(CacheBustSeverity::Medium));
+
+        write(&[("react", "18.3.0"), ("loose-envify", "1.4.1")]);
+        match snapshots.observe(&path, false).unwrap() {
+            LockfileChange::Changed(diff) => {
+                assert_eq!(diff.direct, ["react"]);
+                assert_eq!(diff.severity(), Some(CacheBustSeverity::High));
+            }
+            change => panic!("unexpected change {:?}", change),
+        }
+
+        let many: Vec<(String, String)> = (0..TOTAL_CRITICAL).map(|i| (format!("dep-{}", i), "1.0.0".to_string())).collect();
+        write(&many.iter().map(|(name, version)| (name.as_str(), version.as_str())).collect::<Vec<_>>());
+        assert_eq!(snapshots.observe(&path, false).unwrap().severity(), Some(CacheBustSeverity::Critical));
+
+        fs::write(&path, "{ truncated").unwrap();
+        assert!(matches!(snapshots.observe(&path, false), Some(LockfileChange::Unreadable(_))));
+        assert_eq!(snapshots.observe(&path, true), Some(LockfileChange::Removed));
+    }
+}
@@ -2,16 +2,85 @@
 //! OBINexus Core Module Implementation
 //! Constitutional compliance framework for bustcall daemon architecture
 
+pub mod audit;
+pub mod bust_executor;
+pub mod bust_hook;
+pub mod cache_bus;
+pub mod cache_tier;
+pub mod cgroup;
+pub mod container;
+pub mod content_hash;
 pub mod daemon;
+pub mod debounce;
+pub mod discover;
+pub mod event_journal;
+pub mod fingerprint;
+pub mod health;
+pub mod lockfile;
 pub mod notify;
+pub mod open_files;
 pub mod process;
 pub mod config;
+pub mod config_diff;
+pub mod config_migrate;
+pub mod config_resolver;
+pub mod config_validation;
+pub mod config_watch;
+pub mod plugin;
+pub mod poll_backoff;
+pub mod preflight;
+pub mod priority;
+pub mod probe;
+pub mod proc_events;
+pub mod rebuild;
+pub mod recovery;
+pub mod remote_config;
+pub mod sandbox;
+pub mod secrets;
+pub mod severity;
+pub mod schedule;
+pub mod storm;
+pub mod task_supervisor;
+pub mod watch;
+pub mod watch_backend;
+pub mod watch_ignore;
+pub mod watch_root;
+pub mod watch_template;
 
 // Re-export core types for library interface
-pub use daemon::{Daemon, DaemonConfig, DaemonStatus};
-pub use notify::{NotificationLevel, NotificationManager, NotifyResult};
-pub use process::{ProcessManager, ProcessInfo, ProcessFilter};
-pub use config::{BustcallConfig, ConfigError};
+pub use bust_executor::{BustAction, BustExecutor, BustExecutors, BustOutcome};
+pub use bust_hook::{BustHooks, HookOutcome, HookStage};
+pub use cache_bus::{open_bus, BusKind, BusSubscription, CacheBus, LocalBus};
+pub use cgroup::{CgroupMonitor, CgroupStats, PressureConfig};
+pub use container::{ContainerInfo, ContainerRuntime};
+pub use daemon::{Daemon, DaemonConfig, DaemonRole, DaemonStatus, LeaderElectionConfig, LeaderLock};
+pub use debounce::{Burst, Debouncer, FileChange};
+pub use fingerprint::{EnvFingerprint, FingerprintConfig, Fingerprinter};
+pub use health::HealthProbe;
+pub use poll_backoff::PollBackoff;
+pub use notify::{Notification, NotificationChannel, NotificationLevel, NotificationManager, NotifyResult};
+pub use process::{
+    MatchMode, MetricSample, MultipleMatches, ProcessFilter, ProcessInfo, ProcessManager, RestartOutcome, RestartPolicy,
+    RuntimeLookup,
+};
+pub use config::{BustcallConfig, ConfigError, ConfigFormat, GlobalConfig, TargetConfig};
+pub use config_diff::{ConfigDiff, KeyChange};
+pub use config_resolver::{ConfigResolver, ResolvedConfig};
+pub use config_validation::{validate_file, Diagnostic, DiagnosticLevel, ValidationReport};
+pub use config_watch::{ConfigReload, ConfigWatcher};
+pub use plugin::{DaemonEvent, DaemonPlugin, PluginRegistry};
+pub use preflight::{run_preflight, PreflightConfig, PreflightReport};
+pub use priority::{IoClass, ResourceGovernor, ResourcePolicy};
+pub use probe::{LivenessProbe, LivenessProber, ProbeCheck};
+pub use proc_events::{open_event_source, EventBackend, ProcessEvent, ProcessEventSource};
+pub use rebuild::{RebuildCommand, RebuildExecutor, RebuildOutcome};
+pub use recovery::{CommandActionConfig, RecoveryAction, RecoveryActions, RecoveryConfig};
+pub use remote_config::RemoteConfig;
+pub use sandbox::SandboxProfile;
+pub use schedule::{BustScheduler, QuietHours, QuietHoursAction, TargetSchedule};
+pub use watch::{ProcessAlarm, ProcessWatch, ProcessWatchConfig};
+pub use watch_backend::WatchBackend;
+pub use watch_ignore::IgnoreRules;
 
 // src/core/daemon.rs
 use std::sync::{Arc, Mutex};
@@ -127,245 +196,3 @@ impl Daemon {
         Ok(())
     }
 }
-
-// src/core/notify.rs
-use crate::utils::error::{BustcallError, Result};
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum NotificationLevel {
-    Info,
-    Warning,
-    Error,
-    Critical,
-}
-
-#[derive(Debug, Clone)]
-pub struct NotifyResult {
-    pub level: NotificationLevel,
-    pub message: String,
-    pub timestamp: std::time::SystemTime,
-}
-
-pub struct NotificationManager {
-    // Internal notification state
-}
-
-impl NotificationManager {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    pub fn send(&self, level: NotificationLevel, message: &str) -> Result<NotifyResult> {
-        let result = NotifyResult {
-            level: level.clone(),
-            message: message.to_string(),
-            timestamp: std::time::SystemTime::now(),
-        };
-
-        // Log notification based on level
-        match level {
-            NotificationLevel::Info => log::info!("{}", message),
-            NotificationLevel::Warning => log::warn!("{}", message),
-            NotificationLevel::Error => log::error!("{}", message),
-            NotificationLevel::Critical => {
-                log::error!("CRITICAL: {}", message);
-                // Could trigger additional alerting mechanisms
-            }
-        }
-
-        Ok(result)
-    }
-}
-
-// src/core/process.rs
-use std::collections::HashMap;
-use sysinfo::{ProcessExt, System, SystemExt};
-use crate::utils::error::{BustcallError, Result};
-
-#[derive(Debug, Clone)]
-pub struct ProcessInfo {
-    pub pid: u32,
-    pub name: String,
-    pub cpu_usage: f32,
-    pub memory_usage: u64,
-    pub status: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct ProcessFilter {
-    pub name_pattern: Option<String>,
-    pub min_cpu_usage: Option<f32>,
-    pub min_memory_usage: Option<u64>,
-}
-
-pub struct ProcessManager {
-    system: System,
-}
-
-impl ProcessManager {
-    pub fn new() -> Self {
-        Self {
-            system: System::new_all(),
-        }
-    }
-
-    pub fn refresh(&mut self) {
-        self.system.refresh_all();
-    }
-
-    pub fn get_processes(&self, filter: Option<ProcessFilter>) -> Vec<ProcessInfo> {
-        let mut processes = Vec::new();
-
-        for (pid, process) in self.system.processes() {
-            let process_info = ProcessInfo {
-                pid: pid.as_u32(),
-                name: process.name().to_string(),
-                cpu_usage: process.cpu_usage(),
-                memory_usage: process.memory(),
-                status: format!("{:?}", process.status()),
-            };
-
-            if let Some(ref filter) = filter {
-                if !self.matches_filter(&process_info, filter) {
-                    continue;
-                }
-            }
-
-            processes.push(process_info);
-        }
-
-        processes
-    }
-
-    fn matches_filter(&self, process: &ProcessInfo, filter: &ProcessFilter) -> bool {
-        if let Some(ref pattern) = filter.name_pattern {
-            if !process.name.contains(pattern) {
-                return false;
-            }
-        }
-
-        if let Some(min_cpu) = filter.min_cpu_usage {
-            if process.cpu_usage < min_cpu {
-                return false;
-            }
-        }
-
-        if let Some(min_memory) = filter.min_memory_usage {
-            if process.memory_usage < min_memory {
-                return false;
-            }
-        }
-
-        true
-    }
-}
-
-// src/core/config.rs
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
-use crate::utils::error::{BustcallError, Result};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BustcallConfig {
-    pub global: GlobalConfig,
-    pub target: HashMap<String, TargetConfig>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GlobalConfig {
-    pub self_healing: bool,
-    pub supervisor_mode: bool,
-    pub default_max_retries: u32,
-    pub daemon_interval_seconds: u64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TargetConfig {
-    pub path: String,
-    pub runtime: String,
-    pub pid_watch: bool,
-    pub enabled: bool,
-    pub language_priority: f64,
-    pub dependency_impact: f64,
-    pub build_cost: f64,
-    pub critical_path: bool,
-}
-
-#[derive(Debug, thiserror::Error)]
-pub enum ConfigError {
-    #[error("Configuration file not found: {0}")]
-    NotFound(String),
-    #[error("Configuration parse error: {0}")]
-    ParseError(String),
-    #[error("IO error: {0}")]
-    IoError(#[from] std::io::Error),
-    #[error("TOML error: {0}")]
-    TomlError(#[from] toml::de::Error),
-}
-
-impl BustcallConfig {
-    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        if !path.exists() {
-            return Err(BustcallError::ConfigError(ConfigError::NotFound(
-                path.display().to_string(),
-            )));
-        }
-
-        let content = fs::read_to_string(path)
-            .map_err(|e| BustcallError::ConfigError(ConfigError::IoError(e)))?;
-
-        let config: BustcallConfig = toml::from_str(&content)
-            .map_err(|e| BustcallError::ConfigError(ConfigError::TomlError(e)))?;
-
-        Ok(config)
-    }
-
-    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let content = toml::to_string_pretty(self)
-            .map_err(|e| BustcallError::ConfigError(ConfigError::ParseError(e.to_string())))?;
-
-        fs::write(path, content)
-            .map_err(|e| BustcallError::ConfigError(ConfigError::IoError(e)))?;
-
-        Ok(())
-    }
-
-    pub fn default() -> Self {
-        let mut targets = HashMap::new();
-        
-        targets.insert("node".to_string(), TargetConfig {
-            path: "./node_modules".to_string(),
-            runtime: "node".to_string(),
-            pid_watch: true,
-            enabled: true,
-            language_priority: 0.8,
-            dependency_impact: 0.9,
-            build_cost: 0.7,
-            critical_path: true,
-        });
-
-        targets.insert("python".to_string(), TargetConfig {
-            path: "./venv/lib".to_string(),
-            runtime: "python3".to_string(),
-            pid_watch: true,
-            enabled: true,
-            language_priority: 0.7,
-            dependency_impact: 0.8,
-            build_cost: 0.6,
-            critical_path: false,
-        });
-
-        Self {
-            global: GlobalConfig {
-                self_healing: true,
-                supervisor_mode: true,
-                default_max_retries: 3,
-                daemon_interval_seconds: 5,
-            },
-            target: targets,
-        }
-    }
-}
\ No newline at end of file
@@ -1,6 +1,49 @@
 use crate::utils::error::{BustcallError, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "notify-desktop")]
+pub mod desktop;
+#[cfg(feature = "notify-webhooks")]
+pub mod discord;
+pub mod digest;
+#[cfg(feature = "daemon")]
+pub mod dispatch;
+#[cfg(feature = "notify-email")]
+pub mod email;
+pub mod escalation;
+pub mod exec;
+pub mod history;
+#[cfg(feature = "notify-webhooks")]
+pub mod incident;
+#[cfg(unix)]
+pub mod journald;
+#[cfg(feature = "notify-webhooks")]
+pub mod matrix;
+pub mod mute;
+pub mod policy;
+pub mod retry;
+#[cfg(feature = "notify-webhooks")]
+pub mod slack;
+pub mod syslog;
+#[cfg(feature = "notify-webhooks")]
+pub mod teams;
+pub mod throttle;
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use self::escalation::{EscalationConfig, EscalationTracker};
+use self::history::{ChannelOutcome, HistoryFilter, NotificationHistory, NotificationRecord};
+use self::mute::MuteList;
+use self::policy::{PolicyEngine, PolicyInvocation, PolicyRule};
+use self::retry::{ChannelHealth, HealthChange, RetryConfig};
+use self::throttle::{NotificationThrottle, ThrottleConfig, ThrottleDecision};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum NotificationLevel {
     Info,
     Warning,
@@ -8,20 +51,910 @@ pub enum NotificationLevel {
     Critical,
 }
 
-pub type NotifyResult = Result<()>;
+/// Outcome of a single `NotificationManager::send*` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotifyResult {
+    /// History id, also used to acknowledge escalations; 0 when suppressed
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+    /// One entry per channel that accepted the level
+    pub outcomes: Vec<ChannelOutcome>,
+    /// Swallowed by deduplication, rate limiting or a mute
+    pub suppressed: bool,
+}
+
+impl NotifyResult {
+    fn suppressed(notification: &Notification) -> Self {
+        Self {
+            id: 0,
+            level: notification.level,
+            message: notification.text(),
+            timestamp: Utc::now(),
+            outcomes: Vec::new(),
+            suppressed: true,
+        }
+    }
+
+    /// True when no channel reported a failure
+    pub fn delivered(&self) -> bool {
+        self.outcomes.iter().all(|o| o.delivered)
+    }
+
+    pub fn failures(&self) -> Vec<&ChannelOutcome> {
+        self.outcomes.iter().filter(|o| !o.delivered).collect()
+    }
+
+    /// Turn channel failures into an error for callers that only care
+    /// whether everything went out
+    pub fn into_result(self) -> Result<Self> {
+        if self.delivered() {
+            return Ok(self);
+        }
+
+        let failures = self
+            .failures()
+            .iter()
+            .map(|o| format!("{}: {}", o.channel, o.error.as_deref().unwrap_or("unknown error")))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(BustcallError::NotificationError(failures))
+    }
+}
+
+/// Notification with the context channels need to route and render it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// Error-hashing severity score, 0 (OK) to 12 (catastrophic)
+    #[serde(default)]
+    pub severity: u8,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl Notification {
+    pub fn new(level: NotificationLevel, body: &str) -> Self {
+        Self {
+            level,
+            title: None,
+            body: body.to_string(),
+            target: None,
+            component: None,
+            severity: level.default_severity(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn component(mut self, component: &str) -> Self {
+        self.component = Some(component.to_string());
+        self
+    }
+
+    pub fn severity(mut self, severity: u8) -> Self {
+        self.severity = severity.min(12);
+        self
+    }
+
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Plain-text form used by channels without richer formatting
+    pub fn text(&self) -> String {
+        match &self.title {
+            Some(title) => format!("{}: {}", title, self.body),
+            None => self.body.clone(),
+        }
+    }
+
+    /// Value of a template variable; metadata keys are looked up last
+    pub fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "level" => Some(self.level.as_str().to_string()),
+            "LEVEL" => Some(self.level.as_str().to_uppercase()),
+            "message" => Some(self.text()),
+            "body" => Some(self.body.clone()),
+            "title" => self.title.clone(),
+            "target" => self.target.clone(),
+            "component" => self.component.clone(),
+            "severity" => Some(self.severity.to_string()),
+            other => self.metadata.get(other).cloned(),
+        }
+    }
+}
+
+/// Default message template. See `render_template` for the variables.
+pub const DEFAULT_TEMPLATE: &str = "[{level}] {message}";
+
+/// Outbound notification backend
+pub trait NotificationChannel: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Whether this channel wants notifications of the given level
+    fn accepts(&self, _level: NotificationLevel) -> bool {
+        true
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()>;
+
+    /// Deliver the full structured notification. Channels that use context
+    /// beyond the text (e.g. incident dedup keys by target) override this.
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        self.deliver(notification.level, &notification.text())
+    }
+
+    /// Whether a failed delivery is worth retrying. Configuration errors
+    /// (bad credentials, rejected payloads) are not.
+    fn is_transient(&self, error: &BustcallError) -> bool {
+        !matches!(error, BustcallError::ConfigError(_))
+    }
+
+    /// Periodic housekeeping, e.g. sending a digest whose period ended
+    fn poll(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Deliver anything the channel is holding back (e.g. digests)
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleConfig {
+    #[serde(default = "default_console_template")]
+    pub template: String,
+}
+
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        Self {
+            template: default_console_template(),
+        }
+    }
+}
+
+fn default_console_template() -> String {
+    "[{LEVEL}] {message}".to_string()
+}
+
+/// Writes notifications to stdout
+#[derive(Debug, Default)]
+pub struct ConsoleChannel {
+    config: ConsoleConfig,
+}
+
+impl ConsoleChannel {
+    pub fn new(config: ConsoleConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl NotificationChannel for ConsoleChannel {
+    fn name(&self) -> &str {
+        "console"
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        println!("{}", render_template(&self.config.template, notification));
+        Ok(())
+    }
+}
 
-#[derive(Debug)]
 pub struct NotificationManager {
-    // Implementation details
+    channels: Vec<Box<dyn NotificationChannel>>,
+    throttle: Option<Mutex<NotificationThrottle>>,
+    history: Option<Mutex<NotificationHistory>>,
+    escalation: Option<Mutex<EscalationTracker>>,
+    policy: Option<PolicyEngine>,
+    policy_actions: Mutex<Vec<PolicyInvocation>>,
+    retry: RetryConfig,
+    health: Mutex<ChannelHealth>,
+    mutes: Option<Mutex<MuteList>>,
+    next_id: AtomicU64,
+}
+
+impl std::fmt::Debug for NotificationManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationManager")
+            .field("channels", &self.channel_names())
+            .finish()
+    }
+}
+
+impl Default for NotificationManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl NotificationManager {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            channels: vec![Box::new(ConsoleChannel::default())],
+            throttle: None,
+            history: None,
+            escalation: None,
+            policy: None,
+            policy_actions: Mutex::new(Vec::new()),
+            retry: RetryConfig::none(),
+            health: Mutex::new(ChannelHealth::default()),
+            mutes: None,
+            next_id: AtomicU64::new(1),
+        }
     }
-    
-    pub fn send(&self, level: NotificationLevel, message: &str) -> NotifyResult {
-        println!("[{:?}] {}", level, message);
-        Ok(())
+
+    /// Re-send unacknowledged notifications along an escalation chain
+    pub fn with_escalation(mut self, config: EscalationConfig) -> Self {
+        self.escalation = Some(Mutex::new(EscalationTracker::new(config)));
+        self
+    }
+
+    /// Acknowledge a notification, stopping further escalation
+    pub fn acknowledge(&self, id: u64) -> bool {
+        self.escalation
+            .as_ref()
+            .is_some_and(|e| e.lock().unwrap().acknowledge(id))
+    }
+
+    /// Deliver due escalations; call periodically from the daemon loop
+    pub fn process_escalations(&self) -> Result<()> {
+        let Some(escalation) = &self.escalation else {
+            return Ok(());
+        };
+
+        let steps = escalation.lock().unwrap().poll()?;
+        let mut failures = Vec::new();
+
+        for step in steps {
+            let Some(channel) = self.channels.iter().find(|c| c.name() == step.channel) else {
+                failures.push(format!("{}: escalation channel not configured", step.channel));
+                continue;
+            };
+
+            let pending = &step.notification;
+            let mut escalated = Notification::new(
+                pending.level,
+                &format!(
+                    "[ESCALATED #{}] {} (unacknowledged, ack with `bustcall ack {}`)",
+                    pending.id, pending.message, pending.id
+                ),
+            );
+            escalated.target = pending.target.clone();
+
+            log::warn!("📣 Escalating notification #{} to {}", pending.id, step.channel);
+            if let Err(e) = channel.deliver_notification(&escalated) {
+                failures.push(format!("{}: {}", step.channel, e));
+            }
+        }
+
+        Self::collect_failures(failures)
+    }
+
+    /// Honour mutes written to `path` by `bustcall notify mute`
+    pub fn with_mutes<P: AsRef<std::path::Path>>(mut self, path: P) -> Self {
+        self.mutes = Some(Mutex::new(MuteList::open(path)));
+        self
+    }
+
+    /// Retry transient channel failures with exponential backoff
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = config;
+        self
+    }
+
+    /// Trigger automatic actions for notifications matching these rules
+    pub fn with_policy(mut self, rules: Vec<PolicyRule>) -> Self {
+        self.policy = Some(PolicyEngine::new(rules));
+        self
+    }
+
+    /// Take the policy actions triggered since the last call; the daemon
+    /// executes them with `Daemon::run_policy_actions`
+    pub fn take_policy_actions(&self) -> Vec<PolicyInvocation> {
+        std::mem::take(&mut *self.policy_actions.lock().unwrap())
+    }
+
+    /// Record every delivered notification in the given history
+    pub fn with_history(mut self, history: NotificationHistory) -> Self {
+        self.history = Some(Mutex::new(history));
+        self
+    }
+
+    /// Query recorded notifications, newest first
+    pub fn history(&self, filter: &HistoryFilter) -> Vec<NotificationRecord> {
+        self.history
+            .as_ref()
+            .map(|h| h.lock().unwrap().query(filter))
+            .unwrap_or_default()
+    }
+
+    /// Deduplicate and rate limit everything sent through this manager
+    pub fn with_throttle(mut self, config: &ThrottleConfig) -> Self {
+        self.throttle = Some(Mutex::new(NotificationThrottle::new(config)));
+        self
+    }
+
+    /// Build a manager with the channels enabled in configuration
+    pub fn from_config(config: &crate::core::config::NotificationConfig) -> Result<Self> {
+        let mut manager = Self {
+            channels: Vec::new(),
+            throttle: None,
+            history: None,
+            escalation: None,
+            policy: None,
+            policy_actions: Mutex::new(Vec::new()),
+            retry: RetryConfig::none(),
+            health: Mutex::new(ChannelHealth::default()),
+            mutes: None,
+            next_id: AtomicU64::new(1),
+        };
+        if let Some(escalation) = &config.escalation {
+            manager = manager.with_escalation(escalation.clone());
+        }
+        if let Some(path) = &config.history_path {
+            manager = manager.with_history(NotificationHistory::open(path, config.history_capacity)?);
+        }
+        if let Some(throttle) = &config.throttle {
+            manager = manager.with_throttle(throttle);
+        }
+        if let Some(retry) = &config.retry {
+            manager = manager.with_retry(retry.clone());
+        }
+        if let Some(path) = &config.mute_path {
+            manager = manager.with_mutes(path);
+        }
+        if !config.policy.is_empty() {
+            manager = manager.with_policy(config.policy.clone());
+        }
+        if !config.enabled {
+            return Ok(manager);
+        }
+
+        for name in &config.channels {
+            match name.as_str() {
+                "console" => {
+                    let console = config.console.clone().unwrap_or_default();
+                    manager.add_channel(Box::new(ConsoleChannel::new(console)));
+                }
+                "syslog" => {
+                    let syslog = config.syslog.clone().unwrap_or_default();
+                    manager.add_channel(Box::new(syslog::SyslogChannel::new(syslog)?));
+                }
+                "exec" => {
+                    let exec = config.exec.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("exec channel enabled without [notifications.exec]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(exec::ExecChannel::new(exec)));
+                }
+                #[cfg(unix)]
+                "journald" => {
+                    let journald = config.journald.clone().unwrap_or_default();
+                    manager.add_channel(Box::new(journald::JournaldChannel::new(journald)?));
+                }
+                #[cfg(feature = "notify-webhooks")]
+                "slack" => {
+                    let slack = config.slack.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("slack channel enabled without [notifications.slack]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(slack::SlackChannel::new(slack)?));
+                }
+                #[cfg(feature = "notify-webhooks")]
+                "discord" => {
+                    let discord = config.discord.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("discord channel enabled without [notifications.discord]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(discord::DiscordChannel::new(discord)));
+                }
+                #[cfg(feature = "notify-webhooks")]
+                "teams" => {
+                    let teams = config.teams.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("teams channel enabled without [notifications.teams]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(teams::TeamsChannel::new(teams)));
+                }
+                #[cfg(feature = "notify-webhooks")]
+                "matrix" => {
+                    let matrix = config.matrix.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("matrix channel enabled without [notifications.matrix]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(matrix::MatrixChannel::new(matrix)?));
+                }
+                #[cfg(feature = "notify-desktop")]
+                "desktop" => {
+                    let desktop = config.desktop.clone().unwrap_or_default();
+                    manager.add_channel(Box::new(desktop::DesktopChannel::new(desktop)));
+                }
+                #[cfg(feature = "notify-webhooks")]
+                "pagerduty" | "opsgenie" => {
+                    let incident = config.incident.clone().ok_or_else(|| {
+                        BustcallError::ConfigError(format!("{} channel enabled without [notifications.incident]", name))
+                    })?;
+                    manager.add_channel(Box::new(incident::IncidentChannel::new(incident)));
+                }
+                #[cfg(feature = "notify-email")]
+                "email" => {
+                    let email = config.email.clone().ok_or_else(|| {
+                        BustcallError::ConfigError("email channel enabled without [notifications.email]".to_string())
+                    })?;
+                    manager.add_channel(Box::new(email::EmailChannel::new(email)?));
+                }
+                other => {
+                    return Err(BustcallError::ConfigError(format!(
+                        "Unknown or disabled notification channel: {}",
+                        other
+                    )))
+                }
+            }
+        }
+
+        if let Some(digest) = &config.digest {
+            manager.channels = std::mem::take(&mut manager.channels)
+                .into_iter()
+                .map(|channel| -> Box<dyn NotificationChannel> {
+                    if digest.channels.iter().any(|name| name == channel.name()) {
+                        Box::new(digest::DigestChannel::new(channel, digest))
+                    } else {
+                        channel
+                    }
+                })
+                .collect();
+        }
+
+        Ok(manager)
+    }
+
+    pub fn add_channel(&mut self, channel: Box<dyn NotificationChannel>) {
+        self.channels.push(channel);
+    }
+
+    pub fn channel_names(&self) -> Vec<&str> {
+        self.channels.iter().map(|c| c.name()).collect()
+    }
+
+    pub fn send(&self, level: NotificationLevel, message: &str) -> Result<NotifyResult> {
+        self.send_for_target(level, None, message)
+    }
+
+    pub fn send_for_target(&self, level: NotificationLevel, target: Option<&str>, message: &str) -> Result<NotifyResult> {
+        let mut notification = Notification::new(level, message);
+        notification.target = target.map(str::to_string);
+        self.send_structured(&notification)
+    }
+
+    /// Send a structured notification. Duplicates of the same
+    /// (level, target, text) are collapsed when throttling is enabled.
+    ///
+    /// Channel failures do not make this an `Err`; they are reported in
+    /// `NotifyResult::outcomes` (see `NotifyResult::into_result`).
+    pub fn send_structured(&self, notification: &Notification) -> Result<NotifyResult> {
+        self.emit_suppressed_summaries();
+
+        if let Some(mutes) = &self.mutes {
+            if mutes.lock().unwrap().is_muted(notification) {
+                return Ok(self.record_muted(notification));
+            }
+        }
+
+        if let Some(throttle) = &self.throttle {
+            let text = notification.text();
+            match throttle.lock().unwrap().check(notification.level, notification.target.as_deref(), &text) {
+                ThrottleDecision::Deliver => {}
+                ThrottleDecision::Duplicate | ThrottleDecision::RateLimited => {
+                    return Ok(NotifyResult::suppressed(notification))
+                }
+            }
+        }
+
+        Ok(self.deliver(notification))
+    }
+
+    /// Keep a muted notification in history without sending it anywhere.
+    /// Policy actions still run; only outbound delivery is muted.
+    fn record_muted(&self, notification: &Notification) -> NotifyResult {
+        log::debug!("🔇 Muted notification: {}", notification.text());
+
+        let mut result = NotifyResult::suppressed(notification);
+        if let Some(history) = &self.history {
+            match history.lock().unwrap().record_muted(
+                notification.level,
+                notification.target.as_deref(),
+                &result.message,
+            ) {
+                Ok(id) => result.id = id,
+                Err(e) => log::warn!("Failed to record notification history: {}", e),
+            }
+        }
+
+        self.apply_policy(notification);
+        result
+    }
+
+    fn apply_policy(&self, notification: &Notification) {
+        if let Some(policy) = &self.policy {
+            let invocations = policy.evaluate(notification);
+            if !invocations.is_empty() {
+                self.policy_actions.lock().unwrap().extend(invocations);
+            }
+        }
+    }
+
+    /// Report duplicates swallowed by dedup windows that have now closed
+    fn emit_suppressed_summaries(&self) {
+        let Some(throttle) = &self.throttle else {
+            return;
+        };
+
+        let (summaries, rate_limited) = {
+            let mut throttle = throttle.lock().unwrap();
+            (throttle.drain_expired(), throttle.take_rate_limited())
+        };
+
+        for summary in summaries {
+            let mut notification = Notification::new(summary.level, &summary.render());
+            notification.target = summary.target.clone();
+            self.deliver(&notification);
+        }
+        if rate_limited > 0 {
+            self.deliver(&Notification::new(
+                NotificationLevel::Warning,
+                &format!("Rate limit dropped {} notifications", rate_limited),
+            ));
+        }
+    }
+
+    /// Deliver to every channel that accepts the level. A failing channel
+    /// does not stop delivery to the others.
+    fn deliver(&self, notification: &Notification) -> NotifyResult {
+        let level = notification.level;
+        let target = notification.target.as_deref();
+        let message = notification.text();
+        let timestamp = Utc::now();
+        let mut outcomes = Vec::new();
+
+        for channel in self.channels.iter().filter(|c| c.accepts(level)) {
+            let (result, attempts) = self.deliver_with_retry(channel.as_ref(), notification);
+            if let Err(e) = &result {
+                log::warn!(
+                    "Notification channel {} failed after {} attempt(s): {}",
+                    channel.name(),
+                    attempts,
+                    e
+                );
+            }
+            self.track_health(channel.name(), &result);
+            outcomes.push(ChannelOutcome {
+                channel: channel.name().to_string(),
+                delivered: result.is_ok(),
+                attempts,
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+
+        let recorded = self.history.as_ref().and_then(|history| {
+            history
+                .lock()
+                .unwrap()
+                .record(level, target, &message, outcomes.clone())
+                .map_err(|e| log::warn!("Failed to record notification history: {}", e))
+                .ok()
+        });
+        let id = recorded.unwrap_or_else(|| self.next_id.fetch_add(1, Ordering::SeqCst));
+
+        if let Some(escalation) = &self.escalation {
+            let mut escalation = escalation.lock().unwrap();
+            if escalation.applies_to(level) {
+                escalation.track(id, level, target, &message);
+            }
+        }
+
+        self.apply_policy(notification);
+
+        NotifyResult {
+            id,
+            level,
+            message,
+            timestamp,
+            outcomes,
+            suppressed: false,
+        }
+    }
+
+    fn deliver_with_retry(&self, channel: &dyn NotificationChannel, notification: &Notification) -> (Result<()>, u32) {
+        let mut attempts = 1;
+        loop {
+            match channel.deliver_notification(notification) {
+                Err(e) if attempts < self.retry.max_attempts && channel.is_transient(&e) => {
+                    let delay = self.retry.backoff(attempts);
+                    log::debug!(
+                        "Retrying {} in {:?} after attempt {} failed: {}",
+                        channel.name(),
+                        delay,
+                        attempts,
+                        e
+                    );
+                    std::thread::sleep(delay);
+                    attempts += 1;
+                }
+                result => return (result, attempts),
+            }
+        }
+    }
+
+    /// Report channels that went down or came back on the console, which
+    /// does not depend on any remote service
+    fn track_health(&self, channel: &str, result: &Result<()>) {
+        let down_after = self.retry.down_after;
+        let change = {
+            let mut health = self.health.lock().unwrap();
+            match result {
+                Ok(()) => health.record_success(channel, down_after),
+                Err(_) => health.record_failure(channel, down_after),
+            }
+        };
+
+        let meta = match (change, result) {
+            (Some(HealthChange::Down { failures }), Err(e)) => Notification::new(
+                NotificationLevel::Warning,
+                &format!(
+                    "📉 Notification channel {} is down after {} consecutive failures: {}",
+                    channel, failures, e
+                ),
+            ),
+            (Some(HealthChange::Recovered), _) => Notification::new(
+                NotificationLevel::Info,
+                &format!("📈 Notification channel {} recovered", channel),
+            ),
+            _ => return,
+        }
+        .component("notify");
+
+        let console = self.channels.iter().find(|c| c.name() == "console");
+        let delivered = match console {
+            Some(console) => console.deliver_notification(&meta),
+            None => ConsoleChannel::default().deliver_notification(&meta),
+        };
+        if let Err(e) = delivered {
+            log::warn!("Failed to report channel health: {}", e);
+        }
+    }
+
+    /// Run channel housekeeping such as periodic digests; call from the
+    /// daemon loop alongside `process_escalations`
+    pub fn poll(&self) -> Result<()> {
+        let failures = self
+            .channels
+            .iter()
+            .filter_map(|c| c.poll().err().map(|e| format!("{}: {}", c.name(), e)))
+            .collect();
+
+        Self::collect_failures(failures)
+    }
+
+    /// Flush batched notifications on every channel, e.g. at shutdown
+    pub fn flush(&self) -> Result<()> {
+        self.emit_suppressed_summaries();
+
+        let failures = self
+            .channels
+            .iter()
+            .filter_map(|c| c.flush().err().map(|e| format!("{}: {}", c.name(), e)))
+            .collect();
+
+        Self::collect_failures(failures)
+    }
+
+    fn collect_failures(failures: Vec<String>) -> Result<()> {
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(BustcallError::NotificationError(failures.join("; ")))
+        }
+    }
+}
+
+impl NotificationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "info",
+            NotificationLevel::Warning => "warning",
+            NotificationLevel::Error => "error",
+            NotificationLevel::Critical => "critical",
+        }
+    }
+
+    /// RFC 5424 severity: crit, err, warning, info
+    pub fn syslog_priority(&self) -> u8 {
+        match self {
+            NotificationLevel::Info => 6,
+            NotificationLevel::Warning => 4,
+            NotificationLevel::Error => 3,
+            NotificationLevel::Critical => 2,
+        }
+    }
+
+    /// Lower bound of the level's band in the 0-12 severity scale
+    pub fn default_severity(&self) -> u8 {
+        match self {
+            NotificationLevel::Info => 0,
+            NotificationLevel::Warning => 3,
+            NotificationLevel::Error => 6,
+            NotificationLevel::Critical => 9,
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationLevel {
+    type Err = BustcallError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(NotificationLevel::Info),
+            "warning" | "warn" => Ok(NotificationLevel::Warning),
+            "error" => Ok(NotificationLevel::Error),
+            "critical" => Ok(NotificationLevel::Critical),
+            other => Err(BustcallError::NotificationError(format!(
+                "Unknown notification level: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Render a template such as `💥 {target} busted ({severity}) after {trigger}`.
+///
+/// Built-in variables are `{level}`, `{LEVEL}`, `{message}`, `{body}`,
+/// `{title}`, `{target}`, `{component}` and `{severity}`; any other name is
+/// looked up in the notification metadata. Placeholders without a value are
+/// left as written so a misspelt variable is visible in the output.
+pub fn render_template(template: &str, notification: &Notification) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            let valid = !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            valid
+                .then(|| notification.field(name))
+                .flatten()
+                .map(|value| (value, close))
+        });
+
+        match value {
+            Some((value, close)) => {
+                rendered.push_str(&value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+/// POST a JSON payload, treating any non-2xx response as a failure.
+/// `authorization` is the full header value, e.g. `Bearer <token>`.
+#[cfg(feature = "notify-webhooks")]
+pub(crate) fn post_json(url: &str, body: &serde_json::Value, authorization: Option<&str>) -> Result<()> {
+    send_json("POST", url, body, authorization)
+}
+
+/// Like `post_json` with an explicit HTTP method (e.g. Matrix uses PUT)
+#[cfg(feature = "notify-webhooks")]
+pub(crate) fn send_json(method: &str, url: &str, body: &serde_json::Value, authorization: Option<&str>) -> Result<()> {
+    let mut request = ureq::request(method, url).timeout(std::time::Duration::from_secs(10));
+    if let Some(authorization) = authorization {
+        request = request.set("Authorization", authorization);
+    }
+
+    request.send_json(body.clone()).map(|_| ()).map_err(|e| match e {
+        // Other 4xx responses mean the request itself is wrong; retrying won't help
+        ureq::Error::Status(code, _) if (400..500).contains(&code) && code != 408 && code != 429 => {
+            BustcallError::ConfigError(format!("{} {} rejected with {}", method, url, code))
+        }
+        e => BustcallError::NotificationError(format!("{} {} failed: {}", method, url, e)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_with_metadata() {
+        let notification = Notification::new(NotificationLevel::Error, "rebuild failed")
+            .target("node")
+            .severity(7)
+            .with_metadata("trigger", "package.json")
+            .with_metadata("eta", "30s");
+
+        let rendered = render_template(
+            "💥 {target} busted ({severity}) after {trigger} — rebuild ETA {eta}",
+            &notification,
+        );
+        assert_eq!(rendered, "💥 node busted (7) after package.json — rebuild ETA 30s");
+    }
+
+    struct FailingChannel;
+
+    impl NotificationChannel for FailingChannel {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn deliver(&self, _level: NotificationLevel, _message: &str) -> Result<()> {
+            Err(BustcallError::NotificationError("unreachable".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_notify_result_reports_outcomes_per_channel() {
+        let mut manager = NotificationManager::new();
+        manager.add_channel(Box::new(FailingChannel));
+
+        let result = manager.send(NotificationLevel::Warning, "disk almost full").unwrap();
+        assert_eq!(result.outcomes.len(), 2);
+        assert!(!result.delivered());
+        assert_eq!(result.failures()[0].channel, "failing");
+        assert!(result.into_result().is_err());
+    }
+
+    #[test]
+    fn test_transient_failures_are_retried() {
+        let mut manager = NotificationManager::new().with_retry(RetryConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+            down_after: 2,
+        });
+        manager.add_channel(Box::new(FailingChannel));
+
+        let result = manager.send(NotificationLevel::Error, "webhook down").unwrap();
+        assert_eq!(result.failures()[0].attempts, 3);
+
+        manager.send(NotificationLevel::Error, "webhook still down").unwrap();
+        assert_eq!(manager.health.lock().unwrap().consecutive_failures("failing"), 2);
+    }
+
+    #[test]
+    fn test_render_template_keeps_unknown_placeholders() {
+        let notification = Notification::new(NotificationLevel::Info, "hello {eta}");
+        assert_eq!(
+            render_template("[{LEVEL}] {message} {missing} {", &notification),
+            "[INFO] hello {eta} {missing} {"
+        );
     }
 }
@@ -0,0 +1,82 @@
+//! Desktop toast notifications for interactive local use
+//!
+//! Backed by notify-rust, which targets the freedesktop notification spec on
+//! Linux/BSD, the macOS notification center and Windows toasts.
+
+use serde::{Deserialize, Serialize};
+
+use super::{render_template, Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopConfig {
+    /// Lowest level that raises a toast; quieter levels stay in the log
+    #[serde(default = "default_min_level")]
+    pub min_level: NotificationLevel,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+impl Default for DesktopConfig {
+    fn default() -> Self {
+        Self {
+            min_level: default_min_level(),
+            template: default_template(),
+        }
+    }
+}
+
+fn default_min_level() -> NotificationLevel {
+    NotificationLevel::Warning
+}
+
+fn default_template() -> String {
+    "{message}".to_string()
+}
+
+pub struct DesktopChannel {
+    config: DesktopConfig,
+}
+
+impl DesktopChannel {
+    pub fn new(config: DesktopConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl NotificationChannel for DesktopChannel {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        level >= self.config.min_level
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let level = notification.level;
+        let summary = notification
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("bustcall {}", level.as_str()));
+        let mut toast = notify_rust::Notification::new();
+        toast
+            .appname("bustcall")
+            .summary(&summary)
+            .body(&render_template(&self.config.template, notification));
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        if level == NotificationLevel::Critical {
+            toast.urgency(notify_rust::Urgency::Critical);
+        }
+
+        toast
+            .show()
+            .map(|_| ())
+            .map_err(|e| BustcallError::NotificationError(format!("Desktop notification failed: {}", e)))
+    }
+}
@@ -0,0 +1,228 @@
+//! Digest mode for noisy channels
+//!
+//! A channel in digest mode holds low-severity notifications and sends a
+//! periodic summary instead: counts per level and target, the most frequent
+//! triggers (`trigger` metadata) and bust totals (`bust_severity` metadata).
+//! Levels outside the digest still go out immediately.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::{BustcallError, Result};
+
+const TOP_TRIGGERS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    /// Channels switched to digest mode, by name
+    pub channels: Vec<String>,
+    #[serde(default = "default_levels")]
+    pub levels: Vec<NotificationLevel>,
+    #[serde(default = "default_interval_minutes")]
+    pub interval_minutes: u64,
+}
+
+fn default_levels() -> Vec<NotificationLevel> {
+    vec![NotificationLevel::Info, NotificationLevel::Warning]
+}
+
+fn default_interval_minutes() -> u64 {
+    60
+}
+
+#[derive(Debug, Default)]
+struct DigestSummary {
+    total: usize,
+    by_level: BTreeMap<NotificationLevel, usize>,
+    by_target: BTreeMap<String, usize>,
+    triggers: HashMap<String, usize>,
+    busts: BTreeMap<String, usize>,
+}
+
+impl DigestSummary {
+    fn add(&mut self, notification: &Notification) {
+        self.total += 1;
+        *self.by_level.entry(notification.level).or_default() += 1;
+        if let Some(target) = &notification.target {
+            *self.by_target.entry(target.clone()).or_default() += 1;
+        }
+        if let Some(trigger) = notification.metadata.get("trigger") {
+            *self.triggers.entry(trigger.clone()).or_default() += 1;
+        }
+        if let Some(severity) = notification.metadata.get("bust_severity") {
+            *self.busts.entry(severity.clone()).or_default() += 1;
+        }
+    }
+
+    fn render(&self, interval: Duration) -> String {
+        let mut lines = vec![format!(
+            "{} notifications in the last {} min",
+            self.total,
+            interval.as_secs() / 60
+        )];
+
+        let levels = self
+            .by_level
+            .iter()
+            .map(|(level, count)| format!("{} {}", count, level.as_str()))
+            .collect::<Vec<_>>();
+        lines.push(format!("Levels: {}", levels.join(", ")));
+
+        if !self.by_target.is_empty() {
+            let targets = self
+                .by_target
+                .iter()
+                .map(|(target, count)| format!("{} ({})", target, count))
+                .collect::<Vec<_>>();
+            lines.push(format!("Targets: {}", targets.join(", ")));
+        }
+
+        if !self.triggers.is_empty() {
+            let mut triggers = self.triggers.iter().collect::<Vec<_>>();
+            triggers.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            let top = triggers
+                .iter()
+                .take(TOP_TRIGGERS)
+                .map(|(trigger, count)| format!("{} ({})", trigger, count))
+                .collect::<Vec<_>>();
+            lines.push(format!("Top triggers: {}", top.join(", ")));
+        }
+
+        if !self.busts.is_empty() {
+            let total: usize = self.busts.values().sum();
+            let by_severity = self
+                .busts
+                .iter()
+                .map(|(severity, count)| format!("{} {}", count, severity))
+                .collect::<Vec<_>>();
+            lines.push(format!("Busts: {} ({})", total, by_severity.join(", ")));
+        }
+
+        lines.join("\n")
+    }
+}
+
+struct DigestState {
+    summary: DigestSummary,
+    started: Instant,
+}
+
+/// Wraps a channel, batching digest levels into periodic summaries
+pub struct DigestChannel {
+    inner: Box<dyn NotificationChannel>,
+    levels: Vec<NotificationLevel>,
+    interval: Duration,
+    state: Mutex<DigestState>,
+}
+
+impl DigestChannel {
+    pub fn new(inner: Box<dyn NotificationChannel>, config: &DigestConfig) -> Self {
+        Self {
+            inner,
+            levels: config.levels.clone(),
+            interval: Duration::from_secs(config.interval_minutes * 60),
+            state: Mutex::new(DigestState {
+                summary: DigestSummary::default(),
+                started: Instant::now(),
+            }),
+        }
+    }
+
+    /// Send the summary if anything was collected, and start a new period
+    fn send_summary(&self) -> Result<()> {
+        let summary = {
+            let mut state = self.state.lock().unwrap();
+            state.started = Instant::now();
+            std::mem::take(&mut state.summary)
+        };
+        if summary.total == 0 {
+            return Ok(());
+        }
+
+        let level = summary
+            .by_level
+            .keys()
+            .max()
+            .copied()
+            .unwrap_or(NotificationLevel::Info);
+        let digest = Notification::new(level, &summary.render(self.interval))
+            .title("bustcall digest")
+            .component("digest")
+            .with_metadata("digest_count", &summary.total.to_string());
+
+        self.inner.deliver_notification(&digest)
+    }
+
+    fn period_elapsed(&self) -> bool {
+        self.state.lock().unwrap().started.elapsed() >= self.interval
+    }
+}
+
+impl NotificationChannel for DigestChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        self.inner.accepts(level)
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        if !self.levels.contains(&notification.level) {
+            return self.inner.deliver_notification(notification);
+        }
+
+        self.state.lock().unwrap().summary.add(notification);
+        self.poll()
+    }
+
+    fn is_transient(&self, error: &BustcallError) -> bool {
+        self.inner.is_transient(error)
+    }
+
+    fn poll(&self) -> Result<()> {
+        if self.period_elapsed() {
+            self.send_summary()?;
+        }
+        self.inner.poll()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.send_summary()?;
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts_targets_triggers_and_busts() {
+        let mut summary = DigestSummary::default();
+        for trigger in ["package.json", "package.json", "tsconfig.json"] {
+            summary.add(
+                &Notification::new(NotificationLevel::Info, "busted")
+                    .target("node")
+                    .with_metadata("trigger", trigger)
+                    .with_metadata("bust_severity", "Medium"),
+            );
+        }
+        summary.add(&Notification::new(NotificationLevel::Warning, "slow").target("python"));
+
+        let rendered = summary.render(Duration::from_secs(3600));
+        assert!(rendered.starts_with("4 notifications in the last 60 min"));
+        assert!(rendered.contains("Levels: 3 info, 1 warning"));
+        assert!(rendered.contains("Targets: node (3), python (1)"));
+        assert!(rendered.contains("Top triggers: package.json (2), tsconfig.json (1)"));
+        assert!(rendered.contains("Busts: 3 (3 Medium)"));
+    }
+}
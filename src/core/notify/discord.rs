@@ -0,0 +1,76 @@
+//! Discord notification channel (webhooks)
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{post_json, render_template, Notification, NotificationChannel, NotificationLevel, DEFAULT_TEMPLATE};
+use crate::utils::error::Result;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordConfig {
+    /// Webhook used for levels without a dedicated route
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Discord webhooks are bound to one channel, so routing is per URL
+    #[serde(default)]
+    pub routes: HashMap<NotificationLevel, String>,
+    #[serde(default = "default_username")]
+    pub username: String,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+fn default_username() -> String {
+    "bustcall".to_string()
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+pub struct DiscordChannel {
+    config: DiscordConfig,
+}
+
+impl DiscordChannel {
+    pub fn new(config: DiscordConfig) -> Self {
+        Self { config }
+    }
+
+    fn webhook_for(&self, level: NotificationLevel) -> Option<&String> {
+        self.config
+            .routes
+            .get(&level)
+            .or(self.config.webhook_url.as_ref())
+    }
+}
+
+impl NotificationChannel for DiscordChannel {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        self.webhook_for(level).is_some()
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let level = notification.level;
+        let Some(webhook) = self.webhook_for(level) else {
+            return Ok(());
+        };
+
+        let content = render_template(&self.config.template, notification);
+        post_json(
+            webhook,
+            &json!({ "username": self.config.username, "content": content }),
+            None,
+        )
+    }
+}
@@ -0,0 +1,221 @@
+//! Asynchronous notification dispatch
+//!
+//! Callers enqueue notifications without waiting on network channels; a
+//! single worker task drains the bounded queue through `NotificationManager`.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use super::{Notification, NotificationLevel, NotificationManager};
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued notification to make room
+    #[default]
+    DropOldest,
+    /// Reject the new notification with an error
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatchConfig {
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+    #[serde(default)]
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for DispatchConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_capacity(),
+            overflow: OverflowPolicy::default(),
+        }
+    }
+}
+
+fn default_capacity() -> usize {
+    1024
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    queue: Mutex<VecDeque<Notification>>,
+    /// Queued plus currently delivering
+    in_flight: AtomicUsize,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+    work: Notify,
+    idle: Notify,
+}
+
+pub struct NotificationDispatcher {
+    shared: Arc<Shared>,
+    manager: Arc<NotificationManager>,
+    config: DispatchConfig,
+    worker: JoinHandle<()>,
+}
+
+impl NotificationDispatcher {
+    /// Spawn the worker task; must be called from within a Tokio runtime
+    pub fn spawn(manager: Arc<NotificationManager>, config: DispatchConfig) -> Self {
+        let shared = Arc::new(Shared::default());
+        let worker = tokio::spawn(Self::run_worker(Arc::clone(&shared), Arc::clone(&manager)));
+
+        Self {
+            shared,
+            manager,
+            config,
+            worker,
+        }
+    }
+
+    /// Enqueue without blocking on delivery
+    pub fn send(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.send_for_target(level, None, message)
+    }
+
+    pub fn send_for_target(&self, level: NotificationLevel, target: Option<&str>, message: &str) -> Result<()> {
+        let mut notification = Notification::new(level, message);
+        notification.target = target.map(str::to_string);
+        self.send_structured(&notification)
+    }
+
+    /// Enqueue a structured notification, keeping its title and metadata
+    pub fn send_structured(&self, notification: &Notification) -> Result<()> {
+        if self.shared.closed.load(Ordering::SeqCst) {
+            return Err(BustcallError::NotificationError("Dispatcher is shut down".to_string()));
+        }
+
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            if queue.len() >= self.config.capacity {
+                match self.config.overflow {
+                    OverflowPolicy::DropOldest => {
+                        queue.pop_front();
+                        self.shared.in_flight.fetch_sub(1, Ordering::SeqCst);
+                        self.shared.dropped.fetch_add(1, Ordering::SeqCst);
+                    }
+                    OverflowPolicy::Fail => {
+                        return Err(BustcallError::NotificationError(format!(
+                            "Notification queue full ({} pending)",
+                            queue.len()
+                        )));
+                    }
+                }
+            }
+            self.shared.in_flight.fetch_add(1, Ordering::SeqCst);
+            queue.push_back(notification.clone());
+        }
+
+        self.shared.work.notify_one();
+        Ok(())
+    }
+
+    /// Notifications discarded by the drop-oldest overflow policy
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::SeqCst)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.shared.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait until every queued notification is delivered, then flush
+    /// channel-level batches
+    pub async fn flush(&self) -> Result<()> {
+        loop {
+            let idle = self.shared.idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+
+            if self.shared.in_flight.load(Ordering::SeqCst) == 0 {
+                break;
+            }
+            idle.await;
+        }
+
+        let manager = Arc::clone(&self.manager);
+        tokio::task::spawn_blocking(move || manager.flush())
+            .await
+            .map_err(|e| BustcallError::NotificationError(format!("Flush task failed: {}", e)))?
+    }
+
+    /// `flush` for callers outside the runtime, such as `Daemon::stop`:
+    /// blocks until the queue is delivered or `timeout` passes
+    pub fn drain(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        while self.pending() > 0 {
+            if Instant::now() >= deadline {
+                return Err(BustcallError::NotificationError(format!(
+                    "{} queued notifications not delivered",
+                    self.pending()
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        self.manager.flush()
+    }
+
+    /// Drain the queue and stop the worker; used during daemon shutdown
+    pub async fn shutdown(self) -> Result<()> {
+        let result = self.flush().await;
+        self.shared.closed.store(true, Ordering::SeqCst);
+        self.shared.work.notify_one();
+        let _ = self.worker.await;
+        result
+    }
+
+    async fn run_worker(shared: Arc<Shared>, manager: Arc<NotificationManager>) {
+        loop {
+            let notification = shared.queue.lock().unwrap().pop_front();
+
+            let Some(notification) = notification else {
+                if shared.closed.load(Ordering::SeqCst) {
+                    break;
+                }
+                shared.work.notified().await;
+                continue;
+            };
+
+            let manager = Arc::clone(&manager);
+            let delivery = tokio::task::spawn_blocking(move || manager.send_structured(&notification)).await;
+
+            match delivery.map(|sent| sent.and_then(|result| result.into_result())) {
+                Ok(Err(e)) => log::warn!("Queued notification failed: {}", e),
+                Err(e) => log::error!("Notification worker task panicked: {}", e),
+                Ok(Ok(_)) => {}
+            }
+
+            if shared.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+                shared.idle.notify_waiters();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flush_drains_queue() {
+        let dispatcher = NotificationDispatcher::spawn(Arc::new(NotificationManager::new()), DispatchConfig::default());
+
+        for i in 0..10 {
+            dispatcher.send(NotificationLevel::Info, &format!("event {}", i)).unwrap();
+        }
+
+        dispatcher.flush().await.unwrap();
+        assert_eq!(dispatcher.pending(), 0);
+        dispatcher.shutdown().await.unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+//! SMTP notification channel with digest batching
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::{Deserialize, Serialize};
+
+use super::{render_template, Notification, NotificationChannel, NotificationLevel, DEFAULT_TEMPLATE};
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    None,
+    StartTls,
+    #[default]
+    Tls,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub tls: SmtpTls,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    pub from: String,
+    /// Recipients per level; levels without recipients are not emailed
+    pub recipients: HashMap<NotificationLevel, Vec<String>>,
+    /// Levels coalesced into a periodic digest instead of sent one by one
+    #[serde(default = "default_batch_levels")]
+    pub batch_levels: Vec<NotificationLevel>,
+    #[serde(default = "default_batch_interval_minutes")]
+    pub batch_interval_minutes: u64,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+fn default_batch_levels() -> Vec<NotificationLevel> {
+    vec![NotificationLevel::Warning]
+}
+
+fn default_batch_interval_minutes() -> u64 {
+    15
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+struct PendingDigest {
+    entries: Vec<(NotificationLevel, String)>,
+    started: Instant,
+}
+
+pub struct EmailChannel {
+    config: EmailConfig,
+    transport: SmtpTransport,
+    from: Mailbox,
+    pending: Mutex<PendingDigest>,
+}
+
+impl EmailChannel {
+    pub fn new(config: EmailConfig) -> Result<Self> {
+        let smtp_error = |e: lettre::transport::smtp::Error| {
+            BustcallError::ConfigError(format!("Invalid SMTP configuration: {}", e))
+        };
+
+        let mut builder = match config.tls {
+            SmtpTls::Tls => SmtpTransport::relay(&config.host).map_err(smtp_error)?,
+            SmtpTls::StartTls => SmtpTransport::starttls_relay(&config.host).map_err(smtp_error)?,
+            SmtpTls::None => SmtpTransport::builder_dangerous(&config.host),
+        };
+
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let (Some(user), Some(pass)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(user.clone(), pass.clone()));
+        }
+
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| BustcallError::ConfigError(format!("Invalid from address: {}", e)))?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            pending: Mutex::new(PendingDigest {
+                entries: Vec::new(),
+                started: Instant::now(),
+            }),
+            config,
+        })
+    }
+
+    fn batch_interval(&self) -> Duration {
+        Duration::from_secs(self.config.batch_interval_minutes * 60)
+    }
+
+    fn send_mail(&self, recipients: &[String], subject: &str, body: String) -> Result<()> {
+        let mut builder = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in recipients {
+            let mailbox: Mailbox = recipient.parse().map_err(|e| {
+                BustcallError::NotificationError(format!("Invalid recipient {}: {}", recipient, e))
+            })?;
+            builder = builder.to(mailbox);
+        }
+
+        let email = builder
+            .body(body)
+            .map_err(|e| BustcallError::NotificationError(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(&email)
+            .map(|_| ())
+            .map_err(|e| BustcallError::NotificationError(format!("SMTP delivery failed: {}", e)))
+    }
+
+    /// Send one digest per level holding everything batched so far
+    fn send_digest(&self, entries: Vec<(NotificationLevel, String)>) -> Result<()> {
+        let mut by_level: HashMap<NotificationLevel, Vec<String>> = HashMap::new();
+        for (level, message) in entries {
+            by_level.entry(level).or_default().push(message);
+        }
+
+        for (level, messages) in by_level {
+            let Some(recipients) = self.config.recipients.get(&level) else {
+                continue;
+            };
+            let subject = format!("[bustcall] {} {} notifications", messages.len(), level.as_str());
+            self.send_mail(recipients, &subject, messages.join("\n"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl NotificationChannel for EmailChannel {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        self.config
+            .recipients
+            .get(&level)
+            .is_some_and(|r| !r.is_empty())
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let level = notification.level;
+        let text = render_template(&self.config.template, notification);
+
+        if self.config.batch_levels.contains(&level) {
+            let ready = {
+                let mut pending = self.pending.lock().unwrap();
+                if pending.entries.is_empty() {
+                    pending.started = Instant::now();
+                }
+                pending.entries.push((level, text));
+                pending.started.elapsed() >= self.batch_interval()
+            };
+
+            return if ready { self.flush() } else { Ok(()) };
+        }
+
+        let recipients = &self.config.recipients[&level];
+        let subject = match &notification.title {
+            Some(title) => format!("[bustcall] {}", title),
+            None => format!("[bustcall] {}", level.as_str().to_uppercase()),
+        };
+        self.send_mail(recipients, &subject, text)
+    }
+
+    fn flush(&self) -> Result<()> {
+        let entries = std::mem::take(&mut self.pending.lock().unwrap().entries);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        self.send_digest(entries)
+    }
+}
@@ -0,0 +1,233 @@
+//! Escalation of unacknowledged notifications
+//!
+//! Notifications at escalating levels must be acknowledged (`bustcall ack <id>`
+//! or the HTTP API) within `ack_timeout_secs`; otherwise they are re-sent to
+//! the next channel in the escalation chain. The ack file is only appended
+//! to, and ids restart with each run, so acks written before a tracker was
+//! created are not read.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::NotificationLevel;
+use crate::utils::error::Result;
+
+pub const DEFAULT_ACK_PATH: &str = "/tmp/bustcall-acks";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    #[serde(default = "default_ack_timeout_secs")]
+    pub ack_timeout_secs: u64,
+    /// Channel names tried in order, one per elapsed timeout
+    pub chain: Vec<String>,
+    #[serde(default = "default_levels")]
+    pub levels: Vec<NotificationLevel>,
+    #[serde(default = "default_ack_path")]
+    pub ack_path: String,
+}
+
+fn default_ack_timeout_secs() -> u64 {
+    300
+}
+
+fn default_levels() -> Vec<NotificationLevel> {
+    vec![NotificationLevel::Critical]
+}
+
+fn default_ack_path() -> String {
+    DEFAULT_ACK_PATH.to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingEscalation {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub target: Option<String>,
+    pub message: String,
+    raised_at: Instant,
+    step: usize,
+}
+
+/// Next delivery the manager should perform for an unacknowledged notification
+#[derive(Debug, Clone)]
+pub struct EscalationStep {
+    pub channel: String,
+    pub notification: PendingEscalation,
+}
+
+#[derive(Debug)]
+pub struct EscalationTracker {
+    config: EscalationConfig,
+    pending: HashMap<u64, PendingEscalation>,
+    /// Length of the ack file when this run started
+    ack_offset: u64,
+}
+
+impl EscalationTracker {
+    pub fn new(config: EscalationConfig) -> Self {
+        let ack_offset = std::fs::metadata(&config.ack_path).map_or(0, |meta| meta.len());
+        Self {
+            config,
+            pending: HashMap::new(),
+            ack_offset,
+        }
+    }
+
+    pub fn applies_to(&self, level: NotificationLevel) -> bool {
+        self.config.levels.contains(&level)
+    }
+
+    pub fn track(&mut self, id: u64, level: NotificationLevel, target: Option<&str>, message: &str) {
+        log::warn!("🔔 Notification #{} requires acknowledgement: `bustcall ack {}`", id, id);
+        self.pending.insert(
+            id,
+            PendingEscalation {
+                id,
+                level,
+                target: target.map(str::to_string),
+                message: message.to_string(),
+                raised_at: Instant::now(),
+                step: 0,
+            },
+        );
+    }
+
+    pub fn acknowledge(&mut self, id: u64) -> bool {
+        self.pending.remove(&id).is_some()
+    }
+
+    pub fn pending(&self) -> Vec<u64> {
+        self.pending.keys().copied().collect()
+    }
+
+    /// Pick up acknowledgements written by other processes, then return the
+    /// escalations that are due
+    pub fn poll(&mut self) -> Result<Vec<EscalationStep>> {
+        for id in read_acks(&self.config.ack_path, &mut self.ack_offset)? {
+            self.acknowledge(id);
+        }
+        Ok(self.due_at(Instant::now()))
+    }
+
+    fn due_at(&mut self, now: Instant) -> Vec<EscalationStep> {
+        let timeout = Duration::from_secs(self.config.ack_timeout_secs);
+        let chain = &self.config.chain;
+        let mut steps = Vec::new();
+
+        self.pending.retain(|_, pending| {
+            let deadline = timeout * (pending.step as u32 + 1);
+            if now.saturating_duration_since(pending.raised_at) < deadline {
+                return true;
+            }
+
+            match chain.get(pending.step) {
+                Some(channel) => {
+                    pending.step += 1;
+                    steps.push(EscalationStep {
+                        channel: channel.clone(),
+                        notification: pending.clone(),
+                    });
+                    true
+                }
+                None => {
+                    log::error!("Escalation chain exhausted for notification #{}", pending.id);
+                    false
+                }
+            }
+        });
+
+        steps
+    }
+}
+
+/// Record an acknowledgement for a running daemon to pick up
+pub fn write_ack<P: AsRef<Path>>(path: P, id: u64) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", id)?;
+    Ok(())
+}
+
+/// Acks written after `offset`, from the start when the file was truncated
+/// since
+fn read_acks(path: &str, offset: &mut u64) -> Result<HashSet<u64>> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        *offset = 0;
+        return Ok(HashSet::new());
+    }
+
+    let mut file = File::open(&path)?;
+    if file.metadata()?.len() < *offset {
+        *offset = 0;
+    }
+    file.seek(SeekFrom::Start(*offset))?;
+    let reader = BufReader::new(file);
+    let mut ids = HashSet::new();
+    for line in reader.lines() {
+        if let Ok(id) = line?.trim().parse() {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EscalationConfig {
+        EscalationConfig {
+            ack_timeout_secs: 60,
+            chain: vec!["slack".to_string(), "email".to_string()],
+            levels: default_levels(),
+            ack_path: "/nonexistent/bustcall-acks".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_escalates_through_chain() {
+        let mut tracker = EscalationTracker::new(config());
+        tracker.track(7, NotificationLevel::Critical, Some("node"), "node died");
+        let raised = tracker.pending[&7].raised_at;
+
+        assert!(tracker.due_at(raised + Duration::from_secs(30)).is_empty());
+        let first = tracker.due_at(raised + Duration::from_secs(61));
+        assert_eq!(first[0].channel, "slack");
+        let second = tracker.due_at(raised + Duration::from_secs(121));
+        assert_eq!(second[0].channel, "email");
+        assert!(tracker.due_at(raised + Duration::from_secs(181)).is_empty());
+        assert!(tracker.pending().is_empty());
+    }
+
+    #[test]
+    fn test_acknowledged_notification_not_escalated() {
+        let mut tracker = EscalationTracker::new(config());
+        tracker.track(1, NotificationLevel::Critical, None, "down");
+        assert!(tracker.acknowledge(1));
+        assert!(tracker.due_at(Instant::now() + Duration::from_secs(600)).is_empty());
+    }
+
+    #[test]
+    fn test_acks_from_earlier_runs_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("acks");
+        write_ack(&path, 1).unwrap();
+
+        let mut tracker = EscalationTracker::new(EscalationConfig {
+            ack_path: path.to_string_lossy().into_owned(),
+            ..config()
+        });
+        tracker.track(1, NotificationLevel::Critical, None, "down");
+        tracker.poll().unwrap();
+        assert_eq!(tracker.pending(), vec![1]);
+
+        write_ack(&path, 1).unwrap();
+        tracker.poll().unwrap();
+        assert!(tracker.pending().is_empty());
+    }
+}
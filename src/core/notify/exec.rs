@@ -0,0 +1,219 @@
+//! User hook channel: run a command for each notification
+//!
+//! The notification is written to the command's stdin as JSON and exposed
+//! through `BUSTCALL_*` environment variables, so a shell one-liner can act on
+//! it without parsing anything.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment passed to every invocation
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default = "default_min_level")]
+    pub min_level: NotificationLevel,
+    /// The hook is killed once this elapses
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// Invocations beyond this are rejected rather than queued
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+fn default_min_level() -> NotificationLevel {
+    NotificationLevel::Info
+}
+
+fn default_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_max_concurrent() -> usize {
+    4
+}
+
+pub struct ExecChannel {
+    config: ExecConfig,
+    running: AtomicUsize,
+}
+
+/// Releases a concurrency slot when the invocation ends, however it ends
+struct Slot<'a>(&'a AtomicUsize);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ExecChannel {
+    pub fn new(config: ExecConfig) -> Self {
+        Self {
+            config,
+            running: AtomicUsize::new(0),
+        }
+    }
+
+    fn acquire(&self) -> Option<Slot<'_>> {
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < self.config.max_concurrent).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| Slot(&self.running))
+    }
+
+    fn run(&self, notification: &Notification) -> Result<()> {
+        let payload = serde_json::to_vec(notification)
+            .map_err(|e| BustcallError::NotificationError(format!("Failed to encode notification: {}", e)))?;
+
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .envs(&self.config.env)
+            .envs(hook_env(notification))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| {
+                BustcallError::NotificationError(format!("Failed to run hook {}: {}", self.config.command, e))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that ignores stdin closes the pipe early; that is fine
+            let _ = stdin.write_all(&payload);
+        }
+
+        let timeout = Duration::from_secs(self.config.timeout_seconds);
+        let started = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return if status.success() {
+                    Ok(())
+                } else {
+                    Err(BustcallError::NotificationError(format!(
+                        "Hook {} exited with {}",
+                        self.config.command, status
+                    )))
+                };
+            }
+
+            if started.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(BustcallError::NotificationError(format!(
+                    "Hook {} timed out after {}s",
+                    self.config.command, self.config.timeout_seconds
+                )));
+            }
+
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+impl NotificationChannel for ExecChannel {
+    fn name(&self) -> &str {
+        "exec"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        level >= self.config.min_level
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let Some(_slot) = self.acquire() else {
+            return Err(BustcallError::NotificationError(format!(
+                "Hook {} skipped: {} invocations already running",
+                self.config.command, self.config.max_concurrent
+            )));
+        };
+
+        self.run(notification)
+    }
+}
+
+/// `BUSTCALL_*` variables; metadata keys become `BUSTCALL_META_<KEY>`
+fn hook_env(notification: &Notification) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("BUSTCALL_LEVEL".to_string(), notification.level.as_str().to_string()),
+        ("BUSTCALL_MESSAGE".to_string(), notification.text()),
+        ("BUSTCALL_SEVERITY".to_string(), notification.severity.to_string()),
+    ];
+
+    let optional = [
+        ("BUSTCALL_TITLE", &notification.title),
+        ("BUSTCALL_TARGET", &notification.target),
+        ("BUSTCALL_COMPONENT", &notification.component),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            env.push((key.to_string(), value.clone()));
+        }
+    }
+
+    for (key, value) in &notification.metadata {
+        let key: String = key
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect();
+        env.push((format!("BUSTCALL_META_{}", key), value.clone()));
+    }
+
+    env
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str, timeout_seconds: u64) -> ExecChannel {
+        ExecChannel::new(ExecConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: HashMap::new(),
+            min_level: NotificationLevel::Info,
+            timeout_seconds,
+            max_concurrent: 1,
+        })
+    }
+
+    #[test]
+    fn test_hook_receives_env_and_stdin() {
+        let channel = shell(
+            r#"grep -q '"body":"node down"' && [ "$BUSTCALL_TARGET" = node ] && [ "$BUSTCALL_META_ETA" = 30s ]"#,
+            5,
+        );
+        let notification = Notification::new(NotificationLevel::Critical, "node down")
+            .target("node")
+            .with_metadata("eta", "30s");
+
+        assert!(channel.deliver_notification(&notification).is_ok());
+    }
+
+    #[test]
+    fn test_hook_is_killed_after_timeout() {
+        let channel = shell("sleep 5", 1);
+        let started = Instant::now();
+        assert!(channel.deliver(NotificationLevel::Info, "slow").is_err());
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(channel.running.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,249 @@
+//! Persistent notification history
+//!
+//! Sent notifications are kept in a bounded in-memory ring buffer and
+//! appended to a JSONL file so `bustcall notifications list` can query them
+//! after the daemon has restarted.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::NotificationLevel;
+use crate::utils::error::{BustcallError, Result};
+
+pub const DEFAULT_HISTORY_PATH: &str = "/tmp/bustcall-notifications.jsonl";
+pub const DEFAULT_HISTORY_CAPACITY: usize = 10_000;
+
+/// Delivery outcome for one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelOutcome {
+    pub channel: String,
+    pub delivered: bool,
+    /// Delivery attempts made, including retries
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn default_attempts() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRecord {
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+    pub level: NotificationLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub message: String,
+    pub channels: Vec<ChannelOutcome>,
+    /// Recorded while a mute was active; nothing was sent
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub muted: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub since: Option<DateTime<Utc>>,
+    /// Minimum level; more severe levels are included
+    pub level: Option<NotificationLevel>,
+    pub target: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, record: &NotificationRecord) -> bool {
+        self.since.is_none_or(|since| record.timestamp >= since)
+            && self.level.is_none_or(|level| record.level >= level)
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| record.target.as_ref() == Some(target))
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationHistory {
+    records: VecDeque<NotificationRecord>,
+    capacity: usize,
+    path: Option<PathBuf>,
+    next_id: u64,
+    lines_on_disk: usize,
+}
+
+impl NotificationHistory {
+    /// In-memory only history
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity.min(1024)),
+            capacity,
+            path: None,
+            next_id: 1,
+            lines_on_disk: 0,
+        }
+    }
+
+    /// Load existing history from a JSONL file, keeping the newest entries
+    pub fn open<P: AsRef<Path>>(path: P, capacity: usize) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut history = Self::in_memory(capacity);
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(&path)?);
+            for line in reader.lines() {
+                let line = line?;
+                match serde_json::from_str::<NotificationRecord>(&line) {
+                    Ok(record) => {
+                        history.next_id = history.next_id.max(record.id + 1);
+                        history.push_bounded(record);
+                        history.lines_on_disk += 1;
+                    }
+                    Err(e) => log::warn!("Skipping corrupt history line in {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    pub fn record(
+        &mut self,
+        level: NotificationLevel,
+        target: Option<&str>,
+        message: &str,
+        channels: Vec<ChannelOutcome>,
+    ) -> Result<u64> {
+        self.store(level, target, message, channels, false)
+    }
+
+    /// Record a notification that was suppressed by a mute
+    pub fn record_muted(&mut self, level: NotificationLevel, target: Option<&str>, message: &str) -> Result<u64> {
+        self.store(level, target, message, Vec::new(), true)
+    }
+
+    fn store(
+        &mut self,
+        level: NotificationLevel,
+        target: Option<&str>,
+        message: &str,
+        channels: Vec<ChannelOutcome>,
+        muted: bool,
+    ) -> Result<u64> {
+        let record = NotificationRecord {
+            id: self.next_id,
+            timestamp: Utc::now(),
+            level,
+            target: target.map(str::to_string),
+            message: message.to_string(),
+            channels,
+            muted,
+        };
+        self.next_id += 1;
+
+        self.persist(&record)?;
+        let id = record.id;
+        self.push_bounded(record);
+        Ok(id)
+    }
+
+    /// Matching records, newest first
+    pub fn query(&self, filter: &HistoryFilter) -> Vec<NotificationRecord> {
+        let matches = self.records.iter().rev().filter(|r| filter.matches(r)).cloned();
+        match filter.limit {
+            Some(limit) => matches.take(limit).collect(),
+            None => matches.collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn push_bounded(&mut self, record: NotificationRecord) {
+        if self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    fn persist(&mut self, record: &NotificationRecord) -> Result<()> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
+        };
+
+        // Rewrite the file once it holds twice the ring capacity
+        if self.lines_on_disk >= self.capacity * 2 {
+            self.compact(&path)?;
+        }
+
+        let line = serde_json::to_string(record)
+            .map_err(|e| BustcallError::NotificationError(format!("History serialization failed: {}", e)))?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)?;
+        self.lines_on_disk += 1;
+        Ok(())
+    }
+
+    fn compact(&mut self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("jsonl.tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            for record in &self.records {
+                let line = serde_json::to_string(record).map_err(|e| {
+                    BustcallError::NotificationError(format!("History serialization failed: {}", e))
+                })?;
+                writeln!(file, "{}", line)?;
+            }
+        }
+        fs::rename(&tmp, path)?;
+        self.lines_on_disk = self.records.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_history_survives_reopen() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl");
+
+        {
+            let mut history = NotificationHistory::open(&path, 10).unwrap();
+            history.record(NotificationLevel::Info, None, "started", vec![]).unwrap();
+            history.record(NotificationLevel::Warning, Some("node"), "PID changed", vec![]).unwrap();
+        }
+
+        let history = NotificationHistory::open(&path, 10).unwrap();
+        let warnings = history.query(&HistoryFilter {
+            level: Some(NotificationLevel::Warning),
+            ..Default::default()
+        });
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target.as_deref(), Some("node"));
+    }
+
+    #[test]
+    fn test_ring_buffer_is_bounded() {
+        let mut history = NotificationHistory::in_memory(3);
+        for i in 0..5 {
+            history.record(NotificationLevel::Info, None, &format!("{}", i), vec![]).unwrap();
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.query(&HistoryFilter::default())[0].message, "4");
+    }
+}
@@ -0,0 +1,178 @@
+//! Incident-management channel (PagerDuty Events API v2, Opsgenie)
+//!
+//! Triggering levels open an incident keyed by target; a later notification
+//! for the same target containing the resolve keyword closes it.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{post_json, Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::Result;
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+const OPSGENIE_ALERTS_URL: &str = "https://api.opsgenie.com/v2/alerts";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentProvider {
+    PagerDuty,
+    Opsgenie,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentConfig {
+    pub provider: IncidentProvider,
+    /// PagerDuty integration routing key or Opsgenie API key
+    pub routing_key: String,
+    /// Provider severity/priority per level, e.g. `critical = "critical"`
+    #[serde(default = "default_severity_mapping")]
+    pub severity_mapping: HashMap<NotificationLevel, String>,
+    #[serde(default = "default_trigger_levels")]
+    pub trigger_levels: Vec<NotificationLevel>,
+    #[serde(default = "default_resolve_keyword")]
+    pub resolve_keyword: String,
+    #[serde(default = "default_source")]
+    pub source: String,
+}
+
+fn default_severity_mapping() -> HashMap<NotificationLevel, String> {
+    HashMap::from([
+        (NotificationLevel::Critical, "critical".to_string()),
+        (NotificationLevel::Error, "error".to_string()),
+        (NotificationLevel::Warning, "warning".to_string()),
+        (NotificationLevel::Info, "info".to_string()),
+    ])
+}
+
+fn default_trigger_levels() -> Vec<NotificationLevel> {
+    vec![NotificationLevel::Critical]
+}
+
+fn default_resolve_keyword() -> String {
+    "recovered".to_string()
+}
+
+fn default_source() -> String {
+    "bustcall".to_string()
+}
+
+pub struct IncidentChannel {
+    config: IncidentConfig,
+    open: Mutex<HashSet<String>>,
+}
+
+impl IncidentChannel {
+    pub fn new(config: IncidentConfig) -> Self {
+        Self {
+            config,
+            open: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn dedup_key(&self, target: Option<&str>, message: &str) -> String {
+        match target {
+            Some(target) => format!("{}:{}", self.config.source, target),
+            None => format!("{}:{}", self.config.source, message),
+        }
+    }
+
+    fn trigger(&self, level: NotificationLevel, dedup_key: &str, message: &str) -> Result<()> {
+        let severity = self
+            .config
+            .severity_mapping
+            .get(&level)
+            .map(String::as_str)
+            .unwrap_or("critical");
+
+        match self.config.provider {
+            IncidentProvider::PagerDuty => post_json(
+                PAGERDUTY_EVENTS_URL,
+                &json!({
+                    "routing_key": self.config.routing_key,
+                    "event_action": "trigger",
+                    "dedup_key": dedup_key,
+                    "payload": {
+                        "summary": message,
+                        "source": self.config.source,
+                        "severity": severity,
+                    },
+                }),
+                None,
+            ),
+            IncidentProvider::Opsgenie => post_json(
+                OPSGENIE_ALERTS_URL,
+                &json!({
+                    "message": message,
+                    "alias": dedup_key,
+                    "source": self.config.source,
+                    "priority": severity,
+                }),
+                Some(&self.opsgenie_auth()),
+            ),
+        }
+    }
+
+    fn resolve(&self, dedup_key: &str) -> Result<()> {
+        match self.config.provider {
+            IncidentProvider::PagerDuty => post_json(
+                PAGERDUTY_EVENTS_URL,
+                &json!({
+                    "routing_key": self.config.routing_key,
+                    "event_action": "resolve",
+                    "dedup_key": dedup_key,
+                }),
+                None,
+            ),
+            IncidentProvider::Opsgenie => post_json(
+                &format!("{}/{}/close?identifierType=alias", OPSGENIE_ALERTS_URL, dedup_key),
+                &json!({ "source": self.config.source }),
+                Some(&self.opsgenie_auth()),
+            ),
+        }
+    }
+
+    fn opsgenie_auth(&self) -> String {
+        format!("GenieKey {}", self.config.routing_key)
+    }
+}
+
+impl NotificationChannel for IncidentChannel {
+    fn name(&self) -> &str {
+        match self.config.provider {
+            IncidentProvider::PagerDuty => "pagerduty",
+            IncidentProvider::Opsgenie => "opsgenie",
+        }
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let level = notification.level;
+        let message = &notification.text();
+        let dedup_key = notification
+            .metadata
+            .get("dedup_key")
+            .cloned()
+            .unwrap_or_else(|| self.dedup_key(notification.target.as_deref(), message));
+
+        if self.config.trigger_levels.contains(&level) {
+            self.trigger(level, &dedup_key, message)?;
+            self.open.lock().unwrap().insert(dedup_key);
+            return Ok(());
+        }
+
+        let recovered = message
+            .to_lowercase()
+            .contains(&self.config.resolve_keyword.to_lowercase());
+        if recovered && self.open.lock().unwrap().remove(&dedup_key) {
+            self.resolve(&dedup_key)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! systemd-journald channel using the native journal protocol
+//!
+//! Each notification becomes one journal entry whose `PRIORITY` follows the
+//! syslog mapping, with target, component and metadata as queryable fields
+//! (`journalctl BUSTCALL_TARGET=node`).
+
+use std::os::unix::net::UnixDatagram;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::{BustcallError, Result};
+
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaldConfig {
+    #[serde(default = "default_identifier")]
+    pub identifier: String,
+}
+
+impl Default for JournaldConfig {
+    fn default() -> Self {
+        Self {
+            identifier: default_identifier(),
+        }
+    }
+}
+
+fn default_identifier() -> String {
+    "bustcall".to_string()
+}
+
+pub struct JournaldChannel {
+    config: JournaldConfig,
+    socket: UnixDatagram,
+}
+
+impl JournaldChannel {
+    pub fn new(config: JournaldConfig) -> Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(JOURNAL_SOCKET).map_err(|e| {
+            BustcallError::NotificationError(format!("Cannot connect to {}: {}", JOURNAL_SOCKET, e))
+        })?;
+        Ok(Self { config, socket })
+    }
+}
+
+impl NotificationChannel for JournaldChannel {
+    fn name(&self) -> &str {
+        "journald"
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let entry = encode_entry(&self.config.identifier, notification);
+        self.socket
+            .send(&entry)
+            .map(|_| ())
+            .map_err(|e| BustcallError::NotificationError(format!("Journald delivery failed: {}", e)))
+    }
+}
+
+fn encode_entry(identifier: &str, notification: &Notification) -> Vec<u8> {
+    let mut entry = Vec::new();
+    append_field(&mut entry, "MESSAGE", &notification.text());
+    append_field(&mut entry, "PRIORITY", &notification.level.syslog_priority().to_string());
+    append_field(&mut entry, "SYSLOG_IDENTIFIER", identifier);
+    append_field(&mut entry, "BUSTCALL_LEVEL", notification.level.as_str());
+    append_field(&mut entry, "BUSTCALL_SEVERITY", &notification.severity.to_string());
+    if let Some(target) = &notification.target {
+        append_field(&mut entry, "BUSTCALL_TARGET", target);
+    }
+    if let Some(component) = &notification.component {
+        append_field(&mut entry, "BUSTCALL_COMPONENT", component);
+    }
+    for (key, value) in &notification.metadata {
+        append_field(&mut entry, &format!("BUSTCALL_{}", field_name(key)), value);
+    }
+    entry
+}
+
+/// Journal field names are uppercase ASCII letters, digits and underscores
+fn field_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// Values containing a newline use the length-prefixed binary form
+fn append_field(entry: &mut Vec<u8>, name: &str, value: &str) {
+    entry.extend_from_slice(name.as_bytes());
+    if value.contains('\n') {
+        entry.push(b'\n');
+        entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        entry.push(b'=');
+    }
+    entry.extend_from_slice(value.as_bytes());
+    entry.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_entry_fields() {
+        let notification = Notification::new(NotificationLevel::Warning, "slow rebuild")
+            .target("node")
+            .with_metadata("rebuild-eta", "30s");
+
+        let entry = String::from_utf8(encode_entry("bustcall", &notification)).unwrap();
+        assert!(entry.contains("MESSAGE=slow rebuild\n"));
+        assert!(entry.contains("PRIORITY=4\n"));
+        assert!(entry.contains("BUSTCALL_TARGET=node\n"));
+        assert!(entry.contains("BUSTCALL_REBUILD_ETA=30s\n"));
+    }
+
+    #[test]
+    fn test_multiline_values_are_length_prefixed() {
+        let mut entry = Vec::new();
+        append_field(&mut entry, "MESSAGE", "a\nb");
+        assert_eq!(&entry[..8], b"MESSAGE\n");
+        assert_eq!(&entry[8..16], &3u64.to_le_bytes());
+        assert_eq!(&entry[16..], b"a\nb\n");
+    }
+}
@@ -0,0 +1,131 @@
+//! Matrix notification channel (client-server API with an access token)
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{render_template, send_json, Notification, NotificationChannel, NotificationLevel, DEFAULT_TEMPLATE};
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixConfig {
+    /// e.g. `https://matrix.example.org`
+    pub homeserver: String,
+    pub access_token: String,
+    /// Room id or alias per level, e.g. `critical = "!oncall:example.org"`
+    #[serde(default)]
+    pub routes: HashMap<NotificationLevel, String>,
+    #[serde(default)]
+    pub default_room: Option<String>,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+pub struct MatrixChannel {
+    config: MatrixConfig,
+    /// Transaction ids must be unique per access token
+    txn_prefix: u64,
+    txn_counter: AtomicU64,
+}
+
+impl MatrixChannel {
+    pub fn new(config: MatrixConfig) -> Result<Self> {
+        if config.routes.is_empty() && config.default_room.is_none() {
+            return Err(BustcallError::ConfigError(
+                "Matrix channel requires default_room or routes".to_string(),
+            ));
+        }
+
+        let txn_prefix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Ok(Self {
+            config,
+            txn_prefix,
+            txn_counter: AtomicU64::new(0),
+        })
+    }
+
+    fn room_for(&self, level: NotificationLevel) -> Option<&String> {
+        self.config
+            .routes
+            .get(&level)
+            .or(self.config.default_room.as_ref())
+    }
+}
+
+impl NotificationChannel for MatrixChannel {
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        self.room_for(level).is_some()
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let Some(room) = self.room_for(notification.level) else {
+            return Ok(());
+        };
+
+        let txn_id = format!(
+            "bustcall-{}-{}",
+            self.txn_prefix,
+            self.txn_counter.fetch_add(1, Ordering::SeqCst)
+        );
+        let url = format!(
+            "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
+            self.config.homeserver.trim_end_matches('/'),
+            encode_path_segment(room),
+            txn_id
+        );
+        let msgtype = match notification.level {
+            NotificationLevel::Info => "m.notice",
+            _ => "m.text",
+        };
+
+        send_json(
+            "PUT",
+            &url,
+            &json!({
+                "msgtype": msgtype,
+                "body": render_template(&self.config.template, notification),
+            }),
+            Some(&format!("Bearer {}", self.config.access_token)),
+        )
+    }
+}
+
+/// Percent-encode room ids/aliases (`!`, `#` and `:` are common in them)
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_room_alias_is_percent_encoded() {
+        assert_eq!(encode_path_segment("#ops:example.org"), "%23ops%3Aexample.org");
+    }
+}
@@ -0,0 +1,167 @@
+//! Maintenance-mode mutes
+//!
+//! `bustcall notify mute --for 2h [--target node]` appends a rule to the mute
+//! file; the daemon's manager re-reads it whenever it changes and suppresses
+//! outbound delivery for matching notifications, which are still recorded in
+//! history.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Notification;
+use crate::utils::error::{BustcallError, Result};
+
+pub const DEFAULT_MUTE_PATH: &str = "/tmp/bustcall-mutes.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MuteRule {
+    pub until: DateTime<Utc>,
+    /// Mute one target only; every notification when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl MuteRule {
+    pub fn matches(&self, notification: &Notification, now: DateTime<Utc>) -> bool {
+        now < self.until
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| notification.target.as_ref() == Some(target))
+    }
+}
+
+/// Mute rules loaded from the mute file, reloaded when it changes
+#[derive(Debug)]
+pub struct MuteList {
+    path: PathBuf,
+    rules: Vec<MuteRule>,
+    /// Modification time and size of the file when last read
+    loaded_at: Option<(SystemTime, u64)>,
+}
+
+impl MuteList {
+    pub fn open<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            rules: Vec::new(),
+            loaded_at: None,
+        }
+    }
+
+    pub fn is_muted(&mut self, notification: &Notification) -> bool {
+        if let Err(e) = self.reload() {
+            log::warn!("Failed to read mute file {}: {}", self.path.display(), e);
+        }
+        let now = Utc::now();
+        self.rules.iter().any(|rule| rule.matches(notification, now))
+    }
+
+    /// Active rules, e.g. for status output
+    pub fn active(&mut self) -> Vec<MuteRule> {
+        let _ = self.reload();
+        let now = Utc::now();
+        self.rules.iter().filter(|rule| now < rule.until).cloned().collect()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        let modified = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.modified().ok().map(|time| (time, metadata.len())),
+            Err(_) => {
+                self.rules.clear();
+                self.loaded_at = None;
+                return Ok(());
+            }
+        };
+
+        if modified.is_some() && modified == self.loaded_at {
+            return Ok(());
+        }
+
+        self.rules = read_mutes(&self.path)?;
+        self.loaded_at = modified;
+        Ok(())
+    }
+}
+
+/// Append a mute rule for the daemon to pick up
+pub fn write_mute<P: AsRef<Path>>(path: P, rule: &MuteRule) -> Result<()> {
+    let line = serde_json::to_string(rule)
+        .map_err(|e| BustcallError::NotificationError(format!("Mute serialization failed: {}", e)))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// Remove mutes for `target`, or every mute when `target` is `None`.
+/// Expired rules are dropped at the same time.
+pub fn clear_mutes<P: AsRef<Path>>(path: P, target: Option<&str>) -> Result<usize> {
+    let path = path.as_ref();
+    let rules = read_mutes(path)?;
+    let now = Utc::now();
+    let before = rules.len();
+
+    let kept: Vec<_> = rules
+        .into_iter()
+        .filter(|rule| now < rule.until)
+        .filter(|rule| target.is_some_and(|target| rule.target.as_deref() != Some(target)))
+        .collect();
+    let removed = before - kept.len();
+
+    let mut file = File::create(path)?;
+    for rule in &kept {
+        let line = serde_json::to_string(rule)
+            .map_err(|e| BustcallError::NotificationError(format!("Mute serialization failed: {}", e)))?;
+        writeln!(file, "{}", line)?;
+    }
+    Ok(removed)
+}
+
+fn read_mutes(path: &Path) -> Result<Vec<MuteRule>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let reader = BufReader::new(File::open(path)?);
+    let mut rules = Vec::new();
+    for line in reader.lines() {
+        match serde_json::from_str(&line?) {
+            Ok(rule) => rules.push(rule),
+            Err(e) => log::warn!("Skipping invalid mute rule in {}: {}", path.display(), e),
+        }
+    }
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::notify::NotificationLevel;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_target_mute_only_matches_that_target() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mutes.jsonl");
+        write_mute(
+            &path,
+            &MuteRule {
+                until: Utc::now() + chrono::Duration::hours(2),
+                target: Some("node".to_string()),
+            },
+        )
+        .unwrap();
+
+        let mut mutes = MuteList::open(&path);
+        assert!(mutes.is_muted(&Notification::new(NotificationLevel::Warning, "x").target("node")));
+        assert!(!mutes.is_muted(&Notification::new(NotificationLevel::Warning, "x").target("python")));
+
+        assert_eq!(clear_mutes(&path, Some("node")).unwrap(), 1);
+        assert!(mutes.active().is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! Severity-to-action policy
+//!
+//! Rules bind a level and/or severity band to an automatic action. The
+//! manager records matching actions as notifications go out, and the daemon
+//! drains and executes them on every tick (`Daemon::run_policy_actions`).
+
+use serde::{Deserialize, Serialize};
+
+use super::exec::ExecConfig;
+use super::{Notification, NotificationLevel};
+use crate::dimensional_cache::CacheBustSeverity;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// Bust the notification's target; severity follows the level if unset
+    Bust {
+        #[serde(default)]
+        severity: Option<CacheBustSeverity>,
+    },
+    /// Evict the target's cache entries, with a Low bust
+    Evict,
+    /// Run the target's `restart_command`, or ask supervising plugins to
+    /// restart it when none is configured
+    Restart,
+    /// Run a hook with the notification, as the `exec` channel does
+    RunHook(ExecConfig),
+}
+
+/// `[[notifications.policy]]` entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRule {
+    /// Levels this rule applies to; empty matches every level
+    #[serde(default)]
+    pub levels: Vec<NotificationLevel>,
+    #[serde(default)]
+    pub min_severity: u8,
+    #[serde(default = "default_max_severity")]
+    pub max_severity: u8,
+    /// Restrict the rule to one target
+    #[serde(default)]
+    pub target: Option<String>,
+    pub action: PolicyAction,
+}
+
+fn default_max_severity() -> u8 {
+    12
+}
+
+impl PolicyRule {
+    pub fn matches(&self, notification: &Notification) -> bool {
+        let level_ok = self.levels.is_empty() || self.levels.contains(&notification.level);
+        let severity_ok = (self.min_severity..=self.max_severity).contains(&notification.severity);
+        let target_ok = self
+            .target
+            .as_ref()
+            .is_none_or(|target| notification.target.as_ref() == Some(target));
+
+        level_ok && severity_ok && target_ok
+    }
+}
+
+/// Action triggered by a delivered notification, awaiting execution
+#[derive(Debug, Clone)]
+pub struct PolicyInvocation {
+    pub action: PolicyAction,
+    pub notification: Notification,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PolicyEngine {
+    rules: Vec<PolicyRule>,
+}
+
+impl PolicyEngine {
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Every rule matching the notification, in configuration order
+    pub fn evaluate(&self, notification: &Notification) -> Vec<PolicyInvocation> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(notification))
+            .map(|rule| PolicyInvocation {
+                action: rule.action.clone(),
+                notification: notification.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Bust severity implied by a notification level
+pub fn bust_severity_for(level: NotificationLevel) -> CacheBustSeverity {
+    match level {
+        NotificationLevel::Info => CacheBustSeverity::Low,
+        NotificationLevel::Warning => CacheBustSeverity::Medium,
+        NotificationLevel::Error => CacheBustSeverity::High,
+        NotificationLevel::Critical => CacheBustSeverity::Critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_match_by_level_and_severity_band() {
+        let engine = PolicyEngine::new(vec![
+            PolicyRule {
+                levels: vec![NotificationLevel::Warning],
+                min_severity: 0,
+                max_severity: 12,
+                target: None,
+                action: PolicyAction::Bust { severity: None },
+            },
+            PolicyRule {
+                levels: vec![],
+                min_severity: 10,
+                max_severity: 12,
+                target: Some("node".to_string()),
+                action: PolicyAction::Restart,
+            },
+        ]);
+
+        let warning = Notification::new(NotificationLevel::Warning, "stale").target("node");
+        assert_eq!(engine.evaluate(&warning).len(), 1);
+
+        let meltdown = Notification::new(NotificationLevel::Critical, "down").target("node").severity(11);
+        let actions = engine.evaluate(&meltdown);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0].action, PolicyAction::Restart));
+
+        let other = Notification::new(NotificationLevel::Critical, "down").target("python").severity(11);
+        assert!(engine.evaluate(&other).is_empty());
+    }
+
+    #[test]
+    fn test_policy_rule_from_toml() {
+        let rule: PolicyRule = toml::from_str(
+            r#"
+            levels = ["critical"]
+            action = { type = "bust", severity = "high" }
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            rule.action,
+            PolicyAction::Bust { severity: Some(CacheBustSeverity::High) }
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+//! Delivery retries and channel health
+//!
+//! Transient channel failures are retried with exponential backoff. A channel
+//! failing several notifications in a row is reported as down once, and as
+//! recovered on its next success.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Total attempts per channel, including the first
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Consecutive failed notifications before a channel is reported down
+    #[serde(default = "default_down_after")]
+    pub down_after: u32,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            down_after: default_down_after(),
+        }
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    250
+}
+
+fn default_max_backoff_ms() -> u64 {
+    10_000
+}
+
+fn default_down_after() -> u32 {
+    3
+}
+
+impl RetryConfig {
+    /// No retries; failures are still tracked for health reporting
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based), doubling each time
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u64.checked_shl(retry.saturating_sub(1)).unwrap_or(u64::MAX);
+        let delay = self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms);
+        Duration::from_millis(delay)
+    }
+}
+
+/// Health transition worth telling the operator about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthChange {
+    Down { failures: u32 },
+    Recovered,
+}
+
+#[derive(Debug, Default)]
+pub struct ChannelHealth {
+    consecutive_failures: HashMap<String, u32>,
+}
+
+impl ChannelHealth {
+    pub fn record_success(&mut self, channel: &str, down_after: u32) -> Option<HealthChange> {
+        let failures = self.consecutive_failures.remove(channel).unwrap_or(0);
+        (failures >= down_after).then_some(HealthChange::Recovered)
+    }
+
+    /// Reports `Down` only when the threshold is first crossed
+    pub fn record_failure(&mut self, channel: &str, down_after: u32) -> Option<HealthChange> {
+        let failures = self.consecutive_failures.entry(channel.to_string()).or_insert(0);
+        *failures += 1;
+        (*failures == down_after).then_some(HealthChange::Down { failures: *failures })
+    }
+
+    pub fn consecutive_failures(&self, channel: &str) -> u32 {
+        self.consecutive_failures.get(channel).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = RetryConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 350,
+            ..RetryConfig::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(350));
+        assert_eq!(config.backoff(80), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_down_reported_once_then_recovered() {
+        let mut health = ChannelHealth::default();
+        assert_eq!(health.record_failure("slack", 2), None);
+        assert_eq!(health.record_failure("slack", 2), Some(HealthChange::Down { failures: 2 }));
+        assert_eq!(health.record_failure("slack", 2), None);
+        assert_eq!(health.record_success("slack", 2), Some(HealthChange::Recovered));
+        assert_eq!(health.record_success("slack", 2), None);
+    }
+}
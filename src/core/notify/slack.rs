@@ -0,0 +1,96 @@
+//! Slack notification channel (incoming webhook or bot token)
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{post_json, render_template, Notification, NotificationChannel, NotificationLevel, DEFAULT_TEMPLATE};
+use crate::utils::error::{BustcallError, Result};
+
+const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Incoming webhook URL, used when no bot token is set
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Bot token for `chat.postMessage`, required for per-level routing
+    #[serde(default)]
+    pub bot_token: Option<String>,
+    /// Slack channel per level, e.g. `critical = "#oncall"`
+    #[serde(default)]
+    pub routes: HashMap<NotificationLevel, String>,
+    #[serde(default)]
+    pub default_channel: Option<String>,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+pub struct SlackChannel {
+    config: SlackConfig,
+}
+
+impl SlackChannel {
+    pub fn new(config: SlackConfig) -> Result<Self> {
+        if config.webhook_url.is_none() && config.bot_token.is_none() {
+            return Err(BustcallError::ConfigError(
+                "Slack channel requires webhook_url or bot_token".to_string(),
+            ));
+        }
+        Ok(Self { config })
+    }
+
+    fn route(&self, level: NotificationLevel) -> Option<&String> {
+        self.config
+            .routes
+            .get(&level)
+            .or(self.config.default_channel.as_ref())
+    }
+}
+
+impl NotificationChannel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        // With explicit routes and no fallback, unrouted levels are skipped
+        self.config.routes.is_empty() || self.route(level).is_some()
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let level = notification.level;
+        let text = render_template(&self.config.template, notification);
+        let channel = self.route(level);
+
+        match (&self.config.bot_token, &self.config.webhook_url) {
+            (Some(token), _) => {
+                let channel = channel.ok_or_else(|| {
+                    BustcallError::NotificationError(format!("No Slack channel routed for {:?}", level))
+                })?;
+                post_json(
+                    SLACK_POST_MESSAGE_URL,
+                    &json!({ "channel": channel, "text": text }),
+                    Some(&format!("Bearer {}", token)),
+                )
+            }
+            (None, Some(webhook)) => {
+                let mut body = json!({ "text": text });
+                if let Some(channel) = channel {
+                    body["channel"] = json!(channel);
+                }
+                post_json(webhook, &body, None)
+            }
+            (None, None) => unreachable!("validated in SlackChannel::new"),
+        }
+    }
+}
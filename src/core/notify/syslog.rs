@@ -0,0 +1,230 @@
+//! RFC 5424 syslog channel over the local `/dev/log` socket or UDP
+
+use std::net::UdpSocket;
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{render_template, Notification, NotificationChannel, NotificationLevel};
+use crate::utils::error::{BustcallError, Result};
+
+#[cfg(unix)]
+const DEV_LOG: &str = "/dev/log";
+
+/// Private enterprise number used for the structured-data element
+const SD_ID: &str = "bustcall@32473";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFacility {
+    User,
+    #[default]
+    Daemon,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl SyslogFacility {
+    fn code(&self) -> u8 {
+        match self {
+            SyslogFacility::User => 1,
+            SyslogFacility::Daemon => 3,
+            SyslogFacility::Local0 => 16,
+            SyslogFacility::Local1 => 17,
+            SyslogFacility::Local2 => 18,
+            SyslogFacility::Local3 => 19,
+            SyslogFacility::Local4 => 20,
+            SyslogFacility::Local5 => 21,
+            SyslogFacility::Local6 => 22,
+            SyslogFacility::Local7 => 23,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogConfig {
+    /// `host:port` of a UDP collector; the local `/dev/log` socket when unset
+    #[serde(default)]
+    pub udp_address: Option<String>,
+    #[serde(default)]
+    pub facility: SyslogFacility,
+    #[serde(default = "default_app_name")]
+    pub app_name: String,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        Self {
+            udp_address: None,
+            facility: SyslogFacility::default(),
+            app_name: default_app_name(),
+            template: default_template(),
+        }
+    }
+}
+
+fn default_app_name() -> String {
+    "bustcall".to_string()
+}
+
+fn default_template() -> String {
+    "{message}".to_string()
+}
+
+enum Transport {
+    #[cfg(unix)]
+    DevLog(UnixDatagram),
+    Udp(UdpSocket),
+}
+
+pub struct SyslogChannel {
+    config: SyslogConfig,
+    transport: Transport,
+    hostname: String,
+}
+
+impl SyslogChannel {
+    pub fn new(config: SyslogConfig) -> Result<Self> {
+        let transport = match &config.udp_address {
+            Some(address) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(address.as_str()).map_err(|e| {
+                    BustcallError::ConfigError(format!("Invalid syslog udp_address {}: {}", address, e))
+                })?;
+                Transport::Udp(socket)
+            }
+            #[cfg(unix)]
+            None => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(DEV_LOG).map_err(|e| {
+                    BustcallError::NotificationError(format!("Cannot connect to {}: {}", DEV_LOG, e))
+                })?;
+                Transport::DevLog(socket)
+            }
+            #[cfg(not(unix))]
+            None => {
+                return Err(BustcallError::ConfigError(
+                    "syslog channel requires udp_address on this platform".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            config,
+            transport,
+            hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+        })
+    }
+
+    fn format(&self, notification: &Notification) -> String {
+        format_rfc5424(
+            self.config.facility,
+            &self.hostname,
+            &self.config.app_name,
+            notification,
+            &render_template(&self.config.template, notification),
+        )
+    }
+}
+
+impl NotificationChannel for SyslogChannel {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let line = self.format(notification);
+        let sent = match &self.transport {
+            #[cfg(unix)]
+            Transport::DevLog(socket) => socket.send(line.as_bytes()),
+            Transport::Udp(socket) => socket.send(line.as_bytes()),
+        };
+
+        sent.map(|_| ())
+            .map_err(|e| BustcallError::NotificationError(format!("Syslog delivery failed: {}", e)))
+    }
+}
+
+/// `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID [SD] MSG`
+fn format_rfc5424(
+    facility: SyslogFacility,
+    hostname: &str,
+    app_name: &str,
+    notification: &Notification,
+    message: &str,
+) -> String {
+    let pri = facility.code() as u16 * 8 + notification.level.syslog_priority() as u16;
+
+    format!(
+        "<{}>1 {} {} {} {} {} {} {}",
+        pri,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        hostname,
+        app_name,
+        std::process::id(),
+        notification.level.as_str(),
+        structured_data(notification),
+        message
+    )
+}
+
+fn structured_data(notification: &Notification) -> String {
+    let mut params = vec![format!("severity=\"{}\"", notification.severity)];
+    if let Some(target) = &notification.target {
+        params.push(format!("target=\"{}\"", escape_param(target)));
+    }
+    if let Some(component) = &notification.component {
+        params.push(format!("component=\"{}\"", escape_param(component)));
+    }
+    for (key, value) in &notification.metadata {
+        // SD-NAME is printable ASCII without '=', ' ', ']' or '"'
+        let valid = key
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'));
+        if valid && key.len() <= 32 {
+            params.push(format!("{}=\"{}\"", key, escape_param(value)));
+        }
+    }
+
+    format!("[{} {}]", SD_ID, params.join(" "))
+}
+
+fn escape_param(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc5424_priority_and_structured_data() {
+        let notification = Notification::new(NotificationLevel::Critical, "node down")
+            .target("node")
+            .with_metadata("trigger", "pkg\"json");
+
+        let line = format_rfc5424(SyslogFacility::Daemon, "host", "bustcall", &notification, "node down");
+        // daemon (3) * 8 + crit (2)
+        assert!(line.starts_with("<26>1 "));
+        assert!(line.contains(" host bustcall "));
+        assert!(line.contains(r#"[bustcall@32473 severity="9" target="node" trigger="pkg\"json"]"#));
+        assert!(line.ends_with(" node down"));
+    }
+}
@@ -0,0 +1,102 @@
+//! Microsoft Teams notification channel (incoming webhook, MessageCard)
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{post_json, render_template, Notification, NotificationChannel, NotificationLevel, DEFAULT_TEMPLATE};
+use crate::utils::error::Result;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamsConfig {
+    /// Webhook used for levels without a dedicated route
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Teams webhooks are bound to one channel, so routing is per URL
+    #[serde(default)]
+    pub routes: HashMap<NotificationLevel, String>,
+    #[serde(default = "default_template")]
+    pub template: String,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+pub struct TeamsChannel {
+    config: TeamsConfig,
+}
+
+impl TeamsChannel {
+    pub fn new(config: TeamsConfig) -> Self {
+        Self { config }
+    }
+
+    fn webhook_for(&self, level: NotificationLevel) -> Option<&String> {
+        self.config
+            .routes
+            .get(&level)
+            .or(self.config.webhook_url.as_ref())
+    }
+}
+
+impl NotificationChannel for TeamsChannel {
+    fn name(&self) -> &str {
+        "teams"
+    }
+
+    fn accepts(&self, level: NotificationLevel) -> bool {
+        self.webhook_for(level).is_some()
+    }
+
+    fn deliver(&self, level: NotificationLevel, message: &str) -> Result<()> {
+        self.deliver_notification(&Notification::new(level, message))
+    }
+
+    fn deliver_notification(&self, notification: &Notification) -> Result<()> {
+        let Some(webhook) = self.webhook_for(notification.level) else {
+            return Ok(());
+        };
+
+        let text = render_template(&self.config.template, notification);
+        post_json(webhook, &message_card(notification, &text), None)
+    }
+}
+
+fn message_card(notification: &Notification, text: &str) -> serde_json::Value {
+    let title = notification
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("bustcall {}", notification.level.as_str()));
+
+    let mut facts = vec![json!({ "name": "Severity", "value": notification.severity.to_string() })];
+    if let Some(target) = &notification.target {
+        facts.push(json!({ "name": "Target", "value": target }));
+    }
+    if let Some(component) = &notification.component {
+        facts.push(json!({ "name": "Component", "value": component }));
+    }
+    for (key, value) in &notification.metadata {
+        facts.push(json!({ "name": key, "value": value }));
+    }
+
+    json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "summary": title,
+        "themeColor": theme_color(notification.level),
+        "title": title,
+        "text": text,
+        "sections": [{ "facts": facts }],
+    })
+}
+
+fn theme_color(level: NotificationLevel) -> &'static str {
+    match level {
+        NotificationLevel::Info => "2F80ED",
+        NotificationLevel::Warning => "F2C94C",
+        NotificationLevel::Error => "EB5757",
+        NotificationLevel::Critical => "8B0000",
+    }
+}
@@ -0,0 +1,251 @@
+//! Notification deduplication and rate limiting
+//!
+//! Identical notifications (same level, target and message) inside the dedup
+//! window are dropped and reported once as a "suppressed N duplicates"
+//! summary when the window closes. A global token bucket caps overall volume.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::NotificationLevel;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThrottleConfig {
+    #[serde(default = "default_dedup_window_secs")]
+    pub dedup_window_secs: u64,
+    /// Maximum burst of notifications before rate limiting kicks in
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+    #[serde(default = "default_per_minute")]
+    pub per_minute: u32,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            dedup_window_secs: default_dedup_window_secs(),
+            burst: default_burst(),
+            per_minute: default_per_minute(),
+        }
+    }
+}
+
+fn default_dedup_window_secs() -> u64 {
+    60
+}
+
+fn default_burst() -> u32 {
+    20
+}
+
+fn default_per_minute() -> u32 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThrottleDecision {
+    Deliver,
+    Duplicate,
+    RateLimited,
+}
+
+/// Summary emitted once a dedup window closes with suppressed duplicates
+#[derive(Debug, Clone)]
+pub struct SuppressedSummary {
+    pub level: NotificationLevel,
+    pub target: Option<String>,
+    pub message: String,
+    pub suppressed: u32,
+}
+
+impl SuppressedSummary {
+    pub fn render(&self) -> String {
+        format!("{} (suppressed {} duplicates)", self.message, self.suppressed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DedupKey {
+    level: NotificationLevel,
+    target: Option<String>,
+    message_hash: u64,
+}
+
+#[derive(Debug)]
+struct DedupEntry {
+    first_seen: Instant,
+    message: String,
+    suppressed: u32,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, per_minute: u32, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            refill_per_sec: per_minute as f64 / 60.0,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationThrottle {
+    window: Duration,
+    seen: HashMap<DedupKey, DedupEntry>,
+    bucket: TokenBucket,
+    rate_limited: u32,
+}
+
+impl NotificationThrottle {
+    pub fn new(config: &ThrottleConfig) -> Self {
+        Self {
+            window: Duration::from_secs(config.dedup_window_secs),
+            seen: HashMap::new(),
+            bucket: TokenBucket::new(config.burst, config.per_minute, Instant::now()),
+            rate_limited: 0,
+        }
+    }
+
+    pub fn check(&mut self, level: NotificationLevel, target: Option<&str>, message: &str) -> ThrottleDecision {
+        self.check_at(level, target, message, Instant::now())
+    }
+
+    /// Critical notifications are deduplicated but never rate limited
+    pub fn check_at(
+        &mut self,
+        level: NotificationLevel,
+        target: Option<&str>,
+        message: &str,
+        now: Instant,
+    ) -> ThrottleDecision {
+        let key = DedupKey {
+            level,
+            target: target.map(str::to_string),
+            message_hash: hash_message(message),
+        };
+
+        if let Some(entry) = self.seen.get_mut(&key) {
+            if now.saturating_duration_since(entry.first_seen) < self.window {
+                entry.suppressed += 1;
+                return ThrottleDecision::Duplicate;
+            }
+        }
+
+        if level != NotificationLevel::Critical && !self.bucket.try_take(now) {
+            self.rate_limited += 1;
+            return ThrottleDecision::RateLimited;
+        }
+
+        self.seen.insert(
+            key,
+            DedupEntry {
+                first_seen: now,
+                message: message.to_string(),
+                suppressed: 0,
+            },
+        );
+        ThrottleDecision::Deliver
+    }
+
+    /// Close expired dedup windows, returning summaries for those that
+    /// swallowed duplicates
+    pub fn drain_expired(&mut self) -> Vec<SuppressedSummary> {
+        self.drain_expired_at(Instant::now())
+    }
+
+    pub fn drain_expired_at(&mut self, now: Instant) -> Vec<SuppressedSummary> {
+        let window = self.window;
+        let mut summaries = Vec::new();
+
+        self.seen.retain(|key, entry| {
+            if now.saturating_duration_since(entry.first_seen) < window {
+                return true;
+            }
+            if entry.suppressed > 0 {
+                summaries.push(SuppressedSummary {
+                    level: key.level,
+                    target: key.target.clone(),
+                    message: entry.message.clone(),
+                    suppressed: entry.suppressed,
+                });
+            }
+            false
+        });
+
+        summaries
+    }
+
+    /// Number of notifications dropped by the rate limiter since last call
+    pub fn take_rate_limited(&mut self) -> u32 {
+        std::mem::take(&mut self.rate_limited)
+    }
+}
+
+fn hash_message(message: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    message.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_suppressed_and_summarised() {
+        let mut throttle = NotificationThrottle::new(&ThrottleConfig::default());
+        let start = Instant::now();
+
+        let first = throttle.check_at(NotificationLevel::Warning, Some("node"), "PID flapped", start);
+        assert_eq!(first, ThrottleDecision::Deliver);
+        for _ in 0..3 {
+            let repeat = throttle.check_at(NotificationLevel::Warning, Some("node"), "PID flapped", start);
+            assert_eq!(repeat, ThrottleDecision::Duplicate);
+        }
+
+        let summaries = throttle.drain_expired_at(start + Duration::from_secs(61));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].suppressed, 3);
+    }
+
+    #[test]
+    fn test_token_bucket_limits_burst() {
+        let config = ThrottleConfig {
+            burst: 2,
+            ..Default::default()
+        };
+        let mut throttle = NotificationThrottle::new(&config);
+        let now = Instant::now();
+
+        assert_eq!(throttle.check_at(NotificationLevel::Info, None, "a", now), ThrottleDecision::Deliver);
+        assert_eq!(throttle.check_at(NotificationLevel::Info, None, "b", now), ThrottleDecision::Deliver);
+        assert_eq!(throttle.check_at(NotificationLevel::Info, None, "c", now), ThrottleDecision::RateLimited);
+        assert_eq!(throttle.check_at(NotificationLevel::Critical, None, "d", now), ThrottleDecision::Deliver);
+    }
+}
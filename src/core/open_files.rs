@@ -0,0 +1,294 @@
+//! Open files and listening sockets of a process
+//!
+//! Read from `/proc/<pid>/fd` and the process's own view of `/proc/net`, so
+//! sockets inside a container's network namespace are resolved too. Gathering
+//! this is comparatively expensive, so it is only done on request rather than
+//! for every process in a listing.
+//!
+//! The other way round, [`socket_holders`] finds the processes holding the
+//! socket a runtime target is detected by, e.g. `detect = { port = 3000 }`
+//! for a dev server, from bustcall's own view of `/proc/net`.
+
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OpenFiles {
+    pub fd_count: usize,
+    /// Files and directories held open, excluding pipes and sockets
+    pub paths: Vec<PathBuf>,
+    pub listening: Vec<ListeningSocket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListeningSocket {
+    /// `tcp`, `tcp6`, `udp` or `udp6`
+    pub protocol: &'static str,
+    pub address: SocketAddr,
+}
+
+/// Socket a runtime is identified by, instead of or along with its name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketDetect {
+    /// TCP port listened on, on any address
+    Port(u16),
+    /// Path of a listening Unix socket
+    UnixSocket(PathBuf),
+}
+
+impl std::fmt::Display for SocketDetect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketDetect::Port(port) => write!(f, "port {}", port),
+            SocketDetect::UnixSocket(path) => write!(f, "socket {}", path.display()),
+        }
+    }
+}
+
+/// TCP `LISTEN` state in /proc/net/tcp
+const TCP_LISTEN: &str = "0A";
+/// UDP sockets bound but not connected
+const UDP_UNCONNECTED: &str = "07";
+
+/// Inspect `pid`; `None` if /proc is unavailable or the process belongs to
+/// another user
+#[cfg(target_os = "linux")]
+pub fn open_files(pid: u32) -> Option<OpenFiles> {
+    let entries = std::fs::read_dir(format!("/proc/{}/fd", pid)).ok()?;
+
+    let mut files = OpenFiles::default();
+    let mut socket_inodes = HashSet::new();
+    for entry in entries.flatten() {
+        files.fd_count += 1;
+        let Ok(link) = std::fs::read_link(entry.path()) else {
+            continue;
+        };
+        let link = link.to_string_lossy().into_owned();
+
+        if let Some(inode) = socket_inode(&link) {
+            socket_inodes.insert(inode);
+        } else if link.starts_with('/') {
+            files.paths.push(PathBuf::from(link));
+        }
+    }
+    files.paths.sort();
+    files.paths.dedup();
+
+    let tables = [
+        ("tcp", TCP_LISTEN),
+        ("tcp6", TCP_LISTEN),
+        ("udp", UDP_UNCONNECTED),
+        ("udp6", UDP_UNCONNECTED),
+    ];
+    for (protocol, state) in tables {
+        let Ok(table) = std::fs::read_to_string(format!("/proc/{}/net/{}", pid, protocol)) else {
+            continue;
+        };
+        files.listening.extend(
+            table
+                .lines()
+                .skip(1)
+                .filter_map(|line| parse_socket_line(line, state))
+                .filter(|(_, inode)| socket_inodes.contains(inode))
+                .map(|(address, _)| ListeningSocket { protocol, address }),
+        );
+    }
+
+    Some(files)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn open_files(_pid: u32) -> Option<OpenFiles> {
+    None
+}
+
+/// Pids holding the socket `detect` names open, workers sharing a listener
+/// included; `None` if /proc is unavailable. Processes of other users are
+/// only seen when running as root.
+#[cfg(target_os = "linux")]
+pub fn socket_holders(detect: &SocketDetect) -> Option<HashSet<u32>> {
+    let inodes = listening_inodes(detect)?;
+    let mut holders = HashSet::new();
+    if inodes.is_empty() {
+        return Some(holders);
+    }
+
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let holds = fds.flatten().any(|fd| {
+            std::fs::read_link(fd.path())
+                .ok()
+                .and_then(|link| socket_inode(&link.to_string_lossy()))
+                .is_some_and(|inode| inodes.contains(&inode))
+        });
+        if holds {
+            holders.insert(pid);
+        }
+    }
+    Some(holders)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn socket_holders(_detect: &SocketDetect) -> Option<HashSet<u32>> {
+    None
+}
+
+/// Inodes of the listening sockets `detect` names
+#[cfg(target_os = "linux")]
+fn listening_inodes(detect: &SocketDetect) -> Option<HashSet<u64>> {
+    match detect {
+        SocketDetect::Port(port) => {
+            let mut inodes = HashSet::new();
+            for protocol in ["tcp", "tcp6"] {
+                let Ok(table) = std::fs::read_to_string(format!("/proc/net/{}", protocol)) else {
+                    continue;
+                };
+                inodes.extend(
+                    table
+                        .lines()
+                        .skip(1)
+                        .filter_map(|line| parse_socket_line(line, TCP_LISTEN))
+                        .filter(|(address, _)| address.port() == *port)
+                        .map(|(_, inode)| inode),
+                );
+            }
+            Some(inodes)
+        }
+        SocketDetect::UnixSocket(path) => {
+            let table = std::fs::read_to_string("/proc/net/unix").ok()?;
+            Some(
+                table
+                    .lines()
+                    .skip(1)
+                    .filter_map(parse_unix_line)
+                    .filter(|(bound, _)| bound == path)
+                    .map(|(_, inode)| inode)
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// `socket:[424242]` -> 424242
+fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// Path and inode of a listening /proc/net/unix row
+fn parse_unix_line(line: &str) -> Option<(&Path, u64)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    // Flags carry __SO_ACCEPTCON once the socket listens
+    let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+    if fields.len() < 8 || flags & 0x10000 == 0 {
+        return None;
+    }
+    Some((Path::new(fields[7]), fields[6].parse().ok()?))
+}
+
+/// Local address and inode of a /proc/net/{tcp,udp}[6] row in `state`
+fn parse_socket_line(line: &str, state: &str) -> Option<(SocketAddr, u64)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 || fields[3] != state {
+        return None;
+    }
+    let address = parse_hex_address(fields[1])?;
+    let inode = fields[9].parse().ok()?;
+    Some((address, inode))
+}
+
+/// `0100007F:1F90` or the 32-digit IPv6 form; the kernel prints each 32-bit
+/// word of the address in host byte order
+fn parse_hex_address(value: &str) -> Option<SocketAddr> {
+    let (ip, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let word = |i: usize| -> Option<[u8; 4]> {
+        let raw = u32::from_str_radix(ip.get(i * 8..i * 8 + 8)?, 16).ok()?;
+        Some(raw.to_ne_bytes())
+    };
+
+    let ip = match ip.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(0)?)),
+        32 => {
+            let mut octets = [0u8; 16];
+            for i in 0..4 {
+                octets[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_parse_proc_net_rows() {
+        let listen = "   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 424242 1 0000000000000000 100 0 0 10 0";
+        let (address, inode) = parse_socket_line(listen, TCP_LISTEN).unwrap();
+        assert_eq!(address, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(inode, 424242);
+
+        let established = listen.replacen(" 0A ", " 01 ", 1);
+        assert!(parse_socket_line(&established, TCP_LISTEN).is_none());
+
+        let any6 = parse_hex_address("00000000000000000000000000000000:0050").unwrap();
+        assert_eq!(any6, "[::]:80".parse().unwrap());
+        let loopback6 = parse_hex_address("00000000000000000000000001000000:0050").unwrap();
+        assert_eq!(loopback6, "[::1]:80".parse().unwrap());
+    }
+
+    #[test]
+    fn test_parse_proc_net_unix_rows() {
+        let listening = "0000000000000000: 00000002 00000000 00010000 0001 01 31337 /tmp/app.sock";
+        assert_eq!(parse_unix_line(listening), Some((Path::new("/tmp/app.sock"), 31337)));
+        let connected = "0000000000000000: 00000003 00000000 00000000 0001 03 31338 /tmp/app.sock";
+        assert_eq!(parse_unix_line(connected), None);
+        let unnamed = "0000000000000000: 00000002 00000000 00010000 0001 01 31339";
+        assert_eq!(parse_unix_line(unnamed), None);
+        assert_eq!(socket_inode("socket:[31337]"), Some(31337));
+        assert_eq!(socket_inode("pipe:[31337]"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_finds_holders_of_ports_and_unix_sockets() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let holders = socket_holders(&SocketDetect::Port(port)).unwrap();
+        assert!(holders.contains(&std::process::id()));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.sock");
+        let _unix = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let holders = socket_holders(&SocketDetect::UnixSocket(path)).unwrap();
+        assert!(holders.contains(&std::process::id()));
+
+        drop(listener);
+        assert!(socket_holders(&SocketDetect::Port(port)).unwrap().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sees_own_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let files = open_files(std::process::id()).unwrap();
+        assert!(files.fd_count > 0);
+        assert!(files.listening.iter().any(|socket| socket.address == address));
+    }
+}
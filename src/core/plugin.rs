@@ -0,0 +1,150 @@
+//! Daemon plugin extension point
+//!
+//! Downstream crates implement `DaemonPlugin` and register it on a `Daemon`
+//! to observe lifecycle, watcher events and cache busts without forking.
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use crate::dimensional_cache::CacheBustSeverity;
+use crate::utils::error::Result;
+
+/// Event observed by the daemon pipeline
+#[derive(Debug, Clone)]
+pub struct DaemonEvent {
+    pub kind: String,
+    pub target: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
+/// Hooks invoked by the daemon. All methods default to no-ops.
+pub trait DaemonPlugin: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn on_start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_event(&self, _event: &DaemonEvent) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_bust(&self, _target: &str, _severity: &CacheBustSeverity) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Ordered set of registered plugins shared between daemon clones
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: RwLock<Vec<Arc<dyn DaemonPlugin>>>,
+    #[cfg(feature = "plugin-dylib")]
+    libraries: std::sync::Mutex<Vec<libloading::Library>>,
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("plugins", &self.names())
+            .finish()
+    }
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, plugin: Arc<dyn DaemonPlugin>) {
+        log::info!("🔌 Registered daemon plugin: {}", plugin.name());
+        self.plugins.write().unwrap().push(plugin);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.plugins
+            .read()
+            .unwrap()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    pub fn on_start(&self) {
+        self.each("on_start", |p| p.on_start());
+    }
+
+    pub fn on_event(&self, event: &DaemonEvent) {
+        self.each("on_event", |p| p.on_event(event));
+    }
+
+    pub fn on_bust(&self, target: &str, severity: &CacheBustSeverity) {
+        self.each("on_bust", |p| p.on_bust(target, severity));
+    }
+
+    pub fn on_shutdown(&self) {
+        self.each("on_shutdown", |p| p.on_shutdown());
+    }
+
+    /// Plugin failures are logged and never abort the daemon
+    fn each<F>(&self, hook: &str, f: F)
+    where
+        F: Fn(&dyn DaemonPlugin) -> Result<()>,
+    {
+        for plugin in self.plugins.read().unwrap().iter() {
+            if let Err(e) = f(plugin.as_ref()) {
+                log::warn!("Plugin {} failed in {}: {}", plugin.name(), hook, e);
+            }
+        }
+    }
+
+    /// Load a plugin from a dynamic library exporting `declare_plugin!`
+    #[cfg(feature = "plugin-dylib")]
+    pub fn load_library<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        use crate::utils::error::BustcallError;
+
+        let path = path.as_ref();
+        let library = unsafe { libloading::Library::new(path) }.map_err(|e| {
+            BustcallError::DaemonError(format!("Failed to load plugin {}: {}", path.display(), e))
+        })?;
+
+        let plugin = unsafe {
+            let constructor: libloading::Symbol<PluginCreate> =
+                library.get(PLUGIN_CREATE_SYMBOL).map_err(|e| {
+                    BustcallError::DaemonError(format!(
+                        "Plugin {} has no entry point: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+            Box::from_raw(constructor())
+        };
+
+        self.register(Arc::from(plugin));
+        // Keep the library mapped for as long as the registry lives
+        self.libraries.lock().unwrap().push(library);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "plugin-dylib")]
+pub type PluginCreate = fn() -> *mut dyn DaemonPlugin;
+
+#[cfg(feature = "plugin-dylib")]
+pub const PLUGIN_CREATE_SYMBOL: &[u8] = b"_bustcall_plugin_create";
+
+/// Export a plugin constructor from a `cdylib` crate
+#[cfg(feature = "plugin-dylib")]
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:path) => {
+        #[no_mangle]
+        pub fn _bustcall_plugin_create() -> *mut dyn $crate::core::plugin::DaemonPlugin {
+            let plugin: Box<dyn $crate::core::plugin::DaemonPlugin> = Box::new($constructor());
+            Box::into_raw(plugin)
+        }
+    };
+}
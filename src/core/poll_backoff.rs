@@ -0,0 +1,116 @@
+//! Adaptive pid polling
+//!
+//! ```toml
+//! [global]
+//! daemon_interval_seconds = 5
+//! pid_poll_max_interval_seconds = 60
+//!
+//! [target.node]
+//! runtime = "node"
+//! pid_poll_interval_seconds = 2
+//! ```
+//!
+//! A runtime target is polled every `pid_poll_interval_seconds`, falling
+//! back to `daemon_interval_seconds`. Each poll that finds nothing changed
+//! doubles the wait before the next, up to `pid_poll_max_interval_seconds`,
+//! so a runtime left alone on a laptop costs a poll a minute rather than
+//! twelve. The first poll that finds a change, or a process event for one
+//! of the target's pids, snaps back to the target's own interval.
+
+use std::time::{Duration, Instant};
+
+/// Longest wait between two polls of a stable target
+pub const DEFAULT_MAX_POLL_INTERVAL_SECONDS: u64 = 60;
+
+#[derive(Debug, Clone)]
+pub struct PollBackoff {
+    base: Duration,
+    max: Duration,
+    current: Duration,
+    next_due: Option<Instant>,
+}
+
+impl PollBackoff {
+    /// Poll every `base`, backing off up to `max` while nothing changes
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            current: base,
+            next_due: None,
+        }
+    }
+
+    /// Poll every `interval` whether or not anything changes
+    pub fn fixed(interval: Duration) -> Self {
+        Self::new(interval, interval)
+    }
+
+    /// Wait after the last poll
+    pub fn interval(&self) -> Duration {
+        self.current
+    }
+
+    /// When the next poll is due; `None` before the first
+    pub fn next_due(&self) -> Option<Instant> {
+        self.next_due
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_due.is_none_or(|due| now >= due)
+    }
+
+    /// Schedule the next poll after one at `now` that did or did not find
+    /// a change
+    pub fn record(&mut self, now: Instant, changed: bool) {
+        self.current = if changed || self.next_due.is_none() {
+            self.base
+        } else {
+            self.current.saturating_mul(2).min(self.max)
+        };
+        self.next_due = Some(now + self.current);
+    }
+
+    /// Poll right away and at the base interval from then on
+    pub fn wake(&mut self, now: Instant) {
+        self.current = self.base;
+        self.next_due = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_polls_back_off_and_changes_snap_back() {
+        let start = Instant::now();
+        let mut backoff = PollBackoff::new(Duration::from_secs(5), Duration::from_secs(30));
+        assert!(backoff.is_due(start));
+
+        backoff.record(start, true);
+        assert_eq!(backoff.interval(), Duration::from_secs(5));
+        assert!(!backoff.is_due(start + Duration::from_secs(4)));
+
+        let intervals: Vec<u64> = (0..4)
+            .map(|_| {
+                backoff.record(start, false);
+                backoff.interval().as_secs()
+            })
+            .collect();
+        assert_eq!(intervals, [10, 20, 30, 30]);
+
+        backoff.record(start, true);
+        assert_eq!(backoff.interval(), Duration::from_secs(5));
+
+        backoff.record(start, false);
+        backoff.wake(start + Duration::from_secs(1));
+        assert!(backoff.is_due(start + Duration::from_secs(1)));
+        assert_eq!(backoff.interval(), Duration::from_secs(5));
+
+        let mut fixed = PollBackoff::fixed(Duration::from_secs(5));
+        fixed.record(start, false);
+        fixed.record(start, false);
+        assert_eq!(fixed.interval(), Duration::from_secs(5));
+    }
+}
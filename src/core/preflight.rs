@@ -0,0 +1,322 @@
+//! Startup preflight checks
+//!
+//! Runs every environment check before the daemon starts and reports all
+//! failures together, so a misconfigured host is diagnosed in one pass.
+
+use std::fmt;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::daemon::DaemonConfig;
+use crate::core::watch_backend::{inotify_usage, watches_needed};
+use crate::utils::error::{BustcallError, Result};
+
+pub(crate) const INOTIFY_WATCHES_PATH: &str = "/proc/sys/fs/inotify/max_user_watches";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreflightCheck {
+    WatchPath,
+    Runtime,
+    InotifyLimit,
+    Redis,
+    ControlSocket,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// Single preflight finding with a suggested fix
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub check: PreflightCheck,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    pub hint: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:?}] {:?}: {} (hint: {})",
+            self.severity, self.check, self.message, self.hint
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PreflightConfig {
+    pub watch_paths: Vec<PathBuf>,
+    pub runtimes: Vec<String>,
+    pub min_inotify_watches: u64,
+    pub redis_url: Option<String>,
+    pub control_address: Option<String>,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        Self {
+            watch_paths: vec![],
+            runtimes: vec![],
+            min_inotify_watches: 8192,
+            redis_url: None,
+            control_address: None,
+        }
+    }
+}
+
+impl PreflightConfig {
+    pub fn from_daemon_config(config: &DaemonConfig) -> Self {
+        Self {
+            redis_url: config
+                .leader_election
+                .as_ref()
+                .map(|election| election.redis_url.clone()),
+            control_address: Some(format!("{}:{}", config.bind_address, config.port)),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PreflightReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl PreflightReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == DiagnosticSeverity::Error)
+    }
+
+    /// Log every diagnostic and fail if any of them is an error
+    pub fn into_result(self) -> Result<()> {
+        for diagnostic in &self.diagnostics {
+            match diagnostic.severity {
+                DiagnosticSeverity::Warning => log::warn!("⚠️ {}", diagnostic),
+                DiagnosticSeverity::Error => log::error!("❌ {}", diagnostic),
+            }
+        }
+
+        if self.has_errors() {
+            let summary = self
+                .diagnostics
+                .iter()
+                .filter(|d| d.severity == DiagnosticSeverity::Error)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(BustcallError::DaemonError(format!(
+                "Preflight failed: {}",
+                summary
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, check: PreflightCheck, severity: DiagnosticSeverity, message: String, hint: &str) {
+        self.diagnostics.push(Diagnostic {
+            check,
+            severity,
+            message,
+            hint: hint.to_string(),
+        });
+    }
+}
+
+/// Run all preflight checks and collect the findings
+pub fn run_preflight(config: &PreflightConfig) -> PreflightReport {
+    let mut report = PreflightReport::default();
+
+    check_watch_paths(config, &mut report);
+    check_runtimes(config, &mut report);
+    check_inotify_limit(config, &mut report);
+    check_redis(config, &mut report);
+    check_control_socket(config, &mut report);
+
+    report
+}
+
+fn check_watch_paths(config: &PreflightConfig, report: &mut PreflightReport) {
+    for path in &config.watch_paths {
+        if !path.exists() {
+            report.push(
+                PreflightCheck::WatchPath,
+                DiagnosticSeverity::Error,
+                format!("Watch path does not exist: {}", path.display()),
+                "create the directory or remove it from the target config",
+            );
+        }
+    }
+}
+
+fn check_runtimes(config: &PreflightConfig, report: &mut PreflightReport) {
+    for runtime in &config.runtimes {
+        if find_on_path(runtime).is_none() {
+            report.push(
+                PreflightCheck::Runtime,
+                DiagnosticSeverity::Error,
+                format!("Runtime not found on PATH: {}", runtime),
+                "install the runtime or set an absolute path in the target config",
+            );
+        }
+    }
+}
+
+fn check_inotify_limit(config: &PreflightConfig, report: &mut PreflightReport) {
+    let Ok(content) = std::fs::read_to_string(INOTIFY_WATCHES_PATH) else {
+        // Not Linux, or /proc unavailable - nothing to check
+        return;
+    };
+
+    match content.trim().parse::<u64>() {
+        Ok(limit) if limit < config.min_inotify_watches => report.push(
+            PreflightCheck::InotifyLimit,
+            DiagnosticSeverity::Warning,
+            format!(
+                "inotify max_user_watches is {} (recommended >= {})",
+                limit, config.min_inotify_watches
+            ),
+            "raise it with `sysctl fs.inotify.max_user_watches=<n>`",
+        ),
+        Ok(_) => check_inotify_usage(config, report),
+        Err(e) => report.push(
+            PreflightCheck::InotifyLimit,
+            DiagnosticSeverity::Warning,
+            format!("Could not parse {}: {}", INOTIFY_WATCHES_PATH, e),
+            "check the kernel inotify configuration",
+        ),
+    }
+}
+
+/// Warns when watching the watch paths would use up, or nearly, the
+/// watches left
+fn check_inotify_usage(config: &PreflightConfig, report: &mut PreflightReport) {
+    let Some(usage) = inotify_usage() else {
+        return;
+    };
+    let needed: u64 = config
+        .watch_paths
+        .iter()
+        .filter(|path| path.is_dir())
+        .map(|path| watches_needed(path, usage.limit + 1))
+        .sum();
+    if needed == 0 || (usage.in_use + needed) * 10 <= usage.limit * 9 {
+        return;
+    }
+
+    let severity = if needed > usage.available() {
+        "exceeds"
+    } else {
+        "nearly exhausts"
+    };
+    report.push(
+        PreflightCheck::InotifyLimit,
+        DiagnosticSeverity::Warning,
+        format!(
+            "Watching {} directories {} the inotify watches left ({} of {} in use); the largest trees fall back to polling",
+            needed, severity, usage.in_use, usage.limit
+        ),
+        "raise it with `sysctl fs.inotify.max_user_watches=<n>`, or set `watch_backend = \"poll\"` or `\"fanotify\"` on the largest targets",
+    );
+}
+
+fn check_redis(config: &PreflightConfig, report: &mut PreflightReport) {
+    let Some(url) = &config.redis_url else {
+        return;
+    };
+
+    let address = redis_address(url);
+    let reachable = address
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .map(|addr| TcpStream::connect_timeout(&addr, Duration::from_secs(2)).is_ok())
+        .unwrap_or(false);
+
+    if !reachable {
+        report.push(
+            PreflightCheck::Redis,
+            DiagnosticSeverity::Error,
+            format!("Redis is not reachable at {}", address),
+            "start Redis or fix the configured redis_url",
+        );
+    }
+}
+
+fn check_control_socket(config: &PreflightConfig, report: &mut PreflightReport) {
+    let Some(address) = &config.control_address else {
+        return;
+    };
+
+    if let Err(e) = TcpListener::bind(address.as_str()) {
+        report.push(
+            PreflightCheck::ControlSocket,
+            DiagnosticSeverity::Error,
+            format!("Cannot bind control socket {}: {}", address, e),
+            "stop the process holding the port or change daemon.port",
+        );
+    }
+}
+
+/// Extract `host:port` from a `redis://[user@]host[:port][/db]` URL
+fn redis_address(url: &str) -> String {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host_port = without_scheme
+        .rsplit('@')
+        .next()
+        .unwrap_or(without_scheme)
+        .split('/')
+        .next()
+        .unwrap_or("127.0.0.1");
+
+    if host_port.contains(':') {
+        host_port.to_string()
+    } else {
+        format!("{}:6379", host_port)
+    }
+}
+
+fn find_on_path(executable: &str) -> Option<PathBuf> {
+    let candidate = Path::new(executable);
+    if candidate.components().count() > 1 {
+        return candidate.exists().then(|| candidate.to_path_buf());
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(executable))
+            .find(|path| path.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_all_failures_at_once() {
+        let config = PreflightConfig {
+            watch_paths: vec![PathBuf::from("/definitely/not/here")],
+            runtimes: vec!["bustcall-missing-runtime".to_string()],
+            ..Default::default()
+        };
+
+        let report = run_preflight(&config);
+        assert!(report.has_errors());
+        assert_eq!(report.diagnostics.len(), 2);
+        assert!(report.into_result().is_err());
+    }
+
+    #[test]
+    fn test_redis_address_parsing() {
+        assert_eq!(redis_address("redis://127.0.0.1/"), "127.0.0.1:6379");
+        assert_eq!(redis_address("redis://user@cache:6380/2"), "cache:6380");
+    }
+}
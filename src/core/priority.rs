@@ -0,0 +1,241 @@
+//! CPU affinity and scheduling priority for monitored runtimes
+//!
+//! A target whose processes together use more than its `cpu_quota` is
+//! considered to be starving other build processes; its processes are then
+//! reniced, moved to a lower I/O class and/or pinned to `cpu_set`. Raising
+//! priority again needs privileges bustcall usually lacks, so throttling is
+//! one-way for the lifetime of a process.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::process::ProcessInfo;
+use crate::core::schedule::TargetSchedule;
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IoClass {
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+/// Per-target resource policy, set alongside the target's schedule
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourcePolicy {
+    /// Combined CPU percent of the target's processes (100 per core) above
+    /// which they are throttled
+    #[serde(default)]
+    pub cpu_quota: Option<f64>,
+    /// Nice level applied when throttling, -20..=19
+    #[serde(default)]
+    pub nice: Option<i32>,
+    #[serde(default)]
+    pub io_class: Option<IoClass>,
+    /// Priority within `io_class`, 0 (highest) to 7
+    #[serde(default)]
+    pub io_level: Option<u8>,
+    /// CPUs the target is pinned to when throttling
+    #[serde(default)]
+    pub cpu_set: Option<Vec<usize>>,
+}
+
+impl ResourcePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.cpu_quota.is_none()
+    }
+
+    /// Whether `processes` together exceed the quota
+    pub fn is_starving(&self, processes: &[ProcessInfo]) -> bool {
+        let usage: f64 = processes.iter().map(|process| process.cpu_usage).sum();
+        self.cpu_quota.is_some_and(|quota| usage > quota)
+    }
+
+    /// Apply the configured nice level, I/O class and CPU set to `pid`
+    pub fn apply(&self, pid: u32) -> Result<()> {
+        if let Some(nice) = self.nice {
+            set_nice(pid, nice)?;
+        }
+        if let Some(class) = self.io_class {
+            set_io_priority(pid, class, self.io_level.unwrap_or(4))?;
+        }
+        if let Some(cpus) = &self.cpu_set {
+            set_affinity(pid, cpus)?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies resource policies to targets that exceed their CPU quota
+#[derive(Debug, Default)]
+pub struct ResourceGovernor {
+    policies: HashMap<String, ResourcePolicy>,
+    /// Pids already throttled, per target
+    throttled: HashMap<String, HashSet<u32>>,
+}
+
+impl ResourceGovernor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_targets(targets: &HashMap<String, TargetSchedule>) -> Self {
+        let mut governor = Self::new();
+        for (target, schedule) in targets {
+            governor.set_policy(target, schedule.resources.clone());
+        }
+        governor
+    }
+
+    pub fn set_policy(&mut self, target: &str, policy: ResourcePolicy) {
+        if !policy.is_empty() {
+            self.policies.insert(target.to_string(), policy);
+        }
+    }
+
+    /// Check a target's current processes and throttle them if they are
+    /// starving the machine. Returns the pids throttled by this call.
+    pub fn enforce(&mut self, target: &str, processes: &[ProcessInfo]) -> Result<Vec<u32>> {
+        let Some(policy) = self.policies.get(target) else {
+            return Ok(Vec::new());
+        };
+
+        // Forget exited processes so reused pids are throttled afresh
+        let done = self.throttled.entry(target.to_string()).or_default();
+        done.retain(|pid| processes.iter().any(|process| process.pid == *pid));
+
+        if !policy.is_starving(processes) {
+            return Ok(Vec::new());
+        }
+
+        let pending: Vec<u32> =
+            processes.iter().map(|process| process.pid).filter(|pid| !done.contains(pid)).collect();
+        let mut throttled = Vec::new();
+        for pid in pending {
+            policy.apply(pid)?;
+            done.insert(pid);
+            throttled.push(pid);
+        }
+
+        if !throttled.is_empty() {
+            log::warn!("🐢 Throttled {} over its CPU quota: pids {:?}", target, throttled);
+        }
+        Ok(throttled)
+    }
+}
+
+#[cfg(unix)]
+pub fn set_nice(pid: u32, nice: i32) -> Result<()> {
+    // SAFETY: setpriority only reads its integer arguments
+    let rc = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice.clamp(-20, 19)) };
+    check(rc, "renice", pid)
+}
+
+#[cfg(not(unix))]
+pub fn set_nice(pid: u32, _nice: i32) -> Result<()> {
+    unsupported("Nice levels", pid)
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: u32, class: IoClass, level: u8) -> Result<()> {
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+    let class = match class {
+        IoClass::RealTime => 1,
+        IoClass::BestEffort => 2,
+        IoClass::Idle => 3,
+    };
+    let priority = (class << IOPRIO_CLASS_SHIFT) | libc::c_int::from(level.min(7));
+
+    // SAFETY: ioprio_set takes three integers and touches no memory of ours
+    let rc = unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid as libc::c_int, priority) };
+    check(rc as libc::c_int, "set I/O priority of", pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_io_priority(pid: u32, _class: IoClass, _level: u8) -> Result<()> {
+    unsupported("I/O priorities", pid)
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, cpus: &[usize]) -> Result<()> {
+    // SAFETY: cpu_set_t is plain data, zeroed is a valid empty set, and
+    // CPU_SET bounds-checks the index
+    let rc = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        libc::sched_setaffinity(pid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    check(rc, "set CPU affinity of", pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(pid: u32, _cpus: &[usize]) -> Result<()> {
+    unsupported("CPU affinity", pid)
+}
+
+#[cfg(unix)]
+fn check(rc: libc::c_int, action: &str, pid: u32) -> Result<()> {
+    if rc == -1 {
+        return Err(BustcallError::ProcessError(format!(
+            "Failed to {} pid {}: {}",
+            action,
+            pid,
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn unsupported(what: &str, pid: u32) -> Result<()> {
+    Err(BustcallError::ProcessError(format!(
+        "{} are not supported on this platform (pid {})",
+        what, pid
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, cpu: f64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: "node".to_string(),
+            status: "Run".to_string(),
+            cpu_usage: cpu,
+            memory_usage: 0,
+            parent_pid: None,
+            session_id: None,
+            pgid: None,
+            container: None,
+            cmdline: Vec::new(),
+            start_time: 0,
+            open_files: None,
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_quota_counts_all_workers() {
+        let policy: ResourcePolicy = toml::from_str("cpu_quota = 150.0\nnice = 10").unwrap();
+        assert!(!policy.is_starving(&[process(1, 80.0), process(2, 60.0)]));
+        assert!(policy.is_starving(&[process(1, 80.0), process(2, 90.0)]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_renice_child() {
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        let result = set_nice(child.id(), 5);
+        child.kill().unwrap();
+        child.wait().unwrap();
+        result.unwrap();
+    }
+}
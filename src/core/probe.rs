@@ -0,0 +1,354 @@
+//! Liveness probes for runtime targets
+//!
+//! A runtime can keep its pid while being wedged: deadlocked, stuck in a
+//! retry loop, or no longer accepting connections. Each target may configure
+//! a TCP connect, HTTP GET or exec probe that is run on the daemon interval.
+//! Consecutive failures escalate the bust severity, so a wedged runtime is
+//! rebuilt well before anything notices its pid is gone.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::notify::{Notification, NotificationLevel};
+use crate::core::schedule::TargetSchedule;
+use crate::dimensional_cache::CacheBustSeverity;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProbeCheck {
+    /// Connect to `address`, e.g. `127.0.0.1:8080`
+    Tcp { address: String },
+    /// `GET` a plain `http://` URL and compare the status code
+    Http {
+        url: String,
+        #[serde(default = "default_expected_status")]
+        expected_status: u16,
+    },
+    /// Run a command and compare its exit code
+    Exec {
+        command: Vec<String>,
+        #[serde(default)]
+        expected_exit: i32,
+    },
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+/// `[target.<name>.liveness]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LivenessProbe {
+    #[serde(flatten)]
+    pub check: ProbeCheck,
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// Consecutive failures before the bust is raised to High; twice as many
+    /// raise it to Critical
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+fn default_timeout_seconds() -> u64 {
+    5
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+impl LivenessProbe {
+    /// Run the probe once; the error describes why it failed
+    pub fn run(&self) -> std::result::Result<(), String> {
+        let timeout = Duration::from_secs(self.timeout_seconds.max(1));
+        match &self.check {
+            ProbeCheck::Tcp { address } => connect(address, timeout).map(|_| ()),
+            ProbeCheck::Http { url, expected_status } => {
+                let status = http_get_status(url, timeout)?;
+                if status != *expected_status {
+                    return Err(format!("HTTP {} (expected {})", status, expected_status));
+                }
+                Ok(())
+            }
+            ProbeCheck::Exec { command, expected_exit } => {
+                let code = exec_exit_code(command, timeout)?;
+                if code != *expected_exit {
+                    return Err(format!("exit code {} (expected {})", code, expected_exit));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Severity for the `failures`th consecutive failure; `None` between the
+    /// escalation steps so a persistent failure is not busted every interval
+    pub fn severity_after(&self, failures: u32) -> Option<CacheBustSeverity> {
+        let threshold = self.failure_threshold.max(1);
+        if failures == threshold * 2 {
+            Some(CacheBustSeverity::Critical)
+        } else if failures == threshold {
+            Some(CacheBustSeverity::High)
+        } else if failures == 1 {
+            Some(CacheBustSeverity::Medium)
+        } else {
+            None
+        }
+    }
+}
+
+fn connect(address: &str, timeout: Duration) -> std::result::Result<TcpStream, String> {
+    let addresses = address
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {}: {}", address, e))?;
+
+    let mut last_error = format!("{} resolved to no addresses", address);
+    for addr in addresses {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = format!("connect to {} failed: {}", addr, e),
+        }
+    }
+    Err(last_error)
+}
+
+fn http_get_status(url: &str, timeout: Duration) -> std::result::Result<u16, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("only http:// URLs can be probed, got {}", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+
+    let mut stream = connect(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: bustcall-probe\r\nConnection: close\r\n\r\n",
+        path, authority
+    )
+    .map_err(|e| format!("request to {} failed: {}", url, e))?;
+
+    // The status line is all we need
+    let mut head = [0u8; 64];
+    let read = stream
+        .read(&mut head)
+        .map_err(|e| format!("no response from {}: {}", url, e))?;
+    let head = String::from_utf8_lossy(&head[..read]);
+    head.split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| format!("malformed response from {}", url))
+}
+
+fn exec_exit_code(command: &[String], timeout: Duration) -> std::result::Result<i32, String> {
+    let (program, args) = command.split_first().ok_or("exec probe has an empty command")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", program, e))?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status.code().unwrap_or(-1)),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("{} timed out after {}s", program, timeout.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("failed to wait for {}: {}", program, e)),
+        }
+    }
+}
+
+/// Outcome of one probe run that needs acting upon
+#[derive(Debug, Clone)]
+pub struct ProbeEvent {
+    pub target: String,
+    pub failures: u32,
+    /// `None` when the probe recovered
+    pub error: Option<String>,
+    pub bust: Option<CacheBustSeverity>,
+}
+
+impl ProbeEvent {
+    pub fn notification(&self) -> Notification {
+        let (level, message) = match (&self.error, &self.bust) {
+            (None, _) => (
+                NotificationLevel::Info,
+                format!("{} liveness probe recovered after {} failures", self.target, self.failures),
+            ),
+            (Some(error), severity) => (
+                match severity {
+                    Some(CacheBustSeverity::Critical) => NotificationLevel::Critical,
+                    Some(CacheBustSeverity::High) => NotificationLevel::Error,
+                    _ => NotificationLevel::Warning,
+                },
+                format!("{} liveness probe failed {} time(s): {}", self.target, self.failures, error),
+            ),
+        };
+
+        Notification::new(level, &message)
+            .title("Liveness probe")
+            .component("liveness")
+            .target(&self.target)
+            .with_metadata("failures", &self.failures.to_string())
+    }
+}
+
+/// Runs every target's probe and counts consecutive failures
+#[derive(Debug, Default)]
+pub struct LivenessProber {
+    probes: HashMap<String, LivenessProbe>,
+    failures: HashMap<String, u32>,
+}
+
+impl LivenessProber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_targets(targets: &HashMap<String, TargetSchedule>) -> Self {
+        let mut prober = Self::new();
+        for (target, schedule) in targets {
+            if let Some(probe) = &schedule.liveness {
+                prober.set_probe(target, probe.clone());
+            }
+        }
+        prober
+    }
+
+    pub fn set_probe(&mut self, target: &str, probe: LivenessProbe) {
+        self.probes.insert(target.to_string(), probe);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+
+    /// Probe every target once. Returns failures at an escalation step and
+    /// recoveries; failures in between are only logged.
+    pub fn probe_all(&mut self) -> Vec<ProbeEvent> {
+        let mut events = Vec::new();
+
+        for (target, probe) in &self.probes {
+            let failures = self.failures.entry(target.clone()).or_insert(0);
+            match probe.run() {
+                Ok(()) => {
+                    if *failures > 0 {
+                        log::info!("💚 {} liveness probe recovered", target);
+                        events.push(ProbeEvent {
+                            target: target.clone(),
+                            failures: *failures,
+                            error: None,
+                            bust: None,
+                        });
+                    }
+                    *failures = 0;
+                }
+                Err(error) => {
+                    *failures += 1;
+                    log::warn!("💔 {} liveness probe failed ({}): {}", target, failures, error);
+                    if let Some(severity) = probe.severity_after(*failures) {
+                        events.push(ProbeEvent {
+                            target: target.clone(),
+                            failures: *failures,
+                            error: Some(error),
+                            bust: Some(severity),
+                        });
+                    }
+                }
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn probe(check: ProbeCheck) -> LivenessProbe {
+        LivenessProbe {
+            check,
+            timeout_seconds: 1,
+            failure_threshold: 2,
+        }
+    }
+
+    #[test]
+    fn test_failures_escalate_in_steps() {
+        let probe = probe(ProbeCheck::Tcp {
+            address: "127.0.0.1:1".to_string(),
+        });
+        let steps: Vec<_> = (1..=5).map(|n| probe.severity_after(n)).collect();
+        assert_eq!(
+            steps,
+            vec![
+                Some(CacheBustSeverity::Medium),
+                Some(CacheBustSeverity::High),
+                None,
+                Some(CacheBustSeverity::Critical),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tcp_and_http_probes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 512];
+            let _ = stream.read(&mut request).unwrap();
+            stream.write_all(b"HTTP/1.1 503 Service Unavailable\r\n\r\n").unwrap();
+        });
+
+        let http = probe(ProbeCheck::Http {
+            url: format!("http://{}/healthz", address),
+            expected_status: 200,
+        });
+        assert_eq!(http.run().unwrap_err(), "HTTP 503 (expected 200)");
+        server.join().unwrap();
+
+        let parsed: LivenessProbe = toml::from_str(&format!("type = \"tcp\"\naddress = \"{}\"", address)).unwrap();
+        assert_eq!(parsed.failure_threshold, 3);
+        // The listener is gone, so nothing accepts on that port any more
+        assert!(parsed.run().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec_probe_checks_exit_code() {
+        let ok = probe(ProbeCheck::Exec {
+            command: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            expected_exit: 3,
+        });
+        assert!(ok.run().is_ok());
+
+        let wedged = probe(ProbeCheck::Exec {
+            command: vec!["sleep".to_string(), "5".to_string()],
+            expected_exit: 0,
+        });
+        assert!(wedged.run().unwrap_err().contains("timed out"));
+    }
+}
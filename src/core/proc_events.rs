@@ -0,0 +1,514 @@
+//! Process start/exit events
+//!
+//! On Linux the kernel's proc connector (netlink) reports every exec and
+//! exit as it happens; it needs CAP_NET_ADMIN. On macOS kqueue reports exits
+//! of known processes immediately, with new processes picked up by periodic
+//! rescans. Everywhere else, or when the native backend can't be opened,
+//! events come from diffing the process table on an interval.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::process::ProcessManager;
+use crate::utils::error::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EventBackend {
+    /// Best native backend for the platform, falling back to polling
+    #[default]
+    Auto,
+    Netlink,
+    Kqueue,
+    Polling,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessEvent {
+    /// A process started, or replaced its image with exec
+    Exec { pid: u32 },
+    Exit { pid: u32, exit_code: Option<i32> },
+}
+
+impl ProcessEvent {
+    pub fn pid(&self) -> u32 {
+        match self {
+            ProcessEvent::Exec { pid } | ProcessEvent::Exit { pid, .. } => *pid,
+        }
+    }
+}
+
+pub trait ProcessEventSource: Send {
+    fn name(&self) -> &'static str;
+
+    /// Wait up to `timeout` for events; an empty result means none arrived
+    fn next_events(&mut self, timeout: Duration) -> Result<Vec<ProcessEvent>>;
+}
+
+/// Open the configured backend. Native backends that can't be opened (no
+/// privileges, unsupported platform) fall back to polling every `interval`.
+pub fn open_event_source(backend: EventBackend, interval: Duration) -> Box<dyn ProcessEventSource> {
+    let native: Option<Result<Box<dyn ProcessEventSource>>> = match backend {
+        EventBackend::Polling => None,
+        #[cfg(target_os = "linux")]
+        EventBackend::Auto | EventBackend::Netlink => {
+            Some(netlink::NetlinkSource::open().map(|s| Box::new(s) as Box<dyn ProcessEventSource>))
+        }
+        #[cfg(target_os = "macos")]
+        EventBackend::Auto | EventBackend::Kqueue => {
+            Some(kqueue::KqueueSource::open(interval).map(|s| Box::new(s) as Box<dyn ProcessEventSource>))
+        }
+        #[cfg(not(target_os = "macos"))]
+        EventBackend::Kqueue => {
+            log::warn!("kqueue process events are only available on macOS");
+            None
+        }
+        #[cfg(not(target_os = "linux"))]
+        EventBackend::Netlink => {
+            log::warn!("netlink process events are only available on Linux");
+            None
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        EventBackend::Auto => None,
+    };
+
+    match native {
+        Some(Ok(source)) => {
+            log::info!("🛰️ Process events via {}", source.name());
+            source
+        }
+        Some(Err(e)) => {
+            log::warn!("Native process events unavailable ({}), polling instead", e);
+            Box::new(PollingSource::new(interval))
+        }
+        None => Box::new(PollingSource::new(interval)),
+    }
+}
+
+/// Diffs the process table every `interval`
+pub struct PollingSource {
+    processes: ProcessManager,
+    /// Start time by pid
+    known: Option<HashMap<u32, u64>>,
+    interval: Duration,
+    last_poll: Option<Instant>,
+}
+
+impl PollingSource {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            processes: ProcessManager::new(),
+            known: None,
+            interval,
+            last_poll: None,
+        }
+    }
+}
+
+impl ProcessEventSource for PollingSource {
+    fn name(&self) -> &'static str {
+        "polling"
+    }
+
+    fn next_events(&mut self, timeout: Duration) -> Result<Vec<ProcessEvent>> {
+        if let Some(last) = self.last_poll {
+            let due = self.interval.saturating_sub(last.elapsed());
+            if due > timeout {
+                std::thread::sleep(timeout);
+                return Ok(Vec::new());
+            }
+            std::thread::sleep(due);
+        }
+        self.last_poll = Some(Instant::now());
+
+        let current = self.processes.start_times()?;
+
+        // The first scan only establishes the baseline
+        let events = match &self.known {
+            Some(known) => diff_tables(known, &current),
+            None => Vec::new(),
+        };
+        self.known = Some(current);
+        Ok(events)
+    }
+}
+
+/// Starts and exits between two scans of the process table, start times by
+/// pid. A pid now held by a process started at another time was reused: its
+/// old process exited and a new one started.
+fn diff_tables(previous: &HashMap<u32, u64>, current: &HashMap<u32, u64>) -> Vec<ProcessEvent> {
+    let mut events: Vec<ProcessEvent> = previous
+        .iter()
+        .filter(|(pid, started)| current.get(*pid) != Some(*started))
+        .map(|(&pid, _)| ProcessEvent::Exit { pid, exit_code: None })
+        .chain(
+            current
+                .iter()
+                .filter(|(pid, started)| previous.get(*pid) != Some(*started))
+                .map(|(&pid, _)| ProcessEvent::Exec { pid }),
+        )
+        .collect();
+    // Stable, so a reused pid's exit stays ahead of its start
+    events.sort_by_key(|event| event.pid());
+    events
+}
+
+#[cfg(target_os = "linux")]
+mod netlink {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::time::Duration;
+
+    use super::{ProcessEvent, ProcessEventSource};
+    use crate::utils::error::{BustcallError, Result};
+
+    const NETLINK_CONNECTOR: libc::c_int = 11;
+    const CN_IDX_PROC: u32 = 1;
+    const CN_VAL_PROC: u32 = 1;
+    const PROC_CN_MCAST_LISTEN: u32 = 1;
+
+    const NLMSG_HDRLEN: usize = 16;
+    const CN_MSG_LEN: usize = 20;
+
+    const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+    const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+
+    /// Subscription to the kernel proc connector
+    pub struct NetlinkSource {
+        socket: OwnedFd,
+        buffer: Vec<u8>,
+    }
+
+    impl NetlinkSource {
+        pub fn open() -> Result<Self> {
+            // SAFETY: plain socket/bind/send calls on a descriptor we own;
+            // sockaddr_nl is plain data and valid when zeroed
+            unsafe {
+                let fd = libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, NETLINK_CONNECTOR);
+                if fd < 0 {
+                    return Err(os_error("open netlink socket"));
+                }
+                let socket = OwnedFd::from_raw_fd(fd);
+
+                let mut address: libc::sockaddr_nl = std::mem::zeroed();
+                address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+                // Let the kernel pick the port id so several sources can coexist
+                address.nl_pid = 0;
+                address.nl_groups = CN_IDX_PROC;
+                let rc = libc::bind(
+                    fd,
+                    &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+                );
+                if rc < 0 {
+                    return Err(os_error("bind proc connector"));
+                }
+
+                let message = listen_message();
+                let sent = libc::send(fd, message.as_ptr() as *const libc::c_void, message.len(), 0);
+                if sent < 0 {
+                    return Err(os_error("subscribe to proc connector"));
+                }
+
+                Ok(Self {
+                    socket,
+                    buffer: vec![0; 8192],
+                })
+            }
+        }
+    }
+
+    impl ProcessEventSource for NetlinkSource {
+        fn name(&self) -> &'static str {
+            "netlink"
+        }
+
+        fn next_events(&mut self, timeout: Duration) -> Result<Vec<ProcessEvent>> {
+            let fd = self.socket.as_raw_fd();
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let timeout_ms = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+
+            // SAFETY: pollfd and buffer outlive the calls; recv is bounded by
+            // the buffer length
+            let mut events = Vec::new();
+            unsafe {
+                if libc::poll(&mut pollfd, 1, timeout_ms) <= 0 {
+                    return Ok(events);
+                }
+                loop {
+                    let received = libc::recv(
+                        fd,
+                        self.buffer.as_mut_ptr() as *mut libc::c_void,
+                        self.buffer.len(),
+                        libc::MSG_DONTWAIT,
+                    );
+                    if received <= 0 {
+                        break;
+                    }
+                    events.extend(parse_datagram(&self.buffer[..received as usize]));
+                }
+            }
+            Ok(events)
+        }
+    }
+
+    fn os_error(action: &str) -> BustcallError {
+        BustcallError::ProcessError(format!("Failed to {}: {}", action, std::io::Error::last_os_error()))
+    }
+
+    /// nlmsghdr + cn_msg + PROC_CN_MCAST_LISTEN
+    fn listen_message() -> Vec<u8> {
+        let total = NLMSG_HDRLEN + CN_MSG_LEN + 4;
+        let mut message = Vec::with_capacity(total);
+        message.extend_from_slice(&(total as u32).to_ne_bytes());
+        message.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        message.extend_from_slice(&0u16.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+
+        message.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        message.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes()); // seq
+        message.extend_from_slice(&0u32.to_ne_bytes()); // ack
+        message.extend_from_slice(&4u16.to_ne_bytes()); // len
+        message.extend_from_slice(&0u16.to_ne_bytes()); // flags
+
+        message.extend_from_slice(&PROC_CN_MCAST_LISTEN.to_ne_bytes());
+        message
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_ne_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Every netlink message in one datagram
+    pub(super) fn parse_datagram(data: &[u8]) -> Vec<ProcessEvent> {
+        let mut events = Vec::new();
+        let mut offset = 0;
+        while let Some(length) = read_u32(data, offset).map(|len| len as usize) {
+            if length < NLMSG_HDRLEN || offset + length > data.len() {
+                break;
+            }
+            let payload = &data[offset + NLMSG_HDRLEN..offset + length];
+            if let Some(event) = payload.get(CN_MSG_LEN..).and_then(parse_proc_event) {
+                events.push(event);
+            }
+            // Messages are 4-byte aligned
+            offset += (length + 3) & !3;
+        }
+        events
+    }
+
+    /// struct proc_event: what, cpu, timestamp_ns, then the event union.
+    /// Thread events (pid != tgid) are skipped.
+    fn parse_proc_event(event: &[u8]) -> Option<ProcessEvent> {
+        const DATA: usize = 16;
+        let what = read_u32(event, 0)?;
+        let pid = read_u32(event, DATA)?;
+        let tgid = read_u32(event, DATA + 4)?;
+        if pid != tgid {
+            return None;
+        }
+
+        match what {
+            PROC_EVENT_EXEC => Some(ProcessEvent::Exec { pid }),
+            PROC_EVENT_EXIT => {
+                let status = read_u32(event, DATA + 8)? as i32;
+                Some(ProcessEvent::Exit {
+                    pid,
+                    exit_code: Some((status >> 8) & 0xff),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn datagram(what: u32, fields: &[u32]) -> Vec<u8> {
+            let mut event = Vec::new();
+            event.extend_from_slice(&what.to_ne_bytes());
+            event.extend_from_slice(&0u32.to_ne_bytes());
+            event.extend_from_slice(&0u64.to_ne_bytes());
+            for field in fields {
+                event.extend_from_slice(&field.to_ne_bytes());
+            }
+
+            let total = NLMSG_HDRLEN + CN_MSG_LEN + event.len();
+            let mut data = (total as u32).to_ne_bytes().to_vec();
+            data.resize(NLMSG_HDRLEN + CN_MSG_LEN, 0);
+            data.extend(event);
+            data
+        }
+
+        #[test]
+        fn test_parse_exec_and_exit() {
+            assert_eq!(
+                parse_datagram(&datagram(PROC_EVENT_EXEC, &[42, 42])),
+                vec![ProcessEvent::Exec { pid: 42 }]
+            );
+            assert_eq!(
+                parse_datagram(&datagram(PROC_EVENT_EXIT, &[42, 42, 3 << 8, 17])),
+                vec![ProcessEvent::Exit { pid: 42, exit_code: Some(3) }]
+            );
+            // Thread exit
+            assert!(parse_datagram(&datagram(PROC_EVENT_EXIT, &[43, 42, 0, 17])).is_empty());
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod kqueue {
+    use std::collections::HashSet;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::time::{Duration, Instant};
+
+    use super::{ProcessEvent, ProcessEventSource};
+    use crate::core::process::ProcessManager;
+    use crate::utils::error::{BustcallError, Result};
+
+    /// Exit notifications for every known process, plus rescans every
+    /// `interval` to register new ones
+    pub struct KqueueSource {
+        queue: OwnedFd,
+        processes: ProcessManager,
+        known: HashSet<u32>,
+        interval: Duration,
+        last_scan: Option<Instant>,
+    }
+
+    impl KqueueSource {
+        pub fn open(interval: Duration) -> Result<Self> {
+            // SAFETY: kqueue returns a new descriptor we take ownership of
+            let fd = unsafe { libc::kqueue() };
+            if fd < 0 {
+                return Err(BustcallError::ProcessError(format!(
+                    "Failed to open kqueue: {}",
+                    std::io::Error::last_os_error()
+                )));
+            }
+
+            Ok(Self {
+                // SAFETY: fd is a freshly created, valid descriptor
+                queue: unsafe { OwnedFd::from_raw_fd(fd) },
+                processes: ProcessManager::new(),
+                known: HashSet::new(),
+                interval,
+                last_scan: None,
+            })
+        }
+
+        /// Register exit notifications for processes started since the last
+        /// scan and report them
+        fn rescan(&mut self) -> Result<Vec<ProcessEvent>> {
+            let first = self.last_scan.is_none();
+            self.last_scan = Some(Instant::now());
+
+            let mut events = Vec::new();
+            for pid in self.processes.pids()? {
+                if !self.known.insert(pid) {
+                    continue;
+                }
+                let change = libc::kevent {
+                    ident: pid as libc::uintptr_t,
+                    filter: libc::EVFILT_PROC,
+                    flags: libc::EV_ADD | libc::EV_ONESHOT,
+                    fflags: libc::NOTE_EXIT,
+                    data: 0,
+                    udata: std::ptr::null_mut(),
+                };
+                // SAFETY: registers a single change; the process may already
+                // be gone, which is reported as an error and ignored
+                unsafe {
+                    libc::kevent(
+                        self.queue.as_raw_fd(),
+                        &change,
+                        1,
+                        std::ptr::null_mut(),
+                        0,
+                        std::ptr::null(),
+                    );
+                }
+                if !first {
+                    events.push(ProcessEvent::Exec { pid });
+                }
+            }
+            Ok(events)
+        }
+    }
+
+    impl ProcessEventSource for KqueueSource {
+        fn name(&self) -> &'static str {
+            "kqueue"
+        }
+
+        fn next_events(&mut self, timeout: Duration) -> Result<Vec<ProcessEvent>> {
+            let mut events = match self.last_scan {
+                Some(last) if last.elapsed() < self.interval => Vec::new(),
+                _ => self.rescan()?,
+            };
+
+            let timeout = libc::timespec {
+                tv_sec: timeout.as_secs() as libc::time_t,
+                tv_nsec: timeout.subsec_nanos() as libc::c_long,
+            };
+            // SAFETY: zeroed kevent structs are valid output slots
+            let mut ready: [libc::kevent; 64] = unsafe { std::mem::zeroed() };
+            let count = unsafe {
+                libc::kevent(
+                    self.queue.as_raw_fd(),
+                    std::ptr::null(),
+                    0,
+                    ready.as_mut_ptr(),
+                    ready.len() as libc::c_int,
+                    &timeout,
+                )
+            };
+
+            for event in ready.iter().take(count.max(0) as usize) {
+                let pid = event.ident as u32;
+                self.known.remove(&pid);
+                events.push(ProcessEvent::Exit { pid, exit_code: None });
+            }
+            Ok(events)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polling_diff_reports_starts_and_exits() {
+        let before: HashMap<u32, u64> = [(1, 100), (2, 100), (3, 100)].into_iter().collect();
+        let after: HashMap<u32, u64> = [(1, 100), (3, 250), (4, 250)].into_iter().collect();
+        assert_eq!(
+            diff_tables(&before, &after),
+            vec![
+                ProcessEvent::Exit { pid: 2, exit_code: None },
+                ProcessEvent::Exit { pid: 3, exit_code: None },
+                ProcessEvent::Exec { pid: 3 },
+                ProcessEvent::Exec { pid: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backend_from_config() {
+        #[derive(Deserialize)]
+        struct Monitoring {
+            event_backend: EventBackend,
+        }
+        let config: Monitoring = toml::from_str("event_backend = \"polling\"").unwrap();
+        assert_eq!(config.event_backend, EventBackend::Polling);
+    }
+}
@@ -1,10 +1,111 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+
+use crate::core::container::{ContainerInfo, ContainerResolver};
+use crate::core::fingerprint::{EnvFingerprint, FingerprintConfig, Fingerprinter};
+use crate::core::open_files::{open_files, socket_holders, OpenFiles, SocketDetect};
+use crate::core::sandbox::SandboxProfile;
+use crate::core::schedule::TargetSchedule;
 use crate::utils::error::{BustcallError, Result};
 
-#[derive(Debug, Clone)]
-pub enum ProcessFilter {
-    All,
-    Pid(u32),
-    NamePattern(String),
+/// Process selection; every criterion that is set must match.
+/// `ProcessFilter::default()` matches all processes.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessFilter {
+    pub pid: Option<u32>,
+    /// Substring of the process name
+    pub name_pattern: Option<String>,
+    pub name_regex: Option<Regex>,
+    /// Minimum CPU usage in percent
+    pub min_cpu_usage: Option<f64>,
+    /// Minimum resident memory in bytes
+    pub min_memory_usage: Option<u64>,
+    /// Only this process and its descendants
+    pub tree_root: Option<u32>,
+    /// Container name or ID prefix the process must run in
+    pub container: Option<String>,
+}
+
+impl ProcessFilter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn pid(mut self, pid: u32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    pub fn name(mut self, pattern: &str) -> Self {
+        self.name_pattern = Some(pattern.to_string());
+        self
+    }
+
+    pub fn regex(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| BustcallError::ProcessError(format!("Invalid process regex '{}': {}", pattern, e)))?;
+        self.name_regex = Some(regex);
+        Ok(self)
+    }
+
+    pub fn min_cpu(mut self, percent: f64) -> Self {
+        self.min_cpu_usage = Some(percent);
+        self
+    }
+
+    pub fn min_memory(mut self, bytes: u64) -> Self {
+        self.min_memory_usage = Some(bytes);
+        self
+    }
+
+    pub fn tree_root(mut self, pid: u32) -> Self {
+        self.tree_root = Some(pid);
+        self
+    }
+
+    pub fn container(mut self, selector: &str) -> Self {
+        self.container = Some(selector.to_string());
+        self
+    }
+
+    /// Per-process criteria only; `tree_root` needs the whole table and is
+    /// applied by `ProcessManager::list_processes`
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        self.pid.is_none_or(|pid| process.pid == pid)
+            && self
+                .name_pattern
+                .as_ref()
+                .is_none_or(|pattern| process.name.contains(pattern.as_str()))
+            && self
+                .name_regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&process.name))
+            && self.min_cpu_usage.is_none_or(|min| process.cpu_usage >= min)
+            && self.min_memory_usage.is_none_or(|min| process.memory_usage >= min)
+            && self.container.as_ref().is_none_or(|selector| {
+                process
+                    .container
+                    .as_ref()
+                    .is_some_and(|container| container.matches(selector))
+            })
+    }
+}
+
+/// A process, told apart from others holding its pid before or after it
+/// by when it started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProcessIdentity {
+    pub pid: u32,
+    /// Seconds since the Unix epoch, so a pid reused within the same second
+    /// goes unnoticed
+    pub start_time: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -13,21 +114,797 @@ pub struct ProcessInfo {
     pub name: String,
     pub status: String,
     pub cpu_usage: f64,
+    /// Resident set size; the working set on Windows
+    pub memory_usage: u64,
+    pub parent_pid: Option<u32>,
+    /// Login session on Unix, terminal services session on Windows
+    pub session_id: Option<u32>,
+    /// Process group id; only available on Linux
+    pub pgid: Option<u32>,
+    /// Container the process runs in, if any
+    pub container: Option<ContainerInfo>,
+    pub cmdline: Vec<String>,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+    /// Only filled in by `ProcessManager::inspect`
+    pub open_files: Option<OpenFiles>,
+    /// Only filled in by `ProcessManager::inspect`
+    pub fingerprint: Option<EnvFingerprint>,
+}
+
+impl ProcessInfo {
+    pub fn identity(&self) -> ProcessIdentity {
+        ProcessIdentity {
+            pid: self.pid,
+            start_time: self.start_time,
+        }
+    }
+}
+
+/// `node.exe` -> `node`
+fn executable_stem(name: &str) -> &str {
+    match name.len().checked_sub(4) {
+        Some(split) if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(".exe") => &name[..split],
+        _ => name,
+    }
+}
+
+/// How `RuntimeLookup` compares processes against its pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// Process name equals the pattern
+    #[default]
+    ExactName,
+    /// Full command line contains the pattern, like `pgrep -f`
+    Cmdline,
+}
+
+/// Which process `find_runtime_pid` picks when several match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MultipleMatches {
+    /// Earliest started, usually the supervisor of a worker pool
+    #[default]
+    Oldest,
+    Newest,
+    /// Treat more than one match as an error
+    Unique,
+}
+
+/// Native replacement for `pgrep` when resolving a runtime target to a pid
+#[derive(Debug, Clone)]
+pub struct RuntimeLookup {
+    pub pattern: String,
+    pub mode: MatchMode,
+    pub multiple: MultipleMatches,
+    /// Skip bustcall itself and everything it spawned, whose command lines
+    /// often mention the runtime being looked up
+    pub exclude_own_tree: bool,
+    /// Only processes holding this socket; with an empty `pattern`, every
+    /// process holding it
+    pub socket: Option<SocketDetect>,
+}
+
+impl RuntimeLookup {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            mode: MatchMode::default(),
+            multiple: MultipleMatches::default(),
+            exclude_own_tree: true,
+            socket: None,
+        }
+    }
+
+    /// Processes holding `socket`, whatever their name
+    pub fn by_socket(socket: SocketDetect) -> Self {
+        Self::new("").socket(socket)
+    }
+
+    pub fn mode(mut self, mode: MatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn multiple(mut self, multiple: MultipleMatches) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn include_own_tree(mut self) -> Self {
+        self.exclude_own_tree = false;
+        self
+    }
+
+    pub fn socket(mut self, socket: SocketDetect) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
+    /// Name or socket the lookup is for, for messages
+    pub fn describe(&self) -> String {
+        match (&self.socket, self.pattern.is_empty()) {
+            (Some(socket), true) => socket.to_string(),
+            (Some(socket), false) => format!("{} on {}", self.pattern, socket),
+            (None, _) => self.pattern.clone(),
+        }
+    }
+
+    /// Whether the name matches; the socket is checked by `find_runtime`
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        if self.pattern.is_empty() {
+            return self.socket.is_some();
+        }
+        match self.mode {
+            // Windows names carry the extension and are case-insensitive
+            MatchMode::ExactName if cfg!(windows) => {
+                executable_stem(&process.name).eq_ignore_ascii_case(executable_stem(&self.pattern))
+            }
+            MatchMode::ExactName => process.name == self.pattern,
+            MatchMode::Cmdline => process.cmdline.join(" ").contains(self.pattern.as_str()),
+        }
+    }
+}
+
+/// Restart limits for a target with a `restart_command`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    /// Attempts allowed before giving up on the target
+    #[serde(default = "default_restart_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_restart_initial_backoff")]
+    pub initial_backoff_seconds: u64,
+    #[serde(default = "default_restart_max_backoff")]
+    pub max_backoff_seconds: u64,
+    /// Attempts are forgotten once the target stays up this long
+    #[serde(default = "default_restart_reset_after")]
+    pub reset_after_seconds: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_restart_max_attempts(),
+            initial_backoff_seconds: default_restart_initial_backoff(),
+            max_backoff_seconds: default_restart_max_backoff(),
+            reset_after_seconds: default_restart_reset_after(),
+        }
+    }
+}
+
+fn default_restart_max_attempts() -> u32 {
+    5
+}
+
+fn default_restart_initial_backoff() -> u64 {
+    1
+}
+
+fn default_restart_max_backoff() -> u64 {
+    60
+}
+
+fn default_restart_reset_after() -> u64 {
+    300
+}
+
+impl RestartPolicy {
+    /// Wait required after `attempts` restarts before the next one, doubling
+    /// each time
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        if attempts == 0 {
+            return Duration::ZERO;
+        }
+        let factor = 1u64.checked_shl(attempts - 1).unwrap_or(u64::MAX);
+        Duration::from_secs(
+            self.initial_backoff_seconds
+                .saturating_mul(factor)
+                .min(self.max_backoff_seconds),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartOutcome {
+    Started { pid: u32, attempt: u32 },
+    /// Too soon after the previous attempt
+    BackingOff { retry_in: Duration },
+    /// `max_attempts` used up; the target is left alone until it has been
+    /// quiet for `reset_after_seconds`
+    GaveUp { attempts: u32 },
+}
+
+#[derive(Debug, Default)]
+struct RestartState {
+    attempts: u32,
+    last_attempt: Option<Instant>,
+}
+
+impl RestartState {
+    /// Account for a restart request at `now`; `Ok` carries the attempt number
+    fn begin(&mut self, policy: &RestartPolicy, now: Instant) -> std::result::Result<u32, RestartOutcome> {
+        if let Some(last) = self.last_attempt {
+            let since = now.duration_since(last);
+            if since >= Duration::from_secs(policy.reset_after_seconds) {
+                self.attempts = 0;
+            } else if self.attempts >= policy.max_attempts {
+                return Err(RestartOutcome::GaveUp { attempts: self.attempts });
+            } else if since < policy.backoff(self.attempts) {
+                return Err(RestartOutcome::BackingOff {
+                    retry_in: policy.backoff(self.attempts) - since,
+                });
+            }
+        }
+
+        self.attempts += 1;
+        self.last_attempt = Some(now);
+        Ok(self.attempts)
+    }
+}
+
+/// How long a restart command may take to fail before it is assumed to be
+/// running (or to have handed off to a service manager)
+const RESTART_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+struct RestartTarget {
+    command: Vec<String>,
+    policy: RestartPolicy,
+    sandbox: Option<SandboxProfile>,
+}
+
+/// Samples kept per process by default; an hour at a 5 second interval
+pub const DEFAULT_METRICS_CAPACITY: usize = 720;
+
+/// One point in a process's metrics history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSample {
+    pub timestamp: DateTime<Utc>,
+    pub cpu_usage: f64,
     pub memory_usage: u64,
+    pub status: String,
 }
 
+/// Process table backed by sysinfo.
+///
+/// CPU usage is computed between two refreshes, so the first listing after
+/// `new` reports 0% for every process. Every process returned by
+/// `list_processes` gets a sample appended to its bounded metrics history.
 #[derive(Debug)]
 pub struct ProcessManager {
-    // Implementation details
+    system: Mutex<System>,
+    metrics: Mutex<HashMap<u32, VecDeque<MetricSample>>>,
+    metrics_capacity: usize,
+    containers: ContainerResolver,
+    fingerprinter: Mutex<Fingerprinter>,
+    restart_targets: HashMap<String, RestartTarget>,
+    restarts: Mutex<HashMap<String, RestartState>>,
 }
 
 impl ProcessManager {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            system: Mutex::new(System::new_all()),
+            metrics: Mutex::new(HashMap::new()),
+            metrics_capacity: DEFAULT_METRICS_CAPACITY,
+            containers: ContainerResolver::new(),
+            fingerprinter: Mutex::new(Fingerprinter::new()),
+            restart_targets: HashMap::new(),
+            restarts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Enable `restart` for targets that configure a `restart_command`
+    pub fn with_restart_targets(mut self, targets: &HashMap<String, TargetSchedule>) -> Self {
+        for (target, schedule) in targets {
+            if let Some(command) = schedule.restart_command.clone().filter(|c| !c.is_empty()) {
+                self.restart_targets.insert(
+                    target.clone(),
+                    RestartTarget {
+                        command,
+                        policy: schedule.restart.clone(),
+                        sandbox: schedule.sandbox.clone(),
+                    },
+                );
+            }
+        }
+        self
+    }
+
+    pub fn can_restart(&self, target: &str) -> bool {
+        self.restart_targets.contains_key(target)
     }
-    
-    pub fn list_processes(&self, filter: ProcessFilter) -> Result<Vec<ProcessInfo>> {
-        // Placeholder implementation
-        Ok(vec![])
+
+    /// Run the target's `restart_command`, subject to its backoff and
+    /// attempt limit. A command that exits non-zero within a short grace
+    /// period counts as a failed attempt.
+    pub fn restart(&self, target: &str) -> Result<RestartOutcome> {
+        let restart = self.restart_targets.get(target).ok_or_else(|| {
+            BustcallError::ProcessError(format!("No restart_command configured for {}", target))
+        })?;
+
+        let attempt = match self
+            .restarts
+            .lock()
+            .unwrap()
+            .entry(target.to_string())
+            .or_default()
+            .begin(&restart.policy, Instant::now())
+        {
+            Ok(attempt) => attempt,
+            Err(outcome) => return Ok(outcome),
+        };
+
+        log::info!("♻️ Restarting {} (attempt {}/{})", target, attempt, restart.policy.max_attempts);
+        let mut command = Command::new(&restart.command[0]);
+        if let Some(profile) = &restart.sandbox {
+            profile.apply(&mut command)?;
+        }
+        let mut child = command
+            .args(&restart.command[1..])
+            .stdin(Stdio::null())
+            .spawn()
+            .map_err(|e| BustcallError::ProcessError(format!("Failed to restart {}: {}", target, e)))?;
+        let pid = child.id();
+
+        let started = Instant::now();
+        while started.elapsed() < RESTART_GRACE {
+            match child.try_wait()? {
+                Some(status) if status.success() => return Ok(RestartOutcome::Started { pid, attempt }),
+                Some(status) => {
+                    return Err(BustcallError::ProcessError(format!(
+                        "Restart command for {} failed with {}",
+                        target, status
+                    )))
+                }
+                None => std::thread::sleep(Duration::from_millis(50)),
+            }
+        }
+
+        // Still running: it is the runtime itself, reap it whenever it exits
+        std::thread::spawn(move || child.wait());
+        Ok(RestartOutcome::Started { pid, attempt })
+    }
+
+    /// Keep at most `capacity` samples per process
+    pub fn with_metrics_capacity(mut self, capacity: usize) -> Self {
+        self.metrics_capacity = capacity.max(1);
+        self
+    }
+
+    /// Re-read the process table
+    pub fn refresh(&self) {
+        self.system.lock().unwrap().refresh_processes();
+    }
+
+    pub fn list_processes(&self, filter: &ProcessFilter) -> Result<Vec<ProcessInfo>> {
+        let processes = self.snapshot()?;
+        let tree = filter.tree_root.map(|root| descendants(&processes, root));
+        let alive: HashSet<u32> = processes.iter().map(|process| process.pid).collect();
+
+        let matched: Vec<ProcessInfo> = processes
+            .into_iter()
+            .filter(|process| tree.as_ref().is_none_or(|tree| tree.contains(&process.pid)))
+            .filter(|process| filter.matches(process))
+            .collect();
+
+        self.record_metrics(&matched, &alive);
+        Ok(matched)
+    }
+
+    /// Samples for `pid` from the last `window`, oldest first
+    pub fn metrics_history(&self, pid: u32, window: Duration) -> Vec<MetricSample> {
+        // A window reaching before the epoch simply means everything
+        let since = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| Utc::now().checked_sub_signed(window));
+
+        self.metrics
+            .lock()
+            .unwrap()
+            .get(&pid)
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| since.is_none_or(|since| sample.timestamp >= since))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Pids with recorded metrics
+    pub fn monitored_pids(&self) -> Vec<u32> {
+        let mut pids: Vec<u32> = self.metrics.lock().unwrap().keys().copied().collect();
+        pids.sort_unstable();
+        pids
+    }
+
+    /// Append a sample per listed process and drop histories of exited ones
+    fn record_metrics(&self, processes: &[ProcessInfo], alive: &HashSet<u32>) {
+        let now = Utc::now();
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics.retain(|pid, _| alive.contains(pid));
+
+        for process in processes {
+            let samples = metrics.entry(process.pid).or_default();
+            if samples.len() >= self.metrics_capacity {
+                samples.pop_front();
+            }
+            samples.push_back(MetricSample {
+                timestamp: now,
+                cpu_usage: process.cpu_usage,
+                memory_usage: process.memory_usage,
+                status: process.status.clone(),
+            });
+        }
+    }
+
+    /// Direct children of `pid`
+    pub fn children_of(&self, pid: u32) -> Result<Vec<ProcessInfo>> {
+        Ok(self
+            .snapshot()?
+            .into_iter()
+            .filter(|process| process.parent_pid == Some(pid))
+            .collect())
+    }
+
+    /// Parent, grandparent, ... of `pid`, nearest first
+    pub fn ancestors_of(&self, pid: u32) -> Result<Vec<ProcessInfo>> {
+        let by_pid: HashMap<u32, ProcessInfo> = self
+            .snapshot()?
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect();
+
+        let mut ancestors = Vec::new();
+        let mut seen = HashSet::from([pid]);
+        let mut current = by_pid.get(&pid).and_then(|process| process.parent_pid);
+
+        while let Some(parent) = current {
+            // Guard against pid reuse producing a cycle
+            if !seen.insert(parent) {
+                break;
+            }
+            let Some(process) = by_pid.get(&parent) else {
+                break;
+            };
+            current = process.parent_pid;
+            ancestors.push(process.clone());
+        }
+
+        Ok(ancestors)
+    }
+
+    /// Members of process group `pgid`
+    pub fn process_group(&self, pgid: u32) -> Result<Vec<ProcessInfo>> {
+        Ok(self
+            .snapshot()?
+            .into_iter()
+            .filter(|process| process.pgid == Some(pgid))
+            .collect())
+    }
+
+    /// List matching processes along with their open files, listening
+    /// sockets and environment fingerprint. Interpreter versions are not
+    /// probed here since that means running each executable.
+    pub fn inspect(&self, filter: &ProcessFilter) -> Result<Vec<ProcessInfo>> {
+        let config = FingerprintConfig {
+            probe_version: false,
+            ..FingerprintConfig::default()
+        };
+
+        let mut processes = self.list_processes(filter)?;
+        for process in &mut processes {
+            process.open_files = open_files(process.pid);
+            process.fingerprint = self.fingerprint(process.pid, &config)?;
+        }
+        Ok(processes)
+    }
+
+    /// Fingerprint the environment `pid` runs in; `None` if it has exited
+    pub fn fingerprint(&self, pid: u32, config: &FingerprintConfig) -> Result<Option<EnvFingerprint>> {
+        let system = self
+            .system
+            .lock()
+            .map_err(|_| BustcallError::ProcessError("Process table lock poisoned".to_string()))?;
+        let Some(process) = system.process(sysinfo::Pid::from_u32(pid)) else {
+            return Ok(None);
+        };
+
+        let fingerprint = self
+            .fingerprinter
+            .lock()
+            .unwrap()
+            .fingerprint(process.exe(), process.environ(), config);
+        Ok(Some(fingerprint))
+    }
+
+    /// Every process matching `lookup`, oldest first
+    pub fn find_runtime(&self, lookup: &RuntimeLookup) -> Result<Vec<ProcessInfo>> {
+        let holders = match &lookup.socket {
+            Some(socket) => Some(socket_holders(socket).ok_or_else(|| {
+                BustcallError::ProcessError(format!("Cannot detect runtimes by {} without /proc", socket))
+            })?),
+            None => None,
+        };
+        let processes = self.snapshot()?;
+        let own_tree = if lookup.exclude_own_tree {
+            descendants(&processes, std::process::id())
+        } else {
+            HashSet::new()
+        };
+
+        let mut matched: Vec<ProcessInfo> = processes
+            .into_iter()
+            .filter(|process| !own_tree.contains(&process.pid))
+            .filter(|process| holders.as_ref().is_none_or(|holders| holders.contains(&process.pid)))
+            .filter(|process| lookup.matches(process))
+            .collect();
+
+        matched.sort_by_key(|process| (process.start_time, process.pid));
+        Ok(matched)
+    }
+
+    /// Resolve a runtime to a single pid according to `lookup.multiple`
+    pub fn find_runtime_pid(&self, lookup: &RuntimeLookup) -> Result<Option<u32>> {
+        let matched = self.find_runtime(lookup)?;
+
+        match lookup.multiple {
+            MultipleMatches::Oldest => Ok(matched.first().map(|process| process.pid)),
+            MultipleMatches::Newest => Ok(matched.last().map(|process| process.pid)),
+            MultipleMatches::Unique if matched.len() > 1 => Err(BustcallError::ProcessError(format!(
+                "{} processes match '{}': {:?}",
+                matched.len(),
+                lookup.describe(),
+                matched.iter().map(|process| process.pid).collect::<Vec<_>>()
+            ))),
+            MultipleMatches::Unique => Ok(matched.first().map(|process| process.pid)),
+        }
+    }
+
+    /// Pids currently running, without building full `ProcessInfo`s
+    pub fn pids(&self) -> Result<HashSet<u32>> {
+        let mut system = self
+            .system
+            .lock()
+            .map_err(|_| BustcallError::ProcessError("Process table lock poisoned".to_string()))?;
+        system.refresh_processes();
+        Ok(system.processes().keys().map(|pid| pid.as_u32()).collect())
+    }
+
+    /// Start time of every pid currently running, to tell a reused pid from
+    /// the process that held it before
+    pub fn start_times(&self) -> Result<HashMap<u32, u64>> {
+        let mut system = self
+            .system
+            .lock()
+            .map_err(|_| BustcallError::ProcessError("Process table lock poisoned".to_string()))?;
+        system.refresh_processes();
+        Ok(system.processes().iter().map(|(pid, process)| (pid.as_u32(), process.start_time())).collect())
+    }
+
+    /// Refresh and copy the whole process table, ordered by pid
+    fn snapshot(&self) -> Result<Vec<ProcessInfo>> {
+        let mut system = self
+            .system
+            .lock()
+            .map_err(|_| BustcallError::ProcessError("Process table lock poisoned".to_string()))?;
+        system.refresh_processes();
+
+        let mut processes: Vec<ProcessInfo> = system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessInfo {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                status: process.status().to_string(),
+                cpu_usage: process.cpu_usage() as f64,
+                memory_usage: process.memory(),
+                parent_pid: process.parent().map(|parent| parent.as_u32()),
+                session_id: process.session_id().map(|session| session.as_u32()),
+                pgid: process_group_id(pid.as_u32()),
+                container: self.containers.resolve(pid.as_u32()),
+                cmdline: process.cmd().to_vec(),
+                start_time: process.start_time(),
+                open_files: None,
+                fingerprint: None,
+            })
+            .collect();
+
+        processes.sort_by_key(|process| process.pid);
+        Ok(processes)
+    }
+}
+
+/// `root` and every process below it
+fn descendants(processes: &[ProcessInfo], root: u32) -> HashSet<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for process in processes {
+        if let Some(parent) = process.parent_pid {
+            children.entry(parent).or_default().push(process.pid);
+        }
+    }
+
+    let mut tree = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if tree.insert(pid) {
+            stack.extend(children.get(&pid).into_iter().flatten().copied());
+        }
+    }
+    tree
+}
+
+/// Field 5 of `/proc/<pid>/stat`, read after the parenthesised command name
+/// since that may itself contain spaces
+#[cfg(target_os = "linux")]
+fn process_group_id(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let after_name = &stat[stat.rfind(')')? + 1..];
+    after_name.split_whitespace().nth(2)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_group_id(_pid: u32) -> Option<u32> {
+    None
+}
+
+/// Number of open file descriptors; `None` where /proc is unavailable or
+/// the process belongs to another user
+pub fn open_fd_count(pid: u32) -> Option<u64> {
+    count_entries(&format!("/proc/{}/fd", pid))
+}
+
+/// Number of threads, from the process's task directory
+pub fn thread_count(pid: u32) -> Option<u64> {
+    count_entries(&format!("/proc/{}/task", pid))
+}
+
+fn count_entries(dir: &str) -> Option<u64> {
+    std::fs::read_dir(dir).ok().map(|entries| entries.count() as u64)
+}
+
+impl Default for ProcessManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_current_process() {
+        let manager = ProcessManager::new();
+        let own_pid = std::process::id();
+
+        let processes = manager.list_processes(&ProcessFilter::all().pid(own_pid)).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert!(processes[0].memory_usage > 0);
+    }
+
+    #[test]
+    fn test_filter_criteria_combine() {
+        let process = ProcessInfo {
+            pid: 42,
+            name: "node-server".to_string(),
+            status: "Run".to_string(),
+            cpu_usage: 12.5,
+            memory_usage: 64 * 1024 * 1024,
+            parent_pid: Some(1),
+            session_id: None,
+            pgid: None,
+            container: None,
+            cmdline: vec!["node".to_string(), "server.js".to_string()],
+            start_time: 0,
+            open_files: None,
+            fingerprint: None,
+        };
+
+        let filter = ProcessFilter::all().regex("^node").unwrap().min_cpu(10.0);
+        assert!(filter.matches(&process));
+        assert!(!filter.clone().min_memory(128 * 1024 * 1024).matches(&process));
+        assert!(!filter.clone().container("api").matches(&process));
+        assert!(!filter.pid(7).matches(&process));
+
+        assert!(!RuntimeLookup::new("node").matches(&process));
+        assert!(RuntimeLookup::new("node").mode(MatchMode::Cmdline).matches(&process));
+    }
+
+    #[test]
+    fn test_executable_stem() {
+        assert_eq!(executable_stem("node.exe"), "node");
+        assert_eq!(executable_stem("Python.EXE"), "Python");
+        assert_eq!(executable_stem("node"), "node");
+        assert_eq!(executable_stem(".exe"), "");
+    }
+
+    #[test]
+    fn test_runtime_lookup_excludes_own_tree() {
+        let manager = ProcessManager::new();
+        let own = manager
+            .list_processes(&ProcessFilter::all().pid(std::process::id()))
+            .unwrap()
+            .remove(0);
+
+        let lookup = RuntimeLookup::new(&own.name).multiple(MultipleMatches::Newest);
+        let found = manager.find_runtime(&lookup).unwrap();
+        assert!(found.iter().all(|p| p.pid != own.pid));
+
+        let found = manager.find_runtime(&lookup.include_own_tree()).unwrap();
+        assert!(found.iter().any(|p| p.pid == own.pid));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_runtime_lookup_by_socket() {
+        let manager = ProcessManager::new();
+        let own = manager
+            .list_processes(&ProcessFilter::all().pid(std::process::id()))
+            .unwrap()
+            .remove(0);
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = SocketDetect::Port(listener.local_addr().unwrap().port());
+
+        let found = manager
+            .find_runtime(&RuntimeLookup::by_socket(port.clone()).include_own_tree())
+            .unwrap();
+        assert_eq!(found.iter().map(|p| p.pid).collect::<Vec<_>>(), [own.pid]);
+
+        // Name and socket must both match
+        let lookup = RuntimeLookup::new("no-such-runtime").socket(port).include_own_tree();
+        assert!(manager.find_runtime(&lookup).unwrap().is_empty());
+        assert_eq!(lookup.describe(), format!("no-such-runtime on port {}", listener.local_addr().unwrap().port()));
+    }
+
+    #[test]
+    fn test_current_process_is_in_its_parents_tree() {
+        let manager = ProcessManager::new();
+        let own_pid = std::process::id();
+
+        let ancestors = manager.ancestors_of(own_pid).unwrap();
+        let parent = ancestors.first().expect("test runner has a parent").pid;
+        assert!(manager.children_of(parent).unwrap().iter().any(|p| p.pid == own_pid));
+
+        let tree = manager.list_processes(&ProcessFilter::all().tree_root(parent)).unwrap();
+        assert!(tree.iter().any(|p| p.pid == own_pid));
+    }
+
+    #[test]
+    fn test_restart_backoff_and_attempt_limit() {
+        let policy = RestartPolicy {
+            max_attempts: 2,
+            initial_backoff_seconds: 10,
+            max_backoff_seconds: 60,
+            reset_after_seconds: 300,
+        };
+        let mut state = RestartState::default();
+        let start = Instant::now();
+
+        assert_eq!(state.begin(&policy, start), Ok(1));
+        assert_eq!(
+            state.begin(&policy, start + Duration::from_secs(4)),
+            Err(RestartOutcome::BackingOff { retry_in: Duration::from_secs(6) })
+        );
+        assert_eq!(state.begin(&policy, start + Duration::from_secs(10)), Ok(2));
+        assert_eq!(
+            state.begin(&policy, start + Duration::from_secs(100)),
+            Err(RestartOutcome::GaveUp { attempts: 2 })
+        );
+        assert_eq!(state.begin(&policy, start + Duration::from_secs(400)), Ok(1));
+    }
+
+    #[test]
+    fn test_metrics_history_is_bounded() {
+        let manager = ProcessManager::new().with_metrics_capacity(2);
+        let filter = ProcessFilter::all().pid(std::process::id());
+        for _ in 0..3 {
+            manager.list_processes(&filter).unwrap();
+        }
+
+        let history = manager.metrics_history(std::process::id(), Duration::from_secs(60));
+        assert_eq!(history.len(), 2);
+        assert_eq!(manager.monitored_pids(), vec![std::process::id()]);
     }
 }
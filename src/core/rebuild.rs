@@ -0,0 +1,303 @@
+//! Runs the rebuilds that busts queue
+//!
+//! A bust queues its target on the cache's rebuild heap. `RebuildExecutor`
+//! drains the heap highest priority first and runs each target's
+//! `rebuild_command`, at most `concurrency` at a time and never two for the
+//! same target. Durations feed back into the target's build cost and every
+//! outcome is reported as a notification.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
+use crate::core::sandbox::SandboxProfile;
+use crate::dimensional_cache::{BackgroundTask, DimensionalCacheManager, PriorityEntry};
+
+/// How often the background executor looks for queued and finished rebuilds
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Lines of output kept from a failed rebuild
+const OUTPUT_TAIL_LINES: usize = 10;
+
+/// `[target.<name>] rebuild_command`, run with `sh -c`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebuildCommand {
+    pub command: String,
+    /// Working directory, the daemon's when unset
+    pub cwd: Option<PathBuf>,
+    /// The target's `sandbox`, applied to the shell
+    pub sandbox: Option<SandboxProfile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RebuildOutcome {
+    pub target: String,
+    pub success: bool,
+    pub duration: Duration,
+    /// Tail of stderr and stdout of a failed rebuild
+    pub output: String,
+}
+
+#[derive(Debug)]
+pub struct RebuildExecutor {
+    commands: HashMap<String, RebuildCommand>,
+    concurrency: usize,
+    notifications: Option<Arc<NotificationManager>>,
+}
+
+impl RebuildExecutor {
+    /// `commands` by target; queued targets without one are dropped
+    pub fn new(commands: HashMap<String, RebuildCommand>, concurrency: usize) -> Self {
+        Self {
+            commands,
+            concurrency: concurrency.max(1),
+            notifications: None,
+        }
+    }
+
+    /// Report each finished rebuild through `notifications`
+    pub fn with_notifications(mut self, notifications: Arc<NotificationManager>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Drain the queue in the background until stopped
+    pub fn spawn(self, cache: Arc<DimensionalCacheManager>) -> BackgroundTask {
+        let mut runner = Runner::new(Arc::new(self));
+        BackgroundTask::every(POLL_INTERVAL, move || {
+            runner.collect(&cache, false);
+            runner.start(&cache);
+        })
+    }
+
+    /// Run everything queued now and wait for it, including rebuilds queued
+    /// again meanwhile
+    pub fn run_pending(self, cache: &DimensionalCacheManager) -> Vec<RebuildOutcome> {
+        let mut runner = Runner::new(Arc::new(self));
+        let mut outcomes = Vec::new();
+        loop {
+            runner.start(cache);
+            if runner.running.is_empty() {
+                return outcomes;
+            }
+            outcomes.extend(runner.collect(cache, true));
+        }
+    }
+
+    /// Rebuild `target` right away, bypassing the queue; `None` if it has
+    /// no `rebuild_command`
+    pub fn rebuild_now(&self, target: &str, cache: &DimensionalCacheManager) -> Option<RebuildOutcome> {
+        let command = self.commands.get(target)?;
+        let outcome = self.run(target, command);
+        self.finish(cache, &outcome);
+        Some(outcome)
+    }
+
+    fn run(&self, target: &str, command: &RebuildCommand) -> RebuildOutcome {
+        log::info!("🔨 Rebuilding {}: {}", target, command.command);
+        let started = Instant::now();
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(&command.command);
+        if let Some(cwd) = &command.cwd {
+            process.current_dir(cwd);
+        }
+        let output = match &command.sandbox {
+            Some(profile) => profile.apply(&mut process).map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+        .and_then(|_| process.output().map_err(|e| e.to_string()));
+        let (success, output) = match output {
+            Ok(output) if output.status.success() => (true, String::new()),
+            Ok(output) => {
+                let text = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stderr),
+                    String::from_utf8_lossy(&output.stdout)
+                );
+                let lines: Vec<&str> = text.lines().collect();
+                let tail = lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..].join("\n");
+                (false, format!("{}\n{}", output.status, tail).trim().to_string())
+            }
+            Err(e) => (false, e),
+        };
+        RebuildOutcome {
+            target: target.to_string(),
+            success,
+            duration: started.elapsed(),
+            output,
+        }
+    }
+
+    fn finish(&self, cache: &DimensionalCacheManager, outcome: &RebuildOutcome) {
+        cache.complete_rebuild(&outcome.target, outcome.duration, outcome.success);
+        let seconds = outcome.duration.as_secs_f64();
+
+        let notification = if outcome.success {
+            log::info!("✅ Rebuilt {} in {:.1}s", outcome.target, seconds);
+            Notification::new(NotificationLevel::Info, &format!("Rebuilt {} in {:.1}s", outcome.target, seconds))
+        } else {
+            log::warn!("❌ Rebuild of {} failed after {:.1}s: {}", outcome.target, seconds, outcome.output);
+            Notification::new(
+                NotificationLevel::Error,
+                &format!("Rebuild of {} failed after {:.1}s\n{}", outcome.target, seconds, outcome.output),
+            )
+        };
+        if let Some(notifications) = &self.notifications {
+            let notification = notification
+                .title("Rebuild")
+                .target(&outcome.target)
+                .component("rebuild")
+                .with_metadata("duration_ms", &outcome.duration.as_millis().to_string());
+            if let Err(e) = notifications.send_structured(&notification) {
+                log::warn!("Rebuild notification for {} failed: {}", outcome.target, e);
+            }
+        }
+    }
+}
+
+/// Rebuilds in flight and the ones waiting for their target's to finish
+struct Runner {
+    executor: Arc<RebuildExecutor>,
+    running: HashSet<String>,
+    deferred: Vec<PriorityEntry>,
+    done_tx: mpsc::Sender<RebuildOutcome>,
+    done: mpsc::Receiver<RebuildOutcome>,
+}
+
+impl Runner {
+    fn new(executor: Arc<RebuildExecutor>) -> Self {
+        let (done_tx, done) = mpsc::channel();
+        Self {
+            executor,
+            running: HashSet::new(),
+            deferred: Vec::new(),
+            done_tx,
+            done,
+        }
+    }
+
+    /// Start queued rebuilds up to the concurrency limit
+    fn start(&mut self, cache: &DimensionalCacheManager) {
+        while self.running.len() < self.executor.concurrency {
+            let ready = self.deferred.iter().position(|entry| !self.running.contains(&entry.cache_id));
+            let entry = match ready {
+                Some(index) => self.deferred.remove(index),
+                None => match cache.next_rebuild() {
+                    Some(entry) => entry,
+                    None => return,
+                },
+            };
+            let target = entry.cache_id.clone();
+
+            if self.running.contains(&target) {
+                // Busted again mid-rebuild: run once more afterwards
+                if !self.deferred.iter().any(|deferred| deferred.cache_id == target) {
+                    self.deferred.push(entry);
+                }
+                continue;
+            }
+            let Some(command) = self.executor.commands.get(&target).cloned() else {
+                log::debug!("No rebuild command for {}, dropping queued rebuild", target);
+                continue;
+            };
+
+            self.running.insert(target.clone());
+            let executor = self.executor.clone();
+            let done = self.done_tx.clone();
+            std::thread::spawn(move || {
+                let _ = done.send(executor.run(&target, &command));
+            });
+        }
+    }
+
+    /// Record finished rebuilds, waiting for at least one when `block`
+    fn collect(&mut self, cache: &DimensionalCacheManager, block: bool) -> Vec<RebuildOutcome> {
+        let mut outcomes = Vec::new();
+        if block && !self.running.is_empty() {
+            if let Ok(outcome) = self.done.recv() {
+                outcomes.push(outcome);
+            }
+        }
+        outcomes.extend(self.done.try_iter());
+        for outcome in &outcomes {
+            self.running.remove(&outcome.target);
+            self.executor.finish(cache, outcome);
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensional_cache::{CacheBustSeverity, CacheState, ModelBinding};
+
+    fn command(command: &str) -> RebuildCommand {
+        RebuildCommand {
+            command: command.to_string(),
+            cwd: None,
+            sandbox: None,
+        }
+    }
+
+    #[test]
+    fn test_runs_queued_rebuilds_by_priority() {
+        let cache = DimensionalCacheManager::new().unwrap();
+        for target in ["web", "api", "docs"] {
+            cache
+                .bind_model(
+                    target,
+                    ModelBinding {
+                        runtime: "test".to_string(),
+                        pid: None,
+                        pid_start_time: None,
+                        path: String::new(),
+                        last_modified: 0,
+                        cache_dependencies: Vec::new(),
+                        compiler_caches: Vec::new(),
+                        hooks: Default::default(),
+                    },
+                )
+                .unwrap();
+        }
+        cache.bust_cache("web", CacheBustSeverity::Low).unwrap();
+        cache.bust_cache("web", CacheBustSeverity::Medium).unwrap();
+        cache.bust_cache("api", CacheBustSeverity::Critical).unwrap();
+        cache.bust_cache("docs", CacheBustSeverity::High).unwrap();
+        assert_eq!(cache.pending_rebuilds(), 4);
+
+        let executor = RebuildExecutor::new(
+            HashMap::from([("web".to_string(), command("true")), ("api".to_string(), command("echo broken >&2; exit 3"))]),
+            1,
+        );
+        let outcomes = executor.run_pending(&cache);
+
+        // One at a time, most urgent first; docs has no command
+        let order: Vec<&str> = outcomes.iter().map(|outcome| outcome.target.as_str()).collect();
+        assert_eq!(order, vec!["api", "web"]);
+        assert!(!outcomes[0].success);
+        assert!(outcomes[0].output.contains("broken"), "{}", outcomes[0].output);
+        assert!(outcomes[1].success);
+        assert_eq!(cache.pending_rebuilds(), 0);
+
+        assert_eq!(cache.cache_state("web"), Some(CacheState::Cold));
+        assert_eq!(cache.cache_state("api"), Some(CacheState::Stale));
+        // Quick rebuilds pull the default cost of 0.5 down
+        assert!(cache.limits().weights["web"].build_cost < 0.5);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rebuilds_run_in_the_targets_sandbox() {
+        let cache = DimensionalCacheManager::new().unwrap();
+        let sandbox = SandboxProfile { env_allowlist: Some(vec!["PATH".to_string()]), ..SandboxProfile::default() };
+        let confined = RebuildCommand { sandbox: Some(sandbox), ..command("test -z \"$HOME\" && test -n \"$PATH\"") };
+        let executor = RebuildExecutor::new(HashMap::from([("web".to_string(), confined)]), 1);
+
+        let outcome = executor.rebuild_now("web", &cache).unwrap();
+        assert!(outcome.success, "{}", outcome.output);
+    }
+}
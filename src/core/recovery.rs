@@ -0,0 +1,525 @@
+//! Actions the self-healing architecture recovers a component with
+//!
+//! ```toml
+//! [recovery]
+//! default = ["cache_refresh"]
+//! health_interval_seconds = 30
+//! breaker_failures = 5
+//! breaker_window_seconds = 600
+//!
+//! [recovery.chains]
+//! api = ["cache_refresh", "restart"]
+//! web = ["rebuild", "purge-cdn"]
+//!
+//! [recovery.actions.purge-cdn]
+//! command = "./scripts/purge-cdn.sh"
+//! check = "curl -fs https://example.com/health"
+//! timeout_seconds = 120
+//! ```
+//!
+//! A failed component, usually a target, is recovered by running its chain
+//! in order until an action succeeds and the health check after it passes.
+//! Components without a chain use `default`, or with that empty the
+//! strategies [`crate::self_healing`] picks by severity, which run the same
+//! actions by name.
+//!
+//! Built in are `cache_refresh`, busting the target; `rebuild`, running its
+//! `rebuild_command`, healthy once the target's cache is no longer stale;
+//! `restart`, running its `restart_command` under its restart policy,
+//! healthy while the process it started runs; and `isolate`, which leaves
+//! the component to manual intervention from then on.
+//! `[recovery.actions.<name>]` adds actions running `command` with
+//! `sh -c`, with `BUSTCALL_COMPONENT`, `BUSTCALL_SEVERITY` and
+//! `BUSTCALL_MESSAGE` set; `check`, if set, must exit zero for the
+//! component to count as healthy. Code can register any other
+//! [`RecoveryAction`] with [`RecoveryActions::register`].
+//!
+//! A component whose recovery fails `breaker_failures` times within
+//! `breaker_window_seconds` has its circuit opened: it is escalated as
+//! Critical and left to manual intervention until `bustcall heal reset`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::bust_hook::wait_with_timeout;
+use crate::core::process::{ProcessFilter, ProcessManager, RestartOutcome};
+use crate::core::rebuild::RebuildExecutor;
+use crate::dimensional_cache::{BustReason, CacheBustSeverity, CacheState, DimensionalCacheManager};
+use crate::utils::error::{BustCallError, BustcallError, Result, SeverityLevel};
+
+pub const DEFAULT_ACTION_TIMEOUT_SECONDS: u64 = 300;
+
+/// Names of the actions [`RecoveryActions::builtin`] registers
+pub const BUILTIN_ACTIONS: [&str; 4] = ["cache_refresh", "rebuild", "restart", "isolate"];
+
+pub type RecoveryFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// One way of bringing a failed component back
+pub trait RecoveryAction: Send + Sync {
+    /// Name chains refer to it by
+    fn name(&self) -> &str;
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>>;
+
+    /// Whether `component` is healthy again after `execute` succeeded; by
+    /// default its success is taken as enough
+    fn health_check<'a>(&'a self, _component: &'a str) -> RecoveryFuture<'a, bool> {
+        Box::pin(async { true })
+    }
+}
+
+/// `[recovery]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecoveryConfig {
+    /// Chain of components without one of their own
+    pub default: Vec<String>,
+    /// Actions tried in order, by component
+    pub chains: HashMap<String, Vec<String>>,
+    /// Command actions, by name
+    pub actions: HashMap<String, CommandActionConfig>,
+    /// Seconds between health checks of each enabled target, 0 for none;
+    /// see [`crate::core::health`]
+    pub health_interval_seconds: u64,
+    /// Health score, 0-10, below which a target counts as unhealthy
+    pub health_threshold: u8,
+    /// Unhealthy checks in a row before the target is recovered
+    pub unhealthy_checks: u8,
+    /// Failed recoveries of a component within `breaker_window_seconds`
+    /// that open its circuit, stopping automated recovery until
+    /// `bustcall heal reset`; 0 never opens it
+    pub breaker_failures: u32,
+    pub breaker_window_seconds: u64,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            default: Vec::new(),
+            chains: HashMap::new(),
+            actions: HashMap::new(),
+            health_interval_seconds: 30,
+            health_threshold: 6,
+            unhealthy_checks: 3,
+            breaker_failures: 5,
+            breaker_window_seconds: 600,
+        }
+    }
+}
+
+impl RecoveryConfig {
+    /// Actions recovering `component`, `None` to leave it to the
+    /// strategies picked by severity
+    pub fn chain_for(&self, component: &str) -> Option<&[String]> {
+        match self.chains.get(component) {
+            Some(chain) => Some(chain),
+            None => (!self.default.is_empty()).then_some(self.default.as_slice()),
+        }
+    }
+}
+
+/// `[recovery.actions.<name>]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandActionConfig {
+    pub command: String,
+    /// Exits zero once the component is healthy; unset trusts `command`
+    #[serde(default)]
+    pub check: Option<String>,
+    /// Working directory, the daemon's when unset
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    /// Seconds `command` and `check` may each run,
+    /// `DEFAULT_ACTION_TIMEOUT_SECONDS` if unset
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Recovery actions by name
+#[derive(Default)]
+pub struct RecoveryActions {
+    actions: RwLock<HashMap<String, Arc<dyn RecoveryAction>>>,
+    isolated: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl std::fmt::Debug for RecoveryActions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<String> = self.actions.read().unwrap().keys().cloned().collect();
+        names.sort();
+        f.debug_struct("RecoveryActions").field("actions", &names).finish()
+    }
+}
+
+impl RecoveryActions {
+    /// Only `isolate`, which needs nothing to act on
+    pub fn new() -> Self {
+        let actions = Self::default();
+        actions.register(Arc::new(Isolate { isolated: actions.isolated.clone() }));
+        actions
+    }
+
+    /// Every built-in action, acting on `cache`, the restart commands of
+    /// `processes` and the rebuild commands of `rebuilds`
+    pub fn builtin(cache: Arc<DimensionalCacheManager>, processes: Arc<ProcessManager>, rebuilds: Arc<RebuildExecutor>) -> Self {
+        let actions = Self::new();
+        actions.register(Arc::new(CacheRefresh::new(cache.clone())));
+        actions.register(Arc::new(Rebuild { executor: rebuilds, cache }));
+        actions.register(Arc::new(ProcessRestart::new(processes)));
+        actions
+    }
+
+    /// Also register the command actions of `config`
+    pub fn with_config(self, config: &RecoveryConfig) -> Self {
+        for (name, action) in &config.actions {
+            self.register(Arc::new(CommandAction::new(name, action.clone())));
+        }
+        self
+    }
+
+    /// Add an action, replacing one of the same name
+    pub fn register(&self, action: Arc<dyn RecoveryAction>) {
+        log::debug!("🩹 Recovery action registered: {}", action.name());
+        self.actions.write().unwrap().insert(action.name().to_string(), action);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn RecoveryAction>> {
+        self.actions.read().unwrap().get(name).cloned()
+    }
+
+    /// Whether `isolate` took `component` out of automatic recovery
+    pub fn is_isolated(&self, component: &str) -> bool {
+        self.isolated.lock().unwrap_or_else(PoisonError::into_inner).contains(component)
+    }
+}
+
+/// Run `work` off the async runtime, as busts, rebuilds and restarts block
+async fn blocking<T: Send + 'static>(name: &str, work: impl FnOnce() -> T + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| BustcallError::DaemonError(format!("Recovery action {} failed: {}", name, e)))
+}
+
+/// `cache_refresh`: bust the component's cache
+pub struct CacheRefresh {
+    cache: Arc<DimensionalCacheManager>,
+    severity: CacheBustSeverity,
+}
+
+impl CacheRefresh {
+    /// Busting Medium
+    pub fn new(cache: Arc<DimensionalCacheManager>) -> Self {
+        Self { cache, severity: CacheBustSeverity::Medium }
+    }
+
+    pub fn with_severity(mut self, severity: CacheBustSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl RecoveryAction for CacheRefresh {
+    fn name(&self) -> &str {
+        "cache_refresh"
+    }
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+        let (cache, severity, component) = (self.cache.clone(), self.severity.clone(), error.component.clone());
+        let reason = BustReason::Other { detail: format!("recovery from {}", error.message) };
+        Box::pin(async move {
+            blocking(self.name(), move || cache.bust_cache_with_reason(&component, severity, reason))
+                .await?
+                .map_err(|e| BustcallError::DaemonError(format!("Cache refresh of {} failed: {}", error.component, e)))
+        })
+    }
+}
+
+/// `rebuild`: run the component's `rebuild_command`
+pub struct Rebuild {
+    executor: Arc<RebuildExecutor>,
+    cache: Arc<DimensionalCacheManager>,
+}
+
+impl RecoveryAction for Rebuild {
+    fn name(&self) -> &str {
+        "rebuild"
+    }
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+        let (executor, cache, component) = (self.executor.clone(), self.cache.clone(), error.component.clone());
+        Box::pin(async move {
+            match blocking(self.name(), move || executor.rebuild_now(&component, &cache)).await? {
+                Some(outcome) if outcome.success => Ok(()),
+                Some(outcome) => Err(BustcallError::ProcessError(format!(
+                    "Rebuild of {} failed after {:.1}s: {}",
+                    error.component,
+                    outcome.duration.as_secs_f64(),
+                    outcome.output
+                ))),
+                None => Err(BustcallError::DaemonError(format!("No rebuild_command configured for {}", error.component))),
+            }
+        })
+    }
+
+    /// Rebuilt once its cache is no longer stale; busted again meanwhile,
+    /// it still is
+    fn health_check<'a>(&'a self, component: &'a str) -> RecoveryFuture<'a, bool> {
+        Box::pin(async move { self.cache.cache_state(component) != Some(CacheState::Stale) })
+    }
+}
+
+/// `restart`: run the component's `restart_command`, healthy once the
+/// process it started is still running
+pub struct ProcessRestart {
+    processes: Arc<ProcessManager>,
+    /// Pid last started, by component
+    started: Mutex<HashMap<String, u32>>,
+}
+
+impl ProcessRestart {
+    pub fn new(processes: Arc<ProcessManager>) -> Self {
+        Self { processes, started: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl RecoveryAction for ProcessRestart {
+    fn name(&self) -> &str {
+        "restart"
+    }
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+        let (processes, component) = (self.processes.clone(), error.component.clone());
+        Box::pin(async move {
+            match blocking(self.name(), move || processes.restart(&component)).await?? {
+                RestartOutcome::Started { pid, .. } => {
+                    self.started.lock().unwrap_or_else(PoisonError::into_inner).insert(error.component.clone(), pid);
+                    Ok(())
+                }
+                // Scored Warning: retried by the next recovery, not escalated
+                RestartOutcome::BackingOff { retry_in } => Err(BustCallError::new(
+                    &error.component,
+                    &format!("restart backing off for {}s", retry_in.as_secs()),
+                    SeverityLevel::Warning as u8,
+                )
+                .with_recovery_action(self.name())
+                .into()),
+                RestartOutcome::GaveUp { attempts } => Err(BustCallError::new(
+                    &error.component,
+                    &format!("gave up restarting after {} attempts", attempts),
+                    SeverityLevel::Critical as u8,
+                )
+                .into()),
+            }
+        })
+    }
+
+    fn health_check<'a>(&'a self, component: &'a str) -> RecoveryFuture<'a, bool> {
+        let pid = self.started.lock().unwrap_or_else(PoisonError::into_inner).get(component).copied();
+        Box::pin(async move {
+            let Some(pid) = pid else {
+                return false;
+            };
+            self.processes
+                .list_processes(&ProcessFilter::all().pid(pid))
+                .is_ok_and(|found| !found.is_empty())
+        })
+    }
+}
+
+/// `isolate`: take the component out of automatic recovery
+pub struct Isolate {
+    isolated: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl RecoveryAction for Isolate {
+    fn name(&self) -> &str {
+        "isolate"
+    }
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+        Box::pin(async move {
+            log::warn!("🚧 {} isolated, its recovery is left to manual intervention", error.component);
+            self.isolated.lock().unwrap_or_else(PoisonError::into_inner).insert(error.component.clone());
+            Ok(())
+        })
+    }
+}
+
+/// `[recovery.actions.<name>]`: run a command
+pub struct CommandAction {
+    name: String,
+    config: CommandActionConfig,
+}
+
+impl CommandAction {
+    pub fn new(name: &str, config: CommandActionConfig) -> Self {
+        Self { name: name.to_string(), config }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_seconds.unwrap_or(DEFAULT_ACTION_TIMEOUT_SECONDS))
+    }
+
+    /// Run `command` for `component`, giving whether it exited zero and the
+    /// tail of its output
+    async fn run(&self, command: &str, component: &str, error: Option<&BustCallError>) -> Result<(bool, String)> {
+        let child = {
+            let mut process = Command::new("sh");
+            process
+                .arg("-c")
+                .arg(command)
+                .env("BUSTCALL_COMPONENT", component)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            if let Some(error) = error {
+                process.env("BUSTCALL_SEVERITY", error.severity.as_str()).env("BUSTCALL_MESSAGE", &error.message);
+            }
+            if let Some(cwd) = &self.config.cwd {
+                process.current_dir(cwd);
+            }
+            #[cfg(unix)]
+            std::os::unix::process::CommandExt::process_group(&mut process, 0);
+            process.spawn()?
+        };
+        let timeout = self.timeout();
+        let (success, timed_out, output) = blocking(&self.name, move || wait_with_timeout(child, timeout)).await?;
+        if timed_out {
+            return Err(BustcallError::DaemonError(format!(
+                "{} killed after {}s",
+                command,
+                timeout.as_secs()
+            )));
+        }
+        Ok((success, output))
+    }
+}
+
+impl RecoveryAction for CommandAction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+        Box::pin(async move {
+            log::info!("🩹 Running recovery action {} for {}: {}", self.name, error.component, self.config.command);
+            match self.run(&self.config.command, &error.component, Some(error)).await? {
+                (true, _) => Ok(()),
+                (false, output) => Err(BustcallError::DaemonError(format!("{} failed: {}", self.config.command, output))),
+            }
+        })
+    }
+
+    fn health_check<'a>(&'a self, component: &'a str) -> RecoveryFuture<'a, bool> {
+        Box::pin(async move {
+            let Some(check) = &self.config.check else {
+                return true;
+            };
+            match self.run(check, component, None).await {
+                Ok((healthy, _)) => healthy,
+                Err(e) => {
+                    log::warn!("Health check of {} after {} failed: {}", component, self.name, e);
+                    false
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rebuild::RebuildCommand;
+    use crate::dimensional_cache::ModelBinding;
+
+    #[tokio::test]
+    async fn test_rebuild_reruns_the_rebuild_command() {
+        let cache = Arc::new(DimensionalCacheManager::new().unwrap());
+        for target in ["web", "api"] {
+            cache
+                .bind_model(
+                    target,
+                    ModelBinding {
+                        runtime: "test".to_string(),
+                        pid: None,
+                        pid_start_time: None,
+                        path: String::new(),
+                        last_modified: 0,
+                        cache_dependencies: Vec::new(),
+                        compiler_caches: Vec::new(),
+                        hooks: Default::default(),
+                    },
+                )
+                .unwrap();
+            cache.bust_cache(target, CacheBustSeverity::High).unwrap();
+        }
+        let command = |command: &str| RebuildCommand { command: command.to_string(), cwd: None, sandbox: None };
+        let rebuilds = RebuildExecutor::new(
+            HashMap::from([("web".to_string(), command("true")), ("api".to_string(), command("echo broken >&2; exit 3"))]),
+            1,
+        );
+        let actions = RecoveryActions::builtin(cache.clone(), Arc::new(ProcessManager::new()), Arc::new(rebuilds));
+        let rebuild = actions.get("rebuild").unwrap();
+
+        assert!(!rebuild.health_check("web").await);
+        rebuild.execute(&BustCallError::new("web", "cache bust failed", 6)).await.unwrap();
+        assert!(rebuild.health_check("web").await);
+
+        let failure = rebuild.execute(&BustCallError::new("api", "cache bust failed", 6)).await.unwrap_err();
+        assert!(failure.to_string().contains("broken"), "{}", failure);
+        assert!(!rebuild.health_check("api").await);
+        assert!(rebuild.execute(&BustCallError::new("docs", "cache bust failed", 6)).await.is_err());
+
+        // Without a restart_command there is nothing to restart
+        let restart = actions.get("restart").unwrap();
+        assert!(restart.execute(&BustCallError::new("web", "watcher panicked", 12)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_command_actions_run_and_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: RecoveryConfig = toml::from_str(
+            r#"
+            default = ["isolate"]
+
+            [chains]
+            api = ["purge", "restart"]
+
+            [actions.purge]
+            command = "echo \"$BUSTCALL_COMPONENT $BUSTCALL_SEVERITY\" > purged"
+            check = "test -f purged"
+
+            [actions.broken]
+            command = "echo nope >&2; exit 3"
+            "#,
+        )
+        .unwrap();
+        let mut config = config;
+        for action in config.actions.values_mut() {
+            action.cwd = Some(dir.path().to_path_buf());
+        }
+        assert_eq!(config.chain_for("api").unwrap(), ["purge", "restart"]);
+        assert_eq!(config.chain_for("web").unwrap(), ["isolate"]);
+
+        let actions = RecoveryActions::new().with_config(&config);
+        let error = BustCallError::new("api", "cache bust failed", 6);
+        let purge = actions.get("purge").unwrap();
+        assert!(!purge.health_check("api").await);
+        purge.execute(&error).await.unwrap();
+        assert_eq!(std::fs::read_to_string(dir.path().join("purged")).unwrap(), "api danger\n");
+        assert!(purge.health_check("api").await);
+
+        let failure = actions.get("broken").unwrap().execute(&error).await.unwrap_err();
+        assert!(failure.to_string().contains("nope"));
+
+        assert!(actions.get("restart").is_none());
+        assert!(!actions.is_isolated("api"));
+        actions.get("isolate").unwrap().execute(&error).await.unwrap();
+        assert!(actions.is_isolated("api"));
+    }
+}
@@ -0,0 +1,526 @@
+//! Config files fetched from a central location
+//!
+//! `--config` may name a remote config instead of a local file:
+//!
+//! - `https://configs.internal/bustcall/team-a.toml` (needs the
+//!   `remote-config` feature)
+//! - `s3://bucket/bustcall/team-a.toml`, fetched with the `aws` CLI and its
+//!   usual credentials
+//! - `git::https://git.internal/platform/configs.git//bustcall/team-a.toml?ref=main`
+//!
+//! The last good copy is cached with its ETag (the commit for git), so a
+//! refresh only downloads what changed and a runner keeps working while the
+//! source is unreachable. With an allowed signers file, a fetched config is
+//! only used if `<url>.sig` is an SSH signature over it
+//! (`ssh-keygen -Y sign -n bustcall-config`) by one of the signers.
+//!
+//! Relative target paths and `${workspace_root}` of a remote config are
+//! taken from the working directory, not the cache, and remote configs
+//! cannot `include` other files.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use sha2::{Digest, Sha256};
+use toml::Table;
+
+use crate::core::config::{BustcallConfig, ConfigError, ConfigFormat};
+use crate::core::config_validation::{sorted, validate_str, ValidationReport};
+
+/// Namespace remote config signatures are made for
+pub const SIGNATURE_NAMESPACE: &str = "bustcall-config";
+
+pub const DEFAULT_REFRESH_SECONDS: u64 = 300;
+
+/// Whether `spec` names a remote config rather than a local path
+pub fn is_remote(spec: &str) -> bool {
+    ["http://", "https://", "s3://", "git::"]
+        .iter()
+        .any(|scheme| spec.starts_with(scheme))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RemoteSource {
+    Http(String),
+    S3 {
+        bucket: String,
+        key: String,
+    },
+    Git {
+        repo: String,
+        path: String,
+        reference: Option<String>,
+    },
+}
+
+enum Fetched {
+    NotModified,
+    Changed {
+        content: Vec<u8>,
+        signature: Option<Vec<u8>>,
+        etag: Option<String>,
+    },
+}
+
+impl RemoteSource {
+    fn parse(url: &str) -> Result<Self, ConfigError> {
+        let invalid = |reason: &str| ConfigError::Remote(format!("Invalid remote config {}: {}", url, reason));
+
+        if let Some(location) = url.strip_prefix("s3://") {
+            let (bucket, key) = location.split_once('/').ok_or_else(|| invalid("expected s3://bucket/key"))?;
+            if bucket.is_empty() || key.is_empty() {
+                return Err(invalid("expected s3://bucket/key"));
+            }
+            return Ok(RemoteSource::S3 {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            });
+        }
+
+        if let Some(location) = url.strip_prefix("git::") {
+            let (location, reference) = match location.split_once("?ref=") {
+                Some((location, reference)) => (location, Some(reference.to_string())),
+                None => (location, None),
+            };
+            // `//` after the repository's own `scheme://` separates the path
+            let search_from = location.find("://").map_or(0, |index| index + 3);
+            let split = location[search_from..]
+                .find("//")
+                .map(|index| search_from + index)
+                .ok_or_else(|| invalid("expected git::<repository>//<path>"))?;
+            let (repo, path) = (&location[..split], &location[split + 2..]);
+            if repo.is_empty() || path.is_empty() {
+                return Err(invalid("expected git::<repository>//<path>"));
+            }
+            return Ok(RemoteSource::Git {
+                repo: repo.to_string(),
+                path: path.to_string(),
+                reference,
+            });
+        }
+
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Ok(RemoteSource::Http(url.to_string()));
+        }
+        Err(invalid("expected an http(s)://, s3:// or git:: URL"))
+    }
+
+    /// Name of the remote file, which also gives its format
+    fn file_name(&self) -> &str {
+        let path = match self {
+            RemoteSource::Http(url) => url.split(['?', '#']).next().unwrap_or(url),
+            RemoteSource::S3 { key, .. } => key,
+            RemoteSource::Git { path, .. } => path,
+        };
+        match path.rsplit('/').next() {
+            Some(name) if !name.is_empty() && !name.contains("://") => name,
+            _ => "bustcall.toml",
+        }
+    }
+
+    /// Download the config unless its ETag is still `etag`. `work_dir` holds
+    /// scratch files and, for git, the fetched repository.
+    fn fetch(&self, etag: Option<&str>, with_signature: bool, work_dir: &Path) -> Result<Fetched, ConfigError> {
+        match self {
+            RemoteSource::Http(url) => fetch_http(url, etag, with_signature),
+            RemoteSource::S3 { bucket, key } => {
+                let Some((content, etag)) = fetch_s3(bucket, key, etag, work_dir)? else {
+                    return Ok(Fetched::NotModified);
+                };
+                let signature = if with_signature {
+                    fetch_s3(bucket, &format!("{}.sig", key), None, work_dir)?.map(|(signature, _)| signature)
+                } else {
+                    None
+                };
+                Ok(Fetched::Changed { content, signature, etag })
+            }
+            RemoteSource::Git { repo, path, reference } => {
+                fetch_git(repo, path, reference.as_deref(), etag, with_signature, work_dir)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "remote-config")]
+fn fetch_http(url: &str, etag: Option<&str>, with_signature: bool) -> Result<Fetched, ConfigError> {
+    use std::io::Read;
+
+    let get = |url: &str, etag: Option<&str>| -> Result<ureq::Response, ConfigError> {
+        let mut request = ureq::get(url).timeout(std::time::Duration::from_secs(30));
+        if let Some(etag) = etag {
+            request = request.set("If-None-Match", etag);
+        }
+        request
+            .call()
+            .map_err(|e| ConfigError::Remote(format!("GET {}: {}", url, e)))
+    };
+    let body = |response: ureq::Response| -> Result<Vec<u8>, ConfigError> {
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body)?;
+        Ok(body)
+    };
+
+    let response = get(url, etag)?;
+    if response.status() == 304 {
+        return Ok(Fetched::NotModified);
+    }
+    let etag = response.header("ETag").map(str::to_string);
+    let content = body(response)?;
+    let signature = if with_signature {
+        Some(body(get(&format!("{}.sig", url), None)?)?)
+    } else {
+        None
+    };
+    Ok(Fetched::Changed { content, signature, etag })
+}
+
+#[cfg(not(feature = "remote-config"))]
+fn fetch_http(url: &str, _etag: Option<&str>, _with_signature: bool) -> Result<Fetched, ConfigError> {
+    Err(ConfigError::Remote(format!(
+        "Cannot fetch {}: bustcall was built without the remote-config feature",
+        url
+    )))
+}
+
+/// An S3 object's content and ETag
+type S3Object = (Vec<u8>, Option<String>);
+
+/// The object and its ETag, or `None` when it still has `etag`
+fn fetch_s3(
+    bucket: &str,
+    key: &str,
+    etag: Option<&str>,
+    work_dir: &Path,
+) -> Result<Option<S3Object>, ConfigError> {
+    let download = work_dir.join("s3-download.tmp");
+    let mut command = Command::new("aws");
+    command.args(["s3api", "get-object", "--bucket", bucket, "--key", key]);
+    if let Some(etag) = etag {
+        command.args(["--if-none-match", etag]);
+    }
+    let output = command.arg(&download).stdin(Stdio::null()).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("(304)") || stderr.contains("Not Modified") {
+            return Ok(None);
+        }
+        return Err(ConfigError::Remote(format!("s3://{}/{}: {}", bucket, key, stderr.trim())));
+    }
+
+    let content = std::fs::read(&download)?;
+    let _ = std::fs::remove_file(&download);
+    let etag = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .ok()
+        .and_then(|response| response.get("ETag")?.as_str().map(str::to_string));
+    Ok(Some((content, etag)))
+}
+
+/// Shallow fetch of `reference` into a bare repository in `work_dir`; the
+/// fetched commit is the ETag
+fn fetch_git(
+    repo: &str,
+    path: &str,
+    reference: Option<&str>,
+    etag: Option<&str>,
+    with_signature: bool,
+    work_dir: &Path,
+) -> Result<Fetched, ConfigError> {
+    let mirror = work_dir.join("repo.git");
+    let git = |args: &[&str]| -> Result<Vec<u8>, ConfigError> {
+        let output = Command::new("git")
+            .arg("--git-dir")
+            .arg(&mirror)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            return Err(ConfigError::Remote(format!(
+                "git {} ({}): {}",
+                args.join(" "),
+                repo,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(output.stdout)
+    };
+
+    if !mirror.exists() {
+        git(&["init", "--bare", "--quiet"])?;
+    }
+    git(&["fetch", "--quiet", "--depth", "1", repo, reference.unwrap_or("HEAD")])?;
+    let commit = String::from_utf8_lossy(&git(&["rev-parse", "FETCH_HEAD"])?).trim().to_string();
+    if etag == Some(commit.as_str()) {
+        return Ok(Fetched::NotModified);
+    }
+
+    let content = git(&["show", &format!("{}:{}", commit, path)])?;
+    let signature = if with_signature {
+        Some(git(&["show", &format!("{}:{}.sig", commit, path)])?)
+    } else {
+        None
+    };
+    Ok(Fetched::Changed {
+        content,
+        signature,
+        etag: Some(commit),
+    })
+}
+
+/// Check that `signature` over `content` was made by a key in
+/// `allowed_signers`
+fn verify_signature(content: &Path, signature: &Path, allowed_signers: &Path) -> Result<(), ConfigError> {
+    let rejected = |detail: &[u8]| {
+        ConfigError::Remote(format!(
+            "Signature {} is not valid for {} by a key in {}: {}",
+            signature.display(),
+            content.display(),
+            allowed_signers.display(),
+            String::from_utf8_lossy(detail).trim()
+        ))
+    };
+
+    let principals = Command::new("ssh-keygen")
+        .args(["-Y", "find-principals", "-s"])
+        .arg(signature)
+        .arg("-f")
+        .arg(allowed_signers)
+        .output()?;
+    if !principals.status.success() {
+        return Err(rejected(&principals.stderr));
+    }
+    let stdout = String::from_utf8_lossy(&principals.stdout);
+    let principal = stdout.lines().next().unwrap_or_default().trim();
+
+    let verified = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-n", SIGNATURE_NAMESPACE, "-I", principal, "-f"])
+        .arg(allowed_signers)
+        .arg("-s")
+        .arg(signature)
+        .stdin(std::fs::File::open(content)?)
+        .output()?;
+    if !verified.status.success() {
+        return Err(rejected(&verified.stderr));
+    }
+    Ok(())
+}
+
+/// `$XDG_CACHE_HOME/bustcall/remote`, falling back to `~/.cache`
+fn default_cache_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("bustcall").join("remote")
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteConfig {
+    url: String,
+    source: RemoteSource,
+    cache_dir: PathBuf,
+    allowed_signers: Option<PathBuf>,
+}
+
+impl RemoteConfig {
+    pub fn new(url: &str) -> Result<Self, ConfigError> {
+        Ok(Self {
+            url: url.to_string(),
+            source: RemoteSource::parse(url)?,
+            cache_dir: default_cache_dir(),
+            allowed_signers: None,
+        })
+    }
+
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// Only accept configs signed by a key in this `ssh-keygen` allowed
+    /// signers file
+    pub fn allowed_signers<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.allowed_signers = Some(path.into());
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Where the last good copy is kept
+    pub fn cached_path(&self) -> PathBuf {
+        let digest = hex::encode(Sha256::digest(self.url.as_bytes()));
+        self.cache_dir.join(&digest[..16]).join(self.source.file_name())
+    }
+
+    /// Bring the cached copy up to date and return its path. When the source
+    /// is unreachable or serves a config with a bad signature, an earlier
+    /// copy is used with a warning.
+    pub fn fetch(&self) -> Result<PathBuf, ConfigError> {
+        let cached = self.cached_path();
+        match self.refresh() {
+            Ok(_) => Ok(cached),
+            Err(e) if cached.exists() => {
+                log::warn!("⚠️ Using cached copy of {}: {}", self.url, e);
+                Ok(cached)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Download the config if it changed since the cached copy. Returns
+    /// whether the cached copy was replaced.
+    pub fn refresh(&self) -> Result<bool, ConfigError> {
+        let cached = self.cached_path();
+        let dir = cached.parent().expect("cached path has a directory");
+        std::fs::create_dir_all(dir)?;
+        let etag_path = dir.join("etag");
+        let etag = if cached.exists() {
+            std::fs::read_to_string(&etag_path).ok()
+        } else {
+            None
+        };
+
+        let fetched = self.source.fetch(etag.as_deref().map(str::trim), self.allowed_signers.is_some(), dir)?;
+        let (content, signature, etag) = match fetched {
+            Fetched::NotModified => return Ok(false),
+            Fetched::Changed { content, signature, etag } => (content, signature, etag),
+        };
+
+        let download = dir.join("download.tmp");
+        std::fs::write(&download, &content)?;
+        if let Some(allowed_signers) = &self.allowed_signers {
+            let signature_path = dir.join("download.tmp.sig");
+            std::fs::write(&signature_path, signature.unwrap_or_default())?;
+            let verified = verify_signature(&download, &signature_path, allowed_signers);
+            let _ = std::fs::remove_file(&signature_path);
+            if let Err(e) = verified {
+                let _ = std::fs::remove_file(&download);
+                return Err(e);
+            }
+        }
+
+        let changed = std::fs::read(&cached).ok().as_deref() != Some(content.as_slice());
+        if changed {
+            std::fs::rename(&download, &cached)?;
+            log::info!("📥 Fetched {}", self.url);
+        } else {
+            std::fs::remove_file(&download)?;
+        }
+        match etag {
+            Some(etag) => std::fs::write(&etag_path, etag)?,
+            None => {
+                let _ = std::fs::remove_file(&etag_path);
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Load the cached copy, fetching it first if there is none
+    pub fn load(&self, profile: Option<&str>) -> Result<BustcallConfig, ConfigError> {
+        let cached = self.cached_path();
+        if !cached.exists() {
+            self.fetch()?;
+        }
+        let table: Table = ConfigFormat::from_path(&cached).parse(&std::fs::read_to_string(&cached)?)?;
+        if table.contains_key("include") {
+            return Err(ConfigError::Remote(format!("{}: remote configs cannot include files", self.url)));
+        }
+
+        let mut config = BustcallConfig::load_with_profile(&cached, profile)?;
+        self.resolve_paths(&mut config)?;
+        Ok(config)
+    }
+
+    /// Resolve target paths of a config loaded from the cached copy against
+    /// the working directory
+    pub fn resolve_paths(&self, config: &mut BustcallConfig) -> Result<(), ConfigError> {
+        config.resolve_paths(Path::new(self.source.file_name()), &[])
+    }
+
+    /// Validate the cached copy as if it were in the working directory
+    pub fn validate(&self) -> std::io::Result<ValidationReport> {
+        let cached = self.cached_path();
+        let content = std::fs::read_to_string(&cached)?;
+        Ok(ValidationReport {
+            file: PathBuf::from(&self.url),
+            diagnostics: sorted(validate_str(&content, ConfigFormat::from_path(&cached), Path::new("."))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_sources() {
+        assert_eq!(
+            RemoteSource::parse("s3://configs/bustcall/team-a.toml").unwrap(),
+            RemoteSource::S3 {
+                bucket: "configs".to_string(),
+                key: "bustcall/team-a.toml".to_string(),
+            }
+        );
+        let git = RemoteSource::parse("git::https://git.internal/platform/configs.git//bustcall/team-a.yaml?ref=main")
+            .unwrap();
+        assert_eq!(
+            git,
+            RemoteSource::Git {
+                repo: "https://git.internal/platform/configs.git".to_string(),
+                path: "bustcall/team-a.yaml".to_string(),
+                reference: Some("main".to_string()),
+            }
+        );
+        assert_eq!(git.file_name(), "team-a.yaml");
+        assert_eq!(
+            RemoteSource::parse("https://configs.internal/team-a.json?v=2").unwrap().file_name(),
+            "team-a.json"
+        );
+        assert!(RemoteSource::parse("git::https://git.internal/configs.git").is_err());
+        assert!(!is_remote("configs/bustcall.toml"));
+    }
+
+    #[test]
+    fn test_git_source_is_cached_by_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("configs");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com", "-C"])
+                .arg(&repo)
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&["init", "--quiet"]);
+        std::fs::write(repo.join("team-a.toml"), "[global]\ndaemon_interval_seconds = 7\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "team-a"]);
+
+        let url = format!("git::{}//team-a.toml", repo.display());
+        let remote = RemoteConfig::new(&url).unwrap().cache_dir(dir.path().join("cache"));
+        assert!(remote.refresh().unwrap());
+        assert!(!remote.refresh().unwrap());
+        assert_eq!(remote.load(None).unwrap().global.daemon_interval_seconds, 7);
+
+        std::fs::write(repo.join("team-a.toml"), "[global]\ndaemon_interval_seconds = 3\n").unwrap();
+        git(&["commit", "--quiet", "-am", "faster"]);
+        assert!(remote.refresh().unwrap());
+        assert_eq!(remote.load(None).unwrap().global.daemon_interval_seconds, 3);
+
+        // Unsigned, so rejected; the verified copy stays
+        let signers = dir.path().join("allowed_signers");
+        std::fs::write(&signers, "").unwrap();
+        std::fs::write(repo.join("team-a.toml"), "[global]\ndaemon_interval_seconds = 1\n").unwrap();
+        git(&["commit", "--quiet", "-am", "unsigned"]);
+        let signed_only = remote.clone().allowed_signers(signers);
+        assert!(signed_only.refresh().is_err());
+        assert_eq!(signed_only.fetch().unwrap(), remote.cached_path());
+        assert_eq!(remote.load(None).unwrap().global.daemon_interval_seconds, 3);
+    }
+}
@@ -0,0 +1,350 @@
+//! Sandbox profile for spawned child processes
+//!
+//! Applied to a `Command` before it is spawned: the environment is reduced
+//! to an allowlist, address space and open files are capped with rlimits,
+//! `no_new_privs` is set, and on Linux writes are confined to
+//! `writable_paths` with Landlock. A profile that cannot be enforced fails the
+//! spawn rather than running the child unconfined.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::error::Result;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SandboxProfile {
+    /// Variables passed through from our environment; `None` inherits all
+    #[serde(default)]
+    pub env_allowlist: Option<Vec<String>>,
+    /// RLIMIT_AS
+    #[serde(default)]
+    pub max_address_space_mb: Option<u64>,
+    /// RLIMIT_NOFILE
+    #[serde(default)]
+    pub max_open_files: Option<u64>,
+    #[serde(default = "default_no_new_privs")]
+    pub no_new_privs: bool,
+    /// Paths the child may write beneath; `None` leaves writes unrestricted
+    #[serde(default)]
+    pub writable_paths: Option<Vec<PathBuf>>,
+}
+
+impl Default for SandboxProfile {
+    fn default() -> Self {
+        Self {
+            env_allowlist: None,
+            max_address_space_mb: None,
+            max_open_files: None,
+            no_new_privs: default_no_new_privs(),
+            writable_paths: None,
+        }
+    }
+}
+
+fn default_no_new_privs() -> bool {
+    true
+}
+
+impl SandboxProfile {
+    /// Configure `command` to run under this profile. Environment variables
+    /// meant for the child must be set after this, as the allowlist clears
+    /// them.
+    pub fn apply(&self, command: &mut Command) -> Result<()> {
+        if let Some(allowed) = &self.env_allowlist {
+            let kept: Vec<(String, String)> = std::env::vars()
+                .filter(|(name, _)| allowed.iter().any(|wanted| wanted == name))
+                .collect();
+            command.env_clear().envs(kept);
+        }
+        self.confine(command)
+    }
+
+    #[cfg(unix)]
+    fn confine(&self, command: &mut Command) -> Result<()> {
+        use std::os::unix::process::CommandExt;
+
+        let limits = Limits {
+            address_space: self.max_address_space_mb.map(|mb| mb * 1024 * 1024),
+            open_files: self.max_open_files,
+            no_new_privs: self.no_new_privs,
+        };
+        let landlock = match &self.writable_paths {
+            Some(paths) => Some(landlock::Ruleset::prepare(paths)?),
+            None => None,
+        };
+
+        // SAFETY: the closure only makes raw syscalls on data prepared before
+        // the fork and allocates nothing
+        unsafe {
+            command.pre_exec(move || {
+                limits.apply()?;
+                if let Some(ruleset) = &landlock {
+                    ruleset.restrict_self()?;
+                }
+                Ok(())
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn confine(&self, _command: &mut Command) -> Result<()> {
+        if self.max_address_space_mb.is_some() || self.max_open_files.is_some() || self.writable_paths.is_some() {
+            return Err(crate::utils::error::BustcallError::ProcessError(
+                "Sandbox rlimits and writable paths are not supported on this platform".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    address_space: Option<u64>,
+    open_files: Option<u64>,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    no_new_privs: bool,
+}
+
+#[cfg(unix)]
+impl Limits {
+    fn apply(&self) -> std::io::Result<()> {
+        let limit = |value: u64| libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        // SAFETY: the rlimit outlives each call
+        if let Some(bytes) = self.address_space {
+            check(unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit(bytes)) })?;
+        }
+        if let Some(files) = self.open_files {
+            check(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit(files)) })?;
+        }
+        #[cfg(target_os = "linux")]
+        if self.no_new_privs {
+            // SAFETY: prctl with integer arguments only
+            check(unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn check(rc: libc::c_int) -> std::io::Result<()> {
+    if rc == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+mod landlock {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    use crate::utils::error::{BustcallError, Result};
+
+    const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+    /// REMOVE_DIR, REMOVE_FILE and MAKE_{CHAR,DIR,REG,SOCK,FIFO,BLOCK,SYM}
+    const ACCESS_FS_DIR_WRITE: u64 = 0b1_1111_1111 << 4;
+    /// Linking or renaming into another directory, from ABI 2
+    const ACCESS_FS_REFER: u64 = 1 << 13;
+    /// truncate(2) and O_TRUNC, from ABI 3
+    const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+    const RULE_PATH_BENEATH: libc::c_int = 1;
+
+    #[repr(C)]
+    struct RulesetAttr {
+        handled_access_fs: u64,
+    }
+
+    #[repr(C, packed)]
+    struct PathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: libc::c_int,
+    }
+
+    /// Write rights the kernel's Landlock `abi` can restrict; rights it does
+    /// not know of stay allowed everywhere
+    pub(super) fn handled_access(abi: i64) -> u64 {
+        let mut access = ACCESS_FS_WRITE_FILE | ACCESS_FS_DIR_WRITE;
+        if abi >= 2 {
+            access |= ACCESS_FS_REFER;
+        }
+        if abi >= 3 {
+            access |= ACCESS_FS_TRUNCATE;
+        }
+        access
+    }
+
+    /// Writable paths resolved before forking
+    #[derive(Debug)]
+    pub(super) struct Ruleset {
+        handled: u64,
+        paths: Vec<(CString, u64)>,
+    }
+
+    impl Ruleset {
+        pub(super) fn prepare(paths: &[PathBuf]) -> Result<Self> {
+            // SAFETY: querying the ABI version passes no attributes
+            let abi = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    std::ptr::null::<RulesetAttr>(),
+                    0usize,
+                    1u32, // LANDLOCK_CREATE_RULESET_VERSION
+                )
+            };
+            if abi < 1 {
+                return Err(BustcallError::ProcessError(
+                    "writable_paths needs Landlock, which this kernel does not provide".to_string(),
+                ));
+            }
+
+            let handled = handled_access(abi);
+            let mut prepared = Vec::new();
+            for path in paths {
+                let Ok(metadata) = std::fs::metadata(path) else {
+                    log::warn!("🧱 Sandbox writable path {} does not exist, skipping", path.display());
+                    continue;
+                };
+                // Directory rights do not apply to files
+                let access = if metadata.is_dir() {
+                    handled
+                } else {
+                    handled & (ACCESS_FS_WRITE_FILE | ACCESS_FS_TRUNCATE)
+                };
+                let path = CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+                    BustcallError::ProcessError(format!("Invalid sandbox path {}", path.display()))
+                })?;
+                prepared.push((path, access));
+            }
+            Ok(Self { handled, paths: prepared })
+        }
+
+        /// Runs in the forked child
+        pub(super) fn restrict_self(&self) -> std::io::Result<()> {
+            let attr = RulesetAttr {
+                handled_access_fs: self.handled,
+            };
+            // SAFETY: `attr` outlives the call and its size is passed along
+            let ruleset = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    &attr as *const RulesetAttr,
+                    std::mem::size_of::<RulesetAttr>(),
+                    0u32,
+                )
+            };
+            if ruleset < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let ruleset = ruleset as libc::c_int;
+
+            for (path, access) in &self.paths {
+                // SAFETY: `path` is a NUL-terminated string owned by self
+                let fd = unsafe { libc::open(path.as_ptr(), libc::O_PATH | libc::O_CLOEXEC) };
+                if fd < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                let rule = PathBeneathAttr {
+                    allowed_access: *access,
+                    parent_fd: fd,
+                };
+                // SAFETY: `rule` outlives the call; fds are ours to close
+                let rc = unsafe {
+                    let rc = libc::syscall(
+                        libc::SYS_landlock_add_rule,
+                        ruleset,
+                        RULE_PATH_BENEATH,
+                        &rule as *const PathBeneathAttr,
+                        0u32,
+                    );
+                    libc::close(fd);
+                    rc
+                };
+                if rc < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+
+            // Landlock requires no_new_privs for unprivileged callers
+            // SAFETY: prctl and restrict_self take integer arguments only
+            let rc = unsafe {
+                libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0);
+                let rc = libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0u32);
+                libc::close(ruleset);
+                rc
+            };
+            if rc < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+mod landlock {
+    use std::path::PathBuf;
+
+    use crate::utils::error::{BustcallError, Result};
+
+    #[derive(Debug)]
+    pub(super) struct Ruleset;
+
+    impl Ruleset {
+        pub(super) fn prepare(_paths: &[PathBuf]) -> Result<Self> {
+            Err(BustcallError::ProcessError(
+                "writable_paths is only supported on Linux".to_string(),
+            ))
+        }
+
+        pub(super) fn restrict_self(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_child_sees_env_allowlist_and_rlimit() {
+        let profile = SandboxProfile {
+            env_allowlist: Some(vec!["PATH".to_string()]),
+            max_open_files: Some(64),
+            ..SandboxProfile::default()
+        };
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("echo ${HOME:-unset} $(ulimit -n) $TASK");
+        profile.apply(&mut command).unwrap();
+        command.env("TASK", "build");
+
+        let output = command.output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "unset 64 build");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_landlock_handles_what_the_abi_knows() {
+        let (base, refer, truncate) = (landlock::handled_access(1), 1 << 13, 1 << 14);
+        assert_eq!(base & (refer | truncate), 0);
+        assert_eq!(landlock::handled_access(2), base | refer);
+        assert_eq!(landlock::handled_access(4), base | refer | truncate);
+    }
+
+    #[test]
+    fn test_profile_defaults() {
+        let profile: SandboxProfile = toml::from_str("max_open_files = 256").unwrap();
+        assert!(profile.no_new_privs);
+        assert!(profile.writable_paths.is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! Per-target maintenance windows and quiet hours
+//!
+//! During a target's quiet hours busts are either queued for replay once the
+//! window closes or executed with a downgraded severity.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use chrono::{Local, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use crate::core::fingerprint::FingerprintConfig;
+use crate::core::priority::ResourcePolicy;
+use crate::core::probe::LivenessProbe;
+use crate::core::process::RestartPolicy;
+use crate::core::sandbox::SandboxProfile;
+use crate::dimensional_cache::{BustReason, CacheBustSeverity};
+use crate::utils::error::{BustcallError, Result};
+
+/// Daily time window such as `22:00-06:00`; may wrap past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = BustcallError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (start, end) = s.split_once('-').ok_or_else(|| {
+            BustcallError::ConfigError(format!("Invalid quiet_hours '{}', expected HH:MM-HH:MM", s))
+        })?;
+
+        let parse = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|e| {
+                BustcallError::ConfigError(format!("Invalid quiet_hours time '{}': {}", t, e))
+            })
+        };
+
+        Ok(Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuietHoursAction {
+    /// Hold busts and replay them when the window opens
+    #[default]
+    Queue,
+    /// Execute immediately with one step lower severity
+    Downgrade,
+}
+
+/// Quiet-hours, restart, fingerprint, liveness and resource settings for a
+/// single target
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TargetSchedule {
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    #[serde(default)]
+    pub quiet_hours_action: QuietHoursAction,
+    /// Command and arguments that bring the target back, e.g.
+    /// `["systemctl", "restart", "api"]`
+    #[serde(default)]
+    pub restart_command: Option<Vec<String>>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Bust when the runtime's environment changes under a running process
+    #[serde(default)]
+    pub fingerprint: Option<FingerprintConfig>,
+    /// TCP, HTTP or exec check that the runtime is not wedged
+    #[serde(default)]
+    pub liveness: Option<LivenessProbe>,
+    /// `cpu_quota`, `nice`, ... applied when the target starves other work
+    #[serde(flatten)]
+    pub resources: ResourcePolicy,
+    /// Confines the restart and rebuild commands run for the target
+    #[serde(default)]
+    pub sandbox: Option<SandboxProfile>,
+}
+
+/// Outcome of submitting a bust to the scheduler
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleDecision {
+    Execute(CacheBustSeverity),
+    Queued,
+}
+
+#[derive(Debug, Default)]
+pub struct BustScheduler {
+    windows: HashMap<String, (QuietHours, QuietHoursAction)>,
+    queued: Mutex<Vec<(String, CacheBustSeverity, BustReason)>>,
+}
+
+impl BustScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_targets(targets: &HashMap<String, TargetSchedule>) -> Result<Self> {
+        let mut scheduler = Self::new();
+        for (target, schedule) in targets {
+            if let Some(quiet_hours) = &schedule.quiet_hours {
+                scheduler.set_quiet_hours(target, quiet_hours.parse()?, schedule.quiet_hours_action);
+            }
+        }
+        Ok(scheduler)
+    }
+
+    pub fn set_quiet_hours(&mut self, target: &str, window: QuietHours, action: QuietHoursAction) {
+        self.windows.insert(target.to_string(), (window, action));
+    }
+
+    pub fn submit(&self, target: &str, severity: CacheBustSeverity, reason: &BustReason) -> ScheduleDecision {
+        self.submit_at(target, severity, reason, Local::now().time())
+    }
+
+    /// Critical busts always bypass quiet hours. Queued busts keep their
+    /// reason for the replay.
+    pub fn submit_at(
+        &self,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: &BustReason,
+        now: NaiveTime,
+    ) -> ScheduleDecision {
+        let Some((window, action)) = self.windows.get(target) else {
+            return ScheduleDecision::Execute(severity);
+        };
+
+        if matches!(severity, CacheBustSeverity::Critical) || !window.contains(now) {
+            return ScheduleDecision::Execute(severity);
+        }
+
+        match action {
+            QuietHoursAction::Queue => {
+                log::info!("🌙 Quiet hours for {}, queueing {:?} bust", target, severity);
+                self.queued.lock().unwrap().push((target.to_string(), severity, reason.clone()));
+                ScheduleDecision::Queued
+            }
+            QuietHoursAction::Downgrade => ScheduleDecision::Execute(downgrade(severity)),
+        }
+    }
+
+    /// Remove and return queued busts whose target window has opened
+    pub fn drain_ready(&self) -> Vec<(String, CacheBustSeverity, BustReason)> {
+        self.drain_ready_at(Local::now().time())
+    }
+
+    pub fn drain_ready_at(&self, now: NaiveTime) -> Vec<(String, CacheBustSeverity, BustReason)> {
+        let mut queued = self.queued.lock().unwrap();
+        let (ready, waiting): (Vec<_>, Vec<_>) = queued.drain(..).partition(|(target, _, _)| {
+            self.windows
+                .get(target)
+                .is_none_or(|(window, _)| !window.contains(now))
+        });
+        *queued = waiting;
+        ready
+    }
+
+    pub fn queued_len(&self) -> usize {
+        self.queued.lock().unwrap().len()
+    }
+}
+
+fn downgrade(severity: CacheBustSeverity) -> CacheBustSeverity {
+    match severity {
+        CacheBustSeverity::Critical => CacheBustSeverity::High,
+        CacheBustSeverity::High => CacheBustSeverity::Medium,
+        CacheBustSeverity::Medium | CacheBustSeverity::Low => CacheBustSeverity::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_overnight_window() {
+        let window: QuietHours = "22:00-06:00".parse().unwrap();
+        assert!(window.contains(at(23, 30)));
+        assert!(window.contains(at(5, 59)));
+        assert!(!window.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn test_queued_busts_replay_after_window() {
+        let mut scheduler = BustScheduler::new();
+        scheduler.set_quiet_hours("node", "22:00-06:00".parse().unwrap(), QuietHoursAction::Queue);
+
+        let reason = BustReason::from("test");
+        let decision = scheduler.submit_at("node", CacheBustSeverity::Medium, &reason, at(23, 0));
+        assert_eq!(decision, ScheduleDecision::Queued);
+        assert!(scheduler.drain_ready_at(at(2, 0)).is_empty());
+        assert_eq!(scheduler.drain_ready_at(at(7, 0)), vec![("node".to_string(), CacheBustSeverity::Medium, reason)]);
+        assert_eq!(scheduler.queued_len(), 0);
+    }
+}
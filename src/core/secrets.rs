@@ -0,0 +1,136 @@
+//! Secret references in config values
+//!
+//! Any string value may contain `${env:NAME}` or `${file:/path}`, e.g.
+//! `redis_url = "redis://:${env:REDIS_PASSWORD}@cache/"`. References are
+//! replaced when the config is loaded, and the key paths that held one are
+//! remembered so the values can be redacted for display and are never
+//! written back to disk.
+
+use std::collections::BTreeSet;
+
+use regex::Regex;
+use toml::{Table, Value};
+
+use crate::core::config::ConfigError;
+
+/// Shown in place of a resolved secret
+pub const REDACTED: &str = "<redacted>";
+
+/// Replace every secret reference below `table`. Returns the dotted key
+/// paths of the values that held one; array elements are numbered.
+pub fn resolve_secrets(table: &mut Table) -> Result<BTreeSet<String>, ConfigError> {
+    let pattern = Regex::new(r"\$\{(env|file):([^}]+)\}").expect("valid secret pattern");
+    let mut secrets = BTreeSet::new();
+    for (key, value) in table.iter_mut() {
+        resolve_value(key, value, &pattern, &mut secrets)?;
+    }
+    Ok(secrets)
+}
+
+fn resolve_value(
+    path: &str,
+    value: &mut Value,
+    pattern: &Regex,
+    secrets: &mut BTreeSet<String>,
+) -> Result<(), ConfigError> {
+    match value {
+        Value::String(raw) if pattern.is_match(raw) => {
+            *raw = substitute(raw, pattern)?;
+            secrets.insert(path.to_string());
+        }
+        Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                resolve_value(&format!("{}.{}", path, key), value, pattern, secrets)?;
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter_mut().enumerate() {
+                resolve_value(&format!("{}.{}", path, index), value, pattern, secrets)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn substitute(raw: &str, pattern: &Regex) -> Result<String, ConfigError> {
+    let mut resolved = String::new();
+    let mut last = 0;
+    for captures in pattern.captures_iter(raw) {
+        let reference = captures.get(0).expect("whole match");
+        let name = &captures[2];
+        let secret = match &captures[1] {
+            "env" => std::env::var(name)
+                .map_err(|_| ConfigError::Parse(format!("Secret ${{env:{}}} is not set", name)))?,
+            _ => std::fs::read_to_string(name)
+                .map_err(|e| ConfigError::Parse(format!("Cannot read secret file {}: {}", name, e)))?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+        };
+        resolved.push_str(&raw[last..reference.start()]);
+        resolved.push_str(&secret);
+        last = reference.end();
+    }
+    resolved.push_str(&raw[last..]);
+    Ok(resolved)
+}
+
+/// Replace the values at `secrets` with [`REDACTED`]
+pub fn redact(table: &mut Table, secrets: &BTreeSet<String>) {
+    for path in secrets {
+        let mut parts = path.split('.');
+        let Some(first) = parts.next() else {
+            continue;
+        };
+        let mut node = table.get_mut(first);
+        for part in parts {
+            node = match node {
+                Some(Value::Table(table)) => table.get_mut(part),
+                Some(Value::Array(items)) => part.parse::<usize>().ok().and_then(|index| items.get_mut(index)),
+                _ => None,
+            };
+        }
+        if let Some(value) = node {
+            *value = Value::String(REDACTED.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_and_redacts_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_file = dir.path().join("redis");
+        std::fs::write(&secret_file, "s3cret\n").unwrap();
+        std::env::set_var("SECRETS_TEST_SLACK_TOKEN", "xoxb-123");
+
+        let mut table: Table = format!(
+            "[daemon.leader_election]\nredis_url = \"redis://:${{file:{}}}@cache/\"\n\n\
+             [notify.slack]\nbot_token = \"${{env:SECRETS_TEST_SLACK_TOKEN}}\"\nchannel = \"#builds\"\n",
+            secret_file.display()
+        )
+        .parse()
+        .unwrap();
+
+        let secrets = resolve_secrets(&mut table).unwrap();
+        assert_eq!(
+            secrets.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["daemon.leader_election.redis_url", "notify.slack.bot_token"]
+        );
+        assert_eq!(table["daemon"]["leader_election"]["redis_url"].as_str(), Some("redis://:s3cret@cache/"));
+        assert_eq!(table["notify"]["slack"]["bot_token"].as_str(), Some("xoxb-123"));
+
+        redact(&mut table, &secrets);
+        assert_eq!(table["notify"]["slack"]["bot_token"].as_str(), Some(REDACTED));
+        assert_eq!(table["notify"]["slack"]["channel"].as_str(), Some("#builds"));
+    }
+
+    #[test]
+    fn test_missing_secret_fails_the_load() {
+        let mut table: Table = "password = \"${env:SECRETS_TEST_UNSET}\"".parse().unwrap();
+        assert!(resolve_secrets(&mut table).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Per-target overrides of file change severity
+//!
+//! By default the watcher derives a bust severity from the file extension.
+//! A target can override that for paths matching glob patterns:
+//!
+//! ```toml
+//! [target.web]
+//! path = "web"
+//! severity_overrides = { "*.lock" = "critical", "docs/**" = "ignore" }
+//! ```
+//!
+//! Patterns without a `/` match the file name anywhere below the target;
+//! others match the path relative to the target's `path`. When several
+//! patterns match, the longest one wins.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::TargetConfig;
+use crate::dimensional_cache::CacheBustSeverity;
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityOverride {
+    /// Do not bust at all
+    Ignore,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl SeverityOverride {
+    /// `None` for `Ignore`
+    pub fn severity(self) -> Option<CacheBustSeverity> {
+        match self {
+            SeverityOverride::Ignore => None,
+            SeverityOverride::Low => Some(CacheBustSeverity::Low),
+            SeverityOverride::Medium => Some(CacheBustSeverity::Medium),
+            SeverityOverride::High => Some(CacheBustSeverity::High),
+            SeverityOverride::Critical => Some(CacheBustSeverity::Critical),
+        }
+    }
+}
+
+/// A target's compiled `severity_overrides`
+#[derive(Debug, Clone)]
+pub struct SeverityRules {
+    pub target: String,
+    root: Option<PathBuf>,
+    /// Longest pattern first
+    rules: Vec<(Pattern, SeverityOverride)>,
+}
+
+impl SeverityRules {
+    pub fn new(
+        target: &str,
+        root: Option<PathBuf>,
+        overrides: &BTreeMap<String, SeverityOverride>,
+    ) -> Result<Self> {
+        let mut rules = Vec::new();
+        for (pattern, severity) in overrides {
+            let compiled = Pattern::new(pattern).map_err(|e| {
+                BustcallError::ConfigError(format!("Invalid severity override pattern {} for {}: {}", pattern, target, e))
+            })?;
+            rules.push((compiled, *severity));
+        }
+        rules.sort_by(|(a, _), (b, _)| {
+            let (a, b) = (a.as_str(), b.as_str());
+            b.len().cmp(&a.len()).then_with(|| a.cmp(b))
+        });
+
+        Ok(Self {
+            target: target.to_string(),
+            root,
+            rules,
+        })
+    }
+
+    /// Rules of every target that has overrides
+    pub fn from_targets(targets: &HashMap<String, TargetConfig>) -> Result<Vec<Self>> {
+        let mut all = Vec::new();
+        for (name, target) in targets {
+            if target.enabled && !target.severity_overrides.is_empty() {
+                all.push(Self::new(name, target.root(), &target.severity_overrides)?);
+            }
+        }
+        all.sort_by(|a, b| a.target.cmp(&b.target));
+        Ok(all)
+    }
+
+    /// The override for a change to `path`, if it is below this target's
+    /// path and matches a pattern
+    pub fn classify(&self, path: &Path) -> Option<SeverityOverride> {
+        let relative = match &self.root {
+            Some(root) => path.strip_prefix(root).ok()?,
+            None => path,
+        };
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+
+        self.rules.iter().find_map(|(pattern, severity)| {
+            let matched = if pattern.as_str().contains('/') {
+                pattern.matches_path_with(relative, options)
+            } else {
+                relative
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| pattern.matches_with(name, options))
+            };
+            matched.then_some(*severity)
+        })
+    }
+}
+
+/// The first target whose rules match `path`, with its override
+pub fn override_for<'a>(rules: &'a [SeverityRules], path: &Path) -> Option<(&'a str, SeverityOverride)> {
+    rules
+        .iter()
+        .find_map(|rules| rules.classify(path).map(|severity| (rules.target.as_str(), severity)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_matching_pattern_wins() {
+        let overrides: BTreeMap<String, SeverityOverride> = toml::from_str(
+            r#"
+            "*.lock" = "critical"
+            "docs/**" = "ignore"
+            "docs/api/**" = "high"
+            "#,
+        )
+        .unwrap();
+        let rules = SeverityRules::new("web", Some(PathBuf::from("/repo/web")), &overrides).unwrap();
+
+        let classify = |path: &str| rules.classify(Path::new(path));
+        assert_eq!(classify("/repo/web/package.lock"), Some(SeverityOverride::Critical));
+        assert_eq!(classify("/repo/web/deep/yarn.lock"), Some(SeverityOverride::Critical));
+        assert_eq!(classify("/repo/web/docs/guide/intro.md"), Some(SeverityOverride::Ignore));
+        assert_eq!(classify("/repo/web/docs/api/v1.md"), Some(SeverityOverride::High));
+        assert_eq!(classify("/repo/web/src/index.ts"), None);
+        // Outside the target's path
+        assert_eq!(classify("/repo/api/Cargo.lock"), None);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_a_config_error() {
+        let overrides = BTreeMap::from([("[docs".to_string(), SeverityOverride::Ignore)]);
+        assert!(SeverityRules::new("web", None, &overrides).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+//! Event storms
+//!
+//! A `git checkout` of a large branch or an `npm install` fires thousands of
+//! file events at once, and assessing, hashing and journaling each of them
+//! only to bust the same targets over and over is wasted work. Once events
+//! come faster than the watcher's `max_events_per_second`, it is in a
+//! storm: per-file processing is paused and events are only counted per
+//! target. When the rate has stayed at or below the threshold for the
+//! storm's quiet time, the storm is over, every target it touched gets one
+//! High bust, and a notification describes it.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Time the event rate must stay below the threshold for a storm to end
+pub const DEFAULT_STORM_QUIET: Duration = Duration::from_secs(2);
+
+/// What a storm amounted to
+#[derive(Debug, Clone, PartialEq)]
+pub struct StormReport {
+    pub duration: Duration,
+    pub events: usize,
+    /// Most events seen in one second
+    pub peak_rate: usize,
+    /// Events per target, ignored paths left out
+    pub targets: BTreeMap<String, usize>,
+}
+
+impl StormReport {
+    pub fn describe(&self) -> String {
+        let targets: Vec<String> = self.targets.iter().map(|(target, events)| format!("{} ({})", target, events)).collect();
+        format!(
+            "Event storm of {} events over {:.1}s, peaking at {}/s; busted High once: {}",
+            self.events,
+            self.duration.as_secs_f64(),
+            self.peak_rate,
+            if targets.is_empty() { "no targets".to_string() } else { targets.join(", ") }
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Storm {
+    started: Instant,
+    events: usize,
+    peak_rate: usize,
+    targets: BTreeMap<String, usize>,
+    /// Since when the rate is at or below the threshold
+    calm_since: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct StormDetector {
+    /// Events per second above which a storm starts
+    threshold: usize,
+    quiet: Duration,
+    /// Events of the last second
+    recent: VecDeque<Instant>,
+    storm: Option<Storm>,
+}
+
+impl StormDetector {
+    pub fn new(threshold: u32, quiet: Duration) -> Self {
+        Self {
+            threshold: threshold as usize,
+            quiet,
+            recent: VecDeque::new(),
+            storm: None,
+        }
+    }
+
+    fn trim(&mut self, now: Instant) {
+        while self.recent.front().is_some_and(|time| now.duration_since(*time) >= Duration::from_secs(1)) {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Count an event; true when it starts a storm
+    pub fn record(&mut self, now: Instant) -> bool {
+        self.recent.push_back(now);
+        self.trim(now);
+        let rate = self.recent.len();
+        match &mut self.storm {
+            Some(storm) => {
+                storm.events += 1;
+                storm.peak_rate = storm.peak_rate.max(rate);
+                false
+            }
+            None if rate > self.threshold => {
+                self.storm = Some(Storm {
+                    started: now,
+                    events: rate,
+                    peak_rate: rate,
+                    targets: BTreeMap::new(),
+                    calm_since: None,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn in_storm(&self) -> bool {
+        self.storm.is_some()
+    }
+
+    /// Count a storm's event on `target`
+    pub fn absorb(&mut self, target: &str) {
+        if let Some(storm) = &mut self.storm {
+            *storm.targets.entry(target.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// End the storm once the rate stayed at or below the threshold for
+    /// the quiet time
+    pub fn check(&mut self, now: Instant) -> Option<StormReport> {
+        self.trim(now);
+        let calm = self.recent.len() <= self.threshold;
+        let storm = self.storm.as_mut()?;
+        if !calm {
+            storm.calm_since = None;
+            return None;
+        }
+        let calm_since = *storm.calm_since.get_or_insert(now);
+        if now.duration_since(calm_since) < self.quiet {
+            return None;
+        }
+        self.finish(now)
+    }
+
+    /// End the storm, if any, whatever the rate
+    pub fn finish(&mut self, now: Instant) -> Option<StormReport> {
+        let storm = self.storm.take()?;
+        Some(StormReport {
+            duration: now.duration_since(storm.started),
+            events: storm.events,
+            peak_rate: storm.peak_rate,
+            targets: storm.targets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storms_start_above_the_threshold_and_end_once_quiet() {
+        let start = Instant::now();
+        let mut detector = StormDetector::new(10, Duration::from_secs(2));
+        for i in 0..10 {
+            assert!(!detector.record(start + Duration::from_millis(i * 10)));
+        }
+        assert!(detector.record(start + Duration::from_millis(100)));
+        assert!(detector.in_storm());
+        for i in 0..100 {
+            detector.record(start + Duration::from_millis(100 + i));
+            detector.absorb(if i % 4 == 0 { "web" } else { "node" });
+        }
+        assert_eq!(detector.check(start + Duration::from_millis(500)), None);
+
+        // Quiet from 1.2s, when the last second's events have aged out
+        assert_eq!(detector.check(start + Duration::from_millis(1200)), None);
+        assert_eq!(detector.check(start + Duration::from_millis(3000)), None);
+        let report = detector.check(start + Duration::from_millis(3300)).unwrap();
+        assert!(!detector.in_storm());
+        assert_eq!(report.events, 111);
+        assert_eq!(report.peak_rate, 111);
+        assert_eq!(report.targets, BTreeMap::from([("node".to_string(), 75), ("web".to_string(), 25)]));
+        assert!(report.describe().contains("node (75), web (25)"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Supervised tokio tasks
+//!
+//! A daemon's long-running tasks are spawned into one `JoinSet`. One that
+//! panics is reported through `on_panic` and started again after a backoff
+//! that grows with its consecutive failures, so a task that keeps failing
+//! does not spin; a task that ran longer than the longest backoff before
+//! failing starts over from the shortest. Tasks watch the shutdown channel
+//! and return once it is set, or once its sender is dropped; the supervisor
+//! returns when all of them have.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::hash::Hash;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use futures::FutureExt;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+/// First and longest wait before a task that panicked is started again
+pub const TASK_RESTART_BACKOFF: (Duration, Duration) = (Duration::from_millis(100), Duration::from_secs(30));
+
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Wait before starting a task again after its `failures`th failure in a row
+pub fn restart_backoff(failures: u32) -> Duration {
+    let (min, max) = TASK_RESTART_BACKOFF;
+    min.saturating_mul(2u32.saturating_pow(failures.saturating_sub(1))).min(max)
+}
+
+/// Resolves once `shutdown` is set or its sender is gone
+pub async fn stopping(shutdown: &mut watch::Receiver<bool>) {
+    let _ = shutdown.wait_for(|stopping| *stopping).await;
+}
+
+/// Run `tasks`, each future made by `spawn`, until they return, restarting
+/// those that panic unless shutting down
+pub async fn supervise<K>(
+    tasks: Vec<K>,
+    mut spawn: impl FnMut(K) -> TaskFuture,
+    shutdown: watch::Receiver<bool>,
+    on_panic: impl Fn(K, &str),
+) where
+    K: Copy + Eq + Hash + Debug + Send + 'static,
+{
+    let mut set = JoinSet::new();
+    for task in tasks {
+        set.spawn(run(task, Duration::ZERO, spawn(task), shutdown.clone()));
+    }
+
+    let mut failures: HashMap<K, u32> = HashMap::new();
+    while let Some(joined) = set.join_next().await {
+        let (task, ran, result) = match joined {
+            Ok(joined) => joined,
+            Err(e) => {
+                log::error!("Supervised task aborted: {}", e);
+                continue;
+            }
+        };
+        let Err(panic) = result else {
+            log::debug!("{:?} task finished", task);
+            continue;
+        };
+
+        let message = panic_message(&*panic);
+        on_panic(task, &message);
+        if *shutdown.borrow() || shutdown.has_changed().is_err() {
+            continue;
+        }
+        let failures = failures.entry(task).or_insert(0);
+        *failures = if ran > TASK_RESTART_BACKOFF.1 { 1 } else { *failures + 1 };
+        let backoff = restart_backoff(*failures);
+        log::warn!("🔁 {:?} task panicked ({}), restarting it in {:?}", task, message, backoff);
+        set.spawn(run(task, backoff, spawn(task), shutdown.clone()));
+    }
+}
+
+/// `future` after `delay`, unless shutting down by then, with how long it
+/// ran and whether it panicked
+async fn run<K>(
+    task: K,
+    delay: Duration,
+    future: TaskFuture,
+    mut shutdown: watch::Receiver<bool>,
+) -> (K, Duration, std::thread::Result<()>) {
+    if !delay.is_zero() {
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = stopping(&mut shutdown) => return (task, Duration::ZERO, Ok(())),
+        }
+    }
+    let started = Instant::now();
+    let result = AssertUnwindSafe(future).catch_unwind().await;
+    (task, started.elapsed(), result)
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_panicked_tasks_restart_until_shutdown() {
+        assert_eq!(restart_backoff(1), TASK_RESTART_BACKOFF.0);
+        assert_eq!(restart_backoff(3), TASK_RESTART_BACKOFF.0 * 4);
+        assert_eq!(restart_backoff(40), TASK_RESTART_BACKOFF.1);
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let runs = Arc::new(AtomicU32::new(0));
+        let panics = Arc::new(Mutex::new(Vec::new()));
+        let spawn = {
+            let runs = runs.clone();
+            let shutdown = shutdown_rx.clone();
+            move |task: &'static str| -> TaskFuture {
+                let runs = runs.clone();
+                let mut shutdown = shutdown.clone();
+                Box::pin(async move {
+                    if task == "flaky" && runs.fetch_add(1, Ordering::SeqCst) < 2 {
+                        panic!("flaky failed");
+                    }
+                    stopping(&mut shutdown).await;
+                })
+            }
+        };
+        let on_panic = {
+            let panics = panics.clone();
+            move |task: &'static str, message: &str| panics.lock().unwrap().push(format!("{}: {}", task, message))
+        };
+        let supervisor = tokio::spawn(supervise(vec!["flaky", "steady"], spawn, shutdown_rx, on_panic));
+
+        tokio::time::sleep(TASK_RESTART_BACKOFF.0 * 5).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(*panics.lock().unwrap(), ["flaky: flaky failed", "flaky: flaky failed"]);
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), supervisor).await.unwrap().unwrap();
+    }
+}
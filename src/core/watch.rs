@@ -0,0 +1,313 @@
+//! Per-process resource alarms
+//!
+//! `[[monitoring.watches]]` entries select processes by name or pid and set
+//! limits on CPU, RSS, open file descriptors and threads. A limit must be
+//! exceeded for `consecutive_samples` samples in a row before an alarm is
+//! raised; it is raised once and re-armed when the process drops back below.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::notify::{Notification, NotificationLevel};
+use crate::core::process::{open_fd_count, thread_count, ProcessFilter, ProcessInfo, ProcessManager};
+use crate::dimensional_cache::CacheBustSeverity;
+use crate::utils::error::{BustcallError, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessWatchConfig {
+    /// Substring of the process name
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    #[serde(default)]
+    pub max_cpu_percent: Option<f64>,
+    #[serde(default)]
+    pub max_rss_mb: Option<u64>,
+    #[serde(default)]
+    pub max_fds: Option<u64>,
+    #[serde(default)]
+    pub max_threads: Option<u64>,
+    #[serde(default = "default_consecutive_samples")]
+    pub consecutive_samples: u32,
+    #[serde(default = "default_level")]
+    pub level: NotificationLevel,
+    /// Target named in the notification, and busted if `bust` is set
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub bust: Option<CacheBustSeverity>,
+}
+
+fn default_consecutive_samples() -> u32 {
+    3
+}
+
+fn default_level() -> NotificationLevel {
+    NotificationLevel::Warning
+}
+
+impl ProcessWatchConfig {
+    fn filter(&self) -> ProcessFilter {
+        ProcessFilter {
+            pid: self.pid,
+            name_pattern: self.name.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn label(&self) -> String {
+        match (&self.name, self.pid) {
+            (Some(name), _) => name.clone(),
+            (None, Some(pid)) => format!("pid {}", pid),
+            (None, None) => "process".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Cpu,
+    Rss,
+    Fds,
+    Threads,
+}
+
+impl Resource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resource::Cpu => "cpu",
+            Resource::Rss => "rss",
+            Resource::Fds => "fds",
+            Resource::Threads => "threads",
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            Resource::Cpu => "%",
+            Resource::Rss => " MB",
+            Resource::Fds | Resource::Threads => "",
+        }
+    }
+}
+
+/// One sampled value above its limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breach {
+    pub resource: Resource,
+    pub value: f64,
+    pub limit: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessAlarm {
+    pub pid: u32,
+    pub name: String,
+    pub breach: Breach,
+    pub samples: u32,
+    pub level: NotificationLevel,
+    pub target: Option<String>,
+    pub bust: Option<CacheBustSeverity>,
+}
+
+impl ProcessAlarm {
+    pub fn notification(&self) -> Notification {
+        let breach = &self.breach;
+        let unit = breach.resource.unit();
+        let mut notification = Notification::new(
+            self.level,
+            &format!(
+                "{} (pid {}) {} at {:.1}{} over limit {:.1}{} for {} samples",
+                self.name,
+                self.pid,
+                breach.resource.as_str(),
+                breach.value,
+                unit,
+                breach.limit,
+                unit,
+                self.samples
+            ),
+        )
+        .title("Process threshold exceeded")
+        .component("process-watch")
+        .with_metadata("pid", &self.pid.to_string())
+        .with_metadata("resource", breach.resource.as_str())
+        .with_metadata("trigger", &self.name);
+
+        if let Some(target) = &self.target {
+            notification = notification.target(target);
+        }
+        notification
+    }
+}
+
+/// Limits exceeded by one sample of `process`
+pub fn breaches(config: &ProcessWatchConfig, process: &ProcessInfo) -> Vec<Breach> {
+    let mut breaches = Vec::new();
+    let mut check = |resource, value: Option<f64>, limit: Option<f64>| {
+        if let (Some(value), Some(limit)) = (value, limit) {
+            if value > limit {
+                breaches.push(Breach { resource, value, limit });
+            }
+        }
+    };
+
+    check(Resource::Cpu, Some(process.cpu_usage), config.max_cpu_percent);
+    check(
+        Resource::Rss,
+        Some(process.memory_usage as f64 / (1024.0 * 1024.0)),
+        config.max_rss_mb.map(|mb| mb as f64),
+    );
+    // Only read /proc for limits that are actually configured
+    if config.max_fds.is_some() {
+        check(
+            Resource::Fds,
+            open_fd_count(process.pid).map(|n| n as f64),
+            config.max_fds.map(|n| n as f64),
+        );
+    }
+    if config.max_threads.is_some() {
+        check(
+            Resource::Threads,
+            thread_count(process.pid).map(|n| n as f64),
+            config.max_threads.map(|n| n as f64),
+        );
+    }
+    breaches
+}
+
+/// Samples watched processes and tracks how long each limit has been exceeded
+#[derive(Debug)]
+pub struct ProcessWatch {
+    processes: ProcessManager,
+    watches: Vec<ProcessWatchConfig>,
+    /// Consecutive breaching samples per (watch, pid, resource)
+    streaks: HashMap<(usize, u32, Resource), u32>,
+}
+
+impl ProcessWatch {
+    pub fn new(watches: Vec<ProcessWatchConfig>) -> Result<Self> {
+        if let Some(watch) = watches.iter().find(|w| w.name.is_none() && w.pid.is_none()) {
+            return Err(BustcallError::ConfigError(format!(
+                "Process watch '{}' needs a name or pid",
+                watch.label()
+            )));
+        }
+
+        Ok(Self {
+            processes: ProcessManager::new(),
+            watches,
+            streaks: HashMap::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    /// Take one sample; returns alarms whose streak just reached the
+    /// configured number of samples
+    pub fn sample(&mut self) -> Result<Vec<ProcessAlarm>> {
+        let mut alarms = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index, watch) in self.watches.iter().enumerate() {
+            for process in self.processes.list_processes(&watch.filter())? {
+                for breach in breaches(watch, &process) {
+                    let key = (index, process.pid, breach.resource);
+                    seen.insert(key);
+
+                    let streak = self.streaks.entry(key).or_insert(0);
+                    *streak += 1;
+                    if *streak == watch.consecutive_samples.max(1) {
+                        log::warn!(
+                            "📈 {} (pid {}) over {} limit for {} samples",
+                            process.name,
+                            process.pid,
+                            breach.resource.as_str(),
+                            streak
+                        );
+                        alarms.push(ProcessAlarm {
+                            pid: process.pid,
+                            name: process.name.clone(),
+                            breach,
+                            samples: *streak,
+                            level: watch.level,
+                            target: watch.target.clone(),
+                            bust: watch.bust.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Anything not breaching this sample (or gone) starts over
+        self.streaks.retain(|key, _| seen.contains(key));
+        Ok(alarms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(cpu: f64, rss_mb: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid: 42,
+            name: "node".to_string(),
+            status: "Run".to_string(),
+            cpu_usage: cpu,
+            memory_usage: rss_mb * 1024 * 1024,
+            parent_pid: None,
+            session_id: None,
+            pgid: None,
+            container: None,
+            cmdline: Vec::new(),
+            start_time: 0,
+            open_files: None,
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_breaches_only_configured_limits() {
+        let config: ProcessWatchConfig = toml::from_str(
+            r#"
+            name = "node"
+            max_rss_mb = 512
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.consecutive_samples, 3);
+
+        assert!(breaches(&config, &process(99.0, 256)).is_empty());
+        let over = breaches(&config, &process(99.0, 1024));
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].resource, Resource::Rss);
+    }
+
+    #[test]
+    fn test_alarm_after_consecutive_samples() {
+        let mut watch = ProcessWatch::new(vec![ProcessWatchConfig {
+            name: None,
+            pid: Some(std::process::id()),
+            max_cpu_percent: None,
+            max_rss_mb: Some(0),
+            max_fds: None,
+            max_threads: None,
+            consecutive_samples: 2,
+            level: NotificationLevel::Warning,
+            target: Some("node".to_string()),
+            bust: None,
+        }])
+        .unwrap();
+
+        assert!(watch.sample().unwrap().is_empty());
+        let alarms = watch.sample().unwrap();
+        assert_eq!(alarms.len(), 1);
+        assert_eq!(alarms[0].notification().target.as_deref(), Some("node"));
+        assert!(watch.sample().unwrap().is_empty());
+    }
+}
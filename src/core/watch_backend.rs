@@ -0,0 +1,378 @@
+//! How the file watcher learns of changes
+//!
+//! inotify is cheap and immediate, but takes one watch per directory from a
+//! per-user limit, and sees nothing on network filesystems or bind mounts
+//! written from outside a container. A target picks its backend:
+//!
+//! ```toml
+//! [target.web]
+//! path = "web"
+//! watch_backend = "poll"   # auto, inotify, poll or fanotify
+//! ```
+//!
+//! `auto` polls paths on network filesystems and trees too big for the
+//! inotify watches left, and uses inotify (the platform's native events
+//! elsewhere) otherwise. `inotify` falls back to polling when the watches
+//! run out. `fanotify` marks the whole mount, so it needs no watch per
+//! directory, but only reports writes, not creations and removals, and needs
+//! CAP_SYS_ADMIN; without it `auto` is used instead.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{Config, EventHandler, PollWatcher, RecommendedWatcher, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::core::preflight::INOTIFY_WATCHES_PATH;
+
+/// Share of the inotify watches in use above which a warning is logged
+const INOTIFY_WARN_RATIO: f64 = 0.9;
+
+/// Filesystem types polled by `auto`: their changes are made elsewhere
+const NETWORK_FILESYSTEMS: [&str; 13] = [
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "afs", "ceph", "glusterfs", "fuse.sshfs", "fuse.grpcfuse", "vboxsf", "prl_fs",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchBackend {
+    #[default]
+    Auto,
+    /// inotify on Linux, the native events of other platforms
+    Inotify,
+    Poll,
+    Fanotify,
+}
+
+impl WatchBackend {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatchBackend::Auto => "auto",
+            WatchBackend::Inotify => "inotify",
+            WatchBackend::Poll => "poll",
+            WatchBackend::Fanotify => "fanotify",
+        }
+    }
+}
+
+/// The user's inotify watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InotifyUsage {
+    pub limit: u64,
+    /// Held by the processes of this user that can be inspected
+    pub in_use: u64,
+}
+
+impl InotifyUsage {
+    pub fn available(&self) -> u64 {
+        self.limit.saturating_sub(self.in_use)
+    }
+}
+
+/// `None` off Linux or without `/proc`
+pub fn inotify_usage() -> Option<InotifyUsage> {
+    let limit = fs::read_to_string(INOTIFY_WATCHES_PATH).ok()?.trim().parse().ok()?;
+    Some(InotifyUsage {
+        limit,
+        in_use: inotify_watches_in_use(),
+    })
+}
+
+/// Watches of every inotify instance in the processes we can read
+fn inotify_watches_in_use() -> u64 {
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return 0;
+    };
+    let mut watches = 0;
+    for process in processes.flatten() {
+        let Ok(fds) = fs::read_dir(process.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if fs::read_link(fd.path()).is_ok_and(|target| target.as_os_str() == "anon_inode:inotify") {
+                let info = fs::read_to_string(process.path().join("fdinfo").join(fd.file_name())).unwrap_or_default();
+                watches += info.lines().filter(|line| line.starts_with("inotify wd:")).count() as u64;
+            }
+        }
+    }
+    watches
+}
+
+/// inotify watches a recursive watch of `root` takes, one per directory,
+/// counted up to `cap`
+pub fn watches_needed(root: &Path, cap: u64) -> u64 {
+    let mut count = 0;
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        count += 1;
+        if count >= cap {
+            break;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            // Symlinks are not followed by the watcher either
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                pending.push(entry.path());
+            }
+        }
+    }
+    count
+}
+
+/// Type of the network filesystem holding `path`, if it is on one
+pub fn network_filesystem(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    let mounts = fs::read_to_string("/proc/mounts").ok()?;
+    let (_, fs_type) = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (mount_point, fs_type) = (fields.nth(1)?, fields.next()?);
+            // Spaces in mount points are octal escaped
+            let mount_point = PathBuf::from(mount_point.replace("\\040", " "));
+            path.starts_with(&mount_point).then_some((mount_point, fs_type))
+        })
+        .max_by_key(|(mount_point, _)| mount_point.components().count())?;
+    NETWORK_FILESYSTEMS.contains(&fs_type).then(|| fs_type.to_string())
+}
+
+/// The backend `root` is watched with when `requested`: never `Auto`, and
+/// only one that can work here
+pub fn resolve(requested: WatchBackend, root: &Path) -> WatchBackend {
+    match requested {
+        WatchBackend::Poll => WatchBackend::Poll,
+        WatchBackend::Fanotify if fanotify_available() => WatchBackend::Fanotify,
+        WatchBackend::Fanotify => {
+            log::warn!("⚠️ fanotify unavailable for {} (it needs CAP_SYS_ADMIN on Linux), using auto", root.display());
+            resolve(WatchBackend::Auto, root)
+        }
+        WatchBackend::Auto => match network_filesystem(root) {
+            Some(fs_type) => {
+                log::info!("🔁 Polling {}: on {}, which delivers no change events", root.display(), fs_type);
+                WatchBackend::Poll
+            }
+            None => resolve(WatchBackend::Inotify, root),
+        },
+        WatchBackend::Inotify => {
+            let Some(usage) = inotify_usage() else {
+                return WatchBackend::Inotify;
+            };
+            let needed = watches_needed(root, usage.available() + 1);
+            if needed > usage.available() {
+                log::warn!(
+                    "⚠️ Polling {}: it needs about {} inotify watches and only {} of {} are left; raise them with `sysctl fs.inotify.max_user_watches=<n>`",
+                    root.display(),
+                    needed,
+                    usage.available(),
+                    usage.limit
+                );
+                return WatchBackend::Poll;
+            }
+            if (usage.in_use + needed) as f64 > usage.limit as f64 * INOTIFY_WARN_RATIO {
+                log::warn!(
+                    "⚠️ inotify watches near exhaustion: watching {} takes {} more, {} of {} in use; raise fs.inotify.max_user_watches or use watch_backend = \"poll\" or \"fanotify\"",
+                    root.display(),
+                    needed,
+                    usage.in_use,
+                    usage.limit
+                );
+            }
+            WatchBackend::Inotify
+        }
+    }
+}
+
+/// A watcher of `backend`, which `resolve` returned
+pub fn open_watcher<F: EventHandler>(backend: WatchBackend, handler: F, poll_interval: Duration) -> notify::Result<Box<dyn Watcher + Send>> {
+    Ok(match backend {
+        WatchBackend::Poll => Box::new(PollWatcher::new(handler, Config::default().with_poll_interval(poll_interval))?),
+        #[cfg(target_os = "linux")]
+        WatchBackend::Fanotify => Box::new(fanotify::FanotifyWatcher::new(handler, Config::default())?),
+        _ => Box::new(RecommendedWatcher::new(handler, Config::default())?),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn fanotify_available() -> bool {
+    fanotify::FanotifyWatcher::available()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn fanotify_available() -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+mod fanotify {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread::JoinHandle;
+
+    use notify::event::{DataChange, ModifyKind};
+    use notify::{Config, Event, EventHandler, EventKind, RecursiveMode, Watcher, WatcherKind};
+
+    const EVENT_MASK: u64 = libc::FAN_MODIFY | libc::FAN_CLOSE_WRITE;
+
+    /// Reports writes below the watched paths from a mark on their mounts
+    #[derive(Debug)]
+    pub struct FanotifyWatcher {
+        fd: libc::c_int,
+        roots: Arc<Mutex<Vec<PathBuf>>>,
+        stop: Arc<AtomicBool>,
+        reader: Option<JoinHandle<()>>,
+    }
+
+    fn init() -> libc::c_int {
+        let flags = libc::FAN_CLASS_NOTIF | libc::FAN_CLOEXEC | libc::FAN_NONBLOCK;
+        let event_flags = (libc::O_RDONLY | libc::O_LARGEFILE | libc::O_CLOEXEC) as libc::c_uint;
+        // SAFETY: plain syscall, the returned descriptor is owned by the caller
+        unsafe { libc::fanotify_init(flags, event_flags) }
+    }
+
+    impl FanotifyWatcher {
+        pub fn available() -> bool {
+            let fd = init();
+            if fd < 0 {
+                return false;
+            }
+            // SAFETY: `fd` was just opened and is not used elsewhere
+            unsafe { libc::close(fd) };
+            true
+        }
+    }
+
+    impl Watcher for FanotifyWatcher {
+        fn new<F: EventHandler>(mut handler: F, _config: Config) -> notify::Result<Self> {
+            let fd = init();
+            if fd < 0 {
+                return Err(notify::Error::io(std::io::Error::last_os_error()));
+            }
+            let roots = Arc::new(Mutex::new(Vec::new()));
+            let stop = Arc::new(AtomicBool::new(false));
+            let reader = {
+                let (roots, stop) = (roots.clone(), stop.clone());
+                std::thread::spawn(move || read_events(fd, &roots, &stop, &mut handler))
+            };
+            Ok(Self {
+                fd,
+                roots,
+                stop,
+                reader: Some(reader),
+            })
+        }
+
+        fn watch(&mut self, path: &Path, _mode: RecursiveMode) -> notify::Result<()> {
+            let c_path = CString::new(path.as_os_str().as_bytes())
+                .map_err(|_| notify::Error::generic("path contains a NUL byte").add_path(path.to_path_buf()))?;
+            let flags = libc::FAN_MARK_ADD | libc::FAN_MARK_MOUNT;
+            // SAFETY: `c_path` is a valid C string for the duration of the call
+            if unsafe { libc::fanotify_mark(self.fd, flags, EVENT_MASK, libc::AT_FDCWD, c_path.as_ptr()) } < 0 {
+                return Err(notify::Error::io(std::io::Error::last_os_error()).add_path(path.to_path_buf()));
+            }
+            self.roots.lock().unwrap().push(path.to_path_buf());
+            Ok(())
+        }
+
+        /// The mount stays marked; its events outside the remaining paths
+        /// are dropped
+        fn unwatch(&mut self, path: &Path) -> notify::Result<()> {
+            let mut roots = self.roots.lock().unwrap();
+            let watched = roots.len();
+            roots.retain(|root| root != path);
+            if roots.len() == watched {
+                return Err(notify::Error::watch_not_found().add_path(path.to_path_buf()));
+            }
+            Ok(())
+        }
+
+        fn kind() -> WatcherKind {
+            // notify has no kind of its own for fanotify
+            WatcherKind::NullWatcher
+        }
+    }
+
+    impl Drop for FanotifyWatcher {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+            if let Some(reader) = self.reader.take() {
+                let _ = reader.join();
+            }
+            // SAFETY: the reader, the only other user of `fd`, has stopped
+            unsafe { libc::close(self.fd) };
+        }
+    }
+
+    fn read_events<F: EventHandler>(fd: libc::c_int, roots: &Mutex<Vec<PathBuf>>, stop: &AtomicBool, handler: &mut F) {
+        let header = std::mem::size_of::<libc::fanotify_event_metadata>();
+        let mut buffer = vec![0u8; 64 * 1024];
+        while !stop.load(Ordering::Relaxed) {
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: one valid pollfd; wakes every 500ms to notice a stop
+            if unsafe { libc::poll(&mut pollfd, 1, 500) } <= 0 {
+                continue;
+            }
+            // SAFETY: reads at most `buffer.len()` bytes into `buffer`
+            let read = unsafe { libc::read(fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+            if read <= 0 {
+                continue;
+            }
+
+            let mut offset = 0;
+            while offset + header <= read as usize {
+                // SAFETY: a whole header lies at `offset`; it may be unaligned
+                let metadata: libc::fanotify_event_metadata =
+                    unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr().cast()) };
+                if metadata.event_len < header as u32 {
+                    break;
+                }
+                offset += metadata.event_len as usize;
+                if metadata.fd < 0 {
+                    if metadata.mask & libc::FAN_Q_OVERFLOW != 0 {
+                        handler.handle_event(Err(notify::Error::generic("fanotify queue overflowed, events were lost")));
+                    }
+                    continue;
+                }
+
+                let path = std::fs::read_link(format!("/proc/self/fd/{}", metadata.fd));
+                // SAFETY: the event's descriptor is ours to close
+                unsafe { libc::close(metadata.fd) };
+                let Ok(path) = path else {
+                    continue;
+                };
+                if roots.lock().unwrap().iter().any(|root| path.starts_with(root)) {
+                    let event = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Any))).add_path(path);
+                    handler.handle_event(Ok(event));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_is_kept_and_trees_are_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a/b")).unwrap();
+        fs::create_dir_all(dir.path().join("c")).unwrap();
+        fs::write(dir.path().join("a/file.txt"), "x").unwrap();
+
+        assert_eq!(watches_needed(dir.path(), 100), 4);
+        assert_eq!(watches_needed(dir.path(), 2), 2);
+        assert_eq!(resolve(WatchBackend::Poll, dir.path()), WatchBackend::Poll);
+        assert_ne!(resolve(WatchBackend::Auto, dir.path()), WatchBackend::Auto);
+        assert_eq!(serde_json::from_str::<WatchBackend>("\"fanotify\"").unwrap(), WatchBackend::Fanotify);
+    }
+}
@@ -0,0 +1,175 @@
+//! Paths the file watcher does not react to
+//!
+//! Watching a target recursively also watches its `node_modules`, `.git`
+//! and editor temp files. A target's `ignore` patterns, in `.gitignore`
+//! syntax, drop their events before severity is assessed:
+//!
+//! ```toml
+//! [target.web]
+//! path = "web"
+//! ignore = ["**/.git/**", "**/*.tmp"]
+//! ignore_files = true
+//! ```
+//!
+//! With `ignore_files` the `.gitignore` and `.bustcallignore` files of the
+//! target's directories are honoured too, the deepest one deciding as in
+//! git. They are read on first use and again after they change. `ignore`
+//! patterns apply whatever the files say.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use globset::GlobBuilder;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+use crate::core::config::TargetConfig;
+use crate::utils::error::{BustcallError, Result};
+
+/// Honoured with `ignore_files`, later ones overriding earlier ones
+pub const IGNORE_FILES: [&str; 2] = [".gitignore", ".bustcallignore"];
+
+/// A target's compiled `ignore` patterns and ignore files
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    target: String,
+    root: PathBuf,
+    patterns: Gitignore,
+    ignore_files: bool,
+    /// Ignore files by directory, none for directories without
+    dirs: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl IgnoreRules {
+    pub fn new(target: &str, root: &Path, patterns: &[String], ignore_files: bool) -> Result<Self> {
+        let invalid = |e: &dyn std::fmt::Display| BustcallError::ConfigError(format!("Invalid ignore pattern for {}: {}", target, e));
+        let mut builder = GitignoreBuilder::new(root);
+        for pattern in patterns {
+            // Gitignore takes an unclosed `[` literally, which is rarely meant
+            GlobBuilder::new(pattern).literal_separator(true).backslash_escape(true).build().map_err(|e| invalid(&e))?;
+            builder.add_line(None, pattern).map_err(|e| invalid(&e))?;
+        }
+
+        Ok(Self {
+            target: target.to_string(),
+            root: root.to_path_buf(),
+            patterns: builder.build().map_err(|e| invalid(&e))?,
+            ignore_files,
+            dirs: HashMap::new(),
+        })
+    }
+
+    /// Rules of every target with a path and `ignore` patterns or
+    /// `ignore_files`
+    pub fn from_targets(targets: &HashMap<String, TargetConfig>) -> Result<Vec<Self>> {
+        let mut all = Vec::new();
+        for (name, target) in targets {
+            if !target.enabled || (target.ignore.is_empty() && !target.ignore_files) {
+                continue;
+            }
+            if let Some(root) = target.root() {
+                all.push(Self::new(name, &root, &target.ignore, target.ignore_files)?);
+            }
+        }
+        all.sort_by(|a, b| a.target.cmp(&b.target));
+        Ok(all)
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Whether a change to `path` is ignored; paths outside the target's
+    /// path are not
+    pub fn is_ignored(&mut self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let is_dir = path.is_dir();
+        if self.patterns.matched_path_or_any_parents(path, is_dir).is_ignore() {
+            return true;
+        }
+        if !self.ignore_files {
+            return false;
+        }
+
+        let is_ignore_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| IGNORE_FILES.contains(&name));
+        if let (true, Some(dir)) = (is_ignore_file, path.parent()) {
+            self.dirs.remove(dir);
+        }
+
+        let mut dir = self.root.clone();
+        let mut dirs = vec![dir.clone()];
+        for component in relative.parent().into_iter().flat_map(Path::components) {
+            dir.push(component);
+            dirs.push(dir.clone());
+        }
+        let mut ignored = false;
+        for dir in dirs {
+            let matcher = self.dirs.entry(dir.clone()).or_insert_with(|| read_ignore_files(&dir));
+            match matcher.as_ref().map(|matcher| matcher.matched_path_or_any_parents(path, is_dir)) {
+                Some(Match::Ignore(_)) => ignored = true,
+                Some(Match::Whitelist(_)) => ignored = false,
+                _ => {}
+            }
+        }
+        ignored
+    }
+}
+
+/// The ignore files of `dir` as one matcher, none without any
+fn read_ignore_files(dir: &Path) -> Option<Gitignore> {
+    let files: Vec<PathBuf> = IGNORE_FILES.iter().map(|name| dir.join(name)).filter(|file| file.is_file()).collect();
+    if files.is_empty() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    for file in &files {
+        if let Some(e) = builder.add(file) {
+            log::warn!("Skipping invalid lines of {}: {}", file.display(), e);
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| log::warn!("Ignore files of {} not used: {}", dir.display(), e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_patterns_and_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/gen")).unwrap();
+        fs::write(root.join(".gitignore"), "dist/\n*.log\n").unwrap();
+        fs::write(root.join("src/.bustcallignore"), "gen/\n!keep.log\n").unwrap();
+
+        let patterns = vec!["**/.git/**".to_string(), "**/*.tmp".to_string()];
+        let mut rules = IgnoreRules::new("web", root, &patterns, true).unwrap();
+        let mut ignored = |path: &str| rules.is_ignored(&root.join(path));
+        assert!(ignored(".git/index"));
+        assert!(ignored("src/index.ts.tmp"));
+        assert!(ignored("dist/app.js"));
+        assert!(ignored("build.log"));
+        assert!(ignored("src/gen/types.ts"));
+        assert!(!ignored("src/keep.log"));
+        assert!(!ignored("src/index.ts"));
+
+        // Ignore files are read again once changed
+        fs::write(root.join(".gitignore"), "dist/\n").unwrap();
+        assert!(!ignored(".gitignore"));
+        assert!(!ignored("build.log"));
+
+        let mut without_files = IgnoreRules::new("web", root, &patterns, false).unwrap();
+        assert!(!without_files.is_ignored(&root.join("dist/app.js")));
+        assert!(!without_files.is_ignored(Path::new("/elsewhere/x.tmp")));
+        assert!(IgnoreRules::new("web", root, &["a[".to_string()], false).is_err());
+    }
+}
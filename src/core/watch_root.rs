@@ -0,0 +1,135 @@
+//! Watch paths that disappear and come back
+//!
+//! Recreating a virtualenv or reinstalling `node_modules` deletes the
+//! watched directory itself, and its watch goes with it: no event comes
+//! from the new directory. The watcher checks its watch paths every tick. A
+//! missing one is reported with a Warning and attached again once it
+//! exists, retrying with backoff. One that comes back with other files or
+//! sizes than it was watched with gets its target a Critical bust, as
+//! nothing cached from the old tree can be trusted.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::core::watch_backend::WatchBackend;
+
+/// First and longest wait between attempts to attach a lost watch path
+pub const REATTACH_BACKOFF: (Duration, Duration) = (Duration::from_secs(1), Duration::from_secs(60));
+
+/// Files a fingerprint covers at most; larger trees are compared by their
+/// first files
+const MAX_FINGERPRINT_FILES: usize = 100_000;
+
+/// Digest of the files below `root` with their sizes, `None` when `root`
+/// cannot be read. Modification times are left out, so a tree recreated
+/// with the same files fingerprints the same.
+pub fn tree_fingerprint(root: &Path) -> Option<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![fs::read_dir(root).ok()?];
+    while let Some(entries) = pending.pop() {
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if let Ok(entries) = fs::read_dir(entry.path()) {
+                    pending.push(entries);
+                }
+            } else if files.len() < MAX_FINGERPRINT_FILES {
+                let size = entry.metadata().map_or(0, |metadata| metadata.len());
+                let path = entry.path();
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.push(format!("{}\0{}", relative.to_string_lossy(), size));
+            }
+        }
+    }
+
+    files.sort();
+    let mut hasher = blake3::Hasher::new();
+    for file in &files {
+        hasher.update(file.as_bytes());
+        hasher.update(b"\n");
+    }
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+/// A watch path that is gone, until attached again
+#[derive(Debug, Clone)]
+pub struct LostRoot {
+    /// Backend the path was configured with
+    pub requested: WatchBackend,
+    /// Fingerprint of the tree when it was last attached
+    pub fingerprint: Option<String>,
+    pub since: Instant,
+    pub attempts: u32,
+    next_attempt: Instant,
+}
+
+impl LostRoot {
+    pub fn new(requested: WatchBackend, fingerprint: Option<String>, now: Instant) -> Self {
+        Self {
+            requested,
+            fingerprint,
+            since: now,
+            attempts: 0,
+            next_attempt: now + REATTACH_BACKOFF.0,
+        }
+    }
+
+    pub fn due(&self, now: Instant) -> bool {
+        now >= self.next_attempt
+    }
+
+    /// Wait before the next attempt after one failed
+    pub fn retry_later(&mut self, now: Instant) {
+        self.attempts += 1;
+        let (min, max) = REATTACH_BACKOFF;
+        let backoff = min.saturating_mul(2u32.saturating_pow(self.attempts)).min(max);
+        self.next_attempt = now + backoff;
+    }
+
+    /// Whether the tree attached again differs from the lost one; an
+    /// unknown tree does
+    pub fn replaced_by(&self, fingerprint: Option<&str>) -> bool {
+        match (&self.fingerprint, fingerprint) {
+            (Some(lost), Some(found)) => lost != found,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprints_and_backoff() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("lib/site-packages")).unwrap();
+        fs::write(dir.path().join("lib/site-packages/six.py"), "x = 1").unwrap();
+        let before = tree_fingerprint(dir.path());
+        assert!(before.is_some());
+
+        // Rewritten with the same files and sizes: the same tree
+        fs::write(dir.path().join("lib/site-packages/six.py"), "y = 2").unwrap();
+        assert_eq!(tree_fingerprint(dir.path()), before);
+        fs::write(dir.path().join("lib/site-packages/six.py"), "y = 22").unwrap();
+        assert_ne!(tree_fingerprint(dir.path()), before);
+        assert_eq!(tree_fingerprint(&dir.path().join("missing")), None);
+
+        let now = Instant::now();
+        let mut lost = LostRoot::new(WatchBackend::Auto, before.clone(), now);
+        assert!(!lost.due(now));
+        assert!(lost.due(now + REATTACH_BACKOFF.0));
+        for _ in 0..10 {
+            lost.retry_later(now);
+        }
+        assert!(!lost.due(now + REATTACH_BACKOFF.1 - Duration::from_secs(1)));
+        assert!(lost.due(now + REATTACH_BACKOFF.1));
+
+        assert!(!lost.replaced_by(before.as_deref()));
+        assert!(lost.replaced_by(Some("other")));
+        assert!(LostRoot::new(WatchBackend::Auto, None, now).replaced_by(before.as_deref()));
+    }
+}
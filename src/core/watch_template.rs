@@ -0,0 +1,107 @@
+//! Targets templated over the packages of a monorepo
+//!
+//! A target whose path is a glob stands for every directory it matches:
+//!
+//! ```toml
+//! [target.node]
+//! path = "packages/*/node_modules"
+//! ```
+//!
+//! Each match is a target of its own, with the template's settings and a
+//! name derived from the match: `node:packages/foo` for
+//! `packages/foo/node_modules`, i.e. the part of the match from the
+//! directory holding the first wildcard to the last wildcard. The watcher
+//! expands templates again every `template_interval`, watching packages
+//! that appeared and dropping ones that were removed.
+
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+use crate::core::config::TargetConfig;
+use crate::utils::error::{BustcallError, Result};
+
+fn is_glob(component: &Component) -> bool {
+    component.as_os_str().to_string_lossy().contains(['*', '?', '['])
+}
+
+/// Whether `path` is a template rather than a directory
+pub fn is_template(path: &Path) -> bool {
+    path.components().any(|component| is_glob(&component))
+}
+
+/// Name of the target `matched` by the template `target` with path
+/// `pattern`
+pub fn derived_name(target: &str, pattern: &Path, matched: &Path) -> String {
+    let globs: Vec<usize> = pattern
+        .components()
+        .enumerate()
+        .filter(|(_, component)| is_glob(component))
+        .map(|(i, _)| i)
+        .collect();
+    let (Some(first), Some(last)) = (globs.first(), globs.last()) else {
+        return target.to_string();
+    };
+    let suffix = pattern.components().count() - 1 - last;
+    let components: Vec<String> = matched
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let end = components.len().saturating_sub(suffix);
+    let start = first.saturating_sub(1).min(end);
+    format!("{}:{}", target, components[start..end].join("/"))
+}
+
+/// `targets` with every template replaced by the targets of the
+/// directories it matches now
+pub fn expand_targets(targets: &HashMap<String, TargetConfig>) -> Result<HashMap<String, TargetConfig>> {
+    let mut expanded = HashMap::new();
+    for (name, target) in targets {
+        let Some(pattern) = target.root().filter(|root| is_template(root)) else {
+            expanded.insert(name.clone(), target.clone());
+            continue;
+        };
+        let matches = glob::glob(&pattern.to_string_lossy())
+            .map_err(|e| BustcallError::ConfigError(format!("Invalid path template for {}: {}", name, e)))?;
+        for matched in matches.flatten().filter(|matched| matched.is_dir()) {
+            let mut derived = target.clone();
+            derived.resolved_path = Some(matched.clone());
+            expanded.insert(derived_name(name, &pattern, &matched), derived);
+        }
+    }
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_templates_expand_to_derived_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        for package in ["packages/foo/node_modules", "packages/bar/node_modules", "packages/baz"] {
+            fs::create_dir_all(dir.path().join(package)).unwrap();
+        }
+        let pattern = dir.path().join("packages/*/node_modules");
+        assert!(is_template(&pattern));
+        assert!(!is_template(dir.path()));
+        assert_eq!(
+            derived_name("node", Path::new("/repo/packages/**/node_modules"), Path::new("/repo/packages/a/b/node_modules")),
+            "node:packages/a/b"
+        );
+
+        let targets = HashMap::from([
+            ("node".to_string(), TargetConfig { resolved_path: Some(pattern), debounce_ms: Some(50), ..Default::default() }),
+            ("web".to_string(), TargetConfig { path: Some("web".to_string()), ..Default::default() }),
+        ]);
+        let expanded = expand_targets(&targets).unwrap();
+        let mut names: Vec<&String> = expanded.keys().collect();
+        names.sort();
+        assert_eq!(names, ["node:packages/bar", "node:packages/foo", "web"]);
+        assert_eq!(expanded["node:packages/foo"].root(), Some(dir.path().join("packages/foo/node_modules")));
+        assert_eq!(expanded["node:packages/foo"].debounce_ms, Some(50));
+
+        fs::remove_dir_all(dir.path().join("packages/bar")).unwrap();
+        assert!(!expand_targets(&targets).unwrap().contains_key("node:packages/bar"));
+    }
+}
@@ -4,6 +4,8 @@
 //! Unix-compliant process hierarchy with Byzantine fault tolerance
 //! Implements proof-of-work consensus for distributed task execution
 
+use crate::core::priority::ResourcePolicy;
+use crate::core::sandbox::SandboxProfile;
 use crate::dimensional_cache::{DimensionalCacheManager, CacheBustSeverity};
 use std::collections::{HashMap, BTreeSet};
 use std::process::{Command, Child, Stdio};
@@ -121,6 +123,31 @@ pub struct ResourceRequirements {
     pub max_cpu_percent: f32,
     pub max_disk_io_mb: u64,
     pub required_capabilities: Vec<String>,
+    /// Confinement applied to the spawned child; none when unset
+    #[serde(default)]
+    pub sandbox: Option<SandboxProfile>,
+}
+
+impl ResourceRequirements {
+    /// Delegated tasks exceeding their CPU budget are reniced like
+    /// monitored runtimes over their `cpu_quota`
+    pub fn resource_policy(&self) -> ResourcePolicy {
+        ResourcePolicy {
+            cpu_quota: Some(self.max_cpu_percent as f64),
+            nice: Some(10),
+            ..ResourcePolicy::default()
+        }
+    }
+
+    /// The sandbox to spawn under, with the address space capped at
+    /// `max_memory_mb` unless the profile sets its own limit
+    pub fn sandbox_profile(&self) -> Option<SandboxProfile> {
+        let mut profile = self.sandbox.clone()?;
+        if profile.max_address_space_mb.is_none() && self.max_memory_mb > 0 {
+            profile.max_address_space_mb = Some(self.max_memory_mb);
+        }
+        Some(profile)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -426,8 +453,12 @@ impl ProcessDelegationTree {
         // Generate unique delegate node ID
         let delegate_node_id = format!("delegate-{}", uuid::Uuid::new_v4());
         
-        // Prepare Unix process command
+        // Prepare Unix process command; the sandbox goes first since its
+        // environment allowlist would clear the spec's variables
         let mut command = Command::new(&request.delegation_spec.command_spec.executable_path);
+        if let Some(profile) = request.delegation_spec.resource_requirements.sandbox_profile() {
+            profile.apply(&mut command).context("Failed to apply sandbox profile")?;
+        }
         command.args(&request.delegation_spec.command_spec.arguments)
                .envs(&request.delegation_spec.command_spec.environment_vars)
                .current_dir(&request.delegation_spec.command_spec.working_directory);
@@ -490,7 +521,7 @@ impl ProcessDelegationTree {
         self.active_processes.lock().unwrap().insert(delegate_node_id.clone(), child);
         
         // Trigger cache awareness
-        self.cache_manager.bust_cache(&delegate_node_id, CacheBustSeverity::Medium)?;
+        self.cache_manager.bust_cache_async(&delegate_node_id, CacheBustSeverity::Medium, "delegate spawned").await?;
         
         // Generate proof-of-work if required
         let proof_of_work = if request.delegation_spec.fault_tolerance_level > 5 {
@@ -1,11 +1,22 @@
 // src/dimensional_cache.rs
-use std::collections::{HashMap, BinaryHeap};
+use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
-use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+
+use crate::core::audit::{AuditEvent, AuditLog, BustTrigger};
+use crate::core::bust_executor::{dir_size, BustAction, BustExecutors, CompilerCache};
+use crate::core::bust_hook::{BustHooks, HookOutcome, HookStage};
+use crate::core::cache_bus::{open_bus, BusKind, CacheBus, LocalBus};
+use crate::core::cache_tier::{CacheTier, CacheTiers};
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
+use crate::core::process::ProcessIdentity;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEvicon {
@@ -13,9 +24,56 @@ pub struct CacheEvicon {
     pub model_binding: String,
     pub eviction_strategy: EvictionStrategy,
     pub last_access: u64,
+    /// When the entry was added, for FIFO eviction
+    #[serde(default)]
+    pub inserted_at: u64,
+    /// Size of the file or artifact on disk, 0 when unknown
+    #[serde(default)]
+    pub size_bytes: u64,
     pub access_frequency: u32,
+    /// 100 when the backing file matched `content_hash` at the last check,
+    /// 0 when it did not, `INTEGRITY_UNVERIFIED` without a hash
     pub integrity_score: u8,
     pub dependency_depth: u8,
+    /// BLAKE3 hash of the backing file when registered, hex encoded
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+const MIB: f32 = 1024.0 * 1024.0;
+
+/// Integrity score of entries that have no file to hash
+pub const INTEGRITY_UNVERIFIED: u8 = 50;
+
+/// BLAKE3 hash of a file, hex encoded; `None` when `path` is not a readable
+/// file
+pub(crate) fn hash_file(path: &str) -> Option<String> {
+    let path = std::path::Path::new(path);
+    if !path.is_file() {
+        return None;
+    }
+    let mut hasher = blake3::Hasher::new();
+    let mut file = std::fs::File::open(path).ok()?;
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+/// Bytes `path` takes on disk, a directory's files summed; `None` when it
+/// does not exist
+fn disk_size(path: &str) -> Option<u64> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    Some(if metadata.is_dir() { dir_size(Path::new(path)) } else { metadata.len() })
+}
+
+/// Outcome of `verify`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IntegrityReport {
+    /// Entries with a hash that were re-hashed
+    pub checked: usize,
+    /// Entries whose file changed or disappeared, by cache ID
+    pub mismatched: Vec<String>,
+    /// Whether the mismatches busted the target
+    pub busted: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +85,118 @@ pub enum EvictionStrategy {
     ModelAware(ModelWeights),  // OBINexus model-specific prioritization
 }
 
+impl EvictionStrategy {
+    /// Name evictions are counted under in `metrics`
+    pub fn label(&self) -> &'static str {
+        match self {
+            EvictionStrategy::LRU => "lru",
+            EvictionStrategy::MRU => "mru",
+            EvictionStrategy::LFU => "lfu",
+            EvictionStrategy::FIFO => "fifo",
+            EvictionStrategy::ModelAware(_) => "model_aware",
+        }
+    }
+}
+
+/// Components of an entry's eviction score; the lowest scores go first
+/// under model-aware eviction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvictionScore {
+    /// 0.3 per access
+    pub access: f32,
+    /// 0.2 per integrity point
+    pub integrity: f32,
+    /// Dependency depth times `dependency_impact`
+    pub dependency: f32,
+    /// `language_priority`
+    pub language: f32,
+    /// Minus ln(1 + size in MiB), so large entries go first
+    #[serde(default)]
+    pub size: f32,
+    /// Multiplies the sum: 2 on the critical path, 1 elsewhere
+    pub critical_path: f32,
+    pub total: f32,
+}
+
+/// A target's state moving, for `on_state_change` listeners
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateChange {
+    pub target: String,
+    pub from: CacheState,
+    pub to: CacheState,
+    /// Why the target was busted, when a bust made it Stale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<BustReason>,
+}
+
+/// Handle of a registered listener, for `remove_listener`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+type EvictListener = Arc<dyn Fn(&CacheEvicon) + Send + Sync>;
+type StateListener = Arc<dyn Fn(&StateChange) + Send + Sync>;
+
+#[derive(Default)]
+struct CacheListeners {
+    next_id: AtomicU64,
+    evict: RwLock<Vec<(ListenerId, EvictListener)>>,
+    state: RwLock<Vec<(ListenerId, StateListener)>>,
+}
+
+impl CacheListeners {
+    fn add<L: ?Sized>(&self, listeners: &RwLock<Vec<(ListenerId, Arc<L>)>>, listener: Arc<L>) -> ListenerId {
+        let id = ListenerId(self.next_id.fetch_add(1, AtomicOrdering::Relaxed));
+        listeners.write().unwrap().push((id, listener));
+        id
+    }
+    
+    fn remove(&self, id: ListenerId) -> bool {
+        let mut evict = self.evict.write().unwrap();
+        let mut state = self.state.write().unwrap();
+        let before = evict.len() + state.len();
+        evict.retain(|(listener, _)| *listener != id);
+        state.retain(|(listener, _)| *listener != id);
+        evict.len() + state.len() < before
+    }
+    
+    // Listeners are called outside the lock so they may register others
+    fn evicted(&self, evicon: &CacheEvicon) {
+        let listeners: Vec<EvictListener> = self.evict.read().unwrap().iter().map(|(_, listener)| listener.clone()).collect();
+        for listener in listeners {
+            listener(evicon);
+        }
+    }
+    
+    fn state_changed(&self, change: &StateChange) {
+        let listeners: Vec<StateListener> = self.state.read().unwrap().iter().map(|(_, listener)| listener.clone()).collect();
+        for listener in listeners {
+            listener(change);
+        }
+    }
+}
+
+/// Outcome of one bust of a `bust_many` batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchBustResult {
+    pub target: String,
+    pub severity: CacheBustSeverity,
+    /// Requested target whose cascade reached this one
+    pub cascaded_from: Option<String>,
+    pub error: Option<String>,
+    /// Freed on disk by the bust executors and compiler caches
+    #[serde(default)]
+    pub reclaimed_bytes: u64,
+}
+
+/// An entry `cache_evict` would evict, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvictionCandidate {
+    pub cache_id: String,
+    pub target: String,
+    pub reason: String,
+    pub score: EvictionScore,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelWeights {
     pub language_priority: f32,
@@ -35,16 +205,314 @@ pub struct ModelWeights {
     pub critical_path: bool,
 }
 
-#[derive(Debug, Clone)]
+impl Default for ModelWeights {
+    fn default() -> Self {
+        Self {
+            language_priority: 0.5,
+            dependency_impact: 0.5,
+            build_cost: 0.5,
+            critical_path: false,
+        }
+    }
+}
+
+/// Bounds `enforce_limits` keeps the cache within
+#[derive(Debug, Clone, Default)]
+pub struct CacheLimits {
+    pub max_entries: Option<usize>,
+    pub max_bytes: Option<u64>,
+    /// Seconds an entry of the target may go unaccessed before it expires
+    pub ttl_seconds: HashMap<String, u64>,
+    /// Weights of each target for choosing what to evict over the limits;
+    /// targets without any use the defaults
+    pub weights: HashMap<String, ModelWeights>,
+}
+
+/// What one `enforce_limits` run removed
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExpiryReport {
+    /// Entries past their target's TTL
+    pub expired: usize,
+    /// Entries evicted to get within the limits
+    pub evicted: usize,
+    pub reclaimed_bytes: u64,
+}
+
+/// Totals of every `enforce_limits` run outside dry-run mode
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReclaimStats {
+    pub runs: u64,
+    pub expired: u64,
+    pub evicted: u64,
+    pub reclaimed_bytes: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: u64,
+    misses: u64,
+    busts: BTreeMap<String, u64>,
+    evictions: BTreeMap<String, u64>,
+    stale_to_hot: u64,
+    ignored_events: BTreeMap<String, u64>,
+}
+
+/// Format version written by `snapshot`
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Full cache state, from `snapshot`, for bug reports and regression tests
+/// against real-world state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    pub version: u32,
+    pub created_at: u64,
+    pub evicons: BTreeMap<String, CacheEvicon>,
+    pub dimensions: BTreeMap<String, DiramDimension>,
+    pub bindings: BTreeMap<String, ModelBinding>,
+    /// Highest priority first
+    pub rebuild_queue: Vec<PriorityEntry>,
+}
+
+impl CacheSnapshot {
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let snapshot: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(anyhow!(
+                "Snapshot version {} is newer than supported version {}",
+                snapshot.version,
+                SNAPSHOT_VERSION
+            ));
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Snapshot of the cache's counters and size, from `metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheMetrics {
+    /// `touch` calls that found their entry
+    pub hits: u64,
+    pub misses: u64,
+    /// Applied busts by severity label
+    pub busts: BTreeMap<String, u64>,
+    /// Applied evictions by strategy: lru, mru, lfu, fifo, model_aware,
+    /// ttl, limits or manual
+    pub evictions: BTreeMap<String, u64>,
+    /// Busted (Stale) targets whose entries were used again
+    pub stale_to_hot: u64,
+    pub entries: usize,
+    pub bytes: u64,
+    /// Size of each bound target's entries
+    #[serde(default)]
+    pub target_bytes: BTreeMap<String, u64>,
+    /// Bound targets by cache state
+    pub targets: BTreeMap<String, usize>,
+    pub reclaimed_bytes: u64,
+    /// Entries by the tier holding them; `entries` counts those in memory
+    #[serde(default)]
+    pub tier_entries: BTreeMap<String, u64>,
+    /// File events dropped by ignore patterns, ignore files or `ignore`
+    /// severity overrides, by target
+    #[serde(default)]
+    pub ignored_events: BTreeMap<String, u64>,
+}
+
+impl CacheMetrics {
+    /// Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<(String, u64)>| {
+            out.push_str(&format!("# HELP bustcall_cache_{} {}\n# TYPE bustcall_cache_{} {}\n", name, help, name, kind));
+            for (labels, value) in samples {
+                out.push_str(&format!("bustcall_cache_{}{} {}\n", name, labels, value));
+            }
+        };
+        let labelled = |label: &str, values: &BTreeMap<String, u64>| -> Vec<(String, u64)> {
+            values.iter().map(|(key, value)| (format!("{{{}=\"{}\"}}", label, key), *value)).collect()
+        };
+        
+        metric("hits_total", "counter", "Cache entry accesses that found the entry", vec![(String::new(), self.hits)]);
+        metric("misses_total", "counter", "Cache entry accesses for unknown entries", vec![(String::new(), self.misses)]);
+        metric("busts_total", "counter", "Applied cache busts by severity", labelled("severity", &self.busts));
+        metric("evictions_total", "counter", "Applied evictions by strategy", labelled("strategy", &self.evictions));
+        metric("stale_to_hot_total", "counter", "Busted targets whose entries were used again", vec![(String::new(), self.stale_to_hot)]);
+        metric("reclaimed_bytes_total", "counter", "Bytes reclaimed by expiry and limits", vec![(String::new(), self.reclaimed_bytes)]);
+        metric("entries", "gauge", "Cache entries held", vec![(String::new(), self.entries as u64)]);
+        metric("bytes", "gauge", "Size of the cache entries held", vec![(String::new(), self.bytes)]);
+        metric("target_bytes", "gauge", "Size of the cache entries held by target", labelled("target", &self.target_bytes));
+        let targets: BTreeMap<String, u64> = self.targets.iter().map(|(state, count)| (state.clone(), *count as u64)).collect();
+        metric("targets", "gauge", "Bound targets by cache state", labelled("state", &targets));
+        metric("tier_entries", "gauge", "Cache entries by the tier holding them", labelled("tier", &self.tier_entries));
+        metric("ignored_events_total", "counter", "File events ignored by target", labelled("target", &self.ignored_events));
+        out
+    }
+}
+
+/// Background loop of `spawn_expiry` or `spawn_subscriber`; stops when
+/// stopped or dropped
+#[derive(Debug)]
+pub struct BackgroundTask {
+    stop: Option<mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BackgroundTask {
+    /// Call `tick` every `interval` on a thread of its own
+    pub(crate) fn every(interval: Duration, mut tick: impl FnMut() + Send + 'static) -> Self {
+        Self::spawn(move |stopped| {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                tick();
+            }
+        })
+    }
+    
+    /// Run `task` on a thread of its own; it should return once its
+    /// receiver disconnects
+    pub(crate) fn spawn(task: impl FnOnce(mpsc::Receiver<()>) + Send + 'static) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || task(stopped));
+        
+        BackgroundTask {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+    
+    /// Stop the loop and wait for a run in progress to finish
+    pub fn stop(mut self) {
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// How busts are shared with other daemons on the `CacheBus`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusSync {
+    /// Names this daemon in published busts, so it can skip its own
+    pub node_id: String,
+    /// Channel busts are published to and received from
+    pub channel: String,
+    /// Further channels to receive busts from
+    pub subscribe: Vec<String>,
+}
+
+impl Default for BusSync {
+    fn default() -> Self {
+        Self {
+            node_id: format!("{}-{}", crate::core::daemon::hostname(), std::process::id()),
+            channel: "bustcall:cache_bust".to_string(),
+            subscribe: Vec::new(),
+        }
+    }
+}
+
+impl BusSync {
+    fn channels(&self) -> Vec<String> {
+        let mut channels = vec![self.channel.clone()];
+        channels.extend(self.subscribe.iter().filter(|channel| **channel != self.channel).cloned());
+        channels
+    }
+}
+
+/// First and longest wait between subscriber reconnects
+pub(crate) const RECONNECT_BACKOFF: (Duration, Duration) = (Duration::from_secs(1), Duration::from_secs(60));
+
+/// One bust of a bus message: target, severity and reason, if sent
+pub(crate) type MessageBust<'a> = (&'a str, CacheBustSeverity, Option<BustReason>);
+
+/// Busts published by a daemon: `<origin>|<target>:<Severity>`, with
+/// further `,<target>:<Severity>` for a `bust_many` batch, then
+/// `|<reasons>`, a JSON array of each bust's `BustReason`. Messages from
+/// before origins were added are `<target>:<Severity>`, and ones from
+/// before reasons were added have none.
+pub(crate) fn parse_bust_message(payload: &str) -> Option<(Option<&str>, Vec<MessageBust<'_>>)> {
+    let (origin, busts) = match payload.split_once('|') {
+        Some((origin, busts)) => (Some(origin), busts),
+        None => (None, payload),
+    };
+    let (busts, reasons) = match busts.split_once('|') {
+        Some((busts, reasons)) => (busts, serde_json::from_str::<Vec<BustReason>>(reasons).ok()?),
+        None => (busts, Vec::new()),
+    };
+    let mut reasons = reasons.into_iter();
+    let busts = busts
+        .split(',')
+        .map(|bust| {
+            let (target, severity) = bust.rsplit_once(':')?;
+            let severity = match severity.to_lowercase().as_str() {
+                "low" => CacheBustSeverity::Low,
+                "medium" => CacheBustSeverity::Medium,
+                "high" => CacheBustSeverity::High,
+                "critical" => CacheBustSeverity::Critical,
+                _ => return None,
+            };
+            Some((target, severity, reasons.next()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((origin, busts))
+}
+
+fn bust_message(origin: &str, busts: &[(String, CacheBustSeverity, BustReason)]) -> String {
+    format!("{}|{}", origin, bust_payload(busts))
+}
+
+/// A bust message without its origin, as followers forward it to the
+/// leader, which adds theirs
+pub(crate) fn bust_payload(busts: &[(String, CacheBustSeverity, BustReason)]) -> String {
+    let targets: Vec<String> = busts.iter().map(|(target, severity, _)| format!("{}:{:?}", target, severity)).collect();
+    let reasons: Vec<&BustReason> = busts.iter().map(|(_, _, reason)| reason).collect();
+    let reasons = serde_json::to_string(&reasons).unwrap_or_else(|_| "[]".to_string());
+    format!("{}|{}", targets.join(","), reasons)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiramDimension {
     pub vector_id: String,
     pub hot_path_score: f32,
     pub memory_footprint: usize,
     pub access_pattern: Vec<u64>,
     pub cache_state: CacheState,
+    /// When `hot_path_score` was last decayed
+    #[serde(default)]
+    pub scored_at: u64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl DiramDimension {
+    /// `hot_path_score` as of `now`: every access adds 1 and the sum halves
+    /// every `HOT_PATH_HALF_LIFE_SECONDS`
+    pub fn score_at(&self, now: u64) -> f32 {
+        let idle = now.saturating_sub(self.scored_at) as f32;
+        self.hot_path_score * 0.5f32.powf(idle / HOT_PATH_HALF_LIFE_SECONDS)
+    }
+    
+    fn decay_to(&mut self, now: u64) {
+        self.hot_path_score = self.score_at(now);
+        self.scored_at = self.scored_at.max(now);
+    }
+    
+    /// Hot, Warm or Cold by score; Stale waits for an access or a rebuild
+    fn classify(&mut self) {
+        if self.cache_state == CacheState::Stale {
+            return;
+        }
+        self.cache_state = if self.hot_path_score >= HOT_ACCESS_THRESHOLD as f32 {
+            CacheState::Hot
+        } else if self.hot_path_score >= WARM_SCORE_THRESHOLD {
+            CacheState::Warm
+        } else {
+            CacheState::Cold
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CacheState {
     Hot,      // Frequently accessed, keep in memory
     Warm,     // Occasionally accessed, eligible for eviction
@@ -52,17 +520,22 @@ pub enum CacheState {
     Stale,    // Invalidated, must be rebuilt
 }
 
+/// Rebuild queue, sharded by target: busts of different targets queue
+/// without contending on one lock, which a storm of file changes would
+/// otherwise serialize on
 #[derive(Debug)]
 pub struct HeapPrioritizer {
-    cache_entries: BinaryHeap<PriorityEntry>,
-    model_bindings: HashMap<String, ModelWeights>,
+    /// Queued rebuilds of each target, in the order queued
+    cache_entries: DashMap<String, Vec<PriorityEntry>>,
+    model_bindings: DashMap<String, ModelWeights>,
 }
 
-#[derive(Debug, Clone)]
-struct PriorityEntry {
-    cache_id: String,
-    priority_score: f32,
-    timestamp: u64,
+/// Target queued for rebuild after a bust
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityEntry {
+    pub cache_id: String,
+    pub priority_score: f32,
+    pub timestamp: u64,
 }
 
 impl Eq for PriorityEntry {}
@@ -86,158 +559,1990 @@ impl PartialOrd for PriorityEntry {
     }
 }
 
+/// Clones share the same cache
+#[derive(Clone)]
 pub struct DimensionalCacheManager {
     // Lock-free concurrent storage for high-performance access
     cache_evicons: Arc<DashMap<String, CacheEvicon>>,
     diram_dimensions: Arc<DashMap<String, DiramDimension>>,
-    heap_prioritizer: Arc<Mutex<HeapPrioritizer>>,
+    heap_prioritizer: Arc<HeapPrioritizer>,
     
     // Model binding layer for polyglot runtime integration
     model_bindings: Arc<DashMap<String, ModelBinding>>,
     
-    // Redis connection for distributed cache coordination
-    redis_client: Option<redis::Client>,
+    // Transport for distributed cache coordination
+    bus: Arc<Mutex<Arc<dyn CacheBus>>>,
+    
+    // Dry-run mode records intended mutations instead of applying them
+    dry_run: bool,
+    dry_run_report: Arc<Mutex<Vec<DryRunRecord>>>,
+    
+    // Whether busts spread to the targets depending on the busted one
+    cascade: Arc<AtomicBool>,
+    
+    // Whether this daemon evicts; only the elected leader of a group does
+    evicting: Arc<AtomicBool>,
+    
+    // Most recent busts, newest last
+    bust_log: Arc<Mutex<VecDeque<BustRecord>>>,
+    
+    // TTL and size bounds, and what enforcing them reclaimed so far
+    limits: Arc<Mutex<CacheLimits>>,
+    reclaim_stats: Arc<Mutex<ReclaimStats>>,
+    
+    // Hit, miss, bust and eviction counts for `metrics`
+    counters: Arc<Mutex<CacheCounters>>,
+    
+    // Clear the on-disk caches of a busted target's runtime
+    executors: Arc<Mutex<Option<Arc<BustExecutors>>>>,
+    
+    // Node id and channels for sharing busts on the bus
+    bus_sync: Arc<Mutex<BusSync>>,
+    
+    // Held while a batch of busts is applied and while evicting, so
+    // evictions never see half a batch
+    batch_lock: Arc<Mutex<()>>,
+    
+    // Called back on evictions and state changes
+    listeners: Arc<CacheListeners>,
+    
+    // Append-only record of transitions, busts and evictions
+    audit: Arc<Mutex<Option<AuditLog>>>,
+    
+    // Told of each batch of busts as a whole
+    notifications: Arc<Mutex<Option<Arc<NotificationManager>>>>,
+    
+    // Stores the entries of cooling targets are spilled to, the targets
+    // spilled, and a lock serializing moves between tiers
+    tiers: Arc<Mutex<Option<CacheTiers>>>,
+    spilled: Arc<DashMap<String, SpilledTarget>>,
+    tier_lock: Arc<Mutex<()>>,
 }
 
+/// A target whose entries are held outside memory
 #[derive(Debug, Clone)]
+struct SpilledTarget {
+    tier: CacheTier,
+    entries: usize,
+    bytes: u64,
+}
+
+/// Busts kept for `recent_busts`
+pub const BUST_LOG_CAPACITY: usize = 100;
+
+/// A cache bust that was applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BustRecord {
+    pub timestamp: u64,
+    pub target: String,
+    pub severity: CacheBustSeverity,
+    pub reason: BustReason,
+    /// Freed on disk by the bust executors and compiler caches
+    #[serde(default)]
+    pub reclaimed_bytes: u64,
+}
+
+/// Cache mutation that would have been applied outside dry-run mode
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunRecord {
+    pub timestamp: u64,
+    pub action: DryRunAction,
+    pub target: String,
+    pub severity: Option<CacheBustSeverity>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum DryRunAction {
+    Bust,
+    Evict,
+}
+
+/// Selects entries for `list_entries`; criteria combine
+#[derive(Debug, Clone, Default)]
+pub struct EntryFilter {
+    pub target: Option<String>,
+    /// Only entries of targets in this namespace
+    pub namespace: Option<String>,
+    pub min_access_frequency: Option<u32>,
+    /// Only entries last accessed before this Unix time
+    pub accessed_before: Option<u64>,
+}
+
+impl EntryFilter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+    
+    pub fn target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+    
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+    
+    pub fn min_access_frequency(mut self, frequency: u32) -> Self {
+        self.min_access_frequency = Some(frequency);
+        self
+    }
+    
+    pub fn accessed_before(mut self, timestamp: u64) -> Self {
+        self.accessed_before = Some(timestamp);
+        self
+    }
+    
+    pub fn matches(&self, evicon: &CacheEvicon) -> bool {
+        self.target.as_ref().is_none_or(|target| &evicon.model_binding == target)
+            && self.namespace.as_ref().is_none_or(|namespace| split_namespace(&evicon.model_binding).0 == Some(namespace.as_str()))
+            && self.min_access_frequency.is_none_or(|min| evicon.access_frequency >= min)
+            && self.accessed_before.is_none_or(|before| evicon.last_access < before)
+    }
+}
+
+/// A target's dimensional state and the entries it holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetCacheState {
+    pub target: String,
+    pub cache_state: CacheState,
+    /// Decayed access count; see `DiramDimension::score_at`
+    pub hot_path_score: f32,
+    pub entries: usize,
+    /// Most recent access of any of its entries
+    pub last_access: Option<u64>,
+    /// Mean integrity score of its entries, 100 without entries
+    pub integrity_score: u8,
+    /// Size of its entries on disk
+    #[serde(default)]
+    pub bytes: u64,
+    /// Where its entries are held
+    #[serde(default)]
+    pub tier: CacheTier,
+}
+
+/// What `warm` registered
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WarmReport {
+    pub target: String,
+    pub registered: usize,
+    pub prebuild_queued: bool,
+}
+
+/// A target's share of the cache, for `disk_usage`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetUsage {
+    pub target: String,
+    pub bytes: u64,
+    pub entries: usize,
+    /// Its largest entries by cache ID, largest first; entries spilled
+    /// out of memory are counted but not listed
+    pub largest: Vec<(String, u64)>,
+}
+
+/// Weight of the latest rebuild in a target's learned build cost
+const BUILD_COST_SMOOTHING: f32 = 0.3;
+
+/// Hot path score that makes a target Hot, about this many accesses in
+/// the last half-life
+pub const HOT_ACCESS_THRESHOLD: usize = 10;
+
+/// Hot path score below which a target is Cold
+pub const WARM_SCORE_THRESHOLD: f32 = 1.0;
+
+/// Seconds without accesses for a hot path score to halve
+pub const HOT_PATH_HALF_LIFE_SECONDS: f32 = 300.0;
+
+/// Access timestamps kept per target for the hot path score
+const ACCESS_PATTERN_CAPACITY: usize = 64;
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelBinding {
     pub runtime: String,
     pub pid: Option<u32>,
+    /// When `pid` started, telling a restart that got the same pid back
+    /// apart from the process running on
+    #[serde(default)]
+    pub pid_start_time: Option<u64>,
     pub path: String,
     pub last_modified: u64,
     pub cache_dependencies: Vec<String>,
+    /// External compiler caches cleared on High and Critical busts
+    #[serde(default)]
+    pub compiler_caches: Vec<CompilerCache>,
+    /// Commands run around the target's busts, in `path`
+    #[serde(default)]
+    pub hooks: BustHooks,
+}
+
+/// Separates a namespace from the target or cache ID it qualifies
+pub const NAMESPACE_SEPARATOR: &str = "::";
+
+/// Key of `name` inside `namespace`, e.g. `shop:feature-x::web`
+pub fn qualify(namespace: &str, name: &str) -> String {
+    format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, name)
+}
+
+/// Namespace and bare name of a target or cache ID; no namespace for keys
+/// bound outside of one
+pub fn split_namespace(key: &str) -> (Option<&str>, &str) {
+    match key.split_once(NAMESPACE_SEPARATOR) {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, key),
+    }
+}
+
+/// What `drop_namespace` removed, or would remove in dry-run mode
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct NamespaceDropReport {
+    pub targets: usize,
+    pub entries: usize,
+    pub reclaimed_bytes: u64,
+    /// Queued rebuilds dropped
+    pub rebuilds: usize,
+}
+
+/// The targets and entries of one namespace, e.g. `project:branch`, so
+/// branches built side by side do not collide on target names. Targets,
+/// dependencies and cache IDs are given bare and qualified here; busts,
+/// evictions and lookups stay inside the namespace.
+pub struct CacheNamespace<'a> {
+    manager: &'a DimensionalCacheManager,
+    name: String,
+}
+
+impl CacheNamespace<'_> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    /// Key of `name` in the manager
+    pub fn key(&self, name: &str) -> String {
+        qualify(&self.name, name)
+    }
+    
+    /// Bind `target`; its `cache_dependencies` are targets of this namespace
+    pub fn bind_model(&self, target: &str, mut binding: ModelBinding) -> Result<()> {
+        binding.cache_dependencies = binding.cache_dependencies.iter().map(|dependency| self.key(dependency)).collect();
+        self.manager.bind_model(&self.key(target), binding)
+    }
+    
+    pub fn register_entry(&self, cache_id: &str, target: &str, size_bytes: u64, strategy: EvictionStrategy) {
+        self.manager.register_entry(&self.key(cache_id), &self.key(target), size_bytes, strategy);
+    }
+    
+    pub fn touch(&self, cache_id: &str) -> bool {
+        self.manager.touch(&self.key(cache_id))
+    }
+    
+    pub fn bust_cache(&self, target: &str, severity: CacheBustSeverity) -> Result<()> {
+        self.manager.bust_cache(&self.key(target), severity)
+    }
+    
+    pub fn bust_cache_with_reason(&self, target: &str, severity: CacheBustSeverity, reason: impl Into<BustReason>) -> Result<()> {
+        self.manager.bust_cache_with_reason(&self.key(target), severity, reason)
+    }
+    
+    /// Evict by `strategy` among this namespace's entries only
+    pub fn cache_evict(&self, strategy: &EvictionStrategy) -> Result<Vec<String>> {
+        let filter = EntryFilter::all().namespace(&self.name);
+        self.manager.evict_matching(strategy, |evicon| filter.matches(evicon))
+    }
+    
+    pub fn get_state(&self, target: &str) -> Option<TargetCacheState> {
+        self.manager.get_state(&self.key(target))
+    }
+    
+    /// Entries of this namespace matching `filter`, whose target is bare
+    pub fn list_entries(&self, filter: &EntryFilter) -> Vec<CacheEvicon> {
+        let mut filter = filter.clone().namespace(&self.name);
+        filter.target = filter.target.map(|target| self.key(&target));
+        self.manager.list_entries(&filter)
+    }
+    
+    /// Drop everything in the namespace, e.g. once its branch is deleted
+    pub fn drop_all(self) -> NamespaceDropReport {
+        self.manager.drop_namespace(&self.name)
+    }
 }
 
 impl DimensionalCacheManager {
     pub fn new() -> Result<Self> {
-        let redis_client = redis::Client::open("redis://127.0.0.1/")
-            .ok(); // Optional Redis connection
+        // Redis on localhost when built with it; `with_bus` to change
+        let bus = open_bus(BusKind::default(), None).unwrap_or_else(|_| Arc::new(LocalBus));
         
         Ok(DimensionalCacheManager {
             cache_evicons: Arc::new(DashMap::new()),
             diram_dimensions: Arc::new(DashMap::new()),
-            heap_prioritizer: Arc::new(Mutex::new(HeapPrioritizer::new())),
+            heap_prioritizer: Arc::new(HeapPrioritizer::new()),
             model_bindings: Arc::new(DashMap::new()),
-            redis_client,
+            bus: Arc::new(Mutex::new(bus)),
+            dry_run: false,
+            cascade: Arc::new(AtomicBool::new(false)),
+            evicting: Arc::new(AtomicBool::new(true)),
+            dry_run_report: Arc::new(Mutex::new(Vec::new())),
+            bust_log: Arc::new(Mutex::new(VecDeque::new())),
+            limits: Arc::new(Mutex::new(CacheLimits::default())),
+            reclaim_stats: Arc::new(Mutex::new(ReclaimStats::default())),
+            counters: Arc::new(Mutex::new(CacheCounters::default())),
+            executors: Arc::new(Mutex::new(None)),
+            bus_sync: Arc::new(Mutex::new(BusSync::default())),
+            batch_lock: Arc::new(Mutex::new(())),
+            listeners: Arc::new(CacheListeners::default()),
+            audit: Arc::new(Mutex::new(None)),
+            notifications: Arc::new(Mutex::new(None)),
+            tiers: Arc::new(Mutex::new(None)),
+            spilled: Arc::new(DashMap::new()),
+            tier_lock: Arc::new(Mutex::new(())),
         })
     }
     
-    /// Register a model binding for PID-aware cache management
-    pub fn bind_model(&self, target_name: &str, binding: ModelBinding) -> Result<()> {
-        self.model_bindings.insert(target_name.to_string(), binding);
-        
-        // Initialize dimensional vector for this model
-        let diram = DiramDimension {
-            vector_id: format!("diram_{}", target_name),
-            hot_path_score: 0.0,
-            memory_footprint: 0,
-            access_pattern: Vec::new(),
-            cache_state: CacheState::Cold,
-        };
-        
-        self.diram_dimensions.insert(target_name.to_string(), diram);
-        
-        log::info!("🔗 Model binding established: {}", target_name);
-        Ok(())
+    /// Enable dry-run mode: busts and evictions are only recorded
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
     }
     
-    /// Cache eviction algorithm - model-agnostic with OBINexus extensions
-    pub fn cache_evict(&self, strategy: &EvictionStrategy) -> Result<Vec<String>> {
-        let mut evicted_entries = Vec::new();
-        
-        match strategy {
-            EvictionStrategy::ModelAware(weights) => {
-                // OBINexus model-aware eviction based on language priority and dependency impact
-                let mut candidates: Vec<_> = self.cache_evicons.iter()
-                    .filter(|entry| {
-                        let diram = self.diram_dimensions.get(entry.key());
-                        diram.map_or(false, |d| d.cache_state == CacheState::Cold || d.cache_state == CacheState::Stale)
-                    })
-                    .collect();
-                
-                // Sort by composite score: access frequency + language priority + dependency depth
-                candidates.sort_by(|a, b| {
-                    let score_a = self.calculate_eviction_score(a.value(), weights);
-                    let score_b = self.calculate_eviction_score(b.value(), weights);
-                    score_a.partial_cmp(&score_b).unwrap_or(Ordering::Equal)
-                });
-                
-                // Evict lowest-priority entries
-                for candidate in candidates.iter().take(3) {
-                    evicted_entries.push(candidate.key().clone());
-                    self.cache_evicons.remove(candidate.key());
-                    log::info!("🗑️ Evicted cache entry: {}", candidate.key());
-                }
-            }
-            
-            EvictionStrategy::LRU => {
-                // Traditional LRU implementation
-                let mut candidates: Vec<_> = self.cache_evicons.iter().collect();
-                candidates.sort_by_key(|entry| entry.last_access);
-                
-                if let Some(oldest) = candidates.first() {
-                    evicted_entries.push(oldest.key().clone());
-                    self.cache_evicons.remove(oldest.key());
-                }
-            }
-            
-            _ => {
-                // Other eviction strategies (MRU, LFU, FIFO) implementation
-                // Would be implemented similarly with appropriate sorting criteria
-            }
-        }
-        
-        // Update heap prioritizer after eviction
-        self.update_heap_priorities()?;
-        
-        Ok(evicted_entries)
+    /// Bound the cache; see `enforce_limits`
+    pub fn with_limits(self, limits: CacheLimits) -> Self {
+        self.set_limits(limits);
+        self
     }
     
-    /// Calculate model-aware eviction score for OBINexus framework
+    /// Replace the limits, e.g. after a config reload
+    pub fn set_limits(&self, limits: CacheLimits) {
+        *self.limits.lock().unwrap() = limits;
+    }
+    
+    /// Limits in force, with build costs learned from rebuilds
+    pub fn limits(&self) -> CacheLimits {
+        self.limits.lock().unwrap().clone()
+    }
+    
+    /// Counters since start with the current size of the cache
+    pub fn metrics(&self) -> CacheMetrics {
+        let counters = self.counters.lock().unwrap();
+        let mut busts: BTreeMap<String, u64> = [
+            CacheBustSeverity::Low,
+            CacheBustSeverity::Medium,
+            CacheBustSeverity::High,
+            CacheBustSeverity::Critical,
+        ]
+        .iter()
+        .map(|severity| (severity.label().to_string(), 0))
+        .collect();
+        busts.extend(counters.busts.clone());
+        let mut evictions: BTreeMap<String, u64> = ["lru", "mru", "lfu", "fifo", "model_aware", "ttl", "limits", "manual", "namespace"]
+            .iter()
+            .map(|kind| (kind.to_string(), 0))
+            .collect();
+        evictions.extend(counters.evictions.clone());
+        
+        let mut targets: BTreeMap<String, usize> = ["hot", "warm", "cold", "stale"].iter().map(|state| (state.to_string(), 0)).collect();
+        for diram in self.diram_dimensions.iter() {
+            *targets.entry(format!("{:?}", diram.cache_state).to_lowercase()).or_insert(0) += 1;
+        }
+        let mut tier_entries: BTreeMap<String, u64> = [CacheTier::Disk, CacheTier::Remote]
+            .iter()
+            .map(|tier| (tier.label().to_string(), 0))
+            .collect();
+        tier_entries.insert(CacheTier::Memory.label().to_string(), self.cache_evicons.len() as u64);
+        for spilled in self.spilled.iter() {
+            *tier_entries.entry(spilled.tier.label().to_string()).or_insert(0) += spilled.entries as u64;
+        }
+        
+        CacheMetrics {
+            hits: counters.hits,
+            misses: counters.misses,
+            busts,
+            evictions,
+            stale_to_hot: counters.stale_to_hot,
+            entries: self.cache_evicons.len(),
+            bytes: self.cache_evicons.iter().map(|entry| entry.size_bytes).sum(),
+            target_bytes: self.diram_dimensions.iter()
+                .map(|diram| (diram.key().clone(), diram.memory_footprint as u64))
+                .collect(),
+            targets,
+            reclaimed_bytes: self.reclaim_stats().reclaimed_bytes,
+            tier_entries,
+            ignored_events: counters.ignored_events.clone(),
+        }
+    }
+    
+    /// Digest the entry's file had when registered, if it was hashed
+    pub fn content_hash(&self, cache_id: &str) -> Option<String> {
+        self.cache_evicons.get(cache_id).and_then(|evicon| evicon.content_hash.clone())
+    }
+    
+    /// Count a file event of `target` the watcher ignored
+    pub fn record_ignored(&self, target: &str) {
+        *self.counters.lock().unwrap().ignored_events.entry(target.to_string()).or_insert(0) += 1;
+    }
+    
+    pub fn reclaim_stats(&self) -> ReclaimStats {
+        self.reclaim_stats.lock().unwrap().clone()
+    }
+    
+    /// Bust the dependents of a busted target too; see `bust_cache_cascade`
+    pub fn with_cascade(self, cascade: bool) -> Self {
+        self.set_cascade(cascade);
+        self
+    }
+    
+    pub fn set_cascade(&self, cascade: bool) {
+        self.cascade.store(cascade, AtomicOrdering::Relaxed);
+    }
+    
+    /// Let `cache_evict` and `enforce_limits` evict, or leave eviction to
+    /// the leader of a group of daemons; see [`crate::core::daemon`]
+    pub fn set_evicting(&self, evicting: bool) {
+        self.evicting.store(evicting, AtomicOrdering::Relaxed);
+    }
+    
+    pub fn is_evicting(&self) -> bool {
+        self.evicting.load(AtomicOrdering::Relaxed)
+    }
+    
+    /// Run the executor of a target's runtime in its bound path on every
+    /// bust; without executors a bust only changes cache state
+    pub fn with_executors(self, executors: Arc<BustExecutors>) -> Self {
+        self.set_executors(Some(executors));
+        self
+    }
+    
+    pub fn set_executors(&self, executors: Option<Arc<BustExecutors>>) {
+        *self.executors.lock().unwrap() = executors;
+    }
+    
+    /// Transport busts are shared with other daemons on; a running
+    /// subscriber switches over when it reconnects
+    pub fn with_bus(self, bus: Arc<dyn CacheBus>) -> Self {
+        self.set_bus(bus);
+        self
+    }
+    
+    pub fn set_bus(&self, bus: Arc<dyn CacheBus>) {
+        *self.bus.lock().unwrap() = bus;
+    }
+    
+    pub fn bus(&self) -> Arc<dyn CacheBus> {
+        self.bus.lock().unwrap().clone()
+    }
+    
+    /// Node id and channels used to share busts with other daemons; a
+    /// running subscriber picks up channel changes when it reconnects
+    pub fn with_bus_sync(self, sync: BusSync) -> Self {
+        self.set_bus_sync(sync);
+        self
+    }
+    
+    pub fn set_bus_sync(&self, sync: BusSync) {
+        *self.bus_sync.lock().unwrap() = sync;
+    }
+    
+    pub fn bus_sync(&self) -> BusSync {
+        self.bus_sync.lock().unwrap().clone()
+    }
+    
+    /// Append every state change, bust and eviction to `audit`, for
+    /// `bustcall cache history`
+    pub fn with_audit_log(self, audit: AuditLog) -> Self {
+        self.set_audit_log(Some(audit));
+        self
+    }
+    
+    pub fn set_audit_log(&self, audit: Option<AuditLog>) {
+        *self.audit.lock().unwrap() = audit;
+    }
+    
+    /// Send one notification per `bust_many` batch through `manager`
+    pub fn with_notifications(self, manager: Arc<NotificationManager>) -> Self {
+        self.set_notifications(Some(manager));
+        self
+    }
+    
+    pub fn set_notifications(&self, manager: Option<Arc<NotificationManager>>) {
+        *self.notifications.lock().unwrap() = manager;
+    }
+    
+    /// Write to the audit log; a failing log must not fail the change
+    fn audit(&self, target: &str, event: AuditEvent) {
+        if let Some(audit) = self.audit.lock().unwrap().as_ref() {
+            if let Err(e) = audit.append(target, event) {
+                log::warn!("Audit log {} not written: {}", audit.path().display(), e);
+            }
+        }
+    }
+    
+    /// Spill the entries of targets cooling from Hot to Warm to the disk
+    /// tier, and of those going Cold to the remote tier when there is one.
+    /// Using a target again promotes its entries back into memory.
+    /// Eviction, TTLs and limits only see the entries in memory.
+    pub fn with_tiers(self, tiers: CacheTiers) -> Self {
+        self.set_tiers(Some(tiers));
+        self
+    }
+    
+    /// Replace the tiers, first promoting every spilled target from the
+    /// old ones
+    pub fn set_tiers(&self, tiers: Option<CacheTiers>) {
+        let spilled: Vec<String> = self.spilled.iter().map(|spilled| spilled.key().clone()).collect();
+        for target in spilled {
+            self.promote(&target);
+        }
+        *self.tiers.lock().unwrap() = tiers;
+    }
+    
+    /// Tier holding `target`'s entries
+    pub fn tier_of(&self, target: &str) -> CacheTier {
+        self.spilled.get(target).map_or(CacheTier::Memory, |spilled| spilled.tier)
+    }
+    
+    /// Move a target's entries out of memory when it cools down
+    fn retier(&self, target: &str, from: &CacheState, to: &CacheState) {
+        let cooled = matches!(
+            (from, to),
+            (CacheState::Hot, CacheState::Warm) | (CacheState::Hot, CacheState::Cold) | (CacheState::Warm, CacheState::Cold)
+        );
+        if !cooled {
+            return;
+        }
+        let Some(tiers) = self.tiers.lock().unwrap().clone() else {
+            return;
+        };
+        let tier = match (to, &tiers.remote) {
+            (CacheState::Cold, Some(_)) => CacheTier::Remote,
+            _ => CacheTier::Disk,
+        };
+        self.spill(target, tier, &tiers);
+    }
+    
+    /// Write `target`'s entries to `tier`, with any a previous spill left in
+    /// another, and drop them from memory
+    fn spill(&self, target: &str, tier: CacheTier, tiers: &CacheTiers) {
+        let Some(store) = tiers.store(tier) else {
+            return;
+        };
+        let _moving = self.tier_lock.lock().unwrap();
+        let mut entries = self.list_entries(&EntryFilter::all().target(target));
+        let previous = self.spilled.get(target).map(|spilled| spilled.tier);
+        if let Some(previous_store) = previous.and_then(|previous| tiers.store(previous)) {
+            match previous_store.load(target) {
+                // Entries in memory are the newer
+                Ok(held) => {
+                    let in_memory: HashSet<String> = entries.iter().map(|entry| entry.cache_id.clone()).collect();
+                    entries.extend(held.into_iter().filter(|entry| !in_memory.contains(&entry.cache_id)));
+                }
+                Err(e) => {
+                    log::warn!("{} kept in memory, {} tier unreadable: {}", target, previous_store.name(), e);
+                    return;
+                }
+            }
+        }
+        if entries.is_empty() {
+            return;
+        }
+        if let Err(e) = store.put(target, &entries) {
+            log::warn!("{} kept in memory, not spilled to the {} tier: {}", target, store.name(), e);
+            return;
+        }
+        if let Some(previous_store) = previous.filter(|previous| *previous != tier).and_then(|previous| tiers.store(previous)) {
+            if let Err(e) = previous_store.remove(target) {
+                log::debug!("Stale {} tier copy of {} left behind: {}", previous_store.name(), target, e);
+            }
+        }
+        for entry in &entries {
+            self.cache_evicons.remove(&entry.cache_id);
+        }
+        self.spilled.insert(target.to_string(), SpilledTarget {
+            tier,
+            entries: entries.len(),
+            bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+        });
+        log::debug!("🧊 Spilled {} entries of {} to the {} tier", entries.len(), target, store.name());
+    }
+    
+    /// Bring a spilled target's entries back into memory. Returns false
+    /// when it was not spilled or its tier could not be read.
+    fn promote(&self, target: &str) -> bool {
+        if !self.spilled.contains_key(target) {
+            return false;
+        }
+        let Some(tiers) = self.tiers.lock().unwrap().clone() else {
+            return false;
+        };
+        let _moving = self.tier_lock.lock().unwrap();
+        let Some((_, spilled)) = self.spilled.remove(target) else {
+            return false;
+        };
+        let Some(store) = tiers.store(spilled.tier) else {
+            return false;
+        };
+        let entries = match store.load(target) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("Entries of {} not promoted from the {} tier: {}", target, store.name(), e);
+                self.spilled.insert(target.to_string(), spilled);
+                return false;
+            }
+        };
+        let promoted = entries.len();
+        for entry in entries {
+            self.cache_evicons.entry(entry.cache_id.clone()).or_insert(entry);
+        }
+        if let Err(e) = store.remove(target) {
+            log::debug!("Stale {} tier copy of {} left behind: {}", store.name(), target, e);
+        }
+        log::debug!("🔥 Promoted {} entries of {} from the {} tier", promoted, target, store.name());
+        true
+    }
+    
+    /// Promote the spilled target an entry missing from memory belongs to:
+    /// the one with the longest bound path holding the entry's path
+    fn promote_owner(&self, cache_id: &str) -> bool {
+        if self.spilled.is_empty() {
+            return false;
+        }
+        let (namespace, path) = split_namespace(cache_id);
+        let spilled: Vec<String> = self.spilled.iter().map(|spilled| spilled.key().clone()).collect();
+        let owner = spilled.into_iter()
+            .filter(|target| split_namespace(target).0 == namespace)
+            .filter_map(|target| {
+                let root = self.model_bindings.get(&target)?.path.clone();
+                (!root.is_empty() && Path::new(path).starts_with(&root)).then_some((root.len(), target))
+            })
+            .max();
+        match owner {
+            Some((_, target)) => self.promote(&target) && self.cache_evicons.contains_key(cache_id),
+            None => false,
+        }
+    }
+    
+    /// Drop every spilled entry from the tiers, e.g. when the cache state
+    /// is replaced
+    fn discard_spilled(&self) {
+        let tiers = self.tiers.lock().unwrap().clone();
+        let spilled: Vec<(String, SpilledTarget)> = self.spilled.iter().map(|spilled| (spilled.key().clone(), spilled.value().clone())).collect();
+        self.spilled.clear();
+        for (target, spilled) in spilled {
+            if let Some(store) = tiers.as_ref().and_then(|tiers| tiers.store(spilled.tier)) {
+                if let Err(e) = store.remove(&target) {
+                    log::debug!("Stale {} tier copy of {} left behind: {}", store.name(), target, e);
+                }
+            }
+        }
+    }
+    
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+    
+    /// Everything recorded while in dry-run mode, oldest first
+    pub fn dry_run_report(&self) -> Vec<DryRunRecord> {
+        self.dry_run_report.lock().unwrap().clone()
+    }
+    
+    fn record_dry_run(&self, action: DryRunAction, target: &str, severity: Option<CacheBustSeverity>, reason: &str) {
+        log::info!("🧪 [dry-run] {:?} {} ({:?}): {}", action, target, severity, reason);
+        
+        self.dry_run_report.lock().unwrap().push(DryRunRecord {
+            timestamp: unix_now(),
+            action,
+            target: target.to_string(),
+            severity,
+            reason: reason.to_string(),
+        });
+    }
+    
+    /// Entries, spilled ones included, dimensional vectors, bindings and
+    /// rebuild queue as they are
+    pub fn snapshot(&self) -> CacheSnapshot {
+        let rebuild_queue = self.heap_prioritizer.sorted();
+        let mut evicons: BTreeMap<String, CacheEvicon> = self.cache_evicons.iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        if let Some(tiers) = self.tiers.lock().unwrap().clone() {
+            let spilled: Vec<(String, CacheTier)> = self.spilled.iter().map(|spilled| (spilled.key().clone(), spilled.tier)).collect();
+            for (target, tier) in spilled {
+                let Some(store) = tiers.store(tier) else {
+                    continue;
+                };
+                match store.load(&target) {
+                    Ok(entries) => {
+                        for entry in entries {
+                            evicons.entry(entry.cache_id.clone()).or_insert(entry);
+                        }
+                    }
+                    Err(e) => log::warn!("Spilled entries of {} left out of the snapshot: {}", target, e),
+                }
+            }
+        }
+        CacheSnapshot {
+            version: SNAPSHOT_VERSION,
+            created_at: unix_now(),
+            evicons,
+            dimensions: self.diram_dimensions.iter().map(|entry| (entry.key().clone(), entry.value().clone())).collect(),
+            bindings: self.model_bindings.iter().map(|entry| (entry.key().clone(), entry.value().clone())).collect(),
+            rebuild_queue,
+        }
+    }
+    
+    /// Replace the cache state with a snapshot's. Counters and the bust log
+    /// are kept.
+    pub fn restore(&self, snapshot: CacheSnapshot) -> Result<()> {
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(anyhow!("Snapshot version {} is newer than supported version {}", snapshot.version, SNAPSHOT_VERSION));
+        }
+        if self.dry_run {
+            log::info!("🔍 Dry run, snapshot of {} entries not restored", snapshot.evicons.len());
+            return Ok(());
+        }
+        
+        self.discard_spilled();
+        self.cache_evicons.clear();
+        for (key, evicon) in snapshot.evicons {
+            self.cache_evicons.insert(key, evicon);
+        }
+        self.diram_dimensions.clear();
+        for (target, mut diram) in snapshot.dimensions {
+            // Snapshots from before sizes were tracked hold 0
+            diram.memory_footprint = self.footprint_of(&target);
+            self.diram_dimensions.insert(target, diram);
+        }
+        self.model_bindings.clear();
+        for (target, binding) in snapshot.bindings {
+            self.model_bindings.insert(target, binding);
+        }
+        self.heap_prioritizer.cache_entries.clear();
+        for entry in snapshot.rebuild_queue {
+            self.heap_prioritizer.push(entry);
+        }
+        
+        log::info!("📥 Cache restored from snapshot: {} entries, {} targets", self.cache_evicons.len(), self.model_bindings.len());
+        Ok(())
+    }
+    
+    /// Pre-register the entries `target` is expected to hold, e.g. from a
+    /// manifest or the last snapshot, and start it Warm, so the first build
+    /// after a start does not pay for a cold cache. Hot and Stale targets
+    /// keep their state. `prebuild` queues a Low rebuild for the rebuild
+    /// executor to produce what is still missing.
+    pub fn warm(&self, target: &str, entries: &[String], prebuild: bool) -> Result<WarmReport> {
+        if !self.model_bindings.contains_key(target) {
+            return Err(anyhow!("Cannot warm unbound target {}", target));
+        }
+        for cache_id in entries {
+            self.register_entry(cache_id, target, 0, EvictionStrategy::LRU);
+        }
+        
+        let now = unix_now();
+        let warmed = self.diram_dimensions.get_mut(target).and_then(|mut diram| {
+            diram.decay_to(now);
+            if matches!(diram.cache_state, CacheState::Hot | CacheState::Stale) {
+                return None;
+            }
+            diram.hot_path_score = diram.hot_path_score.max(WARM_SCORE_THRESHOLD);
+            Some(std::mem::replace(&mut diram.cache_state, CacheState::Warm))
+        });
+        if let Some(before) = warmed {
+            self.state_changed(target, before, CacheState::Warm);
+        }
+        
+        let prebuild_queued = prebuild && !self.dry_run;
+        if prebuild_queued {
+            self.queue_rebuild(target, CacheBustSeverity::Low)?;
+        }
+        log::info!("🔥 Warmed {} with {} entries{}", target, entries.len(), if prebuild_queued { ", prebuild queued" } else { "" });
+        Ok(WarmReport {
+            target: target.to_string(),
+            registered: entries.len(),
+            prebuild_queued,
+        })
+    }
+    
+    /// `warm` every bound target with its entries in `snapshot`
+    pub fn warm_from_snapshot(&self, snapshot: &CacheSnapshot, prebuild: bool) -> Result<Vec<WarmReport>> {
+        let mut by_target: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (cache_id, evicon) in &snapshot.evicons {
+            if self.model_bindings.contains_key(&evicon.model_binding) {
+                by_target.entry(evicon.model_binding.as_str()).or_default().push(cache_id.clone());
+            }
+        }
+        by_target.into_iter().map(|(target, entries)| self.warm(target, &entries, prebuild)).collect()
+    }
+    
+    /// Busts applied most recently, oldest first
+    pub fn recent_busts(&self) -> Vec<BustRecord> {
+        self.bust_log.lock().unwrap().iter().cloned().collect()
+    }
+    
+    /// State of a target's dimensional vector, if it has one
+    pub fn cache_state(&self, target: &str) -> Option<CacheState> {
+        self.diram_dimensions.get(target).map(|diram| diram.cache_state.clone())
+    }
+    
+    fn record_bust(&self, target: &str, severity: &CacheBustSeverity, reason: &BustReason, reclaimed_bytes: u64) {
+        let mut log = self.bust_log.lock().unwrap();
+        if log.len() == BUST_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(BustRecord {
+            timestamp: unix_now(),
+            target: target.to_string(),
+            severity: severity.clone(),
+            reason: reason.clone(),
+            reclaimed_bytes,
+        });
+    }
+    
+    /// Evict a single cache entry, honouring dry-run mode
+    pub fn evict_entry(&self, key: &str, reason: &str) {
+        self.evict_as(key, "manual", reason);
+    }
+    
+    /// Evict, counting the eviction under `kind` for `metrics`
+    fn evict_as(&self, key: &str, kind: &str, reason: &str) {
+        if self.dry_run {
+            self.record_dry_run(DryRunAction::Evict, key, None, reason);
+        } else if let Some((_, evicon)) = self.cache_evicons.remove(key) {
+            *self.counters.lock().unwrap().evictions.entry(kind.to_string()).or_insert(0) += 1;
+            self.audit(
+                &evicon.model_binding,
+                AuditEvent::Evict {
+                    cache_id: evicon.cache_id.clone(),
+                    kind: kind.to_string(),
+                    reason: reason.to_string(),
+                },
+            );
+            self.entry_removed(&evicon, true);
+        }
+    }
+    
+    /// Call `listener` with every entry evicted or removed by a bust, as it
+    /// leaves the cache. Listeners run on the thread making the change and
+    /// must not bust or evict themselves; hand such work to another thread.
+    pub fn on_evict(&self, listener: impl Fn(&CacheEvicon) + Send + Sync + 'static) -> ListenerId {
+        let listener: EvictListener = Arc::new(listener);
+        self.listeners.add(&self.listeners.evict, listener)
+    }
+    
+    /// Call `listener` whenever a target's state changes: busted to Stale,
+    /// rebuilt, or moved between Hot, Warm and Cold by accesses and decay.
+    /// Runs like `on_evict` listeners.
+    pub fn on_state_change(&self, listener: impl Fn(&StateChange) + Send + Sync + 'static) -> ListenerId {
+        let listener: StateListener = Arc::new(listener);
+        self.listeners.add(&self.listeners.state, listener)
+    }
+    
+    /// Unregister an `on_evict` or `on_state_change` listener; false when
+    /// it was not registered
+    pub fn remove_listener(&self, id: ListenerId) -> bool {
+        self.listeners.remove(id)
+    }
+    
+    /// Report a state change unless the state stayed the same
+    fn state_changed(&self, target: &str, from: CacheState, to: CacheState) {
+        self.state_changed_by(target, from, to, None);
+    }
+    
+    /// `state_changed`, for a change made by a bust
+    fn state_changed_by(&self, target: &str, from: CacheState, to: CacheState, reason: Option<&BustReason>) {
+        if from != to {
+            self.audit(target, AuditEvent::Transition { from: from.clone(), to: to.clone() });
+            self.retier(target, &from, &to);
+            self.listeners.state_changed(&StateChange {
+                target: target.to_string(),
+                from,
+                to,
+                reason: reason.cloned(),
+            });
+        }
+    }
+    
+    /// Record a cache entry (a file or build artifact) of `target` taking
+    /// `size_bytes`. An entry that is already known counts as an access
+    /// instead.
+    /// An entry naming a file is hashed, for `verify`, and one naming a file
+    /// or directory is sized on disk, `size_bytes` only counting otherwise.
+    pub fn register_entry(&self, cache_id: &str, target: &str, size_bytes: u64, strategy: EvictionStrategy) {
+        self.promote(target);
+        let path = split_namespace(cache_id).1;
+        let size_bytes = disk_size(path).unwrap_or(size_bytes);
+        let content_hash = hash_file(path);
+        let integrity_score = if content_hash.is_some() { 100 } else { INTEGRITY_UNVERIFIED };
+        let resized = self.cache_evicons.get_mut(cache_id).map(|mut evicon| {
+            let old_size = std::mem::replace(&mut evicon.size_bytes, size_bytes);
+            evicon.content_hash = content_hash.clone();
+            evicon.integrity_score = integrity_score;
+            (evicon.model_binding.clone(), old_size)
+        });
+        if let Some((owner, old_size)) = resized {
+            self.adjust_footprint(&owner, old_size, size_bytes);
+        }
+        if self.access(cache_id) {
+            return;
+        }
+        
+        let now = unix_now();
+        self.cache_evicons.insert(cache_id.to_string(), CacheEvicon {
+            cache_id: cache_id.to_string(),
+            model_binding: target.to_string(),
+            eviction_strategy: strategy,
+            last_access: now,
+            inserted_at: now,
+            size_bytes,
+            access_frequency: 1,
+            integrity_score,
+            dependency_depth: 0,
+            content_hash,
+        });
+        self.adjust_footprint(target, 0, size_bytes);
+        self.record_access(target, now);
+    }
+    
+    /// Re-measure the entries whose file or directory holds `path`, after a
+    /// change to it. Returns how many entries changed size.
+    pub fn refresh_sizes(&self, path: &Path) -> usize {
+        let holders: Vec<(String, String, u64)> = self.cache_evicons.iter()
+            .filter(|entry| path.starts_with(split_namespace(entry.key()).1))
+            .map(|entry| (entry.key().clone(), entry.model_binding.clone(), entry.size_bytes))
+            .collect();
+        let mut changed = 0;
+        for (cache_id, target, old_size) in holders {
+            let Some(size) = disk_size(split_namespace(&cache_id).1) else {
+                continue;
+            };
+            if size == old_size {
+                continue;
+            }
+            if let Some(mut evicon) = self.cache_evicons.get_mut(&cache_id) {
+                evicon.size_bytes = size;
+            }
+            self.adjust_footprint(&target, old_size, size);
+            changed += 1;
+        }
+        changed
+    }
+    
+    /// Move `target`'s memory footprint from an entry's old to its new size
+    fn adjust_footprint(&self, target: &str, old_size: u64, new_size: u64) {
+        if let Some(mut diram) = self.diram_dimensions.get_mut(target) {
+            diram.memory_footprint = (diram.memory_footprint as u64).saturating_sub(old_size).saturating_add(new_size) as usize;
+        }
+    }
+    
+    /// Account for an entry that left the cache
+    fn entry_removed(&self, evicon: &CacheEvicon, evicted: bool) {
+        self.adjust_footprint(&evicon.model_binding, evicon.size_bytes, 0);
+        if evicted {
+            self.listeners.evicted(evicon);
+        }
+    }
+    
+    /// Total size of `target`'s entries
+    fn footprint_of(&self, target: &str) -> usize {
+        self.cache_evicons.iter()
+            .filter(|entry| entry.model_binding == target)
+            .map(|entry| entry.size_bytes as usize)
+            .sum()
+    }
+    
+    /// Re-hash the files behind `target`'s entries. Entries that still
+    /// match score 100 and mismatches 0; any mismatch busts the target at
+    /// High, leaving it Stale until rebuilt.
+    pub fn verify(&self, target: &str) -> Result<IntegrityReport> {
+        let mut report = IntegrityReport::default();
+        for entry in self.list_entries(&EntryFilter::all().target(target)) {
+            let Some(expected) = &entry.content_hash else {
+                continue;
+            };
+            report.checked += 1;
+            let intact = hash_file(split_namespace(&entry.cache_id).1).as_ref() == Some(expected);
+            if let Some(mut evicon) = self.cache_evicons.get_mut(&entry.cache_id) {
+                evicon.integrity_score = if intact { 100 } else { 0 };
+            }
+            if !intact {
+                report.mismatched.push(entry.cache_id);
+            }
+        }
+        
+        if !report.mismatched.is_empty() {
+            log::warn!("🔐 {} of {} entries of {} failed verification", report.mismatched.len(), report.checked, target);
+            let reason = format!("integrity: {} changed", report.mismatched.join(", "));
+            self.bust_cache_with_reason(target, CacheBustSeverity::High, reason)?;
+            report.busted = true;
+        }
+        Ok(report)
+    }
+    
+    /// Forget an entry whose file is gone. Unlike eviction this is not a
+    /// cache decision, so dry-run mode does not apply.
+    pub fn remove_entry(&self, cache_id: &str) -> Option<CacheEvicon> {
+        if !self.cache_evicons.contains_key(cache_id) {
+            self.promote_owner(cache_id);
+        }
+        let (_, evicon) = self.cache_evicons.remove(cache_id)?;
+        self.entry_removed(&evicon, false);
+        Some(evicon)
+    }
+    
+    /// Count an access to an entry, a hit in `metrics`. Returns false for
+    /// unknown entries, which count as misses.
+    pub fn touch(&self, cache_id: &str) -> bool {
+        let hit = self.access(cache_id);
+        let mut counters = self.counters.lock().unwrap();
+        if hit {
+            counters.hits += 1;
+        } else {
+            counters.misses += 1;
+        }
+        hit
+    }
+    
+    fn access(&self, cache_id: &str) -> bool {
+        if !self.cache_evicons.contains_key(cache_id) && !self.promote_owner(cache_id) {
+            return false;
+        }
+        let now = unix_now();
+        let target = match self.cache_evicons.get_mut(cache_id) {
+            Some(mut evicon) => {
+                evicon.last_access = now;
+                evicon.access_frequency = evicon.access_frequency.saturating_add(1);
+                evicon.model_binding.clone()
+            }
+            None => return false,
+        };
+        self.record_access(&target, now);
+        true
+    }
+    
+    /// Feed an access into the target's dimensional vector; a rebuilt
+    /// target is Warm again, a busy one Hot
+    fn record_access(&self, target: &str, now: u64) {
+        let Some(mut diram) = self.diram_dimensions.get_mut(target) else {
+            return;
+        };
+        
+        diram.access_pattern.push(now);
+        if diram.access_pattern.len() > ACCESS_PATTERN_CAPACITY {
+            diram.access_pattern.remove(0);
+        }
+        diram.decay_to(now);
+        diram.hot_path_score += 1.0;
+        let before = diram.cache_state.clone();
+        diram.cache_state = CacheState::Warm;
+        diram.classify();
+        let after = diram.cache_state.clone();
+        drop(diram);
+        
+        if before == CacheState::Stale {
+            self.counters.lock().unwrap().stale_to_hot += 1;
+        }
+        self.state_changed(target, before, after);
+    }
+    
+    /// State of a bound target with a summary of its entries
+    pub fn get_state(&self, target: &str) -> Option<TargetCacheState> {
+        let diram = self.diram_dimensions.get(target)?;
+        let entries = self.list_entries(&EntryFilter::all().target(target));
+        let spilled = self.spilled.get(target).map(|spilled| spilled.value().clone());
+        
+        Some(TargetCacheState {
+            target: target.to_string(),
+            cache_state: diram.cache_state.clone(),
+            hot_path_score: diram.score_at(unix_now()),
+            entries: entries.len() + spilled.as_ref().map_or(0, |spilled| spilled.entries),
+            last_access: entries.iter().map(|entry| entry.last_access).max(),
+            integrity_score: match entries.len() {
+                0 => 100,
+                count => (entries.iter().map(|entry| entry.integrity_score as usize).sum::<usize>() / count) as u8,
+            },
+            bytes: diram.memory_footprint as u64,
+            tier: spilled.map_or(CacheTier::Memory, |spilled| spilled.tier),
+        })
+    }
+    
+    /// Targets by the size of their entries, largest first, each with its
+    /// `top` largest entries
+    pub fn disk_usage(&self, top: usize) -> Vec<TargetUsage> {
+        let mut by_target: HashMap<String, Vec<(String, u64)>> = HashMap::new();
+        for entry in self.cache_evicons.iter() {
+            by_target.entry(entry.model_binding.clone()).or_default().push((entry.key().clone(), entry.size_bytes));
+        }
+        let mut usage: Vec<TargetUsage> = by_target.into_iter()
+            .map(|(target, mut entries)| {
+                entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                TargetUsage {
+                    target,
+                    bytes: entries.iter().map(|(_, size)| size).sum(),
+                    entries: entries.len(),
+                    largest: entries.into_iter().take(top).collect(),
+                }
+            })
+            .collect();
+        // Spilled entries count without being read back to list the largest
+        for spilled in self.spilled.iter() {
+            match usage.iter_mut().find(|usage| &usage.target == spilled.key()) {
+                Some(usage) => {
+                    usage.bytes += spilled.bytes;
+                    usage.entries += spilled.entries;
+                }
+                None => usage.push(TargetUsage {
+                    target: spilled.key().clone(),
+                    bytes: spilled.bytes,
+                    entries: spilled.entries,
+                    largest: Vec::new(),
+                }),
+            }
+        }
+        usage.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.target.cmp(&b.target)));
+        usage
+    }
+    
+    /// State of every bound target, by name
+    pub fn target_states(&self) -> Vec<TargetCacheState> {
+        let mut targets: Vec<String> = self.diram_dimensions.iter().map(|diram| diram.key().clone()).collect();
+        targets.sort();
+        targets.iter().filter_map(|target| self.get_state(target)).collect()
+    }
+    
+    /// The targets and entries of `namespace`, e.g. `project:branch`
+    pub fn namespace(&self, namespace: &str) -> Result<CacheNamespace<'_>> {
+        if namespace.is_empty() || namespace.contains(NAMESPACE_SEPARATOR) || namespace.contains('|') {
+            return Err(anyhow!("Invalid cache namespace {:?}: must be non-empty without '{}' or '|'", namespace, NAMESPACE_SEPARATOR));
+        }
+        Ok(CacheNamespace {
+            manager: self,
+            name: namespace.to_string(),
+        })
+    }
+    
+    /// Namespaces with bound targets or entries, sorted
+    pub fn namespaces(&self) -> Vec<String> {
+        let targets: Vec<String> = self.model_bindings.iter().map(|binding| binding.key().clone())
+            .chain(self.cache_evicons.iter().map(|entry| entry.model_binding.clone()))
+            .collect();
+        let namespaces: BTreeSet<&str> = targets.iter().filter_map(|target| split_namespace(target).0).collect();
+        namespaces.into_iter().map(str::to_string).collect()
+    }
+    
+    /// Remove a namespace's bindings, entries, limits and queued rebuilds,
+    /// e.g. once its branch is deleted. Nothing is published: other daemons
+    /// drop the namespace when told to themselves. Dry-run mode records the
+    /// evictions without applying them.
+    pub fn drop_namespace(&self, namespace: &str) -> NamespaceDropReport {
+        let _batch = self.batch_lock.lock().unwrap();
+        let in_namespace = |key: &str| split_namespace(key).0 == Some(namespace);
+        let spilled: Vec<String> = self.spilled.iter().map(|spilled| spilled.key().clone()).filter(|target| in_namespace(target)).collect();
+        for target in spilled {
+            self.promote(&target);
+        }
+        let entries = self.list_entries(&EntryFilter::all().namespace(namespace));
+        let mut report = NamespaceDropReport {
+            targets: self.model_bindings.iter().filter(|binding| in_namespace(binding.key())).count(),
+            entries: entries.len(),
+            reclaimed_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+            rebuilds: 0,
+        };
+        let reason = format!("namespace {} dropped", namespace);
+        
+        if self.dry_run {
+            for entry in &entries {
+                self.record_dry_run(DryRunAction::Evict, &entry.cache_id, None, &reason);
+            }
+            return report;
+        }
+        
+        for entry in &entries {
+            self.evict_as(&entry.cache_id, "namespace", &reason);
+        }
+        self.model_bindings.retain(|target, _| !in_namespace(target));
+        self.diram_dimensions.retain(|target, _| !in_namespace(target));
+        {
+            let mut limits = self.limits.lock().unwrap();
+            limits.ttl_seconds.retain(|target, _| !in_namespace(target));
+            limits.weights.retain(|target, _| !in_namespace(target));
+        }
+        let queue = &self.heap_prioritizer;
+        let queued = queue.len();
+        queue.cache_entries.retain(|target, _| !in_namespace(target));
+        queue.model_bindings.retain(|target, _| !in_namespace(target));
+        report.rebuilds = queued - queue.len();
+        
+        log::info!("🧺 Dropped namespace {}: {} targets, {} entries ({} bytes), {} queued rebuilds",
+            namespace, report.targets, report.entries, report.reclaimed_bytes, report.rebuilds);
+        report
+    }
+    
+    /// Decay every hot path score to `now`, moving idle targets from Hot to
+    /// Warm to Cold. Returns how many targets changed state.
+    pub fn decay_scores(&self, now: u64) -> usize {
+        let mut changes = Vec::new();
+        for mut diram in self.diram_dimensions.iter_mut() {
+            let before = diram.cache_state.clone();
+            diram.decay_to(now);
+            diram.classify();
+            if diram.cache_state != before {
+                log::debug!("🌡️ {} is now {:?} (hot path score {:.2})", diram.key(), diram.cache_state, diram.hot_path_score);
+                changes.push((diram.key().clone(), before, diram.cache_state.clone()));
+            }
+        }
+        let changed = changes.len();
+        for (target, from, to) in changes {
+            self.state_changed(&target, from, to);
+        }
+        changed
+    }
+    
+    /// Entries matching `filter`, by cache ID
+    pub fn list_entries(&self, filter: &EntryFilter) -> Vec<CacheEvicon> {
+        let mut entries: Vec<CacheEvicon> = self.cache_evicons.iter()
+            .filter(|entry| filter.matches(entry.value()))
+            .map(|entry| entry.value().clone())
+            .collect();
+        entries.sort_by(|a, b| a.cache_id.cmp(&b.cache_id));
+        entries
+    }
+    
+    /// Register a model binding for PID-aware cache management
+    pub fn bind_model(&self, target_name: &str, binding: ModelBinding) -> Result<()> {
+        for dependency in &binding.cache_dependencies {
+            if let Some(mut cycle) = self.dependency_path(dependency, target_name) {
+                cycle.insert(0, target_name.to_string());
+                return Err(anyhow!("Cache dependency cycle: {}", cycle.join(" -> ")));
+            }
+        }
+        self.model_bindings.insert(target_name.to_string(), binding);
+        
+        // Initialize dimensional vector for this model
+        let diram = DiramDimension {
+            vector_id: format!("diram_{}", target_name),
+            hot_path_score: 0.0,
+            memory_footprint: self.footprint_of(target_name),
+            access_pattern: Vec::new(),
+            cache_state: CacheState::Cold,
+            scored_at: unix_now(),
+        };
+        
+        self.diram_dimensions.insert(target_name.to_string(), diram);
+        
+        log::info!("🔗 Model binding established: {}", target_name);
+        Ok(())
+    }
+    
+    /// Dependency chain from `from` to `to` through the bound targets'
+    /// `cache_dependencies`, both ends included
+    fn dependency_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![vec![from.to_string()]];
+        while let Some(path) = stack.pop() {
+            let last = path.last().expect("paths are never empty").clone();
+            if last == to {
+                return Some(path);
+            }
+            if !visited.insert(last.clone()) {
+                continue;
+            }
+            if let Some(binding) = self.model_bindings.get(&last) {
+                for dependency in &binding.cache_dependencies {
+                    let mut next = path.clone();
+                    next.push(dependency.clone());
+                    stack.push(next);
+                }
+            }
+        }
+        None
+    }
+    
+    /// Bound targets listing `target` in their `cache_dependencies`
+    pub fn dependents(&self, target: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self.model_bindings.iter()
+            .filter(|binding| binding.cache_dependencies.iter().any(|dependency| dependency == target))
+            .map(|binding| binding.key().clone())
+            .collect();
+        dependents.sort();
+        dependents
+    }
+    
+    /// Cache eviction algorithm - model-agnostic with OBINexus extensions
+    pub fn cache_evict(&self, strategy: &EvictionStrategy) -> Result<Vec<String>> {
+        self.evict_matching(strategy, |_| true)
+    }
+    
+    /// What `cache_evict` would evict by `strategy`, in order, with each
+    /// entry's eviction score broken down. Model-aware plans score with the
+    /// strategy's weights, the others with each target's configured ones.
+    /// Nothing is evicted.
+    pub fn cache_evict_plan(&self, strategy: &EvictionStrategy) -> Vec<EvictionCandidate> {
+        self.plan_matching(strategy, |_| true)
+    }
+    
+    /// `cache_evict` among the entries `candidate` accepts
+    fn evict_matching(&self, strategy: &EvictionStrategy, candidate: impl Fn(&CacheEvicon) -> bool) -> Result<Vec<String>> {
+        if !self.is_evicting() {
+            log::debug!("Not leading, {} eviction left to the leader", strategy.label());
+            return Ok(Vec::new());
+        }
+        let _batch = self.batch_lock.lock().unwrap();
+        let plan = self.plan_matching(strategy, candidate);
+        for victim in &plan {
+            self.evict_as(&victim.cache_id, strategy.label(), &victim.reason);
+            log::info!("🗑️ Evicted cache entry: {}", victim.cache_id);
+        }
+        
+        // Update heap prioritizer after eviction
+        self.update_heap_priorities()?;
+        
+        Ok(plan.into_iter().map(|victim| victim.cache_id).collect())
+    }
+    
+    fn plan_matching(&self, strategy: &EvictionStrategy, candidate: impl Fn(&CacheEvicon) -> bool) -> Vec<EvictionCandidate> {
+        let configured = self.limits.lock().unwrap().weights.clone();
+        let default_weights = ModelWeights::default();
+        let explain = |evicon: CacheEvicon, weights: &ModelWeights, reason: &str| EvictionCandidate {
+            score: self.eviction_score(&evicon, weights),
+            cache_id: evicon.cache_id,
+            target: evicon.model_binding,
+            reason: reason.to_string(),
+        };
+        let first = |order: &dyn Fn(&CacheEvicon, &CacheEvicon) -> Ordering, reason: &str| -> Vec<EvictionCandidate> {
+            self.first_candidate(&candidate, order)
+                .map(|evicon| {
+                    let weights = configured.get(&evicon.model_binding).unwrap_or(&default_weights);
+                    explain(evicon, weights, reason)
+                })
+                .into_iter()
+                .collect()
+        };
+        
+        match strategy {
+            EvictionStrategy::ModelAware(weights) => {
+                // OBINexus model-aware eviction based on language priority and dependency impact
+                // Owned copies: evicting while holding map references would
+                // deadlock on the shard
+                let mut candidates: Vec<CacheEvicon> = self.cache_evicons.iter()
+                    .map(|entry| entry.value().clone())
+                    .filter(|evicon| candidate(evicon))
+                    .filter(|evicon| {
+                        let diram = self.diram_dimensions.get(&evicon.model_binding);
+                        diram.is_some_and(|d| d.cache_state == CacheState::Cold || d.cache_state == CacheState::Stale)
+                    })
+                    .collect();
+                
+                // Sort by composite score: access frequency + language priority + dependency depth
+                candidates.sort_by(|a, b| {
+                    let score_a = self.calculate_eviction_score(a, weights);
+                    let score_b = self.calculate_eviction_score(b, weights);
+                    score_a.partial_cmp(&score_b).unwrap_or(Ordering::Equal)
+                });
+                
+                // Evict lowest-priority entries
+                candidates.into_iter()
+                    .take(3)
+                    .map(|evicon| explain(evicon, weights, "model-aware: cold or stale entry"))
+                    .collect()
+            }
+            
+            // Traditional LRU implementation
+            EvictionStrategy::LRU => first(&|a, b| a.last_access.cmp(&b.last_access), "lru: least recently accessed"),
+            
+            EvictionStrategy::MRU => first(&|a, b| b.last_access.cmp(&a.last_access), "mru: most recently accessed"),
+            
+            // Equally rare entries go least recently used first
+            EvictionStrategy::LFU => first(
+                &|a, b| a.access_frequency.cmp(&b.access_frequency).then(a.last_access.cmp(&b.last_access)),
+                "lfu: least frequently accessed",
+            ),
+            
+            EvictionStrategy::FIFO => first(&|a, b| a.inserted_at.cmp(&b.inserted_at), "fifo: inserted first"),
+        }
+    }
+    
+    /// Entry that sorts first by `order`; ties go to the smallest key so
+    /// the choice does not depend on map iteration order
+    fn first_candidate(
+        &self,
+        candidate: impl Fn(&CacheEvicon) -> bool,
+        order: impl Fn(&CacheEvicon, &CacheEvicon) -> Ordering,
+    ) -> Option<CacheEvicon> {
+        self.cache_evicons.iter()
+            .filter(|entry| candidate(entry.value()))
+            .min_by(|a, b| order(a.value(), b.value()).then_with(|| a.key().cmp(b.key())))
+            .map(|entry| entry.value().clone())
+    }
+    
+    /// Expire entries past their target's TTL, then evict model-aware until
+    /// the cache is within `max_entries` and `max_bytes`: entries of Stale
+    /// and Cold targets first, then by eviction score, then least recently
+    /// used. Dry-run mode records the evictions without applying them.
+    pub fn enforce_limits(&self) -> ExpiryReport {
+        if !self.is_evicting() {
+            return ExpiryReport::default();
+        }
+        let _batch = self.batch_lock.lock().unwrap();
+        let limits = self.limits.lock().unwrap().clone();
+        let now = unix_now();
+        let mut report = ExpiryReport::default();
+        self.decay_scores(now);
+        
+        let mut entries: Vec<CacheEvicon> = self.cache_evicons.iter().map(|entry| entry.value().clone()).collect();
+        entries.retain(|evicon| {
+            let expired = limits.ttl_seconds.get(&evicon.model_binding)
+                .is_some_and(|ttl| now.saturating_sub(evicon.last_access) > *ttl);
+            if expired {
+                self.evict_as(&evicon.cache_id, "ttl", "ttl: not accessed within the target's TTL");
+                report.expired += 1;
+                report.reclaimed_bytes += evicon.size_bytes;
+            }
+            !expired
+        });
+        
+        let state_rank = |target: &str| match self.diram_dimensions.get(target).map(|d| d.cache_state.clone()) {
+            Some(CacheState::Stale) => 0,
+            Some(CacheState::Cold) | None => 1,
+            Some(CacheState::Warm) => 2,
+            Some(CacheState::Hot) => 3,
+        };
+        let default_weights = ModelWeights::default();
+        let score = |evicon: &CacheEvicon| {
+            let weights = limits.weights.get(&evicon.model_binding).unwrap_or(&default_weights);
+            self.calculate_eviction_score(evicon, weights)
+        };
+        entries.sort_by(|a, b| {
+            state_rank(&a.model_binding).cmp(&state_rank(&b.model_binding))
+                .then_with(|| score(a).partial_cmp(&score(b)).unwrap_or(Ordering::Equal))
+                .then(a.last_access.cmp(&b.last_access))
+                .then_with(|| a.cache_id.cmp(&b.cache_id))
+        });
+        
+        let mut count = entries.len();
+        let mut bytes: u64 = entries.iter().map(|evicon| evicon.size_bytes).sum();
+        for evicon in &entries {
+            let over = limits.max_entries.is_some_and(|max| count > max)
+                || limits.max_bytes.is_some_and(|max| bytes > max);
+            if !over {
+                break;
+            }
+            self.evict_as(&evicon.cache_id, "limits", "limits: cache over max_entries or max_bytes");
+            count -= 1;
+            bytes -= evicon.size_bytes;
+            report.evicted += 1;
+            report.reclaimed_bytes += evicon.size_bytes;
+        }
+        
+        if report != ExpiryReport::default() {
+            log::info!("♻️ Cache expiry: {} expired, {} evicted, {} bytes reclaimed",
+                report.expired, report.evicted, report.reclaimed_bytes);
+        }
+        if !self.dry_run {
+            let mut stats = self.reclaim_stats.lock().unwrap();
+            stats.runs += 1;
+            stats.expired += report.expired as u64;
+            stats.evicted += report.evicted as u64;
+            stats.reclaimed_bytes += report.reclaimed_bytes;
+        }
+        report
+    }
+    
+    /// Run `enforce_limits` every `interval` on a background thread
+    pub fn spawn_expiry(manager: Arc<Self>, interval: Duration) -> BackgroundTask {
+        BackgroundTask::every(interval, move || {
+            manager.enforce_limits();
+        })
+    }
+    
+    /// Apply the busts other daemons publish on the bus, on a background
+    /// thread. Lost connections are retried with exponential backoff.
+    /// `None` when the bus is not distributed.
+    pub fn spawn_subscriber(manager: Arc<Self>) -> Option<BackgroundTask> {
+        if !manager.bus().distributed() {
+            return None;
+        }
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            let (min, max) = RECONNECT_BACKOFF;
+            let mut backoff = min;
+            loop {
+                match manager.subscribe(&stopped, &mut backoff) {
+                    Ok(()) => break,
+                    Err(e) => log::warn!("⚠️  Bust subscriber disconnected, retrying in {:?}: {}", backoff, e),
+                }
+                if !matches!(stopped.recv_timeout(backoff), Err(mpsc::RecvTimeoutError::Timeout)) {
+                    break;
+                }
+                backoff = (backoff * 2).min(max);
+            }
+        });
+        
+        Some(BackgroundTask {
+            stop: Some(stop),
+            handle: Some(handle),
+        })
+    }
+    
+    /// Receive busts until stopped (`Ok`) or the connection fails
+    fn subscribe(&self, stopped: &mpsc::Receiver<()>, backoff: &mut Duration) -> Result<()> {
+        let bus = self.bus();
+        let channels = self.bus_sync().channels();
+        let mut subscription = bus.subscribe(&channels)?;
+        log::info!("📡 Receiving remote busts from {} on {}", bus.name(), channels.join(", "));
+        *backoff = RECONNECT_BACKOFF.0;
+        
+        loop {
+            if !matches!(stopped.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                return Ok(());
+            }
+            // Wake up regularly to notice a stop request
+            let Some(payload) = subscription.next_message(Duration::from_secs(1))? else {
+                continue;
+            };
+            if let Err(e) = self.apply_remote_bust(&payload) {
+                log::warn!("Remote bust {:?} not applied: {}", payload, e);
+            }
+        }
+    }
+    
+    /// Apply a bust received from another daemon. Our own busts coming back
+    /// are ignored, and remote busts are neither published again nor
+    /// cascaded: the origin publishes every target it busts. Returns whether
+    /// the bust was applied. A batch is applied as one, like `bust_many`.
+    pub fn apply_remote_bust(&self, payload: &str) -> Result<bool> {
+        let (origin, busts) = parse_bust_message(payload)
+            .ok_or_else(|| anyhow!("Malformed bust message: {}", payload))?;
+        if origin == Some(self.bus_sync().node_id.as_str()) {
+            return Ok(false);
+        }
+        let busts: Vec<_> = busts
+            .into_iter()
+            .map(|(target, severity, reason)| {
+                let reason = reason
+                    .unwrap_or_else(|| format!("remote bust from {}", origin.unwrap_or("unknown node")).into());
+                (target, severity, reason)
+            })
+            .filter(|(target, severity, reason)| match self.pre_bust(target, severity, reason) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Remote bust of {} not applied: {}", target, e);
+                    false
+                }
+            })
+            .collect();
+        {
+            let _batch = self.batch_lock.lock().unwrap();
+            for (target, severity, reason) in &busts {
+                self.apply_bust(target, severity.clone(), reason, BustTrigger::Remote, false)?;
+            }
+        }
+        for (target, severity, reason) in &busts {
+            self.after_bust(target, severity, reason);
+        }
+        Ok(true)
+    }
+    
+    /// Calculate model-aware eviction score for OBINexus framework
     fn calculate_eviction_score(&self, evicon: &CacheEvicon, weights: &ModelWeights) -> f32 {
+        self.eviction_score(evicon, weights).total
+    }
+    
+    /// Model-aware eviction score by component, for `cache_evict_plan`
+    fn eviction_score(&self, evicon: &CacheEvicon, weights: &ModelWeights) -> EvictionScore {
         let access_component = evicon.access_frequency as f32 * 0.3;
         let integrity_component = evicon.integrity_score as f32 * 0.2;
         let dependency_component = evicon.dependency_depth as f32 * weights.dependency_impact;
         let language_component = weights.language_priority;
+        let size_component = -(evicon.size_bytes as f32 / MIB).ln_1p();
         let critical_path_modifier = if weights.critical_path { 2.0 } else { 1.0 };
         
-        (access_component + integrity_component + dependency_component + language_component) 
-            * critical_path_modifier
+        EvictionScore {
+            access: access_component,
+            integrity: integrity_component,
+            dependency: dependency_component,
+            language: language_component,
+            size: size_component,
+            critical_path: critical_path_modifier,
+            total: (access_component + integrity_component + dependency_component + language_component + size_component)
+                * critical_path_modifier,
+        }
+    }
+    
+    /// Trigger cache bust with dimensional analysis
+    pub fn bust_cache(&self, target: &str, severity: CacheBustSeverity) -> Result<()> {
+        self.bust_cache_with_reason(target, severity, "unspecified")
+    }
+    
+    /// Trigger cache bust, recording why it happened in bust records, the
+    /// audit log and bus messages. Strings are recorded as `BustReason::Other`.
+    /// Cascades to dependents when enabled with `with_cascade`
+    pub fn bust_cache_with_reason(&self, target: &str, severity: CacheBustSeverity, reason: impl Into<BustReason>) -> Result<()> {
+        self.bust_cache_reclaiming(target, severity, reason).map(|_| ())
+    }
+    
+    /// `bust_cache_with_reason`, returning the bytes the bust executors and
+    /// compiler caches freed on disk, cascades included
+    pub fn bust_cache_reclaiming(&self, target: &str, severity: CacheBustSeverity, reason: impl Into<BustReason>) -> Result<u64> {
+        let reason = reason.into();
+        if self.cascade.load(AtomicOrdering::Relaxed) {
+            return self.cascade(target, severity, &reason).map(|(_, reclaimed)| reclaimed);
+        }
+        self.bust_single(target, severity, &reason, BustTrigger::Direct, true)
+    }
+    
+    /// `bust_cache_reclaiming` on tokio's blocking pool, for callers on the
+    /// runtime: publishing on the bus and the bust executors' disk IO would
+    /// otherwise stall a worker thread
+    pub async fn bust_cache_async(
+        self: &Arc<Self>,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: impl Into<BustReason>,
+    ) -> Result<u64> {
+        let manager = Arc::clone(self);
+        let (target, reason) = (target.to_string(), reason.into());
+        tokio::task::spawn_blocking(move || manager.bust_cache_reclaiming(&target, severity, reason)).await?
+    }
+    
+    /// `cache_evict` on tokio's blocking pool; see `bust_cache_async`
+    pub async fn cache_evict_async(self: &Arc<Self>, strategy: EvictionStrategy) -> Result<Vec<String>> {
+        let manager = Arc::clone(self);
+        tokio::task::spawn_blocking(move || manager.cache_evict(&strategy)).await?
+    }
+    
+    /// Bust `target`, then everything depending on it, each dependency hop
+    /// one severity level milder. Every target is busted once, at the
+    /// severity of its shortest path, so cycles cannot loop. Returns the
+    /// busted targets in order.
+    pub fn bust_cache_cascade(
+        &self,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: impl Into<BustReason>,
+    ) -> Result<Vec<(String, CacheBustSeverity)>> {
+        self.cascade(target, severity, &reason.into()).map(|(plan, _)| plan)
+    }
+    
+    /// `bust_cache_cascade`, also returning the bytes freed on disk
+    fn cascade(&self, target: &str, severity: CacheBustSeverity, reason: &BustReason) -> Result<(Vec<(String, CacheBustSeverity)>, u64)> {
+        let plan = self.cascade_plan(target, severity);
+        let mut reclaimed = 0;
+        for (current, severity) in &plan {
+            if current == target {
+                reclaimed += self.bust_single(current, severity.clone(), reason, BustTrigger::Direct, true)?;
+            } else {
+                log::info!("🔗 Cascading bust of {} to dependent {} ({:?})", target, current, severity);
+                let reason = BustReason::cascade(target, reason);
+                reclaimed += self.bust_single(current, severity.clone(), &reason, BustTrigger::Cascade, true)?;
+            }
+        }
+        Ok((plan, reclaimed))
+    }
+    
+    /// Targets a cascading bust of `target` reaches, breadth first
+    fn cascade_plan(&self, target: &str, severity: CacheBustSeverity) -> Vec<(String, CacheBustSeverity)> {
+        let mut plan = Vec::new();
+        let mut visited = HashSet::from([target.to_string()]);
+        let mut queue = VecDeque::from([(target.to_string(), severity)]);
+        
+        while let Some((current, severity)) = queue.pop_front() {
+            for dependent in self.dependents(&current) {
+                if visited.insert(dependent.clone()) {
+                    queue.push_back((dependent, severity.decayed()));
+                }
+            }
+            plan.push((current, severity));
+        }
+        plan
     }
     
-    /// Trigger cache bust with dimensional analysis
-    pub fn bust_cache(&self, target: &str, severity: CacheBustSeverity) -> Result<()> {
-        log::warn!("💥 Cache bust triggered for target: {} (severity: {:?})", target, severity);
+    /// Apply a set of busts as one. Evictions wait until the whole batch is
+    /// applied, so none sees it half done; with cascading enabled the
+    /// batch's cascades are merged and every target is busted once, at the
+    /// most severe level asked of it. The batch is published as a single
+    /// bus message and sent as a single notification. Returns the result of
+    /// each bust, the requested targets first.
+    pub fn bust_many(&self, busts: &[(String, CacheBustSeverity)], reason: impl Into<BustReason>) -> Vec<BatchBustResult> {
+        let reason = reason.into();
+        let mut planned: Vec<BatchBustResult> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut plan = |target: String, severity: CacheBustSeverity, cascaded_from: Option<&str>| {
+            match index.get(&target).copied() {
+                Some(i) if severity > planned[i].severity => {
+                    planned[i].severity = severity;
+                    if planned[i].cascaded_from.is_some() {
+                        planned[i].cascaded_from = cascaded_from.map(str::to_string);
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    index.insert(target.clone(), planned.len());
+                    planned.push(BatchBustResult {
+                        target,
+                        severity,
+                        cascaded_from: cascaded_from.map(str::to_string),
+                        error: None,
+                        reclaimed_bytes: 0,
+                    });
+                }
+            }
+        };
+        for (target, severity) in busts {
+            plan(target.clone(), severity.clone(), None);
+        }
+        if self.cascade.load(AtomicOrdering::Relaxed) {
+            for (target, severity) in busts {
+                for (dependent, severity) in self.cascade_plan(target, severity.clone()).into_iter().skip(1) {
+                    plan(dependent, severity, Some(target.as_str()));
+                }
+            }
+        }
+        
+        let reasons: Vec<(BustReason, BustTrigger)> = planned
+            .iter()
+            .map(|result| match &result.cascaded_from {
+                Some(origin) => (BustReason::cascade(origin, &reason), BustTrigger::Cascade),
+                None => (reason.clone(), BustTrigger::Batch),
+            })
+            .collect();
+        for (result, (reason, _)) in planned.iter_mut().zip(&reasons) {
+            if let Err(e) = self.pre_bust(&result.target, &result.severity, reason) {
+                result.error = Some(e.to_string());
+            }
+        }
+        
+        let mut applied = Vec::new();
+        {
+            let _batch = self.batch_lock.lock().unwrap();
+            for (result, (reason, trigger)) in planned.iter_mut().zip(&reasons) {
+                if result.error.is_some() {
+                    continue;
+                }
+                match self.apply_bust(&result.target, result.severity.clone(), reason, *trigger, false) {
+                    Ok(reclaimed) => {
+                        result.reclaimed_bytes = reclaimed;
+                        applied.push((result.target.clone(), result.severity.clone(), reason.clone()));
+                    }
+                    Err(e) => result.error = Some(e.to_string()),
+                }
+            }
+        }
+        for (target, severity, reason) in &applied {
+            self.after_bust(target, severity, reason);
+        }
+        if !self.dry_run && !applied.is_empty() {
+            self.publish_busts(&applied);
+            self.notify_batch(&planned, &reason);
+        }
+        log::info!("💥 Batch of {} busts applied, {} failed", applied.len(), planned.len() - applied.len());
+        planned
+    }
+    
+    /// `bust_many` on tokio's blocking pool; see `bust_cache_async`
+    pub async fn bust_many_async(
+        self: &Arc<Self>,
+        busts: Vec<(String, CacheBustSeverity)>,
+        reason: impl Into<BustReason>,
+    ) -> Result<Vec<BatchBustResult>> {
+        let manager = Arc::clone(self);
+        let reason = reason.into();
+        Ok(tokio::task::spawn_blocking(move || manager.bust_many(&busts, reason)).await?)
+    }
+    
+    /// Bust one target, running its hooks around it; `publish` shares the
+    /// bust with other daemons. Returns the bytes its executor and compiler
+    /// caches freed on disk.
+    fn bust_single(
+        &self,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: &BustReason,
+        trigger: BustTrigger,
+        publish: bool,
+    ) -> Result<u64> {
+        self.pre_bust(target, &severity, reason)?;
+        let reclaimed = self.apply_bust(target, severity.clone(), reason, trigger, publish)?;
+        self.after_bust(target, &severity, reason);
+        Ok(reclaimed)
+    }
+    
+    /// Run the target's `pre_bust` hook; an error when it failed and the
+    /// target aborts busts on that
+    fn pre_bust(&self, target: &str, severity: &CacheBustSeverity, reason: &BustReason) -> Result<()> {
+        match self.run_hook(target, HookStage::PreBust, severity, reason) {
+            Some((outcome, true)) if !outcome.success => {
+                Err(anyhow!("pre_bust hook of {} failed, bust aborted: {}", target, outcome.output))
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    /// Run the target's `on_bust`, then its `post_bust` hook
+    fn after_bust(&self, target: &str, severity: &CacheBustSeverity, reason: &BustReason) {
+        self.run_hook(target, HookStage::OnBust, severity, reason);
+        self.run_hook(target, HookStage::PostBust, severity, reason);
+    }
+    
+    /// Run and audit the target's hook of `stage`, if it has one; with
+    /// whether the target aborts busts on a failing `pre_bust`
+    fn run_hook(&self, target: &str, stage: HookStage, severity: &CacheBustSeverity, reason: &BustReason) -> Option<(HookOutcome, bool)> {
+        if self.dry_run {
+            return None;
+        }
+        let (hooks, cwd) = self.bust_hooks(target)?;
+        let outcome = hooks.run(stage, cwd.as_deref().map(Path::new), target, severity.label(), &reason.to_string())?;
+        self.audit(target, AuditEvent::Hook(outcome.clone()));
+        Some((outcome, hooks.pre_bust_aborts))
+    }
+    
+    /// The bust itself, without hooks: callers holding the batch lock run
+    /// those around it, outside the lock
+    fn apply_bust(
+        &self,
+        target: &str,
+        severity: CacheBustSeverity,
+        reason: &BustReason,
+        trigger: BustTrigger,
+        publish: bool,
+    ) -> Result<u64> {
+        if self.dry_run {
+            for action in self.executor_plan(target, &severity) {
+                log::info!("🔍 Dry run, bust of {} would {}", target, action);
+            }
+            if let Some((hooks, _)) = self.bust_hooks(target) {
+                for stage in [HookStage::PreBust, HookStage::OnBust, HookStage::PostBust] {
+                    if let Some(command) = hooks.command(stage) {
+                        log::info!("🔍 Dry run, bust of {} would run {} hook: {}", target, stage.as_str(), command);
+                    }
+                }
+            }
+            self.record_dry_run(DryRunAction::Bust, target, Some(severity), &reason.to_string());
+            return Ok(0);
+        }
+        
+        log::warn!("💥 Cache bust triggered for target: {} (severity: {:?}): {}", target, severity, reason);
+        self.audit(
+            target,
+            AuditEvent::Bust {
+                severity: severity.clone(),
+                reason: reason.clone(),
+                trigger,
+            },
+        );
+        *self.counters.lock().unwrap().busts.entry(severity.label().to_string()).or_insert(0) += 1;
         
         // Update dimensional vector state
-        if let Some(mut diram) = self.diram_dimensions.get_mut(target) {
-            diram.cache_state = CacheState::Stale;
+        let before = self.diram_dimensions.get_mut(target).map(|mut diram| {
+            let before = std::mem::replace(&mut diram.cache_state, CacheState::Stale);
+            diram.decay_to(unix_now());
             diram.hot_path_score *= 0.5; // Reduce hot path score after bust
+            before
+        });
+        if let Some(before) = before {
+            self.state_changed_by(target, before, CacheState::Stale, Some(reason));
         }
         
-        // Remove cache entries for this target
+        // Remove cache entries for this target, spilled ones included
+        self.promote(target);
         let removed_keys: Vec<_> = self.cache_evicons.iter()
             .filter(|entry| entry.model_binding == target)
             .map(|entry| entry.key().clone())
             .collect();
         
         for key in removed_keys {
-            self.cache_evicons.remove(&key);
+            if let Some((_, evicon)) = self.cache_evicons.remove(&key) {
+                self.entry_removed(&evicon, true);
+            }
         }
         
+        let reclaimed = self.run_executor(target, &severity);
+        self.record_bust(target, &severity, reason, reclaimed);
+        
         // Queue rebuild in heap prioritizer
-        self.queue_rebuild(target, severity)?;
+        self.queue_rebuild(target, severity.clone())?;
         
-        // Optionally notify Redis for distributed coordination
-        if let Some(ref redis_client) = self.redis_client {
-            let mut conn = redis_client.get_connection()?;
-            redis::cmd("PUBLISH")
-                .arg("bustcall:cache_bust")
-                .arg(format!("{}:{:?}", target, severity))
-                .execute(&mut conn);
+        if publish {
+            self.publish_busts(&[(target.to_string(), severity, reason.clone())]);
         }
         
-        Ok(())
+        Ok(reclaimed)
+    }
+    
+    /// Report a batch in one notification, Warning when part of it failed
+    fn notify_batch(&self, results: &[BatchBustResult], reason: &BustReason) {
+        let Some(manager) = self.notifications.lock().unwrap().clone() else {
+            return;
+        };
+        let failed = results.iter().filter(|result| result.error.is_some()).count();
+        let level = if failed > 0 { NotificationLevel::Warning } else { NotificationLevel::Info };
+        let targets: Vec<String> = results
+            .iter()
+            .map(|result| format!("{} ({})", result.target, result.severity.label()))
+            .collect();
+        let notification = Notification::new(
+            level,
+            &format!("Batch of {} busts, {} failed: {}", results.len(), failed, targets.join(", ")),
+        )
+        .title("Batch bust")
+        .component("cache")
+        .with_metadata("reason", &reason.to_string());
+        if let Err(e) = manager.send_structured(&notification) {
+            log::warn!("Batch bust notification not sent: {}", e);
+        }
+    }
+    
+    /// Share busts with other daemons; an unreachable bus must not fail
+    /// the busts themselves
+    fn publish_busts(&self, busts: &[(String, CacheBustSeverity, BustReason)]) {
+        let sync = self.bus_sync();
+        let bus = self.bus();
+        if let Err(e) = bus.publish(&sync.channel, &bust_message(&sync.node_id, busts)) {
+            let targets: Vec<&str> = busts.iter().map(|(target, _, _)| target.as_str()).collect();
+            log::debug!("Busts of {} not published on {}: {}", targets.join(", "), bus.name(), e);
+        }
+    }
+    
+    /// Runtime, root and compiler caches of a bound target, if it has a path
+    fn executor_root(&self, target: &str) -> Option<(String, String, Vec<CompilerCache>)> {
+        let binding = self.model_bindings.get(target)?;
+        (!binding.path.is_empty()).then(|| (binding.runtime.clone(), binding.path.clone(), binding.compiler_caches.clone()))
+    }
+    
+    /// Hooks of a bound target that has any, with the directory they run in
+    fn bust_hooks(&self, target: &str) -> Option<(BustHooks, Option<String>)> {
+        let binding = self.model_bindings.get(target)?;
+        (!binding.hooks.is_empty()).then(|| (binding.hooks.clone(), (!binding.path.is_empty()).then(|| binding.path.clone())))
+    }
+    
+    fn executor_plan(&self, target: &str, severity: &CacheBustSeverity) -> Vec<BustAction> {
+        let executors = self.executors.lock().unwrap().clone();
+        match (executors, self.executor_root(target)) {
+            (Some(executors), Some((runtime, root, caches))) => executors.plan_target(&runtime, Path::new(&root), &caches, severity),
+            _ => Vec::new(),
+        }
+    }
+    
+    /// Clear what the target keeps on disk, returning the bytes freed
+    fn run_executor(&self, target: &str, severity: &CacheBustSeverity) -> u64 {
+        let Some(executors) = self.executors.lock().unwrap().clone() else {
+            return 0;
+        };
+        let Some((runtime, root, caches)) = self.executor_root(target) else {
+            return 0;
+        };
+        let Some(outcome) = executors.execute_target(&runtime, Path::new(&root), &caches, severity) else {
+            return 0;
+        };
+        log::info!(
+            "🧹 Cleared {} on-disk cache: {} dirs removed, {} commands run, {} bytes reclaimed",
+            target,
+            outcome.removed.len(),
+            outcome.commands.len(),
+            outcome.reclaimed_bytes
+        );
+        outcome.reclaimed_bytes
     }
     
     fn queue_rebuild(&self, target: &str, severity: CacheBustSeverity) -> Result<()> {
@@ -254,49 +2559,910 @@ impl DimensionalCacheManager {
             timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
         };
         
-        let mut heap = self.heap_prioritizer.lock().unwrap();
-        heap.cache_entries.push(entry);
+        self.heap_prioritizer.push(entry);
         
         Ok(())
     }
     
+    /// Take the most urgent queued rebuild. Other queued rebuilds of the
+    /// same target are dropped, this one covers them.
+    pub fn next_rebuild(&self) -> Option<PriorityEntry> {
+        self.heap_prioritizer.pop_target()
+    }
+    
+    pub fn pending_rebuilds(&self) -> usize {
+        self.heap_prioritizer.len()
+    }
+    
+    /// Record a finished rebuild: a successful one clears Stale, leaving
+    /// the target Hot, Warm or Cold by its score, and either way its duration moves the target's build cost, which
+    /// weighs model-aware eviction. A minute maps to a cost of 0.5.
+    pub fn complete_rebuild(&self, target: &str, duration: Duration, success: bool) {
+        if success {
+            let rebuilt = self.diram_dimensions.get_mut(target).and_then(|mut diram| {
+                if diram.cache_state != CacheState::Stale {
+                    return None;
+                }
+                diram.cache_state = CacheState::Cold;
+                diram.decay_to(unix_now());
+                diram.classify();
+                Some(diram.cache_state.clone())
+            });
+            if let Some(state) = rebuilt {
+                self.state_changed(target, CacheState::Stale, state);
+            }
+        }
+        
+        let seconds = duration.as_secs_f32();
+        let observed = seconds / (seconds + 60.0);
+        let mut limits = self.limits.lock().unwrap();
+        let weights = limits.weights.entry(target.to_string()).or_default();
+        weights.build_cost = weights.build_cost * (1.0 - BUILD_COST_SMOOTHING) + observed * BUILD_COST_SMOOTHING;
+        self.heap_prioritizer.model_bindings.insert(target.to_string(), weights.clone());
+    }
+    
     fn update_heap_priorities(&self) -> Result<()> {
         // Recalculate priority scores based on current dimensional state
         // This would integrate with the CI/CD pipeline to schedule rebuilds
         Ok(())
     }
     
-    /// Monitor PID changes and trigger appropriate cache actions
-    pub fn monitor_pid_changes(&self, target: &str, old_pid: Option<u32>, new_pid: Option<u32>) -> Result<()> {
-        if old_pid != new_pid {
-            log::info!("🔄 PID change detected for {}: {:?} -> {:?}", target, old_pid, new_pid);
+    /// Monitor PID changes and trigger appropriate cache actions; a pid
+    /// that came back with another start time is a restart too
+    pub fn monitor_pid_changes(&self, target: &str, old: Option<ProcessIdentity>, new: Option<ProcessIdentity>) -> Result<()> {
+        if old != new {
+            let old_pid = old.map(|process| process.pid);
+            let new_pid = new.map(|process| process.pid);
+            if old_pid == new_pid {
+                log::info!("🔄 PID {:?} of {} was reused by a new process", new_pid, target);
+            } else {
+                log::info!("🔄 PID change detected for {}: {:?} -> {:?}", target, old_pid, new_pid);
+            }
             
             // Update model binding with new PID
             if let Some(mut binding) = self.model_bindings.get_mut(target) {
                 binding.pid = new_pid;
+                binding.pid_start_time = new.map(|process| process.start_time);
             }
             
             // Trigger cache bust for PID mutation
-            self.bust_cache(target, CacheBustSeverity::Medium)?;
+            let reason = match new_pid {
+                Some(pid) => BustReason::PidRestarted { pids: vec![pid] },
+                None => BustReason::PidDied { pids: old_pid.into_iter().collect() },
+            };
+            self.bust_cache_with_reason(target, CacheBustSeverity::Medium, reason)?;
         }
         
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+/// Ordered from mildest to most severe
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CacheBustSeverity {
+    #[serde(alias = "low")]
     Low,      // File change, soft rebuild
+    #[serde(alias = "medium")]
     Medium,   // PID change, moderate rebuild
+    #[serde(alias = "high")]
     High,     // Dependency change, full rebuild
+    #[serde(alias = "critical")]
     Critical, // System failure, emergency rebuild
 }
 
+impl CacheBustSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CacheBustSeverity::Low => "low",
+            CacheBustSeverity::Medium => "medium",
+            CacheBustSeverity::High => "high",
+            CacheBustSeverity::Critical => "critical",
+        }
+    }
+    
+    /// One level milder, for the dependents of a busted target; Low stays
+    /// Low so dependents are always invalidated
+    pub fn decayed(&self) -> Self {
+        match self {
+            CacheBustSeverity::Critical => CacheBustSeverity::High,
+            CacheBustSeverity::High => CacheBustSeverity::Medium,
+            CacheBustSeverity::Medium | CacheBustSeverity::Low => CacheBustSeverity::Low,
+        }
+    }
+}
+
+/// Why a target was busted, carried with the bust into records, the audit
+/// log, bus messages and the HTTP API. Free-form reasons are `Other`, which
+/// plain strings convert to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BustReason {
+    /// A watched file was created or modified
+    FileChanged { path: String },
+    FileRemoved { path: String },
+    /// A burst of `events` file events, coalesced into one bust
+    FilesChanged { paths: Vec<String>, events: usize },
+    /// A watched directory was deleted and came back with other files
+    WatchPathReplaced { path: String },
+    /// Caught in a storm of `events` file events over `seconds`, busted
+    /// once when it subsided
+    EventStorm { events: usize, seconds: u64 },
+    /// Processes of the target's runtime exited
+    PidDied { pids: Vec<u32> },
+    /// The target's runtime came back up
+    PidRestarted { pids: Vec<u32> },
+    /// A running process's executable, interpreter or environment changed
+    EnvironmentChanged { pid: u32, changes: Vec<String> },
+    /// Asked for through the API or the CLI
+    Manual {
+        #[serde(default)]
+        user: Option<String>,
+    },
+    /// Reached from the bust of `from`, a target this one depends on
+    DependencyCascade { from: String, cause: Box<BustReason> },
+    Other { detail: String },
+}
+
+impl BustReason {
+    /// The `kind` tag the reason serializes with
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BustReason::FileChanged { .. } => "file_changed",
+            BustReason::FileRemoved { .. } => "file_removed",
+            BustReason::FilesChanged { .. } => "files_changed",
+            BustReason::WatchPathReplaced { .. } => "watch_path_replaced",
+            BustReason::EventStorm { .. } => "event_storm",
+            BustReason::PidDied { .. } => "pid_died",
+            BustReason::PidRestarted { .. } => "pid_restarted",
+            BustReason::EnvironmentChanged { .. } => "environment_changed",
+            BustReason::Manual { .. } => "manual",
+            BustReason::DependencyCascade { .. } => "dependency_cascade",
+            BustReason::Other { .. } => "other",
+        }
+    }
+    
+    pub fn cascade(from: &str, cause: &BustReason) -> Self {
+        BustReason::DependencyCascade {
+            from: from.to_string(),
+            cause: Box::new(cause.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for BustReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pids = |pids: &[u32]| pids.iter().map(u32::to_string).collect::<Vec<_>>().join(", ");
+        match self {
+            BustReason::FileChanged { path } => write!(f, "file changed: {}", path),
+            BustReason::FileRemoved { path } => write!(f, "file removed: {}", path),
+            BustReason::FilesChanged { paths, events } => {
+                write!(f, "{} events on {} files: {}", events, paths.len(), paths.iter().take(3).cloned().collect::<Vec<_>>().join(", "))?;
+                if paths.len() > 3 {
+                    write!(f, " and {} more", paths.len() - 3)?;
+                }
+                Ok(())
+            }
+            BustReason::WatchPathReplaced { path } => write!(f, "watch path replaced: {}", path),
+            BustReason::EventStorm { events, seconds } => write!(f, "event storm of {} events over {}s", events, seconds),
+            BustReason::PidDied { pids: died } => write!(f, "pid {} died", pids(died)),
+            BustReason::PidRestarted { pids: started } => write!(f, "restarted as pid {}", pids(started)),
+            BustReason::EnvironmentChanged { pid, changes } => write!(f, "pid {} changed: {}", pid, changes.join(", ")),
+            BustReason::Manual { user: Some(user) } => write!(f, "manual bust by {}", user),
+            BustReason::Manual { user: None } => write!(f, "manual bust"),
+            BustReason::DependencyCascade { from, cause } => write!(f, "cascade from {}: {}", from, cause),
+            BustReason::Other { detail } => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl From<&str> for BustReason {
+    fn from(detail: &str) -> Self {
+        BustReason::Other { detail: detail.to_string() }
+    }
+}
+
+impl From<String> for BustReason {
+    fn from(detail: String) -> Self {
+        BustReason::Other { detail }
+    }
+}
+
 impl HeapPrioritizer {
     fn new() -> Self {
         HeapPrioritizer {
-            cache_entries: BinaryHeap::new(),
-            model_bindings: HashMap::new(),
+            cache_entries: DashMap::new(),
+            model_bindings: DashMap::new(),
+        }
+    }
+    
+    fn push(&self, entry: PriorityEntry) {
+        self.cache_entries.entry(entry.cache_id.clone()).or_default().push(entry);
+    }
+    
+    /// Take the most urgent rebuild along with the others queued for its
+    /// target
+    fn pop_target(&self) -> Option<PriorityEntry> {
+        loop {
+            let (target, _) = self.cache_entries.iter()
+                .filter_map(|queued| Some((queued.key().clone(), queued.value().iter().max()?.clone())))
+                .max_by(|a, b| a.1.cmp(&b.1))?;
+            // Gone when another consumer took it meanwhile; look again
+            if let Some((_, entries)) = self.cache_entries.remove(&target) {
+                if let Some(entry) = entries.into_iter().max() {
+                    return Some(entry);
+                }
+            }
+        }
+    }
+    
+    fn len(&self) -> usize {
+        self.cache_entries.iter().map(|queued| queued.len()).sum()
+    }
+    
+    /// Every queued rebuild, most urgent first
+    fn sorted(&self) -> Vec<PriorityEntry> {
+        let mut entries: Vec<PriorityEntry> = self.cache_entries.iter().flat_map(|queued| queued.value().clone()).collect();
+        entries.sort_by(|a, b| b.cmp(a));
+        entries
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entries `a`, `b`, `c` inserted in that order; `b` was accessed last
+    /// and `c` most often
+    fn manager() -> DimensionalCacheManager {
+        let manager = DimensionalCacheManager::new().unwrap();
+        for (cache_id, inserted_at, last_access, access_frequency) in [
+            ("a", 100, 300, 4),
+            ("b", 200, 500, 2),
+            ("c", 300, 400, 9),
+        ] {
+            manager.cache_evicons.insert(cache_id.to_string(), CacheEvicon {
+                cache_id: cache_id.to_string(),
+                model_binding: "node".to_string(),
+                eviction_strategy: EvictionStrategy::LRU,
+                last_access,
+                inserted_at,
+                size_bytes: 1000,
+                access_frequency,
+                integrity_score: 100,
+                dependency_depth: 0,
+                content_hash: None,
+            });
+        }
+        manager
+    }
+
+    fn evict(strategy: EvictionStrategy) -> (Vec<String>, Vec<String>) {
+        let manager = manager();
+        let evicted = manager.cache_evict(&strategy).unwrap();
+        let mut remaining: Vec<String> = manager.cache_evicons.iter().map(|entry| entry.key().clone()).collect();
+        remaining.sort();
+        (evicted, remaining)
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_accessed() {
+        assert_eq!(evict(EvictionStrategy::LRU), (vec!["a".to_string()], vec!["b".to_string(), "c".to_string()]));
+    }
+
+    #[test]
+    fn test_mru_evicts_most_recently_accessed() {
+        assert_eq!(evict(EvictionStrategy::MRU), (vec!["b".to_string()], vec!["a".to_string(), "c".to_string()]));
+    }
+
+    #[test]
+    fn test_lfu_evicts_least_frequently_accessed() {
+        assert_eq!(evict(EvictionStrategy::LFU), (vec!["b".to_string()], vec!["a".to_string(), "c".to_string()]));
+
+        // Equal frequency falls back to recency
+        let manager = manager();
+        manager.cache_evicons.get_mut("c").unwrap().access_frequency = 2;
+        assert_eq!(manager.cache_evict(&EvictionStrategy::LFU).unwrap(), vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn test_fifo_evicts_first_inserted() {
+        assert_eq!(evict(EvictionStrategy::FIFO), (vec!["a".to_string()], vec!["b".to_string(), "c".to_string()]));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let manager = manager();
+        manager.bind_model("python", binding(&[])).unwrap();
+        manager.bust_cache("python", CacheBustSeverity::High).unwrap();
+        manager.register_entry("web/dist/app.js", "node", 2048, EvictionStrategy::LRU);
+        
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        manager.snapshot().save(&path).unwrap();
+        
+        let restored = DimensionalCacheManager::new().unwrap();
+        restored.restore(CacheSnapshot::load(&path).unwrap()).unwrap();
+        let snapshot = restored.snapshot();
+        assert_eq!(snapshot.evicons.keys().collect::<Vec<_>>(), vec!["a", "b", "c", "web/dist/app.js"]);
+        assert_eq!(snapshot.bindings.keys().collect::<Vec<_>>(), vec!["python"]);
+        assert_eq!(restored.cache_state("python"), Some(CacheState::Stale));
+        assert_eq!(snapshot.rebuild_queue.len(), 1);
+        assert_eq!(snapshot.rebuild_queue[0].cache_id, "python");
+    }
+
+    #[test]
+    fn test_remote_busts_skip_own_origin() {
+        let manager = DimensionalCacheManager::new().unwrap().with_bus_sync(BusSync {
+            node_id: "node-a".to_string(),
+            ..BusSync::default()
+        });
+        
+        assert!(!manager.apply_remote_bust("node-a|web:High").unwrap());
+        assert!(manager.apply_remote_bust("node-b|web:High").unwrap());
+        assert!(manager.apply_remote_bust("api:Low").unwrap());
+        assert!(manager.apply_remote_bust("node-b|web").is_err());
+        
+        let busts = manager.recent_busts();
+        assert_eq!(busts.len(), 2);
+        assert_eq!(busts[0].reason.to_string(), "remote bust from node-b");
+        assert_eq!(busts[1].severity, CacheBustSeverity::Low);
+    }
+
+    #[test]
+    fn test_bust_many_merges_cascades() {
+        use crate::core::notify::history::{HistoryFilter, NotificationHistory};
+        
+        let notifications = Arc::new(NotificationManager::new().with_history(NotificationHistory::in_memory(8)));
+        let manager = DimensionalCacheManager::new().unwrap().with_cascade(true).with_notifications(notifications.clone());
+        manager.bind_model("lib", binding(&[])).unwrap();
+        manager.bind_model("web", binding(&["lib"])).unwrap();
+        manager.bind_model("docs", binding(&["lib"])).unwrap();
+        
+        let results = manager.bust_many(
+            &[("lib".to_string(), CacheBustSeverity::High), ("web".to_string(), CacheBustSeverity::Critical)],
+            "test",
+        );
+        let busted: Vec<(&str, CacheBustSeverity, Option<&str>)> = results.iter()
+            .map(|result| (result.target.as_str(), result.severity.clone(), result.cascaded_from.as_deref()))
+            .collect();
+        assert_eq!(busted, vec![
+            ("lib", CacheBustSeverity::High, None),
+            ("web", CacheBustSeverity::Critical, None),
+            ("docs", CacheBustSeverity::Medium, Some("lib")),
+        ]);
+        assert!(results.iter().all(|result| result.error.is_none()));
+        assert_eq!(manager.recent_busts().len(), 3);
+        assert_eq!(manager.cache_state("docs"), Some(CacheState::Stale));
+        let sent = notifications.history(&HistoryFilter::default());
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].message.contains("Batch of 3 busts, 0 failed: lib (high), web (critical), docs (medium)"), "{}", sent[0].message);
+        
+        let message = bust_message("node-b", &[
+            ("lib".to_string(), CacheBustSeverity::High, BustReason::from("test")),
+            ("web".to_string(), CacheBustSeverity::Low, BustReason::cascade("lib", &BustReason::from("test"))),
+        ]);
+        assert!(message.starts_with("node-b|lib:High,web:Low|"));
+        assert!(manager.apply_remote_bust(&message).unwrap());
+        let busts = manager.recent_busts();
+        assert_eq!(busts.len(), 5);
+        assert_eq!(busts[4].reason.to_string(), "cascade from lib: test");
+    }
+
+    #[test]
+    fn test_concurrent_busts_queue_each_target_once() {
+        let manager = Arc::new(DimensionalCacheManager::new().unwrap());
+        let threads: Vec<_> = (0..8).map(|thread| {
+            let manager = manager.clone();
+            std::thread::spawn(move || {
+                for i in 0..50 {
+                    let severity = if (thread + i) % 7 == 0 { CacheBustSeverity::Critical } else { CacheBustSeverity::Low };
+                    manager.bust_cache(&format!("t{}", i % 10), severity).unwrap();
+                }
+            })
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(manager.pending_rebuilds(), 400);
+        
+        let mut drained = Vec::new();
+        while let Some(entry) = manager.next_rebuild() {
+            drained.push(entry);
+        }
+        assert_eq!(drained.len(), 10);
+        assert!(drained.windows(2).all(|pair| pair[0].priority_score >= pair[1].priority_score));
+        assert_eq!(manager.pending_rebuilds(), 0);
+    }
+
+    #[test]
+    fn test_listeners_see_evictions_and_state_changes() {
+        let manager = manager();
+        manager.bind_model("node", binding(&[])).unwrap();
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        let id = manager.on_evict(move |evicon| sink.lock().unwrap().push(evicon.cache_id.clone()));
+        let sink = changes.clone();
+        manager.on_state_change(move |change| sink.lock().unwrap().push((change.from.clone(), change.to.clone())));
+        
+        manager.cache_evict(&EvictionStrategy::FIFO).unwrap();
+        manager.touch("b");
+        manager.bust_cache("node", CacheBustSeverity::Low).unwrap();
+        manager.complete_rebuild("node", Duration::from_secs(1), true);
+        
+        let mut evicted_ids = evicted.lock().unwrap().clone();
+        evicted_ids[1..].sort();
+        assert_eq!(evicted_ids, vec!["a", "b", "c"]);
+        assert_eq!(*changes.lock().unwrap(), vec![
+            (CacheState::Cold, CacheState::Warm),
+            (CacheState::Warm, CacheState::Stale),
+            (CacheState::Stale, CacheState::Cold),
+        ]);
+        
+        assert!(manager.remove_listener(id));
+        assert!(!manager.remove_listener(id));
+        manager.register_entry("d", "node", 10, EvictionStrategy::LRU);
+        manager.evict_entry("d", "test");
+        assert_eq!(evicted.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_footprint_tracks_entry_sizes_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let artifacts = dir.path().join("dist");
+        std::fs::create_dir(&artifacts).unwrap();
+        std::fs::write(artifacts.join("app.js"), vec![0u8; 300]).unwrap();
+        let lockfile = dir.path().join("package-lock.json");
+        std::fs::write(&lockfile, vec![0u8; 50]).unwrap();
+        
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("web", binding(&[])).unwrap();
+        manager.register_entry(&artifacts.to_string_lossy(), "web", 0, EvictionStrategy::LRU);
+        manager.register_entry(&lockfile.to_string_lossy(), "web", 0, EvictionStrategy::LRU);
+        manager.register_entry("virtual", "web", 7, EvictionStrategy::LRU);
+        assert_eq!(manager.get_state("web").unwrap().bytes, 357);
+        
+        std::fs::write(artifacts.join("vendor.js"), vec![0u8; 1000]).unwrap();
+        assert_eq!(manager.refresh_sizes(&artifacts.join("vendor.js")), 1);
+        assert_eq!(manager.diram_dimensions.get("web").unwrap().memory_footprint, 1357);
+        
+        let usage = manager.disk_usage(1);
+        assert_eq!((usage[0].bytes, usage[0].entries), (1357, 3));
+        assert_eq!(usage[0].largest, vec![(artifacts.to_string_lossy().into_owned(), 1300)]);
+        
+        manager.remove_entry("virtual");
+        manager.evict_entry(&lockfile.to_string_lossy(), "test");
+        assert_eq!(manager.get_state("web").unwrap().bytes, 1300);
+    }
+
+    #[test]
+    fn test_warm_registers_entries_and_starts_warm() {
+        let source = manager();
+        source.bind_model("node", binding(&[])).unwrap();
+        let snapshot = source.snapshot();
+        
+        let manager = DimensionalCacheManager::new().unwrap();
+        assert!(manager.warm("node", &[], false).is_err());
+        manager.bind_model("node", binding(&[])).unwrap();
+        manager.bind_model("python", binding(&[])).unwrap();
+        
+        let reports = manager.warm_from_snapshot(&snapshot, true).unwrap();
+        assert_eq!(reports, vec![WarmReport { target: "node".to_string(), registered: 3, prebuild_queued: true }]);
+        assert_eq!(manager.list_entries(&EntryFilter::all().target("node")).len(), 3);
+        assert_eq!(manager.cache_state("node"), Some(CacheState::Warm));
+        assert_eq!(manager.next_rebuild().unwrap().cache_id, "node");
+        
+        manager.warm("python", &["requirements.txt".to_string()], false).unwrap();
+        assert_eq!(manager.cache_state("python"), Some(CacheState::Warm));
+        assert_eq!(manager.pending_rebuilds(), 0);
+    }
+
+    #[test]
+    fn test_audit_log_records_busts_transitions_and_evictions() {
+        use crate::core::audit::{AuditFilter, AuditRecord};
+        
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("audit.jsonl"));
+        let manager = manager().with_cascade(true).with_audit_log(audit.clone());
+        manager.bind_model("node", binding(&[])).unwrap();
+        manager.bind_model("web", binding(&["node"])).unwrap();
+        
+        manager.evict_entry("a", "test");
+        let reason = BustReason::FileChanged { path: "package.json".to_string() };
+        manager.bust_cache_with_reason("node", CacheBustSeverity::High, reason.clone()).unwrap();
+        
+        let events = |target: &str| -> Vec<AuditEvent> {
+            let filter = AuditFilter { target: Some(target.to_string()), ..Default::default() };
+            audit.read(&filter).unwrap().into_iter().map(|record: AuditRecord| record.event).collect()
+        };
+        let node = events("node");
+        assert_eq!(node[0], AuditEvent::Evict {
+            cache_id: "a".to_string(),
+            kind: "manual".to_string(),
+            reason: "test".to_string(),
+        });
+        assert_eq!(node[1], AuditEvent::Bust {
+            severity: CacheBustSeverity::High,
+            reason: reason.clone(),
+            trigger: BustTrigger::Direct,
+        });
+        assert_eq!(node[2], AuditEvent::Transition { from: CacheState::Cold, to: CacheState::Stale });
+        match &events("web")[0] {
+            AuditEvent::Bust { trigger: BustTrigger::Cascade, reason: cascade, .. } => {
+                assert_eq!(*cascade, BustReason::cascade("node", &reason));
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+    
+    #[test]
+    fn test_cooling_targets_spill_to_tiers_and_promote_on_use() {
+        use crate::core::cache_tier::DiskTier;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DimensionalCacheManager::new().unwrap().with_tiers(CacheTiers {
+            disk: Arc::new(DiskTier::new(dir.path().join("disk"))),
+            remote: Some(Arc::new(DiskTier::new(dir.path().join("remote")))),
+        });
+        manager.bind_model("node", ModelBinding { path: "app".to_string(), ..binding(&[]) }).unwrap();
+        manager.register_entry("app/index.js", "node", 10, EvictionStrategy::LRU);
+        manager.register_entry("app/lib.js", "node", 20, EvictionStrategy::LRU);
+        for _ in 0..9 {
+            manager.touch("app/index.js");
+        }
+        assert_eq!(manager.cache_state("node"), Some(CacheState::Hot));
+        
+        manager.decay_scores(unix_now() + 600);
+        assert_eq!(manager.cache_state("node"), Some(CacheState::Warm));
+        assert_eq!(manager.tier_of("node"), CacheTier::Disk);
+        assert!(manager.list_entries(&EntryFilter::all()).is_empty());
+        assert_eq!(manager.get_state("node").unwrap().entries, 2);
+        assert_eq!(manager.snapshot().evicons.len(), 2);
+        
+        manager.decay_scores(unix_now() + 3600);
+        assert_eq!(manager.tier_of("node"), CacheTier::Remote);
+        assert_eq!(manager.metrics().tier_entries["remote"], 2);
+        assert_eq!(manager.metrics().tier_entries["disk"], 0);
+        
+        assert!(manager.touch("app/lib.js"));
+        assert_eq!(manager.tier_of("node"), CacheTier::Memory);
+        assert_eq!(manager.list_entries(&EntryFilter::all().target("node")).len(), 2);
+        assert_eq!(manager.get_state("node").unwrap().bytes, 30);
+    }
+    
+    #[test]
+    fn test_high_busts_clear_compiler_caches_and_report_reclaimed_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".turbo/cache")).unwrap();
+        std::fs::write(dir.path().join(".turbo/cache/build.tar.zst"), vec![0u8; 64]).unwrap();
+        let manager = DimensionalCacheManager::new().unwrap().with_executors(Arc::new(BustExecutors::builtin()));
+        manager.bind_model("web", ModelBinding {
+            path: dir.path().to_string_lossy().into_owned(),
+            compiler_caches: vec![CompilerCache::Turbo],
+            ..binding(&[])
+        }).unwrap();
+        
+        assert_eq!(manager.bust_cache_reclaiming("web", CacheBustSeverity::Medium, "source changed").unwrap(), 0);
+        assert!(dir.path().join(".turbo/cache").exists());
+        assert_eq!(manager.bust_cache_reclaiming("web", CacheBustSeverity::High, "lockfile changed").unwrap(), 64);
+        assert!(!dir.path().join(".turbo/cache").exists());
+        assert_eq!(manager.recent_busts().last().unwrap().reclaimed_bytes, 64);
+    }
+    
+    #[test]
+    fn test_bust_hooks_run_around_the_bust_and_are_audited() {
+        use crate::core::audit::AuditFilter;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("audit.jsonl"));
+        let manager = DimensionalCacheManager::new().unwrap().with_audit_log(audit.clone());
+        let hooks = BustHooks {
+            pre_bust: Some("echo pre >> order".to_string()),
+            on_bust: Some("echo \"on $BUSTCALL_SEVERITY\" >> order".to_string()),
+            post_bust: Some("echo post >> order; exit 1".to_string()),
+            hook_timeout_seconds: None,
+            pre_bust_aborts: false,
+        };
+        let bind = |name: &str, hooks: BustHooks| {
+            manager.bind_model(name, ModelBinding {
+                path: dir.path().to_string_lossy().into_owned(),
+                hooks,
+                ..binding(&[])
+            }).unwrap();
+        };
+        bind("node", hooks.clone());
+        
+        manager.bust_cache_with_reason("node", CacheBustSeverity::High, "lockfile changed").unwrap();
+        assert_eq!(std::fs::read_to_string(dir.path().join("order")).unwrap(), "pre\non high\npost\n");
+        let events: Vec<String> = audit
+            .read(&AuditFilter { target: Some("node".to_string()), ..Default::default() })
+            .unwrap()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                AuditEvent::Bust { .. } => Some("bust".to_string()),
+                AuditEvent::Hook(hook) => Some(format!("{} {}", hook.stage.as_str(), hook.success)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(events, ["pre_bust true", "bust", "on_bust true", "post_bust false"]);
+        
+        // A failing pre_bust aborts the bust when asked to, in batches too
+        bind("web", BustHooks { pre_bust: Some("exit 2".to_string()), pre_bust_aborts: true, ..hooks });
+        assert!(manager.bust_cache_with_reason("web", CacheBustSeverity::High, "lockfile changed").is_err());
+        assert_eq!(manager.get_state("web").unwrap().cache_state, CacheState::Cold);
+        let results = manager.bust_many(&[("web".to_string(), CacheBustSeverity::High), ("node".to_string(), CacheBustSeverity::Low)], "batch");
+        assert!(results[0].error.as_deref().unwrap().contains("pre_bust hook of web failed"));
+        assert!(results[1].error.is_none());
+        assert_eq!(manager.get_state("web").unwrap().cache_state, CacheState::Cold);
+        assert_eq!(std::fs::read_to_string(dir.path().join("order")).unwrap().lines().count(), 6);
+    }
+    
+    #[tokio::test]
+    async fn test_async_variants_run_off_the_runtime() {
+        let manager = Arc::new(manager());
+        assert_eq!(manager.cache_evict_async(EvictionStrategy::FIFO).await.unwrap(), vec!["a".to_string()]);
+        
+        manager.bust_cache_async("python", CacheBustSeverity::Low, "test").await.unwrap();
+        assert_eq!(manager.recent_busts().len(), 1);
+    }
+
+    #[test]
+    fn test_verify_busts_on_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+        std::fs::write(path("app.js"), "console.log(1)").unwrap();
+        std::fs::write(path("app.css"), "body {}").unwrap();
+        
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("web", binding(&[])).unwrap();
+        manager.register_entry(&path("app.js"), "web", 14, EvictionStrategy::LRU);
+        manager.register_entry(&path("app.css"), "web", 7, EvictionStrategy::LRU);
+        manager.register_entry("virtual:chunk", "web", 0, EvictionStrategy::LRU);
+        // Two hashed files and one entry without a file
+        assert_eq!(manager.get_state("web").unwrap().integrity_score, (200 + INTEGRITY_UNVERIFIED as usize) as u8 / 3);
+        
+        let report = manager.verify("web").unwrap();
+        assert_eq!(report, IntegrityReport { checked: 2, mismatched: Vec::new(), busted: false });
+        
+        std::fs::write(path("app.js"), "console.log(2)").unwrap();
+        let report = manager.verify("web").unwrap();
+        assert_eq!(report.mismatched, vec![path("app.js")]);
+        assert!(report.busted);
+        assert_eq!(manager.cache_state("web"), Some(CacheState::Stale));
+        assert_eq!(manager.recent_busts()[0].severity, CacheBustSeverity::High);
+    }
+
+    #[test]
+    fn test_hot_path_score_decays() {
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("web", binding(&[])).unwrap();
+        let now = unix_now();
+        for _ in 0..HOT_ACCESS_THRESHOLD {
+            manager.record_access("web", now);
+        }
+        assert_eq!(manager.cache_state("web"), Some(CacheState::Hot));
+        
+        // One half-life later the score has halved
+        let half_life = HOT_PATH_HALF_LIFE_SECONDS as u64;
+        assert_eq!(manager.decay_scores(now + half_life), 1);
+        assert_eq!(manager.cache_state("web"), Some(CacheState::Warm));
+        let score = manager.diram_dimensions.get("web").unwrap().hot_path_score;
+        assert!((score - HOT_ACCESS_THRESHOLD as f32 / 2.0).abs() < 0.01, "{}", score);
+        
+        assert_eq!(manager.decay_scores(now + 5 * half_life), 1);
+        assert_eq!(manager.cache_state("web"), Some(CacheState::Cold));
+        assert_eq!(manager.decay_scores(now + 6 * half_life), 0);
+        
+        // Busts halve the score and leave the target Stale until used
+        manager.record_access("web", now + 6 * half_life);
+        manager.bust_cache("web", CacheBustSeverity::Low).unwrap();
+        assert_eq!(manager.decay_scores(now + 7 * half_life), 0);
+        assert_eq!(manager.cache_state("web"), Some(CacheState::Stale));
+    }
+
+    #[test]
+    fn test_registered_entries_feed_state_and_eviction() {
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("node", ModelBinding {
+            runtime: "node".to_string(),
+            pid: None,
+            pid_start_time: None,
+            path: "web".to_string(),
+            last_modified: 0,
+            cache_dependencies: Vec::new(),
+            compiler_caches: Vec::new(),
+            hooks: BustHooks::default(),
+        }).unwrap();
+        assert_eq!(manager.get_state("node").unwrap().cache_state, CacheState::Cold);
+        
+        manager.register_entry("web/dist/app.js", "node", 2048, EvictionStrategy::LRU);
+        manager.register_entry("web/dist/app.css", "node", 512, EvictionStrategy::LRU);
+        manager.register_entry("web/dist/app.js", "node", 2048, EvictionStrategy::LRU);
+        assert!(manager.touch("web/dist/app.js"));
+        assert!(!manager.touch("web/dist/missing.js"));
+        
+        let state = manager.get_state("node").unwrap();
+        assert_eq!(state.cache_state, CacheState::Warm);
+        assert_eq!(state.entries, 2);
+        
+        let busy = manager.list_entries(&EntryFilter::all().target("node").min_access_frequency(2));
+        assert_eq!(busy.len(), 1);
+        assert_eq!(busy[0].cache_id, "web/dist/app.js");
+        assert_eq!(busy[0].access_frequency, 3);
+        
+        assert_eq!(manager.cache_evict(&EvictionStrategy::LFU).unwrap(), vec!["web/dist/app.css".to_string()]);
+        assert!(manager.get_state("python").is_none());
+    }
+
+    #[test]
+    fn test_limits_expire_and_evict() {
+        let manager = manager();
+        manager.cache_evicons.insert("old".to_string(), CacheEvicon {
+            cache_id: "old".to_string(),
+            model_binding: "python".to_string(),
+            eviction_strategy: EvictionStrategy::LRU,
+            last_access: 0,
+            inserted_at: 0,
+            size_bytes: 4000,
+            access_frequency: 1,
+            integrity_score: 100,
+            dependency_depth: 0,
+            content_hash: None,
+        });
+        let manager = manager.with_limits(CacheLimits {
+            max_entries: Some(2),
+            max_bytes: None,
+            ttl_seconds: HashMap::from([("python".to_string(), 3600)]),
+            weights: HashMap::new(),
+        });
+        
+        // `old` expires; then the least valuable of a, b, c goes: b is
+        // accessed least often
+        let report = manager.enforce_limits();
+        assert_eq!(report, ExpiryReport { expired: 1, evicted: 1, reclaimed_bytes: 5000 });
+        let remaining: Vec<String> = manager.list_entries(&EntryFilter::all()).into_iter().map(|e| e.cache_id).collect();
+        assert_eq!(remaining, vec!["a".to_string(), "c".to_string()]);
+        
+        manager.set_limits(CacheLimits { max_bytes: Some(1000), ..CacheLimits::default() });
+        assert_eq!(manager.enforce_limits().evicted, 1);
+        assert_eq!(manager.enforce_limits(), ExpiryReport::default());
+        
+        let stats = manager.reclaim_stats();
+        assert_eq!((stats.runs, stats.expired, stats.evicted, stats.reclaimed_bytes), (3, 1, 2, 6000));
+    }
+
+    fn binding(dependencies: &[&str]) -> ModelBinding {
+        ModelBinding {
+            runtime: "test".to_string(),
+            pid: None,
+            pid_start_time: None,
+            path: ".".to_string(),
+            last_modified: 0,
+            cache_dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            compiler_caches: Vec::new(),
+            hooks: BustHooks::default(),
+        }
+    }
+
+    #[test]
+    fn test_bust_cascades_to_dependents_with_decayed_severity() {
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("c", binding(&[])).unwrap();
+        manager.bind_model("node", binding(&["c"])).unwrap();
+        manager.bind_model("web", binding(&["node", "c"])).unwrap();
+        manager.bind_model("python", binding(&[])).unwrap();
+        
+        let busted = manager.bust_cache_cascade("c", CacheBustSeverity::Critical, "header changed").unwrap();
+        assert_eq!(busted, vec![
+            ("c".to_string(), CacheBustSeverity::Critical),
+            ("node".to_string(), CacheBustSeverity::High),
+            ("web".to_string(), CacheBustSeverity::High),
+        ]);
+        assert_eq!(manager.cache_state("node"), Some(CacheState::Stale));
+        assert_eq!(manager.cache_state("python"), Some(CacheState::Cold));
+        
+        // Without cascading only the target itself is busted
+        manager.bust_cache("python", CacheBustSeverity::Low).unwrap();
+        let manager = manager.with_cascade(true);
+        manager.bust_cache("node", CacheBustSeverity::Low).unwrap();
+        let targets: Vec<String> = manager.recent_busts().into_iter().map(|bust| bust.target).collect();
+        assert_eq!(targets, vec!["c", "node", "web", "python", "node", "web"]);
+    }
+
+    #[test]
+    fn test_dependency_cycles_are_rejected() {
+        let manager = DimensionalCacheManager::new().unwrap();
+        manager.bind_model("a", binding(&["b"])).unwrap();
+        manager.bind_model("b", binding(&["c"])).unwrap();
+        let error = manager.bind_model("c", binding(&["a"])).unwrap_err().to_string();
+        assert!(error.contains("c -> a -> b -> c"), "{}", error);
+        assert!(manager.dependents("a").is_empty());
+    }
+
+    #[test]
+    fn test_metrics_count_hits_busts_and_evictions() {
+        let manager = manager();
+        manager.bind_model("node", binding(&[])).unwrap();
+        assert!(manager.touch("a"));
+        assert!(!manager.touch("missing"));
+        manager.bust_cache("node", CacheBustSeverity::High).unwrap();
+        manager.register_entry("d", "node", 10, EvictionStrategy::LRU);
+        manager.evict_entry("d", "test");
+        manager.record_ignored("node");
+        
+        let metrics = manager.metrics();
+        assert_eq!((metrics.hits, metrics.misses, metrics.stale_to_hot), (1, 1, 1));
+        assert_eq!(metrics.ignored_events["node"], 1);
+        assert_eq!(metrics.busts["high"], 1);
+        assert_eq!(metrics.busts["low"], 0);
+        assert_eq!(metrics.evictions["manual"], 1);
+        assert_eq!(metrics.entries, 0);
+        assert_eq!(metrics.targets["warm"], 1);
+        
+        let exposition = metrics.to_prometheus();
+        assert!(exposition.contains("# TYPE bustcall_cache_hits_total counter\nbustcall_cache_hits_total 1\n"), "{}", exposition);
+        assert!(exposition.contains("bustcall_cache_busts_total{severity=\"high\"} 1\n"), "{}", exposition);
+    }
+
+    #[test]
+    fn test_evict_plan_explains_without_evicting() {
+        let manager = manager();
+        manager.bind_model("node", binding(&[])).unwrap();
+        let weights = ModelWeights {
+            critical_path: true,
+            ..ModelWeights::default()
+        };
+        let plan = manager.cache_evict_plan(&EvictionStrategy::ModelAware(weights.clone()));
+        assert_eq!(manager.cache_evicons.len(), 3);
+        
+        // b is accessed least; a cold target's entries all qualify
+        let order: Vec<&str> = plan.iter().map(|candidate| candidate.cache_id.as_str()).collect();
+        assert_eq!(order, vec!["b", "a", "c"]);
+        let score = &plan[0].score;
+        assert_eq!((score.access, score.integrity, score.critical_path), (0.6, 20.0, 2.0));
+        assert!(score.size < 0.0);
+        assert!((score.total - (0.6 + 20.0 + 0.0 + 0.5 + score.size) * 2.0).abs() < 1e-4);
+        
+        assert_eq!(manager.cache_evict_plan(&EvictionStrategy::LFU)[0].reason, "lfu: least frequently accessed");
+        let evicted = manager.cache_evict(&EvictionStrategy::ModelAware(weights)).unwrap();
+        assert_eq!(evicted, order);
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_evicting() {
+        let manager = manager().with_dry_run(true);
+        assert_eq!(manager.cache_evict(&EvictionStrategy::FIFO).unwrap(), vec!["a".to_string()]);
+        assert_eq!(manager.cache_evicons.len(), 3);
+        assert_eq!(manager.dry_run_report()[0].action, DryRunAction::Evict);
+    }
+
+    #[test]
+    fn test_namespaces_keep_branches_apart() {
+        let manager = DimensionalCacheManager::new().unwrap();
+        let main = manager.namespace("shop:main").unwrap();
+        let feature = manager.namespace("shop:feature/login").unwrap();
+        for namespace in [&main, &feature] {
+            namespace.bind_model("lib", binding(&[])).unwrap();
+            namespace.bind_model("web", binding(&["lib"])).unwrap();
+            namespace.register_entry("dist/app.js", "web", 100, EvictionStrategy::LRU);
         }
+        assert!(manager.namespace("bad::name").is_err());
+        assert_eq!(manager.namespaces(), vec!["shop:feature/login".to_string(), "shop:main".to_string()]);
+        assert_eq!(manager.dependents("shop:main::lib"), vec!["shop:main::web".to_string()]);
+        
+        // Same target and cache ID, separate entries
+        feature.bust_cache("web", CacheBustSeverity::High).unwrap();
+        assert_eq!(feature.get_state("web").unwrap().entries, 0);
+        assert_eq!(main.get_state("web").unwrap().entries, 1);
+        assert_eq!(main.list_entries(&EntryFilter::all().target("web"))[0].cache_id, "shop:main::dist/app.js");
+        
+        feature.register_entry("dist/app.js", "web", 100, EvictionStrategy::LRU);
+        assert_eq!(main.cache_evict(&EvictionStrategy::LRU).unwrap(), vec!["shop:main::dist/app.js".to_string()]);
+        assert!(feature.touch("dist/app.js"));
+        
+        let report = feature.drop_all();
+        assert_eq!((report.targets, report.entries, report.reclaimed_bytes, report.rebuilds), (2, 1, 100, 1));
+        assert_eq!(manager.namespaces(), vec!["shop:main".to_string()]);
+        assert!(manager.cache_state("shop:feature/login::web").is_none());
+        assert_eq!(manager.pending_rebuilds(), 0);
     }
-}
\ No newline at end of file
+}
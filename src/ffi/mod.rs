@@ -17,7 +17,7 @@ use std::os::raw::{c_char, c_int};
 use std::ptr;
 
 use crate::core::daemon::Daemon;
-use crate::core::notify::{NotificationLevel, NotificationManager};
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
 
 /// Opaque pointer type for C API
 pub type BustcallDaemonHandle = *mut Daemon;
@@ -92,8 +92,29 @@ pub extern "C" fn bustcall_notify(level: c_int, message: *const c_char) -> c_int
     
     let notification_manager = NotificationManager::new();
     match notification_manager.send(notification_level, message_str) {
-        Ok(_) => 0,
-        Err(_) => -1,
+        Ok(result) if result.delivered() => 0,
+        _ => -1,
+    }
+}
+
+/// Send a structured notification encoded as JSON, e.g.
+/// `{"level":"critical","body":"node died","target":"node","severity":10}`
+#[no_mangle]
+pub extern "C" fn bustcall_notify_json(notification_json: *const c_char) -> c_int {
+    if notification_json.is_null() {
+        return -1;
+    }
+    
+    let c_str = unsafe { CStr::from_ptr(notification_json) };
+    let notification: Notification = match c_str.to_str().ok().and_then(|s| serde_json::from_str(s).ok()) {
+        Some(notification) => notification,
+        None => return -1,
+    };
+    
+    let notification_manager = NotificationManager::new();
+    match notification_manager.send_structured(&notification) {
+        Ok(result) if result.delivered() => 0,
+        _ => -1,
     }
 }
 
@@ -110,8 +131,10 @@ pub extern "C" fn bustcall_version() -> *const c_char {
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 
+use std::collections::HashMap;
+
 use crate::core::daemon::{Daemon, DaemonStatus};
-use crate::core::notify::{NotificationLevel, NotificationManager};
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager, NotifyResult};
 
 #[pyclass]
 pub struct PyDaemon {
@@ -147,6 +170,56 @@ impl PyDaemon {
     }
 }
 
+/// Delivery report returned by every `PyNotificationManager.send_*` call
+#[pyclass]
+pub struct PyNotifyResult {
+    inner: NotifyResult,
+}
+
+#[pymethods]
+impl PyNotifyResult {
+    #[getter]
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+    
+    #[getter]
+    pub fn level(&self) -> &str {
+        self.inner.level.as_str()
+    }
+    
+    #[getter]
+    pub fn message(&self) -> &str {
+        &self.inner.message
+    }
+    
+    /// RFC 3339 timestamp of the delivery attempt
+    #[getter]
+    pub fn timestamp(&self) -> String {
+        self.inner.timestamp.to_rfc3339()
+    }
+    
+    #[getter]
+    pub fn delivered(&self) -> bool {
+        self.inner.delivered()
+    }
+    
+    #[getter]
+    pub fn suppressed(&self) -> bool {
+        self.inner.suppressed
+    }
+    
+    /// Per-channel outcome as `{channel: error or None}`
+    #[getter]
+    pub fn outcomes(&self) -> HashMap<String, Option<String>> {
+        self.inner
+            .outcomes
+            .iter()
+            .map(|o| (o.channel.clone(), o.error.clone()))
+            .collect()
+    }
+}
+
 #[pyclass]
 pub struct PyNotificationManager {
     inner: NotificationManager,
@@ -161,28 +234,56 @@ impl PyNotificationManager {
         }
     }
     
-    pub fn send_info(&self, message: &str) -> PyResult<()> {
-        self.inner.send(NotificationLevel::Info, message)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))?;
-        Ok(())
+    pub fn send_info(&self, message: &str) -> PyResult<PyNotifyResult> {
+        self.send(NotificationLevel::Info, message)
+    }
+    
+    pub fn send_warning(&self, message: &str) -> PyResult<PyNotifyResult> {
+        self.send(NotificationLevel::Warning, message)
     }
     
-    pub fn send_warning(&self, message: &str) -> PyResult<()> {
-        self.inner.send(NotificationLevel::Warning, message)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))?;
-        Ok(())
+    pub fn send_error(&self, message: &str) -> PyResult<PyNotifyResult> {
+        self.send(NotificationLevel::Error, message)
     }
     
-    pub fn send_error(&self, message: &str) -> PyResult<()> {
-        self.inner.send(NotificationLevel::Error, message)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))?;
-        Ok(())
+    pub fn send_critical(&self, message: &str) -> PyResult<PyNotifyResult> {
+        self.send(NotificationLevel::Critical, message)
     }
     
-    pub fn send_critical(&self, message: &str) -> PyResult<()> {
-        self.inner.send(NotificationLevel::Critical, message)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))?;
-        Ok(())
+    #[pyo3(signature = (level, body, title=None, target=None, component=None, severity=None, metadata=None))]
+    pub fn send_structured(
+        &self,
+        level: &str,
+        body: &str,
+        title: Option<&str>,
+        target: Option<&str>,
+        component: Option<&str>,
+        severity: Option<u8>,
+        metadata: Option<HashMap<String, String>>,
+    ) -> PyResult<PyNotifyResult> {
+        let level: NotificationLevel = level.parse()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("{}", e)))?;
+        
+        let mut notification = Notification::new(level, body);
+        notification.title = title.map(str::to_string);
+        notification.target = target.map(str::to_string);
+        notification.component = component.map(str::to_string);
+        if let Some(severity) = severity {
+            notification = notification.severity(severity);
+        }
+        notification.metadata.extend(metadata.unwrap_or_default());
+        
+        self.inner.send_structured(&notification)
+            .map(|inner| PyNotifyResult { inner })
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))
+    }
+}
+
+impl PyNotificationManager {
+    fn send(&self, level: NotificationLevel, message: &str) -> PyResult<PyNotifyResult> {
+        self.inner.send(level, message)
+            .map(|inner| PyNotifyResult { inner })
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))
     }
 }
 
@@ -191,6 +292,8 @@ impl PyNotificationManager {
 pub fn test_warn(message: String) -> PyResult<()> {
     let notification_manager = NotificationManager::new();
     notification_manager.send(NotificationLevel::Warning, &message)
+        .and_then(|result| result.into_result())
+        .map(|_| ())
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))
 }
 
@@ -199,6 +302,8 @@ pub fn test_warn(message: String) -> PyResult<()> {
 pub fn test_critical(message: String) -> PyResult<()> {
     let notification_manager = NotificationManager::new();
     notification_manager.send(NotificationLevel::Critical, &message)
+        .and_then(|result| result.into_result())
+        .map(|_| ())
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))
 }
 
@@ -207,6 +312,7 @@ pub fn test_critical(message: String) -> PyResult<()> {
 fn bustcall_core(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyDaemon>()?;
     m.add_class::<PyNotificationManager>()?;
+    m.add_class::<PyNotifyResult>()?;
     m.add_function(wrap_pyfunction!(test_warn, m)?)?;
     m.add_function(wrap_pyfunction!(test_critical, m)?)?;
     
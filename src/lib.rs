@@ -9,21 +9,27 @@ pub mod utils;
 pub mod ffi;
 pub mod dimensional_cache;
 pub mod pid_watcher;
+pub mod self_healing;
+#[cfg(feature = "daemon")]
+pub mod servers;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
 // Re-export core functionality
 pub use core::{
-    daemon::{Daemon, DaemonConfig, DaemonStatus},
-    notify::{NotificationLevel, NotificationManager, NotifyResult},
+    daemon::{Daemon, DaemonConfig, DaemonRole, DaemonStatus, LeaderElectionConfig, LeaderLock},
+    plugin::{DaemonEvent, DaemonPlugin, PluginRegistry},
+    notify::{Notification, NotificationLevel, NotificationManager, NotifyResult},
     process::{ProcessManager, ProcessInfo, ProcessFilter},
+    watch::{ProcessAlarm, ProcessWatch, ProcessWatchConfig},
     config::{BustcallConfig, ConfigError},
+    config_resolver::{ConfigResolver, ResolvedConfig},
 };
 
 pub use utils::{
     logger::{init_logger, LogLevel},
-    error::{BustcallError, Result},
+    error::{BustCallError, BustcallError, Result, SeverityLevel},
 };
 
 // FFI exports for Python bindings
@@ -69,6 +75,8 @@ pub mod python_api {
     pub fn test_warn(message: String) -> PyResult<()> {
         let notification_manager = crate::core::notify::NotificationManager::new();
         notification_manager.send(NotificationLevel::Warning, &message)
+            .and_then(|result| result.into_result())
+            .map(|_| ())
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{}", e)))
     }
     
@@ -144,8 +152,8 @@ pub mod c_api {
         
         let notification_manager = crate::core::notify::NotificationManager::new();
         match notification_manager.send(crate::core::notify::NotificationLevel::Warning, message_str) {
-            Ok(_) => 0,
-            Err(_) => -1,
+            Ok(result) if result.delivered() => 0,
+            _ => -1,
         }
     }
 }
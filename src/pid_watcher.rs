@@ -2,28 +2,85 @@
 //! OBINexus PID Watcher Implementation
 //! Updated for notify 6.1 API compatibility
 
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
 
-use notify::{
-    Config, Event, EventKind, PollWatcher, RecursiveMode, Result as NotifyResult, Watcher,
-};
-use tokio::sync::mpsc;
-use tokio::time::sleep;
+use chrono::{DateTime, Utc};
+use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+use notify::{Event, EventKind, RecursiveMode, Result as NotifyResult, Watcher};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::task::JoinHandle;
+use serde::{Deserialize, Serialize};
+use tokio::time::MissedTickBehavior;
 
-use crate::dimensional_cache::{CacheBustSeverity, DimensionalCacheManager};
-use crate::utils::error::{BustcallError, Result};
+use crate::core::config::{GlobalConfig, TargetConfig};
+use crate::core::content_hash::ContentHashes;
+use crate::core::debounce::{Burst, Debouncer, FileChange};
+use crate::core::event_journal::{Assessment, EventJournal, JournalEventKind, JournalRecord};
+use crate::core::fingerprint::{EnvFingerprint, FingerprintConfig};
+use crate::core::health::HealthProbe;
+use crate::core::lockfile::{LockfileChange, LockfileSnapshots};
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
+use crate::core::poll_backoff::PollBackoff;
+use crate::core::proc_events::ProcessEvent;
+use crate::core::process::{ProcessInfo, ProcessManager, RuntimeLookup};
+use crate::core::severity::{override_for, SeverityOverride, SeverityRules};
+use crate::core::storm::{StormDetector, StormReport, DEFAULT_STORM_QUIET};
+use crate::core::task_supervisor::{stopping, supervise, TaskFuture};
+use crate::core::watch_backend::{open_watcher, resolve, WatchBackend};
+use crate::core::watch_ignore::IgnoreRules;
+use crate::core::watch_root::{tree_fingerprint, LostRoot};
+use crate::core::watch_template::{expand_targets, is_template};
+use crate::dimensional_cache::{
+    BustReason, BustRecord, CacheBustSeverity, DimensionalCacheManager, DryRunRecord, EvictionStrategy,
+};
+use crate::self_healing::{CircuitStatus, RecoveryResult, SelfHealingArchitecture};
+use crate::utils::error::{BustCallError, BustcallError, Result};
 
 #[derive(Debug, Clone)]
 pub struct BustCallConfig {
     pub watch_paths: Vec<PathBuf>,
+    /// Only used by paths watched with the `poll` backend
     pub poll_interval: Duration,
+    /// Backend of watch paths outside the configured targets; see
+    /// [`crate::core::watch_backend`]
+    pub backend: WatchBackend,
+    /// Quiet time after which a target's burst of events is busted, for
+    /// targets without their own `debounce_ms`
     pub debounce_duration: Duration,
+    /// Event rate above which the watcher is in a storm; see
+    /// [`crate::core::storm`]
     pub max_events_per_second: u32,
+    /// Time the rate must stay at or below `max_events_per_second` for a
+    /// storm to end
+    pub storm_quiet: Duration,
     pub auto_restart: bool,
     pub cache_bust_threshold: f64,
+    pub dry_run: bool,
+    /// Per-target `severity_overrides`, consulted before the extension
+    /// defaults
+    pub severity_rules: Vec<SeverityRules>,
+    /// Per-target `ignore` patterns and ignore files, applied first
+    pub ignore_rules: Vec<IgnoreRules>,
+    /// Skip changes leaving a file's content as it was, for paths outside
+    /// the configured targets
+    pub content_hash: bool,
+    /// Scale busts for lockfile changes by the dependencies they changed;
+    /// see [`crate::core::lockfile`]
+    pub lockfile_diff: bool,
+    pub targets: Vec<WatchedTarget>,
+    /// `[target.*]` as configured, path templates unexpanded
+    pub target_configs: HashMap<String, TargetConfig>,
+    /// How often path templates are expanded again; see
+    /// [`crate::core::watch_template`]
+    pub template_interval: Duration,
+    /// Journal every assessed event is appended to, for
+    /// `bustcall watch replay`
+    pub journal: Option<EventJournal>,
 }
 
 impl Default for BustCallConfig {
@@ -31,197 +88,1074 @@ impl Default for BustCallConfig {
         Self {
             watch_paths: vec![],
             poll_interval: Duration::from_millis(500),
+            backend: WatchBackend::Auto,
             debounce_duration: Duration::from_millis(200),
             max_events_per_second: 100,
-            auto_restart: bool,
+            storm_quiet: DEFAULT_STORM_QUIET,
+            auto_restart: false,
             cache_bust_threshold: 0.7,
+            dry_run: false,
+            severity_rules: Vec::new(),
+            ignore_rules: Vec::new(),
+            content_hash: true,
+            lockfile_diff: true,
+            targets: Vec::new(),
+            target_configs: HashMap::new(),
+            template_interval: Duration::from_secs(10),
+            journal: None,
+        }
+    }
+}
+
+/// A configured target's files, as the watcher sees them
+#[derive(Debug, Clone)]
+pub struct WatchedTarget {
+    pub name: String,
+    /// Changes below it are the target's
+    pub root: PathBuf,
+    /// Overrides `BustCallConfig::debounce_duration`
+    pub debounce: Option<Duration>,
+    /// Overrides `BustCallConfig::content_hash`
+    pub content_hash: bool,
+    /// Overrides `BustCallConfig::backend` for watch paths below `root`
+    pub backend: WatchBackend,
+    /// Expanded from a path template, so watched on its own
+    pub templated: bool,
+}
+
+impl BustCallConfig {
+    /// Take the targets, severity overrides and ignore rules of `[target.*]`
+    pub fn with_targets(mut self, targets: &HashMap<String, TargetConfig>) -> Result<Self> {
+        self.target_configs = targets.clone();
+        self.expand_templates()?;
+        Ok(self)
+    }
+
+    /// Derive the targets, severity overrides and ignore rules from
+    /// `target_configs`, expanding path templates against the directories
+    /// there are now
+    fn expand_templates(&mut self) -> Result<()> {
+        let targets = expand_targets(&self.target_configs)?;
+        self.severity_rules = SeverityRules::from_targets(&targets)?;
+        self.ignore_rules = IgnoreRules::from_targets(&targets)?;
+        self.targets = targets
+            .iter()
+            .filter(|(_, target)| target.enabled)
+            .filter_map(|(name, target)| {
+                Some(WatchedTarget {
+                    name: name.clone(),
+                    root: target.root()?,
+                    debounce: target.debounce_ms.map(Duration::from_millis),
+                    content_hash: target.content_hash,
+                    backend: target.watch_backend,
+                    templated: !self.target_configs.contains_key(name),
+                })
+            })
+            .collect();
+        self.targets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(())
+    }
+
+    fn has_templates(&self) -> bool {
+        self.target_configs
+            .values()
+            .any(|target| target.enabled && target.root().is_some_and(|root| is_template(&root)))
+    }
+
+    /// Roots of templated targets outside the watch paths, which are
+    /// watched on their own
+    fn templated_roots(&self) -> BTreeMap<PathBuf, WatchBackend> {
+        self.targets
+            .iter()
+            .filter(|target| target.templated && !self.watch_paths.iter().any(|path| target.root.starts_with(path)))
+            .map(|target| (target.root.clone(), target.backend))
+            .collect()
+    }
+
+    /// The target whose root holds `path` most closely
+    fn target_of(&self, path: &Path) -> Option<&WatchedTarget> {
+        self.targets
+            .iter()
+            .filter(|target| path.starts_with(&target.root))
+            .max_by_key(|target| target.root.components().count())
+    }
+
+    fn backend_for(&self, path: &Path) -> WatchBackend {
+        self.target_of(path).map_or(self.backend, |target| target.backend)
+    }
+
+    fn hashes_content(&self, path: &Path) -> bool {
+        self.target_of(path).map_or(self.content_hash, |target| target.content_hash)
+    }
+
+    fn debouncer(&self) -> Debouncer {
+        self.targets
+            .iter()
+            .fold(Debouncer::new(self.debounce_duration), |debouncer, target| match target.debounce {
+                Some(window) => debouncer.with_window(&target.name, window),
+                None => debouncer,
+            })
+    }
+}
+
+/// What the event loop keeps from one event to the next
+struct WatchState {
+    debouncer: Debouncer,
+    ignore_rules: Vec<IgnoreRules>,
+    hashes: ContentHashes,
+    lockfiles: LockfileSnapshots,
+    storm: StormDetector,
+    activity: Arc<WatchActivity>,
+    feed: broadcast::Sender<WatchFeedEvent>,
+}
+
+impl WatchState {
+    /// State for watching `config`'s targets afresh, their lockfiles
+    /// snapshotted to diff against if `lockfile_diff` is on
+    fn new(config: &BustCallConfig, activity: Arc<WatchActivity>, feed: broadcast::Sender<WatchFeedEvent>) -> Self {
+        let mut lockfiles = LockfileSnapshots::new();
+        if config.lockfile_diff {
+            for root in config.watch_paths.iter().chain(config.targets.iter().map(|target| &target.root)) {
+                lockfiles.snapshot_dir(root);
+            }
+        }
+        Self {
+            debouncer: config.debouncer(),
+            ignore_rules: config.ignore_rules.clone(),
+            hashes: ContentHashes::new(),
+            lockfiles,
+            storm: StormDetector::new(config.max_events_per_second, config.storm_quiet),
+            activity,
+            feed,
+        }
+    }
+}
+
+/// What the watcher's tasks report of themselves, for
+/// [`BustCallDaemon::watchers`]
+#[derive(Debug, Default)]
+struct WatchActivity {
+    /// Last event per target, ignored ones included
+    last_events: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Whether the events task is running, telling one that died apart
+    /// from a quiet one
+    events_task: AtomicBool,
+}
+
+impl WatchActivity {
+    fn seen(&self, target: &str) {
+        self.last_events.lock().unwrap_or_else(PoisonError::into_inner).insert(target.to_string(), Utc::now());
+    }
+}
+
+/// Marks the events task running until dropped, by a panic too
+struct EventsTaskRunning(Arc<WatchActivity>);
+
+impl EventsTaskRunning {
+    fn new(activity: Arc<WatchActivity>) -> Self {
+        activity.events_task.store(true, Ordering::SeqCst);
+        Self(activity)
+    }
+}
+
+impl Drop for EventsTaskRunning {
+    fn drop(&mut self) {
+        self.0.events_task.store(false, Ordering::SeqCst);
+    }
+}
+
+/// A watch path as attached
+#[derive(Debug)]
+struct WatchedRoot {
+    /// Backend it resolved to
+    backend: WatchBackend,
+    /// Backend it was configured with
+    requested: WatchBackend,
+    fingerprint: Option<String>,
+}
+
+/// What became of a watch path since the last check
+#[derive(Debug, Clone, PartialEq)]
+enum RootChange {
+    Lost(PathBuf),
+    /// Attached again; `replaced` when its tree differs from the lost one
+    Reattached { path: PathBuf, replaced: bool },
+}
+
+/// The daemon's watchers, shared with its event loop, which watches and
+/// unwatches templated targets and lost watch paths
+struct Watches {
+    /// One watcher per backend in use
+    watchers: HashMap<WatchBackend, Box<dyn Watcher + Send>>,
+    watched: HashMap<PathBuf, WatchedRoot>,
+    /// Watch paths that disappeared; see [`crate::core::watch_root`]
+    lost: HashMap<PathBuf, LostRoot>,
+    event_tx: mpsc::Sender<Event>,
+    poll_interval: Duration,
+}
+
+impl Watches {
+    fn new(event_tx: mpsc::Sender<Event>, poll_interval: Duration) -> Self {
+        Self {
+            watchers: HashMap::new(),
+            watched: HashMap::new(),
+            lost: HashMap::new(),
+            event_tx,
+            poll_interval,
         }
     }
+
+    /// Watch `path` with the backend `requested` resolves to, polling it
+    /// when a native watch fails part way, e.g. on running out of inotify
+    /// watches
+    fn watch(&mut self, path: &Path, requested: WatchBackend) -> NotifyResult<()> {
+        let mut backend = resolve(requested, path);
+        let watched = self.watcher(backend)?.watch(path, RecursiveMode::Recursive);
+        if let Err(e) = watched {
+            if backend == WatchBackend::Poll {
+                return Err(e);
+            }
+            log::warn!("⚠️ {} watch of {} failed ({}), polling it instead", backend.label(), path.display(), e);
+            let _ = self.watcher(backend)?.unwatch(path);
+            backend = WatchBackend::Poll;
+            self.watcher(backend)?.watch(path, RecursiveMode::Recursive)?;
+        }
+
+        log::info!("👀 Watching {} with {}", path.display(), backend.label());
+        let fingerprint = tree_fingerprint(path);
+        self.watched.insert(path.to_path_buf(), WatchedRoot { backend, requested, fingerprint });
+        Ok(())
+    }
+
+    fn unwatch(&mut self, path: &Path) -> NotifyResult<()> {
+        self.lost.remove(path);
+        match self.watched.remove(path).and_then(|root| self.watchers.get_mut(&root.backend)) {
+            Some(watcher) => watcher.unwatch(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Note watch paths that disappeared, and attach again those due that
+    /// are back
+    fn check_roots(&mut self, now: Instant) -> Vec<RootChange> {
+        let mut changes = Vec::new();
+        let gone: Vec<PathBuf> = self.watched.keys().filter(|path| !path.is_dir()).cloned().collect();
+        for path in gone {
+            if let Some(root) = self.watched.remove(&path) {
+                // Its watch died with it, or will fire no more
+                if let Some(watcher) = self.watchers.get_mut(&root.backend) {
+                    let _ = watcher.unwatch(&path);
+                }
+                self.lost.insert(path.clone(), LostRoot::new(root.requested, root.fingerprint, now));
+                changes.push(RootChange::Lost(path));
+            }
+        }
+
+        let due: Vec<PathBuf> = self.lost.iter().filter(|(_, lost)| lost.due(now)).map(|(path, _)| path.clone()).collect();
+        for path in due {
+            let Some(mut lost) = self.lost.remove(&path) else {
+                continue;
+            };
+            if !path.is_dir() {
+                lost.retry_later(now);
+                self.lost.insert(path, lost);
+                continue;
+            }
+            match self.watch(&path, lost.requested) {
+                Ok(()) => {
+                    let fingerprint = self.watched.get(&path).and_then(|root| root.fingerprint.as_deref());
+                    let replaced = lost.replaced_by(fingerprint);
+                    changes.push(RootChange::Reattached { path, replaced });
+                }
+                Err(e) => {
+                    lost.retry_later(now);
+                    log::debug!("{} not attached again (attempt {}): {}", path.display(), lost.attempts, e);
+                    self.lost.insert(path, lost);
+                }
+            }
+        }
+        changes
+    }
+
+    /// The watcher of `backend`, created on first use
+    fn watcher(&mut self, backend: WatchBackend) -> NotifyResult<&mut Box<dyn Watcher + Send>> {
+        let watcher = match self.watchers.entry(backend) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let event_tx = self.event_tx.clone();
+                let handler = move |result: NotifyResult<Event>| match result {
+                    Ok(event) => {
+                        let _ = event_tx.try_send(event);
+                    }
+                    Err(e) => log::error!("File watcher error: {:?}", e),
+                };
+                entry.insert(open_watcher(backend, handler, self.poll_interval)?)
+            }
+        };
+        Ok(watcher)
+    }
+}
+
+/// The watcher's tasks, run under [`crate::core::task_supervisor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum WatchTask {
+    /// Assesses file events and busts their targets' bursts
+    Events,
+    /// Notices lost watch paths and attaches them again
+    Roots,
+    /// Checks the health of the targets and recovers unhealthy ones
+    Health,
+}
+
+/// How often watch paths are checked for having disappeared
+const ROOT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often health monitors are checked for being due
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often a storm is checked for having subsided
+const STORM_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Feed events held for a subscriber falling behind before it misses some
+const FEED_CAPACITY: usize = 1024;
+
+/// Recovery shared by the watcher's tasks, one attempt at a time
+type Healing = Arc<tokio::sync::Mutex<SelfHealingArchitecture>>;
+
+/// What the watcher's tasks share, so a task restarted after a panic
+/// takes over where the failed one was
+#[derive(Clone)]
+struct TaskContext {
+    /// The configuration with path templates as last expanded
+    config: Arc<Mutex<BustCallConfig>>,
+    watches: Arc<Mutex<Option<Watches>>>,
+    events: Arc<tokio::sync::Mutex<mpsc::Receiver<Event>>>,
+    cache_manager: DimensionalCacheManager,
+    dispatch: Option<BustDispatch>,
+    notifications: Option<Arc<NotificationManager>>,
+    activity: Arc<WatchActivity>,
+    feed: broadcast::Sender<WatchFeedEvent>,
+    healing: Option<Healing>,
+    health: Option<Arc<HealthProbe>>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl TaskContext {
+    fn spawn(&self, task: WatchTask) -> TaskFuture {
+        let context = self.clone();
+        match task {
+            WatchTask::Events => Box::pin(BustCallDaemon::run_events(context)),
+            WatchTask::Roots => Box::pin(BustCallDaemon::run_roots(context)),
+            WatchTask::Health => Box::pin(BustCallDaemon::run_health(context)),
+        }
+    }
+
+    /// Bust through the dispatch when there is one, else the cache directly
+    fn bust(&self, target: &str, severity: CacheBustSeverity, reason: BustReason) -> Result<()> {
+        match &self.dispatch {
+            Some(dispatch) => dispatch(target, severity, reason),
+            None => self
+                .cache_manager
+                .bust_cache_with_reason(target, severity, reason)
+                .map_err(|e| BustcallError::PidWatcherError(format!("Cache bust failed: {}", e))),
+        }
+    }
+}
+
+/// Takes the watcher's busts in place of its cache, so they go the way of a
+/// daemon's own: quiet hours, forwarding to the leader and plugins
+pub type BustDispatch = Arc<dyn Fn(&str, CacheBustSeverity, BustReason) -> Result<()> + Send + Sync>;
+
+/// The watches, even when a task panicked holding them: a failed task must
+/// not take the others down with it
+fn lock_watches(watches: &Mutex<Option<Watches>>) -> MutexGuard<'_, Option<Watches>> {
+    watches.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 pub struct BustCallDaemon {
     config: BustCallConfig,
-    watcher: Option<PollWatcher>,
-    event_tx: Option<mpsc::Sender<Event>>,
-    is_running: Arc<Mutex<bool>>,
+    /// `config` as the running tasks have it, path templates expanded again
+    live_config: Arc<Mutex<BustCallConfig>>,
+    /// Set while running
+    watches: Arc<Mutex<Option<Watches>>>,
+    activity: Arc<WatchActivity>,
+    /// Events and bust decisions as they happen, for `bustcall watch`
+    feed: broadcast::Sender<WatchFeedEvent>,
+    is_running: Arc<AtomicBool>,
+    /// Set to stop the watcher's tasks
+    shutdown: Option<watch::Sender<bool>>,
+    supervisor: Option<JoinHandle<()>>,
+    task_restarts: Arc<AtomicU32>,
     cache_manager: DimensionalCacheManager,
-    event_history: Arc<Mutex<Vec<(Instant, EventKind)>>>,
+    dispatch: Option<BustDispatch>,
+    notifications: Option<Arc<NotificationManager>>,
+    healing: Option<Healing>,
+    health: Option<Arc<HealthProbe>>,
 }
 
 impl BustCallDaemon {
     pub fn new(config: BustCallConfig) -> Result<Self> {
         let cache_manager = DimensionalCacheManager::new()
-            .map_err(|e| BustcallError::PidWatcherError(format!("Cache manager init failed: {}", e)))?;
+            .map_err(|e| BustcallError::PidWatcherError(format!("Cache manager init failed: {}", e)))?
+            .with_dry_run(config.dry_run);
 
         Ok(Self {
+            live_config: Arc::new(Mutex::new(config.clone())),
             config,
-            watcher: None,
-            event_tx: None,
-            is_running: Arc::new(Mutex::new(false)),
+            watches: Arc::new(Mutex::new(None)),
+            activity: Arc::new(WatchActivity::default()),
+            feed: broadcast::channel(FEED_CAPACITY).0,
+            is_running: Arc::new(AtomicBool::new(false)),
+            shutdown: None,
+            supervisor: None,
+            task_restarts: Arc::new(AtomicU32::new(0)),
             cache_manager,
-            event_history: Arc::new(Mutex::new(Vec::new())),
+            dispatch: None,
+            notifications: None,
+            healing: None,
+            health: None,
         })
     }
 
+    /// Warn through `notifications` of watch paths that disappear and of
+    /// watcher tasks that panic
+    pub fn with_notifications(mut self, notifications: Arc<NotificationManager>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Hand busts to `dispatch` rather than busting the cache directly
+    pub fn with_dispatch(mut self, dispatch: BustDispatch) -> Self {
+        self.dispatch = Some(dispatch);
+        self
+    }
+
+    /// Attempt recovery of failed busts and panicked tasks with `healing`,
+    /// for `self_healing = true` under `[global]`
+    pub fn with_self_healing(mut self, healing: SelfHealingArchitecture) -> Self {
+        self.healing = Some(Arc::new(tokio::sync::Mutex::new(healing)));
+        self
+    }
+
+    /// Check the health of the self-healing's monitored targets with
+    /// `probe`, recovering those that stay unhealthy
+    pub fn with_health_probe(mut self, probe: HealthProbe) -> Self {
+        self.health = Some(Arc::new(probe));
+        self
+    }
+
     pub async fn start(&mut self) -> Result<()> {
-        if *self.is_running.lock().unwrap() {
+        if self.is_running.load(Ordering::SeqCst) {
             return Err(BustcallError::PidWatcherError(
                 "Daemon already running".to_string(),
             ));
         }
 
-        let (event_tx, mut event_rx) = mpsc::channel::<Event>(1000);
-        self.event_tx = Some(event_tx.clone());
+        let (event_tx, event_rx) = mpsc::channel::<Event>(1000);
+        let mut watches = Watches::new(event_tx, self.config.poll_interval);
 
-        // Create watcher with updated notify API
-        let mut watcher = PollWatcher::new(
-            move |result: NotifyResult<Event>| {
-                if let Ok(event) = result {
-                    let _ = event_tx.try_send(event);
-                } else if let Err(e) = result {
-                    log::error!("File watcher error: {:?}", e);
-                }
-            },
-            Config::default().with_poll_interval(self.config.poll_interval),
-        )
-        .map_err(|e| BustcallError::PidWatcherError(format!("Watcher creation failed: {}", e)))?;
-
-        // Register watch paths
-        for path in &self.config.watch_paths {
-            watcher
-                .watch(path, RecursiveMode::Recursive)
-                .map_err(|e| {
-                    BustcallError::PidWatcherError(format!(
-                        "Failed to watch path {}: {}",
-                        path.display(),
-                        e
-                    ))
-                })?;
-        }
-
-        self.watcher = Some(watcher);
-        *self.is_running.lock().unwrap() = true;
-
-        // Spawn event processing task
-        let is_running = self.is_running.clone();
-        let cache_manager = self.cache_manager.clone();
-        let event_history = self.event_history.clone();
-        let config = self.config.clone();
-
-        tokio::spawn(async move {
-            let mut debounce_buffer: HashMap<PathBuf, (Instant, EventKind)> = HashMap::new();
-            let mut last_cleanup = Instant::now();
-
-            while *is_running.lock().unwrap() {
-                tokio::select! {
-                    Some(event) = event_rx.recv() => {
-                        if let Err(e) = Self::process_event(
-                            event,
-                            &mut debounce_buffer,
-                            &cache_manager,
-                            &event_history,
-                            &config,
-                        ).await {
-                            log::error!("Event processing failed: {}", e);
-                        }
-                    }
-                    _ = sleep(Duration::from_secs(1)) => {
-                        // Periodic cleanup and debounce processing
-                        if last_cleanup.elapsed() > Duration::from_secs(5) {
-                            Self::cleanup_debounce_buffer(&mut debounce_buffer, &config);
-                            Self::cleanup_event_history(&event_history);
-                            last_cleanup = Instant::now();
-                        }
-                    }
-                }
+        // Register watch paths and templated targets, each with the backend
+        // it resolves to
+        let templated = self.config.templated_roots();
+        for path in self.config.watch_paths.iter().chain(templated.keys()) {
+            watches.watch(path, self.config.backend_for(path)).map_err(|e| {
+                BustcallError::PidWatcherError(format!("Failed to watch path {}: {}", path.display(), e))
+            })?;
+        }
+        *lock_watches(&self.watches) = Some(watches);
+
+        self.is_running.store(true, Ordering::SeqCst);
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        *self.live_config.lock().unwrap_or_else(PoisonError::into_inner) = self.config.clone();
+        let context = TaskContext {
+            config: self.live_config.clone(),
+            watches: self.watches.clone(),
+            events: Arc::new(tokio::sync::Mutex::new(event_rx)),
+            cache_manager: self.cache_manager.clone(),
+            dispatch: self.dispatch.clone(),
+            notifications: self.notifications.clone(),
+            activity: self.activity.clone(),
+            feed: self.feed.clone(),
+            healing: self.healing.clone(),
+            health: self.health.clone(),
+            shutdown: shutdown_rx.clone(),
+        };
+        let (task_restarts, notifications) = (self.task_restarts.clone(), self.notifications.clone());
+        let healing = self.healing.clone();
+        let on_panic = move |task: WatchTask, message: &str| {
+            log::error!("💥 Watcher {:?} task panicked: {}", task, message);
+            task_restarts.fetch_add(1, Ordering::SeqCst);
+            // The supervisor restarts the task; recovery is for what it held
+            let error = BustCallError::new("watcher", &format!("{:?} task panicked: {}", task, message), 9);
+            recover(healing.as_ref(), &error.into(), "watcher");
+            let Some(notifications) = &notifications else {
+                return;
+            };
+            let notification =
+                Notification::new(NotificationLevel::Error, &format!("Watcher {:?} task panicked: {}", task, message))
+                    .title("Watcher task failed")
+                    .component("watcher");
+            if let Err(e) = notifications.send_structured(&notification) {
+                log::warn!("Watcher task notification failed: {}", e);
             }
-        });
+        };
+        let mut tasks = vec![WatchTask::Events, WatchTask::Roots];
+        if self.healing.is_some() && self.health.is_some() {
+            tasks.push(WatchTask::Health);
+        }
+        self.supervisor = Some(tokio::spawn(supervise(tasks, move |task| context.spawn(task), shutdown_rx, on_panic)));
+        self.shutdown = Some(shutdown_tx);
 
         log::info!("🚀 BustCall daemon started, watching {} paths", self.config.watch_paths.len());
         Ok(())
     }
 
+    /// Tell the watcher's tasks to stop; `shutdown` also waits for them
     pub fn stop(&mut self) -> Result<()> {
-        *self.is_running.lock().unwrap() = false;
-        self.watcher = None;
-        self.event_tx = None;
+        self.is_running.store(false, Ordering::SeqCst);
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(true);
+        }
+        *lock_watches(&self.watches) = None;
+        
+        if self.config.dry_run {
+            let report = self.cache_manager.dry_run_report();
+            log::info!("🧪 Dry-run finished: {} cache mutations would have been applied", report.len());
+        }
+        
         log::info!("⏹️ BustCall daemon stopped");
         Ok(())
     }
 
+    /// Stop, and wait until the watcher's tasks are done, bursts still held
+    /// busted
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.stop()?;
+        if let Some(supervisor) = self.supervisor.take() {
+            supervisor
+                .await
+                .map_err(|e| BustcallError::PidWatcherError(format!("Watcher supervisor failed: {}", e)))?;
+        }
+        Ok(())
+    }
+
     pub fn is_running(&self) -> bool {
-        *self.is_running.lock().unwrap()
+        self.is_running.load(Ordering::SeqCst)
     }
 
-    async fn process_event(
+    /// Watcher tasks restarted after panicking since the daemon was created
+    pub fn task_restarts(&self) -> u32 {
+        self.task_restarts.load(Ordering::SeqCst)
+    }
+
+    /// What each configured target's watcher is doing
+    pub fn watchers(&self) -> Vec<WatcherStatus> {
+        self.status_handle().watchers()
+    }
+
+    /// A handle answering [`BustCallDaemon::watchers`] while the daemon
+    /// runs elsewhere, e.g. for the API server
+    pub fn status_handle(&self) -> WatcherStatusHandle {
+        WatcherStatusHandle {
+            config: self.live_config.clone(),
+            watches: self.watches.clone(),
+            is_running: self.is_running.clone(),
+            activity: self.activity.clone(),
+            feed: self.feed.clone(),
+            cache_manager: self.cache_manager.clone(),
+            healing: self.healing.clone(),
+        }
+    }
+
+    /// Every file event and bust decision from now on, until the daemon is
+    /// dropped
+    pub fn subscribe(&self) -> broadcast::Receiver<WatchFeedEvent> {
+        self.feed.subscribe()
+    }
+
+    /// The cache the watcher busts, e.g. to configure from a
+    /// [`crate::core::config::BustcallConfig`] before starting
+    pub fn cache_manager(&self) -> &DimensionalCacheManager {
+        &self.cache_manager
+    }
+
+    /// Assess file events as they come and bust bursts once quiet. Idle,
+    /// it sleeps until the next burst is due or templates are to be
+    /// expanded again.
+    async fn run_events(context: TaskContext) {
+        let mut events = context.events.lock().await;
+        let _running = EventsTaskRunning::new(context.activity.clone());
+        let mut shutdown = context.shutdown.clone();
+        let mut config = context.config.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        let mut state = WatchState::new(&config, context.activity.clone(), context.feed.clone());
+        let mut next_expansion = Instant::now() + config.template_interval;
+
+        loop {
+            let storm_check = state.storm.in_storm().then(|| Instant::now() + STORM_CHECK_INTERVAL);
+            let wake = [state.debouncer.next_due(), config.has_templates().then_some(next_expansion), storm_check]
+                .into_iter()
+                .flatten()
+                .min();
+            let due = async {
+                match wake {
+                    Some(wake) => tokio::time::sleep_until(wake.into()).await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) => Self::process_event(event, &mut state, &context.cache_manager, &config),
+                    // Every watcher is gone
+                    None => break,
+                },
+                _ = due => {}
+                _ = stopping(&mut shutdown) => break,
+            }
+            for burst in state.debouncer.ready(Instant::now()) {
+                if let Err(e) = Self::apply_burst(&burst, &context) {
+                    log::error!("Event processing failed: {}", e);
+                    recover(context.healing.as_ref(), &e, &burst.target);
+                }
+            }
+            if let Some(report) = state.storm.check(Instant::now()) {
+                Self::storm_ended(&report, &mut state, &config, &context);
+            }
+            if config.has_templates() && Instant::now() >= next_expansion {
+                Self::follow_templates(&mut config, &mut state, &context.watches);
+                *context.config.lock().unwrap_or_else(PoisonError::into_inner) = config.clone();
+                next_expansion = Instant::now() + config.template_interval;
+            }
+        }
+
+        // Bursts and storms still held when stopped are busted rather than
+        // lost
+        for burst in state.debouncer.flush() {
+            if let Err(e) = Self::apply_burst(&burst, &context) {
+                log::error!("Event processing failed: {}", e);
+                recover(context.healing.as_ref(), &e, &burst.target);
+            }
+        }
+        if let Some(report) = state.storm.finish(Instant::now()) {
+            Self::storm_ended(&report, &mut state, &config, &context);
+        }
+    }
+
+    /// Bust every target a storm touched once, High, and describe it
+    fn storm_ended(report: &StormReport, state: &mut WatchState, config: &BustCallConfig, context: &TaskContext) {
+        let description = report.describe();
+        log::warn!("🌪️ {}", description);
+        publish(
+            &state.feed,
+            WatchFeedEvent::StormEnded {
+                timestamp: Utc::now(),
+                description: description.clone(),
+                targets: report.targets.keys().cloned().collect(),
+            },
+        );
+        let reason = BustReason::EventStorm { events: report.events, seconds: report.duration.as_secs() };
+        for target in report.targets.keys() {
+            if let Err(e) = context.bust(target, CacheBustSeverity::High, reason.clone()) {
+                log::error!("Cache bust of {} failed: {}", target, e);
+                recover(context.healing.as_ref(), &e, target);
+            }
+            // What the storm did to lockfiles is busted already
+            if let Some(watched) = config.targets.iter().find(|watched| &watched.name == target) {
+                if config.lockfile_diff {
+                    state.lockfiles.snapshot_dir(&watched.root);
+                }
+            }
+        }
+
+        let Some(notifications) = &context.notifications else {
+            return;
+        };
+        let targets: Vec<&str> = report.targets.keys().map(String::as_str).collect();
+        let notification = Notification::new(NotificationLevel::Warning, &description)
+            .title("Event storm")
+            .target(&targets.join(", "))
+            .component("watcher")
+            .with_metadata("events", &report.events.to_string())
+            .with_metadata("peak_rate", &report.peak_rate.to_string());
+        if let Err(e) = notifications.send_structured(&notification) {
+            log::warn!("Event storm notification failed: {}", e);
+        }
+    }
+
+    /// Check the watch paths every `ROOT_CHECK_INTERVAL`
+    async fn run_roots(context: TaskContext) {
+        let mut shutdown = context.shutdown.clone();
+        let mut interval = tokio::time::interval(ROOT_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = stopping(&mut shutdown) => return,
+            }
+            let changes = match lock_watches(&context.watches).as_mut() {
+                Some(watches) => watches.check_roots(Instant::now()),
+                None => return,
+            };
+            if changes.is_empty() {
+                continue;
+            }
+            let config = context.config.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            for change in changes {
+                Self::root_changed(change, &config, &context);
+            }
+        }
+    }
+
+    /// Sample the targets whose health check is due and recover those
+    /// unhealthy for too long
+    async fn run_health(context: TaskContext) {
+        let (Some(healing), Some(probe)) = (context.healing.clone(), context.health.clone()) else {
+            return;
+        };
+        let mut shutdown = context.shutdown.clone();
+        let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = stopping(&mut shutdown) => return,
+            }
+            let (due, error_rates) = {
+                let healing = healing.lock().await;
+                let due = healing.due_monitors(SystemTime::now());
+                let error_rates: HashMap<String, f64> =
+                    due.iter().map(|component| (component.clone(), healing.error_rate(component))).collect();
+                (due, error_rates)
+            };
+            if due.is_empty() {
+                continue;
+            }
+            let probe = probe.clone();
+            let sampled = match tokio::task::spawn_blocking(move || probe.sample(&due, &error_rates)).await {
+                Ok(sampled) => sampled,
+                Err(e) => {
+                    log::warn!("Health check failed: {}", e);
+                    continue;
+                }
+            };
+
+            let mut healing = healing.lock().await;
+            for metrics in sampled {
+                log::debug!("🩺 {} health {}/10", metrics.component, metrics.health_score);
+                if let Some(error) = healing.evaluate_health(metrics) {
+                    log::warn!("🩺 {}", error);
+                    let result = healing.attempt_recovery(&error).await;
+                    log_recovery(&error.component, &result);
+                }
+            }
+        }
+    }
+
+    /// Busts and evictions recorded while running with `dry_run`
+    pub fn dry_run_report(&self) -> Vec<DryRunRecord> {
+        self.cache_manager.dry_run_report()
+    }
+
+    /// Expand path templates again, watching the targets of packages that
+    /// appeared and unwatching those of packages removed
+    fn follow_templates(config: &mut BustCallConfig, state: &mut WatchState, watches: &Mutex<Option<Watches>>) {
+        let names = |config: &BustCallConfig| config.targets.iter().map(|target| target.name.clone()).collect::<BTreeSet<_>>();
+        let (before, roots_before) = (names(config), config.templated_roots());
+        if let Err(e) = config.expand_templates() {
+            log::warn!("Path templates not expanded: {}", e);
+            return;
+        }
+        if names(config) == before {
+            return;
+        }
+
+        state.ignore_rules = config.ignore_rules.clone();
+        for target in &config.targets {
+            if let Some(window) = target.debounce {
+                state.debouncer.set_window(&target.name, window);
+            }
+        }
+        let roots = config.templated_roots();
+        let mut watches = lock_watches(watches);
+        let Some(watches) = watches.as_mut() else {
+            return;
+        };
+        for root in roots_before.keys().filter(|root| !roots.contains_key(*root)) {
+            log::info!("📦 {} is gone, no longer watched", root.display());
+            // Watches of a removed directory are gone with it already
+            let _ = watches.unwatch(root);
+        }
+        for (root, backend) in roots.iter().filter(|(root, _)| !roots_before.contains_key(*root)) {
+            if let Err(e) = watches.watch(root, *backend) {
+                log::warn!("New package {} not watched: {}", root.display(), e);
+            }
+            if config.lockfile_diff {
+                state.lockfiles.snapshot_dir(root);
+            }
+        }
+    }
+
+    /// Warn of a lost watch path, and bust the targets of one that came
+    /// back replaced
+    fn root_changed(
+        change: RootChange,
+        config: &BustCallConfig,
+        context: &TaskContext,
+    ) {
+        let targets_under = |path: &Path| {
+            let mut targets: Vec<String> =
+                config.targets.iter().filter(|target| target.root.starts_with(path)).map(|target| target.name.clone()).collect();
+            if targets.is_empty() {
+                targets.push(Self::target_name(config, path));
+            }
+            targets
+        };
+
+        match change {
+            // A package removed for good is dropped with its template's
+            // next expansion
+            RootChange::Lost(path) if config.templated_roots().contains_key(&path) => {
+                log::info!("📦 {} disappeared, attaching it again if it comes back", path.display());
+            }
+            RootChange::Lost(path) => {
+                log::warn!("⚠️ Watch path {} disappeared, attaching it again once it is back", path.display());
+                let Some(notifications) = &context.notifications else {
+                    return;
+                };
+                let notification = Notification::new(
+                    NotificationLevel::Warning,
+                    &format!("Watch path {} disappeared; its changes are not seen until it is back", path.display()),
+                )
+                .title("Watch path lost")
+                .target(&targets_under(&path).join(", "))
+                .component("watcher");
+                if let Err(e) = notifications.send_structured(&notification) {
+                    log::warn!("Watch path notification for {} failed: {}", path.display(), e);
+                }
+            }
+            RootChange::Reattached { path, replaced: false } => {
+                log::info!("👀 {} is back unchanged, watching it again", path.display());
+            }
+            RootChange::Reattached { path, replaced: true } => {
+                let reason = BustReason::WatchPathReplaced { path: path.display().to_string() };
+                for target in targets_under(&path) {
+                    log::warn!("♻️ {} came back with other files, busting {}", path.display(), target);
+                    if let Err(e) = context.bust(&target, CacheBustSeverity::Critical, reason.clone()) {
+                        log::error!("Cache bust of {} failed: {}", target, e);
+                        recover(context.healing.as_ref(), &e, &target);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add the event's changes to their targets' bursts
+    fn process_event(
         event: Event,
-        debounce_buffer: &mut HashMap<PathBuf, (Instant, EventKind)>,
+        state: &mut WatchState,
         cache_manager: &DimensionalCacheManager,
-        event_history: &Arc<Mutex<Vec<(Instant, EventKind)>>>,
         config: &BustCallConfig,
-    ) -> Result<()> {
+    ) {
         let now = Instant::now();
-        
-        // Update event history for rate limiting
-        {
-            let mut history = event_history.lock().unwrap();
-            history.push((now, event.kind.clone()));
+        if state.storm.record(now) {
+            log::warn!(
+                "🌪️ Event storm, over {} events/s: pausing per-file processing until it subsides",
+                config.max_events_per_second
+            );
+            publish(&state.feed, WatchFeedEvent::StormStarted { timestamp: Utc::now() });
         }
-
-        // Check rate limiting
-        if Self::should_rate_limit(event_history, config) {
-            log::warn!("⚠️ Rate limiting file events - too many events per second");
-            return Ok(());
+        // Only counted per target, and busted once when the storm is over
+        if state.storm.in_storm() {
+            for path in &event.paths {
+                let target = Self::target_name(config, path);
+                state.activity.seen(&target);
+                if !state.ignore_rules.iter_mut().any(|rules| rules.is_ignored(path)) {
+                    state.storm.absorb(&target);
+                }
+            }
+            return;
         }
 
-        // Process each path in the event
-        for path in event.paths {
-            // Debounce logic
-            if let Some((last_time, _)) = debounce_buffer.get(&path) {
-                if now.duration_since(*last_time) < config.debounce_duration {
-                    continue; // Skip debounced events
+        for (index, path) in event.paths.iter().enumerate() {
+            let kind = Self::path_event_kind(&event.kind, index);
+            let mut assessment = Self::assess(config, &mut state.ignore_rules, path, &kind);
+            state.activity.seen(&assessment.target);
+            // Lockfiles are busted by what they changed, unless overridden
+            let overridden = override_for(&config.severity_rules, path).is_some();
+            if config.lockfile_diff && !assessment.ignored && !overridden && Self::is_change(&kind) {
+                if let Some(change) = state.lockfiles.observe(path, matches!(kind, EventKind::Remove(_))) {
+                    Self::log_lockfile_change(path, &change);
+                    assessment.severity = change.severity();
                 }
             }
+            if config.journal.is_some() || state.feed.receiver_count() > 0 {
+                let record = JournalRecord {
+                    timestamp: Utc::now(),
+                    path: path.clone(),
+                    kind: JournalEventKind::of(&kind),
+                    assessment: assessment.clone(),
+                };
+                if let Some(journal) = &config.journal {
+                    if let Err(e) = journal.append(&record) {
+                        log::warn!("Event not journaled to {}: {}", journal.path().display(), e);
+                    }
+                }
+                publish(&state.feed, WatchFeedEvent::Event(record));
+            }
+            if assessment.ignored {
+                cache_manager.record_ignored(&assessment.target);
+                continue;
+            }
+            let Assessment { target: target_name, severity, .. } = assessment;
 
-            debounce_buffer.insert(path.clone(), (now, event.kind.clone()));
+            let change = match kind {
+                EventKind::Remove(_) => FileChange::Removed,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Access(_) => FileChange::Changed,
+                _ => continue,
+            };
+            // Busting is for new content, not new mtimes
+            match (change, &kind) {
+                (FileChange::Removed, _) => state.hashes.forget(path),
+                (FileChange::Changed, EventKind::Create(_) | EventKind::Modify(_))
+                    if severity.is_some() && config.hashes_content(path) =>
+                {
+                    let baseline = cache_manager.content_hash(&path.to_string_lossy());
+                    if !state.hashes.changed(path, baseline.as_deref()) {
+                        log::debug!("🟰 {} changed without new content, not busting {}", path.display(), target_name);
+                        continue;
+                    }
+                }
+                _ => {}
+            }
 
-            // Determine cache bust severity based on file type and event
-            let severity = Self::determine_cache_severity(&path, &event.kind, config);
-            
-            if let Some(severity) = severity {
-                let target_name = Self::extract_target_name(&path);
-                
-                log::info!("📁 Cache bust triggered: {} ({:?}) -> {:?}", 
-                    path.display(), event.kind, severity);
-                
-                cache_manager
-                    .bust_cache(&target_name, severity)
-                    .map_err(|e| BustcallError::PidWatcherError(format!("Cache bust failed: {}", e)))?;
+            match severity {
+                Some(severity) => state.debouncer.push(&target_name, path.clone(), change, severity, now),
+                // Tracked right away, as nothing is busted
+                None => Self::track(cache_manager, &target_name, path, change),
             }
         }
+    }
+
+    fn log_lockfile_change(path: &Path, change: &LockfileChange) {
+        match change {
+            LockfileChange::New => log::info!("🔒 {} has no baseline, busting Critical", path.display()),
+            LockfileChange::Removed => log::info!("🔒 {} removed, busting Critical", path.display()),
+            LockfileChange::Unreadable(e) => log::warn!("🔒 {} not diffed, busting Critical: {}", path.display(), e),
+            LockfileChange::Changed(diff) => log::info!(
+                "🔒 {}: {} added, {} removed, {} changed, {} of them direct ({:?})",
+                path.display(),
+                diff.added.len(),
+                diff.removed.len(),
+                diff.changed.len(),
+                diff.direct.len(),
+                change.severity()
+            ),
+        }
+    }
 
+    /// The target of a change to `path`, its severity, and whether ignore
+    /// rules or severity overrides drop it
+    pub fn assess(config: &BustCallConfig, ignore_rules: &mut [IgnoreRules], path: &Path, kind: &EventKind) -> Assessment {
+        if let Some(rules) = ignore_rules.iter_mut().find_map(|rules| rules.is_ignored(path).then_some(rules)) {
+            log::trace!("🙈 {} ignored by {} ignore rules", path.display(), rules.target());
+            return Assessment { target: rules.target().to_string(), severity: None, ignored: true };
+        }
+
+        // A target's severity overrides take precedence over the defaults by
+        // file type and event
+        match override_for(&config.severity_rules, path) {
+            Some((target, SeverityOverride::Ignore)) => {
+                log::debug!("🙈 {} ignored by {} severity overrides", path.display(), target);
+                Assessment { target: target.to_string(), severity: None, ignored: true }
+            }
+            Some((target, severity)) if Self::is_change(kind) => {
+                Assessment { target: target.to_string(), severity: severity.severity(), ignored: false }
+            }
+            _ => Assessment {
+                target: Self::target_name(config, path),
+                severity: Self::determine_cache_severity(path, kind, config),
+                ignored: false,
+            },
+        }
+    }
+
+    /// Assess journaled events again against `config`'s current rules,
+    /// pairing each with what it would be now
+    pub fn reassess(config: &BustCallConfig, records: Vec<JournalRecord>) -> Vec<(JournalRecord, Assessment)> {
+        let mut ignore_rules = config.ignore_rules.clone();
+        records
+            .into_iter()
+            .map(|record| {
+                let assessment = Self::assess(config, &mut ignore_rules, &record.path, &record.kind.event_kind());
+                (record, assessment)
+            })
+            .collect()
+    }
+
+    /// Bust a burst's target once
+    fn apply_burst(burst: &Burst, context: &TaskContext) -> Result<()> {
+        log::info!(
+            "📁 Cache bust triggered: {} ({} events on {} files) -> {:?}",
+            burst.target,
+            burst.events,
+            burst.paths.len(),
+            burst.severity
+        );
+        context.bust(&burst.target, burst.severity.clone(), burst.reason())?;
+        publish(
+            &context.feed,
+            WatchFeedEvent::Bust {
+                timestamp: Utc::now(),
+                target: burst.target.clone(),
+                severity: burst.severity.clone(),
+                reason: burst.reason(),
+                events: burst.events,
+                files: burst.paths.len(),
+            },
+        );
+
+        // Track the observed files as cache entries of the target, after
+        // the bust so they survive it, giving eviction real data
+        for (path, change) in &burst.paths {
+            Self::track(&context.cache_manager, &burst.target, path, *change);
+        }
         Ok(())
     }
 
-    fn should_rate_limit(
-        event_history: &Arc<Mutex<Vec<(Instant, EventKind)>>>,
-        config: &BustCallConfig,
-    ) -> bool {
-        let history = event_history.lock().unwrap();
-        let one_second_ago = Instant::now() - Duration::from_secs(1);
-        
-        let recent_events = history
-            .iter()
-            .filter(|(time, _)| *time > one_second_ago)
-            .count();
-        
-        recent_events > config.max_events_per_second as usize
+    fn track(cache_manager: &DimensionalCacheManager, target: &str, path: &Path, change: FileChange) {
+        let cache_id = path.to_string_lossy();
+        match change {
+            FileChange::Removed => {
+                cache_manager.remove_entry(&cache_id);
+            }
+            FileChange::Changed => {
+                cache_manager.register_entry(&cache_id, target, 0, EvictionStrategy::LRU);
+            }
+        }
+        // Directory entries holding the file grew or shrank with it
+        cache_manager.refresh_sizes(path);
+    }
+
+    /// The event as seen by its `index`th path: a rename removes its source
+    /// and creates its destination
+    fn path_event_kind(kind: &EventKind, index: usize) -> EventKind {
+        match kind {
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => EventKind::Remove(RemoveKind::Any),
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => EventKind::Create(CreateKind::Any),
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if index == 0 => EventKind::Remove(RemoveKind::Any),
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => EventKind::Create(CreateKind::Any),
+            kind => *kind,
+        }
+    }
+
+    fn is_change(event_kind: &EventKind) -> bool {
+        matches!(event_kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
     }
 
     fn determine_cache_severity(
-        path: &PathBuf,
+        path: &Path,
         event_kind: &EventKind,
-        config: &BustCallConfig,
+        _config: &BustCallConfig,
     ) -> Option<CacheBustSeverity> {
         let extension = path.extension()?.to_str()?;
         let is_critical_file = matches!(
@@ -248,7 +1182,16 @@ impl BustCallDaemon {
         }
     }
 
-    fn extract_target_name(path: &PathBuf) -> String {
+    /// The configured target holding `path`, else one guessed from its
+    /// directory
+    fn target_name(config: &BustCallConfig, path: &Path) -> String {
+        match config.target_of(path) {
+            Some(target) => target.name.clone(),
+            None => Self::extract_target_name(path),
+        }
+    }
+
+    fn extract_target_name(path: &Path) -> String {
         // Extract target name from path components
         if let Some(parent) = path.parent() {
             if let Some(dir_name) = parent.file_name() {
@@ -269,40 +1212,20 @@ impl BustCallDaemon {
         "generic".to_string()
     }
 
-    fn cleanup_debounce_buffer(
-        buffer: &mut HashMap<PathBuf, (Instant, EventKind)>,
-        config: &BustCallConfig,
-    ) {
-        let cutoff = Instant::now() - config.debounce_duration * 10;
-        buffer.retain(|_, (time, _)| *time > cutoff);
-    }
-
-    fn cleanup_event_history(event_history: &Arc<Mutex<Vec<(Instant, EventKind)>>>) {
-        let mut history = event_history.lock().unwrap();
-        let cutoff = Instant::now() - Duration::from_secs(60);
-        history.retain(|(time, _)| *time > cutoff);
-    }
-
     pub fn add_watch_path(&mut self, path: PathBuf) -> Result<()> {
-        if let Some(watcher) = &mut self.watcher {
-            watcher
-                .watch(&path, RecursiveMode::Recursive)
-                .map_err(|e| {
-                    BustcallError::PidWatcherError(format!(
-                        "Failed to add watch path {}: {}",
-                        path.display(),
-                        e
-                    ))
-                })?;
+        if let Some(watches) = lock_watches(&self.watches).as_mut() {
+            watches.watch(&path, self.config.backend_for(&path)).map_err(|e| {
+                BustcallError::PidWatcherError(format!("Failed to add watch path {}: {}", path.display(), e))
+            })?;
         }
-        
+
         self.config.watch_paths.push(path);
         Ok(())
     }
 
     pub fn remove_watch_path(&mut self, path: &PathBuf) -> Result<()> {
-        if let Some(watcher) = &mut self.watcher {
-            watcher.unwatch(path).map_err(|e| {
+        if let Some(watches) = lock_watches(&self.watches).as_mut() {
+            watches.unwatch(path).map_err(|e| {
                 BustcallError::PidWatcherError(format!(
                     "Failed to remove watch path {}: {}",
                     path.display(),
@@ -314,6 +1237,479 @@ impl BustCallDaemon {
         self.config.watch_paths.retain(|p| p != path);
         Ok(())
     }
+
+    /// Backend `path` is watched with while running
+    pub fn watch_backend(&self, path: &Path) -> Option<WatchBackend> {
+        Some(lock_watches(&self.watches).as_ref()?.watched.get(path)?.backend)
+    }
+}
+
+/// How a target's watcher is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherHealth {
+    Healthy,
+    /// Some of its watch paths are lost, or polled after their native
+    /// watch failed
+    Degraded,
+    /// Its changes go unseen: every watch path is lost, or events are no
+    /// longer processed
+    Dead,
+    /// The daemon is not running
+    Stopped,
+}
+
+/// A configured target's watcher, as `bustcall status --verbose` and
+/// `/api/v1/watchers` report it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherStatus {
+    pub target: String,
+    /// Backends its watch paths resolved to
+    pub backends: Vec<WatchBackend>,
+    pub watched_paths: usize,
+    /// Watch paths gone, to be attached again once back
+    pub lost_paths: usize,
+    pub last_event: Option<DateTime<Utc>>,
+    pub last_bust: Option<BustRecord>,
+    /// Processes of its runtime running now
+    pub pids: Vec<u32>,
+    pub health: WatcherHealth,
+    /// Why it is not healthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// What `bustcall watch` streams: every file event as assessed, and the
+/// busts and storms they led to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchFeedEvent {
+    /// A file event, ignored ones and those warranting no bust included
+    Event(JournalRecord),
+    /// A target busted once its burst of events went quiet
+    Bust {
+        timestamp: DateTime<Utc>,
+        target: String,
+        severity: CacheBustSeverity,
+        reason: BustReason,
+        events: usize,
+        files: usize,
+    },
+    /// Per-file processing paused until the storm subsides
+    StormStarted { timestamp: DateTime<Utc> },
+    /// The storm subsided and `targets` were busted High
+    StormEnded { timestamp: DateTime<Utc>, description: String, targets: Vec<String> },
+}
+
+impl WatchFeedEvent {
+    /// Whether it is about `target`, or a target expanded from it if it is
+    /// a path template; storms starting are about every target
+    pub fn concerns(&self, target: &str) -> bool {
+        let is_of = |name: &str| name == target || name.strip_prefix(target).is_some_and(|rest| rest.starts_with(':'));
+        match self {
+            Self::Event(record) => is_of(&record.assessment.target),
+            Self::Bust { target: busted, .. } => is_of(busted),
+            Self::StormStarted { .. } => true,
+            Self::StormEnded { targets, .. } => targets.iter().any(|busted| is_of(busted)),
+        }
+    }
+}
+
+/// Attempt recovery of `error`, raised by `component`, in the background so
+/// the failing task carries on; without `healing` the error is only logged
+fn recover(healing: Option<&Healing>, error: &BustcallError, component: &str) {
+    let Some(healing) = healing.cloned() else {
+        return;
+    };
+    let error = error.scored(component);
+    tokio::spawn(async move {
+        let mut healing = healing.lock().await;
+        healing.record_error(&error.component);
+        let result = healing.attempt_recovery(&error).await;
+        log_recovery(&error.component, &result);
+    });
+}
+
+fn log_recovery(component: &str, result: &RecoveryResult) {
+    match result {
+        RecoveryResult::Success { recovery_time_ms, .. } => {
+            log::info!("🩹 {} recovered in {}ms", component, recovery_time_ms)
+        }
+        RecoveryResult::PartialRecovery { remaining_issues, .. } => {
+            log::warn!("🩹 {} partly recovered: {}", component, remaining_issues.join("; "))
+        }
+        RecoveryResult::Failed { error, .. } => {
+            log::error!("🩹 Recovery of {} failed: {}", component, error)
+        }
+        RecoveryResult::ManualIntervention { reason, .. } => {
+            log::error!("🚨 {} needs manual intervention: {}", component, reason)
+        }
+    }
+}
+
+/// Send `event` to whoever follows the watcher; nobody doing so is fine
+fn publish(feed: &broadcast::Sender<WatchFeedEvent>, event: WatchFeedEvent) {
+    if feed.receiver_count() > 0 {
+        let _ = feed.send(event);
+    }
+}
+
+/// Answers [`BustCallDaemon::watchers`] from the state the daemon shares
+/// with its tasks
+#[derive(Clone)]
+pub struct WatcherStatusHandle {
+    config: Arc<Mutex<BustCallConfig>>,
+    watches: Arc<Mutex<Option<Watches>>>,
+    is_running: Arc<AtomicBool>,
+    activity: Arc<WatchActivity>,
+    feed: broadcast::Sender<WatchFeedEvent>,
+    cache_manager: DimensionalCacheManager,
+    healing: Option<Healing>,
+}
+
+impl WatcherStatusHandle {
+    /// As [`BustCallDaemon::subscribe`], e.g. for `/api/v1/watch/events`
+    pub fn subscribe(&self) -> broadcast::Receiver<WatchFeedEvent> {
+        self.feed.subscribe()
+    }
+
+    /// Recovery circuit breakers, `None` without self-healing
+    pub async fn circuits(&self) -> Option<Vec<CircuitStatus>> {
+        Some(self.healing.as_ref()?.lock().await.circuits())
+    }
+
+    /// Close `component`'s recovery circuit, for `bustcall heal reset`;
+    /// whether it was open, `None` without self-healing
+    pub async fn reset_circuit(&self, component: &str) -> Option<bool> {
+        Some(self.healing.as_ref()?.lock().await.reset_circuit(component))
+    }
+
+    pub fn watchers(&self) -> Vec<WatcherStatus> {
+        let config = self.config.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        let running = self.is_running.load(Ordering::SeqCst);
+        let events_task = self.activity.events_task.load(Ordering::SeqCst);
+        let last_events = self.activity.last_events.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        let busts = self.cache_manager.recent_busts();
+
+        // Watch paths holding a target's files or below its root
+        let roots: Vec<(Vec<WatchBackend>, usize, usize, usize)> = {
+            let watches = lock_watches(&self.watches);
+            config
+                .targets
+                .iter()
+                .map(|target| {
+                    let covers = |path: &Path| target.root.starts_with(path) || path.starts_with(&target.root);
+                    let Some(watches) = watches.as_ref() else {
+                        return (Vec::new(), 0, 0, 0);
+                    };
+                    let watched: Vec<&WatchedRoot> =
+                        watches.watched.iter().filter(|(path, _)| covers(path)).map(|(_, root)| root).collect();
+                    let mut backends: Vec<WatchBackend> = watched.iter().map(|root| root.backend).collect();
+                    backends.sort_by_key(|backend| backend.label());
+                    backends.dedup();
+                    let fell_back = watched
+                        .iter()
+                        .filter(|root| {
+                            root.backend == WatchBackend::Poll
+                                && matches!(root.requested, WatchBackend::Inotify | WatchBackend::Fanotify)
+                        })
+                        .count();
+                    (backends, watched.len(), watches.lost.keys().filter(|path| covers(path)).count(), fell_back)
+                })
+                .collect()
+        };
+
+        let processes = ProcessManager::new();
+        config
+            .targets
+            .iter()
+            .zip(roots)
+            .map(|(target, (backends, watched_paths, lost_paths, fell_back))| {
+                let (health, detail) = if !running {
+                    (WatcherHealth::Stopped, None)
+                } else if !events_task {
+                    (WatcherHealth::Dead, Some("file events are no longer processed".to_string()))
+                } else if watched_paths == 0 && lost_paths > 0 {
+                    (WatcherHealth::Dead, Some(format!("{} watch paths lost", lost_paths)))
+                } else if watched_paths == 0 {
+                    (WatcherHealth::Dead, Some("no watch path covers it".to_string()))
+                } else if lost_paths > 0 {
+                    (WatcherHealth::Degraded, Some(format!("{} watch paths lost", lost_paths)))
+                } else if fell_back > 0 {
+                    (WatcherHealth::Degraded, Some(format!("{} watch paths polled after their native watch failed", fell_back)))
+                } else {
+                    (WatcherHealth::Healthy, None)
+                };
+                let pids = config
+                    .target_configs
+                    .get(&target.name)
+                    .and_then(TargetConfig::runtime_lookup)
+                    .and_then(|lookup| processes.find_runtime(&lookup).ok())
+                    .map(|found| found.iter().map(|process| process.pid).collect())
+                    .unwrap_or_default();
+
+                WatcherStatus {
+                    target: target.name.clone(),
+                    backends,
+                    watched_paths,
+                    lost_paths,
+                    last_event: last_events.get(&target.name).copied(),
+                    last_bust: busts.iter().rev().find(|bust| bust.target == target.name).cloned(),
+                    pids,
+                    health,
+                    detail,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Change in the set of processes backing a runtime target
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeChange {
+    /// The runtime came up with no process running before
+    Started { pids: Vec<u32> },
+    /// More workers joined a running runtime
+    Added { pids: Vec<u32> },
+    /// Some workers died while others are still running
+    PartialDeath { died: Vec<u32>, remaining: usize },
+    /// Every process of the runtime is gone
+    FullDeath { died: Vec<u32> },
+    /// A process kept running while its executable, interpreter version or
+    /// watched environment variables changed
+    EnvironmentChanged {
+        pid: u32,
+        changes: Vec<String>,
+        severity: CacheBustSeverity,
+    },
+}
+
+impl RuntimeChange {
+    /// Bust severity for the change; deaths scale with the share of workers lost
+    pub fn severity(&self) -> Option<CacheBustSeverity> {
+        match self {
+            RuntimeChange::Started { .. } => Some(CacheBustSeverity::Medium),
+            RuntimeChange::Added { .. } => None,
+            RuntimeChange::PartialDeath { died, remaining } => {
+                if died.len() >= *remaining {
+                    Some(CacheBustSeverity::High)
+                } else {
+                    Some(CacheBustSeverity::Medium)
+                }
+            }
+            RuntimeChange::FullDeath { .. } => Some(CacheBustSeverity::Critical),
+            RuntimeChange::EnvironmentChanged { severity, .. } => Some(severity.clone()),
+        }
+    }
+
+    /// Why the change busts the target, when it does
+    pub fn bust_reason(&self) -> BustReason {
+        match self {
+            RuntimeChange::Started { pids } | RuntimeChange::Added { pids } => {
+                BustReason::PidRestarted { pids: pids.clone() }
+            }
+            RuntimeChange::PartialDeath { died, .. } | RuntimeChange::FullDeath { died } => {
+                BustReason::PidDied { pids: died.clone() }
+            }
+            RuntimeChange::EnvironmentChanged { pid, changes, .. } => BustReason::EnvironmentChanged {
+                pid: *pid,
+                changes: changes.clone(),
+            },
+        }
+    }
+}
+
+/// Compare two samples of a runtime's pid set
+pub fn diff_pids(previous: &HashSet<u32>, current: &HashSet<u32>) -> Vec<RuntimeChange> {
+    let start_times = |pids: &HashSet<u32>| pids.iter().map(|&pid| (pid, 0)).collect::<HashMap<u32, u64>>();
+    diff_processes(&start_times(previous), &start_times(current))
+}
+
+/// Compare two samples of a runtime's processes, start times by pid. A pid
+/// held by a process started at another time than before was reused, after
+/// the process that held it died, so it counts as both died and added.
+pub fn diff_processes(previous: &HashMap<u32, u64>, current: &HashMap<u32, u64>) -> Vec<RuntimeChange> {
+    let mut died: Vec<u32> = previous
+        .iter()
+        .filter(|(pid, started)| current.get(*pid) != Some(*started))
+        .map(|(&pid, _)| pid)
+        .collect();
+    let mut added: Vec<u32> = current
+        .iter()
+        .filter(|(pid, started)| previous.get(*pid) != Some(*started))
+        .map(|(&pid, _)| pid)
+        .collect();
+    died.sort_unstable();
+    added.sort_unstable();
+
+    let remaining = previous.len() - died.len();
+
+    let mut changes = Vec::new();
+    if !died.is_empty() {
+        changes.push(if remaining == 0 {
+            RuntimeChange::FullDeath { died }
+        } else {
+            RuntimeChange::PartialDeath { died, remaining }
+        });
+    }
+    if !added.is_empty() {
+        // Replacement workers alongside survivors are additions, not a restart
+        changes.push(if remaining == 0 {
+            RuntimeChange::Started { pids: added }
+        } else {
+            RuntimeChange::Added { pids: added }
+        });
+    }
+    changes
+}
+
+/// Tracks every process backing a runtime target, e.g. all `node` workers
+pub struct RuntimeWatcher {
+    target: String,
+    lookup: RuntimeLookup,
+    processes: ProcessManager,
+    /// Start time by tracked pid
+    pids: HashMap<u32, u64>,
+    fingerprint: Option<FingerprintConfig>,
+    /// Last fingerprint per tracked pid
+    fingerprints: HashMap<u32, EnvFingerprint>,
+    schedule: PollBackoff,
+}
+
+impl RuntimeWatcher {
+    pub fn new(target: &str, lookup: RuntimeLookup) -> Self {
+        Self {
+            target: target.to_string(),
+            lookup,
+            processes: ProcessManager::new(),
+            pids: HashMap::new(),
+            fingerprint: None,
+            fingerprints: HashMap::new(),
+            schedule: PollBackoff::fixed(Duration::from_secs(GlobalConfig::default().daemon_interval_seconds)),
+        }
+    }
+
+    /// Poll on `schedule` rather than every `daemon_interval_seconds`
+    pub fn with_poll_backoff(mut self, schedule: PollBackoff) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Also bust when a tracked process's environment changes under it
+    pub fn with_fingerprint(mut self, config: FingerprintConfig) -> Self {
+        self.fingerprint = Some(config);
+        self
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Pids seen in the last poll
+    pub fn pids(&self) -> HashSet<u32> {
+        self.pids.keys().copied().collect()
+    }
+
+    /// The runtime's processes as they are now, with their CPU usage since
+    /// the last poll
+    pub fn processes(&self) -> Result<Vec<ProcessInfo>> {
+        self.processes
+            .find_runtime(&self.lookup)
+            .map_err(|e| BustcallError::PidWatcherError(format!("Runtime lookup failed: {}", e)))
+    }
+
+    /// Whether `event` warrants polling now rather than on the next interval:
+    /// one of the tracked processes exited
+    pub fn is_affected_by(&self, event: &ProcessEvent) -> bool {
+        matches!(event, ProcessEvent::Exit { pid, .. } if self.pids.contains_key(pid))
+    }
+
+    /// Make the next poll due now if `event` affects the runtime
+    pub fn wake_on(&mut self, event: &ProcessEvent, now: Instant) -> bool {
+        let affected = self.is_affected_by(event);
+        if affected {
+            self.schedule.wake(now);
+        }
+        affected
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.schedule.is_due(now)
+    }
+
+    /// When the next poll is due; `None` before the first
+    pub fn next_poll(&self) -> Option<Instant> {
+        self.schedule.next_due()
+    }
+
+    /// Current wait between polls, grown while the runtime is stable
+    pub fn poll_interval(&self) -> Duration {
+        self.schedule.interval()
+    }
+
+    /// Re-resolve the runtime's processes and report what changed
+    pub fn poll(&mut self) -> Result<Vec<RuntimeChange>> {
+        let current: HashMap<u32, u64> = self
+            .processes
+            .find_runtime(&self.lookup)
+            .map_err(|e| BustcallError::PidWatcherError(format!("Runtime lookup failed: {}", e)))?
+            .into_iter()
+            .map(|process| (process.pid, process.start_time))
+            .collect();
+
+        let mut changes = diff_processes(&self.pids, &current);
+        // A reused pid's fingerprint belongs to the process that held it before
+        self.fingerprints
+            .retain(|pid, _| self.pids.get(pid).is_some_and(|started| current.get(pid) == Some(started)));
+        self.pids = current;
+        if let Some(config) = self.fingerprint.clone() {
+            changes.extend(self.compare_fingerprints(&config)?);
+        }
+        self.schedule.record(Instant::now(), !changes.is_empty());
+        Ok(changes)
+    }
+
+    fn compare_fingerprints(&mut self, config: &FingerprintConfig) -> Result<Vec<RuntimeChange>> {
+        self.fingerprints.retain(|pid, _| self.pids.contains_key(pid));
+
+        let mut pids: Vec<u32> = self.pids.keys().copied().collect();
+        pids.sort_unstable();
+
+        let mut changes = Vec::new();
+        for pid in pids {
+            let Some(current) = self.processes.fingerprint(pid, config)? else {
+                continue;
+            };
+            // A pid's first fingerprint is the baseline, not a change
+            if let Some(previous) = self.fingerprints.insert(pid, current.clone()) {
+                let fields = current.changes_from(&previous);
+                if !fields.is_empty() {
+                    changes.push(RuntimeChange::EnvironmentChanged {
+                        pid,
+                        changes: fields,
+                        severity: config.severity.clone(),
+                    });
+                }
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Poll and bust the target for every change that warrants it
+    pub fn poll_and_bust(&mut self, cache_manager: &DimensionalCacheManager) -> Result<Vec<RuntimeChange>> {
+        let changes = self.poll()?;
+
+        for change in &changes {
+            if let Some(severity) = change.severity() {
+                log::info!("🔁 Runtime {} changed: {:?} -> {:?}", self.target, change, severity);
+                cache_manager
+                    .bust_cache_with_reason(&self.target, severity, change.bust_reason())
+                    .map_err(|e| BustcallError::PidWatcherError(format!("Cache bust failed: {}", e)))?;
+            }
+        }
+
+        Ok(changes)
+    }
 }
 
 #[cfg(test)]
@@ -334,9 +1730,131 @@ mod tests {
 
         daemon.start().await.unwrap();
         assert!(daemon.is_running());
+        assert!(daemon.watch_backend(temp_dir.path()).is_some_and(|backend| backend != WatchBackend::Auto));
 
-        daemon.stop().unwrap();
+        daemon.shutdown().await.unwrap();
         assert!(!daemon.is_running());
+        assert_eq!(daemon.task_restarts(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_watchers_report_activity_and_health() {
+        let dir = TempDir::new().unwrap();
+        let web = dir.path().join("web");
+        std::fs::create_dir(&web).unwrap();
+        let targets = HashMap::from([
+            ("web".to_string(), TargetConfig { path: Some(web.to_string_lossy().into_owned()), ..Default::default() }),
+            ("docs".to_string(), TargetConfig { path: Some("/nowhere/docs".to_string()), ..Default::default() }),
+        ]);
+        let config = BustCallConfig {
+            watch_paths: vec![web.clone()],
+            debounce_duration: Duration::from_millis(20),
+            ..Default::default()
+        }
+        .with_targets(&targets)
+        .unwrap();
+
+        let mut daemon = BustCallDaemon::new(config).unwrap();
+        let handle = daemon.status_handle();
+        assert!(handle.watchers().iter().all(|watcher| watcher.health == WatcherHealth::Stopped));
+
+        daemon.start().await.unwrap();
+        std::fs::write(web.join("index.js"), "export {}").unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let watchers = handle.watchers();
+        let (docs, web) = (&watchers[0], &watchers[1]);
+        assert_eq!((web.target.as_str(), web.health, web.watched_paths), ("web", WatcherHealth::Healthy, 1));
+        assert!(web.last_event.is_some());
+        assert!(web.last_bust.is_some());
+        assert_eq!((docs.health, docs.watched_paths), (WatcherHealth::Dead, 0));
+
+        daemon.shutdown().await.unwrap();
+        assert!(daemon.watchers().iter().all(|watcher| watcher.health == WatcherHealth::Stopped));
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_follow_events_and_busts() {
+        let dir = TempDir::new().unwrap();
+        let targets = HashMap::from([(
+            "web".to_string(),
+            TargetConfig { path: Some(dir.path().to_string_lossy().into_owned()), ..Default::default() },
+        )]);
+        let config = BustCallConfig {
+            watch_paths: vec![dir.path().to_path_buf()],
+            debounce_duration: Duration::from_millis(20),
+            ..Default::default()
+        }
+        .with_targets(&targets)
+        .unwrap();
+
+        let mut daemon = BustCallDaemon::new(config).unwrap();
+        let mut feed = daemon.subscribe();
+        daemon.start().await.unwrap();
+        std::fs::write(dir.path().join("index.js"), "export {}").unwrap();
+
+        let mut seen = Vec::new();
+        let followed = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Ok(event) = feed.recv().await {
+                let busted = matches!(event, WatchFeedEvent::Bust { .. });
+                seen.push(event);
+                if busted {
+                    break;
+                }
+            }
+        })
+        .await;
+        daemon.shutdown().await.unwrap();
+
+        assert!(followed.is_ok(), "no bust followed: {:?}", seen);
+        assert!(seen.iter().all(|event| event.concerns("web") && !event.concerns("we") && !event.concerns("docs")));
+        assert!(matches!(&seen[0], WatchFeedEvent::Event(record) if record.path.ends_with("index.js")));
+        let line = serde_json::to_string(seen.last().unwrap()).unwrap();
+        assert!(line.contains(r#""type":"bust""#));
+        assert_eq!(serde_json::from_str::<WatchFeedEvent>(&line).unwrap(), *seen.last().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_busts_go_through_the_dispatch() {
+        let dir = TempDir::new().unwrap();
+        let targets = HashMap::from([(
+            "web".to_string(),
+            TargetConfig { path: Some(dir.path().to_string_lossy().into_owned()), ..Default::default() },
+        )]);
+        let config = BustCallConfig {
+            watch_paths: vec![dir.path().to_path_buf()],
+            debounce_duration: Duration::from_millis(20),
+            ..Default::default()
+        }
+        .with_targets(&targets)
+        .unwrap();
+
+        let dispatched = Arc::new(Mutex::new(Vec::new()));
+        let seen = dispatched.clone();
+        let dispatch: BustDispatch = Arc::new(move |target, severity, reason| {
+            seen.lock().unwrap().push((target.to_string(), severity, reason));
+            Ok(())
+        });
+        let mut daemon = BustCallDaemon::new(config).unwrap().with_dispatch(dispatch);
+        let mut feed = daemon.subscribe();
+        daemon.start().await.unwrap();
+        std::fs::write(dir.path().join("index.js"), "export {}").unwrap();
+
+        let busted = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Ok(event) = feed.recv().await {
+                if matches!(event, WatchFeedEvent::Bust { .. }) {
+                    break;
+                }
+            }
+        })
+        .await;
+        daemon.shutdown().await.unwrap();
+
+        assert!(busted.is_ok());
+        let dispatched = dispatched.lock().unwrap();
+        assert_eq!(dispatched.len(), 1);
+        assert_eq!(dispatched[0].0, "web");
+        assert!(daemon.cache_manager().recent_busts().is_empty());
     }
 
     #[test]
@@ -372,4 +1890,163 @@ mod tests {
         );
         assert_eq!(severity, Some(CacheBustSeverity::Low));
     }
+
+    #[test]
+    fn test_renames_and_configured_targets() {
+        let rename = EventKind::Modify(ModifyKind::Name(RenameMode::Both));
+        assert!(matches!(BustCallDaemon::path_event_kind(&rename, 0), EventKind::Remove(_)));
+        assert!(matches!(BustCallDaemon::path_event_kind(&rename, 1), EventKind::Create(_)));
+
+        let targets = HashMap::from([
+            ("web".to_string(), TargetConfig { path: Some("/repo/web".to_string()), ..Default::default() }),
+            (
+                "docs".to_string(),
+                TargetConfig {
+                    path: Some("/repo/web/docs".to_string()),
+                    debounce_ms: Some(50),
+                    watch_backend: WatchBackend::Poll,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let config = BustCallConfig::default().with_targets(&targets).unwrap();
+        let target_of = |path: &str| BustCallDaemon::target_name(&config, Path::new(path));
+        assert_eq!(target_of("/repo/web/src/index.ts"), "web");
+        assert_eq!(target_of("/repo/web/docs/intro.md"), "docs");
+        assert_eq!(target_of("/elsewhere/node_modules/x.js"), "node");
+        assert_eq!(config.debouncer().window("docs"), Duration::from_millis(50));
+        assert_eq!(config.debouncer().window("web"), config.debounce_duration);
+        assert_eq!(config.backend_for(Path::new("/repo/web/docs")), WatchBackend::Poll);
+        assert_eq!(config.backend_for(Path::new("/repo/web")), WatchBackend::Auto);
+    }
+
+    #[test]
+    fn test_templated_targets_follow_packages() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("packages/foo/node_modules")).unwrap();
+        let template = TargetConfig {
+            resolved_path: Some(dir.path().join("packages/*/node_modules")),
+            debounce_ms: Some(50),
+            ..Default::default()
+        };
+        let targets = HashMap::from([("node".to_string(), template)]);
+        let mut config = BustCallConfig::default().with_targets(&targets).unwrap();
+        assert!(config.has_templates());
+        assert_eq!(config.targets.len(), 1);
+        assert_eq!(config.targets[0].name, "node:packages/foo");
+        assert!(config.templated_roots().contains_key(&dir.path().join("packages/foo/node_modules")));
+
+        let mut state = WatchState::new(&config, Arc::default(), broadcast::channel(16).0);
+        std::fs::create_dir_all(dir.path().join("packages/bar/node_modules")).unwrap();
+        std::fs::remove_dir_all(dir.path().join("packages/foo")).unwrap();
+        BustCallDaemon::follow_templates(&mut config, &mut state, &Mutex::new(None));
+        let names: Vec<&str> = config.targets.iter().map(|target| target.name.as_str()).collect();
+        assert_eq!(names, ["node:packages/bar"]);
+        assert_eq!(state.debouncer.window("node:packages/bar"), Duration::from_millis(50));
+
+        // Paths inside a watch path are already watched through it
+        config.watch_paths = vec![dir.path().to_path_buf()];
+        assert!(config.templated_roots().is_empty());
+    }
+
+    #[test]
+    fn test_journaled_events_are_reassessed_with_current_rules() {
+        let web = TargetConfig {
+            path: Some("/repo/web".to_string()),
+            severity_overrides: BTreeMap::from([("*.lock".to_string(), SeverityOverride::Critical)]),
+            ignore: vec!["dist/**".to_string()],
+            ..Default::default()
+        };
+        let config = BustCallConfig::default().with_targets(&HashMap::from([("web".to_string(), web)])).unwrap();
+        let record = |path: &str, kind: JournalEventKind| JournalRecord {
+            timestamp: Utc::now(),
+            path: PathBuf::from(path),
+            kind,
+            assessment: Assessment { target: "web".to_string(), severity: Some(CacheBustSeverity::Low), ignored: false },
+        };
+
+        let replayed = BustCallDaemon::reassess(
+            &config,
+            vec![
+                record("/repo/web/yarn.lock", JournalEventKind::Modify),
+                record("/repo/web/dist/app.js", JournalEventKind::Create),
+                record("/repo/web/src/index.ts", JournalEventKind::Remove),
+            ],
+        );
+        assert_eq!(replayed[0].1.severity, Some(CacheBustSeverity::Critical));
+        assert!(replayed[1].1.ignored);
+        assert_eq!(replayed[2].1, Assessment { target: "web".to_string(), severity: Some(CacheBustSeverity::High), ignored: false });
+    }
+
+    #[test]
+    fn test_lost_watch_paths_are_attached_again() {
+        let dir = TempDir::new().unwrap();
+        let venv = dir.path().join("venv");
+        std::fs::create_dir_all(venv.join("lib")).unwrap();
+        std::fs::write(venv.join("lib/six.py"), "x = 1").unwrap();
+        let (event_tx, _event_rx) = mpsc::channel(10);
+        let mut watches = Watches::new(event_tx, Duration::from_secs(60));
+        watches.watch(&venv, WatchBackend::Poll).unwrap();
+
+        let now = Instant::now();
+        assert!(watches.check_roots(now).is_empty());
+        std::fs::remove_dir_all(&venv).unwrap();
+        assert_eq!(watches.check_roots(now), vec![RootChange::Lost(venv.clone())]);
+        // Still gone on the first attempt, so the next one backs off
+        assert!(watches.check_roots(now + Duration::from_secs(1)).is_empty());
+
+        std::fs::create_dir_all(venv.join("lib")).unwrap();
+        std::fs::write(venv.join("lib/six.py"), "x = 10").unwrap();
+        assert!(watches.check_roots(now + Duration::from_secs(2)).is_empty());
+        assert_eq!(
+            watches.check_roots(now + Duration::from_secs(3)),
+            vec![RootChange::Reattached { path: venv.clone(), replaced: true }]
+        );
+        assert_eq!(watches.watched[&venv].backend, WatchBackend::Poll);
+        assert!(watches.lost.is_empty());
+    }
+
+    #[test]
+    fn test_worker_deaths_scale_severity() {
+        let workers: HashSet<u32> = (1..=5).collect();
+
+        let one_dead: HashSet<u32> = (2..=5).collect();
+        let changes = diff_pids(&workers, &one_dead);
+        assert_eq!(changes, vec![RuntimeChange::PartialDeath { died: vec![1], remaining: 4 }]);
+        assert_eq!(changes[0].severity(), Some(CacheBustSeverity::Medium));
+        assert_eq!(changes[0].bust_reason(), BustReason::PidDied { pids: vec![1] });
+
+        let changes = diff_pids(&workers, &HashSet::new());
+        assert_eq!(changes[0].severity(), Some(CacheBustSeverity::Critical));
+
+        let changes = diff_pids(&HashSet::new(), &workers);
+        assert!(matches!(changes[0], RuntimeChange::Started { .. }));
+    }
+    #[test]
+    fn test_reused_pids_count_as_restarts() {
+        let before: HashMap<u32, u64> = [(1, 100)].into_iter().collect();
+        assert!(diff_processes(&before, &before).is_empty());
+
+        // Same pid, started later: the runtime died and came back under it
+        let after: HashMap<u32, u64> = [(1, 250)].into_iter().collect();
+        let changes = diff_processes(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                RuntimeChange::FullDeath { died: vec![1] },
+                RuntimeChange::Started { pids: vec![1] },
+            ]
+        );
+        assert_eq!(changes[0].severity(), Some(CacheBustSeverity::Critical));
+
+        let workers: HashMap<u32, u64> = [(1, 100), (2, 100)].into_iter().collect();
+        let one_reused: HashMap<u32, u64> = [(1, 100), (2, 250)].into_iter().collect();
+        assert_eq!(
+            diff_processes(&workers, &one_reused),
+            vec![
+                RuntimeChange::PartialDeath { died: vec![2], remaining: 1 },
+                RuntimeChange::Added { pids: vec![2] },
+            ]
+        );
+    }
 }
\ No newline at end of file
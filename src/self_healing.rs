@@ -1,592 +1,1144 @@
-// src/self_healing.rs
-// OBINexus Self-Healing Data Architecture - Constitutional Compliance Framework
-// Autonomous recovery system for cache integrity management across polyglot ecosystems
-
-use std::collections::HashMap;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use serde::{Deserialize, Serialize};
-use tokio::time::{sleep, timeout};
-use crate::{BustCallError, SeverityLevel, CacheMetadata};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HealthMetrics {
-    pub timestamp: u64,
-    pub component: String,
-    pub health_score: u8,
-    pub memory_usage_mb: f64,
-    pub cpu_usage_percent: f64,
-    pub cache_hit_ratio: f64,
-    pub error_rate: f64,
-}
-
-#[derive(Debug, Clone)]
-pub enum RecoveryStrategy {
-    SoftRecovery {
-        retry_count: u8,
-        backoff_ms: u64,
-    },
-    HardRecovery {
-        force_rebuild: bool,
-        isolate_component: bool,
-    },
-    EmergencyRecovery {
-        system_restart: bool,
-        escalate_to_supervisor: bool,
-    },
-    ConstitutionalEmergency {
-        trigger_lockdown: bool,
-        notify_board: bool,
-    },
-}
-
-#[derive(Debug)]
-pub enum RecoveryResult {
-    Success {
-        strategy_used: RecoveryStrategy,
-        recovery_time_ms: u64,
-        health_restored: bool,
-    },
-    PartialRecovery {
-        remaining_issues: Vec<String>,
-        next_strategy: RecoveryStrategy,
-    },
-    Failed {
-        error: String,
-        escalation_required: bool,
-    },
-    ManualIntervention {
-        reason: String,
-        emergency_contacts: Vec<String>,
-    },
-}
-
-pub struct SelfHealingArchitecture {
-    recovery_strategies: HashMap<String, RecoveryStrategy>,
-    health_monitors: Vec<HealthMonitor>,
-    constitution_validator: ConstitutionValidator,
-    recovery_history: Vec<RecoveryAttempt>,
-    system_health: SystemHealth,
-    emergency_protocols: EmergencyProtocols,
-}
-
-#[derive(Debug)]
-pub struct HealthMonitor {
-    pub component_name: String,
-    pub monitor_interval_ms: u64,
-    pub health_threshold: u8,
-    pub last_check: SystemTime,
-    pub consecutive_failures: u8,
-}
-
-#[derive(Debug)]
-pub struct ConstitutionValidator {
-    pub compliance_rules: HashMap<String, ComplianceRule>,
-    pub violation_history: Vec<ComplianceViolation>,
-    pub emergency_threshold: u8,
-}
-
-#[derive(Debug)]
-pub struct ComplianceRule {
-    pub rule_id: String,
-    pub description: String,
-    pub violation_severity: SeverityLevel,
-    pub auto_remediation: bool,
-}
-
-#[derive(Debug)]
-pub struct ComplianceViolation {
-    pub rule_id: String,
-    pub timestamp: u64,
-    pub component: String,
-    pub details: String,
-    pub remediation_status: RemediationStatus,
-}
-
-#[derive(Debug)]
-pub enum RemediationStatus {
-    Pending,
-    InProgress,
-    Resolved,
-    Failed,
-    EscalatedToBoard,
-}
-
-#[derive(Debug)]
-pub struct RecoveryAttempt {
-    pub timestamp: u64,
-    pub component: String,
-    pub strategy: RecoveryStrategy,
-    pub result: RecoveryResult,
-    pub constitutional_impact: bool,
-}
-
-#[derive(Debug)]
-pub struct SystemHealth {
-    pub overall_score: u8,
-    pub component_health: HashMap<String, u8>,
-    pub critical_alerts: Vec<String>,
-    pub performance_degradation: bool,
-}
-
-#[derive(Debug)]
-pub struct EmergencyProtocols {
-    pub lockdown_enabled: bool,
-    pub board_notification_active: bool,
-    pub system_isolation_level: IsolationLevel,
-    pub recovery_escalation_chain: Vec<String>,
-}
-
-#[derive(Debug)]
-pub enum IsolationLevel {
-    None,
-    ComponentLevel,
-    SystemLevel,
-    NetworkLevel,
-    ConstitutionalEmergency,
-}
-
-impl SelfHealingArchitecture {
-    pub fn new() -> Self {
-        let mut recovery_strategies = HashMap::new();
-        
-        // Initialize default recovery strategies per language ecosystem
-        recovery_strategies.insert(
-            "node".to_string(),
-            RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1000 }
-        );
-        recovery_strategies.insert(
-            "python".to_string(),
-            RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1500 }
-        );
-        recovery_strategies.insert(
-            "c".to_string(),
-            RecoveryStrategy::HardRecovery { force_rebuild: true, isolate_component: false }
-        );
-        recovery_strategies.insert(
-            "gosilang".to_string(),
-            RecoveryStrategy::HardRecovery { force_rebuild: false, isolate_component: true }
-        );
-
-        Self {
-            recovery_strategies,
-            health_monitors: Self::initialize_health_monitors(),
-            constitution_validator: Self::initialize_constitution_validator(),
-            recovery_history: Vec::new(),
-            system_health: Self::initialize_system_health(),
-            emergency_protocols: Self::initialize_emergency_protocols(),
-        }
-    }
-
-    /// Main entry point for autonomous recovery system
-    pub async fn attempt_recovery(&mut self, error: &BustCallError) -> RecoveryResult {
-        let start_time = SystemTime::now();
-        
-        // Validate constitutional compliance first
-        if let Err(violation) = self.validate_constitutional_compliance(error).await {
-            return self.handle_constitutional_violation(violation).await;
-        }
-
-        // Determine recovery strategy based on error severity and component
-        let strategy = self.determine_recovery_strategy(error);
-        
-        println!("[self-healing] Executing {:?} for component: {}", strategy, error.component);
-
-        let result = match strategy.clone() {
-            RecoveryStrategy::SoftRecovery { retry_count, backoff_ms } => {
-                self.execute_soft_recovery(error, retry_count, backoff_ms).await
-            }
-            RecoveryStrategy::HardRecovery { force_rebuild, isolate_component } => {
-                self.execute_hard_recovery(error, force_rebuild, isolate_component).await
-            }
-            RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor } => {
-                self.execute_emergency_recovery(error, system_restart, escalate_to_supervisor).await
-            }
-            RecoveryStrategy::ConstitutionalEmergency { trigger_lockdown, notify_board } => {
-                self.execute_constitutional_emergency(error, trigger_lockdown, notify_board).await
-            }
-        };
-
-        // Record recovery attempt for historical analysis
-        let recovery_time = start_time.elapsed().unwrap_or(Duration::ZERO).as_millis() as u64;
-        self.record_recovery_attempt(error, strategy, result.clone(), recovery_time);
-
-        result
-    }
-
-    /// Soft recovery for low-severity issues (0-6 severity)
-    async fn execute_soft_recovery(&mut self, error: &BustCallError, retry_count: u8, backoff_ms: u64) -> RecoveryResult {
-        println!("[self-healing] Executing soft recovery for {}", error.component);
-
-        for attempt in 1..=retry_count {
-            println!("[self-healing] Soft recovery attempt {}/{} for {}", attempt, retry_count, error.component);
-            
-            // Exponential backoff
-            let delay = Duration::from_millis(backoff_ms * (2_u64.pow(attempt as u32 - 1)));
-            sleep(delay).await;
-
-            // Attempt cache refresh
-            if let Ok(_) = self.refresh_component_cache(&error.component).await {
-                // Validate health post-recovery
-                if self.validate_component_health(&error.component).await {
-                    return RecoveryResult::Success {
-                        strategy_used: RecoveryStrategy::SoftRecovery { retry_count, backoff_ms },
-                        recovery_time_ms: delay.as_millis() as u64,
-                        health_restored: true,
-                    };
-                }
-            }
-        }
-
-        RecoveryResult::PartialRecovery {
-            remaining_issues: vec![format!("Soft recovery failed for {}", error.component)],
-            next_strategy: RecoveryStrategy::HardRecovery { 
-                force_rebuild: true, 
-                isolate_component: false 
-            },
-        }
-    }
-
-    /// Hard recovery for medium-severity issues (6-9 severity)
-    async fn execute_hard_recovery(&mut self, error: &BustCallError, force_rebuild: bool, isolate_component: bool) -> RecoveryResult {
-        println!("[self-healing] Executing hard recovery for {}", error.component);
-
-        if isolate_component {
-            self.isolate_component(&error.component).await;
-        }
-
-        if force_rebuild {
-            match self.force_rebuild_component(&error.component).await {
-                Ok(_) => {
-                    if self.validate_component_health(&error.component).await {
-                        return RecoveryResult::Success {
-                            strategy_used: RecoveryStrategy::HardRecovery { force_rebuild, isolate_component },
-                            recovery_time_ms: 5000, // Estimated rebuild time
-                            health_restored: true,
-                        };
-                    }
-                }
-                Err(rebuild_error) => {
-                    return RecoveryResult::Failed {
-                        error: format!("Hard recovery rebuild failed: {}", rebuild_error),
-                        escalation_required: true,
-                    };
-                }
-            }
-        }
-
-        RecoveryResult::PartialRecovery {
-            remaining_issues: vec![format!("Hard recovery incomplete for {}", error.component)],
-            next_strategy: RecoveryStrategy::EmergencyRecovery { 
-                system_restart: true, 
-                escalate_to_supervisor: true 
-            },
-        }
-    }
-
-    /// Emergency recovery for high-severity issues (9-12 severity)
-    async fn execute_emergency_recovery(&mut self, error: &BustCallError, system_restart: bool, escalate_to_supervisor: bool) -> RecoveryResult {
-        println!("[self-healing] Executing emergency recovery for {}", error.component);
-
-        // Activate emergency protocols
-        self.emergency_protocols.system_isolation_level = IsolationLevel::SystemLevel;
-
-        if escalate_to_supervisor {
-            self.escalate_to_process_supervisor(error).await;
-        }
-
-        if system_restart {
-            match self.initiate_controlled_restart().await {
-                Ok(_) => {
-                    return RecoveryResult::Success {
-                        strategy_used: RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor },
-                        recovery_time_ms: 10000, // Estimated restart time
-                        health_restored: true,
-                    };
-                }
-                Err(restart_error) => {
-                    return RecoveryResult::Failed {
-                        error: format!("Emergency restart failed: {}", restart_error),
-                        escalation_required: true,
-                    };
-                }
-            }
-        }
-
-        RecoveryResult::ManualIntervention {
-            reason: "Emergency recovery requires manual intervention".to_string(),
-            emergency_contacts: vec![
-                "emergency@obinexus.com".to_string(),
-                "uche.king@obinexus.com".to_string(),
-            ],
-        }
-    }
-
-    /// Constitutional emergency for critical compliance violations
-    async fn execute_constitutional_emergency(&mut self, error: &BustCallError, trigger_lockdown: bool, notify_board: bool) -> RecoveryResult {
-        println!("[self-healing] CONSTITUTIONAL EMERGENCY for {}", error.component);
-
-        if trigger_lockdown {
-            self.emergency_protocols.lockdown_enabled = true;
-            self.emergency_protocols.system_isolation_level = IsolationLevel::ConstitutionalEmergency;
-        }
-
-        if notify_board {
-            self.emergency_protocols.board_notification_active = true;
-            self.notify_constitutional_board(error).await;
-        }
-
-        RecoveryResult::ManualIntervention {
-            reason: "Constitutional compliance violation - Board intervention required".to_string(),
-            emergency_contacts: vec![
-                "constitutional.board@obinexus.com".to_string(),
-                "legal@obinexus.com".to_string(),
-                "uche.king@obinexus.com".to_string(),
-            ],
-        }
-    }
-
-    /// Determine appropriate recovery strategy based on error characteristics
-    fn determine_recovery_strategy(&self, error: &BustCallError) -> RecoveryStrategy {
-        // Check for constitutional violations first
-        if self.is_constitutional_violation(error) {
-            return RecoveryStrategy::ConstitutionalEmergency { 
-                trigger_lockdown: true, 
-                notify_board: true 
-            };
-        }
-
-        // Strategy based on severity level
-        match error.severity {
-            SeverityLevel::Ok | SeverityLevel::Warning => {
-                self.recovery_strategies.get(&error.component)
-                    .cloned()
-                    .unwrap_or(RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1000 })
-            }
-            SeverityLevel::Danger => {
-                RecoveryStrategy::HardRecovery { force_rebuild: true, isolate_component: false }
-            }
-            SeverityLevel::Critical => {
-                RecoveryStrategy::EmergencyRecovery { system_restart: false, escalate_to_supervisor: true }
-            }
-            SeverityLevel::Panic => {
-                RecoveryStrategy::EmergencyRecovery { system_restart: true, escalate_to_supervisor: true }
-            }
-        }
-    }
-
-    /// Validate constitutional compliance for error context
-    async fn validate_constitutional_compliance(&self, error: &BustCallError) -> Result<(), ComplianceViolation> {
-        // Check against OBINexus constitutional rules
-        for (rule_id, rule) in &self.constitution_validator.compliance_rules {
-            if self.check_rule_violation(rule, error) {
-                return Err(ComplianceViolation {
-                    rule_id: rule_id.clone(),
-                    timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-                    component: error.component.clone(),
-                    details: format!("Violation: {} - {}", rule.description, error.message),
-                    remediation_status: RemediationStatus::Pending,
-                });
-            }
-        }
-        Ok(())
-    }
-
-    /// Handle constitutional compliance violations
-    async fn handle_constitutional_violation(&mut self, violation: ComplianceViolation) -> RecoveryResult {
-        println!("[self-healing] Constitutional violation detected: {}", violation.rule_id);
-        
-        self.constitution_validator.violation_history.push(violation.clone());
-
-        RecoveryResult::ManualIntervention {
-            reason: format!("Constitutional violation: {}", violation.details),
-            emergency_contacts: vec![
-                "constitutional.compliance@obinexus.com".to_string(),
-                "legal@obinexus.com".to_string(),
-            ],
-        }
-    }
-
-    // Component-specific recovery operations
-    async fn refresh_component_cache(&self, component: &str) -> Result<(), String> {
-        println!("[self-healing] Refreshing cache for component: {}", component);
-        // Simulate cache refresh - would implement language-specific logic
-        sleep(Duration::from_millis(500)).await;
-        Ok(())
-    }
-
-    async fn force_rebuild_component(&self, component: &str) -> Result<(), String> {
-        println!("[self-healing] Force rebuilding component: {}", component);
-        // Simulate component rebuild - would implement language-specific logic
-        sleep(Duration::from_millis(2000)).await;
-        Ok(())
-    }
-
-    async fn isolate_component(&mut self, component: &str) {
-        println!("[self-healing] Isolating component: {}", component);
-        self.emergency_protocols.system_isolation_level = IsolationLevel::ComponentLevel;
-    }
-
-    async fn validate_component_health(&self, component: &str) -> bool {
-        println!("[self-healing] Validating health for component: {}", component);
-        // Simulate health validation - would implement real health checks
-        true
-    }
-
-    async fn escalate_to_process_supervisor(&self, error: &BustCallError) {
-        println!("[self-healing] Escalating to process supervisor: {}", error.component);
-        // Would send signal to process supervisor
-    }
-
-    async fn initiate_controlled_restart(&self) -> Result<(), String> {
-        println!("[self-healing] Initiating controlled system restart");
-        // Would implement controlled restart logic
-        sleep(Duration::from_millis(3000)).await;
-        Ok(())
-    }
-
-    async fn notify_constitutional_board(&self, error: &BustCallError) {
-        println!("[self-healing] Notifying constitutional board of violation in: {}", error.component);
-        // Would implement board notification system
-    }
-
-    // Utility functions
-    fn is_constitutional_violation(&self, error: &BustCallError) -> bool {
-        error.message.contains("constitutional") || 
-        error.message.contains("compliance") ||
-        error.component.contains("constitution")
-    }
-
-    fn check_rule_violation(&self, rule: &ComplianceRule, error: &BustCallError) -> bool {
-        // Simplified rule checking - would implement comprehensive validation
-        error.message.contains(&rule.rule_id) || error.component.contains(&rule.rule_id)
-    }
-
-    fn record_recovery_attempt(&mut self, error: &BustCallError, strategy: RecoveryStrategy, result: RecoveryResult, recovery_time_ms: u64) {
-        let attempt = RecoveryAttempt {
-            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-            component: error.component.clone(),
-            strategy,
-            result,
-            constitutional_impact: self.is_constitutional_violation(error),
-        };
-        
-        self.recovery_history.push(attempt);
-        
-        // Maintain history size
-        if self.recovery_history.len() > 1000 {
-            self.recovery_history.drain(0..100);
-        }
-    }
-
-    // Initialization functions
-    fn initialize_health_monitors() -> Vec<HealthMonitor> {
-        vec![
-            HealthMonitor {
-                component_name: "cache_manager_node".to_string(),
-                monitor_interval_ms: 5000,
-                health_threshold: 8,
-                last_check: SystemTime::now(),
-                consecutive_failures: 0,
-            },
-            HealthMonitor {
-                component_name: "cache_manager_python".to_string(),
-                monitor_interval_ms: 5000,
-                health_threshold: 8,
-                last_check: SystemTime::now(),
-                consecutive_failures: 0,
-            },
-            HealthMonitor {
-                component_name: "constitutional_validator".to_string(),
-                monitor_interval_ms: 1000,
-                health_threshold: 9,
-                last_check: SystemTime::now(),
-                consecutive_failures: 0,
-            },
-        ]
-    }
-
-    fn initialize_constitution_validator() -> ConstitutionValidator {
-        let mut compliance_rules = HashMap::new();
-        
-        compliance_rules.insert(
-            "AI_TRAINING_PROTECTION".to_string(),
-            ComplianceRule {
-                rule_id: "AI_TRAINING_PROTECTION".to_string(),
-                description: "Prevent unauthorized AI model training on cache data".to_string(),
-                violation_severity: SeverityLevel::Critical,
-                auto_remediation: false,
-            }
-        );
-        
-        compliance_rules.insert(
-            "POLYCORE_V2_CERTIFICATION".to_string(),
-            ComplianceRule {
-                rule_id: "POLYCORE_V2_CERTIFICATION".to_string(),
-                description: "Maintain PolyCore v2 certification standards".to_string(),
-                violation_severity: SeverityLevel::Warning,
-                auto_remediation: true,
-            }
-        );
-
-        ConstitutionValidator {
-            compliance_rules,
-            violation_history: Vec::new(),
-            emergency_threshold: 3,
-        }
-    }
-
-    fn initialize_system_health() -> SystemHealth {
-        SystemHealth {
-            overall_score: 10,
-            component_health: HashMap::new(),
-            critical_alerts: Vec::new(),
-            performance_degradation: false,
-        }
-    }
-
-    fn initialize_emergency_protocols() -> EmergencyProtocols {
-        EmergencyProtocols {
-            lockdown_enabled: false,
-            board_notification_active: false,
-            system_isolation_level: IsolationLevel::None,
-            recovery_escalation_chain: vec![
-                "system_administrator".to_string(),
-                "technical_lead".to_string(),
-                "constitutional_board".to_string(),
-            ],
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_soft_recovery() {
-        let mut healing = SelfHealingArchitecture::new();
-        let error = BustCallError {
-            severity: SeverityLevel::Warning,
-            message: "Test cache warning".to_string(),
-            component: "test_component".to_string(),
-            recovery_action: None,
-        };
-
-        let result = healing.attempt_recovery(&error).await;
-        assert!(matches!(result, RecoveryResult::Success { .. }));
-    }
-
-    #[tokio::test]
-    async fn test_constitutional_compliance() {
-        let healing = SelfHealingArchitecture::new();
-        let error = BustCallError {
-            severity: SeverityLevel::Ok,
-            message: "Normal operation".to_string(),
-            component: "test_component".to_string(),
-            recovery_action: None,
-        };
-
-        let result = healing.validate_constitutional_compliance(&error).await;
-        assert!(result.is_ok());
-    }
+// src/self_healing.rs
+// OBINexus Self-Healing Data Architecture - Constitutional Compliance Framework
+// Autonomous recovery system for cache integrity management across polyglot ecosystems
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
+use crate::core::recovery::{RecoveryActions, RecoveryConfig};
+use crate::utils::error::{BustCallError, BustcallError, SeverityLevel};
+
+/// Window `error_rate` counts errors over
+pub const ERROR_RATE_WINDOW_SECONDS: u64 = 300;
+
+/// Alerts kept in `SystemHealth::critical_alerts`
+const MAX_CRITICAL_ALERTS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthMetrics {
+    pub timestamp: u64,
+    pub component: String,
+    pub health_score: u8,
+    pub memory_usage_mb: f64,
+    pub cpu_usage_percent: f64,
+    pub cache_hit_ratio: f64,
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Clone)]
+pub enum RecoveryStrategy {
+    SoftRecovery {
+        retry_count: u8,
+        backoff_ms: u64,
+    },
+    HardRecovery {
+        force_rebuild: bool,
+        isolate_component: bool,
+    },
+    EmergencyRecovery {
+        system_restart: bool,
+        escalate_to_supervisor: bool,
+    },
+    ConstitutionalEmergency {
+        trigger_lockdown: bool,
+        notify_board: bool,
+    },
+    /// `[recovery.chains]`: recovery actions tried in order
+    Chain {
+        actions: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum RecoveryResult {
+    Success {
+        strategy_used: RecoveryStrategy,
+        recovery_time_ms: u64,
+        health_restored: bool,
+    },
+    PartialRecovery {
+        remaining_issues: Vec<String>,
+        next_strategy: RecoveryStrategy,
+    },
+    Failed {
+        error: String,
+        escalation_required: bool,
+    },
+    ManualIntervention {
+        reason: String,
+        emergency_contacts: Vec<String>,
+    },
+}
+
+pub struct SelfHealingArchitecture {
+    recovery_strategies: HashMap<String, RecoveryStrategy>,
+    health_monitors: Vec<HealthMonitor>,
+    constitution_validator: ConstitutionValidator,
+    recovery_history: Vec<RecoveryAttempt>,
+    system_health: SystemHealth,
+    emergency_protocols: EmergencyProtocols,
+    actions: Arc<RecoveryActions>,
+    recovery: RecoveryConfig,
+    /// Failed recoveries and open circuits, by component
+    breakers: HashMap<String, CircuitBreaker>,
+    notifications: Option<Arc<NotificationManager>>,
+    /// When errors were reported, in seconds since the epoch, by component
+    errors: HashMap<String, VecDeque<u64>>,
+}
+
+#[derive(Debug)]
+pub struct HealthMonitor {
+    pub component_name: String,
+    pub monitor_interval_ms: u64,
+    pub health_threshold: u8,
+    pub last_check: SystemTime,
+    pub consecutive_failures: u8,
+    /// Checks in a row below `health_threshold` before recovery is attempted
+    pub unhealthy_checks: u8,
+}
+
+impl HealthMonitor {
+    /// A monitor whose first check is due right away
+    pub fn new(component: &str, interval: Duration, health_threshold: u8, unhealthy_checks: u8) -> Self {
+        Self {
+            component_name: component.to_string(),
+            monitor_interval_ms: interval.as_millis() as u64,
+            health_threshold,
+            last_check: UNIX_EPOCH,
+            consecutive_failures: 0,
+            unhealthy_checks: unhealthy_checks.max(1),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConstitutionValidator {
+    pub compliance_rules: HashMap<String, ComplianceRule>,
+    pub violation_history: Vec<ComplianceViolation>,
+    pub emergency_threshold: u8,
+}
+
+#[derive(Debug)]
+pub struct ComplianceRule {
+    pub rule_id: String,
+    pub description: String,
+    pub violation_severity: SeverityLevel,
+    pub auto_remediation: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComplianceViolation {
+    pub rule_id: String,
+    pub timestamp: u64,
+    pub component: String,
+    pub details: String,
+    pub remediation_status: RemediationStatus,
+}
+
+#[derive(Debug, Clone)]
+pub enum RemediationStatus {
+    Pending,
+    InProgress,
+    Resolved,
+    Failed,
+    EscalatedToBoard,
+}
+
+/// Failed recoveries of a component within the breaker window, and when
+/// its circuit opened
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    failures: VecDeque<u64>,
+    opened_at: Option<u64>,
+}
+
+/// A component's circuit breaker, for `bustcall heal`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitStatus {
+    pub component: String,
+    /// Automated recovery stopped until reset
+    pub open: bool,
+    /// Seconds since the epoch
+    pub opened_at: Option<u64>,
+    /// Failed recoveries within the window
+    pub failures: usize,
+}
+
+#[derive(Debug)]
+pub struct RecoveryAttempt {
+    pub timestamp: u64,
+    pub component: String,
+    pub strategy: RecoveryStrategy,
+    pub result: RecoveryResult,
+    pub recovery_time_ms: u64,
+    pub constitutional_impact: bool,
+}
+
+#[derive(Debug)]
+pub struct SystemHealth {
+    pub overall_score: u8,
+    pub component_health: HashMap<String, u8>,
+    pub critical_alerts: Vec<String>,
+    pub performance_degradation: bool,
+}
+
+#[derive(Debug)]
+pub struct EmergencyProtocols {
+    pub lockdown_enabled: bool,
+    pub board_notification_active: bool,
+    pub system_isolation_level: IsolationLevel,
+    pub recovery_escalation_chain: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum IsolationLevel {
+    None,
+    ComponentLevel,
+    SystemLevel,
+    NetworkLevel,
+    ConstitutionalEmergency,
+}
+
+impl Default for SelfHealingArchitecture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfHealingArchitecture {
+    pub fn new() -> Self {
+        let mut recovery_strategies = HashMap::new();
+        
+        // Initialize default recovery strategies per language ecosystem
+        recovery_strategies.insert(
+            "node".to_string(),
+            RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1000 }
+        );
+        recovery_strategies.insert(
+            "python".to_string(),
+            RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1500 }
+        );
+        recovery_strategies.insert(
+            "c".to_string(),
+            RecoveryStrategy::HardRecovery { force_rebuild: true, isolate_component: false }
+        );
+        recovery_strategies.insert(
+            "gosilang".to_string(),
+            RecoveryStrategy::HardRecovery { force_rebuild: false, isolate_component: true }
+        );
+
+        Self {
+            recovery_strategies,
+            health_monitors: Self::initialize_health_monitors(),
+            constitution_validator: Self::initialize_constitution_validator(),
+            recovery_history: Vec::new(),
+            system_health: Self::initialize_system_health(),
+            emergency_protocols: Self::initialize_emergency_protocols(),
+            actions: Arc::new(RecoveryActions::new()),
+            recovery: RecoveryConfig::default(),
+            breakers: HashMap::new(),
+            notifications: None,
+            errors: HashMap::new(),
+        }
+    }
+
+    /// Check the health of these components instead
+    pub fn with_health_monitors(mut self, monitors: Vec<HealthMonitor>) -> Self {
+        self.health_monitors = monitors;
+        self
+    }
+
+    /// Recover with `actions`; only `isolate` is available otherwise
+    pub fn with_actions(mut self, actions: RecoveryActions) -> Self {
+        self.actions = Arc::new(actions);
+        self
+    }
+
+    /// Recover components by the chains of `[recovery]` instead of by
+    /// severity, and stop recovering those failing as often as its circuit
+    /// breaker allows
+    pub fn with_recovery(mut self, recovery: RecoveryConfig) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Escalate circuits opening as Critical notifications
+    pub fn with_notifications(mut self, notifications: Arc<NotificationManager>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Circuit of every component whose recovery failed lately, by name
+    pub fn circuits(&self) -> Vec<CircuitStatus> {
+        let mut circuits: Vec<CircuitStatus> = self
+            .breakers
+            .iter()
+            .map(|(component, breaker)| CircuitStatus {
+                component: component.clone(),
+                open: breaker.opened_at.is_some(),
+                opened_at: breaker.opened_at,
+                failures: breaker.failures.len(),
+            })
+            .collect();
+        circuits.sort_by(|a, b| a.component.cmp(&b.component));
+        circuits
+    }
+
+    pub fn is_circuit_open(&self, component: &str) -> bool {
+        self.breakers.get(component).is_some_and(|breaker| breaker.opened_at.is_some())
+    }
+
+    /// Close `component`'s circuit and forget its failures, for
+    /// `bustcall heal reset`; returns whether it was open
+    pub fn reset_circuit(&mut self, component: &str) -> bool {
+        let was_open = self.is_circuit_open(component);
+        self.breakers.remove(component);
+        if was_open {
+            log::info!("[self-healing] Circuit of {} closed, recovery resumes", component);
+        }
+        was_open
+    }
+
+    pub fn actions(&self) -> &RecoveryActions {
+        &self.actions
+    }
+
+    /// Recovery attempts, oldest first
+    pub fn history(&self) -> &[RecoveryAttempt] {
+        &self.recovery_history
+    }
+
+    pub fn health_monitors(&self) -> &[HealthMonitor] {
+        &self.health_monitors
+    }
+
+    pub fn system_health(&self) -> &SystemHealth {
+        &self.system_health
+    }
+
+    pub fn emergency_protocols(&self) -> &EmergencyProtocols {
+        &self.emergency_protocols
+    }
+
+    /// Count an error of `component` towards its error rate
+    pub fn record_error(&mut self, component: &str) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let errors = self.errors.entry(component.to_string()).or_default();
+        errors.push_back(now);
+        while errors.front().is_some_and(|at| *at + ERROR_RATE_WINDOW_SECONDS <= now) {
+            errors.pop_front();
+        }
+    }
+
+    /// Errors reported for `component` per minute, over the last
+    /// `ERROR_RATE_WINDOW_SECONDS`
+    pub fn error_rate(&self, component: &str) -> f64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let recent = self
+            .errors
+            .get(component)
+            .map_or(0, |errors| errors.iter().filter(|at| **at + ERROR_RATE_WINDOW_SECONDS > now).count());
+        recent as f64 * 60.0 / ERROR_RATE_WINDOW_SECONDS as f64
+    }
+
+    /// Components whose health check is due at `now`
+    pub fn due_monitors(&self, now: SystemTime) -> Vec<String> {
+        self.health_monitors
+            .iter()
+            .filter(|monitor| {
+                now.duration_since(monitor.last_check)
+                    .is_ok_and(|elapsed| elapsed >= Duration::from_millis(monitor.monitor_interval_ms))
+            })
+            .map(|monitor| monitor.component_name.clone())
+            .collect()
+    }
+
+    /// Record a health check of `metrics.component`. Returns the error to
+    /// recover it from once it scored below its threshold for
+    /// `unhealthy_checks` checks in a row, scored the worse the lower its
+    /// health.
+    pub fn evaluate_health(&mut self, metrics: HealthMetrics) -> Option<BustCallError> {
+        let monitor = self
+            .health_monitors
+            .iter_mut()
+            .find(|monitor| monitor.component_name == metrics.component)?;
+        monitor.last_check = SystemTime::now();
+        let unhealthy = metrics.health_score < monitor.health_threshold;
+        monitor.consecutive_failures = if unhealthy { monitor.consecutive_failures.saturating_add(1) } else { 0 };
+        let triggered = monitor.consecutive_failures >= monitor.unhealthy_checks;
+        if triggered {
+            monitor.consecutive_failures = 0;
+        }
+        let (threshold, checks) = (monitor.health_threshold, monitor.unhealthy_checks);
+
+        let health = &mut self.system_health;
+        health.component_health.insert(metrics.component.clone(), metrics.health_score);
+        health.overall_score = health.component_health.values().copied().min().unwrap_or(10);
+        health.performance_degradation = self.health_monitors.iter().any(|monitor| {
+            health
+                .component_health
+                .get(&monitor.component_name)
+                .is_some_and(|score| *score < monitor.health_threshold)
+        });
+        if !triggered {
+            return None;
+        }
+
+        let message = format!(
+            "health {}/10 below {} for {} checks (cpu {:.0}%, hit ratio {:.2}, {:.1} errors/min)",
+            metrics.health_score, threshold, checks, metrics.cpu_usage_percent, metrics.cache_hit_ratio, metrics.error_rate
+        );
+        health.critical_alerts.push(format!("{}: {}", metrics.component, message));
+        if health.critical_alerts.len() > MAX_CRITICAL_ALERTS {
+            health.critical_alerts.remove(0);
+        }
+        // Health 0, e.g. a runtime gone, scores 12 and restarts it
+        let score = (10 - metrics.health_score.min(10)) * 6 / 5;
+        Some(BustCallError::new(&metrics.component, &message, score).with_recovery_action("health_monitor"))
+    }
+
+    /// Main entry point for autonomous recovery system
+    pub async fn attempt_recovery(&mut self, error: &BustCallError) -> RecoveryResult {
+        let start_time = SystemTime::now();
+        
+        // Validate constitutional compliance first
+        if let Err(violation) = self.validate_constitutional_compliance(error).await {
+            return self.handle_constitutional_violation(violation).await;
+        }
+
+        if self.actions.is_isolated(&error.component) {
+            return RecoveryResult::ManualIntervention {
+                reason: format!("{} is isolated", error.component),
+                emergency_contacts: self.emergency_protocols.recovery_escalation_chain.clone(),
+            };
+        }
+
+        if let Some(opened_at) = self.breakers.get(&error.component).and_then(|breaker| breaker.opened_at) {
+            return RecoveryResult::ManualIntervention {
+                reason: format!(
+                    "Recovery of {} failed repeatedly, its circuit is open since {}; `bustcall heal reset {}` to retry",
+                    error.component, opened_at, error.component
+                ),
+                emergency_contacts: self.emergency_protocols.recovery_escalation_chain.clone(),
+            };
+        }
+
+        // Determine recovery strategy based on error severity and component
+        let strategy = self.determine_recovery_strategy(error);
+        
+        log::info!("[self-healing] Executing {:?} for component: {}", strategy, error.component);
+
+        let result = match strategy.clone() {
+            RecoveryStrategy::SoftRecovery { retry_count, backoff_ms } => {
+                self.execute_soft_recovery(error, retry_count, backoff_ms).await
+            }
+            RecoveryStrategy::HardRecovery { force_rebuild, isolate_component } => {
+                self.execute_hard_recovery(error, force_rebuild, isolate_component).await
+            }
+            RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor } => {
+                self.execute_emergency_recovery(error, system_restart, escalate_to_supervisor).await
+            }
+            RecoveryStrategy::ConstitutionalEmergency { trigger_lockdown, notify_board } => {
+                self.execute_constitutional_emergency(error, trigger_lockdown, notify_board).await
+            }
+            RecoveryStrategy::Chain { actions } => self.execute_chain(error, actions).await,
+        };
+
+        self.trip_breaker(error, &result);
+
+        // Record recovery attempt for historical analysis
+        let recovery_time = start_time.elapsed().unwrap_or(Duration::ZERO).as_millis() as u64;
+        self.record_recovery_attempt(error, strategy, result.clone(), recovery_time);
+
+        result
+    }
+
+    /// Soft recovery for low-severity issues (0-6 severity)
+    async fn execute_soft_recovery(&mut self, error: &BustCallError, retry_count: u8, backoff_ms: u64) -> RecoveryResult {
+        log::info!("[self-healing] Executing soft recovery for {}", error.component);
+        let started = Instant::now();
+
+        for attempt in 1..=retry_count {
+            log::info!("[self-healing] Soft recovery attempt {}/{} for {}", attempt, retry_count, error.component);
+            
+            // Exponential backoff
+            let delay = Duration::from_millis(backoff_ms * (2_u64.pow(attempt as u32 - 1)));
+            sleep(delay).await;
+
+            // Attempt cache refresh, then validate health post-recovery
+            if let Ok(true) = self.run_action("cache_refresh", error).await {
+                return RecoveryResult::Success {
+                    strategy_used: RecoveryStrategy::SoftRecovery { retry_count, backoff_ms },
+                    recovery_time_ms: started.elapsed().as_millis() as u64,
+                    health_restored: true,
+                };
+            }
+        }
+
+        RecoveryResult::PartialRecovery {
+            remaining_issues: vec![format!("Soft recovery failed for {}", error.component)],
+            next_strategy: RecoveryStrategy::HardRecovery { 
+                force_rebuild: true, 
+                isolate_component: false 
+            },
+        }
+    }
+
+    /// Hard recovery for medium-severity issues (6-9 severity): re-run the
+    /// component's `rebuild_command`
+    async fn execute_hard_recovery(&mut self, error: &BustCallError, force_rebuild: bool, isolate_component: bool) -> RecoveryResult {
+        log::info!("[self-healing] Executing hard recovery for {}", error.component);
+        let started = Instant::now();
+
+        if isolate_component {
+            self.isolate_component(error).await;
+        }
+
+        if force_rebuild {
+            match self.run_action("rebuild", error).await {
+                Ok(true) => {
+                    return RecoveryResult::Success {
+                        strategy_used: RecoveryStrategy::HardRecovery { force_rebuild, isolate_component },
+                        recovery_time_ms: started.elapsed().as_millis() as u64,
+                        health_restored: true,
+                    };
+                }
+                Ok(false) => {
+                    return RecoveryResult::PartialRecovery {
+                        remaining_issues: vec![format!("{} is still stale after its rebuild", error.component)],
+                        next_strategy: RecoveryStrategy::EmergencyRecovery { system_restart: true, escalate_to_supervisor: true },
+                    };
+                }
+                Err(rebuild_error) if is_transient(&rebuild_error) => {
+                    return RecoveryResult::PartialRecovery {
+                        remaining_issues: vec![rebuild_error.to_string()],
+                        next_strategy: RecoveryStrategy::HardRecovery { force_rebuild, isolate_component: false },
+                    };
+                }
+                Err(rebuild_error) => {
+                    return RecoveryResult::Failed {
+                        error: format!("Hard recovery rebuild failed: {}", rebuild_error),
+                        escalation_required: true,
+                    };
+                }
+            }
+        }
+
+        RecoveryResult::PartialRecovery {
+            remaining_issues: vec![format!("Hard recovery incomplete for {}", error.component)],
+            next_strategy: RecoveryStrategy::EmergencyRecovery { 
+                system_restart: true, 
+                escalate_to_supervisor: true 
+            },
+        }
+    }
+
+    /// Emergency recovery for high-severity issues (9-12 severity): restart
+    /// the component's runtime with its `restart_command`
+    async fn execute_emergency_recovery(&mut self, error: &BustCallError, system_restart: bool, escalate_to_supervisor: bool) -> RecoveryResult {
+        log::info!("[self-healing] Executing emergency recovery for {}", error.component);
+        let started = Instant::now();
+
+        // Activate emergency protocols
+        self.emergency_protocols.system_isolation_level = IsolationLevel::SystemLevel;
+
+        if escalate_to_supervisor {
+            self.escalate_to_process_supervisor(error).await;
+        }
+
+        if system_restart {
+            match self.run_action("restart", error).await {
+                Ok(true) => {
+                    self.emergency_protocols.system_isolation_level = IsolationLevel::None;
+                    return RecoveryResult::Success {
+                        strategy_used: RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor },
+                        recovery_time_ms: started.elapsed().as_millis() as u64,
+                        health_restored: true,
+                    };
+                }
+                Ok(false) => {
+                    return RecoveryResult::PartialRecovery {
+                        remaining_issues: vec![format!("{} exited again after its restart", error.component)],
+                        next_strategy: RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor },
+                    };
+                }
+                // Backing off between restarts
+                Err(restart_error) if is_transient(&restart_error) => {
+                    return RecoveryResult::PartialRecovery {
+                        remaining_issues: vec![restart_error.to_string()],
+                        next_strategy: RecoveryStrategy::EmergencyRecovery { system_restart, escalate_to_supervisor },
+                    };
+                }
+                Err(restart_error) => {
+                    return RecoveryResult::Failed {
+                        error: format!("Emergency restart failed: {}", restart_error),
+                        escalation_required: true,
+                    };
+                }
+            }
+        }
+
+        RecoveryResult::ManualIntervention {
+            reason: "Emergency recovery requires manual intervention".to_string(),
+            emergency_contacts: vec![
+                "emergency@obinexus.com".to_string(),
+                "uche.king@obinexus.com".to_string(),
+            ],
+        }
+    }
+
+    /// Constitutional emergency for critical compliance violations
+    async fn execute_constitutional_emergency(&mut self, error: &BustCallError, trigger_lockdown: bool, notify_board: bool) -> RecoveryResult {
+        log::error!("[self-healing] CONSTITUTIONAL EMERGENCY for {}", error.component);
+
+        if trigger_lockdown {
+            self.emergency_protocols.lockdown_enabled = true;
+            self.emergency_protocols.system_isolation_level = IsolationLevel::ConstitutionalEmergency;
+        }
+
+        if notify_board {
+            self.emergency_protocols.board_notification_active = true;
+            self.notify_constitutional_board(error).await;
+        }
+
+        RecoveryResult::ManualIntervention {
+            reason: "Constitutional compliance violation - Board intervention required".to_string(),
+            emergency_contacts: vec![
+                "constitutional.board@obinexus.com".to_string(),
+                "legal@obinexus.com".to_string(),
+                "uche.king@obinexus.com".to_string(),
+            ],
+        }
+    }
+
+    /// Run the actions of a `[recovery.chains]` entry until one restores
+    /// the component's health
+    async fn execute_chain(&mut self, error: &BustCallError, actions: Vec<String>) -> RecoveryResult {
+        log::info!("[self-healing] Executing recovery chain {:?} for {}", actions, error.component);
+        let started = Instant::now();
+        let mut remaining_issues = Vec::new();
+
+        for name in &actions {
+            let outcome = self.run_action(name, error).await;
+            if self.actions.is_isolated(&error.component) {
+                return RecoveryResult::ManualIntervention {
+                    reason: format!("{} isolated by {}", error.component, name),
+                    emergency_contacts: self.emergency_protocols.recovery_escalation_chain.clone(),
+                };
+            }
+            match outcome {
+                Ok(true) => {
+                    return RecoveryResult::Success {
+                        strategy_used: RecoveryStrategy::Chain { actions: actions.clone() },
+                        recovery_time_ms: started.elapsed().as_millis() as u64,
+                        health_restored: true,
+                    };
+                }
+                Ok(false) => remaining_issues.push(format!("{} left {} unhealthy", name, error.component)),
+                Err(action_error) => remaining_issues.push(action_error.to_string()),
+            }
+        }
+
+        RecoveryResult::Failed {
+            error: format!("Recovery chain for {} exhausted: {}", error.component, remaining_issues.join("; ")),
+            escalation_required: true,
+        }
+    }
+
+    /// Determine appropriate recovery strategy based on error characteristics
+    fn determine_recovery_strategy(&self, error: &BustCallError) -> RecoveryStrategy {
+        // Check for constitutional violations first
+        if self.is_constitutional_violation(error) {
+            return RecoveryStrategy::ConstitutionalEmergency { 
+                trigger_lockdown: true, 
+                notify_board: true 
+            };
+        }
+
+        if let Some(actions) = self.recovery.chain_for(&error.component) {
+            return RecoveryStrategy::Chain { actions: actions.to_vec() };
+        }
+
+        // Strategy based on severity level
+        match error.severity {
+            SeverityLevel::Ok | SeverityLevel::Warning => {
+                self.recovery_strategies.get(&error.component)
+                    .cloned()
+                    .unwrap_or(RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1000 })
+            }
+            SeverityLevel::Danger => {
+                RecoveryStrategy::HardRecovery { force_rebuild: true, isolate_component: false }
+            }
+            SeverityLevel::Critical => {
+                RecoveryStrategy::EmergencyRecovery { system_restart: false, escalate_to_supervisor: true }
+            }
+            SeverityLevel::Panic => {
+                RecoveryStrategy::EmergencyRecovery { system_restart: true, escalate_to_supervisor: true }
+            }
+        }
+    }
+
+    /// Validate constitutional compliance for error context
+    async fn validate_constitutional_compliance(&self, error: &BustCallError) -> Result<(), ComplianceViolation> {
+        // Check against OBINexus constitutional rules
+        for (rule_id, rule) in &self.constitution_validator.compliance_rules {
+            if self.check_rule_violation(rule, error) {
+                return Err(ComplianceViolation {
+                    rule_id: rule_id.clone(),
+                    timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+                    component: error.component.clone(),
+                    details: format!("Violation: {} - {}", rule.description, error.message),
+                    remediation_status: RemediationStatus::Pending,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle constitutional compliance violations
+    async fn handle_constitutional_violation(&mut self, violation: ComplianceViolation) -> RecoveryResult {
+        log::error!("[self-healing] Constitutional violation detected: {}", violation.rule_id);
+        
+        self.constitution_validator.violation_history.push(violation.clone());
+
+        RecoveryResult::ManualIntervention {
+            reason: format!("Constitutional violation: {}", violation.details),
+            emergency_contacts: vec![
+                "constitutional.compliance@obinexus.com".to_string(),
+                "legal@obinexus.com".to_string(),
+            ],
+        }
+    }
+
+    // Component-specific recovery operations
+
+    /// Run the recovery action `name`, then its health check on the
+    /// component; `Ok(false)` if it ran but left the component unhealthy
+    async fn run_action(&self, name: &str, error: &BustCallError) -> Result<bool, BustcallError> {
+        let action = self
+            .actions
+            .get(name)
+            .ok_or_else(|| BustcallError::DaemonError(format!("No recovery action named {}", name)))?;
+        log::info!("[self-healing] Running {} for component: {}", name, error.component);
+        if let Err(e) = action.execute(error).await {
+            log::warn!("[self-healing] {} failed for {}: {}", name, error.component, e);
+            return Err(e);
+        }
+        let healthy = action.health_check(&error.component).await;
+        if !healthy {
+            log::warn!("[self-healing] {} still unhealthy after {}", error.component, name);
+        }
+        Ok(healthy)
+    }
+
+    async fn isolate_component(&mut self, error: &BustCallError) {
+        log::info!("[self-healing] Isolating component: {}", error.component);
+        self.emergency_protocols.system_isolation_level = IsolationLevel::ComponentLevel;
+        if let Err(e) = self.run_action("isolate", error).await {
+            log::warn!("[self-healing] {}", e);
+        }
+    }
+
+    async fn escalate_to_process_supervisor(&self, error: &BustCallError) {
+        log::info!("[self-healing] Escalating to process supervisor: {}", error.component);
+        // Would send signal to process supervisor
+    }
+
+    async fn notify_constitutional_board(&self, error: &BustCallError) {
+        log::info!("[self-healing] Notifying constitutional board of violation in: {}", error.component);
+        // Would implement board notification system
+    }
+
+    /// Count a failed recovery towards the component's circuit breaker,
+    /// opening the circuit and escalating once `breaker_failures` failed
+    /// within `breaker_window_seconds`; a recovery succeeding resets it
+    fn trip_breaker(&mut self, error: &BustCallError, result: &RecoveryResult) {
+        let failed = match result {
+            RecoveryResult::Success { .. } => {
+                self.breakers.remove(&error.component);
+                return;
+            }
+            RecoveryResult::PartialRecovery { .. } | RecoveryResult::Failed { .. } => true,
+            RecoveryResult::ManualIntervention { .. } => false,
+        };
+        let (limit, window) = (self.recovery.breaker_failures, self.recovery.breaker_window_seconds);
+        if !failed || limit == 0 {
+            return;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let breaker = self.breakers.entry(error.component.clone()).or_default();
+        breaker.failures.push_back(now);
+        while breaker.failures.front().is_some_and(|at| *at + window <= now) {
+            breaker.failures.pop_front();
+        }
+        if breaker.failures.len() < limit as usize {
+            return;
+        }
+        breaker.opened_at = Some(now);
+
+        let message = format!(
+            "Recovery of {} failed {} times within {}s; automated recovery stopped until `bustcall heal reset {}`",
+            error.component, breaker.failures.len(), window, error.component
+        );
+        log::error!("[self-healing] 🚨 {}", message);
+        self.system_health.critical_alerts.push(message.clone());
+        if self.system_health.critical_alerts.len() > MAX_CRITICAL_ALERTS {
+            self.system_health.critical_alerts.remove(0);
+        }
+        if let Some(notifications) = &self.notifications {
+            let notification = Notification::new(NotificationLevel::Critical, &message)
+                .title("Recovery circuit open")
+                .target(&error.component)
+                .component("self_healing")
+                .with_metadata("last_error", &error.message);
+            if let Err(e) = notifications.send_structured(&notification) {
+                log::warn!("Circuit breaker notification for {} failed: {}", error.component, e);
+            }
+        }
+    }
+
+    // Utility functions
+    fn is_constitutional_violation(&self, error: &BustCallError) -> bool {
+        error.message.contains("constitutional") || 
+        error.message.contains("compliance") ||
+        error.component.contains("constitution")
+    }
+
+    fn check_rule_violation(&self, rule: &ComplianceRule, error: &BustCallError) -> bool {
+        // Simplified rule checking - would implement comprehensive validation
+        error.message.contains(&rule.rule_id) || error.component.contains(&rule.rule_id)
+    }
+
+    fn record_recovery_attempt(&mut self, error: &BustCallError, strategy: RecoveryStrategy, result: RecoveryResult, recovery_time_ms: u64) {
+        let attempt = RecoveryAttempt {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            component: error.component.clone(),
+            strategy,
+            result,
+            recovery_time_ms,
+            constitutional_impact: self.is_constitutional_violation(error),
+        };
+        
+        self.recovery_history.push(attempt);
+        
+        // Maintain history size
+        if self.recovery_history.len() > 1000 {
+            self.recovery_history.drain(0..100);
+        }
+    }
+
+    // Initialization functions
+    fn initialize_health_monitors() -> Vec<HealthMonitor> {
+        vec![
+            HealthMonitor {
+                component_name: "cache_manager_node".to_string(),
+                monitor_interval_ms: 5000,
+                health_threshold: 8,
+                last_check: SystemTime::now(),
+                consecutive_failures: 0,
+                unhealthy_checks: 3,
+            },
+            HealthMonitor {
+                component_name: "cache_manager_python".to_string(),
+                monitor_interval_ms: 5000,
+                health_threshold: 8,
+                last_check: SystemTime::now(),
+                consecutive_failures: 0,
+                unhealthy_checks: 3,
+            },
+            HealthMonitor {
+                component_name: "constitutional_validator".to_string(),
+                monitor_interval_ms: 1000,
+                health_threshold: 9,
+                last_check: SystemTime::now(),
+                consecutive_failures: 0,
+                unhealthy_checks: 3,
+            },
+        ]
+    }
+
+    fn initialize_constitution_validator() -> ConstitutionValidator {
+        let mut compliance_rules = HashMap::new();
+        
+        compliance_rules.insert(
+            "AI_TRAINING_PROTECTION".to_string(),
+            ComplianceRule {
+                rule_id: "AI_TRAINING_PROTECTION".to_string(),
+                description: "Prevent unauthorized AI model training on cache data".to_string(),
+                violation_severity: SeverityLevel::Critical,
+                auto_remediation: false,
+            }
+        );
+        
+        compliance_rules.insert(
+            "POLYCORE_V2_CERTIFICATION".to_string(),
+            ComplianceRule {
+                rule_id: "POLYCORE_V2_CERTIFICATION".to_string(),
+                description: "Maintain PolyCore v2 certification standards".to_string(),
+                violation_severity: SeverityLevel::Warning,
+                auto_remediation: true,
+            }
+        );
+
+        ConstitutionValidator {
+            compliance_rules,
+            violation_history: Vec::new(),
+            emergency_threshold: 3,
+        }
+    }
+
+    fn initialize_system_health() -> SystemHealth {
+        SystemHealth {
+            overall_score: 10,
+            component_health: HashMap::new(),
+            critical_alerts: Vec::new(),
+            performance_degradation: false,
+        }
+    }
+
+    fn initialize_emergency_protocols() -> EmergencyProtocols {
+        EmergencyProtocols {
+            lockdown_enabled: false,
+            board_notification_active: false,
+            system_isolation_level: IsolationLevel::None,
+            recovery_escalation_chain: vec![
+                "system_administrator".to_string(),
+                "technical_lead".to_string(),
+                "constitutional_board".to_string(),
+            ],
+        }
+    }
+}
+
+/// Whether a failed action is worth retrying as is, e.g. a restart backing
+/// off, rather than escalating
+fn is_transient(error: &BustcallError) -> bool {
+    error.severity() <= SeverityLevel::Warning
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::recovery::{RecoveryAction, RecoveryFuture};
+    use crate::utils::error::Result;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fails its first `failures` runs
+    struct Flaky {
+        name: &'static str,
+        failures: usize,
+        runs: AtomicUsize,
+    }
+
+    impl Flaky {
+        fn new(name: &'static str, failures: usize) -> Arc<Self> {
+            Arc::new(Self { name, failures, runs: AtomicUsize::new(0) })
+        }
+    }
+
+    impl RecoveryAction for Flaky {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn execute<'a>(&'a self, _error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+            Box::pin(async move {
+                if self.runs.fetch_add(1, Ordering::SeqCst) < self.failures {
+                    return Err(BustcallError::DaemonError(format!("{} broke", self.name)));
+                }
+                Ok(())
+            })
+        }
+    }
+
+    /// Fails every run with an error scored `score`
+    struct Fails {
+        name: &'static str,
+        score: u8,
+    }
+
+    impl RecoveryAction for Fails {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn execute<'a>(&'a self, error: &'a BustCallError) -> RecoveryFuture<'a, Result<()>> {
+            Box::pin(async move { Err(BustCallError::new(&error.component, "broke", self.score).into()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_soft_recovery() {
+        let actions = RecoveryActions::new();
+        actions.register(Flaky::new("cache_refresh", 0));
+        let mut healing = SelfHealingArchitecture::new().with_actions(actions);
+        let error = BustCallError {
+            severity: SeverityLevel::Warning,
+            message: "Test cache warning".to_string(),
+            component: "test_component".to_string(),
+            recovery_action: None,
+        };
+
+        let result = healing.attempt_recovery(&error).await;
+        assert!(matches!(result, RecoveryResult::Success { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_chains_run_actions_in_order() {
+        let (refresh, restart) = (Flaky::new("cache_refresh", 1), Flaky::new("restart", 0));
+        let actions = RecoveryActions::new();
+        actions.register(refresh.clone());
+        actions.register(restart.clone());
+        let chains: RecoveryConfig = toml::from_str(
+            r#"
+            [chains]
+            api = ["cache_refresh", "restart"]
+            web = ["purge", "isolate", "restart"]
+            "#,
+        )
+        .unwrap();
+        let mut healing = SelfHealingArchitecture::new().with_actions(actions).with_recovery(chains);
+
+        let api = BustCallError::new("api", "cache bust failed", 6);
+        let result = healing.attempt_recovery(&api).await;
+        assert!(matches!(result, RecoveryResult::Success { strategy_used: RecoveryStrategy::Chain { .. }, .. }));
+        assert_eq!((refresh.runs.load(Ordering::SeqCst), restart.runs.load(Ordering::SeqCst)), (1, 1));
+
+        // Unknown actions are skipped; isolating stops the chain for good
+        let web = BustCallError::new("web", "cache bust failed", 6);
+        let result = healing.attempt_recovery(&web).await;
+        assert!(matches!(result, RecoveryResult::ManualIntervention { .. }));
+        assert_eq!(restart.runs.load(Ordering::SeqCst), 1);
+        assert!(healing.actions().is_isolated("web"));
+        assert!(matches!(healing.attempt_recovery(&web).await, RecoveryResult::ManualIntervention { .. }));
+
+        // Without a chain, severity still picks the strategy
+        let node = BustCallError::new("node", "cache bust failed", 6);
+        assert!(matches!(healing.determine_recovery_strategy(&node), RecoveryStrategy::HardRecovery { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_and_restart_results() {
+        let recover = |rebuild: Arc<dyn RecoveryAction>, restart: Arc<dyn RecoveryAction>, score: u8| async move {
+            let actions = RecoveryActions::new();
+            actions.register(rebuild);
+            actions.register(restart);
+            let mut healing = SelfHealingArchitecture::new().with_actions(actions);
+            healing.attempt_recovery(&BustCallError::new("api", "cache bust failed", score)).await
+        };
+
+        // Danger rebuilds; a failed rebuild escalates
+        let result = recover(Flaky::new("rebuild", 0), Flaky::new("restart", 0), 6).await;
+        assert!(matches!(result, RecoveryResult::Success { strategy_used: RecoveryStrategy::HardRecovery { .. }, .. }));
+        let result = recover(Arc::new(Fails { name: "rebuild", score: 6 }), Flaky::new("restart", 0), 6).await;
+        assert!(matches!(result, RecoveryResult::Failed { escalation_required: true, .. }));
+
+        // Panic restarts; backing off is retried rather than escalated
+        let result = recover(Flaky::new("rebuild", 0), Flaky::new("restart", 0), 12).await;
+        assert!(matches!(result, RecoveryResult::Success { strategy_used: RecoveryStrategy::EmergencyRecovery { .. }, .. }));
+        let result = recover(Flaky::new("rebuild", 0), Arc::new(Fails { name: "restart", score: 3 }), 12).await;
+        assert!(matches!(
+            result,
+            RecoveryResult::PartialRecovery { next_strategy: RecoveryStrategy::EmergencyRecovery { system_restart: true, .. }, .. }
+        ));
+        let result = recover(Flaky::new("rebuild", 0), Arc::new(Fails { name: "restart", score: 9 }), 12).await;
+        assert!(matches!(result, RecoveryResult::Failed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_open_the_circuit() {
+        let actions = RecoveryActions::new();
+        actions.register(Arc::new(Fails { name: "rebuild", score: 6 }));
+        let recovery = RecoveryConfig { breaker_failures: 2, ..RecoveryConfig::default() };
+        let mut healing = SelfHealingArchitecture::new().with_actions(actions).with_recovery(recovery);
+        let error = BustCallError::new("api", "cache bust failed", 6);
+
+        assert!(matches!(healing.attempt_recovery(&error).await, RecoveryResult::Failed { .. }));
+        assert!(!healing.is_circuit_open("api"));
+        assert!(matches!(healing.attempt_recovery(&error).await, RecoveryResult::Failed { .. }));
+        assert!(healing.is_circuit_open("api"));
+        assert_eq!(healing.system_health().critical_alerts.len(), 1);
+
+        // Open, recovery is left to manual intervention until reset
+        assert!(matches!(healing.attempt_recovery(&error).await, RecoveryResult::ManualIntervention { .. }));
+        assert_eq!(healing.circuits()[0].failures, 2);
+        assert!(healing.reset_circuit("api"));
+        assert!(healing.circuits().is_empty());
+        assert!(matches!(healing.attempt_recovery(&error).await, RecoveryResult::Failed { .. }));
+        assert!(!healing.reset_circuit("api"));
+    }
+
+    #[test]
+    fn test_health_checks_trigger_recovery() {
+        let mut healing = SelfHealingArchitecture::new()
+            .with_health_monitors(vec![HealthMonitor::new("api", Duration::from_secs(30), 6, 2)]);
+        let now = SystemTime::now();
+        assert_eq!(healing.due_monitors(now), ["api"]);
+        let check = |health_score: u8| HealthMetrics {
+            timestamp: 0,
+            component: "api".to_string(),
+            health_score,
+            memory_usage_mb: 0.0,
+            cpu_usage_percent: 0.0,
+            cache_hit_ratio: 1.0,
+            error_rate: 0.0,
+        };
+
+        // Below the threshold twice in a row, not just twice
+        assert!(healing.evaluate_health(check(4)).is_none());
+        assert!(healing.due_monitors(now).is_empty());
+        assert!(healing.evaluate_health(check(8)).is_none());
+        assert!(healing.evaluate_health(check(4)).is_none());
+        assert!(healing.system_health().performance_degradation);
+        let error = healing.evaluate_health(check(0)).unwrap();
+        assert_eq!((error.component.as_str(), error.severity), ("api", SeverityLevel::Panic));
+        assert_eq!(healing.system_health().component_health["api"], 0);
+        assert_eq!(healing.system_health().critical_alerts.len(), 1);
+        assert!(healing.evaluate_health(check(4)).is_none());
+        assert!(healing.evaluate_health(check(9)).is_none());
+        assert!(!healing.system_health().performance_degradation);
+
+        healing.record_error("api");
+        healing.record_error("api");
+        assert_eq!(healing.error_rate("api"), 0.4);
+        assert_eq!(healing.error_rate("web"), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_constitutional_compliance() {
+        let healing = SelfHealingArchitecture::new();
+        let error = BustCallError {
+            severity: SeverityLevel::Ok,
+            message: "Normal operation".to_string(),
+            component: "test_component".to_string(),
+            recovery_action: None,
+        };
+
+        let result = healing.validate_constitutional_compliance(&error).await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_daemon_errors_pick_strategies_by_severity() {
+        let healing = SelfHealingArchitecture::new();
+        let config = BustcallError::ConfigError("unknown key".to_string()).scored("node");
+        assert_eq!((config.severity, config.component.as_str()), (SeverityLevel::Warning, "node"));
+        assert!(matches!(
+            healing.determine_recovery_strategy(&config),
+            RecoveryStrategy::SoftRecovery { retry_count: 3, backoff_ms: 1000 }
+        ));
+
+        let bust = BustcallError::PidWatcherError("Cache bust failed".to_string()).scored("node");
+        assert_eq!(bust.severity, SeverityLevel::Danger);
+        assert!(matches!(healing.determine_recovery_strategy(&bust), RecoveryStrategy::HardRecovery { .. }));
+
+        let panicked = BustcallError::from(BustCallError::new("watcher", "task panicked", 10));
+        assert_eq!(panicked.severity(), SeverityLevel::Critical);
+        assert_eq!(panicked.scored("ignored").component, "watcher");
+        assert_eq!(SeverityLevel::from(40), SeverityLevel::Panic);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+// src/servers/mod.rs
+//! HTTP API served by the daemon, which the CLI's daemon-backed commands
+//! talk to
+
+pub mod server;
+
+pub use server::BustcallServer;
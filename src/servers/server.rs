@@ -2,13 +2,32 @@
 //! Constitutional REST API server implementing FaultTorrent execution model
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
+use futures::StreamExt;
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
+use sha2::Digest;
 use warp::{Filter, Reply};
 
+use crate::core::cgroup::{stats_for_pids, CgroupStats};
+use crate::core::config::{BustcallConfig, ConfigError, MonitoringConfig};
 use crate::core::daemon::Daemon;
-use crate::ffi::{BustcallDaemonHandle, bustcall_daemon_new, bustcall_daemon_start};
+use crate::core::notify::dispatch::{DispatchConfig, NotificationDispatcher};
+use crate::core::notify::{Notification, NotificationLevel, NotificationManager};
+use crate::core::priority::ResourceGovernor;
+use crate::core::probe::LivenessProber;
+use crate::core::process::{MetricSample, ProcessFilter, ProcessManager};
+use crate::core::rebuild::RebuildExecutor;
+use crate::core::schedule::BustScheduler;
+use crate::core::watch::ProcessWatch;
+use crate::dimensional_cache::{
+    qualify, BackgroundTask, BatchBustResult, BustReason, BustRecord, CacheBustSeverity, CacheMetrics, CacheSnapshot, CacheState,
+    DimensionalCacheManager, EvictionCandidate, EvictionStrategy, ReclaimStats, TargetCacheState, TargetUsage,
+};
+use crate::pid_watcher::{BustCallConfig, BustCallDaemon, WatchFeedEvent, WatcherStatusHandle};
 
 /// FaultTorrent execution stages
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +55,40 @@ pub struct BustRequest {
     pub strategy: Option<String>,
     pub binding: Option<String>,
     pub fault_tolerance: Option<u8>,
+    /// Defaults to medium
+    pub severity: Option<CacheBustSeverity>,
+    /// Namespace `target` is bound in, e.g. `project:branch`
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Defaults to a manual bust
+    #[serde(default)]
+    pub reason: Option<BustReason>,
+}
+
+/// Busts applied as one; see `DimensionalCacheManager::bust_many`
+#[derive(Debug, Deserialize)]
+pub struct BatchBustRequest {
+    pub busts: Vec<BatchBustItem>,
+    /// Namespace the targets are bound in, e.g. `project:branch`
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Shared by the batch; defaults to a manual bust
+    #[serde(default)]
+    pub reason: Option<BustReason>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchBustItem {
+    pub target: String,
+    /// Defaults to medium
+    #[serde(default)]
+    pub severity: Option<CacheBustSeverity>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchBustResponse {
+    pub status: String,
+    pub results: Vec<BatchBustResult>,
 }
 
 /// Cache bust response structure
@@ -46,6 +99,9 @@ pub struct BustResponse {
     pub delegate: String,
     pub fault_stage: u8,
     pub execution_time_ms: u64,
+    /// Freed on disk by the bust executors and compiler caches
+    pub reclaimed_bytes: u64,
+    pub reason: BustReason,
 }
 
 /// Daemon status response
@@ -55,6 +111,77 @@ pub struct StatusResponse {
     pub bindings: HashMap<String, BindingStatus>,
     pub cache_size: String,
     pub fault_history: Vec<FaultEvent>,
+    /// Latest sample of each monitored process
+    pub processes: HashMap<u32, MetricSample>,
+    /// Counters of the dimensional cache, when the server has one
+    pub cache: Option<CacheMetrics>,
+    /// State and hot path score of each bound target
+    pub targets: Vec<TargetCacheState>,
+}
+
+/// Metrics history query, `?window=<seconds>`
+#[derive(Debug, Deserialize)]
+pub struct MetricsQuery {
+    pub window: Option<u64>,
+}
+
+/// Cache eviction request; `dry_run` only plans it
+#[derive(Debug, Deserialize)]
+pub struct EvictRequest {
+    pub strategy: EvictionStrategy,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Entries evicted, or that would be, with their score breakdowns
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvictResponse {
+    pub dry_run: bool,
+    pub candidates: Vec<EvictionCandidate>,
+}
+
+/// Cache usage query, `?top=<entries per target>`
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    pub top: Option<usize>,
+}
+
+/// Namespace to drop, `?name=<project:branch>`
+#[derive(Debug, Deserialize)]
+pub struct NamespaceQuery {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricsResponse {
+    pub pid: u32,
+    pub window_seconds: u64,
+    pub samples: Vec<MetricSample>,
+}
+
+/// cgroup v2 stats of the processes named like a target
+#[derive(Debug, Serialize)]
+pub struct TargetMetricsResponse {
+    pub target: String,
+    pub cgroups: Vec<CgroupStats>,
+}
+
+/// Monitored targets with their processes and cache state, for `bustcall top`
+#[derive(Debug, Serialize)]
+pub struct TargetsResponse {
+    pub targets: Vec<TargetSummary>,
+    /// Oldest first
+    pub recent_busts: Vec<BustRecord>,
+    /// Space reclaimed by cache expiry so far
+    pub reclaimed: Option<ReclaimStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TargetSummary {
+    pub name: String,
+    pub cache_state: Option<CacheState>,
+    /// Latest sample per pid
+    pub processes: Vec<(u32, MetricSample)>,
 }
 
 #[derive(Debug, Serialize)]
@@ -73,9 +200,29 @@ pub struct FaultEvent {
 
 /// OBINexus Bustcall API Server
 pub struct BustcallServer {
-    daemon_handle: Option<BustcallDaemonHandle>,
+    /// Started with the server
+    daemon: Option<Daemon>,
     bindings: Arc<RwLock<HashMap<String, BindingMetadata>>>,
     fault_history: Arc<RwLock<Vec<FaultEvent>>>,
+    processes: Arc<ProcessManager>,
+    monitoring: MonitoringConfig,
+    cache: Option<Arc<DimensionalCacheManager>>,
+    /// Redacted tree of the config the daemon runs with
+    config: Option<Arc<toml::Table>>,
+    /// File watcher reported at `/api/v1/watchers`
+    watchers: Option<WatcherStatusHandle>,
+    /// Config to set the cache up with on start
+    loaded: Option<BustcallConfig>,
+    expiry_interval: std::time::Duration,
+    expiry: Option<BackgroundTask>,
+    subscriber: Option<BackgroundTask>,
+    rebuilds: Option<BackgroundTask>,
+}
+
+impl Default for BustcallServer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BustcallServer {
@@ -100,25 +247,155 @@ impl BustcallServer {
         });
 
         Self {
-            daemon_handle: None,
+            daemon: None,
             bindings: Arc::new(RwLock::new(bindings)),
             fault_history: Arc::new(RwLock::new(Vec::new())),
+            processes: Arc::new(ProcessManager::new()),
+            monitoring: crate::core::config::BustcallConfig::default().monitoring,
+            cache: None,
+            config: None,
+            watchers: None,
+            loaded: None,
+            expiry_interval: std::time::Duration::from_secs(60),
+            expiry: None,
+            subscriber: None,
+            rebuilds: None,
         }
     }
 
+    /// Sample the processes named in `[monitoring]` for the metrics API
+    pub fn with_monitoring(mut self, monitoring: MonitoringConfig) -> Self {
+        self.monitoring = monitoring;
+        self
+    }
+
+    /// Report cache state and recent busts of this cache in `/api/v1/targets`
+    pub fn with_cache(mut self, cache: Arc<DimensionalCacheManager>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Report the file watcher's targets at `/api/v1/watchers`, for
+    /// `bustcall status --verbose`
+    pub fn with_watchers(mut self, watchers: WatcherStatusHandle) -> Self {
+        self.watchers = Some(watchers);
+        self
+    }
+
+    /// The server as `bustcall-server` runs it: a cache, a file watcher of
+    /// the enabled targets and the daemon loop, all built from `config`. The
+    /// watcher's busts go through the daemon; it is returned started, for
+    /// the caller to hold and shut down.
+    pub async fn from_config(config: &BustcallConfig) -> Result<(Self, BustCallDaemon), Box<dyn std::error::Error>> {
+        let targets: HashMap<_, _> = config
+            .target
+            .iter()
+            .filter(|(_, target)| target.enabled)
+            .map(|(name, target)| (name.clone(), target.clone()))
+            .collect();
+        let mut watch = BustCallConfig::default().with_targets(&targets)?;
+        watch.watch_paths = watch
+            .targets
+            .iter()
+            .filter(|target| !target.templated && target.root.exists())
+            .map(|target| target.root.clone())
+            .collect();
+
+        let notifications = Arc::new(NotificationManager::from_config(&config.notify)?);
+        let mut watcher = BustCallDaemon::new(watch)?.with_notifications(notifications.clone());
+        let cache = Arc::new(watcher.cache_manager().clone());
+        if config.global.self_healing {
+            let (healing, probe) = (config.self_healing(cache.clone()), config.health_probe(cache.clone()));
+            watcher = watcher.with_self_healing(healing).with_health_probe(probe);
+        }
+
+        let schedules = config.target_schedules();
+        let runtimes = targets
+            .iter()
+            .filter_map(|(name, target)| target.runtime_watcher(name, &config.global))
+            .collect();
+        let dispatcher = NotificationDispatcher::spawn(notifications.clone(), DispatchConfig::default());
+        let mut daemon = Daemon::with_config(config.daemon.clone())?
+            .with_cache(cache.clone())
+            .with_notifications(notifications)
+            .with_dispatcher(Arc::new(dispatcher))
+            .with_scheduler(BustScheduler::from_targets(&schedules)?)
+            .with_process_manager(Arc::new(ProcessManager::new().with_restart_targets(&schedules)))
+            .with_liveness(LivenessProber::from_targets(&schedules))
+            .with_resource_governor(ResourceGovernor::from_targets(&schedules))
+            .with_runtime_watchers(runtimes)
+            .with_tick_interval(Duration::from_secs(config.global.daemon_interval_seconds.max(1)));
+        if !config.monitoring.watches.is_empty() {
+            daemon = daemon.with_process_watch(ProcessWatch::new(config.monitoring.watches.clone())?);
+        }
+
+        let mut watcher = watcher.with_dispatch(daemon.watcher_dispatch((*cache).clone()));
+        watcher.start().await?;
+        let server = Self::new()
+            .with_config(config)?
+            .with_cache(cache)
+            .with_watchers(watcher.status_handle())
+            .with_daemon(daemon);
+        Ok((server, watcher))
+    }
+
+    /// Run `daemon`'s loop alongside the API
+    pub fn with_daemon(mut self, daemon: Daemon) -> Self {
+        self.daemon = Some(daemon);
+        self
+    }
+
+    /// Serve `config` at `/api/v1/config` for `bustcall config diff --daemon`
+    pub fn with_config(mut self, config: &BustcallConfig) -> Result<Self, ConfigError> {
+        self.monitoring = config.monitoring.clone();
+        self.loaded = Some(config.clone());
+        self.expiry_interval = std::time::Duration::from_secs(config.cache.expiry_interval_seconds.max(1));
+        self.config = Some(Arc::new(config.redacted()?));
+        Ok(self)
+    }
+
     pub async fn start(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Initialize daemon
-        self.daemon_handle = Some(unsafe { bustcall_daemon_new() });
-        
-        if let Some(handle) = self.daemon_handle {
-            unsafe {
-                bustcall_daemon_start(handle);
+        let (_, serving) = self.bind(SocketAddr::from(([127, 0, 0, 1], 8989)))?;
+        println!("🌀 OBINexus Bustcall API Server starting on port 8989");
+        println!("Constitutional compliance: FaultTorrent enabled");
+        serving.await;
+
+        Ok(())
+    }
+
+    /// Start the daemon and background tasks and bind the API to `address`,
+    /// port 0 for any free one. The API is served while the returned future
+    /// is polled.
+    pub fn bind(
+        &mut self,
+        address: SocketAddr,
+    ) -> Result<(SocketAddr, impl Future<Output = ()>), Box<dyn std::error::Error>> {
+        if let Some(daemon) = &mut self.daemon {
+            daemon.start()?;
+        }
+
+        self.spawn_sampler();
+        if let Some(cache) = &self.cache {
+            if let Some(config) = &self.loaded {
+                config.configure_cache(cache)?;
+                config.warm_cache(cache)?;
+            }
+            self.expiry = Some(DimensionalCacheManager::spawn_expiry(cache.clone(), self.expiry_interval));
+            self.subscriber = DimensionalCacheManager::spawn_subscriber(cache.clone());
+            if let Some(config) = &self.loaded {
+                let mut executor = RebuildExecutor::new(config.rebuild_commands(), config.cache.rebuild_concurrency);
+                match NotificationManager::from_config(&config.notify) {
+                    Ok(notifications) => executor = executor.with_notifications(Arc::new(notifications)),
+                    Err(e) => log::warn!("Rebuild notifications disabled: {}", e),
+                }
+                self.rebuilds = Some(executor.spawn(cache.clone()));
             }
         }
 
         // Start web server
         let bindings = self.bindings.clone();
         let fault_history = self.fault_history.clone();
+        let processes = self.processes.clone();
 
         // API Routes
         let bust_route = warp::path!("api" / "v1" / "bust")
@@ -126,32 +403,160 @@ impl BustcallServer {
             .and(warp::body::json())
             .and(with_state(bindings.clone()))
             .and(with_state(fault_history.clone()))
+            .and(with_state(self.cache.clone()))
             .and_then(handle_bust);
 
+        let batch_bust_route = warp::path!("api" / "v1" / "bust" / "batch")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_batch_bust);
+
         let status_route = warp::path!("api" / "v1" / "status")
             .and(warp::get())
             .and(with_state(bindings.clone()))
             .and(with_state(fault_history.clone()))
+            .and(with_state(processes.clone()))
+            .and(with_state(self.cache.clone()))
             .and_then(handle_status);
 
+        let prometheus_route = warp::path!("metrics")
+            .and(warp::get())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_prometheus);
+
+        let metrics_route = warp::path!("api" / "v1" / "processes" / u32 / "metrics")
+            .and(warp::get())
+            .and(warp::query::<MetricsQuery>())
+            .and(with_state(processes.clone()))
+            .and_then(handle_metrics);
+
+        let targets_route = warp::path!("api" / "v1" / "targets")
+            .and(warp::get())
+            .and(with_state(Arc::new(self.monitoring.processes.clone())))
+            .and(with_state(processes.clone()))
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_targets);
+
+        let target_metrics_route = warp::path!("api" / "v1" / "targets" / String / "metrics")
+            .and(warp::get())
+            .and(with_state(processes.clone()))
+            .and_then(handle_target_metrics);
+
+        let snapshot_route = warp::path!("api" / "v1" / "cache" / "snapshot")
+            .and(warp::get())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_snapshot);
+
+        let restore_route = warp::path!("api" / "v1" / "cache" / "snapshot")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_restore);
+
+        let usage_route = warp::path!("api" / "v1" / "cache" / "usage")
+            .and(warp::get())
+            .and(warp::query::<UsageQuery>())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_usage);
+
+        let evict_route = warp::path!("api" / "v1" / "cache" / "evict")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_evict);
+
+        let drop_namespace_route = warp::path!("api" / "v1" / "cache" / "namespace")
+            .and(warp::delete())
+            .and(warp::query::<NamespaceQuery>())
+            .and(with_state(self.cache.clone()))
+            .and_then(handle_drop_namespace);
+
+        let config_route = warp::path!("api" / "v1" / "config")
+            .and(warp::get())
+            .and(with_state(self.config.clone()))
+            .and_then(handle_config);
+
+        let watchers_route = warp::path!("api" / "v1" / "watchers")
+            .and(warp::get())
+            .and(with_state(self.watchers.clone()))
+            .and_then(handle_watchers);
+
+        let watch_events_route = warp::path!("api" / "v1" / "watch" / "events")
+            .and(warp::get())
+            .and(with_state(self.watchers.clone()))
+            .and_then(handle_watch_events);
+
+        let circuits_route = warp::path!("api" / "v1" / "heal" / "circuits")
+            .and(warp::get())
+            .and(with_state(self.watchers.clone()))
+            .and_then(handle_circuits);
+
+        let reset_circuit_route = warp::path!("api" / "v1" / "heal" / "circuits" / String / "reset")
+            .and(warp::post())
+            .and(with_state(self.watchers.clone()))
+            .and_then(handle_reset_circuit);
+
         let capabilities_route = warp::path!("api" / "v1" / "bindings" / "capabilities")
             .and(warp::get())
             .and(with_state(bindings.clone()))
             .and_then(handle_capabilities);
 
+        let notify_route = warp::path!("api" / "v1" / "notify")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(handle_notify);
+
+        let ack_route = warp::path!("api" / "v1" / "notifications" / u64 / "ack")
+            .and(warp::post())
+            .and_then(handle_ack);
+
         let routes = bust_route
+            .or(batch_bust_route)
             .or(status_route)
             .or(capabilities_route)
+            .or(notify_route)
+            .or(ack_route)
+            .or(metrics_route)
+            .or(targets_route)
+            .or(target_metrics_route)
+            .or(config_route)
+            .or(watchers_route)
+            .or(watch_events_route)
+            .or(circuits_route)
+            .or(reset_circuit_route)
+            .or(prometheus_route)
+            .or(snapshot_route)
+            .or(restore_route)
+            .or(drop_namespace_route)
+            .or(evict_route)
+            .or(usage_route)
             .with(warp::cors().allow_any_origin());
 
-        println!("🌀 OBINexus Bustcall API Server starting on port 8989");
-        println!("Constitutional compliance: FaultTorrent enabled");
-        
-        warp::serve(routes)
-            .run(([127, 0, 0, 1], 8989))
-            .await;
+        Ok(warp::serve(routes).try_bind_ephemeral(address)?)
+    }
 
-        Ok(())
+    /// Periodically list monitored processes so their history fills up
+    fn spawn_sampler(&self) {
+        if self.monitoring.processes.is_empty() {
+            return;
+        }
+
+        let processes = self.processes.clone();
+        let names = self.monitoring.processes.clone();
+        let period = std::time::Duration::from_secs(self.monitoring.interval_seconds.max(1));
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                for name in &names {
+                    if let Err(e) = processes.list_processes(&ProcessFilter::all().name(name)) {
+                        log::warn!("Process sampling failed for {}: {}", name, e);
+                    }
+                }
+            }
+        });
     }
 }
 
@@ -165,8 +570,9 @@ fn with_state<T: Clone + Send>(
 /// Handle cache bust requests
 async fn handle_bust(
     request: BustRequest,
-    bindings: Arc<RwLock<HashMap<String, BindingMetadata>>>,
+    _bindings: Arc<RwLock<HashMap<String, BindingMetadata>>>,
     fault_history: Arc<RwLock<Vec<FaultEvent>>>,
+    cache: Option<Arc<DimensionalCacheManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
     let start_time = std::time::Instant::now();
     
@@ -176,7 +582,28 @@ async fn handle_bust(
         None => "pybustcall".to_string(), // Default to Python binding
     };
 
-    // Simulate cache bust operation
+    let reason = request.reason.clone().unwrap_or(BustReason::Manual { user: None });
+    let mut reclaimed_bytes = 0;
+    let status = match &cache {
+        Some(cache) => {
+            let severity = request.severity.clone().unwrap_or(CacheBustSeverity::Medium);
+            let target = match &request.namespace {
+                Some(namespace) => qualify(namespace, &request.target),
+                None => request.target.clone(),
+            };
+            match cache.bust_cache_async(&target, severity, reason.clone()).await {
+                Ok(reclaimed) => {
+                    reclaimed_bytes = reclaimed;
+                    "success".to_string()
+                }
+                Err(e) => {
+                    log::warn!("Cache bust of {} failed: {}", request.target, e);
+                    format!("error: {}", e)
+                }
+            }
+        }
+        None => "success".to_string(),
+    };
     let cache_key = format!("sha256:{}", hex::encode(sha2::Sha256::digest(request.target.as_bytes())));
     
     // Check fault tolerance threshold
@@ -196,11 +623,13 @@ async fn handle_bust(
     let execution_time = start_time.elapsed().as_millis() as u64;
 
     let response = BustResponse {
-        status: "success".to_string(),
+        status,
         cache_key,
         delegate: selected_binding,
         fault_stage,
         execution_time_ms: execution_time,
+        reclaimed_bytes,
+        reason,
     };
 
     Ok(warp::reply::json(&response))
@@ -210,6 +639,8 @@ async fn handle_bust(
 async fn handle_status(
     bindings: Arc<RwLock<HashMap<String, BindingMetadata>>>,
     fault_history: Arc<RwLock<Vec<FaultEvent>>>,
+    processes: Arc<ProcessManager>,
+    cache: Option<Arc<DimensionalCacheManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
     let bindings_map = bindings.read().await;
     let history = fault_history.read().await;
@@ -222,16 +653,235 @@ async fn handle_status(
         });
     }
 
+    let targets = cache.as_ref().map(|cache| cache.target_states()).unwrap_or_default();
+    let cache = cache.map(|cache| cache.metrics());
     let response = StatusResponse {
         daemon_pid: std::process::id(),
         bindings: binding_statuses,
-        cache_size: "1.2MB".to_string(),
+        cache_size: format!("{}B", cache.as_ref().map_or(0, |metrics| metrics.bytes)),
         fault_history: history.clone(),
+        processes: processes
+            .monitored_pids()
+            .into_iter()
+            .filter_map(|pid| {
+                let latest = processes.metrics_history(pid, std::time::Duration::MAX).pop()?;
+                Some((pid, latest))
+            })
+            .collect(),
+        cache,
+        targets,
     };
 
     Ok(warp::reply::json(&response))
 }
 
+/// Handle Prometheus scrapes
+async fn handle_prometheus(cache: Option<Arc<DimensionalCacheManager>>) -> Result<impl Reply, warp::Rejection> {
+    let body = cache.map(|cache| cache.metrics().to_prometheus()).unwrap_or_default();
+    Ok(warp::reply::with_header(body, "Content-Type", "text/plain; version=0.0.4"))
+}
+
+/// Export the cache state for `bustcall cache export`
+async fn handle_snapshot(cache: Option<Arc<DimensionalCacheManager>>) -> Result<impl Reply, warp::Rejection> {
+    match cache {
+        Some(cache) => Ok(warp::reply::json(&cache.snapshot())),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+/// Replace the cache state for `bustcall cache import`
+async fn handle_restore(
+    snapshot: CacheSnapshot,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let Some(cache) = cache else {
+        return Err(warp::reject::not_found());
+    };
+    let entries = snapshot.evicons.len();
+    let reply = match cache.restore(snapshot) {
+        Ok(()) => warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({ "status": "restored", "entries": entries })),
+            warp::http::StatusCode::OK,
+        ),
+        Err(e) => warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({ "status": "error", "message": e.to_string() })),
+            warp::http::StatusCode::BAD_REQUEST,
+        ),
+    };
+    Ok(reply)
+}
+
+async fn handle_batch_bust(
+    request: BatchBustRequest,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let Some(cache) = cache else {
+        return Err(warp::reject::not_found());
+    };
+    let busts = request
+        .busts
+        .into_iter()
+        .map(|bust| {
+            let target = match &request.namespace {
+                Some(namespace) => qualify(namespace, &bust.target),
+                None => bust.target,
+            };
+            (target, bust.severity.unwrap_or(CacheBustSeverity::Medium))
+        })
+        .collect();
+    let reason = request.reason.unwrap_or(BustReason::Manual { user: None });
+    let results = match cache.bust_many_async(busts, reason.clone()).await {
+        Ok(results) => results,
+        Err(e) => {
+            log::warn!("Batch cache bust failed: {}", e);
+            return Ok(warp::reply::json(&BatchBustResponse {
+                status: format!("error: {}", e),
+                results: Vec::new(),
+            }));
+        }
+    };
+
+    // One notification for the whole batch
+    let failed = results.iter().filter(|result| result.error.is_some()).count();
+    let summary: Vec<String> = results
+        .iter()
+        .map(|result| match &result.error {
+            Some(error) => format!("{} ({}, failed: {})", result.target, result.severity.label(), error),
+            None => format!("{} ({})", result.target, result.severity.label()),
+        })
+        .collect();
+    let level = if failed == 0 { NotificationLevel::Info } else { NotificationLevel::Warning };
+    let notification = Notification::new(level, &format!("Busted {} targets: {}", results.len() - failed, summary.join(", ")))
+        .title("Batch cache bust")
+        .component("cache")
+        .with_metadata("reason", &reason.to_string())
+        .with_metadata("reason_kind", reason.kind());
+    if let Err(e) = NotificationManager::new().send_structured(&notification) {
+        log::warn!("Batch bust notification failed: {}", e);
+    }
+
+    Ok(warp::reply::json(&BatchBustResponse {
+        status: if failed == 0 { "success".to_string() } else { format!("{} of {} busts failed", failed, results.len()) },
+        results,
+    }))
+}
+
+async fn handle_usage(
+    query: UsageQuery,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    match cache {
+        Some(cache) => {
+            let usage: Vec<TargetUsage> = cache.disk_usage(query.top.unwrap_or(5));
+            Ok(warp::reply::json(&usage))
+        }
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+async fn handle_evict(
+    request: EvictRequest,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let Some(cache) = cache else {
+        return Err(warp::reject::not_found());
+    };
+    let candidates = cache.cache_evict_plan(&request.strategy);
+    if !request.dry_run {
+        if let Err(e) = cache.cache_evict_async(request.strategy).await {
+            log::warn!("Cache eviction failed: {}", e);
+        }
+    }
+    Ok(warp::reply::json(&EvictResponse {
+        dry_run: request.dry_run,
+        candidates,
+    }))
+}
+
+async fn handle_drop_namespace(
+    query: NamespaceQuery,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let Some(cache) = cache else {
+        return Err(warp::reject::not_found());
+    };
+    let reply = match cache.namespace(&query.name) {
+        Ok(namespace) => warp::reply::with_status(
+            warp::reply::json(&namespace.drop_all()),
+            warp::http::StatusCode::OK,
+        ),
+        Err(e) => warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({ "status": "error", "message": e.to_string() })),
+            warp::http::StatusCode::BAD_REQUEST,
+        ),
+    };
+    Ok(reply)
+}
+
+/// Handle process metrics history requests
+async fn handle_metrics(
+    pid: u32,
+    query: MetricsQuery,
+    processes: Arc<ProcessManager>,
+) -> Result<impl Reply, warp::Rejection> {
+    let window_seconds = query.window.unwrap_or(3600);
+    let samples = processes.metrics_history(pid, std::time::Duration::from_secs(window_seconds));
+
+    Ok(warp::reply::json(&MetricsResponse {
+        pid,
+        window_seconds,
+        samples,
+    }))
+}
+
+/// Handle monitored target overview requests
+async fn handle_targets(
+    names: Arc<Vec<String>>,
+    processes: Arc<ProcessManager>,
+    cache: Option<Arc<DimensionalCacheManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let targets = names
+        .iter()
+        .map(|name| {
+            let samples = processes
+                .list_processes(&ProcessFilter::all().name(name))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|process| {
+                    let latest = processes.metrics_history(process.pid, std::time::Duration::MAX).pop()?;
+                    Some((process.pid, latest))
+                })
+                .collect();
+            TargetSummary {
+                name: name.clone(),
+                cache_state: cache.as_ref().and_then(|cache| cache.cache_state(name)),
+                processes: samples,
+            }
+        })
+        .collect();
+
+    Ok(warp::reply::json(&TargetsResponse {
+        targets,
+        recent_busts: cache.as_ref().map(|cache| cache.recent_busts()).unwrap_or_default(),
+        reclaimed: cache.map(|cache| cache.reclaim_stats()),
+    }))
+}
+
+/// Handle target cgroup metrics requests
+async fn handle_target_metrics(target: String, processes: Arc<ProcessManager>) -> Result<impl Reply, warp::Rejection> {
+    let pids: Vec<u32> = processes
+        .list_processes(&ProcessFilter::all().name(&target))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|process| process.pid)
+        .collect();
+
+    Ok(warp::reply::json(&TargetMetricsResponse {
+        target,
+        cgroups: stats_for_pids(pids),
+    }))
+}
+
 /// Handle capabilities requests
 async fn handle_capabilities(
     bindings: Arc<RwLock<HashMap<String, BindingMetadata>>>,
@@ -240,12 +890,121 @@ async fn handle_capabilities(
     Ok(warp::reply::json(&*bindings_map))
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    env_logger::init();
-    
-    let mut server = BustcallServer::new();
-    server.start().await?;
-    
-    Ok(())
+/// Handle loaded config requests
+async fn handle_config(config: Option<Arc<toml::Table>>) -> Result<impl Reply, warp::Rejection> {
+    match config {
+        Some(config) => Ok(warp::reply::json(&*config)),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+async fn handle_watchers(watchers: Option<WatcherStatusHandle>) -> Result<impl Reply, warp::Rejection> {
+    match watchers {
+        Some(watchers) => Ok(warp::reply::json(&watchers.watchers())),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+/// Recovery circuit breakers, for `bustcall heal status`
+async fn handle_circuits(watchers: Option<WatcherStatusHandle>) -> Result<impl Reply, warp::Rejection> {
+    let Some(watchers) = watchers else {
+        return Err(warp::reject::not_found());
+    };
+    match watchers.circuits().await {
+        Some(circuits) => Ok(warp::reply::json(&circuits)),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+/// Close a component's recovery circuit, for `bustcall heal reset`
+async fn handle_reset_circuit(
+    component: String,
+    watchers: Option<WatcherStatusHandle>,
+) -> Result<impl Reply, warp::Rejection> {
+    let Some(watchers) = watchers else {
+        return Err(warp::reject::not_found());
+    };
+    // The component arrives as sent, percent-encoded to fit one path segment
+    let component = percent_encoding::percent_decode_str(&component).decode_utf8_lossy().into_owned();
+    match watchers.reset_circuit(&component).await {
+        Some(was_open) => Ok(warp::reply::json(&serde_json::json!({ "component": component, "was_open": was_open }))),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
+/// Stream the file watcher's events and bust decisions as server-sent
+/// events, one JSON [`WatchFeedEvent`] each, for `bustcall watch`
+async fn handle_watch_events(watchers: Option<WatcherStatusHandle>) -> Result<impl Reply, warp::Rejection> {
+    let Some(watchers) = watchers else {
+        return Err(warp::reject::not_found());
+    };
+    let events = futures::stream::unfold(watchers.subscribe(), |mut feed| async move {
+        loop {
+            match feed.recv().await {
+                Ok(event) => return Some((event, feed)),
+                // A slow client misses events rather than holding the watcher up
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                    log::warn!("Watch event stream fell behind, {} events dropped", missed);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .map(|event: WatchFeedEvent| warp::sse::Event::default().json_data(&event));
+    Ok(warp::sse::reply(warp::sse::keep_alive().stream(events)))
+}
+
+/// Handle structured notification requests
+async fn handle_notify(notification: Notification) -> Result<impl Reply, warp::Rejection> {
+    let manager = NotificationManager::new();
+    let reply = match manager.send_structured(&notification) {
+        Ok(result) => serde_json::to_value(&result).unwrap_or_default(),
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    };
+
+    Ok(warp::reply::json(&reply))
+}
+
+/// Handle notification acknowledgement requests
+async fn handle_ack(id: u64) -> Result<impl Reply, warp::Rejection> {
+    use crate::core::notify::escalation::{write_ack, DEFAULT_ACK_PATH};
+
+    let acknowledged = write_ack(DEFAULT_ACK_PATH, id).is_ok();
+    Ok(warp::reply::json(&serde_json::json!({
+        "id": id,
+        "acknowledged": acknowledged,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    async fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_server_from_config_serves_its_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BustcallConfig::from_toml(&format!("[target.web]\npath = {:?}\n", dir.path())).unwrap();
+        let (mut server, mut watcher) = BustcallServer::from_config(&config).await.unwrap();
+        let (address, serving) = server.bind(SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
+        tokio::spawn(serving);
+
+        let response = get(address, "/api/v1/cache/snapshot").await;
+        watcher.shutdown().await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        // The configured target is bound in the cache the server reports
+        assert!(response.contains(r#""web""#), "{}", response);
+        assert!(watcher.watchers().iter().any(|status| status.target == "web"));
+    }
 }
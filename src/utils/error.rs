@@ -1,22 +1,118 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, thiserror::Error)]
 pub enum BustcallError {
     #[error("Daemon error: {0}")]
     DaemonError(String),
-    
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
-    
+
     #[error("Process error: {0}")]
     ProcessError(String),
-    
+
     #[error("Notification error: {0}")]
     NotificationError(String),
-    
+
+    #[error("PID watcher error: {0}")]
+    PidWatcherError(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] toml::de::Error),
+
+    #[error(transparent)]
+    Scored(#[from] BustCallError),
+}
+
+impl BustcallError {
+    /// Where the error sits on the 0-12 severity scale
+    pub fn severity(&self) -> SeverityLevel {
+        match self {
+            BustcallError::ConfigError(_)
+            | BustcallError::NotificationError(_)
+            | BustcallError::Serialization(_) => SeverityLevel::Warning,
+            BustcallError::DaemonError(_)
+            | BustcallError::ProcessError(_)
+            | BustcallError::PidWatcherError(_)
+            | BustcallError::Io(_) => SeverityLevel::Danger,
+            BustcallError::Scored(error) => error.severity,
+        }
+    }
+
+    /// The error as raised by `component`, for
+    /// [`crate::self_healing::SelfHealingArchitecture::attempt_recovery`]
+    pub fn scored(&self, component: &str) -> BustCallError {
+        match self {
+            BustcallError::Scored(error) => error.clone(),
+            other => BustCallError::new(component, &other.to_string(), other.severity() as u8),
+        }
+    }
+}
+
+/// Bands of the 0-12 severity scale, each named by its lower bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeverityLevel {
+    Ok = 0,       // 0-2: Operating normally
+    Warning = 3,  // 3-5: Degraded, recovers on its own
+    Danger = 6,   // 6-8: Needs a rebuild of the component
+    Critical = 9, // 9-11: Needs the supervisor
+    Panic = 12,   // 12: Needs a restart
+}
+
+impl SeverityLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeverityLevel::Ok => "ok",
+            SeverityLevel::Warning => "warning",
+            SeverityLevel::Danger => "danger",
+            SeverityLevel::Critical => "critical",
+            SeverityLevel::Panic => "panic",
+        }
+    }
+}
+
+impl From<u8> for SeverityLevel {
+    fn from(score: u8) -> Self {
+        match score {
+            0..=2 => SeverityLevel::Ok,
+            3..=5 => SeverityLevel::Warning,
+            6..=8 => SeverityLevel::Danger,
+            9..=11 => SeverityLevel::Critical,
+            _ => SeverityLevel::Panic,
+        }
+    }
+}
+
+/// An error scored by severity, naming the component it came from and
+/// what would recover it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("{component}: {message}")]
+pub struct BustCallError {
+    pub severity: SeverityLevel,
+    pub message: String,
+    pub component: String,
+    pub recovery_action: Option<String>,
+}
+
+impl BustCallError {
+    /// Score on the 0-12 scale, clamped
+    pub fn new(component: &str, message: &str, score: u8) -> Self {
+        Self {
+            severity: SeverityLevel::from(score),
+            message: message.to_string(),
+            component: component.to_string(),
+            recovery_action: None,
+        }
+    }
+
+    pub fn with_recovery_action(mut self, action: &str) -> Self {
+        self.recovery_action = Some(action.to_string());
+        self
+    }
 }
 
 pub type Result<T> = std::result::Result<T, BustcallError>;